@@ -0,0 +1,65 @@
+package chunker
+
+import (
+	"bytes"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// binarySampleSize is how much of the start of a file is inspected for
+// binary or minified content
+const binarySampleSize = 64 * 1024
+
+// Minified code is recognized by lines far longer than anyone writes by hand
+const (
+	minifiedLineLength    = 5000 // Any line this long
+	minifiedAverageLength = 300  // Or lines this long on average
+)
+
+// minifiedFileSuffixes are the names bundlers and minifiers give their output
+var minifiedFileSuffixes = []string{".min.js", ".min.mjs", ".min.css", ".bundle.js", "-bundle.js", ".chunk.js"}
+
+// isBinaryOrMinified reports whether a file is binary, or code that was
+// minified or bundled, neither of which is worth embedding. Documentation
+// and notebooks may have long lines, so only code is checked for
+// minification.
+func isBinaryOrMinified(filePath, language string) (bool, error) {
+	base := strings.ToLower(filepath.Base(filePath))
+	for _, suffix := range minifiedFileSuffixes {
+		if strings.HasSuffix(base, suffix) {
+			return true, nil
+		}
+	}
+
+	file, err := os.Open(filePath)
+	if err != nil {
+		return false, fmt.Errorf("failed to read file: %w", err)
+	}
+	defer file.Close()
+
+	sample := make([]byte, binarySampleSize)
+	n, err := io.ReadFull(file, sample)
+	if err != nil && err != io.ErrUnexpectedEOF && err != io.EOF {
+		return false, fmt.Errorf("failed to read file: %w", err)
+	}
+	sample = sample[:n]
+
+	if bytes.IndexByte(sample, 0) >= 0 {
+		return true, nil
+	}
+	switch language {
+	case "markdown", "text", "rst", "notebook":
+		return false, nil
+	}
+
+	lines := bytes.Split(sample, []byte("\n"))
+	for _, line := range lines {
+		if len(line) >= minifiedLineLength {
+			return true, nil
+		}
+	}
+	return len(sample)/len(lines) >= minifiedAverageLength, nil
+}
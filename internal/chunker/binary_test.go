@@ -0,0 +1,43 @@
+package chunker
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestIsBinaryOrMinified(t *testing.T) {
+	minified := "!function(e){" + strings.Repeat("var a=e.b,c=e.d;", 400) + "}(window);\n"
+	paragraph := strings.Repeat("A long paragraph written on one line. ", 200) + "\n"
+
+	tests := []struct {
+		name     string
+		language string
+		source   string
+		expected bool
+	}{
+		{"app.js", "javascript", "function main() {\n  return 1;\n}\n", false},
+		{"vendor.min.js", "javascript", "function main() {}\n", true},
+		{"bundle.js", "javascript", minified, true},
+		{"data.py", "python", "x = 1\n\x00\x01\x02", true},
+		{"README.md", "markdown", paragraph, false},
+		{"notes.txt", "text", "binary\x00data", true},
+	}
+	for _, tt := range tests {
+		skip, err := isBinaryOrMinified(writeSource(t, tt.name, tt.source), tt.language)
+		require.NoError(t, err)
+		assert.Equal(t, tt.expected, skip, tt.name)
+	}
+}
+
+func TestChunkFileSkipsMinified(t *testing.T) {
+	path := writeSource(t, "bundle.js", "!function(e){"+strings.Repeat("var a=e.b,c=e.d;", 400)+"}(window);\n")
+
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(path, "javascript")
+	require.NoError(t, err)
+	assert.Empty(t, chunks)
+}
@@ -0,0 +1,221 @@
+package chunker
+
+import (
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+// commentStyle describes how a language writes comments, so line-based
+// chunkers can find block boundaries and doc comments without a grammar
+type commentStyle struct {
+	line  []string // Line comment markers, e.g. "//" or "#"
+	block bool     // Whether /* ... */ block comments are used
+}
+
+// cStyle covers C-family languages (C#, Kotlin, Swift, TypeScript, ...)
+var cStyle = commentStyle{line: []string{"//"}, block: true}
+
+// scanBraces returns the brace depth change for a line, ignoring braces inside
+// string literals and comments. inBlock carries block comment state across lines.
+func (s commentStyle) scanBraces(line string, inBlock *bool) (delta int, opened bool) {
+	var quote byte
+	for i := 0; i < len(line); i++ {
+		c := line[i]
+		if *inBlock {
+			if c == '*' && i+1 < len(line) && line[i+1] == '/' {
+				*inBlock = false
+				i++
+			}
+			continue
+		}
+		if quote != 0 {
+			if c == '\\' {
+				i++
+			} else if c == quote {
+				quote = 0
+			}
+			continue
+		}
+		if s.startsLineComment(line[i:]) {
+			break
+		}
+		switch c {
+		case '"', '\'', '`':
+			quote = c
+		case '/':
+			if s.block && i+1 < len(line) && line[i+1] == '*' {
+				*inBlock = true
+				i++
+			}
+		case '{':
+			delta++
+			opened = true
+		case '}':
+			delta--
+		}
+	}
+	return delta, opened
+}
+
+// startsLineComment reports whether text begins with a line comment marker
+func (s commentStyle) startsLineComment(text string) bool {
+	for _, marker := range s.line {
+		if strings.HasPrefix(text, marker) {
+			return true
+		}
+	}
+	return false
+}
+
+// blockEnd returns the index of the line that closes the brace block opened on
+// or after lines[start]. Declarations without a body end at a trailing ';' or
+// just before the next blank line.
+func (s commentStyle) blockEnd(lines []string, start int) int {
+	depth := 0
+	opened := false
+	inBlock := false
+	for i := start; i < len(lines); i++ {
+		trimmed := strings.TrimSpace(lines[i])
+		if !opened && i > start && trimmed == "" {
+			return i - 1
+		}
+
+		delta, sawOpen := s.scanBraces(lines[i], &inBlock)
+		depth += delta
+		opened = opened || sawOpen
+
+		if opened && depth <= 0 {
+			return i
+		}
+		if !opened && strings.HasSuffix(trimmed, ";") {
+			return i
+		}
+	}
+	return len(lines) - 1
+}
+
+// declarationEnd returns the last line of a declaration in a language without
+// statement terminators (Kotlin, Swift). Braced bodies end at the matching
+// brace; bodiless and expression-bodied declarations (fun f() = 1) end at the
+// last line indented deeper than the declaration.
+func (s commentStyle) declarationEnd(lines []string, start int) int {
+	line := lines[start]
+	inBlock := false
+	if _, opened := s.scanBraces(line, &inBlock); opened {
+		return s.blockEnd(lines, start)
+	}
+
+	// A parameter list continuing onto later lines ends before the body opens
+	if strings.Count(line, "(") > strings.Count(line, ")") {
+		return s.blockEnd(lines, start)
+	}
+
+	if !strings.Contains(line[strings.LastIndex(line, ")")+1:], "=") {
+		for next := start + 1; next < len(lines); next++ {
+			trimmed := strings.TrimSpace(lines[next])
+			if trimmed == "" {
+				continue
+			}
+			if strings.HasPrefix(trimmed, "{") {
+				return s.blockEnd(lines, start)
+			}
+			break
+		}
+	}
+
+	return indentedEnd(lines, start)
+}
+
+// indentedEnd returns the last line after lines[start] that is indented deeper
+// than it, skipping blank lines within the block
+func indentedEnd(lines []string, start int) int {
+	indent := indentWidth(lines[start])
+	end := start
+	for i := start + 1; i < len(lines); i++ {
+		if strings.TrimSpace(lines[i]) == "" {
+			continue
+		}
+		if indentWidth(lines[i]) <= indent {
+			break
+		}
+		end = i
+	}
+	return end
+}
+
+// indentWidth returns the number of leading whitespace characters in a line
+func indentWidth(line string) int {
+	return len(line) - len(strings.TrimLeft(line, " \t"))
+}
+
+// extendAbove returns the first line of the run of lines directly above decl
+// that match, such as annotations or attributes stacked on a declaration
+func extendAbove(lines []string, decl int, match func(trimmed string) bool) int {
+	start := decl
+	for start > 0 && match(strings.TrimSpace(lines[start-1])) {
+		start--
+	}
+	return start
+}
+
+// leadingComment returns the comment directly above lines[start] with comment
+// markers stripped. A blank line or any non-comment line ends the comment.
+func (s commentStyle) leadingComment(lines []string, start int) string {
+	var collected []string
+	for i := start - 1; i >= 0; i-- {
+		trimmed := strings.TrimSpace(lines[i])
+		if trimmed == "" || !s.isCommentLine(trimmed) {
+			break
+		}
+		collected = append(collected, s.stripMarkers(trimmed))
+	}
+
+	// Lines were collected bottom-up
+	for l, r := 0, len(collected)-1; l < r; l, r = l+1, r-1 {
+		collected[l], collected[r] = collected[r], collected[l]
+	}
+	return strings.TrimSpace(strings.Join(collected, "\n"))
+}
+
+// isCommentLine reports whether a trimmed line is part of a comment
+func (s commentStyle) isCommentLine(trimmed string) bool {
+	if s.startsLineComment(trimmed) {
+		return true
+	}
+	return s.block && (strings.HasPrefix(trimmed, "/*") || strings.HasPrefix(trimmed, "*"))
+}
+
+// stripMarkers removes comment markers from a trimmed comment line
+func (s commentStyle) stripMarkers(trimmed string) string {
+	if s.block {
+		trimmed = strings.TrimPrefix(trimmed, "/**")
+		trimmed = strings.TrimPrefix(trimmed, "/*")
+		trimmed = strings.TrimSuffix(trimmed, "*/")
+		trimmed = strings.TrimPrefix(trimmed, "*")
+	}
+	for _, marker := range s.line {
+		if strings.HasPrefix(trimmed, marker) {
+			// Also strips repeated markers such as "///" or "##"
+			trimmed = strings.TrimLeft(trimmed, marker)
+			break
+		}
+	}
+	return strings.TrimSpace(trimmed)
+}
+
+// newLineChunk creates a code chunk covering lines[start:end+1] (0-indexed, inclusive)
+func newLineChunk(filePath, language string, lines []string, start, end int, chunkType, name string) Chunk {
+	return Chunk{
+		ID:            uuid.New().String(),
+		FilePath:      filePath,
+		LineStart:     start + 1,
+		LineEnd:       end + 1,
+		Language:      language,
+		Code:          strings.Join(lines[start:end+1], "\n"),
+		ChunkType:     chunkType,
+		Name:          name,
+		Metadata:      make(map[string]string),
+		EmbeddingType: "code",
+	}
+}
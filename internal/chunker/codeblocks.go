@@ -0,0 +1,115 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches the opening fence of a code block with a language tag: ```go, ~~~ python
+	fenceTagRegex = regexp.MustCompile("^\\s{0,3}(```+|~~~+)\\s*([\\w#+.-]+)")
+
+	// fenceLanguages maps code block language tags to chunker language names
+	fenceLanguages = map[string]string{
+		"go":         "go",
+		"golang":     "go",
+		"python":     "python",
+		"py":         "python",
+		"javascript": "javascript",
+		"js":         "javascript",
+		"jsx":        "javascript",
+		"typescript": "typescript",
+		"ts":         "typescript",
+		"tsx":        "typescript",
+		"java":       "java",
+		"c":          "c",
+		"cpp":        "cpp",
+		"c++":        "cpp",
+		"ruby":       "ruby",
+		"rb":         "ruby",
+		"php":        "php",
+		"scala":      "scala",
+		"csharp":     "csharp",
+		"cs":         "csharp",
+		"c#":         "csharp",
+		"kotlin":     "kotlin",
+		"kt":         "kotlin",
+		"swift":      "swift",
+		"zig":        "zig",
+		"haskell":    "haskell",
+		"hs":         "haskell",
+		"elixir":     "elixir",
+		"ex":         "elixir",
+		"erlang":     "erlang",
+		"erl":        "erlang",
+		"lua":        "lua",
+		"sql":        "sql",
+		"bash":       "shell",
+		"sh":         "shell",
+		"shell":      "shell",
+		"zsh":        "shell",
+		"dockerfile": "dockerfile",
+		"docker":     "dockerfile",
+	}
+)
+
+// chunkCodeBlocks runs the matching code chunker over each language-tagged
+// fenced code block in the given markdown sections. The resulting chunks keep
+// their file line numbers and link back to their section through "parent_id"
+// and "section" metadata.
+func (s *SemanticChunker) chunkCodeBlocks(sections []Chunk) []Chunk {
+	var chunks []Chunk
+	for _, section := range sections {
+		lines := strings.Split(section.Code, "\n")
+		for i := 0; i < len(lines); i++ {
+			matches := fenceTagRegex.FindStringSubmatch(lines[i])
+			if matches == nil {
+				continue
+			}
+
+			end := i + 1
+			for end < len(lines) && !isClosingFence(lines[end], matches[1]) {
+				end++
+			}
+
+			if language, ok := fenceLanguages[strings.ToLower(matches[2])]; ok && end > i+1 {
+				chunks = append(chunks, s.chunkCodeBlock(section, language, lines[i+1:end], section.LineStart+i)...)
+			}
+			i = end
+		}
+	}
+	return chunks
+}
+
+// chunkCodeBlock chunks the lines of one code block, whose first line is
+// offset lines below the start of the file. Blocks the code chunker finds
+// nothing in, such as short fragments, become a single block chunk.
+func (s *SemanticChunker) chunkCodeBlock(section Chunk, language string, lines []string, offset int) []Chunk {
+	sourceCode := []byte(strings.Join(lines, "\n") + "\n")
+	chunks, err := s.chunkSnippet(section.FilePath, language, sourceCode)
+	if err != nil || len(chunks) == 0 {
+		chunks = []Chunk{newLineChunk(section.FilePath, language, lines, 0, len(lines)-1, string(parser.ChunkTypeBlock), section.Name)}
+	}
+
+	for i := range chunks {
+		chunks[i].LineStart += offset
+		chunks[i].LineEnd += offset
+		chunks[i].EmbeddingType = "code"
+		if chunks[i].Metadata == nil {
+			chunks[i].Metadata = make(map[string]string)
+		}
+		chunks[i].Metadata["parent_id"] = section.ID
+		if path := section.Metadata["symbol"]; path != "" {
+			chunks[i].Metadata["section"] = path
+		}
+	}
+	return chunks
+}
+
+// isClosingFence reports whether line closes a code block opened with fence
+func isClosingFence(line, fence string) bool {
+	trimmed := strings.TrimSpace(line)
+	return strings.HasPrefix(trimmed, fence) && strings.Trim(trimmed, fence[:1]) == ""
+}
@@ -0,0 +1,60 @@
+package chunker
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkMarkdownCodeBlocks(t *testing.T) {
+	source := "# Usage\n" +
+		"\n" +
+		"## Client\n" +
+		"\n" +
+		"```go\n" +
+		"// NewClient creates a client.\n" +
+		"func NewClient() *Client {\n" +
+		"\treturn &Client{}\n" +
+		"}\n" +
+		"```\n" +
+		"\n" +
+		"```sh\n" +
+		"./bin/serve --port 8080\n" +
+		"```\n" +
+		"\n" +
+		"```\n" +
+		"plain text\n" +
+		"```\n"
+
+	mdFile := filepath.Join(t.TempDir(), "usage.md")
+	require.NoError(t, os.WriteFile(mdFile, []byte(source), 0644))
+
+	chunker, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := chunker.ChunkFile(mdFile, "markdown")
+	require.NoError(t, err)
+	require.Len(t, chunks, 4)
+
+	client := chunks[1]
+	assert.Equal(t, "Client", client.Name)
+	assert.Equal(t, "docs", client.EmbeddingType)
+
+	newClient := chunks[2]
+	assert.Equal(t, "NewClient", newClient.Name)
+	assert.Equal(t, "function", newClient.ChunkType)
+	assert.Equal(t, "go", newClient.Language)
+	assert.Equal(t, "code", newClient.EmbeddingType)
+	assert.Equal(t, 7, newClient.LineStart)
+	assert.Equal(t, 9, newClient.LineEnd)
+	assert.Equal(t, client.ID, newClient.Metadata["parent_id"])
+	assert.Equal(t, "Usage > Client", newClient.Metadata["section"])
+
+	script := chunks[3]
+	assert.Equal(t, "shell", script.Language)
+	assert.Equal(t, 13, script.LineStart)
+	assert.Equal(t, 13, script.LineEnd)
+	assert.Equal(t, client.ID, script.Metadata["parent_id"])
+}
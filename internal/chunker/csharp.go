@@ -0,0 +1,173 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+const (
+	csharpAttributes = `^\s*((?:\[[^\]]*\]\s*)*)`
+	csharpModifier   = `(?:public|private|protected|internal|static|abstract|sealed|partial|readonly|virtual|override|async|extern|unsafe|new|file|required|ref)\s+`
+	csharpType       = `[\w.]+(?:<[^()]*?>)?[?\[\]]*`
+)
+
+var (
+	// Matches type declarations: public sealed class Foo, record struct Point, enum Status
+	csharpTypeRegex = regexp.MustCompile(csharpAttributes + `(?:` + csharpModifier + `)*(class|interface|struct|enum|record(?:\s+class|\s+struct)?)\s+(\w+)`)
+	// Matches properties: public string Name { get; set; } or public int Count => items.Count;
+	csharpPropertyRegex = regexp.MustCompile(csharpAttributes + `((?:` + csharpModifier + `)+)(` + csharpType + `)\s+(\w+)\s*(?:\{\s*(?:get|set|init|private|protected|internal)\b|=>)`)
+	// Matches methods and constructors: public async Task<User> GetAsync(, public User(
+	csharpMethodRegex = regexp.MustCompile(csharpAttributes + `((?:` + csharpModifier + `)*)(?:(` + csharpType + `)\s+)?(\w+)\s*(?:<[^()]*?>)?\s*\(`)
+	// Matches block and file-scoped namespaces
+	csharpNamespaceRegex = regexp.MustCompile(`^\s*namespace\s+([\w.]+)`)
+	// Matches a line holding only attributes, e.g. [HttpGet("{id}")]
+	csharpAttributeLineRegex = regexp.MustCompile(`^\s*\[.*\]\s*$`)
+	// Matches XML tags in doc comments
+	xmlTagRegex = regexp.MustCompile(`<[^>]+>`)
+
+	// Statements that look like method calls at the start of a line
+	csharpStatementKeywords = map[string]bool{
+		"if": true, "for": true, "foreach": true, "while": true, "switch": true, "catch": true,
+		"using": true, "lock": true, "return": true, "nameof": true, "typeof": true, "sizeof": true,
+		"base": true, "this": true, "fixed": true, "when": true, "await": true, "throw": true,
+	}
+)
+
+// chunkCSharp extracts classes, interfaces, structs, records, enums, methods,
+// and properties from C# source. Chunks start at the XML doc comment and
+// attributes above a declaration so that both are embedded with the code.
+func chunkCSharp(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type typeScope struct {
+		name string
+		end  int
+	}
+	var (
+		chunks    []Chunk
+		types     []typeScope
+		namespace string
+	)
+
+	for i := 0; i < len(lines); i++ {
+		// Leave types whose body has ended
+		for len(types) > 0 && i > types[len(types)-1].end {
+			types = types[:len(types)-1]
+		}
+
+		line := lines[i]
+		if matches := csharpNamespaceRegex.FindStringSubmatch(line); matches != nil {
+			namespace = matches[1]
+			continue
+		}
+
+		var (
+			chunkType  parser.ChunkType
+			name       string
+			attributes string
+			kind       string
+		)
+
+		if matches := csharpTypeRegex.FindStringSubmatch(line); matches != nil {
+			attributes, kind, name = matches[1], strings.Join(strings.Fields(matches[2]), " "), matches[3]
+			switch kind {
+			case "interface":
+				chunkType = parser.ChunkTypeInterface
+			case "enum":
+				chunkType = parser.ChunkTypeEnum
+			case "struct", "record struct":
+				chunkType = parser.ChunkTypeStruct
+			default:
+				chunkType = parser.ChunkTypeClass
+			}
+		} else if len(types) == 0 {
+			// Members only exist inside types
+			continue
+		} else if matches := csharpPropertyRegex.FindStringSubmatch(line); matches != nil {
+			attributes, name = matches[1], matches[4]
+			chunkType = parser.ChunkTypeProperty
+		} else if matches := csharpMethodRegex.FindStringSubmatch(line); matches != nil {
+			attributes, name = matches[1], matches[4]
+			if csharpStatementKeywords[name] || csharpStatementKeywords[matches[3]] || (matches[2] == "" && matches[3] == "") {
+				continue
+			}
+			chunkType = parser.ChunkTypeMethod
+		} else {
+			continue
+		}
+
+		start := extendAbove(lines, i, isCSharpPreamble)
+		end := cStyle.blockEnd(lines, i)
+		chunk := newLineChunk(filePath, "csharp", lines, start, end, string(chunkType), name)
+
+		if namespace != "" {
+			chunk.Metadata["namespace"] = namespace
+		}
+		if kind != "" {
+			chunk.Metadata["kind"] = kind
+		}
+		if len(types) > 0 {
+			receiver := types[len(types)-1].name
+			chunk.Metadata["receiver"] = receiver
+			if chunkType == parser.ChunkTypeMethod && name == receiver {
+				chunk.Metadata["constructor"] = "true"
+			}
+		}
+		if doc := csharpDocComment(lines[start:i]); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		if attrs := csharpAttributeList(lines[start:i], attributes); attrs != "" {
+			chunk.Metadata["attributes"] = attrs
+		}
+		chunks = append(chunks, chunk)
+
+		// Types contain members, so keep scanning inside them; skip member bodies
+		if chunkType == parser.ChunkTypeClass || chunkType == parser.ChunkTypeInterface || chunkType == parser.ChunkTypeStruct {
+			types = append(types, typeScope{name: name, end: end})
+		} else {
+			i = end
+		}
+	}
+
+	return chunks
+}
+
+// isCSharpPreamble reports whether a trimmed line is a /// doc comment or
+// attribute line that belongs to the declaration below it
+func isCSharpPreamble(trimmed string) bool {
+	return strings.HasPrefix(trimmed, "///") || csharpAttributeLineRegex.MatchString(trimmed)
+}
+
+// csharpDocComment returns the text of /// XML doc comment lines with the
+// comment markers and XML tags removed
+func csharpDocComment(lines []string) string {
+	var doc []string
+	for _, line := range lines {
+		trimmed := strings.TrimSpace(line)
+		if !strings.HasPrefix(trimmed, "///") {
+			continue
+		}
+		text := strings.TrimSpace(xmlTagRegex.ReplaceAllString(strings.TrimLeft(trimmed, "/"), ""))
+		if text != "" {
+			doc = append(doc, text)
+		}
+	}
+	return strings.Join(doc, "\n")
+}
+
+// csharpAttributeList joins the attribute lines above a declaration and any
+// attributes on the declaration line itself
+func csharpAttributeList(lines []string, inline string) string {
+	var attrs []string
+	for _, line := range lines {
+		if trimmed := strings.TrimSpace(line); csharpAttributeLineRegex.MatchString(trimmed) {
+			attrs = append(attrs, trimmed)
+		}
+	}
+	if inline = strings.TrimSpace(inline); inline != "" {
+		attrs = append(attrs, inline)
+	}
+	return strings.Join(attrs, ", ")
+}
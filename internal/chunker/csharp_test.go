@@ -0,0 +1,90 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkCSharp(t *testing.T) {
+	source := `using System;
+
+namespace Shop.Api;
+
+/// <summary>
+/// Manages orders.
+/// </summary>
+[ApiController]
+[Route("api/orders")]
+public sealed class OrdersController : ControllerBase
+{
+    private readonly IOrderService _service;
+
+    public OrdersController(IOrderService service)
+    {
+        _service = service;
+    }
+
+    public int Count { get; private set; }
+
+    /// <summary>Gets an order by id.</summary>
+    [HttpGet("{id}")]
+    public async Task<ActionResult<Order>> GetAsync(int id)
+    {
+        if (id < 0)
+        {
+            return BadRequest();
+        }
+        return await _service.FindAsync(id);
+    }
+}
+
+public record Order(int Id, string Name);
+
+public interface IOrderService
+{
+    Task<Order> FindAsync(int id);
+}
+`
+
+	chunks := chunkCSharp("OrdersController.cs", []byte(source))
+
+	require.Len(t, chunks, 7)
+	byName := make(map[string]Chunk)
+	for _, chunk := range chunks {
+		// The constructor shares its class's name; keep the class
+		if _, exists := byName[chunk.Name]; !exists {
+			byName[chunk.Name] = chunk
+		}
+	}
+
+	controller := byName["OrdersController"]
+	assert.Equal(t, "class", controller.ChunkType)
+	assert.Equal(t, 5, controller.LineStart, "chunk should start at the XML doc comment")
+	assert.Equal(t, 31, controller.LineEnd)
+	assert.Equal(t, "Manages orders.", controller.Metadata["doc_comment"])
+	assert.Equal(t, `[ApiController], [Route("api/orders")]`, controller.Metadata["attributes"])
+	assert.Equal(t, "Shop.Api", controller.Metadata["namespace"])
+
+	ctor := chunks[1]
+	assert.Equal(t, "OrdersController", ctor.Name)
+	assert.Equal(t, "true", ctor.Metadata["constructor"])
+
+	assert.Equal(t, "property", byName["Count"].ChunkType)
+	assert.Equal(t, "OrdersController", byName["Count"].Metadata["receiver"])
+
+	get := byName["GetAsync"]
+	assert.Equal(t, "method", get.ChunkType)
+	assert.Equal(t, 21, get.LineStart)
+	assert.Equal(t, 30, get.LineEnd)
+	assert.Equal(t, "Gets an order by id.", get.Metadata["doc_comment"])
+	assert.Equal(t, `[HttpGet("{id}")]`, get.Metadata["attributes"])
+
+	assert.Equal(t, "class", byName["Order"].ChunkType)
+	assert.Equal(t, "record", byName["Order"].Metadata["kind"])
+	assert.Equal(t, 33, byName["Order"].LineEnd)
+
+	assert.Equal(t, "interface", byName["IOrderService"].ChunkType)
+	assert.Equal(t, "IOrderService", byName["FindAsync"].Metadata["receiver"])
+}
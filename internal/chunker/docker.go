@@ -0,0 +1,124 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches a stage instruction: FROM --platform=$BUILDPLATFORM golang:1.22 AS build
+	dockerFromRegex = regexp.MustCompile(`(?i)^\s*FROM\s+(?:--\S+\s+)*(\S+)(?:\s+AS\s+(\S+))?`)
+	// Matches a key of a compose file's top-level services mapping: "  postgres:"
+	composeServiceRegex = regexp.MustCompile(`^(\s+)([\w.-]+|"[^"]+"|'[^']+'):\s*(?:#.*)?$`)
+	// Matches a scalar setting within a service: image: postgres:16
+	composeSettingRegex = regexp.MustCompile(`^\s+(image|container_name|build):\s*["']?([^"'#\s]+)`)
+
+	dockerStyle = commentStyle{line: []string{"#"}}
+)
+
+// chunkDockerfile splits a Dockerfile into one chunk per build stage, from its
+// FROM instruction to the next. Instructions before the first FROM (global
+// ARGs and comments) belong to the first stage.
+func chunkDockerfile(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type stage struct {
+		line  int
+		image string
+		name  string
+	}
+	var stages []stage
+	for i, line := range lines {
+		if matches := dockerFromRegex.FindStringSubmatch(line); matches != nil {
+			stages = append(stages, stage{line: i, image: matches[1], name: matches[2]})
+		}
+	}
+
+	var chunks []Chunk
+	for n, s := range stages {
+		start := 0
+		if n > 0 {
+			start = extendAbove(lines, s.line, dockerStyle.isCommentLine)
+		}
+
+		end := len(lines) - 1
+		if n+1 < len(stages) {
+			end = extendAbove(lines, stages[n+1].line, dockerStyle.isCommentLine) - 1
+		}
+		for end > start && strings.TrimSpace(lines[end]) == "" {
+			end--
+		}
+
+		name := s.name
+		if name == "" {
+			name = s.image
+		}
+		chunk := newLineChunk(filePath, "dockerfile", lines, start, end, string(parser.ChunkTypeBlock), name)
+		chunk.Metadata["kind"] = "stage"
+		chunk.Metadata["image"] = s.image
+		if doc := dockerStyle.leadingComment(lines, s.line); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+	}
+
+	return chunks
+}
+
+// chunkCompose splits a docker-compose file into one chunk per entry of its
+// top-level services mapping, recording the service's image, container name,
+// and build context when set
+func chunkCompose(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	services := -1
+	for i, line := range lines {
+		if strings.HasPrefix(line, "services:") {
+			services = i
+			break
+		}
+	}
+	if services < 0 {
+		return nil
+	}
+
+	// The services mapping ends at the next top-level key
+	end := indentedEnd(lines, services)
+	var chunks []Chunk
+	serviceIndent := -1
+	for i := services + 1; i <= end; i++ {
+		matches := composeServiceRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			continue
+		}
+		if serviceIndent < 0 {
+			serviceIndent = len(matches[1])
+		}
+		if len(matches[1]) != serviceIndent {
+			continue
+		}
+
+		serviceEnd := indentedEnd(lines, i)
+		start := extendAbove(lines, i, dockerStyle.isCommentLine)
+		name := strings.Trim(matches[2], `"'`)
+		chunk := newLineChunk(filePath, "compose", lines, start, serviceEnd, string(parser.ChunkTypeBlock), name)
+		chunk.Metadata["kind"] = "service"
+		for _, line := range lines[i+1 : serviceEnd+1] {
+			if setting := composeSettingRegex.FindStringSubmatch(line); setting != nil && indentWidth(line) > serviceIndent {
+				if _, seen := chunk.Metadata[setting[1]]; !seen {
+					chunk.Metadata[setting[1]] = setting[2]
+				}
+			}
+		}
+		if doc := dockerStyle.leadingComment(lines, i); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		i = serviceEnd
+	}
+
+	return chunks
+}
@@ -0,0 +1,76 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkDockerfile(t *testing.T) {
+	source := `ARG GO_VERSION=1.22
+
+FROM golang:${GO_VERSION} AS build
+WORKDIR /src
+COPY . .
+RUN go build -o /bin/app ./cmd/app
+
+# Minimal runtime image.
+FROM gcr.io/distroless/base
+COPY --from=build /bin/app /app
+ENTRYPOINT ["/app"]
+`
+
+	chunks := chunkDockerfile("Dockerfile", []byte(source))
+	require.Len(t, chunks, 2)
+
+	build := chunks[0]
+	assert.Equal(t, "build", build.Name)
+	assert.Equal(t, "stage", build.Metadata["kind"])
+	assert.Equal(t, "golang:${GO_VERSION}", build.Metadata["image"])
+	assert.Equal(t, 1, build.LineStart)
+	assert.Equal(t, 6, build.LineEnd)
+
+	runtime := chunks[1]
+	assert.Equal(t, "gcr.io/distroless/base", runtime.Name)
+	assert.Equal(t, "Minimal runtime image.", runtime.Metadata["doc_comment"])
+	assert.Equal(t, 8, runtime.LineStart)
+	assert.Equal(t, 11, runtime.LineEnd)
+}
+
+func TestChunkCompose(t *testing.T) {
+	source := `services:
+  # Primary database.
+  postgres:
+    image: postgres:16
+    environment:
+      POSTGRES_PASSWORD: secret
+    ports:
+      - "5432:5432"
+
+  api:
+    build: ./api
+    depends_on:
+      - postgres
+
+volumes:
+  data:
+`
+
+	chunks := chunkCompose("docker-compose.yml", []byte(source))
+	require.Len(t, chunks, 2)
+
+	postgres := chunks[0]
+	assert.Equal(t, "postgres", postgres.Name)
+	assert.Equal(t, "service", postgres.Metadata["kind"])
+	assert.Equal(t, "postgres:16", postgres.Metadata["image"])
+	assert.Equal(t, "Primary database.", postgres.Metadata["doc_comment"])
+	assert.Equal(t, 2, postgres.LineStart)
+	assert.Equal(t, 8, postgres.LineEnd)
+
+	api := chunks[1]
+	assert.Equal(t, "api", api.Name)
+	assert.Equal(t, "./api", api.Metadata["build"])
+	assert.Equal(t, 10, api.LineStart)
+	assert.Equal(t, 13, api.LineEnd)
+}
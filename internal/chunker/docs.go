@@ -0,0 +1,32 @@
+package chunker
+
+import "strings"
+
+// summarizeDocs records the first sentence of each chunk's doc comment as its
+// doc_summary, shown as the summary line in search output
+func summarizeDocs(chunks []Chunk) {
+	for i := range chunks {
+		if summary := docSummary(chunks[i].Metadata["doc_comment"]); summary != "" {
+			chunks[i].Metadata["doc_summary"] = summary
+		}
+	}
+}
+
+// docSummary returns the first sentence of a doc comment. The summary ends at
+// a blank line or at the first tag line (@param, :param, Args:).
+func docSummary(doc string) string {
+	var lines []string
+	for _, line := range strings.Split(strings.TrimSpace(doc), "\n") {
+		line = strings.TrimSpace(line)
+		if line == "" || strings.HasPrefix(line, "@") || strings.HasPrefix(line, ":") || line == "Args:" || line == "Returns:" {
+			break
+		}
+		lines = append(lines, line)
+	}
+
+	summary := strings.Join(lines, " ")
+	if end := strings.Index(summary, ". "); end >= 0 {
+		summary = summary[:end+1]
+	}
+	return summary
+}
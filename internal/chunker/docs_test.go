@@ -0,0 +1,35 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestDocSummary(t *testing.T) {
+	tests := []struct {
+		doc      string
+		expected string
+	}{
+		{"", ""},
+		{"Returns the user's name.", "Returns the user's name."},
+		{"Parses a config file. Unknown keys are ignored.", "Parses a config file."},
+		{"Opens the store\nat the given path.\n\nThe directory is created if needed.", "Opens the store at the given path."},
+		{"Sends a request\n@param url the endpoint\n@return the response", "Sends a request"},
+		{"Fetch a page.\n\nArgs:\n    url: the page", "Fetch a page."},
+	}
+	for _, tt := range tests {
+		assert.Equal(t, tt.expected, docSummary(tt.doc), "doc %q", tt.doc)
+	}
+}
+
+func TestSummarizeDocs(t *testing.T) {
+	chunks := []Chunk{
+		{Metadata: map[string]string{"doc_comment": "Adds two numbers. Overflow wraps."}},
+		{Metadata: map[string]string{}},
+	}
+	summarizeDocs(chunks)
+
+	assert.Equal(t, "Adds two numbers.", chunks[0].Metadata["doc_summary"])
+	assert.NotContains(t, chunks[1].Metadata, "doc_summary")
+}
@@ -0,0 +1,220 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches module definitions: defmodule MyApp.Accounts do
+	elixirModuleRegex = regexp.MustCompile(`^\s*(defmodule|defprotocol|defimpl)\s+([\w.]+)`)
+	// Matches function and macro definitions: def name(, defp name, defmacro name(
+	elixirDefRegex = regexp.MustCompile(`^\s*(def|defp|defmacro|defmacrop|defguard|defguardp|defdelegate)\s+([a-z_][\w]*[?!]?)`)
+	// Matches block keywords that open or close a do/end block
+	elixirBlockRegex = regexp.MustCompile(`\b(do|fn|end)\b(:?)`)
+	// Matches attributes that document or annotate the definition below them
+	elixirAttributeRegex = regexp.MustCompile(`^\s*@(doc|moduledoc|spec|impl|deprecated|typedoc)\b`)
+)
+
+// chunkElixir extracts modules, protocols, implementations, functions, and
+// macros from Elixir source. @doc, @spec, and related attributes directly above
+// a definition are kept in its chunk, and @moduledoc is kept with its module.
+func chunkElixir(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type moduleScope struct {
+		name string
+		end  int
+	}
+	var (
+		chunks    []Chunk
+		modules   []moduleScope
+		inHeredoc bool
+	)
+
+	for i := 0; i < len(lines); i++ {
+		for len(modules) > 0 && i > modules[len(modules)-1].end {
+			modules = modules[:len(modules)-1]
+		}
+
+		// Skip heredoc contents such as iex> examples in @doc
+		line := lines[i]
+		if strings.Count(line, `"""`)%2 == 1 {
+			inHeredoc = !inHeredoc
+			continue
+		}
+		if inHeredoc {
+			continue
+		}
+
+		if matches := elixirModuleRegex.FindStringSubmatch(line); matches != nil {
+			end := elixirBlockEnd(lines, i)
+			chunk := newLineChunk(filePath, "elixir", lines, i, end, string(parser.ChunkTypeModule), matches[2])
+			chunk.Metadata["kind"] = matches[1]
+			if doc := elixirDocAttribute(lines[i+1:end+1], "moduledoc"); doc != "" {
+				chunk.Metadata["doc_comment"] = doc
+			}
+			chunks = append(chunks, chunk)
+			modules = append(modules, moduleScope{name: matches[2], end: end})
+			continue
+		}
+
+		matches := elixirDefRegex.FindStringSubmatch(line)
+		if matches == nil {
+			continue
+		}
+
+		chunkType := parser.ChunkTypeFunction
+		if len(modules) > 0 {
+			chunkType = parser.ChunkTypeMethod
+		}
+
+		start := elixirAttributesStart(lines, i)
+		end := elixirBlockEnd(lines, i)
+		chunk := newLineChunk(filePath, "elixir", lines, start, end, string(chunkType), matches[2])
+		chunk.Metadata["kind"] = matches[1]
+		if strings.HasSuffix(matches[1], "p") {
+			chunk.Metadata["private"] = "true"
+		}
+		if len(modules) > 0 {
+			chunk.Metadata["receiver"] = modules[len(modules)-1].name
+		}
+		if doc := elixirDocAttribute(lines[start:i], "doc"); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		i = end
+	}
+
+	return chunks
+}
+
+// elixirBlockEnd returns the line holding the `end` that closes the do block
+// opened on lines[start]. Keyword-form definitions (def f(x), do: x) and
+// bodiless heads have no block and end at the last line indented deeper than
+// the definition.
+func elixirBlockEnd(lines []string, start int) int {
+	depth := 0
+	opened := false
+	inHeredoc := false
+	for i := start; i < len(lines); i++ {
+		if strings.Count(lines[i], `"""`)%2 == 1 {
+			inHeredoc = !inHeredoc
+			continue
+		}
+		if inHeredoc {
+			continue
+		}
+		code := stripStringsAndComments(lines[i], '#')
+
+		for _, match := range elixirBlockRegex.FindAllStringSubmatch(code, -1) {
+			switch {
+			case match[2] == ":":
+				// Keyword form (do: expr) opens no block
+			case match[1] == "end":
+				depth--
+			default:
+				depth++
+				opened = true
+			}
+		}
+
+		if opened && depth <= 0 {
+			return i
+		}
+		// A complete head without `do` has no block; a head whose parameters
+		// continue on later lines may still open one
+		if !opened && i == start && strings.Count(code, "(") == strings.Count(code, ")") {
+			return indentedEnd(lines, start)
+		}
+	}
+	return len(lines) - 1
+}
+
+// elixirAttributesStart returns the first line of the attributes (@doc, @spec,
+// ...) directly above a definition, including multi-line @doc heredocs
+func elixirAttributesStart(lines []string, decl int) int {
+	start := decl
+	for start > 0 {
+		prev := strings.TrimSpace(lines[start-1])
+		if elixirAttributeRegex.MatchString(prev) {
+			start--
+			continue
+		}
+		if prev == `"""` {
+			// Closing line of a heredoc; find the attribute that opened it
+			open := start - 2
+			for open >= 0 && !strings.Contains(lines[open], `"""`) {
+				open--
+			}
+			if open < 0 || !elixirAttributeRegex.MatchString(strings.TrimSpace(lines[open])) {
+				return start
+			}
+			start = open
+			continue
+		}
+		return start
+	}
+	return start
+}
+
+// elixirDocAttribute returns the text of the first @doc or @moduledoc attribute
+// among lines, given either as a single string or as a heredoc
+func elixirDocAttribute(lines []string, attribute string) string {
+	prefix := "@" + attribute
+	for i, line := range lines {
+		trimmed := strings.TrimSpace(line)
+		if !strings.HasPrefix(trimmed, prefix+" ") {
+			continue
+		}
+		value := strings.TrimSpace(strings.TrimPrefix(trimmed, prefix))
+		if value == "false" {
+			return ""
+		}
+		if value != `"""` && value != `~S"""` {
+			return strings.Trim(value, `"`)
+		}
+
+		var doc []string
+		for _, docLine := range lines[i+1:] {
+			if strings.TrimSpace(docLine) == `"""` {
+				break
+			}
+			doc = append(doc, strings.TrimSpace(docLine))
+		}
+		return strings.TrimSpace(strings.Join(doc, "\n"))
+	}
+	return ""
+}
+
+// stripStringsAndComments blanks out string literals and removes a trailing
+// comment starting with marker, so keywords inside either are not matched
+func stripStringsAndComments(line string, marker byte) string {
+	var (
+		code  strings.Builder
+		quote byte
+	)
+	for i := 0; i < len(line); i++ {
+		c := line[i]
+		if quote != 0 {
+			if c == '\\' {
+				i++
+			} else if c == quote {
+				quote = 0
+				code.WriteByte(c)
+			}
+			continue
+		}
+		switch c {
+		case '"', '\'':
+			quote = c
+		case marker:
+			return code.String()
+		}
+		code.WriteByte(c)
+	}
+	return code.String()
+}
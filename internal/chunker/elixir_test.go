@@ -0,0 +1,62 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkElixir(t *testing.T) {
+	source := `defmodule MyApp.Accounts do
+  @moduledoc """
+  Manages user accounts.
+  """
+
+  @doc "Fetches a user by id."
+  @spec get(integer()) :: map()
+  def get(id) do
+    Repo.get(User, id)
+  end
+
+  defp normalize(email), do: String.downcase(email)
+
+  defmacro debug(expr) do
+    quote do
+      IO.inspect(unquote(expr))
+    end
+  end
+end
+`
+
+	chunks := chunkElixir("accounts.ex", []byte(source))
+	require.Len(t, chunks, 4)
+
+	module := chunks[0]
+	assert.Equal(t, "MyApp.Accounts", module.Name)
+	assert.Equal(t, "module", module.ChunkType)
+	assert.Equal(t, "Manages user accounts.", module.Metadata["doc_comment"])
+	assert.Equal(t, 1, module.LineStart)
+	assert.Equal(t, 19, module.LineEnd)
+
+	get := chunks[1]
+	assert.Equal(t, "get", get.Name)
+	assert.Equal(t, "method", get.ChunkType)
+	assert.Equal(t, "MyApp.Accounts", get.Metadata["receiver"])
+	assert.Equal(t, "Fetches a user by id.", get.Metadata["doc_comment"])
+	assert.Equal(t, 6, get.LineStart)
+	assert.Equal(t, 10, get.LineEnd)
+	assert.Contains(t, get.Content, "@spec get(integer())")
+
+	normalize := chunks[2]
+	assert.Equal(t, "normalize", normalize.Name)
+	assert.Equal(t, "true", normalize.Metadata["private"])
+	assert.Equal(t, 12, normalize.LineStart)
+	assert.Equal(t, 12, normalize.LineEnd)
+
+	debug := chunks[3]
+	assert.Equal(t, "debug", debug.Name)
+	assert.Equal(t, "defmacro", debug.Metadata["kind"])
+	assert.Equal(t, 14, debug.LineStart)
+	assert.Equal(t, 18, debug.LineEnd)
+}
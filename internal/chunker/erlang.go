@@ -0,0 +1,100 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches the module attribute: -module(accounts).
+	erlangModuleRegex = regexp.MustCompile(`^-module\(\s*'?([\w@.]+)'?\s*\)`)
+	// Matches the first clause of a function at column 0: handle_call(Request, From, State) ->
+	erlangFunctionRegex = regexp.MustCompile(`^('[^']+'|[a-z][\w@]*)\s*\(`)
+	// Matches attributes that belong to the function below them
+	erlangAttributeRegex = regexp.MustCompile(`^-(spec|doc|deprecated)\b`)
+)
+
+// chunkErlang extracts functions from Erlang source. All clauses of a function
+// form one chunk, together with the -spec, -doc, and %% comments directly above
+// it; EDoc comments (%% @doc ...) and -doc attributes become its doc comment.
+func chunkErlang(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var (
+		chunks []Chunk
+		module string
+	)
+
+	for i := 0; i < len(lines); i++ {
+		line := lines[i]
+		if matches := erlangModuleRegex.FindStringSubmatch(line); matches != nil {
+			module = matches[1]
+			continue
+		}
+
+		matches := erlangFunctionRegex.FindStringSubmatch(line)
+		if matches == nil {
+			continue
+		}
+
+		name := strings.Trim(matches[1], "'")
+		start := extendAbove(lines, i, func(trimmed string) bool {
+			return strings.HasPrefix(trimmed, "%") || erlangAttributeRegex.MatchString(trimmed)
+		})
+		end := erlangFormEnd(lines, i)
+
+		chunk := newLineChunk(filePath, "erlang", lines, start, end, string(parser.ChunkTypeFunction), name)
+		if module != "" {
+			chunk.Metadata["module"] = module
+			chunk.Metadata["receiver"] = module
+		}
+		if doc := erlangDocComment(lines[start:i]); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		i = end
+	}
+
+	return chunks
+}
+
+// erlangFormEnd returns the line on which the form starting at lines[start]
+// ends with a terminating '.', ignoring periods in strings and comments
+func erlangFormEnd(lines []string, start int) int {
+	for i := start; i < len(lines); i++ {
+		code := strings.TrimSpace(stripStringsAndComments(lines[i], '%'))
+		if strings.HasSuffix(code, ".") {
+			return i
+		}
+	}
+	return len(lines) - 1
+}
+
+// erlangDocComment returns the documentation found in the comments and
+// attributes above a function: %% comment text (with EDoc's @doc tag removed)
+// and the contents of a -doc("...") attribute
+func erlangDocComment(lines []string) string {
+	var doc []string
+	for _, line := range lines {
+		trimmed := strings.TrimSpace(line)
+		switch {
+		case strings.HasPrefix(trimmed, "%"):
+			text := strings.TrimSpace(strings.TrimLeft(trimmed, "%"))
+			text = strings.TrimSpace(strings.TrimPrefix(text, "@doc"))
+			if text != "" {
+				doc = append(doc, text)
+			}
+		case strings.HasPrefix(trimmed, "-doc"):
+			text := strings.TrimPrefix(trimmed, "-doc")
+			text = strings.TrimSuffix(strings.TrimSpace(text), ".")
+			text = strings.Trim(strings.TrimSpace(text), "()")
+			if text = strings.Trim(strings.TrimSpace(text), `"`); text != "" {
+				doc = append(doc, text)
+			}
+		}
+	}
+	return strings.Join(doc, "\n")
+}
@@ -0,0 +1,46 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkErlang(t *testing.T) {
+	source := `-module(accounts).
+-export([get/1, fetch/2]).
+
+%% @doc Looks up an account.
+-spec get(integer()) -> map().
+get(Id) when is_integer(Id) ->
+    lookup(Id);
+get(_) ->
+    {error, "bad id."}.
+
+-doc("Fetches with a default.").
+fetch(Id, Default) ->
+    case get(Id) of
+        {error, _} -> Default;
+        Account -> Account
+    end.
+`
+
+	chunks := chunkErlang("accounts.erl", []byte(source))
+	require.Len(t, chunks, 2)
+
+	get := chunks[0]
+	assert.Equal(t, "get", get.Name)
+	assert.Equal(t, "function", get.ChunkType)
+	assert.Equal(t, "accounts", get.Metadata["module"])
+	assert.Equal(t, "Looks up an account.", get.Metadata["doc_comment"])
+	assert.Equal(t, 4, get.LineStart)
+	assert.Equal(t, 9, get.LineEnd)
+	assert.Contains(t, get.Content, "get(_) ->")
+
+	fetch := chunks[1]
+	assert.Equal(t, "fetch", fetch.Name)
+	assert.Equal(t, "Fetches with a default.", fetch.Metadata["doc_comment"])
+	assert.Equal(t, 11, fetch.LineStart)
+	assert.Equal(t, 16, fetch.LineEnd)
+}
@@ -0,0 +1,86 @@
+package chunker
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+)
+
+// generatedFileSuffixes are file name endings of common code generators'
+// output: protoc, gRPC, Dart build_runner, and .NET designers
+var generatedFileSuffixes = []string{
+	"_pb2.py",
+	"_pb2_grpc.py",
+	"_pb2.pyi",
+	".pb.go",
+	".pb.gw.go",
+	".pb.cc",
+	".pb.h",
+	"_pb.js",
+	"_pb.d.ts",
+	"_grpc_pb.js",
+	".g.dart",
+	".freezed.dart",
+	".pb.dart",
+	".g.cs",
+	".Designer.cs",
+	".designer.cs",
+	"_generated.go",
+	".gen.go",
+}
+
+// generatedHeaderLines is how many lines at the top of a file are checked for
+// a generated-code marker
+const generatedHeaderLines = 20
+
+var (
+	// goGeneratedRegex is the Go convention for generated files (go help generate)
+	goGeneratedRegex = regexp.MustCompile(`^// Code generated .* DO NOT EDIT\.$`)
+
+	// generatedMarkers are phrases other generators put in their file headers
+	generatedMarkers = []string{
+		"@generated",
+		"<auto-generated",
+		"Generated by the protocol buffer compiler",
+		"This file was automatically generated",
+		"This file is automatically generated",
+		"AUTO-GENERATED FILE",
+		"DO NOT EDIT",
+	}
+)
+
+// isGeneratedFile reports whether a file is generated code, by its name or a
+// marker comment in its first lines
+func isGeneratedFile(filePath string) (bool, error) {
+	base := filepath.Base(filePath)
+	for _, suffix := range generatedFileSuffixes {
+		if strings.HasSuffix(base, suffix) {
+			return true, nil
+		}
+	}
+
+	file, err := os.Open(filePath)
+	if err != nil {
+		return false, fmt.Errorf("failed to read file: %w", err)
+	}
+	defer file.Close()
+
+	scanner := bufio.NewScanner(file)
+	for i := 0; i < generatedHeaderLines && scanner.Scan(); i++ {
+		line := strings.TrimSpace(scanner.Text())
+		if goGeneratedRegex.MatchString(line) {
+			return true, nil
+		}
+		for _, marker := range generatedMarkers {
+			if strings.Contains(line, marker) {
+				return true, nil
+			}
+		}
+	}
+	// A first line longer than the scanner's buffer, as in minified files, is not
+	// a marker; the chunkers report real read errors
+	return false, nil
+}
@@ -0,0 +1,78 @@
+package chunker
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func writeSource(t *testing.T, name, source string) string {
+	t.Helper()
+	path := filepath.Join(t.TempDir(), name)
+	require.NoError(t, os.WriteFile(path, []byte(source), 0644))
+	return path
+}
+
+func TestIsGeneratedFile(t *testing.T) {
+	tests := []struct {
+		name     string
+		source   string
+		expected bool
+	}{
+		{"user.pb.go", "package user\n", true},
+		{"user_pb2.py", "x = 1\n", true},
+		{"model.g.dart", "part of 'model.dart';\n", true},
+		{"mock.go", "// Code generated by MockGen. DO NOT EDIT.\n\npackage mock\n", true},
+		{"schema.ts", "/**\n * @generated\n */\nexport type A = string;\n", true},
+		{"Form.cs", "// <auto-generated>\n//     This code was generated by a tool.\n// </auto-generated>\n", true},
+		{"main.go", "package main\n\n// Code generated here is checked by hand\nfunc main() {}\n", false},
+		{"main.py", "def main():\n    pass\n", false},
+	}
+	for _, tt := range tests {
+		generated, err := isGeneratedFile(writeSource(t, tt.name, tt.source))
+		require.NoError(t, err)
+		assert.Equal(t, tt.expected, generated, tt.name)
+	}
+}
+
+func TestChunkFileTagsGenerated(t *testing.T) {
+	path := writeSource(t, "mock.go", "// Code generated by MockGen. DO NOT EDIT.\n\npackage mock\n\nfunc New() {}\n")
+
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(path, "go")
+	require.NoError(t, err)
+	require.NotEmpty(t, chunks)
+	for _, chunk := range chunks {
+		assert.Equal(t, "true", chunk.Metadata["generated"])
+	}
+
+	skipping, err := NewSemanticWithOptions(Options{SkipGenerated: true})
+	require.NoError(t, err)
+	chunks, err = skipping.ChunkFile(path, "go")
+	require.NoError(t, err)
+	assert.Empty(t, chunks)
+}
+
+func TestSkipReason(t *testing.T) {
+	generated := writeSource(t, "mock.go", "// Code generated by MockGen. DO NOT EDIT.\n\npackage mock\n")
+	minified := writeSource(t, "app.min.js", "var a=1;\n")
+	plain := writeSource(t, "main.go", "package main\n")
+
+	semantic, err := NewSemanticWithOptions(Options{SkipGenerated: true})
+	require.NoError(t, err)
+	for path, expected := range map[string]string{generated: "generated", minified: "binary or minified", plain: ""} {
+		reason, err := semantic.SkipReason(path, "go")
+		require.NoError(t, err)
+		assert.Equal(t, expected, reason, path)
+	}
+
+	tagging, err := NewSemantic()
+	require.NoError(t, err)
+	reason, err := tagging.SkipReason(generated, "go")
+	require.NoError(t, err)
+	assert.Empty(t, reason)
+}
@@ -0,0 +1,198 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches schema and operation definitions: type User implements Node {, extend type Query {,
+	// query GetUser($id: ID!) {, directive @auth on FIELD_DEFINITION
+	graphqlDefinitionRegex = regexp.MustCompile(`^(extend\s+)?(type|input|interface|enum|union|scalar|schema|directive|query|mutation|subscription|fragment)\b\s*@?(\w*)`)
+	// Matches the interfaces an object type implements: implements Node & Entity
+	graphqlImplementsRegex = regexp.MustCompile(`\bimplements\s+&?\s*([\w\s&]+?)\s*(?:@|\{|$)`)
+	// Matches a field of a root operation type: user(id: ID!): User
+	graphqlFieldRegex = regexp.MustCompile(`^\s*(\w+)\s*([(:])`)
+
+	graphqlStyle = commentStyle{line: []string{"#"}}
+
+	// Root operation types whose fields are the API's entry points
+	graphqlRootTypes = map[string]bool{"Query": true, "Mutation": true, "Subscription": true}
+)
+
+// chunkGraphQL extracts type, input, interface, enum, union, scalar, and
+// directive definitions from GraphQL schemas, plus query, mutation,
+// subscription, and fragment operations. Descriptions ("..." or """...""")
+// stay with their definition and become its doc comment. Fields of the Query,
+// Mutation, and Subscription types also get their own chunks.
+func chunkGraphQL(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var chunks []Chunk
+	for i := 0; i < len(lines); i++ {
+		matches := graphqlDefinitionRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			continue
+		}
+
+		kind, name := matches[2], matches[3]
+		if name == "" {
+			name = kind
+		}
+
+		var chunkType parser.ChunkType
+		switch kind {
+		case "type", "input":
+			chunkType = parser.ChunkTypeStruct
+		case "interface":
+			chunkType = parser.ChunkTypeInterface
+		case "enum":
+			chunkType = parser.ChunkTypeEnum
+		case "union", "scalar":
+			chunkType = parser.ChunkTypeTypeAlias
+		case "query", "mutation", "subscription", "fragment":
+			chunkType = parser.ChunkTypeFunction
+		default:
+			chunkType = parser.ChunkTypeBlock
+		}
+
+		end := graphqlDefinitionEnd(lines, i)
+		start, description := graphqlDescription(lines, i)
+		chunk := newLineChunk(filePath, "graphql", lines, start, end, string(chunkType), name)
+		chunk.Metadata["kind"] = kind
+		if matches[1] != "" {
+			chunk.Metadata["extend"] = "true"
+		}
+		if implements := graphqlImplementsRegex.FindStringSubmatch(lines[i]); implements != nil {
+			chunk.Metadata["implements"] = strings.Join(strings.Fields(strings.ReplaceAll(implements[1], "&", " ")), ", ")
+		}
+		if description != "" {
+			chunk.Metadata["doc_comment"] = description
+		}
+		chunks = append(chunks, chunk)
+
+		if kind == "type" && graphqlRootTypes[name] {
+			chunks = append(chunks, graphqlRootFields(filePath, lines, i, end, name)...)
+		}
+
+		i = end
+	}
+
+	return chunks
+}
+
+// graphqlDefinitionEnd returns the last line of the definition starting at
+// lines[start]: the closing brace of its body, or for bodiless definitions
+// (union, scalar, directive) the last line indented below it
+func graphqlDefinitionEnd(lines []string, start int) int {
+	if strings.Contains(lines[start], "{") {
+		return graphqlStyle.blockEnd(lines, start)
+	}
+	for next := start + 1; next < len(lines); next++ {
+		trimmed := strings.TrimSpace(lines[next])
+		if trimmed == "" {
+			continue
+		}
+		if strings.HasPrefix(trimmed, "{") || strings.HasPrefix(trimmed, "(") {
+			return graphqlStyle.blockEnd(lines, start)
+		}
+		break
+	}
+	return indentedEnd(lines, start)
+}
+
+// graphqlRootFields returns a chunk for each field of a root operation type
+// whose body spans lines[start+1:end]
+func graphqlRootFields(filePath string, lines []string, start, end int, typeName string) []Chunk {
+	var chunks []Chunk
+	fieldIndent := -1
+	inDescription := false
+	for i := start + 1; i < end; i++ {
+		// Skip the contents of block string descriptions
+		if strings.Count(lines[i], `"""`)%2 == 1 {
+			inDescription = !inDescription
+			continue
+		}
+		if inDescription {
+			continue
+		}
+
+		matches := graphqlFieldRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			continue
+		}
+		if fieldIndent < 0 {
+			fieldIndent = indentWidth(lines[i])
+		}
+		if indentWidth(lines[i]) != fieldIndent {
+			continue
+		}
+
+		// Arguments may span several lines up to the closing parenthesis
+		fieldEnd := i
+		if matches[2] == "(" {
+			depth := 0
+			for fieldEnd = i; fieldEnd < end; fieldEnd++ {
+				depth += strings.Count(lines[fieldEnd], "(") - strings.Count(lines[fieldEnd], ")")
+				if depth <= 0 {
+					break
+				}
+			}
+		}
+
+		fieldStart, description := graphqlDescription(lines, i)
+		chunk := newLineChunk(filePath, "graphql", lines, fieldStart, fieldEnd, string(parser.ChunkTypeMethod), matches[1])
+		chunk.Metadata["kind"] = "field"
+		chunk.Metadata["receiver"] = typeName
+		signature := strings.TrimSpace(strings.Join(lines[i:fieldEnd+1], " "))
+		chunk.Metadata["signature"] = strings.Join(strings.Fields(strings.TrimPrefix(signature, matches[1])), " ")
+		if description != "" {
+			chunk.Metadata["doc_comment"] = description
+		}
+		chunks = append(chunks, chunk)
+
+		i = fieldEnd
+	}
+	return chunks
+}
+
+// graphqlDescription returns the first line of the description directly
+// above lines[decl] and its text, or decl and "" when there is none. # comments
+// are used as the description when no string description is present.
+func graphqlDescription(lines []string, decl int) (int, string) {
+	if decl == 0 {
+		return decl, ""
+	}
+
+	prev := strings.TrimSpace(lines[decl-1])
+	switch {
+	case strings.HasSuffix(prev, `"""`):
+		// Find the line that opens the block string
+		open := decl - 1
+		if strings.Count(prev, `"""`) < 2 {
+			open--
+			for open >= 0 && !strings.Contains(lines[open], `"""`) {
+				open--
+			}
+			if open < 0 {
+				return decl, ""
+			}
+		}
+		text := strings.Join(lines[open:decl], "\n")
+		text = strings.ReplaceAll(text, `"""`, "")
+		var doc []string
+		for _, line := range strings.Split(text, "\n") {
+			if line = strings.TrimSpace(line); line != "" {
+				doc = append(doc, line)
+			}
+		}
+		return open, strings.Join(doc, "\n")
+	case strings.HasPrefix(prev, `"`) && strings.HasSuffix(prev, `"`) && len(prev) > 1:
+		return decl - 1, strings.Trim(prev, `"`)
+	case strings.HasPrefix(prev, "#"):
+		return extendAbove(lines, decl, graphqlStyle.isCommentLine), graphqlStyle.leadingComment(lines, decl)
+	}
+	return decl, ""
+}
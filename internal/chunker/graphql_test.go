@@ -0,0 +1,89 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkGraphQL(t *testing.T) {
+	source := `"""
+A registered user.
+"""
+type User implements Node & Entity {
+  id: ID!
+  email: String!
+}
+
+"Fields needed to create a user."
+input CreateUserInput {
+  email: String!
+}
+
+union SearchResult = User | Post
+
+type Query {
+  "Looks up a user by id."
+  user(id: ID!): User
+  search(
+    term: String!
+    first: Int = 10
+  ): [SearchResult!]!
+}
+
+# Creates a user.
+mutation CreateUser($input: CreateUserInput!) {
+  createUser(input: $input) {
+    id
+  }
+}
+`
+
+	chunks := chunkGraphQL("schema.graphql", []byte(source))
+	require.Len(t, chunks, 7)
+
+	user := chunks[0]
+	assert.Equal(t, "User", user.Name)
+	assert.Equal(t, "struct", user.ChunkType)
+	assert.Equal(t, "A registered user.", user.Metadata["doc_comment"])
+	assert.Equal(t, "Node, Entity", user.Metadata["implements"])
+	assert.Equal(t, 1, user.LineStart)
+	assert.Equal(t, 7, user.LineEnd)
+
+	input := chunks[1]
+	assert.Equal(t, "CreateUserInput", input.Name)
+	assert.Equal(t, "input", input.Metadata["kind"])
+	assert.Equal(t, "Fields needed to create a user.", input.Metadata["doc_comment"])
+	assert.Equal(t, 9, input.LineStart)
+
+	union := chunks[2]
+	assert.Equal(t, "SearchResult", union.Name)
+	assert.Equal(t, "type_alias", union.ChunkType)
+	assert.Equal(t, 14, union.LineEnd)
+
+	query := chunks[3]
+	assert.Equal(t, "Query", query.Name)
+	assert.Equal(t, 16, query.LineStart)
+	assert.Equal(t, 23, query.LineEnd)
+
+	userField := chunks[4]
+	assert.Equal(t, "user", userField.Name)
+	assert.Equal(t, "method", userField.ChunkType)
+	assert.Equal(t, "Query", userField.Metadata["receiver"])
+	assert.Equal(t, "(id: ID!): User", userField.Metadata["signature"])
+	assert.Equal(t, "Looks up a user by id.", userField.Metadata["doc_comment"])
+	assert.Equal(t, 17, userField.LineStart)
+
+	search := chunks[5]
+	assert.Equal(t, "search", search.Name)
+	assert.Equal(t, 19, search.LineStart)
+	assert.Equal(t, 22, search.LineEnd)
+
+	mutation := chunks[6]
+	assert.Equal(t, "CreateUser", mutation.Name)
+	assert.Equal(t, "function", mutation.ChunkType)
+	assert.Equal(t, "Creates a user.", mutation.Metadata["doc_comment"])
+	assert.Equal(t, 25, mutation.LineStart)
+	assert.Equal(t, 30, mutation.LineEnd)
+}
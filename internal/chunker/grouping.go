@@ -0,0 +1,99 @@
+package chunker
+
+import (
+	"strconv"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+// groupedTypes are the chunk types that get an aggregate chunk with the
+// signatures of their methods
+var groupedTypes = map[string]bool{
+	"struct":    true,
+	"class":     true,
+	"enum":      true,
+	"interface": true,
+}
+
+// groupTypes returns an aggregate chunk for each type in chunks that has
+// methods, made of the type's definition followed by the signatures of its
+// methods, so a question about what a type does finds the whole type in one
+// chunk. The methods keep their own chunks as well.
+func groupTypes(chunks []Chunk) []Chunk {
+	methods := make(map[string][]Chunk)
+	for _, chunk := range chunks {
+		if chunk.ChunkType == "method" && chunk.Metadata["receiver"] != "" {
+			key := typeKey(chunk.Metadata["receiver"])
+			methods[key] = append(methods[key], chunk)
+		}
+	}
+
+	var groups []Chunk
+	for _, chunk := range chunks {
+		if !groupedTypes[chunk.ChunkType] || chunk.EmbeddingType != "code" {
+			continue
+		}
+		typeMethods := methods[typeKey(chunk.Name)]
+		if len(typeMethods) == 0 {
+			continue
+		}
+		groups = append(groups, groupType(chunk, typeMethods))
+	}
+	return groups
+}
+
+// groupType builds the aggregate chunk of a type and its methods
+func groupType(typeChunk Chunk, methods []Chunk) Chunk {
+	lineStart, lineEnd := typeChunk.LineStart, typeChunk.LineEnd
+	names := make([]string, 0, len(methods))
+	signatures := make([]string, 0, len(methods))
+	for _, method := range methods {
+		lineStart = min(lineStart, method.LineStart)
+		lineEnd = max(lineEnd, method.LineEnd)
+		names = append(names, method.Name)
+		signatures = append(signatures, methodSignature(method))
+	}
+
+	metadata := map[string]string{
+		"group":        "type",
+		"group_of":     typeChunk.ChunkType,
+		"methods":      strings.Join(names, ", "),
+		"method_count": strconv.Itoa(len(methods)),
+	}
+	for _, key := range []string{"qualified_name", "kind", "visibility", "doc_comment", "doc_summary", "attributes"} {
+		if value := typeChunk.Metadata[key]; value != "" {
+			metadata[key] = value
+		}
+	}
+
+	return Chunk{
+		ID:            uuid.New().String(),
+		FilePath:      typeChunk.FilePath,
+		LineStart:     lineStart,
+		LineEnd:       lineEnd,
+		Language:      typeChunk.Language,
+		Code:          typeChunk.Code + "\n\n" + strings.Join(signatures, "\n"),
+		ChunkType:     "type_group",
+		Name:          typeChunk.Name,
+		Metadata:      metadata,
+		EmbeddingType: typeChunk.EmbeddingType,
+	}
+}
+
+// methodSignature returns a method's declaration line without its opening
+// brace, e.g. "pub fn new(x: f64, y: f64) -> Self"
+func methodSignature(method Chunk) string {
+	line := strings.TrimSpace(declarationLine(strings.Split(method.Code, "\n")))
+	return strings.TrimSpace(strings.TrimSuffix(line, "{"))
+}
+
+// typeKey reduces a type or receiver to its bare name, so Point, *Point, and
+// Container<T> match the types Point and Container
+func typeKey(name string) string {
+	name = strings.TrimLeft(strings.TrimSpace(name), "*&")
+	if end := strings.IndexAny(name, "<["); end >= 0 {
+		name = name[:end]
+	}
+	return strings.TrimSpace(name)
+}
@@ -0,0 +1,69 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func findGroup(chunks []Chunk, name string) *Chunk {
+	for i := range chunks {
+		if chunks[i].ChunkType == "type_group" && chunks[i].Name == name {
+			return &chunks[i]
+		}
+	}
+	return nil
+}
+
+func TestGroupTypes(t *testing.T) {
+	semantic, err := NewSemanticWithOptions(Options{GroupTypes: true})
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile("testdata/sample.rs", "rust")
+	require.NoError(t, err)
+
+	point := findGroup(chunks, "Point")
+	require.NotNil(t, point)
+	assert.Contains(t, point.Code, "pub struct Point {\n    pub x: f64,")
+	assert.Contains(t, point.Code, "\n\npub fn new(x: f64, y: f64) -> Self\npub fn distance_from_origin(&self) -> f64\npub fn origin() -> Self")
+	assert.NotContains(t, point.Code, "sqrt")
+	assert.Equal(t, "new, distance_from_origin, origin", point.Metadata["methods"])
+	assert.Equal(t, "3", point.Metadata["method_count"])
+	assert.Equal(t, "struct", point.Metadata["group_of"])
+
+	// Generic impls and trait impls count toward their type
+	container := findGroup(chunks, "Container")
+	require.NotNil(t, container)
+	assert.Equal(t, "new, get, map", container.Metadata["methods"])
+
+	user := findGroup(chunks, "User")
+	require.NotNil(t, user)
+	assert.Equal(t, "new, fmt", user.Metadata["methods"])
+
+	status := findGroup(chunks, "Status")
+	require.NotNil(t, status)
+	assert.Equal(t, "enum", status.Metadata["group_of"])
+
+	// The methods keep their own chunks
+	methods := 0
+	for _, chunk := range chunks {
+		if chunk.ChunkType == "method" && chunk.Metadata["receiver"] == "Point" {
+			methods++
+		}
+	}
+	assert.Equal(t, 3, methods)
+}
+
+func TestGroupTypesDisabled(t *testing.T) {
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile("testdata/sample.rs", "rust")
+	require.NoError(t, err)
+	assert.Nil(t, findGroup(chunks, "Point"))
+}
+
+func TestTypeKey(t *testing.T) {
+	assert.Equal(t, "Point", typeKey("*Point"))
+	assert.Equal(t, "Container", typeKey("Container<T>"))
+	assert.Equal(t, "List", typeKey("List[T]"))
+}
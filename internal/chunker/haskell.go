@@ -0,0 +1,200 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches the module header: module Data.Queue (Queue, push) where
+	haskellModuleRegex = regexp.MustCompile(`^module\s+([\w.]+)`)
+	// Matches type-level declarations at column 0
+	haskellTypeRegex = regexp.MustCompile(`^(data|newtype|type|class|instance)\s+(.*)`)
+	// Matches a type signature for one or more bindings: push, pop :: Queue a -> ...
+	haskellSignatureRegex = regexp.MustCompile(`^((?:[a-z_][\w']*|\([^)\s]+\))(?:\s*,\s*(?:[a-z_][\w']*|\([^)\s]+\)))*)\s*::\s*(.*)`)
+	// Matches the name a top-level binding defines: foo x y = ... or (<+>) a b = ...
+	haskellBindingRegex = regexp.MustCompile(`^([a-z_][\w']*|\([^)\s]+\))`)
+	// Matches a type constructor name, skipping an optional context: (Eq a) => Set a
+	haskellTypeNameRegex = regexp.MustCompile(`^(?:.*=>\s*)?([A-Z][\w']*|\([^)]+\))`)
+
+	// Column-0 keywords that never start a function binding
+	haskellKeywords = map[string]bool{
+		"module": true, "import": true, "deriving": true, "infix": true, "infixl": true,
+		"infixr": true, "foreign": true, "default": true, "pattern": true, "where": true,
+	}
+)
+
+// chunkHaskell extracts top-level function bindings and type-level
+// declarations (data, newtype, type, class, instance) from Haskell source. A
+// function's chunk holds its Haddock comment, type signature, and every
+// equation, so `foo :: Int -> Int` is embedded together with its definition.
+func chunkHaskell(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var (
+		chunks []Chunk
+		module string
+	)
+
+	for i := 0; i < len(lines); i++ {
+		line := lines[i]
+		if line == "" || indentWidth(line) > 0 || strings.HasPrefix(line, "--") || strings.HasPrefix(line, "{-") {
+			continue
+		}
+
+		if matches := haskellModuleRegex.FindStringSubmatch(line); matches != nil {
+			module = matches[1]
+			i = indentedEnd(lines, i)
+			continue
+		}
+
+		var (
+			chunkType parser.ChunkType
+			name      string
+			kind      string
+			signature string
+		)
+
+		if matches := haskellTypeRegex.FindStringSubmatch(line); matches != nil {
+			kind = matches[1]
+			head := strings.TrimSpace(strings.SplitN(matches[2], " where", 2)[0])
+			switch kind {
+			case "data", "newtype":
+				chunkType = parser.ChunkTypeStruct
+			case "type":
+				chunkType = parser.ChunkTypeTypeAlias
+			case "class":
+				chunkType = parser.ChunkTypeInterface
+			case "instance":
+				chunkType = parser.ChunkTypeImpl
+			}
+			if kind == "instance" {
+				// Instances are named by their head: Show (Tree a)
+				if idx := strings.Index(head, "=>"); idx >= 0 {
+					head = strings.TrimSpace(head[idx+2:])
+				}
+				name = head
+			} else if nameMatch := haskellTypeNameRegex.FindStringSubmatch(head); nameMatch != nil {
+				name = nameMatch[1]
+			}
+		} else if matches := haskellSignatureRegex.FindStringSubmatch(line); matches != nil {
+			chunkType = parser.ChunkTypeFunction
+			name = strings.TrimSpace(strings.Split(matches[1], ",")[0])
+			signature = strings.TrimSpace(matches[2])
+		} else if matches := haskellBindingRegex.FindStringSubmatch(line); matches != nil && !haskellKeywords[matches[1]] {
+			chunkType = parser.ChunkTypeFunction
+			name = matches[1]
+		} else {
+			continue
+		}
+
+		if name == "" {
+			continue
+		}
+
+		// A top-level item continues over every line indented past column 0
+		end := indentedEnd(lines, i)
+		if chunkType == parser.ChunkTypeFunction {
+			// Pull in the following equations of the same binding
+			for next := haskellNextItem(lines, end); next >= 0 && haskellBindingName(lines[next]) == name; next = haskellNextItem(lines, end) {
+				end = indentedEnd(lines, next)
+			}
+		}
+
+		start := haskellDocStart(lines, i)
+		chunk := newLineChunk(filePath, "haskell", lines, start, end, string(chunkType), strings.Trim(name, "()"))
+
+		if module != "" {
+			chunk.Metadata["module"] = module
+		}
+		if kind != "" {
+			chunk.Metadata["kind"] = kind
+		}
+		if signature != "" {
+			chunk.Metadata["signature"] = signature
+		}
+		if doc := haskellDocComment(lines[start:i]); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		i = end
+	}
+
+	return chunks
+}
+
+// haskellNextItem returns the index of the next column-0 line after end if only
+// blank lines come between, or -1 when a comment or the end of file intervenes
+func haskellNextItem(lines []string, end int) int {
+	for i := end + 1; i < len(lines); i++ {
+		if strings.TrimSpace(lines[i]) == "" {
+			continue
+		}
+		if strings.HasPrefix(lines[i], "--") || strings.HasPrefix(lines[i], "{-") || indentWidth(lines[i]) > 0 {
+			return -1
+		}
+		return i
+	}
+	return -1
+}
+
+// haskellBindingName returns the name an equation defines, or "" for type
+// signatures and declarations
+func haskellBindingName(line string) string {
+	if haskellSignatureRegex.MatchString(line) || haskellTypeRegex.MatchString(line) {
+		return ""
+	}
+	matches := haskellBindingRegex.FindStringSubmatch(line)
+	if matches == nil || haskellKeywords[matches[1]] {
+		return ""
+	}
+	return matches[1]
+}
+
+// haskellDocStart returns the first line of the Haddock comment directly above
+// a declaration, either "-- |" line comments or a "{- | ... -}" block
+func haskellDocStart(lines []string, decl int) int {
+	start := decl
+	for start > 0 {
+		prev := lines[start-1]
+		switch {
+		case strings.HasPrefix(prev, "--"):
+			start--
+		case strings.HasSuffix(strings.TrimSpace(prev), "-}"):
+			// Walk back to the opening of the block comment
+			open := start - 1
+			for open > 0 && !strings.HasPrefix(lines[open], "{-") {
+				open--
+			}
+			if !strings.HasPrefix(lines[open], "{-") || strings.HasPrefix(lines[open], "{-#") {
+				return start
+			}
+			start = open
+		default:
+			return start
+		}
+	}
+	return start
+}
+
+// haskellDocComment returns Haddock comment text with the comment markers
+// (--, {-, -}) and the Haddock "|" and "^" markers removed
+func haskellDocComment(lines []string) string {
+	var doc []string
+	for _, line := range lines {
+		text := strings.TrimSpace(line)
+		text = strings.TrimPrefix(text, "{-")
+		text = strings.TrimSuffix(text, "-}")
+		text = strings.TrimLeft(text, "-")
+		text = strings.TrimSpace(text)
+		text = strings.TrimPrefix(text, "|")
+		text = strings.TrimPrefix(text, "^")
+		if text = strings.TrimSpace(text); text != "" {
+			doc = append(doc, text)
+		}
+	}
+	return strings.Join(doc, "\n")
+}
@@ -0,0 +1,74 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkHaskell(t *testing.T) {
+	source := `{-# LANGUAGE ScopedTypeVariables #-}
+module Data.Queue
+  ( Queue
+  , push
+  ) where
+
+import Data.List (foldl')
+
+-- | A FIFO queue.
+data Queue a = Queue [a] [a]
+  deriving (Show)
+
+newtype Size = Size Int
+
+class Container f where
+  empty :: f a
+
+-- | Push an element.
+push :: a -> Queue a -> Queue a
+push x (Queue f b) = Queue f (x : b)
+
+-- | Length of a queue.
+size :: Queue a -> Int
+size (Queue [] []) = 0
+size (Queue f b) =
+  length f + length b
+
+main = print (size (push 1 (Queue [] [])))
+`
+
+	chunks := chunkHaskell("Queue.hs", []byte(source))
+	require.Len(t, chunks, 6)
+
+	queue := chunks[0]
+	assert.Equal(t, "Queue", queue.Name)
+	assert.Equal(t, "struct", queue.ChunkType)
+	assert.Equal(t, "data", queue.Metadata["kind"])
+	assert.Equal(t, "Data.Queue", queue.Metadata["module"])
+	assert.Equal(t, "A FIFO queue.", queue.Metadata["doc_comment"])
+	assert.Equal(t, 9, queue.LineStart, "chunk should start at the Haddock comment")
+	assert.Equal(t, 11, queue.LineEnd)
+
+	assert.Equal(t, "Size", chunks[1].Name)
+	assert.Equal(t, "newtype", chunks[1].Metadata["kind"])
+
+	assert.Equal(t, "Container", chunks[2].Name)
+	assert.Equal(t, "interface", chunks[2].ChunkType)
+	assert.Equal(t, 16, chunks[2].LineEnd)
+
+	push := chunks[3]
+	assert.Equal(t, "push", push.Name)
+	assert.Equal(t, "function", push.ChunkType)
+	assert.Equal(t, "a -> Queue a -> Queue a", push.Metadata["signature"])
+	assert.Equal(t, 18, push.LineStart)
+	assert.Equal(t, 20, push.LineEnd)
+
+	size := chunks[4]
+	assert.Equal(t, "size", size.Name)
+	assert.Equal(t, 22, size.LineStart)
+	assert.Equal(t, 26, size.LineEnd, "all equations belong to one chunk")
+
+	assert.Equal(t, "main", chunks[5].Name)
+	assert.Empty(t, chunks[5].Metadata["signature"])
+}
@@ -0,0 +1,48 @@
+package chunker
+
+import (
+	"strconv"
+	"strings"
+
+	"github.com/google/uuid"
+)
+
+// chunkIDNamespace is the UUID namespace chunk IDs are derived in
+var chunkIDNamespace = uuid.MustParse("6f1d3c2e-8a4b-5e7f-9c0d-2b6a4e8f1c3d")
+
+// assignIDs gives each chunk an ID derived from its file, its symbol, and its
+// code with whitespace collapsed, rather than from its position, so a chunk
+// keeps its ID when it moves within its file or is reformatted. Identical
+// chunks of a file are told apart by their order.
+func assignIDs(chunks []Chunk) {
+	assignIDsFrom(chunks, make(map[string]int))
+}
+
+// assignIDsFrom assigns IDs counting identical chunks in occurrences, so the
+// chunks of one file can be given IDs a batch at a time
+func assignIDsFrom(chunks []Chunk, occurrences map[string]int) {
+	for i := range chunks {
+		base := chunkKey(chunks[i])
+		key := base
+		if n := occurrences[base]; n > 0 {
+			key += "\x00" + strconv.Itoa(n)
+		}
+		occurrences[base]++
+		chunks[i].ID = uuid.NewSHA1(chunkIDNamespace, []byte(key)).String()
+	}
+}
+
+// chunkKey identifies a chunk by what it is rather than where it is
+func chunkKey(chunk Chunk) string {
+	symbol := chunk.Metadata["qualified_name"]
+	if symbol == "" {
+		symbol = chunk.Name
+	}
+	code := strings.Join(strings.Fields(chunk.Code), " ")
+	return strings.Join([]string{chunk.FilePath, chunk.ChunkType, symbol, code}, "\x00")
+}
+
+// partID derives the ID of one part of a split chunk from the chunk's ID
+func partID(parentID string, part int) string {
+	return uuid.NewSHA1(chunkIDNamespace, []byte(parentID+"\x00part\x00"+strconv.Itoa(part))).String()
+}
@@ -0,0 +1,68 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func chunkIDs(t *testing.T, name, source string) map[string]string {
+	t.Helper()
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(writeSource(t, name, source), "go")
+	require.NoError(t, err)
+
+	ids := make(map[string]string)
+	for _, chunk := range chunks {
+		ids[chunk.Name] = chunk.ID
+	}
+	return ids
+}
+
+func TestChunkIDsSurviveMovesAndWhitespace(t *testing.T) {
+	original := chunkIDs(t, "main.go", `package main
+
+func add(a, b int) int {
+	return a + b
+}
+
+func main() {}
+`)
+	// add moves below main and is reformatted
+	moved := chunkIDs(t, "main.go", `package main
+
+func main() {}
+
+func add(a, b int) int {
+		return a +  b
+}
+`)
+
+	require.NotEmpty(t, original["add"])
+	assert.Equal(t, original["add"], moved["add"])
+	assert.Equal(t, original["main"], moved["main"])
+	assert.NotEqual(t, original["add"], original["main"])
+}
+
+func TestChunkIDsChangeWithContent(t *testing.T) {
+	before := chunkIDs(t, "main.go", "package main\n\nfunc add(a, b int) int { return a + b }\n")
+	after := chunkIDs(t, "main.go", "package main\n\nfunc add(a, b int) int { return a - b }\n")
+	assert.NotEqual(t, before["add"], after["add"])
+}
+
+func TestAssignIDsDistinguishesDuplicates(t *testing.T) {
+	chunks := []Chunk{
+		{FilePath: "a.sql", ChunkType: "block", Code: "SELECT 1;"},
+		{FilePath: "a.sql", ChunkType: "block", Code: "SELECT 1;"},
+		{FilePath: "b.sql", ChunkType: "block", Code: "SELECT 1;"},
+	}
+	assignIDs(chunks)
+	assert.NotEqual(t, chunks[0].ID, chunks[1].ID)
+	assert.NotEqual(t, chunks[0].ID, chunks[2].ID)
+
+	again := []Chunk{chunks[0], chunks[1], chunks[2]}
+	assignIDs(again)
+	assert.Equal(t, chunks[1].ID, again[1].ID)
+}
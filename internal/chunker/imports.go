@@ -0,0 +1,56 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+)
+
+// identifierRegex matches the identifiers in code and import statements
+var identifierRegex = regexp.MustCompile(`[A-Za-z_][A-Za-z0-9_]*`)
+
+// importKeywords are the words of import statements that are not imported names
+var importKeywords = map[string]bool{
+	"use": true, "pub": true, "crate": true, "self": true, "super": true,
+	"import": true, "from": true, "as": true, "type": true, "typeof": true,
+	"static": true, "package": true, "namespace": true, "function": true,
+	"const": true, "include": true, "using": true, "require": true,
+}
+
+// RelevantImports returns the import statements visible to a chunk that name
+// something the chunk's code refers to
+func (c Chunk) RelevantImports() []string {
+	statements := c.Metadata["import_statements"]
+	if statements == "" {
+		return nil
+	}
+
+	words := make(map[string]bool)
+	for _, word := range identifierRegex.FindAllString(c.Code, -1) {
+		words[word] = true
+	}
+
+	var relevant []string
+	for _, statement := range strings.Split(statements, "\n") {
+		for _, name := range identifierRegex.FindAllString(statement, -1) {
+			if !importKeywords[name] && words[name] {
+				relevant = append(relevant, statement)
+				break
+			}
+		}
+	}
+	return relevant
+}
+
+// EmbeddingText returns the text embedded for a chunk: its code, prefixed with
+// its relevant imports when withImports is set so the embedding knows where
+// the external types it uses come from
+func (c Chunk) EmbeddingText(withImports bool) string {
+	if !withImports {
+		return c.Code
+	}
+	imports := c.RelevantImports()
+	if len(imports) == 0 {
+		return c.Code
+	}
+	return strings.Join(imports, "\n") + "\n\n" + c.Code
+}
@@ -0,0 +1,26 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestRelevantImports(t *testing.T) {
+	chunk := Chunk{
+		Code: "pub fn count(words: &[&str]) -> HashMap<String, usize> {\n    HashMap::new()\n}",
+		Metadata: map[string]string{
+			"import_statements": "use std::collections::HashMap;\nuse std::fmt;\nuse super::*;",
+		},
+	}
+	assert.Equal(t, []string{"use std::collections::HashMap;"}, chunk.RelevantImports())
+
+	assert.Equal(t, chunk.Code, chunk.EmbeddingText(false))
+	assert.Equal(t, "use std::collections::HashMap;\n\n"+chunk.Code, chunk.EmbeddingText(true))
+}
+
+func TestEmbeddingTextWithoutImports(t *testing.T) {
+	chunk := Chunk{Code: "fn main() {}"}
+	assert.Nil(t, chunk.RelevantImports())
+	assert.Equal(t, chunk.Code, chunk.EmbeddingText(true))
+}
@@ -0,0 +1,132 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+const (
+	kotlinAnnotations = `^\s*((?:@[\w.:]+(?:\([^)]*\))?\s+)*)`
+	kotlinModifiers   = `((?:(?:public|private|protected|internal|open|abstract|final|sealed|data|inner|enum|annotation|value|inline|override|suspend|operator|infix|tailrec|external|expect|actual)\s+)*)`
+)
+
+var (
+	// Matches classes, interfaces, objects, and companion objects
+	kotlinTypeRegex = regexp.MustCompile(kotlinAnnotations + kotlinModifiers + `(class|interface|fun\s+interface|companion\s+object|object)\b\s*(\w+)?`)
+	// Matches functions, including extension functions: fun <T> List<T>.second(): T
+	kotlinFunRegex = regexp.MustCompile(kotlinAnnotations + kotlinModifiers + `fun\s+(?:<[^>]*>\s*)?(?:([\w.<>?, *]+?)\.)?(\w+)\s*\(`)
+	// Matches a line holding only an annotation, e.g. @Composable or @GetMapping("/users")
+	kotlinAnnotationLineRegex = regexp.MustCompile(`^@[\w.:]+(?:\(.*\))?$`)
+	// Matches one annotation written before a declaration on its line
+	kotlinAnnotationRegex = regexp.MustCompile(`@[\w.:]+(?:\([^)]*\))?`)
+)
+
+// chunkKotlin extracts classes, data classes, objects, companion objects,
+// interfaces, and functions from Kotlin source. Extension functions record
+// the type they extend, and KDoc above a declaration is kept as its doc comment.
+func chunkKotlin(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type typeScope struct {
+		name string
+		end  int
+	}
+	var (
+		chunks []Chunk
+		types  []typeScope
+	)
+
+	for i := 0; i < len(lines); i++ {
+		for len(types) > 0 && i > types[len(types)-1].end {
+			types = types[:len(types)-1]
+		}
+
+		var (
+			chunkType parser.ChunkType
+			name      string
+			modifiers string
+			inline    string
+			extends   string
+			isType    bool
+		)
+
+		line := lines[i]
+		if matches := kotlinTypeRegex.FindStringSubmatch(line); matches != nil {
+			inline, modifiers = matches[1], matches[2]
+			keyword := strings.Join(strings.Fields(matches[3]), " ")
+			name = matches[4]
+			if name == "" && keyword == "companion object" {
+				name = "Companion"
+			}
+			isType = true
+			switch {
+			case strings.Contains(keyword, "interface"):
+				chunkType = parser.ChunkTypeInterface
+			case strings.Contains(modifiers, "enum"):
+				chunkType = parser.ChunkTypeEnum
+			default:
+				chunkType = parser.ChunkTypeClass
+			}
+			if keyword != "class" {
+				modifiers += keyword
+			}
+		} else if matches := kotlinFunRegex.FindStringSubmatch(line); matches != nil {
+			inline, modifiers, extends, name = matches[1], matches[2], strings.TrimSpace(matches[3]), matches[4]
+			chunkType = parser.ChunkTypeFunction
+			if len(types) > 0 {
+				chunkType = parser.ChunkTypeMethod
+			}
+		} else {
+			continue
+		}
+
+		if name == "" {
+			continue
+		}
+
+		start := extendAbove(lines, i, kotlinAnnotationLineRegex.MatchString)
+		end := cStyle.declarationEnd(lines, i)
+		chunk := newLineChunk(filePath, "kotlin", lines, start, end, string(chunkType), name)
+
+		if kind := strings.TrimSpace(modifiers); kind != "" {
+			chunk.Metadata["modifiers"] = strings.Join(strings.Fields(kind), " ")
+		}
+		if len(types) > 0 {
+			chunk.Metadata["receiver"] = types[len(types)-1].name
+		}
+		if extends != "" {
+			chunk.Metadata["extension_receiver"] = extends
+		}
+		if doc := cStyle.leadingComment(lines, start); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		if annotations := kotlinAnnotationList(lines[start:i], inline); annotations != "" {
+			chunk.Metadata["attributes"] = annotations
+		}
+		chunks = append(chunks, chunk)
+
+		// Types contain members, so keep scanning inside them; skip function bodies
+		if isType {
+			types = append(types, typeScope{name: name, end: end})
+		} else {
+			i = end
+		}
+	}
+
+	return chunks
+}
+
+// kotlinAnnotationList returns the annotations on the lines above a
+// declaration and before it on its own line, e.g. "@Composable, @Preview"
+func kotlinAnnotationList(lines []string, inline string) string {
+	var annotations []string
+	for _, line := range lines {
+		if trimmed := strings.TrimSpace(line); kotlinAnnotationLineRegex.MatchString(trimmed) {
+			annotations = append(annotations, trimmed)
+		}
+	}
+	annotations = append(annotations, kotlinAnnotationRegex.FindAllString(inline, -1)...)
+	return strings.Join(annotations, ", ")
+}
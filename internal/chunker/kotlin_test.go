@@ -0,0 +1,80 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkKotlin(t *testing.T) {
+	source := `package com.example
+
+/**
+ * A point in 2D space.
+ */
+data class Point(val x: Int, val y: Int)
+
+class Repository(private val db: Database) {
+    fun find(id: Int): User? {
+        return db.query(id)
+    }
+
+    companion object {
+        fun create(): Repository = Repository(Database())
+    }
+}
+
+object Registry {
+    val items = mutableListOf<String>()
+}
+
+/** Shouts the string. */
+fun String.shout(): String = uppercase() + "!"
+
+@Composable
+fun Greeting(name: String) {
+    Text("Hello $name")
+}
+`
+
+	chunks := chunkKotlin("Repository.kt", []byte(source))
+	require.Len(t, chunks, 8)
+
+	byName := make(map[string]Chunk)
+	for _, chunk := range chunks {
+		byName[chunk.Name] = chunk
+	}
+
+	point := byName["Point"]
+	assert.Equal(t, "class", point.ChunkType)
+	assert.Equal(t, "data", point.Metadata["modifiers"])
+	assert.Equal(t, "A point in 2D space.", point.Metadata["doc_comment"])
+	assert.Equal(t, 6, point.LineEnd)
+
+	repo := byName["Repository"]
+	assert.Equal(t, 8, repo.LineStart)
+	assert.Equal(t, 16, repo.LineEnd)
+
+	assert.Equal(t, "method", byName["find"].ChunkType)
+	assert.Equal(t, "Repository", byName["find"].Metadata["receiver"])
+
+	companion := byName["Companion"]
+	assert.Equal(t, "companion object", companion.Metadata["modifiers"])
+	assert.Equal(t, "Repository", companion.Metadata["receiver"])
+	assert.Equal(t, "Companion", byName["create"].Metadata["receiver"])
+	assert.Equal(t, 14, byName["create"].LineEnd)
+
+	assert.Equal(t, "object", byName["Registry"].Metadata["modifiers"])
+
+	shout := byName["shout"]
+	assert.Equal(t, "function", shout.ChunkType)
+	assert.Equal(t, "String", shout.Metadata["extension_receiver"])
+	assert.Equal(t, "Shouts the string.", shout.Metadata["doc_comment"])
+	assert.Equal(t, 23, shout.LineEnd)
+
+	greeting := byName["Greeting"]
+	assert.Equal(t, 25, greeting.LineStart, "chunk should start at the annotation")
+	assert.Equal(t, 28, greeting.LineEnd)
+	assert.Equal(t, "@Composable", greeting.Metadata["attributes"])
+}
@@ -0,0 +1,90 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches function statements: function M.new(, local function helper(, function Account:deposit(
+	luaFunctionRegex = regexp.MustCompile(`^\s*(local\s+)?function\s+([\w.:]+)\s*\(`)
+	// Matches functions assigned to a name: local handler = function(, M.setup = function(
+	luaAssignedFunctionRegex = regexp.MustCompile(`^\s*(local\s+)?([\w.]+)\s*=\s*function\s*\(`)
+	// Matches keywords that open or close a block
+	luaBlockRegex = regexp.MustCompile(`\b(function|if|do|repeat|end|until)\b`)
+
+	// Lua comments start with --; LuaLS annotations use ---
+	luaStyle = commentStyle{line: []string{"--"}}
+)
+
+// chunkLua extracts functions from Lua source: global and local functions and
+// functions defined on tables (function M.setup, function Account:deposit),
+// which take the table as their receiver.
+func chunkLua(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var chunks []Chunk
+	for i := 0; i < len(lines); i++ {
+		matches := luaFunctionRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			matches = luaAssignedFunctionRegex.FindStringSubmatch(lines[i])
+		}
+		if matches == nil {
+			continue
+		}
+
+		name := matches[2]
+		receiver := ""
+		chunkType := parser.ChunkTypeFunction
+		if idx := strings.LastIndexAny(name, ".:"); idx >= 0 {
+			receiver, name = name[:idx], name[idx+1:]
+			chunkType = parser.ChunkTypeMethod
+		}
+
+		end := luaBlockEnd(lines, i)
+		chunk := newLineChunk(filePath, "lua", lines, i, end, string(chunkType), name)
+		if matches[1] != "" {
+			chunk.Metadata["local"] = "true"
+		}
+		if receiver != "" {
+			chunk.Metadata["receiver"] = receiver
+		}
+		if doc := luaStyle.leadingComment(lines, i); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		// Nested functions stay part of the enclosing chunk
+		i = end
+	}
+
+	return chunks
+}
+
+// luaBlockEnd returns the line holding the `end` that closes the function
+// opened on lines[start], ignoring keywords inside strings and comments
+func luaBlockEnd(lines []string, start int) int {
+	depth := 0
+	for i := start; i < len(lines); i++ {
+		code := stripStringsAndComments(lines[i], 0)
+		if idx := strings.Index(code, "--"); idx >= 0 {
+			code = code[:idx]
+		}
+
+		for _, match := range luaBlockRegex.FindAllStringSubmatch(code, -1) {
+			switch match[1] {
+			case "end", "until":
+				depth--
+			default:
+				depth++
+			}
+		}
+
+		if depth <= 0 {
+			return i
+		}
+	}
+	return len(lines) - 1
+}
@@ -0,0 +1,69 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkLua(t *testing.T) {
+	source := `local M = {}
+
+--- Configures the plugin.
+-- @param opts table
+function M.setup(opts)
+  for k, v in pairs(opts) do
+    if v then
+      M[k] = v
+    end
+  end
+end
+
+local function trim(s) return (s:gsub("^%s+", "")) end
+
+function Account:deposit(amount)
+  local log = function(msg)
+    print("end: " .. msg)
+  end
+  self.balance = self.balance + amount
+end
+
+M.reset = function()
+  repeat
+    M.count = M.count - 1
+  until M.count == 0
+end
+
+return M
+`
+
+	chunks := chunkLua("plugin.lua", []byte(source))
+	require.Len(t, chunks, 4)
+
+	setup := chunks[0]
+	assert.Equal(t, "setup", setup.Name)
+	assert.Equal(t, "method", setup.ChunkType)
+	assert.Equal(t, "M", setup.Metadata["receiver"])
+	assert.Equal(t, "Configures the plugin.\n@param opts table", setup.Metadata["doc_comment"])
+	assert.Equal(t, 5, setup.LineStart)
+	assert.Equal(t, 11, setup.LineEnd)
+
+	trim := chunks[1]
+	assert.Equal(t, "trim", trim.Name)
+	assert.Equal(t, "function", trim.ChunkType)
+	assert.Equal(t, "true", trim.Metadata["local"])
+	assert.Equal(t, 13, trim.LineEnd)
+
+	deposit := chunks[2]
+	assert.Equal(t, "deposit", deposit.Name)
+	assert.Equal(t, "Account", deposit.Metadata["receiver"])
+	assert.Equal(t, 15, deposit.LineStart)
+	assert.Equal(t, 20, deposit.LineEnd)
+
+	reset := chunks[3]
+	assert.Equal(t, "reset", reset.Name)
+	assert.Equal(t, "M", reset.Metadata["receiver"])
+	assert.Equal(t, 22, reset.LineStart)
+	assert.Equal(t, 26, reset.LineEnd)
+}
@@ -14,8 +14,18 @@ import (
 var (
 	// Matches markdown headers: # Header, ## Header, ### Header
 	headerRegex = regexp.MustCompile(`^(#{1,6})\s+(.+)$`)
+	// Matches the optional closing sequence of an ATX header: ## Header ##
+	closingHashesRegex = regexp.MustCompile(`\s+#+\s*$`)
+	// Matches the opening or closing fence of a code block: ``` or ~~~
+	fenceRegex = regexp.MustCompile("^\\s{0,3}(```+|~~~+)")
 )
 
+// section is an open section in the heading hierarchy
+type section struct {
+	level int
+	title string
+}
+
 // MarkdownChunker chunks markdown files by headers
 type MarkdownChunker struct{}
 
@@ -24,7 +34,8 @@ func NewMarkdownChunker() *MarkdownChunker {
 	return &MarkdownChunker{}
 }
 
-// ChunkMarkdown splits a markdown file into sections based on headers (H1-H3)
+// ChunkMarkdown splits a markdown file into sections based on headers (H1-H6).
+// Fenced code blocks stay in their section; # lines inside them are not headers.
 func (mc *MarkdownChunker) ChunkMarkdown(filePath string) ([]Chunk, error) {
 	file, err := os.Open(filePath)
 	if err != nil {
@@ -35,64 +46,55 @@ func (mc *MarkdownChunker) ChunkMarkdown(filePath string) ([]Chunk, error) {
 	var chunks []Chunk
 	var currentLines []string
 	var chunkStartLine int = 1
-	var currentHeading string
-	var currentLevel int
-	var parentHeadings []string // Stack of parent headings for context
+	var sections []section // Open sections, outermost first
+	var fence string       // Fence of the code block being read, if any
 	lineNum := 1
 
 	scanner := bufio.NewScanner(file)
 	for scanner.Scan() {
 		line := scanner.Text()
 
+		// Lines inside code blocks belong to the enclosing section, even when
+		// they look like headers (e.g. shell comments)
+		if matches := fenceRegex.FindStringSubmatch(line); matches != nil {
+			if fence == "" {
+				fence = matches[1]
+			} else if strings.HasPrefix(matches[1], fence) && strings.TrimSpace(line) == matches[1] {
+				fence = ""
+			}
+		}
+
 		// Check if this line is a header
-		if matches := headerRegex.FindStringSubmatch(line); matches != nil {
+		matches := headerRegex.FindStringSubmatch(line)
+		if matches != nil && fence == "" {
 			headerLevel := len(matches[1]) // Count the #'s
-			headerText := strings.TrimSpace(matches[2])
+			headerText := strings.TrimSpace(closingHashesRegex.ReplaceAllString(matches[2], ""))
 
 			// If we have accumulated content, create a chunk for it
 			if len(currentLines) > 0 {
-				chunk := mc.createChunk(filePath, chunkStartLine, lineNum-1, currentLines, currentHeading, currentLevel, parentHeadings)
+				chunk := mc.createChunk(filePath, chunkStartLine, lineNum-1, currentLines, sections)
 				chunks = append(chunks, chunk)
 				currentLines = nil
 			}
 
-			// Update parent heading stack based on level
-			// If we're at level 1, clear the stack
-			// If we're deeper, maintain parent context
-			if headerLevel == 1 {
-				parentHeadings = nil
-			} else if headerLevel > currentLevel {
-				// Going deeper - add current heading as parent
-				if currentHeading != "" {
-					parentHeadings = append(parentHeadings, currentHeading)
-				}
-			} else if headerLevel <= currentLevel {
-				// Same level or going up - pop parent headings to match level
-				targetParents := headerLevel - 2
-				if targetParents < 0 {
-					targetParents = 0
-				}
-				if len(parentHeadings) > targetParents {
-					parentHeadings = parentHeadings[:targetParents]
-				}
+			// Close sections at the same or a deeper level, then open this one
+			for len(sections) > 0 && sections[len(sections)-1].level >= headerLevel {
+				sections = sections[:len(sections)-1]
 			}
+			sections = append(sections, section{level: headerLevel, title: headerText})
 
 			// Start new section
-			currentHeading = headerText
-			currentLevel = headerLevel
 			chunkStartLine = lineNum
-			currentLines = append(currentLines, line)
-		} else {
-			// Add line to current section
-			currentLines = append(currentLines, line)
 		}
 
+		// Add line to current section
+		currentLines = append(currentLines, line)
 		lineNum++
 	}
 
 	// Create chunk for remaining content
 	if len(currentLines) > 0 {
-		chunk := mc.createChunk(filePath, chunkStartLine, lineNum-1, currentLines, currentHeading, currentLevel, parentHeadings)
+		chunk := mc.createChunk(filePath, chunkStartLine, lineNum-1, currentLines, sections)
 		chunks = append(chunks, chunk)
 	}
 
@@ -112,17 +114,26 @@ func (mc *MarkdownChunker) ChunkMarkdown(filePath string) ([]Chunk, error) {
 	return chunks, nil
 }
 
-// createChunk creates a chunk with appropriate metadata
-func (mc *MarkdownChunker) createChunk(filePath string, startLine, endLine int, lines []string, heading string, level int, parents []string) Chunk {
+// createChunk creates a chunk with appropriate metadata. The "symbol" metadata
+// holds the full heading path, e.g. "Setup > Database > Migrations".
+func (mc *MarkdownChunker) createChunk(filePath string, startLine, endLine int, lines []string, sections []section) Chunk {
 	metadata := make(map[string]string)
 
-	if heading != "" {
+	var heading string
+	if len(sections) > 0 {
+		current := sections[len(sections)-1]
+		heading = current.title
 		metadata["heading"] = heading
-		metadata["heading_level"] = fmt.Sprintf("%d", level)
-	}
+		metadata["heading_level"] = fmt.Sprintf("%d", current.level)
 
-	if len(parents) > 0 {
-		metadata["parent_heading"] = strings.Join(parents, " > ")
+		path := make([]string, len(sections))
+		for i, s := range sections {
+			path[i] = s.title
+		}
+		metadata["symbol"] = strings.Join(path, " > ")
+		if len(path) > 1 {
+			metadata["parent_heading"] = strings.Join(path[:len(path)-1], " > ")
+		}
 	}
 
 	chunkType := "section"
@@ -3,6 +3,7 @@ package chunker
 import (
 	"os"
 	"path/filepath"
+	"strings"
 	"testing"
 )
 
@@ -169,3 +170,53 @@ New chapter.
 		}
 	}
 }
+
+func TestMarkdownChunker_HeadingPath(t *testing.T) {
+	tmpDir := t.TempDir()
+	mdFile := filepath.Join(tmpDir, "setup.md")
+
+	content := "# Setup\n" +
+		"\n" +
+		"## Database\n" +
+		"\n" +
+		"### Migrations\n" +
+		"\n" +
+		"```bash\n" +
+		"# apply all pending migrations\n" +
+		"make migrate\n" +
+		"```\n" +
+		"\n" +
+		"## Cache ##\n" +
+		"\n" +
+		"Redis settings.\n"
+
+	if err := os.WriteFile(mdFile, []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	chunker := NewMarkdownChunker()
+	chunks, err := chunker.ChunkMarkdown(mdFile)
+	if err != nil {
+		t.Fatalf("ChunkMarkdown failed: %v", err)
+	}
+
+	if len(chunks) != 4 {
+		t.Fatalf("Expected 4 chunks, got %d", len(chunks))
+	}
+
+	migrations := chunks[2]
+	if migrations.Metadata["symbol"] != "Setup > Database > Migrations" {
+		t.Errorf("Expected heading path 'Setup > Database > Migrations', got '%s'", migrations.Metadata["symbol"])
+	}
+	if migrations.Metadata["parent_heading"] != "Setup > Database" {
+		t.Errorf("Expected parent heading 'Setup > Database', got '%s'", migrations.Metadata["parent_heading"])
+	}
+	if !strings.Contains(migrations.Code, "make migrate") || migrations.LineEnd != 11 {
+		t.Errorf("Code block should stay in the Migrations section, got lines %d-%d", migrations.LineStart, migrations.LineEnd)
+	}
+
+	cache := chunks[3]
+	if cache.Name != "Cache" || cache.Metadata["symbol"] != "Setup > Cache" {
+		t.Errorf("Expected 'Cache' with path 'Setup > Cache', got '%s' with path '%s'", cache.Name, cache.Metadata["symbol"])
+	}
+}
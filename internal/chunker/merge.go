@@ -0,0 +1,105 @@
+package chunker
+
+import (
+	"strconv"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+// mergeSmallChunks merges runs of consecutive small sibling chunks, such as
+// one-line getters of the same type or a list of constants, into single chunks
+// of up to MergeTokens tokens. Metadata shared by every merged item is kept,
+// merged_names lists the items, and merged counts them.
+func (s *SemanticChunker) mergeSmallChunks(chunks []Chunk) []Chunk {
+	if s.options.MergeTokens <= 0 {
+		return chunks
+	}
+	tok := s.options.CodeLimit.Tokenizer
+	if tok == nil {
+		tok = tokenizer.Approximate{}
+	}
+
+	merged := make([]Chunk, 0, len(chunks))
+	for i := 0; i < len(chunks); {
+		run := []Chunk{chunks[i]}
+		tokens := tok.Count(chunks[i].Code)
+		for next := i + 1; next < len(chunks) && tokens < s.options.MergeTokens; next++ {
+			if !areSiblings(run[len(run)-1], chunks[next]) {
+				break
+			}
+			// The joining newline counts too
+			cost := tok.Count(chunks[next].Code) + tok.Count("\n")
+			if tokens+cost > s.options.MergeTokens {
+				break
+			}
+			run = append(run, chunks[next])
+			tokens += cost
+		}
+
+		if len(run) == 1 {
+			merged = append(merged, run[0])
+		} else {
+			merged = append(merged, mergeChunks(run))
+		}
+		i += len(run)
+	}
+	return merged
+}
+
+// areSiblings reports whether next directly follows prev as an item of the
+// same kind and with the same enclosing type, so they can be merged
+func areSiblings(prev, next Chunk) bool {
+	if prev.FilePath != next.FilePath || prev.EmbeddingType != "code" || next.EmbeddingType != "code" {
+		return false
+	}
+	if prev.ChunkType != next.ChunkType || next.LineStart <= prev.LineEnd {
+		return false
+	}
+	return prev.Metadata["receiver"] == next.Metadata["receiver"] &&
+		prev.Metadata["parent_id"] == next.Metadata["parent_id"]
+}
+
+// mergeChunks combines a run of sibling chunks into one
+func mergeChunks(run []Chunk) Chunk {
+	first, last := run[0], run[len(run)-1]
+
+	names := make([]string, 0, len(run))
+	codes := make([]string, 0, len(run))
+	for _, chunk := range run {
+		if chunk.Name != "" {
+			names = append(names, chunk.Name)
+		}
+		codes = append(codes, chunk.Code)
+	}
+
+	metadata := make(map[string]string)
+	for key, value := range first.Metadata {
+		shared := true
+		for _, chunk := range run[1:] {
+			if chunk.Metadata[key] != value {
+				shared = false
+				break
+			}
+		}
+		if shared {
+			metadata[key] = value
+		}
+	}
+	metadata["merged_names"] = strings.Join(names, ", ")
+	metadata["merged"] = strconv.Itoa(len(run))
+
+	return Chunk{
+		ID:            uuid.New().String(),
+		FilePath:      first.FilePath,
+		LineStart:     first.LineStart,
+		LineEnd:       last.LineEnd,
+		Language:      first.Language,
+		Code:          strings.Join(codes, "\n"),
+		ChunkType:     first.ChunkType,
+		Name:          strings.Join(names, ", "),
+		Metadata:      metadata,
+		EmbeddingType: first.EmbeddingType,
+	}
+}
@@ -0,0 +1,70 @@
+package chunker
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+const mergeSource = `pub const Point = struct {
+    x: i32,
+    y: i32,
+
+    pub fn getX(self: Point) i32 { return self.x; }
+    pub fn getY(self: Point) i32 { return self.y; }
+    pub fn zero(self: Point) i32 { _ = self; return 0; }
+};
+
+pub fn main() void {}
+`
+
+func chunkWithMerge(t *testing.T, mergeTokens int) []Chunk {
+	t.Helper()
+	zigFile := filepath.Join(t.TempDir(), "point.zig")
+	require.NoError(t, os.WriteFile(zigFile, []byte(mergeSource), 0644))
+
+	semantic, err := NewSemanticWithOptions(Options{MergeTokens: mergeTokens})
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(zigFile, "zig")
+	require.NoError(t, err)
+	return chunks
+}
+
+func TestMergeSmallChunks(t *testing.T) {
+	chunks := chunkWithMerge(t, 100)
+	require.Len(t, chunks, 3)
+
+	assert.Equal(t, "Point", chunks[0].Name)
+
+	getters := chunks[1]
+	assert.Equal(t, "getX, getY, zero", getters.Name)
+	assert.Equal(t, "method", getters.ChunkType)
+	assert.Equal(t, 5, getters.LineStart)
+	assert.Equal(t, 7, getters.LineEnd)
+	assert.Equal(t, "3", getters.Metadata["merged"])
+	assert.Equal(t, "getX, getY, zero", getters.Metadata["merged_names"])
+	assert.Equal(t, "Point", getters.Metadata["receiver"])
+	assert.Contains(t, getters.Code, "return self.x;")
+	assert.Contains(t, getters.Code, "return 0;")
+
+	// A top-level function is not a sibling of the methods before it
+	assert.Equal(t, "main", chunks[2].Name)
+	assert.NotContains(t, chunks[2].Metadata, "merged")
+}
+
+func TestMergeStopsAtThreshold(t *testing.T) {
+	// Each getter is about 13 tokens, so only two fit in 30
+	chunks := chunkWithMerge(t, 30)
+	require.Len(t, chunks, 4)
+
+	assert.Equal(t, "getX, getY", chunks[1].Name)
+	assert.Equal(t, "zero", chunks[2].Name)
+}
+
+func TestMergeDisabled(t *testing.T) {
+	chunks := chunkWithMerge(t, 0)
+	assert.Len(t, chunks, 5)
+}
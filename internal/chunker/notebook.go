@@ -0,0 +1,153 @@
+package chunker
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/google/uuid"
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+// notebookCell is a cell of a Jupyter notebook
+type notebookCell struct {
+	CellType string          `json:"cell_type"`
+	Source   json.RawMessage `json:"source"` // A string or a list of lines
+}
+
+// notebookMetadata holds the notebook fields that name its kernel language
+type notebookMetadata struct {
+	KernelSpec struct {
+		Language string `json:"language"`
+	} `json:"kernelspec"`
+	LanguageInfo struct {
+		Name string `json:"name"`
+	} `json:"language_info"`
+}
+
+// chunkNotebook extracts one chunk per code cell of a Jupyter notebook. The
+// markdown cells directly before a code cell are recorded as its "context"
+// metadata, and line numbers point at the cell within the notebook's JSON.
+func chunkNotebook(filePath string, sourceCode []byte) []Chunk {
+	cells, offsets, metadata, err := parseNotebook(sourceCode)
+	if err != nil {
+		return nil
+	}
+
+	language := metadata.KernelSpec.Language
+	if language == "" {
+		language = metadata.LanguageInfo.Name
+	}
+	if language == "" {
+		language = "python"
+	}
+
+	var (
+		chunks  []Chunk
+		context []string
+	)
+	for i, cell := range cells {
+		source := notebookSource(cell.Source)
+		if cell.CellType == "markdown" {
+			if text := strings.TrimSpace(source); text != "" {
+				context = append(context, text)
+			}
+			continue
+		}
+		if cell.CellType != "code" || strings.TrimSpace(source) == "" {
+			continue
+		}
+
+		chunk := Chunk{
+			ID:            uuid.New().String(),
+			FilePath:      filePath,
+			LineStart:     bytes.Count(sourceCode[:offsets[i][0]], []byte("\n")) + 1,
+			LineEnd:       bytes.Count(sourceCode[:offsets[i][1]], []byte("\n")) + 1,
+			Language:      language,
+			Code:          source,
+			ChunkType:     string(parser.ChunkTypeBlock),
+			Name:          fmt.Sprintf("cell %d", i+1),
+			Metadata:      map[string]string{"kind": "code_cell", "cell": fmt.Sprintf("%d", i+1)},
+			EmbeddingType: "code",
+		}
+		if len(context) > 0 {
+			chunk.Metadata["context"] = strings.Join(context, "\n\n")
+		}
+		chunks = append(chunks, chunk)
+		context = nil
+	}
+
+	return chunks
+}
+
+// parseNotebook decodes the cells and metadata of a notebook, along with the
+// byte range each cell occupies in the source
+func parseNotebook(sourceCode []byte) ([]notebookCell, [][2]int64, notebookMetadata, error) {
+	var (
+		cells    []notebookCell
+		offsets  [][2]int64
+		metadata notebookMetadata
+	)
+
+	dec := json.NewDecoder(bytes.NewReader(sourceCode))
+	if tok, err := dec.Token(); err != nil || tok != json.Delim('{') {
+		return nil, nil, metadata, fmt.Errorf("notebook is not a JSON object")
+	}
+
+	for dec.More() {
+		tok, err := dec.Token()
+		if err != nil {
+			return nil, nil, metadata, fmt.Errorf("failed to read notebook: %w", err)
+		}
+
+		switch tok {
+		case "cells":
+			if tok, err := dec.Token(); err != nil || tok != json.Delim('[') {
+				return nil, nil, metadata, fmt.Errorf("notebook cells are not a list")
+			}
+			for dec.More() {
+				// The offset is just past the previous token; skip to the cell itself
+				start := dec.InputOffset()
+				for start < int64(len(sourceCode)) && strings.ContainsRune(" \t\r\n,", rune(sourceCode[start])) {
+					start++
+				}
+
+				var cell notebookCell
+				if err := dec.Decode(&cell); err != nil {
+					return nil, nil, metadata, fmt.Errorf("failed to decode notebook cell: %w", err)
+				}
+				cells = append(cells, cell)
+				offsets = append(offsets, [2]int64{start, dec.InputOffset()})
+			}
+			if _, err := dec.Token(); err != nil {
+				return nil, nil, metadata, fmt.Errorf("failed to read notebook: %w", err)
+			}
+		case "metadata":
+			if err := dec.Decode(&metadata); err != nil {
+				return nil, nil, metadata, fmt.Errorf("failed to decode notebook metadata: %w", err)
+			}
+		default:
+			var skip json.RawMessage
+			if err := dec.Decode(&skip); err != nil {
+				return nil, nil, metadata, fmt.Errorf("failed to read notebook: %w", err)
+			}
+		}
+	}
+
+	return cells, offsets, metadata, nil
+}
+
+// notebookSource joins a cell's source, which notebooks store either as one
+// string or as a list of lines that keep their trailing newlines
+func notebookSource(raw json.RawMessage) string {
+	var text string
+	if err := json.Unmarshal(raw, &text); err == nil {
+		return text
+	}
+	var lines []string
+	if err := json.Unmarshal(raw, &lines); err == nil {
+		return strings.Join(lines, "")
+	}
+	return ""
+}
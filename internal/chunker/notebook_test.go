@@ -0,0 +1,65 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkNotebook(t *testing.T) {
+	source := `{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": ["# Load data\n", "Reads the training set."]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "metadata": {},
+   "outputs": [],
+   "source": [
+    "import pandas as pd\n",
+    "df = pd.read_csv(\"train.csv\")"
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": null,
+   "metadata": {},
+   "outputs": [],
+   "source": "df.describe()"
+  }
+ ],
+ "metadata": {
+  "kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"}
+ },
+ "nbformat": 4,
+ "nbformat_minor": 5
+}
+`
+
+	chunks := chunkNotebook("analysis.ipynb", []byte(source))
+	require.Len(t, chunks, 2)
+
+	load := chunks[0]
+	assert.Equal(t, "cell 2", load.Name)
+	assert.Equal(t, "python", load.Language)
+	assert.Equal(t, "code_cell", load.Metadata["kind"])
+	assert.Equal(t, "import pandas as pd\ndf = pd.read_csv(\"train.csv\")", load.Code)
+	assert.Equal(t, "# Load data\nReads the training set.", load.Metadata["context"])
+	assert.Equal(t, 8, load.LineStart)
+	assert.Equal(t, 17, load.LineEnd)
+
+	describe := chunks[1]
+	assert.Equal(t, "df.describe()", describe.Code)
+	assert.Empty(t, describe.Metadata["context"])
+	assert.Equal(t, 18, describe.LineStart)
+	assert.Equal(t, 24, describe.LineEnd)
+}
+
+func TestChunkNotebookInvalid(t *testing.T) {
+	assert.Empty(t, chunkNotebook("broken.ipynb", []byte(`{"cells": [`)))
+}
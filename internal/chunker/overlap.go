@@ -0,0 +1,137 @@
+package chunker
+
+import (
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+)
+
+// Line prefixes skipped when looking for an enclosing item's declaration line
+var headerSkipPrefixes = []string{"//", "/*", "*", "#", "--", "%", "@", `"""`, "'''"}
+
+// applyOverlap extends the chunks of a file with the configured overlap. Chunks
+// keep their own lines; overlap_before and overlap_after record how many lines
+// of context were added, and overlap_header the enclosing item's header.
+func (s *SemanticChunker) applyOverlap(filePath, language string, chunks []Chunk) ([]Chunk, error) {
+	if s.options.OverlapLines == 0 && !s.options.OverlapHeader {
+		return chunks, nil
+	}
+	// Notebook chunk lines point into the JSON document, not the cell source
+	if language == "notebook" {
+		return chunks, nil
+	}
+
+	sourceCode, err := os.ReadFile(filePath)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read file: %w", err)
+	}
+	lines := strings.Split(strings.TrimSuffix(string(sourceCode), "\n"), "\n")
+
+	// Headers are found from the original ranges, before any are widened
+	headers := make([]string, len(chunks))
+	if s.options.OverlapHeader {
+		for i := range chunks {
+			headers[i] = enclosingHeader(lines, chunks, i)
+		}
+	}
+
+	for i := range chunks {
+		chunk := &chunks[i]
+		if chunk.LineStart < 1 || chunk.LineEnd < chunk.LineStart || chunk.LineEnd > len(lines) {
+			continue
+		}
+
+		var before, after []string
+		if n := s.options.OverlapLines; n > 0 {
+			before = lines[max(chunk.LineStart-1-n, 0) : chunk.LineStart-1]
+			after = lines[chunk.LineEnd:min(chunk.LineEnd+n, len(lines))]
+		}
+
+		code := chunk.Code
+		if len(before) > 0 {
+			code = strings.Join(before, "\n") + "\n" + code
+		}
+		if len(after) > 0 {
+			code = code + "\n" + strings.Join(after, "\n")
+		}
+		// The header is only added when the leading context does not already hold it
+		if header := headers[i]; header != "" && !containsLine(before, header) {
+			code = header + "\n" + code
+			setMetadata(chunk, "overlap_header", strings.TrimSpace(header))
+		}
+		if len(before) > 0 {
+			setMetadata(chunk, "overlap_before", strconv.Itoa(len(before)))
+		}
+		if len(after) > 0 {
+			setMetadata(chunk, "overlap_after", strconv.Itoa(len(after)))
+		}
+
+		chunk.Code = code
+		chunk.LineStart -= len(before)
+		chunk.LineEnd += len(after)
+	}
+
+	return chunks, nil
+}
+
+// enclosingHeader returns the declaration line of the smallest chunk that
+// contains chunks[index], or "" when the chunk is not nested in another
+func enclosingHeader(lines []string, chunks []Chunk, index int) string {
+	inner := chunks[index]
+	parent := -1
+	for j, outer := range chunks {
+		if j == index || outer.FilePath != inner.FilePath {
+			continue
+		}
+		if outer.LineStart > inner.LineStart || outer.LineEnd < inner.LineEnd {
+			continue
+		}
+		if outer.LineEnd-outer.LineStart <= inner.LineEnd-inner.LineStart {
+			continue
+		}
+		if parent < 0 || outer.LineEnd-outer.LineStart < chunks[parent].LineEnd-chunks[parent].LineStart {
+			parent = j
+		}
+	}
+	if parent < 0 {
+		return ""
+	}
+
+	// The enclosing chunk may begin with its doc comment or attributes
+	for i := chunks[parent].LineStart - 1; i < inner.LineStart-1 && i < len(lines); i++ {
+		trimmed := strings.TrimSpace(lines[i])
+		if trimmed != "" && !hasAnyPrefix(trimmed, headerSkipPrefixes) {
+			return lines[i]
+		}
+	}
+	return ""
+}
+
+// hasAnyPrefix reports whether s starts with one of prefixes
+func hasAnyPrefix(s string, prefixes []string) bool {
+	for _, prefix := range prefixes {
+		if strings.HasPrefix(s, prefix) {
+			return true
+		}
+	}
+	return false
+}
+
+// containsLine reports whether lines holds line
+func containsLine(lines []string, line string) bool {
+	for _, l := range lines {
+		if l == line {
+			return true
+		}
+	}
+	return false
+}
+
+// setMetadata sets a metadata key on a chunk, creating the map if needed
+func setMetadata(chunk *Chunk, key, value string) {
+	if chunk.Metadata == nil {
+		chunk.Metadata = make(map[string]string)
+	}
+	chunk.Metadata[key] = value
+}
@@ -0,0 +1,90 @@
+package chunker
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+const overlapSource = `const std = @import("std");
+
+/// A point in 2D space
+pub const Point = struct {
+    x: i32,
+    y: i32,
+
+    pub fn sum(self: Point) i32 {
+        return self.x + self.y;
+    }
+};
+
+pub fn main() void {
+    _ = Point{ .x = 1, .y = 2 };
+}
+`
+
+func chunkWithOverlap(t *testing.T, options Options) map[string]Chunk {
+	t.Helper()
+	zigFile := filepath.Join(t.TempDir(), "point.zig")
+	require.NoError(t, os.WriteFile(zigFile, []byte(overlapSource), 0644))
+
+	semantic, err := NewSemanticWithOptions(options)
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(zigFile, "zig")
+	require.NoError(t, err)
+
+	byName := make(map[string]Chunk)
+	for _, chunk := range chunks {
+		byName[chunk.Name] = chunk
+	}
+	return byName
+}
+
+func TestOverlapLines(t *testing.T) {
+	chunks := chunkWithOverlap(t, Options{OverlapLines: 2})
+
+	sum := chunks["sum"]
+	assert.Equal(t, 6, sum.LineStart)
+	assert.Equal(t, 12, sum.LineEnd)
+	assert.Equal(t, "    y: i32,\n\n    pub fn sum(self: Point) i32 {\n        return self.x + self.y;\n    }\n};\n", sum.Code)
+	assert.Equal(t, "2", sum.Metadata["overlap_before"])
+	assert.Equal(t, "2", sum.Metadata["overlap_after"])
+
+	// Context stops at the end of the file
+	main := chunks["main"]
+	assert.Equal(t, 11, main.LineStart)
+	assert.Equal(t, 15, main.LineEnd)
+	assert.Equal(t, "2", main.Metadata["overlap_before"])
+	assert.NotContains(t, main.Metadata, "overlap_after")
+	assert.NotContains(t, main.Metadata, "overlap_header")
+}
+
+func TestOverlapHeader(t *testing.T) {
+	chunks := chunkWithOverlap(t, Options{OverlapHeader: true})
+
+	sum := chunks["sum"]
+	assert.Equal(t, 8, sum.LineStart)
+	assert.Equal(t, 10, sum.LineEnd)
+	assert.Equal(t, "pub const Point = struct {\n    pub fn sum(self: Point) i32 {\n        return self.x + self.y;\n    }", sum.Code)
+	assert.Equal(t, "pub const Point = struct {", sum.Metadata["overlap_header"])
+
+	assert.NotContains(t, chunks["Point"].Metadata, "overlap_header")
+	assert.NotContains(t, chunks["main"].Metadata, "overlap_header")
+}
+
+func TestOverlapDisabled(t *testing.T) {
+	chunks := chunkWithOverlap(t, Options{})
+
+	sum := chunks["sum"]
+	assert.Equal(t, 8, sum.LineStart)
+	assert.Equal(t, 10, sum.LineEnd)
+	assert.NotContains(t, sum.Metadata, "overlap_before")
+}
+
+func TestNegativeOverlap(t *testing.T) {
+	_, err := NewSemanticWithOptions(Options{OverlapLines: -1})
+	assert.Error(t, err)
+}
@@ -0,0 +1,108 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches the package declaration: package acme.billing.v1;
+	protoPackageRegex = regexp.MustCompile(`^\s*package\s+([\w.]+)\s*;`)
+	// Matches message, enum, and service definitions: message Invoice {
+	protoTypeRegex = regexp.MustCompile(`^\s*(message|enum|service)\s+(\w+)\s*\{`)
+	// Matches an rpc and its request and response types:
+	// rpc GetInvoice(GetInvoiceRequest) returns (stream Invoice)
+	protoRPCRegex = regexp.MustCompile(`^\s*rpc\s+(\w+)\s*(\(\s*(?:stream\s+)?[\w.]+\s*\))\s*returns\s*(\(\s*(?:stream\s+)?[\w.]+\s*\))`)
+)
+
+// chunkProtobuf extracts messages, enums, services, and rpcs from Protocol
+// Buffers definitions. Nested messages and enums get their own chunks, and
+// every chunk records its fully qualified name (package.Outer.Inner) as its
+// "symbol" metadata.
+func chunkProtobuf(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type typeScope struct {
+		name string
+		end  int
+	}
+	var (
+		chunks []Chunk
+		scopes []typeScope
+		pkg    string
+	)
+
+	for i := 0; i < len(lines); i++ {
+		for len(scopes) > 0 && i > scopes[len(scopes)-1].end {
+			scopes = scopes[:len(scopes)-1]
+		}
+
+		line := lines[i]
+		if matches := protoPackageRegex.FindStringSubmatch(line); matches != nil {
+			pkg = matches[1]
+			continue
+		}
+
+		var (
+			chunkType parser.ChunkType
+			name      string
+			kind      string
+			signature string
+		)
+		if matches := protoTypeRegex.FindStringSubmatch(line); matches != nil {
+			kind, name = matches[1], matches[2]
+			switch kind {
+			case "message":
+				chunkType = parser.ChunkTypeStruct
+			case "enum":
+				chunkType = parser.ChunkTypeEnum
+			case "service":
+				chunkType = parser.ChunkTypeInterface
+			}
+		} else if matches := protoRPCRegex.FindStringSubmatch(line); matches != nil {
+			kind, name = "rpc", matches[1]
+			chunkType = parser.ChunkTypeMethod
+			signature = matches[2] + " returns " + matches[3]
+		} else {
+			continue
+		}
+
+		qualified := []string{}
+		if pkg != "" {
+			qualified = append(qualified, pkg)
+		}
+		for _, scope := range scopes {
+			qualified = append(qualified, scope.name)
+		}
+		qualified = append(qualified, name)
+
+		end := cStyle.blockEnd(lines, i)
+		chunk := newLineChunk(filePath, "protobuf", lines, i, end, string(chunkType), name)
+		chunk.Metadata["kind"] = kind
+		chunk.Metadata["symbol"] = strings.Join(qualified, ".")
+		if pkg != "" {
+			chunk.Metadata["package"] = pkg
+		}
+		if signature != "" {
+			chunk.Metadata["signature"] = signature
+		}
+		if len(scopes) > 0 {
+			chunk.Metadata["receiver"] = scopes[len(scopes)-1].name
+		}
+		if doc := cStyle.leadingComment(lines, i); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		// Messages and services hold nested definitions; enums and rpcs do not
+		if kind == "message" || kind == "service" {
+			scopes = append(scopes, typeScope{name: name, end: end})
+		} else {
+			i = end
+		}
+	}
+
+	return chunks
+}
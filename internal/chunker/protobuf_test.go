@@ -0,0 +1,69 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkProtobuf(t *testing.T) {
+	source := `syntax = "proto3";
+
+package acme.billing.v1;
+
+// An issued invoice.
+message Invoice {
+  string id = 1;
+
+  enum Status {
+    STATUS_UNSPECIFIED = 0;
+    STATUS_PAID = 1;
+  }
+
+  Status status = 2;
+}
+
+service InvoiceService {
+  // Fetches one invoice.
+  rpc GetInvoice(GetInvoiceRequest) returns (Invoice);
+  rpc WatchInvoices(WatchRequest) returns (stream Invoice) {
+    option idempotency_level = NO_SIDE_EFFECTS;
+  }
+}
+`
+
+	chunks := chunkProtobuf("invoice.proto", []byte(source))
+	require.Len(t, chunks, 5)
+
+	invoice := chunks[0]
+	assert.Equal(t, "Invoice", invoice.Name)
+	assert.Equal(t, "struct", invoice.ChunkType)
+	assert.Equal(t, "acme.billing.v1.Invoice", invoice.Metadata["symbol"])
+	assert.Equal(t, "An issued invoice.", invoice.Metadata["doc_comment"])
+	assert.Equal(t, 6, invoice.LineStart)
+	assert.Equal(t, 15, invoice.LineEnd)
+
+	status := chunks[1]
+	assert.Equal(t, "enum", status.ChunkType)
+	assert.Equal(t, "acme.billing.v1.Invoice.Status", status.Metadata["symbol"])
+	assert.Equal(t, 12, status.LineEnd)
+
+	service := chunks[2]
+	assert.Equal(t, "interface", service.ChunkType)
+	assert.Equal(t, "acme.billing.v1.InvoiceService", service.Metadata["symbol"])
+
+	get := chunks[3]
+	assert.Equal(t, "GetInvoice", get.Name)
+	assert.Equal(t, "method", get.ChunkType)
+	assert.Equal(t, "InvoiceService", get.Metadata["receiver"])
+	assert.Equal(t, "(GetInvoiceRequest) returns (Invoice)", get.Metadata["signature"])
+	assert.Equal(t, "Fetches one invoice.", get.Metadata["doc_comment"])
+	assert.Equal(t, 19, get.LineStart)
+	assert.Equal(t, 19, get.LineEnd)
+
+	watch := chunks[4]
+	assert.Equal(t, "acme.billing.v1.InvoiceService.WatchInvoices", watch.Metadata["symbol"])
+	assert.Equal(t, "(WatchRequest) returns (stream Invoice)", watch.Metadata["signature"])
+	assert.Equal(t, 22, watch.LineEnd)
+}
@@ -0,0 +1,80 @@
+package chunker
+
+import (
+	"context"
+	"fmt"
+	"sync"
+
+	"github.com/jlanders/code-scout/internal/parser"
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// LanguageChunker splits the source code of one language into chunks. Register
+// an implementation to add a language without changing the built-in chunkers.
+type LanguageChunker interface {
+	ChunkSource(filePath string, sourceCode []byte) ([]Chunk, error)
+}
+
+// LanguageChunkerFunc adapts a function to the LanguageChunker interface
+type LanguageChunkerFunc func(filePath string, sourceCode []byte) ([]Chunk, error)
+
+// ChunkSource calls f(filePath, sourceCode)
+func (f LanguageChunkerFunc) ChunkSource(filePath string, sourceCode []byte) ([]Chunk, error) {
+	return f(filePath, sourceCode)
+}
+
+var (
+	registryMu sync.RWMutex
+	registry   = make(map[string]LanguageChunker)
+)
+
+// Register makes c the chunker for files of the given language, replacing any
+// previously registered or built-in chunker for it
+func Register(language string, c LanguageChunker) {
+	registryMu.Lock()
+	defer registryMu.Unlock()
+	registry[language] = c
+}
+
+// lookupChunker returns the registered chunker for a language
+func lookupChunker(language string) (LanguageChunker, bool) {
+	registryMu.RLock()
+	defer registryMu.RUnlock()
+	c, ok := registry[language]
+	return c, ok
+}
+
+// GrammarChunker chunks source code with a Tree-sitter grammar and query, so
+// grammars loaded at runtime can be used without a built-in extractor
+type GrammarChunker struct {
+	language string
+	grammar  *sitter.Language
+	query    string
+}
+
+// NewGrammarChunker creates a chunker for language that parses with grammar
+// and extracts the chunks matched by query (see parser.ExtractWithQuery)
+func NewGrammarChunker(language string, grammar *sitter.Language, query string) (*GrammarChunker, error) {
+	compiled, queryErr := sitter.NewQuery(grammar, query)
+	if queryErr != nil {
+		return nil, fmt.Errorf("invalid query for %s: %w", language, queryErr)
+	}
+	compiled.Close()
+
+	return &GrammarChunker{language: language, grammar: grammar, query: query}, nil
+}
+
+// ChunkSource parses sourceCode and returns a chunk per query match
+func (g *GrammarChunker) ChunkSource(filePath string, sourceCode []byte) ([]Chunk, error) {
+	// Parsers are not safe for concurrent use, so each call gets its own
+	p, err := parser.NewGrammarParser(g.grammar)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create parser for %s: %w", g.language, err)
+	}
+
+	parserChunks, err := parser.NewExtractor(p, sourceCode).ExtractWithQuery(context.Background(), g.query)
+	if err != nil {
+		return nil, fmt.Errorf("failed to extract chunks: %w", err)
+	}
+	return convertParserChunks(filePath, g.language, parserChunks), nil
+}
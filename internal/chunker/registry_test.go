@@ -0,0 +1,52 @@
+package chunker
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	sitter "github.com/tree-sitter/go-tree-sitter"
+	tree_sitter_go "github.com/tree-sitter/tree-sitter-go/bindings/go"
+)
+
+func TestRegisterLanguageChunker(t *testing.T) {
+	Register("nim", LanguageChunkerFunc(func(filePath string, sourceCode []byte) ([]Chunk, error) {
+		return []Chunk{{FilePath: filePath, Code: string(sourceCode), Name: "whole"}}, nil
+	}))
+	defer func() {
+		registryMu.Lock()
+		delete(registry, "nim")
+		registryMu.Unlock()
+	}()
+
+	nimFile := filepath.Join(t.TempDir(), "main.nim")
+	require.NoError(t, os.WriteFile(nimFile, []byte("echo \"hi\"\n"), 0644))
+
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(nimFile, "nim")
+	require.NoError(t, err)
+	require.Len(t, chunks, 1)
+	assert.Equal(t, "whole", chunks[0].Name)
+}
+
+func TestGrammarChunker(t *testing.T) {
+	grammar := sitter.NewLanguage(tree_sitter_go.Language())
+	query := `(function_declaration name: (identifier) @function.name) @function.definition`
+
+	grammarChunker, err := NewGrammarChunker("go-query", grammar, query)
+	require.NoError(t, err)
+
+	chunks, err := grammarChunker.ChunkSource("main.go", []byte("package main\n\nfunc main() {}\n"))
+	require.NoError(t, err)
+	require.Len(t, chunks, 1)
+	assert.Equal(t, "main", chunks[0].Name)
+	assert.Equal(t, "function", chunks[0].ChunkType)
+	assert.Equal(t, "go-query", chunks[0].Language)
+	assert.Equal(t, 3, chunks[0].LineStart)
+
+	_, err = NewGrammarChunker("go-query", grammar, "(no_such_node) @x")
+	assert.Error(t, err)
+}
@@ -12,21 +12,160 @@ import (
 // SemanticChunker uses Tree-sitter for code and header-based chunking for docs
 type SemanticChunker struct {
 	markdownChunker *MarkdownChunker
+	options         Options
+}
+
+// Options configures the semantic chunker
+type Options struct {
+	// OverlapLines is the number of lines before and after each chunk that are
+	// added to it, so text near a boundary is embedded with both neighbours
+	OverlapLines int
+	// OverlapHeader prepends the declaration line of the enclosing item, such
+	// as a class or impl block, to chunks nested inside it
+	OverlapHeader bool
+	// CodeLimit and DocsLimit cap the size of chunks embedded with the code
+	// and documentation models; larger chunks are split to fit
+	CodeLimit TokenLimit
+	DocsLimit TokenLimit
+	// MergeTokens merges runs of consecutive small sibling items, such as
+	// one-line methods, into chunks of up to this many tokens; 0 disables it
+	MergeTokens int
+	// GroupTypes adds an aggregate chunk for each type with methods, made of
+	// the type's definition and its method signatures
+	GroupTypes bool
+	// SkipGenerated leaves generated files out entirely instead of tagging
+	// their chunks as generated
+	SkipGenerated bool
+	// StreamThreshold is the file size in bytes above which StreamFile reads
+	// a file a line at a time; 0 uses DefaultStreamThreshold
+	StreamThreshold int64
 }
 
 // NewSemantic creates a new semantic chunker
 func NewSemantic() (*SemanticChunker, error) {
+	return NewSemanticWithOptions(Options{})
+}
+
+// NewSemanticWithOptions creates a new semantic chunker with the given options
+func NewSemanticWithOptions(options Options) (*SemanticChunker, error) {
+	if options.OverlapLines < 0 {
+		return nil, fmt.Errorf("overlap lines cannot be negative: %d", options.OverlapLines)
+	}
+	if options.CodeLimit.MaxTokens < 0 || options.DocsLimit.MaxTokens < 0 || options.MergeTokens < 0 {
+		return nil, fmt.Errorf("token limits cannot be negative")
+	}
+	for _, strategy := range []string{options.CodeLimit.Strategy, options.DocsLimit.Strategy} {
+		switch strategy {
+		case "", TruncateSplit, TruncateHead, TruncateTail, TruncateMiddle:
+		default:
+			return nil, fmt.Errorf("unknown truncation strategy: %s", strategy)
+		}
+	}
+	if options.StreamThreshold < 0 {
+		return nil, fmt.Errorf("stream threshold cannot be negative: %d", options.StreamThreshold)
+	}
 	return &SemanticChunker{
 		markdownChunker: NewMarkdownChunker(),
+		options:         options,
 	}, nil
 }
 
-// ChunkFile splits a file into semantic chunks based on language type
+// lineChunkers handles languages without a vendored Tree-sitter grammar
+var lineChunkers = map[string]func(filePath string, sourceCode []byte) []Chunk{
+	"csharp":     chunkCSharp,
+	"kotlin":     chunkKotlin,
+	"swift":      chunkSwift,
+	"zig":        chunkZig,
+	"haskell":    chunkHaskell,
+	"elixir":     chunkElixir,
+	"erlang":     chunkErlang,
+	"lua":        chunkLua,
+	"sql":        chunkSQL,
+	"shell":      chunkShell,
+	"dockerfile": chunkDockerfile,
+	"compose":    chunkCompose,
+	"notebook":   chunkNotebook,
+	"yaml":       chunkYAML,
+	"terraform":  chunkTerraform,
+	"protobuf":   chunkProtobuf,
+	"graphql":    chunkGraphQL,
+}
+
+// ChunkFile splits a file into semantic chunks based on language type. The
+// chunks of generated files are tagged with generated, or left out when
+// SkipGenerated is set. Binary and minified files have no chunks.
 func (s *SemanticChunker) ChunkFile(filePath, language string) ([]Chunk, error) {
+	skip, err := isBinaryOrMinified(filePath, language)
+	if err != nil {
+		return nil, err
+	}
+	if skip {
+		return nil, nil
+	}
+
+	generated, err := isGeneratedFile(filePath)
+	if err != nil {
+		return nil, err
+	}
+	if generated && s.options.SkipGenerated {
+		return nil, nil
+	}
+
+	chunks, err := s.chunkFile(filePath, language)
+	if err != nil {
+		return nil, err
+	}
+	if generated {
+		for i := range chunks {
+			setMetadata(&chunks[i], "generated", "true")
+		}
+	}
+	return chunks, nil
+}
+
+// SkipReason returns why ChunkFile leaves a file without chunks, or "" when
+// it chunks the file
+func (s *SemanticChunker) SkipReason(filePath, language string) (string, error) {
+	skip, err := isBinaryOrMinified(filePath, language)
+	if err != nil {
+		return "", err
+	}
+	if skip {
+		return "binary or minified", nil
+	}
+
+	if !s.options.SkipGenerated {
+		return "", nil
+	}
+	generated, err := isGeneratedFile(filePath)
+	if err != nil {
+		return "", err
+	}
+	if generated {
+		return "generated", nil
+	}
+	return "", nil
+}
+
+// chunkFile routes a file to the chunker for its language
+func (s *SemanticChunker) chunkFile(filePath, language string) ([]Chunk, error) {
 	// Route to appropriate chunker based on language
 	var chunks []Chunk
 	var err error
 
+	// Registered chunkers take precedence over the built-in ones
+	if registered, ok := lookupChunker(language); ok {
+		sourceCode, readErr := os.ReadFile(filePath)
+		if readErr != nil {
+			return nil, fmt.Errorf("failed to read file: %w", readErr)
+		}
+		chunks, err = registered.ChunkSource(filePath, sourceCode)
+		if err != nil {
+			return nil, err
+		}
+		return s.finishChunks(filePath, language, chunks)
+	}
+
 	switch language {
 	case "markdown", "text", "rst":
 		// Documentation files - use markdown chunker
@@ -35,14 +174,41 @@ func (s *SemanticChunker) ChunkFile(filePath, language string) ([]Chunk, error)
 		// Code files - use tree-sitter
 		chunks, err = s.chunkCode(filePath, language)
 	default:
-		return nil, fmt.Errorf("unsupported language: %s", language)
+		lineChunker, ok := lineChunkers[language]
+		if !ok {
+			return nil, fmt.Errorf("unsupported language: %s", language)
+		}
+		chunks, err = s.chunkLines(filePath, lineChunker)
 	}
 
 	if err != nil {
 		return nil, err
 	}
 
-	return chunks, nil
+	return s.finishChunks(filePath, language, chunks)
+}
+
+// finishChunks summarizes a file's doc comments, merges its small sibling
+// chunks, gives them content-derived IDs, adds the configured overlap, then
+// splits any chunks that exceed the token limit of the model that will embed
+// them
+func (s *SemanticChunker) finishChunks(filePath, language string, chunks []Chunk) ([]Chunk, error) {
+	summarizeDocs(chunks)
+	qualifyNames(chunks)
+	tagTests(filePath, chunks)
+	var groups []Chunk
+	if s.options.GroupTypes {
+		groups = groupTypes(chunks)
+		assignIDs(groups)
+	}
+	chunks = s.mergeSmallChunks(chunks)
+	// IDs come from the code before overlap, which depends on the neighbours
+	assignIDs(chunks)
+	chunks, err := s.applyOverlap(filePath, language, chunks)
+	if err != nil {
+		return nil, err
+	}
+	return s.applyTokenLimits(append(chunks, groups...)), nil
 }
 
 // chunkDocumentation handles markdown, text, and rst files
@@ -82,6 +248,11 @@ func (s *SemanticChunker) chunkDocumentation(filePath, language string) ([]Chunk
 		chunks[i].EmbeddingType = "docs"
 	}
 
+	// Code examples in markdown are also chunked as code
+	if language == "markdown" {
+		chunks = append(chunks, s.chunkCodeBlocks(chunks)...)
+	}
+
 	return chunks, nil
 }
 
@@ -99,6 +270,16 @@ func (s *SemanticChunker) chunkCode(filePath, language string) ([]Chunk, error)
 		return nil, fmt.Errorf("could not detect language for file: %s", filePath)
 	}
 
+	// A .h file may hold C or C++, so report what the content was detected as
+	if language == "c" || language == "cpp" {
+		language = lang.String()
+	}
+
+	return s.extractChunks(filePath, language, lang, sourceCode)
+}
+
+// extractChunks runs the Tree-sitter extractor for lang over sourceCode
+func (s *SemanticChunker) extractChunks(filePath, language string, lang parser.Language, sourceCode []byte) ([]Chunk, error) {
 	// Create parser for the detected language
 	p, err := parser.NewParser(lang)
 	if err != nil {
@@ -112,7 +293,17 @@ func (s *SemanticChunker) chunkCode(filePath, language string) ([]Chunk, error)
 		return nil, fmt.Errorf("failed to extract chunks: %w", err)
 	}
 
-	// Convert parser chunks to chunker chunks
+	chunks := convertParserChunks(filePath, language, parserChunks)
+
+	if lang == parser.LanguageTypeScript {
+		chunks = append(chunks, chunkTypeScriptDeclarations(filePath, sourceCode)...)
+	}
+
+	return chunks, nil
+}
+
+// convertParserChunks converts parser chunks to chunker chunks
+func convertParserChunks(filePath, language string, parserChunks []*parser.Chunk) []Chunk {
 	chunks := make([]Chunk, 0, len(parserChunks))
 	for _, pc := range parserChunks {
 		chunk := Chunk{
@@ -155,5 +346,32 @@ func (s *SemanticChunker) chunkCode(filePath, language string) ([]Chunk, error)
 		chunks = append(chunks, chunk)
 	}
 
-	return chunks, nil
+	return chunks
+}
+
+// chunkLines handles code files whose language is chunked line by line
+func (s *SemanticChunker) chunkLines(filePath string, chunkFn func(string, []byte) []Chunk) ([]Chunk, error) {
+	sourceCode, err := os.ReadFile(filePath)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read file: %w", err)
+	}
+
+	return chunkFn(filePath, sourceCode), nil
+}
+
+// chunkSnippet chunks source code that is not a file of its own, such as a
+// code block embedded in documentation, given its language name
+func (s *SemanticChunker) chunkSnippet(filePath, language string, sourceCode []byte) ([]Chunk, error) {
+	if registered, ok := lookupChunker(language); ok {
+		return registered.ChunkSource(filePath, sourceCode)
+	}
+	if lineChunker, ok := lineChunkers[language]; ok {
+		return lineChunker(filePath, sourceCode), nil
+	}
+
+	lang := parser.LanguageFromName(language)
+	if lang == parser.LanguageUnknown {
+		return nil, fmt.Errorf("unsupported language: %s", language)
+	}
+	return s.extractChunks(filePath, language, lang, sourceCode)
 }
@@ -0,0 +1,159 @@
+package chunker
+
+import (
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches function definitions: deploy() {, function deploy {, function deploy() {
+	shellFunctionRegex = regexp.MustCompile(`^\s*(?:function\s+([\w:.-]+)\s*(?:\(\s*\))?|([\w:.-]+)\s*\(\s*\))\s*(\{|$)`)
+
+	shellStyle = commentStyle{line: []string{"#"}}
+)
+
+// chunkShell extracts function definitions from bash, zsh, and sh scripts.
+// Scripts without functions become a single chunk covering the whole file.
+// The comment header at the top of the script is recorded as "header" metadata
+// on every chunk.
+func chunkShell(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+	header := shellHeader(lines)
+	interpreter := shellInterpreter(lines[0])
+
+	var chunks []Chunk
+	for i := 0; i < len(lines); i++ {
+		matches := shellFunctionRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			continue
+		}
+		name := matches[1] + matches[2]
+
+		end := shellBlockEnd(lines, i)
+		chunk := newLineChunk(filePath, "shell", lines, i, end, string(parser.ChunkTypeFunction), name)
+		if doc := shellStyle.leadingComment(lines, i); doc != "" && doc != header {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		i = end
+	}
+
+	if len(chunks) == 0 && strings.TrimSpace(string(sourceCode)) != "" {
+		end := len(lines) - 1
+		for end > 0 && strings.TrimSpace(lines[end]) == "" {
+			end--
+		}
+		chunks = append(chunks, newLineChunk(filePath, "shell", lines, 0, end, string(parser.ChunkTypeModule), filepath.Base(filePath)))
+	}
+
+	for i := range chunks {
+		if header != "" {
+			chunks[i].Metadata["header"] = header
+		}
+		if interpreter != "" {
+			chunks[i].Metadata["interpreter"] = interpreter
+		}
+	}
+
+	return chunks
+}
+
+// shellInterpreter returns the shell named by a shebang line, looking through
+// env: #!/usr/bin/env bash and #!/bin/bash both give "bash"
+func shellInterpreter(line string) string {
+	if !strings.HasPrefix(line, "#!") {
+		return ""
+	}
+	fields := strings.Fields(strings.TrimPrefix(line, "#!"))
+	if len(fields) == 0 {
+		return ""
+	}
+	interpreter := filepath.Base(fields[0])
+	if interpreter == "env" {
+		for _, field := range fields[1:] {
+			if !strings.HasPrefix(field, "-") {
+				return field
+			}
+		}
+	}
+	return interpreter
+}
+
+// shellHeader returns the comment block at the top of a script, after the
+// shebang line, with comment markers stripped
+func shellHeader(lines []string) string {
+	start := 0
+	if strings.HasPrefix(lines[0], "#!") {
+		start = 1
+	}
+
+	var header []string
+	for _, line := range lines[start:] {
+		trimmed := strings.TrimSpace(line)
+		if !strings.HasPrefix(trimmed, "#") {
+			break
+		}
+		header = append(header, shellStyle.stripMarkers(trimmed))
+	}
+	return strings.TrimSpace(strings.Join(header, "\n"))
+}
+
+// shellBlockEnd returns the line holding the brace that closes the function
+// body opened on or after lines[start]
+func shellBlockEnd(lines []string, start int) int {
+	depth := 0
+	opened := false
+	for i := start; i < len(lines); i++ {
+		code := shellCode(lines[i])
+		for j := 0; j < len(code); j++ {
+			switch code[j] {
+			case '{':
+				depth++
+				opened = true
+			case '}':
+				depth--
+			}
+		}
+		if opened && depth <= 0 {
+			return i
+		}
+	}
+	return len(lines) - 1
+}
+
+// shellCode blanks out quoted strings and removes a trailing comment. Unlike
+// most languages, # only starts a comment at the beginning of a word, so
+// ${#array[@]} and $# are kept.
+func shellCode(line string) string {
+	var (
+		code  strings.Builder
+		quote byte
+	)
+	for i := 0; i < len(line); i++ {
+		c := line[i]
+		if quote != 0 {
+			if c == '\\' && quote != '\'' {
+				i++
+			} else if c == quote {
+				quote = 0
+			}
+			continue
+		}
+		switch {
+		case c == '\\':
+			i++
+			continue
+		case c == '"' || c == '\'':
+			quote = c
+			continue
+		case c == '#' && (i == 0 || line[i-1] == ' ' || line[i-1] == '\t'):
+			return code.String()
+		}
+		code.WriteByte(c)
+	}
+	return code.String()
+}
@@ -0,0 +1,71 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkShell(t *testing.T) {
+	source := `#!/usr/bin/env bash
+# Deploys the application.
+# Usage: deploy.sh <env>
+
+set -euo pipefail
+
+# Prints a message to stderr.
+log() {
+  echo "[deploy] $*" >&2
+}
+
+function deploy {
+  local hosts=("$@")
+  if [ "${#hosts[@]}" -eq 0 ]; then
+    log "no hosts }"
+  fi
+}
+
+function cleanup() { rm -rf "$TMP"; }
+`
+
+	chunks := chunkShell("deploy.sh", []byte(source))
+	require.Len(t, chunks, 3)
+
+	logFn := chunks[0]
+	assert.Equal(t, "log", logFn.Name)
+	assert.Equal(t, "function", logFn.ChunkType)
+	assert.Equal(t, "Prints a message to stderr.", logFn.Metadata["doc_comment"])
+	assert.Equal(t, "Deploys the application.\nUsage: deploy.sh <env>", logFn.Metadata["header"])
+	assert.Equal(t, "bash", logFn.Metadata["interpreter"])
+	assert.Equal(t, 8, logFn.LineStart)
+	assert.Equal(t, 10, logFn.LineEnd)
+
+	deploy := chunks[1]
+	assert.Equal(t, "deploy", deploy.Name)
+	assert.Equal(t, 12, deploy.LineStart)
+	assert.Equal(t, 17, deploy.LineEnd)
+
+	cleanup := chunks[2]
+	assert.Equal(t, "cleanup", cleanup.Name)
+	assert.Equal(t, 19, cleanup.LineStart)
+	assert.Equal(t, 19, cleanup.LineEnd)
+}
+
+func TestChunkShellWithoutFunctions(t *testing.T) {
+	source := `#!/bin/sh
+# Runs the test suite.
+
+go test ./...
+
+`
+
+	chunks := chunkShell("test.sh", []byte(source))
+	require.Len(t, chunks, 1)
+	assert.Equal(t, "test.sh", chunks[0].Name)
+	assert.Equal(t, "module", chunks[0].ChunkType)
+	assert.Equal(t, "Runs the test suite.", chunks[0].Metadata["header"])
+	assert.Equal(t, "sh", chunks[0].Metadata["interpreter"])
+	assert.Equal(t, 1, chunks[0].LineStart)
+	assert.Equal(t, 4, chunks[0].LineEnd)
+}
@@ -0,0 +1,282 @@
+package chunker
+
+import (
+	"strconv"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+// Strategies for chunks over a token limit
+const (
+	// TruncateSplit splits a chunk into parts that fit, the default
+	TruncateSplit = "split"
+	// TruncateHead keeps the first lines of a chunk that fit
+	TruncateHead = "head"
+	// TruncateTail keeps the last lines of a chunk that fit
+	TruncateTail = "tail"
+	// TruncateMiddle keeps the first and last lines of a chunk that fit and
+	// replaces the lines between them with a line of "..."
+	TruncateMiddle = "middle"
+)
+
+// elisionMarker stands in for the lines TruncateMiddle leaves out
+const elisionMarker = "..."
+
+// TokenLimit caps the number of tokens in the chunks embedded with one model
+type TokenLimit struct {
+	// Tokenizer measures chunks; the approximate tokenizer is used when nil
+	Tokenizer tokenizer.Tokenizer
+	// MaxTokens is the model's input limit; 0 disables splitting
+	MaxTokens int
+	// Strategy says what becomes of a chunk over the limit: TruncateSplit
+	// (the default when empty), TruncateHead, TruncateTail, or TruncateMiddle
+	Strategy string
+}
+
+// linePiece is a line of a chunk, or part of a line too long to fit on its own
+type linePiece struct {
+	text string
+	line int
+}
+
+// applyTokenLimits splits the chunks that exceed the token limit of the model
+// that will embed them
+func (s *SemanticChunker) applyTokenLimits(chunks []Chunk) []Chunk {
+	if s.options.CodeLimit.MaxTokens == 0 && s.options.DocsLimit.MaxTokens == 0 {
+		return chunks
+	}
+
+	sized := make([]Chunk, 0, len(chunks))
+	for _, chunk := range chunks {
+		limit := s.options.CodeLimit
+		if chunk.EmbeddingType == "docs" {
+			limit = s.options.DocsLimit
+		}
+		if limit.Strategy == "" || limit.Strategy == TruncateSplit {
+			sized = append(sized, limit.split(chunk)...)
+		} else {
+			sized = append(sized, limit.truncate(chunk))
+		}
+	}
+	return sized
+}
+
+// truncate cuts a chunk that is over the limit down to the lines its strategy
+// keeps, recording the strategy as truncated in its metadata. Its line range
+// shrinks to the lines kept, except for TruncateMiddle, which keeps both ends.
+func (l TokenLimit) truncate(chunk Chunk) Chunk {
+	if l.MaxTokens <= 0 {
+		return chunk
+	}
+	tok := l.Tokenizer
+	if tok == nil {
+		tok = tokenizer.Approximate{}
+	}
+	if tok.Count(chunk.Code) <= l.MaxTokens {
+		return chunk
+	}
+
+	lines := strings.Split(chunk.Code, "\n")
+	offset := (chunk.LineEnd - chunk.LineStart + 1) - len(lines)
+	newline := tok.Count("\n")
+	var pieces []linePiece
+	for i, line := range lines {
+		lineNum := chunk.LineStart + max(i+offset, 0)
+		for _, text := range fitLine(tok, line, l.MaxTokens) {
+			pieces = append(pieces, linePiece{text: text, line: lineNum})
+		}
+	}
+	cost := func(piece linePiece) int { return tok.Count(piece.text) + newline }
+
+	// take returns how many pieces from the start, or from the end, fit budget
+	take := func(fromEnd bool, budget int) int {
+		n, tokens := 0, 0
+		for n < len(pieces) {
+			piece := pieces[n]
+			if fromEnd {
+				piece = pieces[len(pieces)-1-n]
+			}
+			if tokens+cost(piece) > budget {
+				break
+			}
+			tokens += cost(piece)
+			n++
+		}
+		return n
+	}
+
+	truncated := chunk
+	switch l.Strategy {
+	case TruncateTail:
+		kept := pieces[len(pieces)-max(take(true, l.MaxTokens), 1):]
+		truncated.Code = joinPieces(kept)
+		truncated.LineStart = kept[0].line
+	case TruncateMiddle:
+		budget := l.MaxTokens - tok.Count(elisionMarker) - newline
+		head := take(false, budget/2)
+		tail := min(take(true, budget-budget/2), len(pieces)-head)
+		parts := []string{elisionMarker}
+		if head > 0 {
+			parts = append([]string{joinPieces(pieces[:head])}, parts...)
+		}
+		if tail > 0 {
+			parts = append(parts, joinPieces(pieces[len(pieces)-tail:]))
+		}
+		truncated.Code = strings.Join(parts, "\n")
+	default:
+		kept := pieces[:max(take(false, l.MaxTokens), 1)]
+		truncated.Code = joinPieces(kept)
+		truncated.LineEnd = kept[len(kept)-1].line
+	}
+
+	truncated.Metadata = make(map[string]string, len(chunk.Metadata)+1)
+	for key, value := range chunk.Metadata {
+		truncated.Metadata[key] = value
+	}
+	truncated.Metadata["truncated"] = l.Strategy
+	return truncated
+}
+
+// joinPieces joins pieces back into lines, the pieces of one line without a
+// newline between them
+func joinPieces(pieces []linePiece) string {
+	var code strings.Builder
+	for j, piece := range pieces {
+		if j > 0 && piece.line != pieces[j-1].line {
+			code.WriteByte('\n')
+		}
+		code.WriteString(piece.text)
+	}
+	return code.String()
+}
+
+// split divides a chunk that is over the limit into consecutive parts, packing
+// as many whole lines into each part as fit. Parts keep the chunk's name and
+// metadata, with part and parts recording their position and parent_id the ID
+// of the chunk they came from. Every part after the first repeats the chunk's
+// declaration line, so the sub-chunks of a long function all carry its
+// signature.
+func (l TokenLimit) split(chunk Chunk) []Chunk {
+	if l.MaxTokens <= 0 {
+		return []Chunk{chunk}
+	}
+	tok := l.Tokenizer
+	if tok == nil {
+		tok = tokenizer.Approximate{}
+	}
+	if tok.Count(chunk.Code) <= l.MaxTokens {
+		return []Chunk{chunk}
+	}
+
+	lines := strings.Split(chunk.Code, "\n")
+	// Lines prepended to the code, such as an overlap header, share the first line number
+	offset := (chunk.LineEnd - chunk.LineStart + 1) - len(lines)
+	newline := tok.Count("\n")
+
+	// Overlap context comes before the chunk's own declaration
+	skip, _ := strconv.Atoi(chunk.Metadata["overlap_before"])
+	if chunk.Metadata["overlap_header"] != "" {
+		skip++
+	}
+	header := declarationLine(lines[min(skip, len(lines)):])
+	headerCost := 0
+	if header != "" {
+		headerCost = tok.Count(header) + newline
+	}
+	// A header that leaves no room for code is left out
+	if headerCost >= l.MaxTokens/2 {
+		header, headerCost = "", 0
+	}
+
+	var (
+		groups  [][]linePiece
+		current []linePiece
+		tokens  int
+	)
+	for i, line := range lines {
+		lineNum := chunk.LineStart + max(i+offset, 0)
+		for _, text := range fitLine(tok, line, l.MaxTokens-headerCost) {
+			cost := tok.Count(text) + newline
+			if len(current) > 0 && tokens+cost > l.MaxTokens {
+				groups = append(groups, current)
+				current, tokens = nil, headerCost
+			}
+			current = append(current, linePiece{text: text, line: lineNum})
+			tokens += cost
+		}
+	}
+	if len(current) > 0 {
+		groups = append(groups, current)
+	}
+
+	parts := make([]Chunk, 0, len(groups))
+	for i, group := range groups {
+		part := chunk
+		part.ID = partID(chunk.ID, i+1)
+		part.LineStart = group[0].line
+		part.LineEnd = group[len(group)-1].line
+
+		part.Code = joinPieces(group)
+		if i > 0 && header != "" {
+			part.Code = header + "\n" + part.Code
+		}
+
+		part.Metadata = make(map[string]string, len(chunk.Metadata)+4)
+		for key, value := range chunk.Metadata {
+			part.Metadata[key] = value
+		}
+		if part.Metadata["signature"] == "" && header != "" {
+			part.Metadata["signature"] = strings.TrimSpace(header)
+		}
+		part.Metadata["parent_id"] = chunk.ID
+		part.Metadata["part"] = strconv.Itoa(i + 1)
+		part.Metadata["parts"] = strconv.Itoa(len(groups))
+		parts = append(parts, part)
+	}
+	return parts
+}
+
+// declarationLine returns the first line of a chunk that is not blank or a
+// comment, which for a function is the line its signature starts on
+func declarationLine(lines []string) string {
+	for _, line := range lines {
+		trimmed := strings.TrimSpace(line)
+		if trimmed != "" && !hasAnyPrefix(trimmed, headerSkipPrefixes) {
+			return line
+		}
+	}
+	return ""
+}
+
+// fitLine returns a line as is when it fits in budget tokens, or else split at
+// spaces, and words that are still too long split into pieces shorter than
+// budget bytes; no tokenizer produces more tokens than bytes
+func fitLine(tok tokenizer.Tokenizer, line string, budget int) []string {
+	if tok.Count(line) < budget {
+		return []string{line}
+	}
+
+	var pieces []string
+	current := ""
+	for _, word := range strings.SplitAfter(line, " ") {
+		if current != "" && tok.Count(current+word) >= budget {
+			pieces = append(pieces, current)
+			current = ""
+		}
+		for tok.Count(word) >= budget && len(word) > 1 {
+			cut := min(budget-1, len(word)-1)
+			for cut > 1 && !utf8.RuneStart(word[cut]) {
+				cut--
+			}
+			pieces = append(pieces, word[:max(cut, 1)])
+			word = word[max(cut, 1):]
+		}
+		current += word
+	}
+	if current != "" {
+		pieces = append(pieces, current)
+	}
+	return pieces
+}
@@ -0,0 +1,107 @@
+package chunker
+
+import (
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestTokenLimitSplitsChunk(t *testing.T) {
+	source := `pub fn main() void {
+    const a = 1111111111;
+    const b = 2222222222;
+    const c = 3333333333;
+}
+`
+	zigFile := filepath.Join(t.TempDir(), "main.zig")
+	require.NoError(t, os.WriteFile(zigFile, []byte(source), 0644))
+
+	// The approximate tokenizer counts the function as 25 tokens
+	semantic, err := NewSemanticWithOptions(Options{CodeLimit: TokenLimit{MaxTokens: 22}})
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile(zigFile, "zig")
+	require.NoError(t, err)
+	require.Len(t, chunks, 2)
+
+	assert.Equal(t, 1, chunks[0].LineStart)
+	assert.Equal(t, 3, chunks[0].LineEnd)
+	assert.Equal(t, "pub fn main() void {\n    const a = 1111111111;\n    const b = 2222222222;", chunks[0].Code)
+
+	// Later parts repeat the function's signature
+	assert.Equal(t, 4, chunks[1].LineStart)
+	assert.Equal(t, 5, chunks[1].LineEnd)
+	assert.Equal(t, "pub fn main() void {\n    const c = 3333333333;\n}", chunks[1].Code)
+
+	for i, chunk := range chunks {
+		assert.Equal(t, "main", chunk.Name)
+		assert.Equal(t, "function", chunk.ChunkType)
+		assert.Equal(t, strconv.Itoa(i+1), chunk.Metadata["part"])
+		assert.Equal(t, "2", chunk.Metadata["parts"])
+		assert.Equal(t, "pub fn main() void {", chunk.Metadata["signature"])
+	}
+	assert.NotEqual(t, chunks[0].ID, chunks[1].ID)
+	assert.NotEmpty(t, chunks[0].Metadata["parent_id"])
+	assert.Equal(t, chunks[0].Metadata["parent_id"], chunks[1].Metadata["parent_id"])
+}
+
+func TestTokenLimitKeepsSmallChunk(t *testing.T) {
+	chunk := Chunk{ID: "a", LineStart: 1, LineEnd: 1, Code: "fn f() {}", Metadata: map[string]string{}}
+	parts := TokenLimit{MaxTokens: 100}.split(chunk)
+	require.Len(t, parts, 1)
+	assert.Equal(t, chunk, parts[0])
+}
+
+func TestTokenLimitSplitsLongLine(t *testing.T) {
+	chunk := Chunk{ID: "a", LineStart: 7, LineEnd: 7, Code: "aaaa bbbb cccc dddd"}
+	parts := TokenLimit{MaxTokens: 3}.split(chunk)
+	require.Len(t, parts, 4)
+
+	var code strings.Builder
+	for _, part := range parts {
+		assert.Equal(t, 7, part.LineStart)
+		assert.Equal(t, 7, part.LineEnd)
+		assert.Equal(t, "a", part.Metadata["parent_id"])
+		code.WriteString(part.Code)
+	}
+	assert.Equal(t, chunk.Code, code.String())
+}
+
+func TestTokenLimitTruncates(t *testing.T) {
+	// The approximate tokenizer counts the chunk as 8 tokens and each line,
+	// with its newline, as 2
+	chunk := Chunk{ID: "a", LineStart: 1, LineEnd: 6, Code: "aaaa\nbbbb\ncccc\ndddd\neeee\nffff", Metadata: map[string]string{}}
+
+	head := TokenLimit{MaxTokens: 6, Strategy: TruncateHead}.truncate(chunk)
+	assert.Equal(t, "aaaa\nbbbb\ncccc", head.Code)
+	assert.Equal(t, 1, head.LineStart)
+	assert.Equal(t, 3, head.LineEnd)
+	assert.Equal(t, TruncateHead, head.Metadata["truncated"])
+
+	tail := TokenLimit{MaxTokens: 6, Strategy: TruncateTail}.truncate(chunk)
+	assert.Equal(t, "dddd\neeee\nffff", tail.Code)
+	assert.Equal(t, 4, tail.LineStart)
+	assert.Equal(t, 6, tail.LineEnd)
+
+	middle := TokenLimit{MaxTokens: 6, Strategy: TruncateMiddle}.truncate(chunk)
+	assert.Equal(t, "aaaa\n...\nffff", middle.Code)
+	assert.Equal(t, 1, middle.LineStart)
+	assert.Equal(t, 6, middle.LineEnd)
+
+	// The original chunk's metadata is left alone
+	assert.Empty(t, chunk.Metadata["truncated"])
+}
+
+func TestTokenLimitTruncateKeepsSmallChunk(t *testing.T) {
+	chunk := Chunk{ID: "a", LineStart: 1, LineEnd: 1, Code: "fn f() {}", Metadata: map[string]string{}}
+	assert.Equal(t, chunk, TokenLimit{MaxTokens: 100, Strategy: TruncateHead}.truncate(chunk))
+}
+
+func TestUnknownTruncationStrategy(t *testing.T) {
+	_, err := NewSemanticWithOptions(Options{CodeLimit: TokenLimit{MaxTokens: 10, Strategy: "shuffle"}})
+	assert.Error(t, err)
+}
@@ -0,0 +1,295 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches the object a CREATE statement defines, e.g.
+	// CREATE OR REPLACE FUNCTION billing.total(, CREATE TABLE IF NOT EXISTS "users"
+	sqlCreateRegex = regexp.MustCompile(`(?is)^CREATE\s+(?:OR\s+REPLACE\s+)?` +
+		`(?:(?:TEMP|TEMPORARY|UNLOGGED|MATERIALIZED|DEFINER\s*=\s*\S+|ALGORITHM\s*=\s*\w+|SQL\s+SECURITY\s+\w+)\s+)*` +
+		`(TABLE|VIEW|FUNCTION|PROCEDURE|TRIGGER)\s+(?:IF\s+NOT\s+EXISTS\s+)?` +
+		"((?:[\\w$]+|\"[^\"]+\"|`[^`]+`|\\[[^\\]]+\\])(?:\\s*\\.\\s*(?:[\\w$]+|\"[^\"]+\"|`[^`]+`|\\[[^\\]]+\\]))*)")
+	// Matches migration and query step markers used by goose, sql-migrate,
+	// dbmate, and sqlc: -- +goose Up, -- migrate:down, -- name: GetUser :one
+	sqlStepRegex = regexp.MustCompile(`(?i)^--\s*(?:\+goose\s+(up|down)\b|\+migrate\s+(up|down)\b|migrate:(up|down)\b|name:\s*([\w.-]+))`)
+	// Matches a dollar-quote tag opening a PostgreSQL function body: $$ or $body$
+	sqlDollarTagRegex = regexp.MustCompile(`^\$\w*\$`)
+
+	sqlStyle = commentStyle{line: []string{"--"}, block: true}
+)
+
+// sqlStatement is one statement of a SQL file, spanning lines[start:end+1]
+type sqlStatement struct {
+	start int
+	end   int
+	code  string // Statement text with comments removed
+}
+
+// chunkSQL chunks SQL files by the objects they define: each CREATE TABLE,
+// VIEW, FUNCTION, PROCEDURE, or TRIGGER statement becomes a chunk whose
+// "symbol" metadata holds the (schema-qualified) object name. Named migration
+// steps (goose, sql-migrate, dbmate) and sqlc queries also become chunks.
+func chunkSQL(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var chunks []Chunk
+	chunks = append(chunks, sqlSteps(filePath, lines)...)
+
+	for _, stmt := range splitSQLStatements(lines) {
+		matches := sqlCreateRegex.FindStringSubmatch(stmt.code)
+		if matches == nil {
+			continue
+		}
+
+		kind := strings.ToLower(matches[1])
+		chunkType := parser.ChunkTypeFunction
+		switch kind {
+		case "table":
+			chunkType = parser.ChunkTypeTable
+		case "view":
+			chunkType = parser.ChunkTypeView
+		}
+
+		symbol := sqlObjectName(matches[2])
+		name := symbol[strings.LastIndex(symbol, ".")+1:]
+
+		start := extendAbove(lines, stmt.start, func(trimmed string) bool {
+			return strings.HasPrefix(trimmed, "--") && !sqlStepRegex.MatchString(trimmed)
+		})
+		chunk := newLineChunk(filePath, "sql", lines, start, stmt.end, string(chunkType), name)
+		chunk.Metadata["kind"] = kind
+		chunk.Metadata["symbol"] = symbol
+		if doc := sqlCommentText(lines[start:stmt.start]); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+	}
+
+	return chunks
+}
+
+// sqlCommentText returns the text of -- comment lines with markers stripped
+func sqlCommentText(lines []string) string {
+	var doc []string
+	for _, line := range lines {
+		if text := sqlStyle.stripMarkers(strings.TrimSpace(line)); text != "" {
+			doc = append(doc, text)
+		}
+	}
+	return strings.Join(doc, "\n")
+}
+
+// sqlSteps returns a chunk for each named migration step or query, running
+// from its marker comment to the line before the next marker
+func sqlSteps(filePath string, lines []string) []Chunk {
+	type step struct {
+		line int
+		name string
+		kind string
+	}
+	var steps []step
+	for i, line := range lines {
+		matches := sqlStepRegex.FindStringSubmatch(strings.TrimSpace(line))
+		if matches == nil {
+			continue
+		}
+		if matches[4] != "" {
+			steps = append(steps, step{line: i, name: matches[4], kind: "query"})
+			continue
+		}
+		direction := strings.ToLower(matches[1] + matches[2] + matches[3])
+		steps = append(steps, step{line: i, name: direction, kind: "migration"})
+	}
+
+	var chunks []Chunk
+	for n, s := range steps {
+		end := len(lines) - 1
+		if n+1 < len(steps) {
+			end = steps[n+1].line - 1
+		}
+		for end > s.line && strings.TrimSpace(lines[end]) == "" {
+			end--
+		}
+
+		chunk := newLineChunk(filePath, "sql", lines, s.line, end, string(parser.ChunkTypeBlock), s.name)
+		chunk.Metadata["kind"] = s.kind
+		chunk.Metadata["symbol"] = s.name
+		chunks = append(chunks, chunk)
+	}
+	return chunks
+}
+
+// sqlObjectName normalizes a possibly quoted, schema-qualified object name:
+// "billing" . [Invoices] becomes billing.Invoices
+func sqlObjectName(raw string) string {
+	parts := strings.Split(raw, ".")
+	for i, part := range parts {
+		parts[i] = strings.Trim(strings.TrimSpace(part), "\"`[]")
+	}
+	return strings.Join(parts, ".")
+}
+
+// splitSQLStatements splits SQL source into statements at the current
+// delimiter (";" unless changed with MySQL's DELIMITER command), ignoring
+// delimiters inside strings, comments, dollar-quoted bodies, and BEGIN ... END
+// blocks of stored routines
+func splitSQLStatements(lines []string) []sqlStatement {
+	var (
+		statements []sqlStatement
+		code       strings.Builder
+		start      = -1
+		delimiter  = ";"
+		inComment  bool
+		quote      byte
+		dollarTag  string
+		depth      int
+		prevWord   string
+	)
+
+	finish := func(end int) {
+		if start >= 0 {
+			statements = append(statements, sqlStatement{start: start, end: end, code: strings.TrimSpace(code.String())})
+		}
+		code.Reset()
+		start = -1
+		depth = 0
+		prevWord = ""
+	}
+
+	for i, line := range lines {
+		trimmed := strings.TrimSpace(line)
+		if start < 0 && !inComment && strings.HasPrefix(strings.ToUpper(trimmed), "DELIMITER ") {
+			delimiter = strings.TrimSpace(trimmed[len("DELIMITER "):])
+			continue
+		}
+
+		for j := 0; j < len(line); j++ {
+			c := line[j]
+			switch {
+			case inComment:
+				if strings.HasPrefix(line[j:], "*/") {
+					inComment = false
+					j++
+				}
+				continue
+			case dollarTag != "":
+				if strings.HasPrefix(line[j:], dollarTag) {
+					code.WriteString(dollarTag)
+					j += len(dollarTag) - 1
+					dollarTag = ""
+				}
+				continue
+			case quote != 0:
+				code.WriteByte(c)
+				if c == quote {
+					quote = 0
+				}
+				continue
+			case strings.HasPrefix(line[j:], "--"):
+				j = len(line)
+				continue
+			case strings.HasPrefix(line[j:], "/*"):
+				inComment = true
+				j++
+				continue
+			case c == ' ' || c == '\t' || c == '\r':
+				code.WriteByte(' ')
+				continue
+			}
+
+			if start < 0 {
+				start = i
+			}
+
+			if depth <= 0 && strings.HasPrefix(line[j:], delimiter) {
+				finish(i)
+				j += len(delimiter) - 1
+				continue
+			}
+
+			switch {
+			case c == '\'' || c == '"' || c == '`':
+				quote = c
+				code.WriteByte(c)
+			case c == '$' && sqlDollarTagRegex.MatchString(line[j:]):
+				dollarTag = sqlDollarTagRegex.FindString(line[j:])
+				code.WriteString(dollarTag)
+				j += len(dollarTag) - 1
+			case isSQLWordStart(line, j):
+				word := strings.ToUpper(sqlWordAt(line, j))
+				depth += sqlBlockDelta(prevWord, word, line[j+len(word):], delimiter)
+				prevWord = word
+				code.WriteString(line[j : j+len(word)])
+				j += len(word) - 1
+			default:
+				code.WriteByte(c)
+			}
+		}
+		code.WriteByte('\n')
+	}
+
+	// A final statement without a delimiter ends at the last non-blank line
+	if start >= 0 {
+		end := len(lines) - 1
+		for end > start && strings.TrimSpace(lines[end]) == "" {
+			end--
+		}
+		finish(end)
+	}
+
+	return statements
+}
+
+// sqlBlockDelta returns how a keyword changes the BEGIN ... END nesting depth.
+// BEGIN starting a transaction and END closing IF, LOOP, or similar statements
+// do not count; CASE counts since it is closed by a bare END.
+func sqlBlockDelta(prev, word, rest, delimiter string) int {
+	rest = strings.TrimSpace(rest)
+	next := strings.ToUpper(sqlWordAt(rest, 0))
+	switch word {
+	case "BEGIN":
+		if strings.HasPrefix(rest, delimiter) || next == "TRANSACTION" || next == "WORK" || next == "TRAN" {
+			return 0
+		}
+		return 1
+	case "CASE":
+		if prev == "END" {
+			// END CASE was already counted by its END
+			return 0
+		}
+		return 1
+	case "END":
+		switch next {
+		case "IF", "LOOP", "WHILE", "REPEAT", "FOR":
+			return 0
+		}
+		return -1
+	}
+	return 0
+}
+
+// isSQLWordStart reports whether line[j] begins an identifier or keyword
+func isSQLWordStart(line string, j int) bool {
+	if !isSQLWordChar(line[j]) || (line[j] >= '0' && line[j] <= '9') {
+		return false
+	}
+	return j == 0 || !isSQLWordChar(line[j-1])
+}
+
+// sqlWordAt returns the identifier or keyword starting at text[j]
+func sqlWordAt(text string, j int) string {
+	end := j
+	for end < len(text) && isSQLWordChar(text[end]) {
+		end++
+	}
+	return text[j:end]
+}
+
+func isSQLWordChar(c byte) bool {
+	return c == '_' || c == '$' || (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c >= '0' && c <= '9')
+}
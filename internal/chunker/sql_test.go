@@ -0,0 +1,97 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkSQL(t *testing.T) {
+	source := "-- +goose Up\n" +
+		"-- Application users.\n" +
+		"CREATE TABLE IF NOT EXISTS \"public\".\"users\" (\n" +
+		"    id SERIAL PRIMARY KEY,\n" +
+		"    email TEXT NOT NULL -- unique; enforced below\n" +
+		");\n" +
+		"\n" +
+		"CREATE OR REPLACE VIEW active_users AS\n" +
+		"    SELECT * FROM users WHERE status = 'active;';\n" +
+		"\n" +
+		"CREATE FUNCTION billing.total(x int) RETURNS int AS $$\n" +
+		"BEGIN\n" +
+		"    RETURN x * 2;\n" +
+		"END;\n" +
+		"$$ LANGUAGE plpgsql;\n" +
+		"\n" +
+		"-- +goose Down\n" +
+		"DROP TABLE users;\n" +
+		"\n" +
+		"DELIMITER //\n" +
+		"CREATE PROCEDURE `close_accounts`()\n" +
+		"BEGIN\n" +
+		"    IF 1 THEN\n" +
+		"        UPDATE accounts SET closed = CASE WHEN x THEN 1 ELSE 0 END;\n" +
+		"    END IF;\n" +
+		"END //\n" +
+		"DELIMITER ;\n"
+
+	chunks := chunkSQL("001_init.sql", []byte(source))
+	require.Len(t, chunks, 6)
+
+	up := chunks[0]
+	assert.Equal(t, "up", up.Name)
+	assert.Equal(t, "block", up.ChunkType)
+	assert.Equal(t, "migration", up.Metadata["kind"])
+	assert.Equal(t, 1, up.LineStart)
+	assert.Equal(t, 15, up.LineEnd)
+
+	down := chunks[1]
+	assert.Equal(t, "down", down.Name)
+	assert.Equal(t, 17, down.LineStart)
+	assert.Equal(t, 27, down.LineEnd)
+
+	users := chunks[2]
+	assert.Equal(t, "users", users.Name)
+	assert.Equal(t, "table", users.ChunkType)
+	assert.Equal(t, "public.users", users.Metadata["symbol"])
+	assert.Equal(t, "Application users.", users.Metadata["doc_comment"])
+	assert.Equal(t, 2, users.LineStart)
+	assert.Equal(t, 6, users.LineEnd)
+
+	view := chunks[3]
+	assert.Equal(t, "active_users", view.Name)
+	assert.Equal(t, "view", view.ChunkType)
+	assert.Equal(t, 8, view.LineStart)
+	assert.Equal(t, 9, view.LineEnd)
+
+	total := chunks[4]
+	assert.Equal(t, "total", total.Name)
+	assert.Equal(t, "function", total.ChunkType)
+	assert.Equal(t, "billing.total", total.Metadata["symbol"])
+	assert.Equal(t, 11, total.LineStart)
+	assert.Equal(t, 15, total.LineEnd)
+
+	procedure := chunks[5]
+	assert.Equal(t, "close_accounts", procedure.Name)
+	assert.Equal(t, "procedure", procedure.Metadata["kind"])
+	assert.Equal(t, 21, procedure.LineStart)
+	assert.Equal(t, 26, procedure.LineEnd)
+}
+
+func TestChunkSQLQueries(t *testing.T) {
+	source := `-- name: GetUser :one
+SELECT * FROM users WHERE id = $1;
+
+-- name: ListUsers :many
+SELECT * FROM users ORDER BY email;
+`
+
+	chunks := chunkSQL("queries.sql", []byte(source))
+	require.Len(t, chunks, 2)
+	assert.Equal(t, "GetUser", chunks[0].Name)
+	assert.Equal(t, "query", chunks[0].Metadata["kind"])
+	assert.Equal(t, "GetUser", chunks[0].Metadata["symbol"])
+	assert.Equal(t, 2, chunks[0].LineEnd)
+	assert.Equal(t, "ListUsers", chunks[1].Name)
+}
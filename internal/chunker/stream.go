@@ -0,0 +1,142 @@
+package chunker
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+)
+
+// DefaultStreamThreshold is the file size above which StreamFile reads a file
+// a line at a time instead of parsing it whole
+const DefaultStreamThreshold = 4 << 20
+
+// Streamed files are cut into blocks of whole lines
+const (
+	streamMinLines   = 20  // A block ends at a top-level line only after this many lines
+	streamMaxLines   = 200 // A block ends here regardless
+	streamBatchSize  = 64  // Blocks passed to emit at a time
+	streamReadBuffer = 64 * 1024
+)
+
+// streamThreshold returns the file size above which files are streamed
+func (s *SemanticChunker) streamThreshold() int64 {
+	if s.options.StreamThreshold > 0 {
+		return s.options.StreamThreshold
+	}
+	return DefaultStreamThreshold
+}
+
+// Streams reports whether StreamFile reads a file a line at a time rather than
+// chunking it whole
+func (s *SemanticChunker) Streams(filePath string) bool {
+	info, err := os.Stat(filePath)
+	return err == nil && info.Size() > s.streamThreshold()
+}
+
+// StreamFile chunks a file and passes its chunks to emit as they are made.
+// Files up to StreamThreshold go through ChunkFile and are emitted at once.
+// Larger files are never held in memory whole: they are read a line at a time
+// and cut into blocks at blank lines followed by a top-level line, or every
+// streamMaxLines lines, and emitted a batch of blocks at a time. Streamed
+// chunks have no symbol names or overlap, but are tagged and split to the
+// token limits like any other.
+func (s *SemanticChunker) StreamFile(filePath, language string, emit func([]Chunk) error) error {
+	if !s.Streams(filePath) {
+		chunks, err := s.ChunkFile(filePath, language)
+		if err != nil || len(chunks) == 0 {
+			return err
+		}
+		return emit(chunks)
+	}
+
+	skip, err := isBinaryOrMinified(filePath, language)
+	if err != nil {
+		return err
+	}
+	if skip {
+		return nil
+	}
+	generated, err := isGeneratedFile(filePath)
+	if err != nil {
+		return err
+	}
+	if generated && s.options.SkipGenerated {
+		return nil
+	}
+
+	file, err := os.Open(filePath)
+	if err != nil {
+		return fmt.Errorf("failed to read file: %w", err)
+	}
+	defer file.Close()
+
+	embeddingType := "code"
+	switch language {
+	case "markdown", "text", "rst":
+		embeddingType = "docs"
+	}
+
+	var block []string
+	var pending []Chunk
+	blockStart := 1
+	// Shared by every batch, so identical blocks keep distinct IDs
+	occurrences := make(map[string]int)
+	cut := func() {
+		if len(block) == 0 {
+			return
+		}
+		chunk := newLineChunk(filePath, language, block, 0, len(block)-1, "block", "")
+		chunk.LineStart, chunk.LineEnd = blockStart, blockStart+len(block)-1
+		chunk.EmbeddingType = embeddingType
+		if generated {
+			setMetadata(&chunk, "generated", "true")
+		}
+		pending = append(pending, chunk)
+		blockStart += len(block)
+		block = nil
+	}
+	flush := func() error {
+		if len(pending) == 0 {
+			return nil
+		}
+		tagTests(filePath, pending)
+		assignIDsFrom(pending, occurrences)
+		chunks := s.applyTokenLimits(pending)
+		pending = nil
+		return emit(chunks)
+	}
+
+	reader := bufio.NewReaderSize(file, streamReadBuffer)
+	for {
+		line, readErr := reader.ReadString('\n')
+		if readErr != nil && readErr != io.EOF {
+			return fmt.Errorf("failed to read file: %w", readErr)
+		}
+		if line != "" {
+			line = strings.TrimSuffix(strings.TrimSuffix(line, "\n"), "\r")
+			if len(block) >= streamMaxLines ||
+				len(block) >= streamMinLines && strings.TrimSpace(block[len(block)-1]) == "" && startsTopLevel(line) {
+				cut()
+			}
+			block = append(block, line)
+			if len(pending) >= streamBatchSize {
+				if err := flush(); err != nil {
+					return err
+				}
+			}
+		}
+		if readErr == io.EOF {
+			break
+		}
+	}
+	cut()
+	return flush()
+}
+
+// startsTopLevel reports whether a line starts at the left margin, as the
+// declarations and headings that begin a new block do
+func startsTopLevel(line string) bool {
+	return line != "" && line[0] != ' ' && line[0] != '\t'
+}
@@ -0,0 +1,83 @@
+package chunker
+
+import (
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func streamFile(t *testing.T, c *SemanticChunker, path, language string) ([]Chunk, int) {
+	t.Helper()
+	var chunks []Chunk
+	emits := 0
+	require.NoError(t, c.StreamFile(path, language, func(batch []Chunk) error {
+		chunks = append(chunks, batch...)
+		emits++
+		return nil
+	}))
+	return chunks, emits
+}
+
+func TestStreamFileCutsAtTopLevelLines(t *testing.T) {
+	var source strings.Builder
+	source.WriteString("package big\n")
+	for i := 0; i < 200; i++ {
+		fmt.Fprintf(&source, "\nfunc f%d() int {\n\tx := %d\n\treturn x\n}\n", i, i)
+	}
+	path := writeSource(t, "big.go", source.String())
+	totalLines := strings.Count(source.String(), "\n")
+
+	c, err := NewSemanticWithOptions(Options{StreamThreshold: 1})
+	require.NoError(t, err)
+	require.True(t, c.Streams(path))
+
+	chunks, emits := streamFile(t, c, path, "go")
+	require.NotEmpty(t, chunks)
+	assert.Greater(t, emits, 0)
+
+	next := 1
+	ids := make(map[string]bool)
+	for i, chunk := range chunks {
+		assert.Equal(t, next, chunk.LineStart, "chunks cover the file in order")
+		assert.LessOrEqual(t, chunk.LineEnd-chunk.LineStart+1, streamMaxLines)
+		if i > 0 {
+			assert.True(t, strings.HasPrefix(chunk.Code, "func "), chunk.Code)
+		}
+		assert.Equal(t, "code", chunk.EmbeddingType)
+		assert.False(t, ids[chunk.ID], "IDs are unique")
+		ids[chunk.ID] = true
+		next = chunk.LineEnd + 1
+	}
+	assert.Equal(t, totalLines, chunks[len(chunks)-1].LineEnd)
+}
+
+func TestStreamFileCapsBlockLines(t *testing.T) {
+	source := strings.Repeat("\tvalue,\n", 500)
+	path := writeSource(t, "table.go", source)
+
+	c, err := NewSemanticWithOptions(Options{StreamThreshold: 1})
+	require.NoError(t, err)
+	chunks, _ := streamFile(t, c, path, "go")
+	require.Len(t, chunks, 3)
+	assert.Equal(t, 1, chunks[0].LineStart)
+	assert.Equal(t, streamMaxLines, chunks[0].LineEnd)
+	assert.Equal(t, 500, chunks[2].LineEnd)
+	assert.NotEqual(t, chunks[0].ID, chunks[1].ID, "identical blocks get distinct IDs")
+}
+
+func TestStreamFileChunksSmallFilesWhole(t *testing.T) {
+	path := writeSource(t, "small.go", "package small\n\nfunc main() {}\n")
+
+	c, err := NewSemantic()
+	require.NoError(t, err)
+	require.False(t, c.Streams(path))
+
+	streamed, emits := streamFile(t, c, path, "go")
+	chunked, err := c.ChunkFile(path, "go")
+	require.NoError(t, err)
+	assert.Equal(t, 1, emits)
+	assert.Equal(t, chunked, streamed)
+}
@@ -0,0 +1,124 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+const (
+	swiftAttributes = `^\s*((?:@\w+(?:\([^)]*\))?\s+)*)`
+	swiftModifiers  = `((?:(?:public|private|fileprivate|internal|open|final|static|class|override|mutating|nonmutating|convenience|required|lazy|weak|unowned|dynamic|indirect|nonisolated)(?:\([^)]*\))?\s+)*)`
+)
+
+var (
+	// Matches type declarations and extensions: public struct Foo, extension Array where ...
+	swiftTypeRegex = regexp.MustCompile(swiftAttributes + swiftModifiers + `(struct|class|enum|protocol|extension|actor)\s+([\w.]+)`)
+	// Matches functions, initializers, and subscripts
+	swiftFuncRegex = regexp.MustCompile(swiftAttributes + swiftModifiers + `(?:func\s+([^\s(<]+)|(init[?!]?|subscript)\s*(?:<[^>]*>)?\s*\(|(deinit)\s*\{)`)
+	// Matches properties that carry an attribute, e.g. @State private var count = 0
+	swiftWrappedPropertyRegex = regexp.MustCompile(`^\s*((?:@\w+(?:\([^)]*\))?\s+)+)` + swiftModifiers + `(?:var|let)\s+(\w+)`)
+	// Matches a line holding only attributes, e.g. @MainActor or @available(iOS 15, *)
+	swiftAttributeLineRegex = regexp.MustCompile(`^(?:@\w+(?:\(.*\))?\s*)+$`)
+)
+
+// chunkSwift extracts structs, classes, enums, protocols, extensions, actors,
+// functions, and attributed properties (property wrappers such as @State or
+// @Published) from Swift source, keeping /// doc comments as metadata.
+func chunkSwift(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type typeScope struct {
+		name string
+		end  int
+	}
+	var (
+		chunks []Chunk
+		types  []typeScope
+	)
+
+	for i := 0; i < len(lines); i++ {
+		for len(types) > 0 && i > types[len(types)-1].end {
+			types = types[:len(types)-1]
+		}
+
+		var (
+			chunkType  parser.ChunkType
+			name       string
+			attributes string
+			isType     bool
+		)
+
+		line := lines[i]
+		if matches := swiftTypeRegex.FindStringSubmatch(line); matches != nil && !isSwiftMemberKeyword(matches[4]) {
+			attributes, name, isType = matches[1], matches[4], true
+			switch matches[3] {
+			case "struct":
+				chunkType = parser.ChunkTypeStruct
+			case "enum":
+				chunkType = parser.ChunkTypeEnum
+			case "protocol":
+				chunkType = parser.ChunkTypeInterface
+			case "extension":
+				chunkType = parser.ChunkTypeImpl
+			default:
+				chunkType = parser.ChunkTypeClass
+			}
+		} else if matches := swiftFuncRegex.FindStringSubmatch(line); matches != nil {
+			attributes, name = matches[1], matches[3]+matches[4]+matches[5]
+			chunkType = parser.ChunkTypeFunction
+			if len(types) > 0 {
+				chunkType = parser.ChunkTypeMethod
+			}
+		} else if matches := swiftWrappedPropertyRegex.FindStringSubmatch(line); matches != nil && len(types) > 0 {
+			attributes, name = matches[1], matches[3]
+			chunkType = parser.ChunkTypeProperty
+		} else {
+			continue
+		}
+
+		start := extendAbove(lines, i, swiftAttributeLineRegex.MatchString)
+		end := cStyle.declarationEnd(lines, i)
+		chunk := newLineChunk(filePath, "swift", lines, start, end, string(chunkType), name)
+
+		var attrs []string
+		for _, attrLine := range lines[start:i] {
+			attrs = append(attrs, strings.Fields(attrLine)...)
+		}
+		attrs = append(attrs, strings.Fields(attributes)...)
+		if len(attrs) > 0 {
+			key := "attributes"
+			if chunkType == parser.ChunkTypeProperty {
+				key = "property_wrapper"
+			}
+			chunk.Metadata[key] = strings.Join(attrs, " ")
+		}
+		if len(types) > 0 {
+			chunk.Metadata["receiver"] = types[len(types)-1].name
+		}
+		if doc := cStyle.leadingComment(lines, start); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		// Types contain members, so keep scanning inside them; skip function bodies
+		if isType {
+			types = append(types, typeScope{name: name, end: end})
+		} else {
+			i = end
+		}
+	}
+
+	return chunks
+}
+
+// isSwiftMemberKeyword reports whether a word following `class` makes it a
+// modifier rather than a class declaration (class func, class var)
+func isSwiftMemberKeyword(word string) bool {
+	switch word {
+	case "func", "var", "let", "subscript", "init":
+		return true
+	}
+	return false
+}
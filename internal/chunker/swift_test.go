@@ -0,0 +1,83 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkSwift(t *testing.T) {
+	source := `import SwiftUI
+
+/// A counter screen.
+struct CounterView: View {
+    @State private var count = 0
+
+    var body: some View {
+        Button("Tap") { count += 1 }
+    }
+}
+
+protocol Store {
+    func load() -> [Item]
+}
+
+extension Array where Element == Item {
+    /// Sorted by name.
+    func sortedByName() -> [Item] {
+        sorted { $0.name < $1.name }
+    }
+}
+
+@MainActor
+final class ViewModel: ObservableObject {
+    @Published var items: [Item] = []
+
+    init(items: [Item]) {
+        self.items = items
+    }
+
+    class func shared() -> ViewModel {
+        ViewModel(items: [])
+    }
+}
+`
+
+	chunks := chunkSwift("CounterView.swift", []byte(source))
+	require.Len(t, chunks, 10)
+
+	byName := make(map[string]Chunk)
+	for _, chunk := range chunks {
+		byName[chunk.Name] = chunk
+	}
+
+	counter := byName["CounterView"]
+	assert.Equal(t, "struct", counter.ChunkType)
+	assert.Equal(t, "A counter screen.", counter.Metadata["doc_comment"])
+	assert.Equal(t, 10, counter.LineEnd)
+
+	count := byName["count"]
+	assert.Equal(t, "property", count.ChunkType)
+	assert.Equal(t, "@State", count.Metadata["property_wrapper"])
+	assert.Equal(t, "CounterView", count.Metadata["receiver"])
+
+	assert.Equal(t, "interface", byName["Store"].ChunkType)
+	assert.Equal(t, 13, byName["load"].LineEnd, "protocol requirements have no body")
+
+	ext := byName["Array"]
+	assert.Equal(t, "impl", ext.ChunkType)
+	sorted := byName["sortedByName"]
+	assert.Equal(t, "method", sorted.ChunkType)
+	assert.Equal(t, "Array", sorted.Metadata["receiver"])
+	assert.Equal(t, "Sorted by name.", sorted.Metadata["doc_comment"])
+
+	viewModel := byName["ViewModel"]
+	assert.Equal(t, "class", viewModel.ChunkType)
+	assert.Equal(t, 23, viewModel.LineStart, "chunk should start at the attribute line")
+	assert.Equal(t, "@MainActor", viewModel.Metadata["attributes"])
+
+	assert.Equal(t, "@Published", byName["items"].Metadata["property_wrapper"])
+	assert.Equal(t, "ViewModel", byName["init"].Metadata["receiver"])
+	assert.Equal(t, "method", byName["shared"].ChunkType)
+}
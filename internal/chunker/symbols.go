@@ -0,0 +1,32 @@
+package chunker
+
+// qualifiedSeparators holds the path separator of languages that do not use "."
+var qualifiedSeparators = map[string]string{
+	"rust": "::",
+	"cpp":  "::",
+	"php":  "::",
+	"ruby": "::",
+	"perl": "::",
+}
+
+// qualifyNames sets the qualified_name of code chunks that do not have one,
+// such as Point.distance for a method, from their receiver and name. Chunkers
+// that know a chunk's module path, like the Rust extractor, set it themselves.
+func qualifyNames(chunks []Chunk) {
+	for i := range chunks {
+		chunk := &chunks[i]
+		if chunk.EmbeddingType != "code" || chunk.Name == "" || chunk.Metadata["qualified_name"] != "" {
+			continue
+		}
+
+		qualified := chunk.Name
+		if receiver := chunk.Metadata["receiver"]; receiver != "" {
+			separator, ok := qualifiedSeparators[chunk.Language]
+			if !ok {
+				separator = "."
+			}
+			qualified = receiver + separator + chunk.Name
+		}
+		setMetadata(chunk, "qualified_name", qualified)
+	}
+}
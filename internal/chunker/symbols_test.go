@@ -0,0 +1,47 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestQualifyNames(t *testing.T) {
+	chunks := []Chunk{
+		{Name: "distance", Language: "python", EmbeddingType: "code", Metadata: map[string]string{"receiver": "Point"}},
+		{Name: "find", Language: "cpp", EmbeddingType: "code", Metadata: map[string]string{"receiver": "UserRepository"}},
+		{Name: "main", Language: "go", EmbeddingType: "code"},
+		{Name: "slugify", Language: "rust", EmbeddingType: "code", Metadata: map[string]string{"qualified_name": "utils::slugify"}},
+		{Name: "Usage", Language: "markdown", EmbeddingType: "docs", Metadata: map[string]string{}},
+	}
+	qualifyNames(chunks)
+
+	assert.Equal(t, "Point.distance", chunks[0].Metadata["qualified_name"])
+	assert.Equal(t, "UserRepository::find", chunks[1].Metadata["qualified_name"])
+	assert.Equal(t, "main", chunks[2].Metadata["qualified_name"])
+	assert.Equal(t, "utils::slugify", chunks[3].Metadata["qualified_name"])
+	assert.NotContains(t, chunks[4].Metadata, "qualified_name")
+}
+
+func TestQualifiedNamesInRustSample(t *testing.T) {
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile("testdata/sample.rs", "rust")
+	require.NoError(t, err)
+
+	qualified := make(map[string]bool)
+	for _, chunk := range chunks {
+		qualified[chunk.Metadata["qualified_name"]] = true
+	}
+	for _, name := range []string{
+		"greet",
+		"utils::slugify",
+		"UserRepository::find_all",
+		"UserRepository::find",
+		"Container<T>::map",
+		"tests::test_greet",
+	} {
+		assert.True(t, qualified[name], "missing qualified name %s", name)
+	}
+}
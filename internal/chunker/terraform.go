@@ -0,0 +1,83 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches a top-level block and its labels: resource "aws_instance" "web" {
+	hclBlockRegex = regexp.MustCompile(`^([a-z_]+)((?:\s+(?:"[^"]*"|[\w-]+))*)\s*\{`)
+	// Matches a block label, quoted or bare
+	hclLabelRegex = regexp.MustCompile(`"([^"]*)"|([\w-]+)`)
+	// Matches a description attribute: description = "AWS region to deploy to"
+	hclDescriptionRegex = regexp.MustCompile(`^\s*description\s*=\s*"((?:[^"\\]|\\.)*)"`)
+
+	hclStyle = commentStyle{line: []string{"#", "//"}, block: true}
+)
+
+// chunkTerraform extracts top-level blocks (resource, data, module, variable,
+// output, provider, locals, terraform) from Terraform and HCL files. Each
+// chunk's "symbol" metadata is the address Terraform uses for the block, such
+// as aws_instance.web, module.vpc, or var.region.
+func chunkTerraform(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var chunks []Chunk
+	for i := 0; i < len(lines); i++ {
+		matches := hclBlockRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			continue
+		}
+
+		blockType := matches[1]
+		var labels []string
+		for _, label := range hclLabelRegex.FindAllStringSubmatch(matches[2], -1) {
+			labels = append(labels, label[1]+label[2])
+		}
+
+		name := blockType
+		if len(labels) > 0 {
+			name = labels[len(labels)-1]
+		}
+
+		end := hclStyle.blockEnd(lines, i)
+		chunk := newLineChunk(filePath, "terraform", lines, i, end, string(parser.ChunkTypeBlock), name)
+		chunk.Metadata["kind"] = blockType
+		chunk.Metadata["symbol"] = hclAddress(blockType, labels)
+		if (blockType == "resource" || blockType == "data") && len(labels) == 2 {
+			chunk.Metadata["resource_type"] = labels[0]
+		}
+
+		doc := hclStyle.leadingComment(lines, i)
+		if doc == "" {
+			for _, line := range lines[i+1 : end+1] {
+				if description := hclDescriptionRegex.FindStringSubmatch(line); description != nil {
+					doc = description[1]
+					break
+				}
+			}
+		}
+		if doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		i = end
+	}
+
+	return chunks
+}
+
+// hclAddress returns the address Terraform uses to refer to a block
+func hclAddress(blockType string, labels []string) string {
+	switch blockType {
+	case "resource":
+		return strings.Join(labels, ".")
+	case "variable":
+		blockType = "var"
+	}
+	return strings.Join(append([]string{blockType}, labels...), ".")
+}
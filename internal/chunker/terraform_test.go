@@ -0,0 +1,56 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkTerraform(t *testing.T) {
+	source := `variable "region" {
+  description = "AWS region to deploy to"
+  default     = "us-east-1"
+}
+
+# Web server for the public site.
+resource "aws_instance" "web" {
+  ami           = data.aws_ami.ubuntu.id
+  instance_type = "t3.micro"
+
+  tags = {
+    Name = "web-${var.region}"
+  }
+}
+
+module "vpc" {
+  source = "./modules/vpc"
+}
+
+locals {
+  env = "prod"
+}
+`
+
+	chunks := chunkTerraform("main.tf", []byte(source))
+	require.Len(t, chunks, 4)
+
+	region := chunks[0]
+	assert.Equal(t, "region", region.Name)
+	assert.Equal(t, "variable", region.Metadata["kind"])
+	assert.Equal(t, "var.region", region.Metadata["symbol"])
+	assert.Equal(t, "AWS region to deploy to", region.Metadata["doc_comment"])
+	assert.Equal(t, 4, region.LineEnd)
+
+	web := chunks[1]
+	assert.Equal(t, "web", web.Name)
+	assert.Equal(t, "aws_instance.web", web.Metadata["symbol"])
+	assert.Equal(t, "aws_instance", web.Metadata["resource_type"])
+	assert.Equal(t, "Web server for the public site.", web.Metadata["doc_comment"])
+	assert.Equal(t, 7, web.LineStart)
+	assert.Equal(t, 15, web.LineEnd)
+
+	assert.Equal(t, "module.vpc", chunks[2].Metadata["symbol"])
+	assert.Equal(t, "locals", chunks[3].Name)
+	assert.Equal(t, "locals", chunks[3].Metadata["symbol"])
+}
@@ -0,0 +1,97 @@
+package chunker
+
+import (
+	"path/filepath"
+	"strings"
+)
+
+// testDirs are directories whose files are all tests
+var testDirs = map[string]bool{
+	"test":      true,
+	"tests":     true,
+	"__tests__": true,
+	"spec":      true,
+}
+
+// testFileSuffixes are file name endings used for test files
+var testFileSuffixes = []string{
+	"_test.go",
+	"_test.py",
+	"_test.rs",
+	"_spec.rb",
+	"_test.rb",
+	"_test.exs",
+	"Test.java",
+	"Tests.java",
+	"Test.kt",
+	"Tests.cs",
+	"Tests.swift",
+}
+
+// testFileMarkers are infixes of JavaScript and TypeScript test files, as in
+// user.spec.ts or user.test.js
+var testFileMarkers = []string{".spec.", ".test."}
+
+// testAttributes are attributes and annotations that mark a test function
+var testAttributes = []string{"#[test]", "#[tokio::test]", "#[rstest]", "@Test", "@ParameterizedTest", "[Test]", "[Fact]", "[Theory]"}
+
+// tagTests sets test to "true" on the chunks of test files and on chunks
+// marked as tests by their attributes, so search can include or exclude them.
+// Chunkers may tag chunks themselves, as the Rust extractor does for items in
+// #[cfg(test)] modules.
+func tagTests(filePath string, chunks []Chunk) {
+	testFile := isTestFile(filePath)
+	for i := range chunks {
+		chunk := &chunks[i]
+		if chunk.EmbeddingType != "code" {
+			continue
+		}
+		if !testFile && !hasTestAttribute(chunk.Metadata["attributes"]) {
+			continue
+		}
+		setMetadata(chunk, "test", "true")
+	}
+}
+
+// isTestFile reports whether a file is a test by its name or directory
+func isTestFile(filePath string) bool {
+	base := filepath.Base(filePath)
+	for _, suffix := range testFileSuffixes {
+		if strings.HasSuffix(base, suffix) {
+			return true
+		}
+	}
+	for _, marker := range testFileMarkers {
+		if strings.Contains(base, marker) {
+			return true
+		}
+	}
+	if strings.HasPrefix(base, "test_") && strings.HasSuffix(base, ".py") {
+		return true
+	}
+
+	for _, dir := range strings.Split(filepath.ToSlash(filepath.Dir(filePath)), "/") {
+		if testDirs[dir] {
+			return true
+		}
+	}
+	return false
+}
+
+// hasTestAttribute reports whether a chunk's attributes mark it as a test,
+// with or without arguments such as #[tokio::test(flavor = "multi_thread")]
+func hasTestAttribute(attributes string) bool {
+	for _, attribute := range strings.Split(attributes, ", ") {
+		attribute = strings.TrimSpace(attribute)
+		for _, marker := range testAttributes {
+			open := marker + "("
+			if closing := strings.LastIndexByte(marker, ']'); closing >= 0 {
+				open = marker[:closing] + "("
+			}
+			if attribute == marker || strings.HasPrefix(attribute, open) {
+				return true
+			}
+		}
+	}
+	return false
+}
@@ -0,0 +1,57 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestIsTestFile(t *testing.T) {
+	tests := []struct {
+		path     string
+		expected bool
+	}{
+		{"internal/chunker/semantic_test.go", true},
+		{"src/user.spec.ts", true},
+		{"src/user.test.js", true},
+		{"tests/integration.rs", true},
+		{"app/__tests__/App.jsx", true},
+		{"tests/test_api.py", true},
+		{"test_models.py", true},
+		{"src/main/java/UserServiceTest.java", true},
+		{"internal/chunker/semantic.go", false},
+		{"src/testing.ts", false},
+		{"internal/chunker/testdata/sample.rs", false},
+		{"src/contest.py", false},
+	}
+	for _, tt := range tests {
+		assert.Equal(t, tt.expected, isTestFile(tt.path), tt.path)
+	}
+}
+
+func TestHasTestAttribute(t *testing.T) {
+	assert.True(t, hasTestAttribute("#[test]"))
+	assert.True(t, hasTestAttribute(`#[tokio::test(flavor = "multi_thread")]`))
+	assert.True(t, hasTestAttribute("@Test"))
+	assert.True(t, hasTestAttribute("@DisplayName(\"x\"), @ParameterizedTest"))
+	assert.False(t, hasTestAttribute("#[derive(Debug, Clone)]"))
+	assert.False(t, hasTestAttribute("@TestConfiguration"))
+	assert.False(t, hasTestAttribute(""))
+}
+
+func TestTagTestsInRustSample(t *testing.T) {
+	semantic, err := NewSemantic()
+	require.NoError(t, err)
+	chunks, err := semantic.ChunkFile("testdata/sample.rs", "rust")
+	require.NoError(t, err)
+
+	tests := make(map[string]bool)
+	for _, chunk := range chunks {
+		tests[chunk.Name] = chunk.Metadata["test"] == "true"
+	}
+	assert.True(t, tests["test_greet"])
+	assert.True(t, tests["test_point_distance"])
+	assert.False(t, tests["greet"])
+	assert.False(t, tests["Point"])
+}
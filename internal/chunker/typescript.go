@@ -0,0 +1,57 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches TypeScript-only declarations: interface Foo, type Foo =, enum Foo, namespace Foo
+	typeScriptDeclRegex = regexp.MustCompile(`^\s*(export\s+)?(?:default\s+)?(?:declare\s+)?(interface|type|enum|const\s+enum|namespace|module)\s+([A-Za-z_$][\w$.]*)`)
+)
+
+// chunkTypeScriptDeclarations extracts interfaces, type aliases, enums, and
+// namespaces from TypeScript source. TypeScript is parsed with the JavaScript
+// grammar, which cannot represent these constructs, so they are found by line.
+func chunkTypeScriptDeclarations(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var chunks []Chunk
+	for i := 0; i < len(lines); i++ {
+		matches := typeScriptDeclRegex.FindStringSubmatch(lines[i])
+		if matches == nil {
+			continue
+		}
+
+		var chunkType parser.ChunkType
+		switch keyword := strings.Join(strings.Fields(matches[2]), " "); keyword {
+		case "interface":
+			chunkType = parser.ChunkTypeInterface
+		case "type":
+			chunkType = parser.ChunkTypeTypeAlias
+		case "enum", "const enum":
+			chunkType = parser.ChunkTypeEnum
+		default:
+			chunkType = parser.ChunkTypeModule
+		}
+
+		end := cStyle.blockEnd(lines, i)
+		chunk := newLineChunk(filePath, "typescript", lines, i, end, string(chunkType), matches[3])
+		if matches[1] != "" {
+			chunk.Metadata["exported"] = "true"
+		}
+		if doc := cStyle.leadingComment(lines, i); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		// Namespaces can contain further declarations, so keep scanning inside them
+		if chunkType != parser.ChunkTypeModule {
+			i = end
+		}
+	}
+
+	return chunks
+}
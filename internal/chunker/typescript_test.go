@@ -0,0 +1,73 @@
+package chunker
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkTypeScriptDeclarations(t *testing.T) {
+	source := `type UserId = string;
+
+// A user of the system
+export interface User {
+    id: UserId;
+    name: string;
+}
+
+enum Status {
+    Active = 'ACTIVE',
+    Done = 'DONE'
+}
+
+namespace Utils {
+    export type Slug = string;
+}
+`
+
+	chunks := chunkTypeScriptDeclarations("types.ts", []byte(source))
+	require.Len(t, chunks, 5)
+
+	assert.Equal(t, "UserId", chunks[0].Name)
+	assert.Equal(t, "type_alias", chunks[0].ChunkType)
+	assert.Equal(t, 1, chunks[0].LineEnd)
+
+	assert.Equal(t, "User", chunks[1].Name)
+	assert.Equal(t, "interface", chunks[1].ChunkType)
+	assert.Equal(t, 4, chunks[1].LineStart)
+	assert.Equal(t, 7, chunks[1].LineEnd)
+	assert.Equal(t, "true", chunks[1].Metadata["exported"])
+	assert.Equal(t, "A user of the system", chunks[1].Metadata["doc_comment"])
+
+	assert.Equal(t, "Status", chunks[2].Name)
+	assert.Equal(t, "enum", chunks[2].ChunkType)
+	assert.Equal(t, 12, chunks[2].LineEnd)
+
+	assert.Equal(t, "Utils", chunks[3].Name)
+	assert.Equal(t, "module", chunks[3].ChunkType)
+	assert.Equal(t, "Slug", chunks[4].Name)
+}
+
+func TestSemanticChunkerTypeScriptSample(t *testing.T) {
+	chunker, err := NewSemantic()
+	require.NoError(t, err)
+
+	absPath, err := filepath.Abs("testdata/sample.ts")
+	require.NoError(t, err)
+
+	chunks, err := chunker.ChunkFile(absPath, "typescript")
+	require.NoError(t, err)
+
+	found := make(map[string]string)
+	for _, chunk := range chunks {
+		found[chunk.Name] = chunk.ChunkType
+	}
+
+	assert.Equal(t, "interface", found["Repository"])
+	assert.Equal(t, "type_alias", found["UserRole"])
+	assert.Equal(t, "enum", found["LogLevel"])
+	assert.Equal(t, "module", found["Utils"])
+	assert.Equal(t, "function", found["greet"])
+}
@@ -0,0 +1,153 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches a mapping key at the start of a line: name:, "app.kubernetes.io/name":
+	yamlKeyRegex = regexp.MustCompile(`^(\s*)("[^"]+"|'[^']+'|[^\s#:\-"'][^:#]*?):(?:\s+(.*?))?\s*$`)
+
+	yamlStyle = commentStyle{line: []string{"#"}}
+)
+
+// chunkYAML splits YAML files into one chunk per document. Kubernetes
+// resources (documents with kind and metadata.name) are named after the
+// resource with "Kind/name" as their symbol; other documents are chunked per
+// top-level key, with the key path as the symbol.
+func chunkYAML(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	var chunks []Chunk
+	start := 0
+	for i := 0; i <= len(lines); i++ {
+		if i < len(lines) && !strings.HasPrefix(lines[i], "---") && !strings.HasPrefix(lines[i], "...") {
+			continue
+		}
+		chunks = append(chunks, yamlDocument(filePath, lines, start, i-1)...)
+		start = i + 1
+	}
+
+	return chunks
+}
+
+// yamlDocument chunks the document on lines[start:end+1]
+func yamlDocument(filePath string, lines []string, start, end int) []Chunk {
+	for end >= start && strings.TrimSpace(lines[end]) == "" {
+		end--
+	}
+
+	// Top-level keys and the values they were given on the same line
+	var keys []int
+	values := make(map[string]string)
+	for i := start; i <= end; i++ {
+		if matches := yamlKeyRegex.FindStringSubmatch(lines[i]); matches != nil && matches[1] == "" {
+			keys = append(keys, i)
+			values[yamlKeyName(matches[2])] = yamlScalar(matches[3])
+		}
+	}
+	if len(keys) == 0 {
+		return nil
+	}
+
+	if kind := values["kind"]; kind != "" {
+		if name, namespace := yamlResourceName(lines, start, end); name != "" {
+			docStart := start
+			for docStart < end && strings.TrimSpace(lines[docStart]) == "" {
+				docStart++
+			}
+			chunk := newLineChunk(filePath, "yaml", lines, docStart, end, string(parser.ChunkTypeBlock), name)
+			chunk.Metadata["kind"] = kind
+			chunk.Metadata["symbol"] = kind + "/" + name
+			if apiVersion := values["apiVersion"]; apiVersion != "" {
+				chunk.Metadata["api_version"] = apiVersion
+			}
+			if namespace != "" {
+				chunk.Metadata["namespace"] = namespace
+			}
+			return []Chunk{chunk}
+		}
+	}
+
+	var chunks []Chunk
+	for n, key := range keys {
+		keyStart := extendAbove(lines, key, yamlStyle.isCommentLine)
+		if keyStart < start {
+			keyStart = start
+		}
+		keyEnd := end
+		if n+1 < len(keys) {
+			keyEnd = extendAbove(lines, keys[n+1], yamlStyle.isCommentLine) - 1
+		}
+		for keyEnd > key && strings.TrimSpace(lines[keyEnd]) == "" {
+			keyEnd--
+		}
+
+		name := yamlKeyName(yamlKeyRegex.FindStringSubmatch(lines[key])[2])
+		chunk := newLineChunk(filePath, "yaml", lines, keyStart, keyEnd, string(parser.ChunkTypeProperty), name)
+		chunk.Metadata["symbol"] = name
+		if doc := yamlStyle.leadingComment(lines, key); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+	}
+
+	return chunks
+}
+
+// yamlResourceName returns metadata.name and metadata.namespace of a
+// Kubernetes resource document
+func yamlResourceName(lines []string, start, end int) (name, namespace string) {
+	for i := start; i <= end; i++ {
+		matches := yamlKeyRegex.FindStringSubmatch(lines[i])
+		if matches == nil || matches[1] != "" || yamlKeyName(matches[2]) != "metadata" {
+			continue
+		}
+
+		// Only direct children of metadata count, not labels or annotations
+		childIndent := -1
+		for j := i + 1; j <= end; j++ {
+			child := yamlKeyRegex.FindStringSubmatch(lines[j])
+			if strings.TrimSpace(lines[j]) == "" || strings.HasPrefix(strings.TrimSpace(lines[j]), "#") {
+				continue
+			}
+			if indentWidth(lines[j]) == 0 {
+				break
+			}
+			if child == nil {
+				continue
+			}
+			if childIndent < 0 {
+				childIndent = len(child[1])
+			}
+			if len(child[1]) != childIndent {
+				continue
+			}
+			switch yamlKeyName(child[2]) {
+			case "name":
+				name = yamlScalar(child[3])
+			case "namespace":
+				namespace = yamlScalar(child[3])
+			}
+		}
+		return name, namespace
+	}
+	return "", ""
+}
+
+// yamlKeyName removes the quotes around a mapping key
+func yamlKeyName(key string) string {
+	return strings.Trim(strings.TrimSpace(key), `"'`)
+}
+
+// yamlScalar returns a plain or quoted scalar value without its quotes or a
+// trailing comment
+func yamlScalar(value string) string {
+	if idx := strings.Index(value, " #"); idx >= 0 {
+		value = value[:idx]
+	}
+	return strings.Trim(strings.TrimSpace(value), `"'`)
+}
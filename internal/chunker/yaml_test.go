@@ -0,0 +1,73 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkYAMLKubernetes(t *testing.T) {
+	source := `# Web frontend
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: web
+  namespace: prod
+  labels:
+    name: not-this
+spec:
+  rules:
+  - host: example.com
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: "web-svc"
+spec:
+  ports:
+  - port: 80
+`
+
+	chunks := chunkYAML("web.yaml", []byte(source))
+	require.Len(t, chunks, 2)
+
+	ingress := chunks[0]
+	assert.Equal(t, "web", ingress.Name)
+	assert.Equal(t, "Ingress", ingress.Metadata["kind"])
+	assert.Equal(t, "Ingress/web", ingress.Metadata["symbol"])
+	assert.Equal(t, "prod", ingress.Metadata["namespace"])
+	assert.Equal(t, "networking.k8s.io/v1", ingress.Metadata["api_version"])
+	assert.Equal(t, 1, ingress.LineStart)
+	assert.Equal(t, 11, ingress.LineEnd)
+
+	service := chunks[1]
+	assert.Equal(t, "Service/web-svc", service.Metadata["symbol"])
+	assert.Equal(t, 13, service.LineStart)
+	assert.Equal(t, 19, service.LineEnd)
+}
+
+func TestChunkYAMLKeys(t *testing.T) {
+	source := `# Build settings
+build:
+  target: release
+
+deploy:
+- staging
+- prod
+`
+
+	chunks := chunkYAML("ci.yml", []byte(source))
+	require.Len(t, chunks, 2)
+
+	assert.Equal(t, "build", chunks[0].Name)
+	assert.Equal(t, "property", chunks[0].ChunkType)
+	assert.Equal(t, "build", chunks[0].Metadata["symbol"])
+	assert.Equal(t, "Build settings", chunks[0].Metadata["doc_comment"])
+	assert.Equal(t, 1, chunks[0].LineStart)
+	assert.Equal(t, 3, chunks[0].LineEnd)
+
+	assert.Equal(t, "deploy", chunks[1].Name)
+	assert.Equal(t, 5, chunks[1].LineStart)
+	assert.Equal(t, 7, chunks[1].LineEnd)
+}
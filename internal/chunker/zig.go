@@ -0,0 +1,97 @@
+package chunker
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/parser"
+)
+
+var (
+	// Matches functions: pub fn init(, export fn main(, pub inline fn max(
+	zigFnRegex = regexp.MustCompile(`^\s*(pub\s+)?(?:(?:export|extern(?:\s+"\w+")?|inline|noinline)\s+)*fn\s+(@"[^"]+"|\w+)\s*\(`)
+	// Matches container declarations: pub const Point = struct {, const Tag = union(enum) {
+	zigContainerRegex = regexp.MustCompile(`^\s*(pub\s+)?const\s+(\w+)\s*(?::\s*[^=]+)?=\s*(?:extern\s+|packed\s+)?(struct|enum|union|opaque)\b`)
+	// Matches top-level comptime blocks
+	zigComptimeRegex = regexp.MustCompile(`^comptime\s*\{`)
+
+	// Zig only has line comments; /// is a doc comment
+	zigStyle = commentStyle{line: []string{"//"}}
+)
+
+// chunkZig extracts functions, container types (struct, enum, union, opaque),
+// and top-level comptime blocks from Zig source. Functions declared inside a
+// container take it as their receiver, and /// doc comments are kept.
+func chunkZig(filePath string, sourceCode []byte) []Chunk {
+	lines := strings.Split(string(sourceCode), "\n")
+
+	type containerScope struct {
+		name string
+		end  int
+	}
+	var (
+		chunks     []Chunk
+		containers []containerScope
+	)
+
+	for i := 0; i < len(lines); i++ {
+		for len(containers) > 0 && i > containers[len(containers)-1].end {
+			containers = containers[:len(containers)-1]
+		}
+
+		var (
+			chunkType parser.ChunkType
+			name      string
+			kind      string
+			public    bool
+		)
+
+		line := lines[i]
+		if matches := zigContainerRegex.FindStringSubmatch(line); matches != nil {
+			public, name, kind = matches[1] != "", matches[2], matches[3]
+			switch kind {
+			case "enum":
+				chunkType = parser.ChunkTypeEnum
+			default:
+				chunkType = parser.ChunkTypeStruct
+			}
+		} else if matches := zigFnRegex.FindStringSubmatch(line); matches != nil {
+			public, name = matches[1] != "", matches[2]
+			chunkType = parser.ChunkTypeFunction
+			if len(containers) > 0 {
+				chunkType = parser.ChunkTypeMethod
+			}
+		} else if zigComptimeRegex.MatchString(line) {
+			name = "comptime"
+			chunkType = parser.ChunkTypeBlock
+		} else {
+			continue
+		}
+
+		end := zigStyle.blockEnd(lines, i)
+		chunk := newLineChunk(filePath, "zig", lines, i, end, string(chunkType), name)
+
+		if public {
+			chunk.Metadata["pub"] = "true"
+		}
+		if kind != "" {
+			chunk.Metadata["kind"] = kind
+		}
+		if len(containers) > 0 {
+			chunk.Metadata["receiver"] = containers[len(containers)-1].name
+		}
+		if doc := zigStyle.leadingComment(lines, i); doc != "" {
+			chunk.Metadata["doc_comment"] = doc
+		}
+		chunks = append(chunks, chunk)
+
+		// Containers hold methods, so keep scanning inside them; skip function bodies
+		if kind != "" {
+			containers = append(containers, containerScope{name: name, end: end})
+		} else {
+			i = end
+		}
+	}
+
+	return chunks
+}
@@ -0,0 +1,61 @@
+package chunker
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestChunkZig(t *testing.T) {
+	source := `const std = @import("std");
+
+/// A 2D point.
+pub const Point = struct {
+    x: f32,
+    y: f32,
+
+    pub fn add(self: Point, other: Point) Point {
+        return .{ .x = self.x + other.x, .y = self.y + other.y };
+    }
+};
+
+const Color = enum { red, green };
+
+comptime {
+    std.debug.assert(@sizeOf(Point) == 8);
+}
+
+export fn main() void {
+    std.debug.print("{d}\n", .{1});
+}
+`
+
+	chunks := chunkZig("point.zig", []byte(source))
+	require.Len(t, chunks, 5)
+
+	point := chunks[0]
+	assert.Equal(t, "Point", point.Name)
+	assert.Equal(t, "struct", point.ChunkType)
+	assert.Equal(t, "true", point.Metadata["pub"])
+	assert.Equal(t, "A 2D point.", point.Metadata["doc_comment"])
+	assert.Equal(t, 4, point.LineStart)
+	assert.Equal(t, 11, point.LineEnd)
+
+	add := chunks[1]
+	assert.Equal(t, "add", add.Name)
+	assert.Equal(t, "method", add.ChunkType)
+	assert.Equal(t, "Point", add.Metadata["receiver"])
+
+	assert.Equal(t, "Color", chunks[2].Name)
+	assert.Equal(t, "enum", chunks[2].ChunkType)
+	assert.Equal(t, 13, chunks[2].LineEnd)
+
+	assert.Equal(t, "comptime", chunks[3].Name)
+	assert.Equal(t, "block", chunks[3].ChunkType)
+	assert.Equal(t, 17, chunks[3].LineEnd)
+
+	assert.Equal(t, "main", chunks[4].Name)
+	assert.Equal(t, "function", chunks[4].ChunkType)
+	assert.Empty(t, chunks[4].Metadata["receiver"])
+}
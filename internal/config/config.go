@@ -5,16 +5,176 @@ import (
 	"fmt"
 	"net/url"
 	"os"
+	"path"
 	"path/filepath"
+	"slices"
+	"strconv"
 	"strings"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/embeddings"
 )
 
+// maxDimensions is the size of the vectors the index stores; shorter vectors
+// are padded with zeros
+const maxDimensions = 3584
+
 // Config holds the application configuration
 type Config struct {
-	Endpoint  string `json:"endpoint"`
-	APIKey    string `json:"api_key,omitempty"`    // Optional API key for authentication
-	CodeModel string `json:"code_model"`
-	TextModel string `json:"text_model"`
+	Endpoint      string          `json:"endpoint"`
+	APIKey        string          `json:"api_key,omitempty"` // Optional API key for authentication
+	CodeModel     string          `json:"code_model"`
+	TextModel     string          `json:"text_model"`
+	Grammars      []GrammarConfig `json:"grammars,omitempty"`       // Tree-sitter grammars loaded at runtime
+	OverlapLines  int             `json:"overlap_lines,omitempty"`  // Lines of context shared between adjacent chunks
+	OverlapHeader bool            `json:"overlap_header,omitempty"` // Prepend the enclosing item's header to nested chunks
+
+	// The API the endpoint serves
+	Provider            string            `json:"provider,omitempty"`               // openai (default) for any OpenAI-compatible API, or ollama, huggingface, tei, azure, gemini, cohere, voyage, jina, or onnx for local models
+	KeepAlive           string            `json:"keep_alive,omitempty"`             // How long Ollama keeps a model loaded after a request, e.g. 10m
+	GPU                 string            `json:"gpu,omitempty"`                    // Where Ollama runs the models: auto (default), on, falling back to the CPU, or off
+	Dimensions          int               `json:"dimensions,omitempty"`             // Size of the vectors asked of models that can shorten them, such as text-embedding-3
+	Matryoshka          bool              `json:"matryoshka,omitempty"`             // Shorten vectors to dimensions locally and renormalize them, for Matryoshka models whose API cannot
+	Pooling             map[string]string `json:"pooling,omitempty"`                // How the token embeddings of a model without pooling are combined, by model: mean (default) or cls
+	APIVersion          string            `json:"api_version,omitempty"`            // Azure OpenAI API version, e.g. 2024-06-01
+	AzureADTokenCommand string            `json:"azure_ad_token_command,omitempty"` // Prints an Azure AD token, used with azure when there is no API key
+	ONNXRuntime         string            `json:"onnx_runtime,omitempty"`          // Path to the ONNX Runtime shared library the onnx provider loads, e.g. /usr/lib/libonnxruntime.so
+	ONNXDevice          string            `json:"onnx_device,omitempty"`           // Where the onnx provider runs the models: cpu (default), cuda, or coreml for the GPU and Neural Engine of Apple silicon
+
+	// Limits on the requests made to the provider, shared by the code and text
+	// models; 0 leaves a limit off
+	MaxInFlight       int `json:"max_in_flight,omitempty"`       // Requests waiting on a response at once
+	RequestsPerMinute int `json:"requests_per_minute,omitempty"` // Requests started per minute
+	TokensPerMinute   int `json:"tokens_per_minute,omitempty"`   // Estimated tokens sent per minute
+
+	Retry *RetryConfig `json:"retry,omitempty"` // Which failed requests to the provider are retried, and how
+
+	Spaces []SpaceConfig `json:"spaces,omitempty"` // Embedding spaces: vectors of the index's chunks from other models
+
+	DualEmbedding bool `json:"dual_embedding,omitempty"` // Also embed code chunks' doc comments with the text model, fused with the code in searches
+
+	Fallbacks []FallbackConfig `json:"fallbacks,omitempty"` // Models that embed in turn while the provider is down or rate limited
+
+	TokenPrices map[string]float64 `json:"token_prices,omitempty"` // Price in USD of a million tokens, by model, for the costs indexing reports
+
+	// Token limits of the embedding models; chunks over a limit are split
+	CodeTokenizer string `json:"code_tokenizer,omitempty"`  // Path to the code model's tokenizer.json
+	CodeMaxTokens int    `json:"code_max_tokens,omitempty"` // Input limit of the code model
+	TextTokenizer string `json:"text_tokenizer,omitempty"`  // Path to the text model's tokenizer.json
+	TextMaxTokens int    `json:"text_max_tokens,omitempty"` // Input limit of the text model
+	Truncation    string `json:"truncation,omitempty"`      // What becomes of chunks over a limit: split (default), head, tail, or middle
+	MergeTokens   int    `json:"merge_tokens,omitempty"`    // Merge small consecutive sibling items up to this size
+	GroupTypes    bool   `json:"group_types,omitempty"`     // Add a chunk per type with its definition and method signatures
+	SkipGenerated bool   `json:"skip_generated,omitempty"`  // Leave generated files out of the index instead of tagging them
+	EmbedImports  bool   `json:"embed_imports,omitempty"`   // Prefix embedded code with the imports it uses
+
+	// Globs relative to the indexed directory that limit which files are indexed
+	Include []string `json:"include,omitempty"` // Only index files matching one of these
+	Exclude []string `json:"exclude,omitempty"` // Skip files and directories matching any of these
+
+	// Languages, by the names code-scout detects, that limit which files are indexed
+	Languages        []string `json:"languages,omitempty"`         // Only index files in one of these
+	ExcludeLanguages []string `json:"exclude_languages,omitempty"` // Skip files in any of these
+
+	Submodules string `json:"submodules,omitempty"` // How git submodules are indexed: include (default), exclude, or shallow
+	Symlinks   string `json:"symlinks,omitempty"`   // Which symbolic links are followed: within-root (default), follow, or skip
+
+	Repos []RepoConfig `json:"repos,omitempty"` // Repositories indexed together instead of the current directory
+	Root  string       `json:"root,omitempty"`  // Directory a named index covers, used instead of the current directory
+
+	IndexKeyCommand string `json:"index_key_command,omitempty"` // Prints the key the index is encrypted with, e.g. from the OS keychain
+	IndexRemote     string `json:"index_remote,omitempty"`      // URL or path index push and pull share the index through
+	IndexBucket     string `json:"index_bucket,omitempty"`      // s3:// or gs:// URL the index is kept in, cached locally
+
+	Quantization string `json:"quantization,omitempty"` // Vectors searches scan before rescoring the best on the full ones: int8 or binary
+
+	BranchIndexes   bool `json:"branch_indexes,omitempty"`   // Keep a separate index for each git branch
+	ReadOnly        bool `json:"read_only,omitempty"`        // Never change the index, as for CI jobs and shared network mounts
+	CacheEmbeddings bool `json:"cache_embeddings,omitempty"` // Keep embeddings in the user cache directory, by model and content, across re-indexes
+}
+
+// RetryConfig describes how failed embedding requests are retried. Requests
+// that fail without a response, as on a timeout, are always retried.
+type RetryConfig struct {
+	Attempts int    `json:"attempts,omitempty"`  // Attempts at a request before it fails, 3 by default
+	Backoff  string `json:"backoff,omitempty"`   // How the wait grows between attempts: exponential (default) or constant
+	Delay    string `json:"delay,omitempty"`     // Wait before the first retry, 1s by default
+	MaxDelay string `json:"max_delay,omitempty"` // Longest wait between attempts, 1m by default
+	Statuses []int  `json:"statuses,omitempty"`  // HTTP statuses that are retried, 408, 429, 500, 502, 503, and 504 by default
+}
+
+// SpaceConfig describes a named embedding space, which holds vectors of the
+// index's chunks made with other models than the index's own. A space without
+// an endpoint uses the configured provider, endpoint, and API key.
+type SpaceConfig struct {
+	Name       string `json:"name"`                 // Name searches select the space by
+	Provider   string `json:"provider,omitempty"`   // The API the space's endpoint serves, as for the index's
+	Endpoint   string `json:"endpoint,omitempty"`   // Endpoint of the space's models
+	APIKey     string `json:"api_key,omitempty"`    // API key of the space's endpoint
+	CodeModel  string `json:"code_model"`           // Model code chunks are embedded with
+	TextModel  string `json:"text_model,omitempty"` // Model documentation chunks are embedded with, the code model by default
+	Dimensions int    `json:"dimensions,omitempty"` // Size of the vectors asked of models that can shorten them
+}
+
+// SpaceTextModel returns the model the space embeds documentation with
+func (s SpaceConfig) SpaceTextModel() string {
+	if s.TextModel != "" {
+		return s.TextModel
+	}
+	return s.CodeModel
+}
+
+// FallbackConfig describes models that stand in for the configured ones
+// while their provider is down or rate limited. The vectors of the chunks
+// and queries a fallback embeds are searched only with each other.
+type FallbackConfig struct {
+	Provider   string `json:"provider,omitempty"`   // The API the fallback's endpoint serves, as for the index's
+	Endpoint   string `json:"endpoint,omitempty"`   // Endpoint of the fallback's models, the index's by default
+	APIKey     string `json:"api_key,omitempty"`    // API key of the fallback's endpoint
+	CodeModel  string `json:"code_model"`           // Model code chunks are embedded with
+	TextModel  string `json:"text_model,omitempty"` // Model documentation chunks are embedded with, the code model by default
+	Dimensions int    `json:"dimensions,omitempty"` // Size of the vectors asked of models that can shorten them
+}
+
+// FallbackTextModel returns the model the fallback embeds documentation with
+func (f FallbackConfig) FallbackTextModel() string {
+	if f.TextModel != "" {
+		return f.TextModel
+	}
+	return f.CodeModel
+}
+
+// RepoConfig describes one repository of a workspace whose repositories share
+// an index
+type RepoConfig struct {
+	Name string `json:"name,omitempty"` // Name recorded on the repository's chunks, defaults to its directory name
+	Path string `json:"path"`           // Repository root, relative to the workspace directory
+}
+
+// RepoName returns the repository's name
+func (r RepoConfig) RepoName() string {
+	if r.Name != "" {
+		return r.Name
+	}
+	return filepath.Base(filepath.Clean(r.Path))
+}
+
+// GrammarConfig describes a Tree-sitter grammar compiled as a shared library,
+// which adds a language without rebuilding code-scout
+type GrammarConfig struct {
+	Language   string   `json:"language"`         // Language name, e.g. "elixir"
+	Library    string   `json:"library"`          // Path to the compiled grammar (.so or .dylib)
+	Symbol     string   `json:"symbol,omitempty"` // Exported language function, defaults to tree_sitter_<language>
+	Extensions []string `json:"extensions"`       // File extensions, e.g. [".ex", ".exs"]
+	Query      string   `json:"query"`            // Path to a query file with @<type>.definition and @<type>.name captures
+}
+
+// LanguageSymbol returns the grammar's exported language function name
+func (g GrammarConfig) LanguageSymbol() string {
+	if g.Symbol != "" {
+		return g.Symbol
+	}
+	return "tree_sitter_" + strings.ReplaceAll(g.Language, "-", "_")
 }
 
 // Default returns the default configuration
@@ -80,6 +240,46 @@ func loadFromFile(path string) (*Config, error) {
 	return &cfg, nil
 }
 
+// ApplyEnv sets the API key from the environment: CODE_SCOUT_API_KEY for any
+// endpoint, overriding the configuration files, or when no key is configured,
+// OPENAI_API_KEY for OpenAI's own API, HF_TOKEN for Hugging Face's,
+// AZURE_OPENAI_API_KEY for Azure OpenAI, GEMINI_API_KEY for Gemini,
+// CO_API_KEY for Cohere, VOYAGE_API_KEY for Voyage AI, and JINA_API_KEY for
+// Jina AI
+func (c *Config) ApplyEnv() {
+	if key := os.Getenv("CODE_SCOUT_API_KEY"); key != "" {
+		c.APIKey = key
+		return
+	}
+	if c.APIKey != "" {
+		return
+	}
+	switch c.Provider {
+	case "huggingface":
+		c.APIKey = os.Getenv("HF_TOKEN")
+		return
+	case "azure":
+		c.APIKey = os.Getenv("AZURE_OPENAI_API_KEY")
+		return
+	case "gemini":
+		c.APIKey = os.Getenv("GEMINI_API_KEY")
+		return
+	case "cohere":
+		c.APIKey = os.Getenv("CO_API_KEY")
+		return
+	case "voyage":
+		c.APIKey = os.Getenv("VOYAGE_API_KEY")
+		return
+	case "jina":
+		c.APIKey = os.Getenv("JINA_API_KEY")
+		return
+	}
+	// Other endpoints never see the OpenAI key
+	if u, err := url.Parse(c.Endpoint); err == nil && u.Hostname() == "api.openai.com" {
+		c.APIKey = os.Getenv("OPENAI_API_KEY")
+	}
+}
+
 // mergeConfig merges non-empty values from src into dst
 func mergeConfig(dst, src *Config) {
 	if src.Endpoint != "" {
@@ -88,12 +288,146 @@ func mergeConfig(dst, src *Config) {
 	if src.APIKey != "" {
 		dst.APIKey = src.APIKey
 	}
+	if src.Provider != "" {
+		dst.Provider = src.Provider
+	}
+	if src.KeepAlive != "" {
+		dst.KeepAlive = src.KeepAlive
+	}
+	if src.GPU != "" {
+		dst.GPU = src.GPU
+	}
+	if src.Dimensions != 0 {
+		dst.Dimensions = src.Dimensions
+	}
+	if src.Matryoshka {
+		dst.Matryoshka = true
+	}
+	if len(src.Pooling) > 0 {
+		dst.Pooling = src.Pooling
+	}
+	if src.APIVersion != "" {
+		dst.APIVersion = src.APIVersion
+	}
+	if src.AzureADTokenCommand != "" {
+		dst.AzureADTokenCommand = src.AzureADTokenCommand
+	}
+	if src.ONNXRuntime != "" {
+		dst.ONNXRuntime = src.ONNXRuntime
+	}
+	if src.ONNXDevice != "" {
+		dst.ONNXDevice = src.ONNXDevice
+	}
+	if src.MaxInFlight != 0 {
+		dst.MaxInFlight = src.MaxInFlight
+	}
+	if src.RequestsPerMinute != 0 {
+		dst.RequestsPerMinute = src.RequestsPerMinute
+	}
+	if src.TokensPerMinute != 0 {
+		dst.TokensPerMinute = src.TokensPerMinute
+	}
+	if src.Retry != nil {
+		dst.Retry = src.Retry
+	}
+	if len(src.Spaces) > 0 {
+		dst.Spaces = src.Spaces
+	}
+	if len(src.Fallbacks) > 0 {
+		dst.Fallbacks = src.Fallbacks
+	}
+	if src.DualEmbedding {
+		dst.DualEmbedding = true
+	}
+	if len(src.TokenPrices) > 0 {
+		dst.TokenPrices = src.TokenPrices
+	}
 	if src.CodeModel != "" {
 		dst.CodeModel = src.CodeModel
 	}
 	if src.TextModel != "" {
 		dst.TextModel = src.TextModel
 	}
+	if src.OverlapLines != 0 {
+		dst.OverlapLines = src.OverlapLines
+	}
+	if src.OverlapHeader {
+		dst.OverlapHeader = true
+	}
+	if src.CodeTokenizer != "" {
+		dst.CodeTokenizer = src.CodeTokenizer
+	}
+	if src.CodeMaxTokens != 0 {
+		dst.CodeMaxTokens = src.CodeMaxTokens
+	}
+	if src.TextTokenizer != "" {
+		dst.TextTokenizer = src.TextTokenizer
+	}
+	if src.TextMaxTokens != 0 {
+		dst.TextMaxTokens = src.TextMaxTokens
+	}
+	if src.Truncation != "" {
+		dst.Truncation = src.Truncation
+	}
+	if src.MergeTokens != 0 {
+		dst.MergeTokens = src.MergeTokens
+	}
+	if src.GroupTypes {
+		dst.GroupTypes = true
+	}
+	if src.SkipGenerated {
+		dst.SkipGenerated = true
+	}
+	if src.EmbedImports {
+		dst.EmbedImports = true
+	}
+	if len(src.Include) > 0 {
+		dst.Include = src.Include
+	}
+	if len(src.Exclude) > 0 {
+		dst.Exclude = src.Exclude
+	}
+	if len(src.Languages) > 0 {
+		dst.Languages = src.Languages
+	}
+	if len(src.ExcludeLanguages) > 0 {
+		dst.ExcludeLanguages = src.ExcludeLanguages
+	}
+	if src.Submodules != "" {
+		dst.Submodules = src.Submodules
+	}
+	if src.Symlinks != "" {
+		dst.Symlinks = src.Symlinks
+	}
+	if len(src.Repos) > 0 {
+		dst.Repos = src.Repos
+	}
+	if src.Root != "" {
+		dst.Root = src.Root
+	}
+	if src.IndexKeyCommand != "" {
+		dst.IndexKeyCommand = src.IndexKeyCommand
+	}
+	if src.IndexRemote != "" {
+		dst.IndexRemote = src.IndexRemote
+	}
+	if src.IndexBucket != "" {
+		dst.IndexBucket = src.IndexBucket
+	}
+	if src.BranchIndexes {
+		dst.BranchIndexes = true
+	}
+	if src.ReadOnly {
+		dst.ReadOnly = true
+	}
+	if src.CacheEmbeddings {
+		dst.CacheEmbeddings = true
+	}
+	if src.Quantization != "" {
+		dst.Quantization = src.Quantization
+	}
+	// Grammars accumulate, so user-level and project-level grammars both load
+	dst.Grammars = append(dst.Grammars, src.Grammars...)
 }
 
 // Validate validates the configuration
@@ -115,6 +449,72 @@ func (c *Config) Validate() error {
 	// Remove trailing slash if present
 	c.Endpoint = strings.TrimSuffix(c.Endpoint, "/")
 
+	// Providers registered with embeddings.RegisterProvider are accepted too
+	if c.Provider != "" && !embeddings.HasProvider(c.Provider) {
+		return fmt.Errorf("provider must be openai, ollama, huggingface, tei, azure, gemini, cohere, voyage, jina, onnx, or a registered provider, got: %s", c.Provider)
+	}
+	if c.Provider == "azure" && c.APIKey == "" && c.AzureADTokenCommand == "" {
+		return fmt.Errorf("the azure provider needs an api_key or an azure_ad_token_command")
+	}
+	for model, pooling := range c.Pooling {
+		if pooling != "mean" && pooling != "cls" {
+			return fmt.Errorf("pooling of %s must be mean or cls, got: %s", model, pooling)
+		}
+	}
+	switch c.GPU {
+	case "", "auto", "on", "off":
+	default:
+		return fmt.Errorf("gpu must be auto, on, or off, got: %s", c.GPU)
+	}
+	switch c.ONNXDevice {
+	case "", "cpu", "cuda", "coreml":
+	default:
+		return fmt.Errorf("onnx_device must be cpu, cuda, or coreml, got: %s", c.ONNXDevice)
+	}
+	if c.KeepAlive != "" {
+		if _, err := time.ParseDuration(c.KeepAlive); err != nil {
+			if _, err := strconv.Atoi(c.KeepAlive); err != nil {
+				return fmt.Errorf("keep_alive must be a duration such as 10m or a number of seconds, got: %s", c.KeepAlive)
+			}
+		}
+	}
+
+	if c.MaxInFlight < 0 || c.RequestsPerMinute < 0 || c.TokensPerMinute < 0 {
+		return fmt.Errorf("max_in_flight, requests_per_minute, and tokens_per_minute cannot be negative")
+	}
+
+	if err := c.Retry.validate(); err != nil {
+		return err
+	}
+
+	if c.Dimensions < 0 || c.Dimensions > maxDimensions {
+		return fmt.Errorf("dimensions must be between 1 and %d, got: %d", maxDimensions, c.Dimensions)
+	}
+	if c.Matryoshka && c.Dimensions == 0 {
+		return fmt.Errorf("matryoshka needs the dimensions vectors are shortened to")
+	}
+
+	spaceNames := make(map[string]bool)
+	for i, sp := range c.Spaces {
+		if err := sp.validate(); err != nil {
+			return fmt.Errorf("spaces[%d]: %w", i, err)
+		}
+		if spaceNames[sp.Name] {
+			return fmt.Errorf("spaces[%d]: duplicate space name %q", i, sp.Name)
+		}
+		spaceNames[sp.Name] = true
+	}
+	for i, fb := range c.Fallbacks {
+		if err := fb.validate(); err != nil {
+			return fmt.Errorf("fallbacks[%d]: %w", i, err)
+		}
+	}
+	for model, price := range c.TokenPrices {
+		if price < 0 {
+			return fmt.Errorf("token price of %s cannot be negative, got: %g", model, price)
+		}
+	}
+
 	// Validate models are not empty
 	if c.CodeModel == "" {
 		return fmt.Errorf("code_model cannot be empty")
@@ -123,6 +523,84 @@ func (c *Config) Validate() error {
 		return fmt.Errorf("text_model cannot be empty")
 	}
 
+	if c.OverlapLines < 0 {
+		return fmt.Errorf("overlap_lines cannot be negative, got: %d", c.OverlapLines)
+	}
+
+	if c.CodeMaxTokens < 0 || c.TextMaxTokens < 0 || c.MergeTokens < 0 {
+		return fmt.Errorf("code_max_tokens, text_max_tokens, and merge_tokens cannot be negative")
+	}
+	switch c.Truncation {
+	case "", "split", "head", "tail", "middle":
+	default:
+		return fmt.Errorf("truncation must be split, head, tail, or middle, got: %s", c.Truncation)
+	}
+
+	for _, glob := range append(append([]string{}, c.Include...), c.Exclude...) {
+		if _, err := path.Match(glob, ""); err != nil {
+			return fmt.Errorf("invalid glob %q in include or exclude: %w", glob, err)
+		}
+	}
+
+	for _, language := range c.Languages {
+		if slices.Contains(c.ExcludeLanguages, language) {
+			return fmt.Errorf("language %s cannot be in both languages and exclude_languages", language)
+		}
+	}
+
+	switch c.Submodules {
+	case "", "include", "exclude", "shallow":
+	default:
+		return fmt.Errorf("submodules must be include, exclude, or shallow, got: %s", c.Submodules)
+	}
+	switch c.Symlinks {
+	case "", "within-root", "follow", "skip":
+	default:
+		return fmt.Errorf("symlinks must be within-root, follow, or skip, got: %s", c.Symlinks)
+	}
+
+	if c.Root != "" && !filepath.IsAbs(c.Root) {
+		return fmt.Errorf("root must be an absolute path, got: %s", c.Root)
+	}
+
+	repoNames := make(map[string]bool)
+	for i, r := range c.Repos {
+		if r.Path == "" {
+			return fmt.Errorf("repos[%d]: path cannot be empty", i)
+		}
+		if repoNames[r.RepoName()] {
+			return fmt.Errorf("repos[%d]: duplicate repository name %q", i, r.RepoName())
+		}
+		repoNames[r.RepoName()] = true
+	}
+
+	switch c.Quantization {
+	case "", "int8", "binary":
+	default:
+		return fmt.Errorf("quantization must be int8 or binary, got: %s", c.Quantization)
+	}
+
+	if c.IndexBucket != "" && !strings.HasPrefix(c.IndexBucket, "s3://") && !strings.HasPrefix(c.IndexBucket, "gs://") {
+		return fmt.Errorf("index_bucket must be an s3:// or gs:// URL, got: %s", c.IndexBucket)
+	}
+
+	for i, g := range c.Grammars {
+		if g.Language == "" {
+			return fmt.Errorf("grammars[%d]: language cannot be empty", i)
+		}
+		if g.Library == "" || g.Query == "" {
+			return fmt.Errorf("grammar %s: library and query are required", g.Language)
+		}
+		if len(g.Extensions) == 0 {
+			return fmt.Errorf("grammar %s: at least one extension is required", g.Language)
+		}
+		for _, ext := range g.Extensions {
+			if !strings.HasPrefix(ext, ".") {
+				return fmt.Errorf("grammar %s: extension %q must start with a dot", g.Language, ext)
+			}
+		}
+	}
+
 	return nil
 }
 
@@ -141,3 +619,80 @@ func (c *Config) Save(path string) error {
 
 	return os.WriteFile(path, data, 0644)
 }
+
+// validate checks an embedding space's settings
+func (s SpaceConfig) validate() error {
+	// The name is part of the space's table name
+	if s.Name == "" || strings.Trim(s.Name, "abcdefghijklmnopqrstuvwxyz0123456789_-") != "" {
+		return fmt.Errorf("name must be lowercase letters, digits, - and _, got: %q", s.Name)
+	}
+	if s.Name == "default" {
+		return fmt.Errorf("name default is taken by the index's own vectors")
+	}
+	if s.Name == "summary" {
+		return fmt.Errorf("name summary is taken by the vectors of dual_embedding")
+	}
+	if s.CodeModel == "" {
+		return fmt.Errorf("space %s: code_model cannot be empty", s.Name)
+	}
+	if s.Endpoint != "" {
+		parsedURL, err := url.Parse(s.Endpoint)
+		if err != nil || (parsedURL.Scheme != "http" && parsedURL.Scheme != "https") {
+			return fmt.Errorf("space %s: endpoint must be an http or https URL, got: %s", s.Name, s.Endpoint)
+		}
+	}
+	if s.Provider != "" && !embeddings.HasProvider(s.Provider) {
+		return fmt.Errorf("space %s: unknown provider %s", s.Name, s.Provider)
+	}
+	if s.Dimensions < 0 || s.Dimensions > maxDimensions {
+		return fmt.Errorf("space %s: dimensions must be between 1 and %d, got: %d", s.Name, maxDimensions, s.Dimensions)
+	}
+	return nil
+}
+
+// validate checks a fallback's settings
+func (f FallbackConfig) validate() error {
+	if f.CodeModel == "" {
+		return fmt.Errorf("code_model cannot be empty")
+	}
+	if f.Endpoint != "" {
+		parsedURL, err := url.Parse(f.Endpoint)
+		if err != nil || (parsedURL.Scheme != "http" && parsedURL.Scheme != "https") {
+			return fmt.Errorf("endpoint must be an http or https URL, got: %s", f.Endpoint)
+		}
+	}
+	if f.Provider != "" && !embeddings.HasProvider(f.Provider) {
+		return fmt.Errorf("unknown provider %s", f.Provider)
+	}
+	if f.Dimensions < 0 || f.Dimensions > maxDimensions {
+		return fmt.Errorf("dimensions must be between 1 and %d, got: %d", maxDimensions, f.Dimensions)
+	}
+	return nil
+}
+
+// validate checks the retry settings; a nil RetryConfig keeps the defaults
+func (r *RetryConfig) validate() error {
+	if r == nil {
+		return nil
+	}
+	if r.Attempts < 0 {
+		return fmt.Errorf("retry attempts cannot be negative, got: %d", r.Attempts)
+	}
+	if r.Backoff != "" && r.Backoff != "exponential" && r.Backoff != "constant" {
+		return fmt.Errorf("retry backoff must be exponential or constant, got: %s", r.Backoff)
+	}
+	for _, d := range []string{r.Delay, r.MaxDelay} {
+		if d == "" {
+			continue
+		}
+		if _, err := time.ParseDuration(d); err != nil {
+			return fmt.Errorf("retry delays must be durations such as 1s or 2m, got: %s", d)
+		}
+	}
+	for _, status := range r.Statuses {
+		if status < 100 || status > 599 {
+			return fmt.Errorf("retry statuses must be HTTP statuses, got: %d", status)
+		}
+	}
+	return nil
+}
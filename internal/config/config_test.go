@@ -83,6 +83,76 @@ func TestMergeConfig(t *testing.T) {
 	}
 }
 
+func TestMergeConfig_Grammars(t *testing.T) {
+	dst := Default()
+	mergeConfig(dst, &Config{Grammars: []GrammarConfig{{Language: "nim"}}})
+	mergeConfig(dst, &Config{Grammars: []GrammarConfig{{Language: "odin"}}})
+
+	if len(dst.Grammars) != 2 || dst.Grammars[0].Language != "nim" || dst.Grammars[1].Language != "odin" {
+		t.Errorf("expected user and project grammars to accumulate, got %+v", dst.Grammars)
+	}
+}
+
+func TestMergeConfig_Overlap(t *testing.T) {
+	dst := Default()
+	mergeConfig(dst, &Config{OverlapLines: 3, OverlapHeader: true})
+	mergeConfig(dst, &Config{OverlapLines: 1})
+
+	if dst.OverlapLines != 1 {
+		t.Errorf("expected project overlap_lines to override, got %d", dst.OverlapLines)
+	}
+	if !dst.OverlapHeader {
+		t.Error("expected overlap_header to be kept from user config")
+	}
+}
+
+func TestMergeConfig_TokenLimits(t *testing.T) {
+	dst := Default()
+	mergeConfig(dst, &Config{CodeTokenizer: "/models/code/tokenizer.json", CodeMaxTokens: 8192, TextMaxTokens: 2048})
+	mergeConfig(dst, &Config{CodeMaxTokens: 4096})
+
+	if dst.CodeTokenizer != "/models/code/tokenizer.json" {
+		t.Errorf("expected code tokenizer to be kept, got %s", dst.CodeTokenizer)
+	}
+	if dst.CodeMaxTokens != 4096 {
+		t.Errorf("expected project code_max_tokens to override, got %d", dst.CodeMaxTokens)
+	}
+	if dst.TextMaxTokens != 2048 {
+		t.Errorf("expected text_max_tokens 2048, got %d", dst.TextMaxTokens)
+	}
+}
+
+func TestMergeConfig_Globs(t *testing.T) {
+	dst := Default()
+	mergeConfig(dst, &Config{Include: []string{"src/**"}, Exclude: []string{"vendor/**"}})
+	mergeConfig(dst, &Config{Exclude: []string{"**/*.min.js"}})
+
+	if len(dst.Include) != 1 || dst.Include[0] != "src/**" {
+		t.Errorf("expected include to be kept from user config, got %v", dst.Include)
+	}
+	if len(dst.Exclude) != 1 || dst.Exclude[0] != "**/*.min.js" {
+		t.Errorf("expected project exclude to override, got %v", dst.Exclude)
+	}
+}
+
+func TestRepoName(t *testing.T) {
+	if got := (RepoConfig{Path: "../services/api/"}).RepoName(); got != "api" {
+		t.Errorf("expected name from path, got %s", got)
+	}
+	if got := (RepoConfig{Name: "backend", Path: "../api"}).RepoName(); got != "backend" {
+		t.Errorf("expected configured name, got %s", got)
+	}
+}
+
+func TestGrammarLanguageSymbol(t *testing.T) {
+	if got := (GrammarConfig{Language: "c-sharp"}).LanguageSymbol(); got != "tree_sitter_c_sharp" {
+		t.Errorf("expected default symbol tree_sitter_c_sharp, got %s", got)
+	}
+	if got := (GrammarConfig{Language: "nim", Symbol: "tree_sitter_nim2"}).LanguageSymbol(); got != "tree_sitter_nim2" {
+		t.Errorf("expected configured symbol tree_sitter_nim2, got %s", got)
+	}
+}
+
 func TestSave(t *testing.T) {
 	tempDir := t.TempDir()
 	configPath := filepath.Join(tempDir, "subdir", "config.json")
@@ -184,6 +254,499 @@ func TestValidate(t *testing.T) {
 			},
 			expectErr: true,
 		},
+		{
+			name: "valid grammar",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Grammars: []GrammarConfig{
+					{Language: "nim", Library: "/opt/grammars/nim.so", Extensions: []string{".nim"}, Query: "nim.scm"},
+				},
+			},
+			expectErr: false,
+		},
+		{
+			name: "grammar without library",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Grammars:  []GrammarConfig{{Language: "nim", Extensions: []string{".nim"}, Query: "nim.scm"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "grammar extension without dot",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Grammars: []GrammarConfig{
+					{Language: "nim", Library: "/opt/grammars/nim.so", Extensions: []string{"nim"}, Query: "nim.scm"},
+				},
+			},
+			expectErr: true,
+		},
+		{
+			name: "negative overlap",
+			config: &Config{
+				Endpoint:     "http://localhost:11434",
+				CodeModel:    "model1",
+				TextModel:    "model2",
+				OverlapLines: -2,
+			},
+			expectErr: true,
+		},
+		{
+			name: "negative max tokens",
+			config: &Config{
+				Endpoint:      "http://localhost:11434",
+				CodeModel:     "model1",
+				TextModel:     "model2",
+				TextMaxTokens: -1,
+			},
+			expectErr: true,
+		},
+		{
+			name: "malformed exclude glob",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Exclude:   []string{"vendor/[a-"},
+			},
+			expectErr: true,
+		},
+		{
+			name: "language both allowed and excluded",
+			config: &Config{
+				Endpoint:         "http://localhost:11434",
+				CodeModel:        "model1",
+				TextModel:        "model2",
+				Languages:        []string{"rust", "python"},
+				ExcludeLanguages: []string{"python"},
+			},
+			expectErr: true,
+		},
+		{
+			name: "relative root",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Root:      "src/work",
+			},
+			expectErr: true,
+		},
+		{
+			name: "unknown submodules mode",
+			config: &Config{
+				Endpoint:   "http://localhost:11434",
+				CodeModel:  "model1",
+				TextModel:  "model2",
+				Submodules: "skip",
+			},
+			expectErr: true,
+		},
+		{
+			name: "unknown symlinks policy",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Symlinks:  "always",
+			},
+			expectErr: true,
+		},
+		{
+			name: "duplicate repo names",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Repos:     []RepoConfig{{Path: "../api"}, {Path: "services/api"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "index bucket without object store scheme",
+			config: &Config{
+				Endpoint:    "http://localhost:11434",
+				CodeModel:   "model1",
+				TextModel:   "model2",
+				IndexBucket: "https://bucket.example.com/index",
+			},
+			expectErr: true,
+		},
+		{
+			name: "ollama provider with keep-alive",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "ollama",
+				KeepAlive: "10m",
+			},
+			expectErr: false,
+		},
+		{
+			name: "keep-alive in seconds",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "ollama",
+				KeepAlive: "-1",
+			},
+			expectErr: false,
+		},
+		{
+			name: "ollama provider on the GPU",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "ollama",
+				GPU:       "on",
+			},
+			expectErr: false,
+		},
+		{
+			name: "invalid gpu",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "ollama",
+				GPU:       "cuda",
+			},
+			expectErr: true,
+		},
+		{
+			name: "onnx provider on CUDA",
+			config: &Config{
+				Endpoint:   "http://localhost:11434",
+				CodeModel:  "/models/bge-small-en-v1.5",
+				TextModel:  "/models/all-MiniLM-L6-v2",
+				Provider:   "onnx",
+				ONNXDevice: "cuda",
+			},
+			expectErr: false,
+		},
+		{
+			name: "invalid onnx device",
+			config: &Config{
+				Endpoint:   "http://localhost:11434",
+				CodeModel:  "/models/bge-small-en-v1.5",
+				TextModel:  "/models/all-MiniLM-L6-v2",
+				Provider:   "onnx",
+				ONNXDevice: "metal",
+			},
+			expectErr: true,
+		},
+		{
+			name: "unknown provider",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "not-a-provider",
+			},
+			expectErr: true,
+		},
+		{
+			name: "invalid keep-alive",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "ollama",
+				KeepAlive: "forever",
+			},
+			expectErr: true,
+		},
+		{
+			name: "huggingface provider with pooling",
+			config: &Config{
+				Endpoint:  "https://router.huggingface.co/hf-inference",
+				CodeModel: "microsoft/codebert-base",
+				TextModel: "BAAI/bge-small-en-v1.5",
+				Provider:  "huggingface",
+				Pooling:   map[string]string{"microsoft/codebert-base": "cls"},
+			},
+			expectErr: false,
+		},
+		{
+			name: "azure provider with token command",
+			config: &Config{
+				Endpoint:            "https://example.openai.azure.com",
+				CodeModel:           "code-deployment",
+				TextModel:           "text-deployment",
+				Provider:            "azure",
+				APIVersion:          "2024-06-01",
+				AzureADTokenCommand: "az account get-access-token --resource https://cognitiveservices.azure.com --query accessToken -o tsv",
+			},
+			expectErr: false,
+		},
+		{
+			name: "azure provider without credentials",
+			config: &Config{
+				Endpoint:  "https://example.openai.azure.com",
+				CodeModel: "code-deployment",
+				TextModel: "text-deployment",
+				Provider:  "azure",
+			},
+			expectErr: true,
+		},
+		{
+			name: "rate limits",
+			config: &Config{
+				Endpoint:          "https://api.openai.com",
+				CodeModel:         "text-embedding-3-small",
+				TextModel:         "text-embedding-3-small",
+				MaxInFlight:       4,
+				RequestsPerMinute: 3000,
+				TokensPerMinute:   1000000,
+			},
+			expectErr: false,
+		},
+		{
+			name: "negative rate limit",
+			config: &Config{
+				Endpoint:        "http://localhost:11434",
+				CodeModel:       "model1",
+				TextModel:       "model2",
+				TokensPerMinute: -1,
+			},
+			expectErr: true,
+		},
+		{
+			name: "retry policy",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Retry:     &RetryConfig{Attempts: 5, Backoff: "constant", Delay: "2s", Statuses: []int{429, 503}},
+			},
+			expectErr: false,
+		},
+		{
+			name: "invalid retry backoff",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Retry:     &RetryConfig{Backoff: "linear"},
+			},
+			expectErr: true,
+		},
+		{
+			name: "invalid retry status",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Retry:     &RetryConfig{Statuses: []int{42}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "unknown truncation",
+			config: &Config{
+				Endpoint:   "http://localhost:11434",
+				CodeModel:  "model1",
+				TextModel:  "model2",
+				Truncation: "shuffle",
+			},
+			expectErr: true,
+		},
+		{
+			name: "unknown pooling",
+			config: &Config{
+				Endpoint:  "http://localhost:8080",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Provider:  "tei",
+				Pooling:   map[string]string{"model1": "max"},
+			},
+			expectErr: true,
+		},
+		{
+			name: "shortened vectors",
+			config: &Config{
+				Endpoint:   "https://api.openai.com",
+				CodeModel:  "text-embedding-3-large",
+				TextModel:  "text-embedding-3-small",
+				Dimensions: 1024,
+			},
+			expectErr: false,
+		},
+		{
+			name: "vectors larger than the index stores",
+			config: &Config{
+				Endpoint:   "https://api.openai.com",
+				CodeModel:  "model1",
+				TextModel:  "model2",
+				Dimensions: 4096,
+			},
+			expectErr: true,
+		},
+		{
+			name: "matryoshka vectors",
+			config: &Config{
+				Endpoint:   "http://localhost:11434",
+				CodeModel:  "model1",
+				TextModel:  "nomic-embed-text",
+				Dimensions: 256,
+				Matryoshka: true,
+			},
+			expectErr: false,
+		},
+		{
+			name: "matryoshka without dimensions",
+			config: &Config{
+				Endpoint:   "http://localhost:11434",
+				CodeModel:  "model1",
+				TextModel:  "nomic-embed-text",
+				Matryoshka: true,
+			},
+			expectErr: true,
+		},
+		{
+			name: "binary quantization",
+			config: &Config{
+				Endpoint:     "http://localhost:11434",
+				CodeModel:    "model1",
+				TextModel:    "model2",
+				Quantization: "binary",
+			},
+			expectErr: false,
+		},
+		{
+			name: "unknown quantization",
+			config: &Config{
+				Endpoint:     "http://localhost:11434",
+				CodeModel:    "model1",
+				TextModel:    "model2",
+				Quantization: "pq",
+			},
+			expectErr: true,
+		},
+		{
+			name: "embedding spaces",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Spaces: []SpaceConfig{
+					{Name: "local", CodeModel: "nomic-embed-code"},
+					{Name: "voyage", Provider: "voyage", Endpoint: "https://api.voyageai.com", CodeModel: "voyage-code-3"},
+				},
+			},
+			expectErr: false,
+		},
+		{
+			name: "duplicate space name",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Spaces:    []SpaceConfig{{Name: "a", CodeModel: "m"}, {Name: "a", CodeModel: "n"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "space named default",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Spaces:    []SpaceConfig{{Name: "default", CodeModel: "m"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "space name that is not a table name",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Spaces:    []SpaceConfig{{Name: "My Space", CodeModel: "m"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "space named summary",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Spaces:    []SpaceConfig{{Name: "summary", CodeModel: "m"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "space without a code model",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Spaces:    []SpaceConfig{{Name: "a"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "fallback to a local model",
+			config: &Config{
+				Endpoint:  "https://api.voyageai.com",
+				Provider:  "voyage",
+				CodeModel: "voyage-code-3",
+				TextModel: "voyage-3",
+				Fallbacks: []FallbackConfig{{Provider: "ollama", Endpoint: "http://localhost:11434", CodeModel: "nomic-embed-code"}},
+			},
+			expectErr: false,
+		},
+		{
+			name: "token prices",
+			config: &Config{
+				Endpoint:    "http://localhost:11434",
+				CodeModel:   "model1",
+				TextModel:   "model2",
+				TokenPrices: map[string]float64{"model1": 0.18, "model2": 0},
+			},
+			expectErr: false,
+		},
+		{
+			name: "negative token price",
+			config: &Config{
+				Endpoint:    "http://localhost:11434",
+				CodeModel:   "model1",
+				TextModel:   "model2",
+				TokenPrices: map[string]float64{"model1": -1},
+			},
+			expectErr: true,
+		},
+		{
+			name: "fallback without a code model",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Fallbacks: []FallbackConfig{{Endpoint: "http://localhost:11434"}},
+			},
+			expectErr: true,
+		},
+		{
+			name: "fallback with an invalid endpoint",
+			config: &Config{
+				Endpoint:  "http://localhost:11434",
+				CodeModel: "model1",
+				TextModel: "model2",
+				Fallbacks: []FallbackConfig{{Endpoint: "localhost:11434", CodeModel: "m"}},
+			},
+			expectErr: true,
+		},
 	}
 
 	for _, tt := range tests {
@@ -214,3 +777,175 @@ func TestValidate_TrimsTrailingSlash(t *testing.T) {
 		t.Errorf("expected endpoint without trailing slash, got: %s", cfg.Endpoint)
 	}
 }
+
+func TestApplyEnv(t *testing.T) {
+	t.Setenv("CODE_SCOUT_API_KEY", "")
+	t.Setenv("OPENAI_API_KEY", "sk-openai")
+
+	cfg := &Config{Endpoint: "https://api.openai.com"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "sk-openai" {
+		t.Errorf("expected OPENAI_API_KEY for api.openai.com, got %q", cfg.APIKey)
+	}
+
+	cfg = &Config{Endpoint: "https://openrouter.ai/api"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "" {
+		t.Errorf("expected OPENAI_API_KEY not to be sent to another endpoint, got %q", cfg.APIKey)
+	}
+
+	cfg = &Config{Endpoint: "https://api.openai.com", APIKey: "sk-config"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "sk-config" {
+		t.Errorf("expected the configured key to win over OPENAI_API_KEY, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("HF_TOKEN", "hf-token")
+	cfg = &Config{Endpoint: "https://router.huggingface.co/hf-inference", Provider: "huggingface"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "hf-token" {
+		t.Errorf("expected HF_TOKEN with the huggingface provider, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("AZURE_OPENAI_API_KEY", "azure-key")
+	cfg = &Config{Endpoint: "https://example.openai.azure.com", Provider: "azure"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "azure-key" {
+		t.Errorf("expected AZURE_OPENAI_API_KEY with the azure provider, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("GEMINI_API_KEY", "gemini-key")
+	cfg = &Config{Endpoint: "https://generativelanguage.googleapis.com", Provider: "gemini"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "gemini-key" {
+		t.Errorf("expected GEMINI_API_KEY with the gemini provider, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("CO_API_KEY", "cohere-key")
+	cfg = &Config{Endpoint: "https://api.cohere.com", Provider: "cohere"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "cohere-key" {
+		t.Errorf("expected CO_API_KEY with the cohere provider, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("VOYAGE_API_KEY", "voyage-key")
+	cfg = &Config{Endpoint: "https://api.voyageai.com", Provider: "voyage"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "voyage-key" {
+		t.Errorf("expected VOYAGE_API_KEY with the voyage provider, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("JINA_API_KEY", "jina-key")
+	cfg = &Config{Endpoint: "https://api.jina.ai", Provider: "jina"}
+	cfg.ApplyEnv()
+	if cfg.APIKey != "jina-key" {
+		t.Errorf("expected JINA_API_KEY with the jina provider, got %q", cfg.APIKey)
+	}
+
+	t.Setenv("CODE_SCOUT_API_KEY", "sk-env")
+	cfg.ApplyEnv()
+	if cfg.APIKey != "sk-env" {
+		t.Errorf("expected CODE_SCOUT_API_KEY to override the configured key, got %q", cfg.APIKey)
+	}
+}
+
+func TestLoadNamed(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+
+	if _, err := LoadNamed("work"); err == nil {
+		t.Error("expected an error for an index that does not exist")
+	}
+	if _, err := NamedIndexDir("../work"); err == nil {
+		t.Error("expected an error for an invalid index name")
+	}
+
+	userConfig := &Config{CodeModel: "user-code"}
+	if err := userConfig.Save(filepath.Join(home, ".code-scout", "config.json")); err != nil {
+		t.Fatalf("failed to save user config: %v", err)
+	}
+	dir, err := NamedIndexDir("work")
+	if err != nil {
+		t.Fatalf("NamedIndexDir failed: %v", err)
+	}
+	indexConfig := &Config{TextModel: "work-text", Root: "/src/work"}
+	if err := indexConfig.Save(filepath.Join(dir, "config.json")); err != nil {
+		t.Fatalf("failed to save index config: %v", err)
+	}
+
+	cfg, err := LoadNamed("work")
+	if err != nil {
+		t.Fatalf("LoadNamed failed: %v", err)
+	}
+	if cfg.CodeModel != "user-code" || cfg.TextModel != "work-text" || cfg.Root != "/src/work" {
+		t.Errorf("expected the index config over the user config, got %+v", cfg)
+	}
+
+	names, err := ListIndexes()
+	if err != nil {
+		t.Fatalf("ListIndexes failed: %v", err)
+	}
+	if len(names) != 1 || names[0] != "work" {
+		t.Errorf("expected [work], got %v", names)
+	}
+}
+
+func TestLoadDirConfig(t *testing.T) {
+	dir := t.TempDir()
+	if cfg, err := LoadDirConfig(dir); err != nil || cfg != nil {
+		t.Fatalf("expected no config in an empty directory, got %v, %v", cfg, err)
+	}
+
+	contents := `# Settings for the API service
+overlap_lines = 3
+skip_generated = true
+exclude = [
+  "gen/**", # generated clients
+  'fixtures',
+]
+languages = ["go", "proto"]
+`
+	if err := os.WriteFile(filepath.Join(dir, DirConfigFileName), []byte(contents), 0644); err != nil {
+		t.Fatalf("failed to write test config: %v", err)
+	}
+	cfg, err := LoadDirConfig(dir)
+	if err != nil {
+		t.Fatalf("failed to load config: %v", err)
+	}
+	if cfg.OverlapLines == nil || *cfg.OverlapLines != 3 {
+		t.Errorf("expected overlap_lines 3, got %v", cfg.OverlapLines)
+	}
+	if cfg.SkipGenerated == nil || !*cfg.SkipGenerated {
+		t.Errorf("expected skip_generated true, got %v", cfg.SkipGenerated)
+	}
+	if cfg.MergeTokens != nil {
+		t.Errorf("expected merge_tokens unset, got %d", *cfg.MergeTokens)
+	}
+	if len(cfg.Exclude) != 2 || cfg.Exclude[0] != "gen/**" || cfg.Exclude[1] != "fixtures" {
+		t.Errorf("expected exclude [gen/** fixtures], got %v", cfg.Exclude)
+	}
+	if len(cfg.Languages) != 2 || cfg.Languages[1] != "proto" {
+		t.Errorf("expected languages [go proto], got %v", cfg.Languages)
+	}
+	if cfg.ExcludeLanguages != nil {
+		t.Errorf("expected exclude_languages unset, got %v", cfg.ExcludeLanguages)
+	}
+}
+
+func TestParseDirConfig_Invalid(t *testing.T) {
+	tests := []string{
+		"overlap_lines = -1",
+		"overlap_lines = \"3\"",
+		"chunk_size = 10",
+		"exclude = \"vendor\"",
+		"languages = [\"go\"]\nexclude_languages = [\"go\"]",
+		"[chunking]\noverlap_lines = 2",
+		"overlap_lines = 2\noverlap_lines = 3",
+		"exclude = [\"vendor\"",
+	}
+	for _, contents := range tests {
+		if _, err := parseDirConfig(contents); err == nil {
+			t.Errorf("expected an error for %q", contents)
+		}
+	}
+}
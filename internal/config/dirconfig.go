@@ -0,0 +1,121 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+)
+
+// DirConfigFileName is the file that overrides settings for the directory it
+// is in and everything below it, like an .editorconfig
+const DirConfigFileName = ".code-scout.toml"
+
+// DirConfig holds the settings a .code-scout.toml file overrides. Settings it
+// does not set are nil, and fall back to those of the directories above it.
+type DirConfig struct {
+	OverlapLines  *int  // overlap_lines
+	MergeTokens   *int  // merge_tokens
+	CodeMaxTokens *int  // code_max_tokens
+	TextMaxTokens *int  // text_max_tokens
+	SkipGenerated *bool // skip_generated
+
+	// Globs relative to the file's directory, added to the excludes above it
+	Exclude []string // exclude
+
+	// Language filters that replace those above the directory
+	Languages        []string // languages
+	ExcludeLanguages []string // exclude_languages
+}
+
+// LoadDirConfig loads the .code-scout.toml file of a directory, returning nil
+// when it has none
+func LoadDirConfig(dir string) (*DirConfig, error) {
+	path := filepath.Join(dir, DirConfigFileName)
+	data, err := os.ReadFile(path)
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	cfg, err := parseDirConfig(string(data))
+	if err != nil {
+		return nil, fmt.Errorf("invalid %s: %w", path, err)
+	}
+	return cfg, nil
+}
+
+// parseDirConfig decodes and validates the contents of a .code-scout.toml file
+func parseDirConfig(data string) (*DirConfig, error) {
+	values, err := parseTOML(data)
+	if err != nil {
+		return nil, err
+	}
+
+	cfg := &DirConfig{}
+	for key, value := range values {
+		switch key {
+		case "overlap_lines":
+			err = setInt(&cfg.OverlapLines, key, value)
+		case "merge_tokens":
+			err = setInt(&cfg.MergeTokens, key, value)
+		case "code_max_tokens":
+			err = setInt(&cfg.CodeMaxTokens, key, value)
+		case "text_max_tokens":
+			err = setInt(&cfg.TextMaxTokens, key, value)
+		case "skip_generated":
+			b, ok := value.(bool)
+			if !ok {
+				return nil, fmt.Errorf("%s must be true or false", key)
+			}
+			cfg.SkipGenerated = &b
+		case "exclude":
+			cfg.Exclude, err = stringList(key, value)
+		case "languages":
+			cfg.Languages, err = stringList(key, value)
+		case "exclude_languages":
+			cfg.ExcludeLanguages, err = stringList(key, value)
+		default:
+			return nil, fmt.Errorf("unknown setting %s", key)
+		}
+		if err != nil {
+			return nil, err
+		}
+	}
+
+	for _, language := range cfg.Languages {
+		if slices.Contains(cfg.ExcludeLanguages, language) {
+			return nil, fmt.Errorf("language %s is both in languages and exclude_languages", language)
+		}
+	}
+	return cfg, nil
+}
+
+// setInt sets a non-negative integer setting
+func setInt(field **int, key string, value any) error {
+	n, ok := value.(int64)
+	if !ok || n < 0 {
+		return fmt.Errorf("%s must be a non-negative integer", key)
+	}
+	i := int(n)
+	*field = &i
+	return nil
+}
+
+// stringList converts an array setting to a list of strings
+func stringList(key string, value any) ([]string, error) {
+	values, ok := value.([]any)
+	if !ok {
+		return nil, fmt.Errorf("%s must be an array of strings", key)
+	}
+	list := make([]string, 0, len(values))
+	for _, v := range values {
+		s, ok := v.(string)
+		if !ok {
+			return nil, fmt.Errorf("%s must be an array of strings", key)
+		}
+		list = append(list, s)
+	}
+	return list, nil
+}
@@ -0,0 +1,89 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+)
+
+// indexNameRegex is what a named index may be called
+var indexNameRegex = regexp.MustCompile(`^[A-Za-z0-9][A-Za-z0-9._-]*$`)
+
+// ValidateIndexName reports whether name can name an index
+func ValidateIndexName(name string) error {
+	if !indexNameRegex.MatchString(name) {
+		return fmt.Errorf("invalid index name %q: use letters, digits, '.', '_', and '-'", name)
+	}
+	return nil
+}
+
+// IndexesDir returns ~/.code-scout/indexes, which holds a directory per named
+// index with its config.json and storage
+func IndexesDir() (string, error) {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(homeDir, ".code-scout", "indexes"), nil
+}
+
+// NamedIndexDir returns the directory of a named index
+func NamedIndexDir(name string) (string, error) {
+	if err := ValidateIndexName(name); err != nil {
+		return "", err
+	}
+	dir, err := IndexesDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, name), nil
+}
+
+// ListIndexes returns the names of the named indexes, in order
+func ListIndexes() ([]string, error) {
+	dir, err := IndexesDir()
+	if err != nil {
+		return nil, err
+	}
+	entries, err := os.ReadDir(dir)
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	var names []string
+	for _, entry := range entries {
+		if entry.IsDir() && indexNameRegex.MatchString(entry.Name()) {
+			names = append(names, entry.Name())
+		}
+	}
+	return names, nil
+}
+
+// LoadNamed loads the configuration of a named index: the user-level config
+// overridden by the index's own config.json. The project-level config does
+// not apply, because a named index is not tied to the current directory.
+func LoadNamed(name string) (*Config, error) {
+	dir, err := NamedIndexDir(name)
+	if err != nil {
+		return nil, err
+	}
+	if _, err := os.Stat(dir); os.IsNotExist(err) {
+		return nil, fmt.Errorf("index %s does not exist; create it with 'code-scout indexes create %s'", name, name)
+	}
+
+	cfg := Default()
+	if userConfig, err := loadUserConfig(); err == nil && userConfig != nil {
+		mergeConfig(cfg, userConfig)
+	}
+	indexConfig, err := loadFromFile(filepath.Join(dir, "config.json"))
+	if err != nil {
+		return nil, fmt.Errorf("failed to load the config of index %s: %w", name, err)
+	}
+	if indexConfig != nil {
+		mergeConfig(cfg, indexConfig)
+	}
+	return cfg, nil
+}
@@ -0,0 +1,43 @@
+package embeddings
+
+import (
+	"net/http"
+	"net/url"
+
+	public "github.com/jlanders/code-scout/pkg/embeddings"
+)
+
+// DefaultAzureAPIVersion is the Azure OpenAI API version requested when none
+// is configured
+const DefaultAzureAPIVersion = "2024-06-01"
+
+// TokenFunc returns a bearer token for a request, such as an Azure AD token
+type TokenFunc = public.TokenFunc
+
+// NewAzureClient creates a client for an Azure OpenAI deployment, which serves
+// its model at <endpoint>/openai/deployments/<deployment>/embeddings. Requests
+// carry the API key in the api-key header, or without a key, an Azure AD token
+// from adToken.
+func NewAzureClient(endpoint, deployment, apiVersion, apiKey string, adToken TokenFunc, dimensions int) *OpenAIClient {
+	if apiVersion == "" {
+		apiVersion = DefaultAzureAPIVersion
+	}
+	c := NewClientWithDimensions(endpoint, "", deployment, dimensions)
+	c.url = endpoint + "/openai/deployments/" + url.PathEscape(deployment) + "/embeddings?api-version=" + url.QueryEscape(apiVersion)
+	c.authorize = func(req *http.Request) error {
+		if apiKey != "" {
+			req.Header.Set("api-key", apiKey)
+			return nil
+		}
+		if adToken == nil {
+			return nil
+		}
+		token, err := adToken()
+		if err != nil {
+			return err
+		}
+		req.Header.Set("Authorization", "Bearer "+token)
+		return nil
+	}
+	return c
+}
@@ -0,0 +1,117 @@
+package embeddings
+
+import (
+	"crypto/sha256"
+	"encoding/binary"
+	"encoding/hex"
+	"math"
+	"os"
+	"path/filepath"
+)
+
+// CachingClient keeps the embeddings of indexed texts on disk, keyed by the
+// hash of their content, and embeds with its client only the texts it has not
+// embedded before, so re-indexing moved or renamed code costs no requests.
+// Search queries are embedded without the cache.
+type CachingClient struct {
+	client Client
+	dir    string
+}
+
+// NewCachingClient creates a client that caches the embeddings client makes
+// in dir. The embeddings of a model are only comparable with its own, so each
+// model, with the settings that change its vectors, needs a directory of its
+// own; CacheDir names one.
+func NewCachingClient(client Client, dir string) *CachingClient {
+	return &CachingClient{client: client, dir: dir}
+}
+
+// CacheDir returns the directory under root that caches the embeddings of the
+// model identified by key, such as its provider, endpoint, and name
+func CacheDir(root, key string) string {
+	sum := sha256.Sum256([]byte(key))
+	return filepath.Join(root, hex.EncodeToString(sum[:8]))
+}
+
+// Embed generates an embedding for a search query
+func (c *CachingClient) Embed(text string) ([]float64, error) {
+	return c.client.Embed(text)
+}
+
+// EmbedMany returns the cached embeddings of texts, embedding and caching
+// those that are not cached yet
+func (c *CachingClient) EmbedMany(texts []string) ([][]float64, error) {
+	embeddings := make([][]float64, len(texts))
+	var missing []string
+	var missingIndices []int
+	for i, text := range texts {
+		if embedding := c.load(text); embedding != nil {
+			embeddings[i] = embedding
+			continue
+		}
+		missing = append(missing, text)
+		missingIndices = append(missingIndices, i)
+	}
+	if len(missing) == 0 {
+		return embeddings, nil
+	}
+
+	embedded, err := c.client.EmbedMany(missing)
+	if err != nil {
+		return nil, err
+	}
+	for i, embedding := range embedded {
+		embeddings[missingIndices[i]] = embedding
+		c.store(missing[i], embedding)
+	}
+	return embeddings, nil
+}
+
+// path returns the file that caches the embedding of text
+func (c *CachingClient) path(text string) string {
+	sum := sha256.Sum256([]byte(text))
+	hash := hex.EncodeToString(sum[:])
+	return filepath.Join(c.dir, hash[:2], hash)
+}
+
+// load reads the cached embedding of text, or nil when it is not cached
+func (c *CachingClient) load(text string) []float64 {
+	data, err := os.ReadFile(c.path(text))
+	if err != nil || len(data) == 0 || len(data)%4 != 0 {
+		return nil
+	}
+	embedding := make([]float64, len(data)/4)
+	for i := range embedding {
+		embedding[i] = float64(math.Float32frombits(binary.LittleEndian.Uint32(data[i*4:])))
+	}
+	return embedding
+}
+
+// store caches the embedding of text as float32s, the precision the index
+// stores vectors at. A cache that cannot be written only costs requests
+// later, so failures are ignored.
+func (c *CachingClient) store(text string, embedding []float64) {
+	data := make([]byte, len(embedding)*4)
+	for i, v := range embedding {
+		binary.LittleEndian.PutUint32(data[i*4:], math.Float32bits(float32(v)))
+	}
+	path := c.path(text)
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return
+	}
+	// Written under a temporary name and renamed, so concurrent workers and
+	// interrupted runs never leave a partial file under the final name
+	tmp, err := os.CreateTemp(filepath.Dir(path), ".tmp-*")
+	if err != nil {
+		return
+	}
+	_, writeErr := tmp.Write(data)
+	closeErr := tmp.Close()
+	if writeErr != nil || closeErr != nil {
+		os.Remove(tmp.Name())
+		return
+	}
+	if err := os.Rename(tmp.Name(), path); err != nil {
+		os.Remove(tmp.Name())
+	}
+}
@@ -0,0 +1,91 @@
+package embeddings
+
+import (
+	"slices"
+	"testing"
+)
+
+// countingClient returns [offset, len(text)] for every text and records the
+// texts it was asked to embed
+type countingClient struct {
+	offset   float64
+	embedded []string
+}
+
+func (c *countingClient) Embed(text string) ([]float64, error) {
+	embeddings, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+func (c *countingClient) EmbedMany(texts []string) ([][]float64, error) {
+	c.embedded = append(c.embedded, texts...)
+	embeddings := make([][]float64, len(texts))
+	for i, text := range texts {
+		embeddings[i] = []float64{c.offset, float64(len(text))}
+	}
+	return embeddings, nil
+}
+
+func TestCachingClientEmbedsOnlyMisses(t *testing.T) {
+	inner := &countingClient{offset: 1}
+	client := NewCachingClient(inner, CacheDir(t.TempDir(), "model-a"))
+
+	first, err := client.EmbedMany([]string{"func a() {}", "func b() {}"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	second, err := client.EmbedMany([]string{"func b() {}", "func c() {}", "func a() {}"})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if want := []string{"func a() {}", "func b() {}", "func c() {}"}; !slices.Equal(inner.embedded, want) {
+		t.Errorf("embedded %q, want each text once: %q", inner.embedded, want)
+	}
+	if !slices.Equal(second[0], first[1]) || !slices.Equal(second[2], first[0]) {
+		t.Errorf("cached embeddings %v differ from the ones made: %v", second, first)
+	}
+	if !slices.Equal(second[1], []float64{1, 11}) {
+		t.Errorf("embedding of the missed text = %v, want [1 11]", second[1])
+	}
+}
+
+func TestCachingClientKeepsModelsApart(t *testing.T) {
+	root := t.TempDir()
+	modelA, modelB := &countingClient{offset: 1}, &countingClient{offset: 2}
+	clientA := NewCachingClient(modelA, CacheDir(root, "model-a"))
+	clientB := NewCachingClient(modelB, CacheDir(root, "model-b"))
+
+	if _, err := clientA.EmbedMany([]string{"same text"}); err != nil {
+		t.Fatal(err)
+	}
+	embeddings, err := clientB.EmbedMany([]string{"same text"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(modelB.embedded) != 1 {
+		t.Errorf("model b embedded %q, want the text model a cached embedded again", modelB.embedded)
+	}
+	if !slices.Equal(embeddings[0], []float64{2, 9}) {
+		t.Errorf("model b's embedding = %v, want its own, [2 9]", embeddings[0])
+	}
+	if CacheDir(root, "model-a") == CacheDir(root, "model-b") {
+		t.Error("two models share a cache directory")
+	}
+}
+
+func TestCachingClientDoesNotCacheQueries(t *testing.T) {
+	inner := &countingClient{offset: 1}
+	client := NewCachingClient(inner, CacheDir(t.TempDir(), "model-a"))
+	for range 2 {
+		if _, err := client.Embed("query"); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if len(inner.embedded) != 2 {
+		t.Errorf("embedded %q, want the query embedded every time", inner.embedded)
+	}
+}
@@ -6,7 +6,10 @@ import (
 	"fmt"
 	"io"
 	"net/http"
-	"time"
+	"sort"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+	public "github.com/jlanders/code-scout/pkg/embeddings"
 )
 
 const (
@@ -16,32 +19,63 @@ const (
 	DefaultCodeModel = "code-scout-code"
 	// DefaultTextModel is the default model for text/documentation embeddings
 	DefaultTextModel = "code-scout-text"
+
+	// ProviderOpenAI is an OpenAI-compatible API, the default
+	ProviderOpenAI = "openai"
+	// ProviderOllama is Ollama's own API
+	ProviderOllama = "ollama"
+	// ProviderHuggingFace is the Hugging Face Inference API
+	ProviderHuggingFace = "huggingface"
+	// ProviderTEI is a text-embeddings-inference server's own API
+	ProviderTEI = "tei"
+	// ProviderAzure is Azure OpenAI, which serves models as deployments
+	ProviderAzure = "azure"
+	// ProviderGemini is the Gemini API
+	ProviderGemini = "gemini"
+	// ProviderCohere is Cohere's embed API
+	ProviderCohere = "cohere"
+	// ProviderVoyage is Voyage AI's embedding API
+	ProviderVoyage = "voyage"
+	// ProviderJina is Jina AI's embedding API
+	ProviderJina = "jina"
+	// ProviderONNX runs ONNX models in process, in builds with -tags onnx
+	ProviderONNX = "onnx"
+
+	// maxRequestInputs is the most texts OpenAI embeds in one request
+	maxRequestInputs = 2048
+	// requestTokenBudget caps the estimated tokens of one request, below the
+	// 300,000 OpenAI allows so that estimates that run low still fit
+	requestTokenBudget = 200000
 )
 
-// Client is the interface for embedding clients
-type Client interface {
-	Embed(text string) ([]float64, error)
-	EmbedMany(texts []string) ([][]float64, error)
-}
+// Client is the interface for embedding clients, that of the public package
+// so that providers of other modules implement it
+type Client = public.Client
 
 // OpenAIClient handles communication with OpenAI-compatible embedding APIs
 // (supports Ollama, OpenRouter, and other compatible services)
 type OpenAIClient struct {
-	endpoint string
-	apiKey   string        // Optional API key for authentication
-	model    string
-	client   *http.Client
+	endpoint   string
+	apiKey     string // Optional API key for authentication
+	model      string
+	dimensions int // Size of the vectors requested, for models that can shorten them; 0 for the model's own
+	client     *http.Client
+
+	url       string                       // Request URL, when it is not <endpoint>/v1/embeddings
+	authorize func(req *http.Request) error // Authenticates a request, in place of the API key as a bearer token
 }
 
 // openAIEmbedRequest represents the OpenAI-compatible embedding request
 type openAIEmbedRequest struct {
-	Model string      `json:"model"`
-	Input interface{} `json:"input"`
+	Model      string      `json:"model"`
+	Input      interface{} `json:"input"`
+	Dimensions int         `json:"dimensions,omitempty"`
 }
 
 // openAIEmbedResponse represents the OpenAI-compatible embedding response
 type openAIEmbedResponse struct {
 	Data []struct {
+		Index     int       `json:"index"`
 		Embedding []float64 `json:"embedding"`
 	} `json:"data"`
 }
@@ -83,6 +117,14 @@ func NewClientWithConfig(endpoint, apiKey, model string) *OpenAIClient {
 	}
 }
 
+// NewClientWithDimensions creates a new embedding client that asks the model
+// for vectors of the given size, as text-embedding-3 models can return
+func NewClientWithDimensions(endpoint, apiKey, model string, dimensions int) *OpenAIClient {
+	c := NewClientWithConfig(endpoint, apiKey, model)
+	c.dimensions = dimensions
+	return c
+}
+
 // Deprecated: Use NewClient instead
 func NewOllamaClient() *OpenAIClient {
 	return NewClient()
@@ -110,12 +152,32 @@ func (c *OpenAIClient) Embed(text string) ([]float64, error) {
 	return embeddings[0], nil
 }
 
-// EmbedMany generates embeddings for multiple texts in a single API request when possible
+// EmbedMany generates embeddings for multiple texts in a single API request
+// when possible. Texts beyond the inputs or tokens one request may carry are
+// sent in further requests.
 func (c *OpenAIClient) EmbedMany(texts []string) ([][]float64, error) {
 	if len(texts) == 0 {
 		return nil, nil
 	}
-	return c.embedWithRetry(texts, len(texts))
+	var all [][]float64
+	for start := 0; start < len(texts); {
+		end, tokens := start, 0
+		for end < len(texts) && end-start < maxRequestInputs {
+			n := tokenizer.Approximate{}.Count(texts[end])
+			if end > start && tokens+n > requestTokenBudget {
+				break
+			}
+			tokens += n
+			end++
+		}
+		embeddings, err := embedWithRetry(c.embedOnce, texts[start:end], end-start)
+		if err != nil {
+			return nil, err
+		}
+		all = append(all, embeddings...)
+		start = end
+	}
+	return all, nil
 }
 
 // EmbedBatch generates embeddings for multiple texts (alias for EmbedMany)
@@ -123,36 +185,12 @@ func (c *OpenAIClient) EmbedBatch(texts []string) ([][]float64, error) {
 	return c.EmbedMany(texts)
 }
 
-func (c *OpenAIClient) embedWithRetry(texts []string, expected int) ([][]float64, error) {
-	const maxRetries = 3
-	const initialBackoff = 1 * time.Second
-
-	var lastErr error
-	for attempt := 0; attempt < maxRetries; attempt++ {
-		if attempt > 0 {
-			backoff := initialBackoff * time.Duration(1<<uint(attempt-1))
-			time.Sleep(backoff)
-		}
-
-		embeddings, err := c.embedOnce(texts)
-		if err == nil {
-			if len(embeddings) != expected {
-				return nil, fmt.Errorf("expected %d embeddings, got %d", expected, len(embeddings))
-			}
-			return embeddings, nil
-		}
-
-		lastErr = err
-	}
-
-	return nil, fmt.Errorf("failed after %d attempts: %w", maxRetries, lastErr)
-}
-
 // embedOnce makes a single embedding request without retries
 func (c *OpenAIClient) embedOnce(texts []string) ([][]float64, error) {
 	reqBody := openAIEmbedRequest{
-		Model: c.model,
-		Input: texts,
+		Model:      c.model,
+		Input:      texts,
+		Dimensions: c.dimensions,
 	}
 
 	jsonData, err := json.Marshal(reqBody)
@@ -160,15 +198,22 @@ func (c *OpenAIClient) embedOnce(texts []string) ([][]float64, error) {
 		return nil, fmt.Errorf("failed to marshal request: %w", err)
 	}
 
-	url := c.endpoint + "/v1/embeddings"
+	url := c.url
+	if url == "" {
+		url = c.endpoint + "/v1/embeddings"
+	}
 	req, err := http.NewRequest("POST", url, bytes.NewBuffer(jsonData))
 	if err != nil {
 		return nil, fmt.Errorf("failed to create request: %w", err)
 	}
 	req.Header.Set("Content-Type", "application/json")
 
-	// Add Authorization header if API key is provided
-	if c.apiKey != "" {
+	if c.authorize != nil {
+		if err := c.authorize(req); err != nil {
+			return nil, fmt.Errorf("failed to authenticate request: %w", err)
+		}
+	} else if c.apiKey != "" {
+		// Add Authorization header if API key is provided
 		req.Header.Set("Authorization", "Bearer "+c.apiKey)
 	}
 
@@ -180,7 +225,7 @@ func (c *OpenAIClient) embedOnce(texts []string) ([][]float64, error) {
 
 	if resp.StatusCode != http.StatusOK {
 		body, _ := io.ReadAll(resp.Body)
-		return nil, fmt.Errorf("embedding API returned status %d: %s", resp.StatusCode, string(body))
+		return nil, statusError(resp, body)
 	}
 
 	var embedResp openAIEmbedResponse
@@ -192,6 +237,11 @@ func (c *OpenAIClient) embedOnce(texts []string) ([][]float64, error) {
 		return nil, fmt.Errorf("no embedding data in response")
 	}
 
+	// Embeddings are listed with the index of their input, which compatible
+	// APIs that list them in order may leave at 0
+	sort.SliceStable(embedResp.Data, func(i, j int) bool {
+		return embedResp.Data[i].Index < embedResp.Data[j].Index
+	})
 	embeddings := make([][]float64, len(embedResp.Data))
 	for i, data := range embedResp.Data {
 		embeddings[i] = data.Embedding
@@ -0,0 +1,127 @@
+package embeddings
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+)
+
+const (
+	// cohereBatchSize is the most texts Cohere embeds in one request
+	cohereBatchSize = 96
+	// cohereDocumentInput and cohereQueryInput are the input types Cohere
+	// embeds indexed chunks and search queries with; its models are trained
+	// to match queries to documents only when each is marked as such
+	cohereDocumentInput = "search_document"
+	cohereQueryInput    = "search_query"
+)
+
+// CohereClient handles communication with Cohere's embed API
+type CohereClient struct {
+	endpoint   string
+	apiKey     string
+	model      string
+	dimensions int // Size of the vectors requested, for models that can shorten them; 0 for the model's own
+	client     *http.Client
+}
+
+// cohereEmbedRequest represents a /v2/embed request
+type cohereEmbedRequest struct {
+	Model           string   `json:"model"`
+	Texts           []string `json:"texts"`
+	InputType       string   `json:"input_type"`
+	EmbeddingTypes  []string `json:"embedding_types"`
+	Truncate        string   `json:"truncate"`
+	OutputDimension int      `json:"output_dimension,omitempty"`
+}
+
+// cohereEmbedResponse represents a /v2/embed response
+type cohereEmbedResponse struct {
+	Embeddings struct {
+		Float [][]float64 `json:"float"`
+	} `json:"embeddings"`
+}
+
+// NewCohereClient creates a client for a Cohere embedding model such as
+// embed-v4.0, served at <endpoint>/v2/embed
+func NewCohereClient(endpoint, apiKey, model string, dimensions int) *CohereClient {
+	return &CohereClient{
+		endpoint:   endpoint,
+		apiKey:     apiKey,
+		model:      model,
+		dimensions: dimensions,
+		client:     &http.Client{},
+	}
+}
+
+// Embed generates an embedding for a search query with retry logic
+func (c *CohereClient) Embed(text string) ([]float64, error) {
+	embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+		return c.embedOnce(texts, cohereQueryInput)
+	}, []string{text}, 1)
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for chunks being indexed, in requests of up
+// to cohereBatchSize texts
+func (c *CohereClient) EmbedMany(texts []string) ([][]float64, error) {
+	var all [][]float64
+	for start := 0; start < len(texts); start += cohereBatchSize {
+		end := min(start+cohereBatchSize, len(texts))
+		embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+			return c.embedOnce(texts, cohereDocumentInput)
+		}, texts[start:end], end-start)
+		if err != nil {
+			return nil, err
+		}
+		all = append(all, embeddings...)
+	}
+	return all, nil
+}
+
+// embedOnce makes a single /v2/embed request without retries
+func (c *CohereClient) embedOnce(texts []string, inputType string) ([][]float64, error) {
+	// Texts over the model's input limit are cut rather than rejected
+	jsonData, err := json.Marshal(cohereEmbedRequest{
+		Model:           c.model,
+		Texts:           texts,
+		InputType:       inputType,
+		EmbeddingTypes:  []string{"float"},
+		Truncate:        "END",
+		OutputDimension: c.dimensions,
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	req, err := http.NewRequest("POST", c.endpoint+"/v2/embed", bytes.NewBuffer(jsonData))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	if c.apiKey != "" {
+		req.Header.Set("Authorization", "Bearer "+c.apiKey)
+	}
+
+	resp, err := c.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to make request to embedding API: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, statusError(resp, body)
+	}
+
+	var embedResp cohereEmbedResponse
+	if err := json.NewDecoder(resp.Body).Decode(&embedResp); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+	return embedResp.Embeddings.Float, nil
+}
@@ -0,0 +1,123 @@
+package embeddings
+
+import (
+	"errors"
+	"fmt"
+	"net/http"
+	"sync"
+	"time"
+)
+
+// fallbackCooldown is how long a client that failed is passed over before
+// it is tried again, so each request does not wait out its retries first
+const fallbackCooldown = time.Minute
+
+// FallbackClient embeds texts with the first of an ordered list of clients
+// that succeeds: a primary client, usually a cloud provider, and fallbacks,
+// usually local models, that stand in for it while it is down or rate
+// limited. Vectors of different models cannot be compared, so the
+// ...From methods report which fallback embedded the texts.
+type FallbackClient struct {
+	clients []Client
+	models  []string // The fallbacks' models, identifying their vectors
+
+	mu        sync.Mutex
+	downUntil []time.Time // When each client that failed is tried again
+}
+
+// NewFallbackClient creates a client that embeds with primary and, when it
+// fails, with each of fallbacks in turn; models names the fallbacks' models
+func NewFallbackClient(primary Client, fallbacks []Client, models []string) *FallbackClient {
+	return &FallbackClient{
+		clients:   append([]Client{primary}, fallbacks...),
+		models:    append([]string{""}, models...),
+		downUntil: make([]time.Time, len(fallbacks)+1),
+	}
+}
+
+// Embed generates an embedding for the given text
+func (c *FallbackClient) Embed(text string) ([]float64, error) {
+	embedding, _, err := c.EmbedFrom(text)
+	return embedding, err
+}
+
+// EmbedMany generates embeddings for multiple texts
+func (c *FallbackClient) EmbedMany(texts []string) ([][]float64, error) {
+	embeddings, _, err := c.EmbedManyFrom(texts)
+	return embeddings, err
+}
+
+// EmbedFrom generates an embedding for the given text and returns the model
+// of the fallback that embedded it, or "" when the primary client did
+func (c *FallbackClient) EmbedFrom(text string) ([]float64, string, error) {
+	var embedding []float64
+	model, err := c.try(func(client Client) (err error) {
+		embedding, err = client.Embed(text)
+		return err
+	})
+	return embedding, model, err
+}
+
+// EmbedManyFrom generates embeddings for multiple texts and returns the model
+// of the fallback that embedded them, or "" when the primary client did
+func (c *FallbackClient) EmbedManyFrom(texts []string) ([][]float64, string, error) {
+	var embeddings [][]float64
+	model, err := c.try(func(client Client) (err error) {
+		embeddings, err = client.EmbedMany(texts)
+		return err
+	})
+	return embeddings, model, err
+}
+
+// try calls embed with each client in turn until one succeeds, passing over
+// clients that failed within the cooldown unless every one has. An error
+// that another model would answer the same way, as a rejected text, is
+// returned as it is.
+func (c *FallbackClient) try(embed func(Client) error) (string, error) {
+	var errs []error
+	for _, skipDown := range []bool{true, false} {
+		for i, client := range c.clients {
+			if skipDown && c.isDown(i) {
+				continue
+			}
+			err := embed(client)
+			if err == nil {
+				return c.models[i], nil
+			}
+			if !unavailable(err) {
+				return "", err
+			}
+			c.markDown(i)
+			if i > 0 {
+				err = fmt.Errorf("fallback %s: %w", c.models[i], err)
+			}
+			errs = append(errs, err)
+		}
+		if len(errs) > 0 {
+			break
+		}
+	}
+	return "", fmt.Errorf("every embedding provider failed: %w", errors.Join(errs...))
+}
+
+func (c *FallbackClient) isDown(i int) bool {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return time.Now().Before(c.downUntil[i])
+}
+
+func (c *FallbackClient) markDown(i int) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.downUntil[i] = time.Now().Add(fallbackCooldown)
+}
+
+// unavailable reports whether err means the provider could not embed at
+// all: it could not be reached, it rate limited the request, or it failed
+func unavailable(err error) bool {
+	var statusErr *StatusError
+	if !errors.As(err, &statusErr) {
+		return true
+	}
+	return statusErr.StatusCode == http.StatusTooManyRequests || statusErr.StatusCode >= 500
+}
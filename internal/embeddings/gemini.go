@@ -0,0 +1,150 @@
+package embeddings
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+)
+
+const (
+	// geminiBatchSize is the most texts Gemini embeds in one request
+	geminiBatchSize = 100
+	// geminiDocumentTask and geminiQueryTask are the task types Gemini embeds
+	// indexed chunks and search queries with, which it embeds differently so
+	// that queries land near the documents that answer them
+	geminiDocumentTask = "RETRIEVAL_DOCUMENT"
+	geminiQueryTask    = "RETRIEVAL_QUERY"
+)
+
+// GeminiClient handles communication with the Gemini API's embedding models
+type GeminiClient struct {
+	endpoint   string
+	apiKey     string
+	model      string // Model resource name, e.g. models/gemini-embedding-001
+	dimensions int    // Size of the vectors requested; 0 for the model's own
+	client     *http.Client
+}
+
+// geminiPart is a piece of the content of a Gemini request
+type geminiPart struct {
+	Text string `json:"text"`
+}
+
+// geminiContent holds the text of a Gemini request
+type geminiContent struct {
+	Parts []geminiPart `json:"parts"`
+}
+
+// geminiEmbedRequest represents one text of a batchEmbedContents request
+type geminiEmbedRequest struct {
+	Model                string        `json:"model"`
+	Content              geminiContent `json:"content"`
+	TaskType             string        `json:"taskType"`
+	OutputDimensionality int           `json:"outputDimensionality,omitempty"`
+}
+
+// geminiBatchRequest represents a batchEmbedContents request
+type geminiBatchRequest struct {
+	Requests []geminiEmbedRequest `json:"requests"`
+}
+
+// geminiBatchResponse represents a batchEmbedContents response
+type geminiBatchResponse struct {
+	Embeddings []struct {
+		Values []float64 `json:"values"`
+	} `json:"embeddings"`
+}
+
+// NewGeminiClient creates a client for a Gemini embedding model such as
+// gemini-embedding-001, served at <endpoint>/v1beta/models/<model>
+func NewGeminiClient(endpoint, apiKey, model string, dimensions int) *GeminiClient {
+	if !strings.HasPrefix(model, "models/") {
+		model = "models/" + model
+	}
+	return &GeminiClient{
+		endpoint:   endpoint,
+		apiKey:     apiKey,
+		model:      model,
+		dimensions: dimensions,
+		client:     &http.Client{},
+	}
+}
+
+// Embed generates an embedding for a search query with retry logic
+func (c *GeminiClient) Embed(text string) ([]float64, error) {
+	embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+		return c.embedOnce(texts, geminiQueryTask)
+	}, []string{text}, 1)
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for chunks being indexed, in requests of up
+// to geminiBatchSize texts
+func (c *GeminiClient) EmbedMany(texts []string) ([][]float64, error) {
+	var all [][]float64
+	for start := 0; start < len(texts); start += geminiBatchSize {
+		end := min(start+geminiBatchSize, len(texts))
+		embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+			return c.embedOnce(texts, geminiDocumentTask)
+		}, texts[start:end], end-start)
+		if err != nil {
+			return nil, err
+		}
+		all = append(all, embeddings...)
+	}
+	return all, nil
+}
+
+// embedOnce makes a single batchEmbedContents request without retries
+func (c *GeminiClient) embedOnce(texts []string, taskType string) ([][]float64, error) {
+	batch := geminiBatchRequest{Requests: make([]geminiEmbedRequest, len(texts))}
+	for i, text := range texts {
+		batch.Requests[i] = geminiEmbedRequest{
+			Model:                c.model,
+			Content:              geminiContent{Parts: []geminiPart{{Text: text}}},
+			TaskType:             taskType,
+			OutputDimensionality: c.dimensions,
+		}
+	}
+	jsonData, err := json.Marshal(batch)
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	url := c.endpoint + "/v1beta/" + c.model + ":batchEmbedContents"
+	req, err := http.NewRequest("POST", url, bytes.NewBuffer(jsonData))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	if c.apiKey != "" {
+		req.Header.Set("x-goog-api-key", c.apiKey)
+	}
+
+	resp, err := c.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to make request to embedding API: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, statusError(resp, body)
+	}
+
+	var batchResp geminiBatchResponse
+	if err := json.NewDecoder(resp.Body).Decode(&batchResp); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+	embeddings := make([][]float64, len(batchResp.Embeddings))
+	for i, embedding := range batchResp.Embeddings {
+		embeddings[i] = embedding.Values
+	}
+	return embeddings, nil
+}
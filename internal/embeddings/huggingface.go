@@ -0,0 +1,171 @@
+package embeddings
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"math"
+	"net/http"
+	"strings"
+)
+
+const (
+	// PoolingMean averages the token embeddings of a text, the default
+	PoolingMean = "mean"
+	// PoolingCLS takes the embedding of a text's first token
+	PoolingCLS = "cls"
+)
+
+// HuggingFaceClient handles communication with the Hugging Face Inference API
+// and with text-embeddings-inference (TEI) servers, which share a request
+// format. Models that are not sentence-transformers return an embedding per
+// token, which the client pools into one per text.
+type HuggingFaceClient struct {
+	url     string
+	token   string // Optional Hugging Face or TEI API token
+	model   string
+	pooling string // How token embeddings are pooled: PoolingMean or PoolingCLS
+	client  *http.Client
+}
+
+// huggingFaceEmbedRequest represents a feature-extraction or TEI /embed request
+type huggingFaceEmbedRequest struct {
+	Inputs   []string `json:"inputs"`
+	Truncate bool     `json:"truncate"`
+}
+
+// NewHuggingFaceClient creates a client for the Hugging Face Inference API,
+// which serves the model at <endpoint>/models/<model>
+func NewHuggingFaceClient(endpoint, token, model, pooling string) *HuggingFaceClient {
+	return newHuggingFaceClient(endpoint+"/models/"+model, token, model, pooling)
+}
+
+// NewTEIClient creates a client for a text-embeddings-inference server, which
+// serves the one model it was started with at <endpoint>/embed
+func NewTEIClient(endpoint, token, model, pooling string) *HuggingFaceClient {
+	return newHuggingFaceClient(endpoint+"/embed", token, model, pooling)
+}
+
+func newHuggingFaceClient(url, token, model, pooling string) *HuggingFaceClient {
+	if pooling == "" {
+		pooling = PoolingMean
+	}
+	return &HuggingFaceClient{
+		url:     url,
+		token:   token,
+		model:   model,
+		pooling: pooling,
+		client:  &http.Client{},
+	}
+}
+
+// Embed generates an embedding for the given text with retry logic
+func (c *HuggingFaceClient) Embed(text string) ([]float64, error) {
+	embeddings, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for multiple texts in a single request
+func (c *HuggingFaceClient) EmbedMany(texts []string) ([][]float64, error) {
+	if len(texts) == 0 {
+		return nil, nil
+	}
+	return embedWithRetry(c.embedOnce, texts, len(texts))
+}
+
+// embedOnce makes a single embedding request without retries
+func (c *HuggingFaceClient) embedOnce(texts []string) ([][]float64, error) {
+	// Texts over the model's input limit are cut rather than rejected
+	jsonData, err := json.Marshal(huggingFaceEmbedRequest{Inputs: texts, Truncate: true})
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	req, err := http.NewRequest("POST", c.url, bytes.NewBuffer(jsonData))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	// A model the Inference API has not loaded yet is waited for rather than
+	// answered with 503
+	req.Header.Set("X-Wait-For-Model", "true")
+	if c.token != "" {
+		req.Header.Set("Authorization", "Bearer "+c.token)
+	}
+
+	resp, err := c.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to make request to embedding API: %w", err)
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read response: %w", err)
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, statusError(resp, []byte(strings.TrimSpace(string(body))))
+	}
+	return c.decode(body)
+}
+
+// decode reads the embeddings of a response: one per text from a pooling
+// model, or one per token of each text, which are pooled
+func (c *HuggingFaceClient) decode(body []byte) ([][]float64, error) {
+	var pooled [][]float64
+	if err := json.Unmarshal(body, &pooled); err == nil {
+		return pooled, nil
+	}
+	var tokens [][][]float64
+	if err := json.Unmarshal(body, &tokens); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+	embeddings := make([][]float64, len(tokens))
+	for i, tokenEmbeddings := range tokens {
+		if len(tokenEmbeddings) == 0 {
+			return nil, fmt.Errorf("no token embeddings in response for %s", c.model)
+		}
+		embeddings[i] = normalize(pool(tokenEmbeddings, c.pooling))
+	}
+	return embeddings, nil
+}
+
+// pool combines the token embeddings of a text into one embedding
+func pool(tokens [][]float64, pooling string) []float64 {
+	if pooling == PoolingCLS {
+		return tokens[0]
+	}
+	mean := make([]float64, len(tokens[0]))
+	for _, token := range tokens {
+		for j, v := range token {
+			if j < len(mean) {
+				mean[j] += v
+			}
+		}
+	}
+	for j := range mean {
+		mean[j] /= float64(len(tokens))
+	}
+	return mean
+}
+
+// normalize scales an embedding to unit length, as sentence-transformers
+// models do after pooling
+func normalize(embedding []float64) []float64 {
+	var sum float64
+	for _, v := range embedding {
+		sum += v * v
+	}
+	if sum == 0 {
+		return embedding
+	}
+	norm := math.Sqrt(sum)
+	for i := range embedding {
+		embedding[i] /= norm
+	}
+	return embedding
+}
@@ -0,0 +1,131 @@
+package embeddings
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"sort"
+)
+
+// jinaBatchSize is the most texts sent to Jina AI in one request, which keeps
+// a request of long-context chunks to a size the API accepts
+const jinaBatchSize = 256
+
+// jinaTasks are the tasks that Jina AI's task-aware models embed indexed chunks
+// and search queries with, by model. Other models, such as
+// jina-embeddings-v2-base-code, take no task.
+var jinaTasks = map[string][2]string{
+	"jina-embeddings-v3":        {"retrieval.passage", "retrieval.query"},
+	"jina-embeddings-v4":        {"retrieval.passage", "retrieval.query"},
+	"jina-code-embeddings-0.5b": {"nl2code.passage", "nl2code.query"},
+	"jina-code-embeddings-1.5b": {"nl2code.passage", "nl2code.query"},
+}
+
+// JinaClient handles communication with Jina AI's embedding API
+type JinaClient struct {
+	endpoint   string
+	apiKey     string
+	model      string
+	dimensions int // Size of the vectors requested, for models that can shorten them; 0 for the model's own
+	client     *http.Client
+}
+
+// jinaEmbedRequest represents a /v1/embeddings request
+type jinaEmbedRequest struct {
+	Model      string   `json:"model"`
+	Input      []string `json:"input"`
+	Task       string   `json:"task,omitempty"`
+	Dimensions int      `json:"dimensions,omitempty"`
+	Truncate   bool     `json:"truncate"`
+}
+
+// NewJinaClient creates a client for a Jina AI model such as
+// jina-code-embeddings-1.5b, served at <endpoint>/v1/embeddings
+func NewJinaClient(endpoint, apiKey, model string, dimensions int) *JinaClient {
+	return &JinaClient{
+		endpoint:   endpoint,
+		apiKey:     apiKey,
+		model:      model,
+		dimensions: dimensions,
+		client:     &http.Client{},
+	}
+}
+
+// Embed generates an embedding for a search query with retry logic
+func (c *JinaClient) Embed(text string) ([]float64, error) {
+	embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+		return c.embedOnce(texts, jinaTasks[c.model][1])
+	}, []string{text}, 1)
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for chunks being indexed, in requests of up
+// to jinaBatchSize texts
+func (c *JinaClient) EmbedMany(texts []string) ([][]float64, error) {
+	var all [][]float64
+	for start := 0; start < len(texts); start += jinaBatchSize {
+		end := min(start+jinaBatchSize, len(texts))
+		embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+			return c.embedOnce(texts, jinaTasks[c.model][0])
+		}, texts[start:end], end-start)
+		if err != nil {
+			return nil, err
+		}
+		all = append(all, embeddings...)
+	}
+	return all, nil
+}
+
+// embedOnce makes a single /v1/embeddings request without retries
+func (c *JinaClient) embedOnce(texts []string, task string) ([][]float64, error) {
+	// Texts over the model's input limit are cut rather than rejected
+	jsonData, err := json.Marshal(jinaEmbedRequest{
+		Model:      c.model,
+		Input:      texts,
+		Task:       task,
+		Dimensions: c.dimensions,
+		Truncate:   true,
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	req, err := http.NewRequest("POST", c.endpoint+"/v1/embeddings", bytes.NewBuffer(jsonData))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	if c.apiKey != "" {
+		req.Header.Set("Authorization", "Bearer "+c.apiKey)
+	}
+
+	resp, err := c.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to make request to embedding API: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, statusError(resp, body)
+	}
+
+	// The response has the OpenAI format
+	var embedResp openAIEmbedResponse
+	if err := json.NewDecoder(resp.Body).Decode(&embedResp); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+	sort.SliceStable(embedResp.Data, func(i, j int) bool {
+		return embedResp.Data[i].Index < embedResp.Data[j].Index
+	})
+	embeddings := make([][]float64, len(embedResp.Data))
+	for i, data := range embedResp.Data {
+		embeddings[i] = data.Embedding
+	}
+	return embeddings, nil
+}
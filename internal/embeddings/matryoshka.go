@@ -0,0 +1,47 @@
+package embeddings
+
+// TruncatingClient shortens the embeddings of a Matryoshka (MRL) model to
+// fewer dimensions and scales them back to unit length. Such models, like
+// text-embedding-3 and nomic-embed-text v1.5, put the most information in the
+// leading dimensions, so the shortened vectors lose little recall. This
+// serves models whose API cannot shorten vectors itself.
+type TruncatingClient struct {
+	client     Client
+	dimensions int
+}
+
+// NewTruncatingClient creates a client that shortens client's embeddings to
+// dimensions
+func NewTruncatingClient(client Client, dimensions int) *TruncatingClient {
+	return &TruncatingClient{client: client, dimensions: dimensions}
+}
+
+// Embed generates a shortened embedding for the given text
+func (c *TruncatingClient) Embed(text string) ([]float64, error) {
+	embedding, err := c.client.Embed(text)
+	if err != nil {
+		return nil, err
+	}
+	return c.truncate(embedding), nil
+}
+
+// EmbedMany generates shortened embeddings for multiple texts
+func (c *TruncatingClient) EmbedMany(texts []string) ([][]float64, error) {
+	embeddings, err := c.client.EmbedMany(texts)
+	if err != nil {
+		return nil, err
+	}
+	for i, embedding := range embeddings {
+		embeddings[i] = c.truncate(embedding)
+	}
+	return embeddings, nil
+}
+
+// truncate keeps the leading dimensions of an embedding and renormalizes
+// them; embeddings no longer than that are left as they are
+func (c *TruncatingClient) truncate(embedding []float64) []float64 {
+	if len(embedding) <= c.dimensions {
+		return embedding
+	}
+	return normalize(append([]float64(nil), embedding[:c.dimensions]...))
+}
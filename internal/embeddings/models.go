@@ -0,0 +1,32 @@
+package embeddings
+
+import "strings"
+
+// inputLimits are the input limits, in tokens, of hosted embedding models
+var inputLimits = map[string]int{
+	"text-embedding-3-small": 8191,
+	"text-embedding-3-large": 8191,
+	"text-embedding-ada-002": 8191,
+	"gemini-embedding-001":   2048,
+	"text-embedding-004":     2048,
+
+	// Cohere truncates longer inputs, which the chunker splits instead
+	"embed-english-v3.0":       512,
+	"embed-multilingual-v3.0":  512,
+	"embed-english-light-v3.0": 512,
+
+	"voyage-code-3": 32000,
+	"voyage-code-2": 16000,
+
+	"jina-embeddings-v2-base-code": 8192,
+	"jina-embeddings-v3":           8192,
+	"jina-embeddings-v4":           32768,
+	"jina-code-embeddings-0.5b":    32768,
+	"jina-code-embeddings-1.5b":    32768,
+}
+
+// MaxInputTokens returns the input limit in tokens of a known model, or 0 for
+// a model whose limit is not known
+func MaxInputTokens(model string) int {
+	return inputLimits[strings.TrimPrefix(model, "models/")]
+}
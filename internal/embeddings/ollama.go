@@ -0,0 +1,172 @@
+package embeddings
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strconv"
+	"strings"
+	"sync/atomic"
+)
+
+const (
+	// GPUOn asks Ollama to run every layer of the model on the GPU, with CUDA,
+	// ROCm, or Metal, falling back to the CPU if the GPU cannot run it
+	GPUOn = "on"
+	// GPUOff makes Ollama run the model on the CPU
+	GPUOff = "off"
+	// allGPULayers is a num_gpu larger than any model's layer count, so every
+	// layer is offloaded
+	allGPULayers = 999
+)
+
+// OllamaClient handles communication with Ollama's own embedding API, which
+// needs no API key and, unlike Ollama's OpenAI-compatible API, can keep the
+// model loaded between indexing runs
+type OllamaClient struct {
+	endpoint  string
+	model     string
+	keepAlive interface{} // Duration string or number of seconds; nil for Ollama's default
+	gpu       string      // GPUOn, GPUOff, or "" for Ollama's own choice
+	onCPU     atomic.Bool // Set once the GPU failed and requests fell back to the CPU
+	client    *http.Client
+}
+
+// ollamaEmbedRequest represents an /api/embeddings request
+type ollamaEmbedRequest struct {
+	Model     string         `json:"model"`
+	Prompt    string         `json:"prompt"`
+	KeepAlive interface{}    `json:"keep_alive,omitempty"`
+	Options   *ollamaOptions `json:"options,omitempty"`
+}
+
+// ollamaOptions holds the model options of a request
+type ollamaOptions struct {
+	NumGPU int `json:"num_gpu"` // Layers offloaded to the GPU; 0 runs the model on the CPU
+}
+
+// ollamaEmbedResponse represents an /api/embeddings response
+type ollamaEmbedResponse struct {
+	Embedding []float64 `json:"embedding"`
+}
+
+// NewOllamaAPIClient creates a client for Ollama's /api/embeddings endpoint.
+// keepAlive is how long Ollama keeps the model loaded after a request, as a
+// duration such as "10m" or a number of seconds; "" leaves Ollama's default.
+// gpu is GPUOn, GPUOff, or "" to let Ollama choose where the model runs.
+func NewOllamaAPIClient(endpoint, model, keepAlive, gpu string) *OllamaClient {
+	c := &OllamaClient{
+		endpoint: endpoint,
+		model:    model,
+		gpu:      gpu,
+		client:   &http.Client{},
+	}
+	if keepAlive != "" {
+		c.keepAlive = keepAlive
+		// Ollama reads a bare number as seconds only when it is sent as one
+		if seconds, err := strconv.Atoi(keepAlive); err == nil {
+			c.keepAlive = seconds
+		}
+	}
+	return c
+}
+
+// Embed generates an embedding for the given text with retry logic
+func (c *OllamaClient) Embed(text string) ([]float64, error) {
+	embeddings, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for multiple texts. /api/embeddings embeds
+// one text per request, so the texts are sent one after another.
+func (c *OllamaClient) EmbedMany(texts []string) ([][]float64, error) {
+	if len(texts) == 0 {
+		return nil, nil
+	}
+	return embedWithRetry(c.embedOnce, texts, len(texts))
+}
+
+// embedOnce embeds texts without retries. With GPUOn, a failure to load the
+// model on the GPU moves every later request to the CPU. Other server errors
+// are left to embedWithRetry.
+func (c *OllamaClient) embedOnce(texts []string) ([][]float64, error) {
+	embeddings := make([][]float64, 0, len(texts))
+	for _, text := range texts {
+		embedding, err := c.embedText(text)
+		if err != nil && c.gpu == GPUOn && isGPUFailure(err) {
+			if c.onCPU.CompareAndSwap(false, true) {
+				fmt.Fprintf(os.Stderr, "Warning: Ollama could not run %s on the GPU (%v); embedding on the CPU\n", c.model, err)
+			}
+			embedding, err = c.embedText(text)
+		}
+		if err != nil {
+			return nil, err
+		}
+		embeddings = append(embeddings, embedding)
+	}
+	return embeddings, nil
+}
+
+// isGPUFailure reports whether err is Ollama failing to load or run a model on
+// the GPU, a 500 whose message mentions running out of memory, CUDA, or the
+// model failing to load
+func isGPUFailure(err error) bool {
+	var statusErr *StatusError
+	if !errors.As(err, &statusErr) || statusErr.StatusCode != http.StatusInternalServerError {
+		return false
+	}
+	body := strings.ToLower(statusErr.Body)
+	return strings.Contains(body, "out of memory") || strings.Contains(body, "cuda") || strings.Contains(body, "failed to load")
+}
+
+// embedText makes a single /api/embeddings request
+func (c *OllamaClient) embedText(text string) ([]float64, error) {
+	request := ollamaEmbedRequest{
+		Model:     c.model,
+		Prompt:    text,
+		KeepAlive: c.keepAlive,
+	}
+	switch {
+	case c.gpu == GPUOff || c.onCPU.Load():
+		request.Options = &ollamaOptions{NumGPU: 0}
+	case c.gpu == GPUOn:
+		request.Options = &ollamaOptions{NumGPU: allGPULayers}
+	}
+	jsonData, err := json.Marshal(request)
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	req, err := http.NewRequest("POST", c.endpoint+"/api/embeddings", bytes.NewBuffer(jsonData))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+
+	resp, err := c.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to make request to Ollama: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, statusError(resp, body)
+	}
+
+	var embedResp ollamaEmbedResponse
+	if err := json.NewDecoder(resp.Body).Decode(&embedResp); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+	if len(embedResp.Embedding) == 0 {
+		return nil, fmt.Errorf("no embedding in response; is %s an embedding model?", c.model)
+	}
+	return embedResp.Embedding, nil
+}
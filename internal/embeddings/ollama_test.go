@@ -0,0 +1,80 @@
+package embeddings
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+// gpuServer is an Ollama that answers requests for the GPU with status and
+// body, and embeds requests for the CPU. It counts the requests of each.
+func gpuServer(t *testing.T, status int, body string) (server *httptest.Server, gpuRequests, cpuRequests *int) {
+	t.Helper()
+	gpuRequests, cpuRequests = new(int), new(int)
+	server = httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var request ollamaEmbedRequest
+		if err := json.NewDecoder(r.Body).Decode(&request); err != nil {
+			t.Errorf("failed to decode request: %v", err)
+		}
+		if request.Options != nil && request.Options.NumGPU > 0 {
+			*gpuRequests++
+			http.Error(w, body, status)
+			return
+		}
+		*cpuRequests++
+		json.NewEncoder(w).Encode(ollamaEmbedResponse{Embedding: []float64{1, 2}})
+	}))
+	t.Cleanup(server.Close)
+	return server, gpuRequests, cpuRequests
+}
+
+func TestOllamaFallsBackToCPUWhenGPUFails(t *testing.T) {
+	server, gpuRequests, cpuRequests := gpuServer(t, http.StatusInternalServerError,
+		`{"error":"llama runner process has terminated: CUDA error: out of memory"}`)
+	client := NewOllamaAPIClient(server.URL, "nomic-embed-text", "", GPUOn)
+
+	embeddings, err := client.embedOnce([]string{"first", "second"})
+	if err != nil {
+		t.Fatalf("embedOnce() error = %v, want the texts embedded on the CPU", err)
+	}
+	if len(embeddings) != 2 {
+		t.Fatalf("got %d embeddings, want 2", len(embeddings))
+	}
+	// Only the first request tries the GPU; later ones go straight to the CPU
+	if *gpuRequests != 1 || *cpuRequests != 2 {
+		t.Errorf("GPU requests = %d, CPU requests = %d, want 1 and 2", *gpuRequests, *cpuRequests)
+	}
+	if !client.onCPU.Load() {
+		t.Error("client did not switch to the CPU")
+	}
+}
+
+func TestOllamaKeepsGPUOnOtherServerErrors(t *testing.T) {
+	tests := []struct {
+		name   string
+		status int
+		body   string
+	}{
+		{"unavailable", http.StatusServiceUnavailable, `{"error":"server busy, please try again"}`},
+		{"bad gateway", http.StatusBadGateway, `{"error":"CUDA error: out of memory"}`},
+		{"other internal error", http.StatusInternalServerError, `{"error":"context canceled"}`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server, gpuRequests, cpuRequests := gpuServer(t, tt.status, tt.body)
+			client := NewOllamaAPIClient(server.URL, "nomic-embed-text", "", GPUOn)
+
+			_, err := client.embedOnce([]string{"text"})
+			var statusErr *StatusError
+			if !errors.As(err, &statusErr) || statusErr.StatusCode != tt.status {
+				t.Fatalf("embedOnce() error = %v, want the server's status %d for embedWithRetry", err, tt.status)
+			}
+			if *gpuRequests != 1 || *cpuRequests != 0 || client.onCPU.Load() {
+				t.Errorf("GPU requests = %d, CPU requests = %d, on CPU = %v, want the GPU kept",
+					*gpuRequests, *cpuRequests, client.onCPU.Load())
+			}
+		})
+	}
+}
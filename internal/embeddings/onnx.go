@@ -0,0 +1,185 @@
+package embeddings
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+const (
+	// onnxMaxTokens is the most tokens of a text the model is fed, the
+	// input limit of BERT-style models; the rest are cut off
+	onnxMaxTokens = 512
+	// onnxBatchSize is the most texts run through the model at once
+	onnxBatchSize = 32
+
+	// DeviceCPU runs ONNX models on the CPU, the default
+	DeviceCPU = "cpu"
+	// DeviceCUDA runs them on an NVIDIA GPU, with an ONNX Runtime built with
+	// CUDA
+	DeviceCUDA = "cuda"
+	// DeviceCoreML runs them with Core ML, on the GPU and Neural Engine of
+	// Apple silicon
+	DeviceCoreML = "coreml"
+)
+
+// ErrNoONNXRuntime is returned by the onnx provider's clients in builds
+// without ONNX Runtime
+var ErrNoONNXRuntime = errors.New("this build of code-scout cannot run ONNX models; run 'go get github.com/yalue/onnxruntime_go' and build it with -tags onnx")
+
+// onnxSession runs a model on a batch of texts' token ids, all padded to the
+// same length, with a mask of 1 for each real token. It returns the model's
+// output and its shape: [batch, tokens, dimensions] for the embeddings of
+// every token, or [batch, dimensions] for a model that pools them itself.
+type onnxSession interface {
+	run(ids, mask [][]int64) (output []float32, shape []int64, err error)
+	close() error
+}
+
+// ONNXClient embeds texts with an ONNX model run in process, so indexing needs
+// no embedding server or network. It reads model.onnx, or onnx/model.onnx,
+// and tokenizer.json from the model's directory, as published with models
+// such as all-MiniLM-L6-v2 and bge-small-en-v1.5.
+type ONNXClient struct {
+	model   string
+	encoder tokenizer.Encoder
+	session onnxSession
+	pooling string // How token embeddings are pooled: PoolingMean or PoolingCLS
+	err     error  // Why the model could not be loaded, returned by every call
+}
+
+// NewONNXClient loads the ONNX model in the directory dir, with ONNX Runtime
+// from the shared library at runtime, or the platform's default library name
+// when runtime is "", to run on device: DeviceCPU, DeviceCUDA, or
+// DeviceCoreML. A model that cannot be loaded gives a client whose calls
+// return the reason.
+func NewONNXClient(dir, runtime, device, pooling string) *ONNXClient {
+	if pooling == "" {
+		pooling = PoolingMean
+	}
+	c := &ONNXClient{model: dir, pooling: pooling}
+
+	tok, err := tokenizer.Load(filepath.Join(dir, "tokenizer.json"))
+	if err != nil {
+		c.err = fmt.Errorf("failed to load the tokenizer of %s: %w", dir, err)
+		return c
+	}
+	encoder, ok := tok.(tokenizer.Encoder)
+	if !ok {
+		c.err = fmt.Errorf("the tokenizer of %s is not WordPiece, the only one ONNX models can be run with", dir)
+		return c
+	}
+	c.encoder = encoder
+
+	modelPath := filepath.Join(dir, "model.onnx")
+	if _, err := os.Stat(modelPath); err != nil {
+		modelPath = filepath.Join(dir, "onnx", "model.onnx")
+	}
+	if c.session, err = newONNXSession(modelPath, runtime, device); err != nil {
+		c.err = fmt.Errorf("failed to load %s: %w", modelPath, err)
+	}
+	return c
+}
+
+// Embed generates an embedding for the given text
+func (c *ONNXClient) Embed(text string) ([]float64, error) {
+	embeddings, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for texts, a batch at a time
+func (c *ONNXClient) EmbedMany(texts []string) ([][]float64, error) {
+	if c.err != nil {
+		return nil, c.err
+	}
+	embeddings := make([][]float64, 0, len(texts))
+	for start := 0; start < len(texts); start += onnxBatchSize {
+		batch, err := c.embedBatch(texts[start:min(start+onnxBatchSize, len(texts))])
+		if err != nil {
+			return nil, err
+		}
+		embeddings = append(embeddings, batch...)
+	}
+	return embeddings, nil
+}
+
+// Close releases the model
+func (c *ONNXClient) Close() error {
+	if c.session == nil {
+		return nil
+	}
+	return c.session.close()
+}
+
+// embedBatch runs the model once on texts, padded to the longest of them
+func (c *ONNXClient) embedBatch(texts []string) ([][]float64, error) {
+	encoded := make([][]int, len(texts))
+	longest := 0
+	for i, text := range texts {
+		ids := c.encoder.Encode(text)
+		if len(ids) > onnxMaxTokens {
+			// The text keeps the [SEP] that ends it
+			ids = append(ids[:onnxMaxTokens-1], ids[len(ids)-1])
+		}
+		encoded[i] = ids
+		longest = max(longest, len(ids))
+	}
+	ids := make([][]int64, len(texts))
+	mask := make([][]int64, len(texts))
+	for i, tokens := range encoded {
+		ids[i], mask[i] = make([]int64, longest), make([]int64, longest)
+		for j, id := range tokens {
+			ids[i][j], mask[i][j] = int64(id), 1
+		}
+	}
+
+	output, shape, err := c.session.run(ids, mask)
+	if err != nil {
+		return nil, fmt.Errorf("failed to run %s: %w", c.model, err)
+	}
+	return poolOutput(output, shape, encoded, c.pooling)
+}
+
+// poolOutput reads one embedding per text from a model's output, pooling the
+// embeddings of each text's own tokens, not its padding, when the model
+// returns them all
+func poolOutput(output []float32, shape []int64, encoded [][]int, pooling string) ([][]float64, error) {
+	size := int64(1)
+	for _, n := range shape {
+		size *= n
+	}
+	if len(shape) < 2 || len(shape) > 3 || shape[0] != int64(len(encoded)) || size != int64(len(output)) {
+		return nil, fmt.Errorf("unexpected output of shape %v for %d texts", shape, len(encoded))
+	}
+	dimensions := int(shape[len(shape)-1])
+	embeddings := make([][]float64, len(encoded))
+	for i, ids := range encoded {
+		if len(shape) == 2 {
+			embeddings[i] = normalize(toFloat64(output[i*dimensions : (i+1)*dimensions]))
+			continue
+		}
+		stride := int(shape[1]) * dimensions
+		tokens := make([][]float64, min(len(ids), int(shape[1])))
+		for j := range tokens {
+			start := i*stride + j*dimensions
+			tokens[j] = toFloat64(output[start : start+dimensions])
+		}
+		embeddings[i] = normalize(pool(tokens, pooling))
+	}
+	return embeddings, nil
+}
+
+// toFloat64 converts a model's float32 output to float64s
+func toFloat64(values []float32) []float64 {
+	converted := make([]float64, len(values))
+	for i, v := range values {
+		converted[i] = float64(v)
+	}
+	return converted
+}
@@ -0,0 +1,8 @@
+//go:build !onnx
+
+package embeddings
+
+// newONNXSession fails in builds without ONNX Runtime
+func newONNXSession(path, runtime, device string) (onnxSession, error) {
+	return nil, ErrNoONNXRuntime
+}
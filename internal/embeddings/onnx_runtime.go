@@ -0,0 +1,139 @@
+//go:build onnx
+
+package embeddings
+
+import (
+	"fmt"
+	"os"
+	"slices"
+	"sync"
+
+	ort "github.com/yalue/onnxruntime_go"
+)
+
+// ONNX Runtime is loaded once per process, from the library of the first
+// model loaded
+var (
+	ortOnce sync.Once
+	ortErr  error
+)
+
+// ortSession runs a model with ONNX Runtime
+type ortSession struct {
+	session *ort.DynamicAdvancedSession
+	inputs  []string // The model's inputs: input_ids, attention_mask, and token_type_ids if it takes them
+}
+
+// newONNXSession loads the model at path with the ONNX Runtime library at
+// runtime, to run on device
+func newONNXSession(path, runtime, device string) (onnxSession, error) {
+	ortOnce.Do(func() {
+		if runtime != "" {
+			ort.SetSharedLibraryPath(runtime)
+		}
+		ortErr = ort.InitializeEnvironment()
+	})
+	if ortErr != nil {
+		return nil, fmt.Errorf("failed to load ONNX Runtime: %w", ortErr)
+	}
+
+	inputInfo, outputInfo, err := ort.GetInputOutputInfo(path)
+	if err != nil {
+		return nil, err
+	}
+	var inputs []string
+	for _, name := range []string{"input_ids", "attention_mask", "token_type_ids"} {
+		if slices.ContainsFunc(inputInfo, func(info ort.InputOutputInfo) bool { return info.Name == name }) {
+			inputs = append(inputs, name)
+		}
+	}
+	if len(inputs) < 2 || inputs[0] != "input_ids" || inputs[1] != "attention_mask" {
+		return nil, fmt.Errorf("the model does not take input_ids and attention_mask")
+	}
+	if len(outputInfo) == 0 {
+		return nil, fmt.Errorf("the model has no outputs")
+	}
+	// The embeddings of every token, which are pooled here, or the model's
+	// own pooled embedding
+	output := outputInfo[0].Name
+	for _, info := range outputInfo {
+		if info.Name == "last_hidden_state" {
+			output = info.Name
+		}
+	}
+
+	options, err := sessionOptions(path, device)
+	if err != nil {
+		return nil, err
+	}
+	defer options.Destroy()
+	session, err := ort.NewDynamicAdvancedSession(path, inputs, []string{output}, options)
+	if err != nil {
+		return nil, err
+	}
+	return &ortSession{session: session, inputs: inputs}, nil
+}
+
+// sessionOptions makes the options of a session that runs the model at path
+// on device, which is the CPU unless it is DeviceCUDA or DeviceCoreML. A
+// runtime built without the device's execution provider, or a machine without
+// the device, runs the model on the CPU, more slowly.
+func sessionOptions(path, device string) (*ort.SessionOptions, error) {
+	options, err := ort.NewSessionOptions()
+	if err != nil {
+		return nil, err
+	}
+	switch device {
+	case DeviceCUDA:
+		var cuda *ort.CUDAProviderOptions
+		if cuda, err = ort.NewCUDAProviderOptions(); err == nil {
+			err = options.AppendExecutionProviderCUDA(cuda)
+			cuda.Destroy()
+		}
+	case DeviceCoreML:
+		err = options.AppendExecutionProviderCoreML(0)
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Warning: ONNX Runtime could not run %s on %s (%v); embedding on the CPU\n", path, device, err)
+		options.Destroy()
+		return ort.NewSessionOptions()
+	}
+	return options, nil
+}
+
+func (s *ortSession) run(ids, mask [][]int64) ([]float32, []int64, error) {
+	shape := ort.NewShape(int64(len(ids)), int64(len(ids[0])))
+	flat := func(rows [][]int64) []int64 {
+		values := make([]int64, 0, len(rows)*len(rows[0]))
+		for _, row := range rows {
+			values = append(values, row...)
+		}
+		return values
+	}
+	data := [][]int64{flat(ids), flat(mask), make([]int64, len(ids)*len(ids[0]))}
+
+	inputs := make([]ort.Value, len(s.inputs))
+	for i := range s.inputs {
+		tensor, err := ort.NewTensor(shape, data[i])
+		if err != nil {
+			return nil, nil, err
+		}
+		defer tensor.Destroy()
+		inputs[i] = tensor
+	}
+	// A nil output is allocated by the session to the size the model returns
+	outputs := []ort.Value{nil}
+	if err := s.session.Run(inputs, outputs); err != nil {
+		return nil, nil, err
+	}
+	defer outputs[0].Destroy()
+	tensor, ok := outputs[0].(*ort.Tensor[float32])
+	if !ok {
+		return nil, nil, fmt.Errorf("the model's output is not float32")
+	}
+	return slices.Clone(tensor.GetData()), slices.Clone([]int64(tensor.GetShape())), nil
+}
+
+func (s *ortSession) close() error {
+	return s.session.Destroy()
+}
@@ -0,0 +1,114 @@
+package embeddings
+
+import (
+	"math"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+// testTokenizer is a WordPiece tokenizer.json with a few words
+const testTokenizer = `{
+  "normalizer": {"type": "BertNormalizer", "lowercase": true},
+  "model": {
+    "type": "WordPiece",
+    "vocab": {"[PAD]": 0, "[UNK]": 1, "[CLS]": 2, "[SEP]": 3, "hello": 4, "world": 5}
+  }
+}`
+
+// tokenSession returns [1, 0] for each of a text's tokens and [0, 100] for
+// its padding, so an embedding that pools the padding leans off the x axis
+type tokenSession struct {
+	lengths []int // Tokens of each text of the last batch, counted by the mask
+}
+
+func (s *tokenSession) run(ids, mask [][]int64) ([]float32, []int64, error) {
+	s.lengths = nil
+	var output []float32
+	for _, row := range mask {
+		length := 0
+		for _, m := range row {
+			if m == 1 {
+				length++
+				output = append(output, 1, 0)
+			} else {
+				output = append(output, 0, 100)
+			}
+		}
+		s.lengths = append(s.lengths, length)
+	}
+	return output, []int64{int64(len(ids)), int64(len(ids[0])), 2}, nil
+}
+
+func (s *tokenSession) close() error { return nil }
+
+// pooledSession returns the embedding [3, 4] for every text, as a model that
+// pools its token embeddings itself
+type pooledSession struct{}
+
+func (pooledSession) run(ids, mask [][]int64) ([]float32, []int64, error) {
+	var output []float32
+	for range ids {
+		output = append(output, 3, 4)
+	}
+	return output, []int64{int64(len(ids)), 2}, nil
+}
+
+func (pooledSession) close() error { return nil }
+
+func testEncoder(t *testing.T) tokenizer.Encoder {
+	t.Helper()
+	path := filepath.Join(t.TempDir(), "tokenizer.json")
+	if err := os.WriteFile(path, []byte(testTokenizer), 0644); err != nil {
+		t.Fatal(err)
+	}
+	tok, err := tokenizer.Load(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return tok.(tokenizer.Encoder)
+}
+
+func TestONNXClientPoolsOnlyRealTokens(t *testing.T) {
+	session := &tokenSession{}
+	client := &ONNXClient{model: "test", encoder: testEncoder(t), session: session, pooling: PoolingMean}
+
+	embeddings, err := client.EmbedMany([]string{"hello", "hello world " + strings.Repeat("world ", 600)})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(embeddings) != 2 {
+		t.Fatalf("got %d embeddings, want 2", len(embeddings))
+	}
+	for i, embedding := range embeddings {
+		if math.Abs(embedding[0]-1) > 1e-9 || math.Abs(embedding[1]) > 1e-9 {
+			t.Errorf("embedding %d = %v, want [1 0] from its own tokens only", i, embedding)
+		}
+	}
+	// [CLS] hello [SEP], and the long text cut off at the model's limit
+	if session.lengths[0] != 3 || session.lengths[1] != onnxMaxTokens {
+		t.Errorf("token counts = %v, want 3 and %d", session.lengths, onnxMaxTokens)
+	}
+}
+
+func TestONNXClientNormalizesPooledOutput(t *testing.T) {
+	client := &ONNXClient{model: "test", encoder: testEncoder(t), session: pooledSession{}, pooling: PoolingMean}
+
+	embedding, err := client.Embed("hello")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if math.Abs(embedding[0]-0.6) > 1e-9 || math.Abs(embedding[1]-0.8) > 1e-9 {
+		t.Errorf("embedding = %v, want [0.6 0.8]", embedding)
+	}
+}
+
+func TestONNXClientWithoutModel(t *testing.T) {
+	client := NewONNXClient(t.TempDir(), "", "", "")
+	if _, err := client.Embed("hello"); err == nil || !strings.Contains(err.Error(), "tokenizer") {
+		t.Errorf("err = %v, want the tokenizer that could not be loaded", err)
+	}
+}
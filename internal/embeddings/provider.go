@@ -0,0 +1,74 @@
+package embeddings
+
+import public "github.com/jlanders/code-scout/pkg/embeddings"
+
+// ProviderSettings and ProviderFactory are those of the public package, where
+// providers of other modules register
+type (
+	ProviderSettings = public.ProviderSettings
+	ProviderFactory  = public.ProviderFactory
+)
+
+// builtinProviders are the factories of the built-in providers by name
+var builtinProviders = map[string]ProviderFactory{
+	ProviderOpenAI: func(s ProviderSettings) Client {
+		return NewClientWithDimensions(s.Endpoint, s.APIKey, s.Model, s.Dimensions)
+	},
+	ProviderOllama: func(s ProviderSettings) Client {
+		return NewOllamaAPIClient(s.Endpoint, s.Model, s.KeepAlive, s.GPU)
+	},
+	ProviderHuggingFace: func(s ProviderSettings) Client {
+		return NewHuggingFaceClient(s.Endpoint, s.APIKey, s.Model, s.Pooling)
+	},
+	ProviderTEI: func(s ProviderSettings) Client {
+		return NewTEIClient(s.Endpoint, s.APIKey, s.Model, s.Pooling)
+	},
+	ProviderAzure: func(s ProviderSettings) Client {
+		// The models are the names of deployments
+		return NewAzureClient(s.Endpoint, s.Model, s.APIVersion, s.APIKey, s.ADToken, s.Dimensions)
+	},
+	ProviderGemini: func(s ProviderSettings) Client {
+		return NewGeminiClient(s.Endpoint, s.APIKey, s.Model, s.Dimensions)
+	},
+	ProviderCohere: func(s ProviderSettings) Client {
+		return NewCohereClient(s.Endpoint, s.APIKey, s.Model, s.Dimensions)
+	},
+	ProviderVoyage: func(s ProviderSettings) Client {
+		return NewVoyageClient(s.Endpoint, s.APIKey, s.Model, s.Dimensions)
+	},
+	ProviderJina: func(s ProviderSettings) Client {
+		return NewJinaClient(s.Endpoint, s.APIKey, s.Model, s.Dimensions)
+	},
+	ProviderONNX: func(s ProviderSettings) Client {
+		// The models are the directories the model files are in
+		return NewONNXClient(s.Model, s.ONNXRuntime, s.ONNXDevice, s.Pooling)
+	},
+}
+
+// lookupProvider returns the factory of the named provider. A provider
+// registered with the public package's RegisterProvider takes the place of
+// the built-in one of the same name.
+func lookupProvider(name string) (ProviderFactory, bool) {
+	if factory, ok := public.LookupProvider(name); ok {
+		return factory, true
+	}
+	factory, ok := builtinProviders[name]
+	return factory, ok
+}
+
+// HasProvider reports whether a provider of the given name is built in or
+// registered
+func HasProvider(name string) bool {
+	_, ok := lookupProvider(name)
+	return ok
+}
+
+// NewProviderClient creates a client of the named provider, or of the
+// built-in OpenAI-compatible provider when name is empty or not registered
+func NewProviderClient(name string, settings ProviderSettings) Client {
+	factory, ok := lookupProvider(name)
+	if !ok {
+		factory = builtinProviders[ProviderOpenAI]
+	}
+	return factory(settings)
+}
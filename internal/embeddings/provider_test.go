@@ -0,0 +1,52 @@
+package embeddings
+
+import (
+	"fmt"
+	"testing"
+
+	public "github.com/jlanders/code-scout/pkg/embeddings"
+)
+
+// staticClient returns the same vector for every text
+type staticClient struct{}
+
+func (staticClient) Embed(text string) ([]float64, error) {
+	return []float64{1}, nil
+}
+
+func (staticClient) EmbedMany(texts []string) ([][]float64, error) {
+	return make([][]float64, len(texts)), nil
+}
+
+func TestNewProviderClient(t *testing.T) {
+	public.RegisterProvider("test-registered", func(ProviderSettings) Client { return staticClient{} })
+
+	tests := []struct {
+		provider string
+		want     string
+	}{
+		{"test-registered", "embeddings.staticClient"},
+		{ProviderOllama, "*embeddings.OllamaClient"},
+		// An empty or unknown name falls back to the built-in OpenAI provider
+		{"", "*embeddings.OpenAIClient"},
+		{"not-registered", "*embeddings.OpenAIClient"},
+	}
+	for _, tt := range tests {
+		client := NewProviderClient(tt.provider, ProviderSettings{Endpoint: DefaultEndpoint, Model: DefaultCodeModel})
+		if got := fmt.Sprintf("%T", client); got != tt.want {
+			t.Errorf("provider %q created a %s, want a %s", tt.provider, got, tt.want)
+		}
+	}
+	if !HasProvider("test-registered") || !HasProvider(ProviderJina) || HasProvider("not-registered") {
+		t.Error("HasProvider does not list the built-in and registered providers alone")
+	}
+}
+
+func TestRegisteredProviderReplacesBuiltIn(t *testing.T) {
+	public.RegisterProvider(ProviderTEI, func(ProviderSettings) Client { return staticClient{} })
+	defer public.RegisterProvider(ProviderTEI, builtinProviders[ProviderTEI])
+
+	if client := NewProviderClient(ProviderTEI, ProviderSettings{}); client != (staticClient{}) {
+		t.Errorf("provider %q created a %T, want the registered one's client", ProviderTEI, client)
+	}
+}
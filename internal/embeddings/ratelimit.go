@@ -0,0 +1,141 @@
+package embeddings
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+	"sync"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+// StatusError is the error of an embedding request the API answered with a
+// status other than 200 OK
+type StatusError struct {
+	StatusCode int
+	Body       string
+	RetryAfter time.Duration // How long the API asked to wait before retrying; 0 if it did not say
+}
+
+func (e *StatusError) Error() string {
+	return fmt.Sprintf("embedding API returned status %d: %s", e.StatusCode, e.Body)
+}
+
+// statusError returns the error of a response that is not 200 OK
+func statusError(resp *http.Response, body []byte) error {
+	err := &StatusError{StatusCode: resp.StatusCode, Body: string(body)}
+	if seconds, parseErr := strconv.Atoi(resp.Header.Get("Retry-After")); parseErr == nil && seconds > 0 {
+		err.RetryAfter = time.Duration(seconds) * time.Second
+	}
+	return err
+}
+
+// RateLimits caps the requests made to an embedding API. A zero field leaves
+// that limit off.
+type RateLimits struct {
+	MaxInFlight       int // Requests waiting on a response at once
+	RequestsPerMinute int
+	TokensPerMinute   int // Estimated tokens of the texts sent
+}
+
+// Limiter holds the requests of the clients that share it to their
+// RateLimits, so the code and text models of one account are limited together
+type Limiter struct {
+	inFlight chan struct{}
+	mu       sync.Mutex
+	requests *rateBucket
+	tokens   *rateBucket
+}
+
+// rateBucket is a token bucket refilled at a steady rate up to a minute's worth
+type rateBucket struct {
+	perMinute float64
+	available float64
+	updated   time.Time
+}
+
+// NewLimiter creates a limiter enforcing limits
+func NewLimiter(limits RateLimits) *Limiter {
+	l := &Limiter{}
+	if limits.MaxInFlight > 0 {
+		l.inFlight = make(chan struct{}, limits.MaxInFlight)
+	}
+	now := time.Now()
+	if limits.RequestsPerMinute > 0 {
+		l.requests = &rateBucket{perMinute: float64(limits.RequestsPerMinute), available: float64(limits.RequestsPerMinute), updated: now}
+	}
+	if limits.TokensPerMinute > 0 {
+		l.tokens = &rateBucket{perMinute: float64(limits.TokensPerMinute), available: float64(limits.TokensPerMinute), updated: now}
+	}
+	return l
+}
+
+// reserve takes n from the bucket, which may leave it owing, and returns how
+// long to wait until the bucket has paid the debt back. A request larger than
+// a minute's worth takes the whole minute rather than waiting forever.
+func (b *rateBucket) reserve(n float64, now time.Time) time.Duration {
+	b.available = min(b.perMinute, b.available+now.Sub(b.updated).Minutes()*b.perMinute)
+	b.updated = now
+	b.available -= min(n, b.perMinute)
+	if b.available >= 0 {
+		return 0
+	}
+	return time.Duration(-b.available / b.perMinute * float64(time.Minute))
+}
+
+// acquire waits until a request of the given tokens fits the limits and
+// returns the function that releases its in-flight slot
+func (l *Limiter) acquire(tokens int) func() {
+	l.mu.Lock()
+	var wait time.Duration
+	now := time.Now()
+	if l.requests != nil {
+		wait = max(wait, l.requests.reserve(1, now))
+	}
+	if l.tokens != nil {
+		wait = max(wait, l.tokens.reserve(float64(tokens), now))
+	}
+	l.mu.Unlock()
+	if wait > 0 {
+		time.Sleep(wait)
+	}
+
+	if l.inFlight == nil {
+		return func() {}
+	}
+	l.inFlight <- struct{}{}
+	return func() { <-l.inFlight }
+}
+
+// RateLimitedClient makes its client's requests within the limits of a
+// Limiter. Each call counts as one request, though a client may split a long
+// list of texts into several.
+type RateLimitedClient struct {
+	client  Client
+	limiter *Limiter
+}
+
+// NewRateLimitedClient creates a client that waits on limiter before each of
+// client's requests
+func NewRateLimitedClient(client Client, limiter *Limiter) *RateLimitedClient {
+	return &RateLimitedClient{client: client, limiter: limiter}
+}
+
+// Embed generates an embedding for the given text within the limits
+func (c *RateLimitedClient) Embed(text string) ([]float64, error) {
+	release := c.limiter.acquire(tokenizer.Approximate{}.Count(text))
+	defer release()
+	return c.client.Embed(text)
+}
+
+// EmbedMany generates embeddings for multiple texts within the limits
+func (c *RateLimitedClient) EmbedMany(texts []string) ([][]float64, error) {
+	tokens := 0
+	for _, text := range texts {
+		tokens += tokenizer.Approximate{}.Count(text)
+	}
+	release := c.limiter.acquire(tokens)
+	defer release()
+	return c.client.EmbedMany(texts)
+}
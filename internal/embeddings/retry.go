@@ -0,0 +1,92 @@
+package embeddings
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"time"
+)
+
+// maxRateLimitedAttempts is how many times a request the API turns away with
+// 429 Too Many Requests is attempted, when 429 is retried at all; rate limits
+// pass, so such requests are retried for longer than other failures
+const maxRateLimitedAttempts = 10
+
+// RetryPolicy says which failed requests are retried and how long to wait
+// between attempts. Requests that fail without a response, as on a timeout or
+// a refused connection, are always retried.
+type RetryPolicy struct {
+	Attempts int           // Attempts at a request before it fails
+	Constant bool          // Wait Delay before every retry instead of doubling it each time
+	Delay    time.Duration // Wait before the first retry
+	MaxDelay time.Duration // Longest wait between attempts
+	Statuses []int         // HTTP statuses that are retried
+}
+
+// DefaultRetryPolicy retries timeouts, rate limiting, and server errors three
+// times with exponential backoff from a second, up to a minute
+var DefaultRetryPolicy = RetryPolicy{
+	Attempts: 3,
+	Delay:    1 * time.Second,
+	MaxDelay: 1 * time.Minute,
+	Statuses: []int{408, 429, 500, 502, 503, 504},
+}
+
+// retryPolicy is the policy every client retries with
+var retryPolicy = DefaultRetryPolicy
+
+// SetRetryPolicy sets the policy every client retries failed requests with.
+// It is meant to be called once, before any client is used.
+func SetRetryPolicy(policy RetryPolicy) {
+	retryPolicy = policy
+}
+
+// embedWithRetry makes an embedding request with embedOnce, retrying it under
+// the retry policy. A request the API turns away with 429 Too Many Requests is
+// retried for longer, waiting as long as the API asks, so a long run outlasts
+// a burst of rate limiting instead of failing part way.
+func embedWithRetry(embedOnce func([]string) ([][]float64, error), texts []string, expected int) ([][]float64, error) {
+	policy := retryPolicy
+	var lastErr error
+	attempts, rateLimited := 0, 0
+	for attempts < max(policy.Attempts, 1) && rateLimited < maxRateLimitedAttempts {
+		if tries := attempts + rateLimited; tries > 0 {
+			backoff := policy.Delay
+			if !policy.Constant {
+				backoff = policy.Delay * time.Duration(1<<uint(min(tries-1, 30)))
+			}
+			if policy.MaxDelay > 0 {
+				backoff = min(backoff, policy.MaxDelay)
+			}
+			var statusErr *StatusError
+			if errors.As(lastErr, &statusErr) && statusErr.RetryAfter > backoff {
+				backoff = statusErr.RetryAfter
+			}
+			time.Sleep(backoff)
+		}
+
+		embeddings, err := embedOnce(texts)
+		if err == nil {
+			if len(embeddings) != expected {
+				return nil, fmt.Errorf("expected %d embeddings, got %d", expected, len(embeddings))
+			}
+			return embeddings, nil
+		}
+
+		lastErr = err
+		var statusErr *StatusError
+		if errors.As(err, &statusErr) {
+			if !slices.Contains(policy.Statuses, statusErr.StatusCode) {
+				// The API will answer the same way however often it is asked
+				return nil, err
+			}
+			if statusErr.StatusCode == 429 {
+				rateLimited++
+				continue
+			}
+		}
+		attempts++
+	}
+
+	return nil, fmt.Errorf("failed after %d attempts: %w", attempts+rateLimited, lastErr)
+}
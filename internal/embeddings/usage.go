@@ -0,0 +1,57 @@
+package embeddings
+
+import (
+	"sync/atomic"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+// Usage counts the requests an embedding client made of its provider and
+// the texts and tokens they sent. Tokens are estimated as the rate limits
+// estimate them, about four bytes each; providers bill their own count.
+type Usage struct {
+	Requests atomic.Int64
+	Texts    atomic.Int64
+	Tokens   atomic.Int64
+}
+
+// MeteredClient counts the usage of a client's requests. Only requests that
+// succeed are counted, since providers do not bill the ones that fail.
+type MeteredClient struct {
+	client Client
+	usage  *Usage
+}
+
+// NewMeteredClient creates a client that counts client's usage in usage,
+// which clients of the same provider and model may share
+func NewMeteredClient(client Client, usage *Usage) *MeteredClient {
+	return &MeteredClient{client: client, usage: usage}
+}
+
+// Embed generates an embedding for the given text
+func (c *MeteredClient) Embed(text string) ([]float64, error) {
+	embedding, err := c.client.Embed(text)
+	if err == nil {
+		c.count([]string{text})
+	}
+	return embedding, err
+}
+
+// EmbedMany generates embeddings for multiple texts
+func (c *MeteredClient) EmbedMany(texts []string) ([][]float64, error) {
+	embeddings, err := c.client.EmbedMany(texts)
+	if err == nil {
+		c.count(texts)
+	}
+	return embeddings, err
+}
+
+func (c *MeteredClient) count(texts []string) {
+	tokens := 0
+	for _, text := range texts {
+		tokens += tokenizer.Approximate{}.Count(text)
+	}
+	c.usage.Requests.Add(1)
+	c.usage.Texts.Add(int64(len(texts)))
+	c.usage.Tokens.Add(int64(tokens))
+}
@@ -0,0 +1,142 @@
+package embeddings
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"sort"
+
+	"github.com/jlanders/code-scout/internal/tokenizer"
+)
+
+const (
+	// voyageBatchSize is the most texts Voyage AI embeds in one request
+	voyageBatchSize = 1000
+	// voyageTokenBudget caps the estimated tokens of one request, below the
+	// 120,000 Voyage allows for voyage-code models so that estimates that run
+	// low still fit
+	voyageTokenBudget = 80000
+	// voyageDocumentInput and voyageQueryInput are the input types Voyage AI
+	// embeds indexed chunks and search queries with
+	voyageDocumentInput = "document"
+	voyageQueryInput    = "query"
+)
+
+// VoyageClient handles communication with Voyage AI's embedding API, whose
+// voyage-code models are tuned for code retrieval
+type VoyageClient struct {
+	endpoint   string
+	apiKey     string
+	model      string
+	dimensions int // Size of the vectors requested, for models that can shorten them; 0 for the model's own
+	client     *http.Client
+}
+
+// voyageEmbedRequest represents a /v1/embeddings request
+type voyageEmbedRequest struct {
+	Model           string   `json:"model"`
+	Input           []string `json:"input"`
+	InputType       string   `json:"input_type"`
+	Truncation      bool     `json:"truncation"`
+	OutputDimension int      `json:"output_dimension,omitempty"`
+}
+
+// NewVoyageClient creates a client for a Voyage AI model such as
+// voyage-code-3, served at <endpoint>/v1/embeddings
+func NewVoyageClient(endpoint, apiKey, model string, dimensions int) *VoyageClient {
+	return &VoyageClient{
+		endpoint:   endpoint,
+		apiKey:     apiKey,
+		model:      model,
+		dimensions: dimensions,
+		client:     &http.Client{},
+	}
+}
+
+// Embed generates an embedding for a search query with retry logic
+func (c *VoyageClient) Embed(text string) ([]float64, error) {
+	embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+		return c.embedOnce(texts, voyageQueryInput)
+	}, []string{text}, 1)
+	if err != nil {
+		return nil, err
+	}
+	return embeddings[0], nil
+}
+
+// EmbedMany generates embeddings for chunks being indexed. Texts beyond the
+// inputs or tokens one request may carry are sent in further requests.
+func (c *VoyageClient) EmbedMany(texts []string) ([][]float64, error) {
+	var all [][]float64
+	for start := 0; start < len(texts); {
+		end, tokens := start, 0
+		for end < len(texts) && end-start < voyageBatchSize {
+			n := tokenizer.Approximate{}.Count(texts[end])
+			if end > start && tokens+n > voyageTokenBudget {
+				break
+			}
+			tokens += n
+			end++
+		}
+		embeddings, err := embedWithRetry(func(texts []string) ([][]float64, error) {
+			return c.embedOnce(texts, voyageDocumentInput)
+		}, texts[start:end], end-start)
+		if err != nil {
+			return nil, err
+		}
+		all = append(all, embeddings...)
+		start = end
+	}
+	return all, nil
+}
+
+// embedOnce makes a single /v1/embeddings request without retries
+func (c *VoyageClient) embedOnce(texts []string, inputType string) ([][]float64, error) {
+	// Texts over the model's input limit are cut rather than rejected
+	jsonData, err := json.Marshal(voyageEmbedRequest{
+		Model:           c.model,
+		Input:           texts,
+		InputType:       inputType,
+		Truncation:      true,
+		OutputDimension: c.dimensions,
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	req, err := http.NewRequest("POST", c.endpoint+"/v1/embeddings", bytes.NewBuffer(jsonData))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	if c.apiKey != "" {
+		req.Header.Set("Authorization", "Bearer "+c.apiKey)
+	}
+
+	resp, err := c.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to make request to embedding API: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, statusError(resp, body)
+	}
+
+	// The response has the OpenAI format
+	var embedResp openAIEmbedResponse
+	if err := json.NewDecoder(resp.Body).Decode(&embedResp); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+	sort.SliceStable(embedResp.Data, func(i, j int) bool {
+		return embedResp.Data[i].Index < embedResp.Data[j].Index
+	})
+	embeddings := make([][]float64, len(embedResp.Data))
+	for i, data := range embedResp.Data {
+		embeddings[i] = data.Embedding
+	}
+	return embeddings, nil
+}
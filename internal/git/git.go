@@ -0,0 +1,102 @@
+package git
+
+import (
+	"bytes"
+	"fmt"
+	"os/exec"
+	"path/filepath"
+	"strings"
+)
+
+// Change is a file changed between two commits
+type Change struct {
+	Path    string // Absolute path of the file
+	Deleted bool   // The file no longer exists at the later commit
+}
+
+// run runs a git command in dir and returns its output
+func run(dir string, args ...string) (string, error) {
+	cmd := exec.Command("git", args...)
+	cmd.Dir = dir
+	var stderr bytes.Buffer
+	cmd.Stderr = &stderr
+	output, err := cmd.Output()
+	if err != nil {
+		if message := strings.TrimSpace(stderr.String()); message != "" {
+			return "", fmt.Errorf("git %s: %s", args[0], message)
+		}
+		return "", fmt.Errorf("git %s: %w", args[0], err)
+	}
+	return string(output), nil
+}
+
+// Head returns the commit checked out in the repository containing dir
+func Head(dir string) (string, error) {
+	output, err := run(dir, "rev-parse", "HEAD")
+	if err != nil {
+		return "", err
+	}
+	return strings.TrimSpace(output), nil
+}
+
+// CurrentBranch returns the branch checked out in the repository containing
+// dir, or "" when HEAD is detached
+func CurrentBranch(dir string) (string, error) {
+	if _, err := run(dir, "rev-parse", "--git-dir"); err != nil {
+		return "", err
+	}
+	output, err := run(dir, "symbolic-ref", "--quiet", "--short", "HEAD")
+	if err != nil {
+		// symbolic-ref fails without output when HEAD is not a branch
+		return "", nil
+	}
+	return strings.TrimSpace(output), nil
+}
+
+// ChangedFiles returns the files under dir that differ between rev and HEAD.
+// A renamed file is reported as the deletion of its old path and a change to
+// its new one.
+func ChangedFiles(dir, rev string) ([]Change, error) {
+	// --relative limits the diff to dir and prints paths relative to it
+	output, err := run(dir, "diff", "--name-status", "--no-renames", "--relative", "-z", rev, "HEAD")
+	if err != nil {
+		return nil, err
+	}
+
+	// -z output alternates status and path fields, each ending in a NUL
+	fields := strings.Split(strings.TrimSuffix(output, "\x00"), "\x00")
+	var changes []Change
+	for i := 0; i+1 < len(fields); i += 2 {
+		changes = append(changes, Change{
+			Path:    filepath.Join(dir, filepath.FromSlash(fields[i+1])),
+			Deleted: strings.HasPrefix(fields[i], "D"),
+		})
+	}
+	return changes, nil
+}
+
+// DriftedFiles returns the tracked files under dir whose working tree copies
+// differ from rev, whether the changes are committed or not
+func DriftedFiles(dir, rev string) ([]string, error) {
+	output, err := run(dir, "diff", "--name-only", "--relative", "-z", rev)
+	if err != nil {
+		return nil, err
+	}
+	var paths []string
+	for _, name := range strings.Split(strings.TrimSuffix(output, "\x00"), "\x00") {
+		if name != "" {
+			paths = append(paths, filepath.Join(dir, filepath.FromSlash(name)))
+		}
+	}
+	return paths, nil
+}
+
+// ExcludesFile returns the global ignore file set as core.excludesFile for the
+// repository containing dir, or "" when none is set or git is unavailable
+func ExcludesFile(dir string) string {
+	output, err := run(dir, "config", "--path", "core.excludesFile")
+	if err != nil {
+		return ""
+	}
+	return strings.TrimSpace(output)
+}
@@ -0,0 +1,154 @@
+package git
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"testing"
+)
+
+// initRepo creates a repository with a commit of the given files
+func initRepo(t *testing.T, files map[string]string) string {
+	t.Helper()
+	if _, err := exec.LookPath("git"); err != nil {
+		t.Skip("git is not installed")
+	}
+
+	dir := t.TempDir()
+	if resolved, err := filepath.EvalSymlinks(dir); err == nil {
+		dir = resolved
+	}
+	gitCmd(t, dir, "init", "-q")
+	gitCmd(t, dir, "config", "user.email", "test@example.com")
+	gitCmd(t, dir, "config", "user.name", "Test")
+	gitCmd(t, dir, "config", "commit.gpgsign", "false")
+	commitFiles(t, dir, files)
+	return dir
+}
+
+func gitCmd(t *testing.T, dir string, args ...string) {
+	t.Helper()
+	if _, err := run(dir, args...); err != nil {
+		t.Fatalf("git %v failed: %v", args, err)
+	}
+}
+
+func commitFiles(t *testing.T, dir string, files map[string]string) {
+	t.Helper()
+	for name, content := range files {
+		path := filepath.Join(dir, name)
+		if content == "" {
+			if err := os.Remove(path); err != nil {
+				t.Fatalf("Failed to remove %s: %v", name, err)
+			}
+			continue
+		}
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatalf("Failed to create directory: %v", err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatalf("Failed to write %s: %v", name, err)
+		}
+	}
+	gitCmd(t, dir, "add", "-A")
+	gitCmd(t, dir, "commit", "-q", "-m", "update")
+}
+
+func TestChangedFiles(t *testing.T) {
+	dir := initRepo(t, map[string]string{
+		"main.go":      "package main\n",
+		"old.go":       "package main\n",
+		"docs/a.md":    "# A\n",
+		"unchanged.go": "package main\n",
+	})
+	base, err := Head(dir)
+	if err != nil {
+		t.Fatalf("Head failed: %v", err)
+	}
+
+	commitFiles(t, dir, map[string]string{
+		"main.go":   "package main\n\nfunc main() {}\n",
+		"old.go":    "",
+		"docs/b.md": "# B\n",
+	})
+
+	changes, err := ChangedFiles(dir, base)
+	if err != nil {
+		t.Fatalf("ChangedFiles failed: %v", err)
+	}
+
+	got := make(map[string]bool)
+	for _, change := range changes {
+		got[change.Path] = change.Deleted
+	}
+	expected := map[string]bool{
+		filepath.Join(dir, "main.go"):   false,
+		filepath.Join(dir, "old.go"):    true,
+		filepath.Join(dir, "docs/b.md"): false,
+	}
+	if len(got) != len(expected) {
+		t.Fatalf("Expected %d changes, got %v", len(expected), changes)
+	}
+	for path, deleted := range expected {
+		if d, ok := got[path]; !ok || d != deleted {
+			t.Errorf("Expected %s (deleted=%v), got %v", path, deleted, changes)
+		}
+	}
+}
+
+func TestChangedFilesBadRevision(t *testing.T) {
+	dir := initRepo(t, map[string]string{"main.go": "package main\n"})
+	if _, err := ChangedFiles(dir, "does-not-exist"); err == nil {
+		t.Error("Expected an error for an unknown revision")
+	}
+}
+
+func TestCurrentBranch(t *testing.T) {
+	dir := initRepo(t, map[string]string{"main.go": "package main\n"})
+	gitCmd(t, dir, "checkout", "-q", "-b", "feature/search")
+
+	branch, err := CurrentBranch(dir)
+	if err != nil {
+		t.Fatalf("CurrentBranch failed: %v", err)
+	}
+	if branch != "feature/search" {
+		t.Errorf("Expected feature/search, got %q", branch)
+	}
+
+	gitCmd(t, dir, "checkout", "-q", "--detach")
+	branch, err = CurrentBranch(dir)
+	if err != nil {
+		t.Fatalf("CurrentBranch failed: %v", err)
+	}
+	if branch != "" {
+		t.Errorf("Expected no branch for a detached HEAD, got %q", branch)
+	}
+
+	if _, err := CurrentBranch(t.TempDir()); err == nil {
+		t.Error("Expected an error outside a repository")
+	}
+}
+
+func TestDriftedFiles(t *testing.T) {
+	dir := initRepo(t, map[string]string{
+		"main.go": "package main\n",
+		"util.go": "package main\n",
+	})
+	base, err := Head(dir)
+	if err != nil {
+		t.Fatalf("Head failed: %v", err)
+	}
+
+	commitFiles(t, dir, map[string]string{"util.go": "package main\n\nfunc util() {}\n"})
+	if err := os.WriteFile(filepath.Join(dir, "main.go"), []byte("package main\n\nfunc main() {}\n"), 0644); err != nil {
+		t.Fatalf("Failed to write main.go: %v", err)
+	}
+
+	drifted, err := DriftedFiles(dir, base)
+	if err != nil {
+		t.Fatalf("DriftedFiles failed: %v", err)
+	}
+	if len(drifted) != 2 {
+		t.Errorf("Expected 2 drifted files (one committed, one not), got %v", drifted)
+	}
+}
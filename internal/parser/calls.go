@@ -0,0 +1,98 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// callKinds maps the call node kinds of the supported grammars to the field
+// that holds what is called
+var callKinds = map[string]string{
+	"call_expression":          "function", // Go, JavaScript, TypeScript, Rust, C, C++, Scala
+	"call":                     "function", // Python; Ruby uses "method"
+	"method_invocation":        "name",     // Java
+	"function_call_expression": "function", // PHP
+	"member_call_expression":   "name",     // PHP
+	"scoped_call_expression":   "name",     // PHP
+}
+
+// calleeFields are the fields that lead from a callee expression such as
+// user.save or Point::new to the name of what is called
+var calleeFields = []string{"field", "property", "attribute", "name", "method", "function"}
+
+// attachCalls records the names of the functions and methods each function or
+// method chunk calls as its calls metadata, in order of first call. Names are
+// found syntactically, so a call through a variable or closure is recorded by
+// its variable name and calls through computed expressions are left out.
+func (e *Extractor) attachCalls(rootNode *sitter.Node, chunks []*Chunk) {
+	for _, chunk := range chunks {
+		if chunk.Type != ChunkTypeFunction && chunk.Type != ChunkTypeMethod {
+			continue
+		}
+		node := rootNode.DescendantForByteRange(uint(chunk.StartByte), uint(chunk.EndByte))
+		if node == nil {
+			continue
+		}
+
+		var calls []string
+		seen := make(map[string]bool)
+		e.collectCalls(node, chunk, func(name string) {
+			if !seen[name] {
+				seen[name] = true
+				calls = append(calls, name)
+			}
+		})
+		if len(calls) == 0 {
+			continue
+		}
+		if chunk.Metadata == nil {
+			chunk.Metadata = make(map[string]string)
+		}
+		chunk.Metadata["calls"] = strings.Join(calls, ", ")
+	}
+}
+
+// collectCalls reports the callee of every call inside a chunk's byte range
+func (e *Extractor) collectCalls(node *sitter.Node, chunk *Chunk, report func(string)) {
+	if int(node.EndByte()) <= chunk.StartByte || int(node.StartByte()) >= chunk.EndByte {
+		return
+	}
+	if field, ok := callKinds[node.Kind()]; ok {
+		callee := node.ChildByFieldName(field)
+		if callee == nil {
+			callee = node.ChildByFieldName("method")
+		}
+		if name := e.calleeName(callee); name != "" {
+			report(name)
+		}
+	}
+
+	childCount := node.NamedChildCount()
+	for i := uint(0); i < childCount; i++ {
+		if child := node.NamedChild(i); child != nil {
+			e.collectCalls(child, chunk, report)
+		}
+	}
+}
+
+// calleeName reduces a callee expression to the name being called, e.g. save
+// for user.save, new for Point::new, and parse for str.parse::<T>
+func (e *Extractor) calleeName(node *sitter.Node) string {
+	for node != nil {
+		switch node.Kind() {
+		case "identifier", "field_identifier", "property_identifier", "type_identifier", "name", "constant":
+			return node.Utf8Text(e.sourceCode)
+		}
+
+		var next *sitter.Node
+		for _, field := range calleeFields {
+			if child := node.ChildByFieldName(field); child != nil {
+				next = child
+				break
+			}
+		}
+		node = next
+	}
+	return ""
+}
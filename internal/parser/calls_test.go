@@ -0,0 +1,106 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractCalls(t *testing.T) {
+	tests := []struct {
+		name     string
+		language Language
+		source   string
+		expected string
+	}{
+		{
+			name:     "Go",
+			language: LanguageGo,
+			source: `package main
+
+func run(s *Store) error {
+	data := load()
+	s.Save(data)
+	fmt.Println(load())
+	return nil
+}
+`,
+			expected: "load, Save, Println",
+		},
+		{
+			name:     "Rust",
+			language: LanguageRust,
+			source: `fn build() -> Point {
+    let p = Point::new(1.0, 2.0);
+    let n: i32 = "5".parse::<i32>().unwrap();
+    p.scale(n)
+}
+`,
+			expected: "new, unwrap, parse, scale",
+		},
+		{
+			name:     "Python",
+			language: LanguagePython,
+			source: `def main():
+    user = User.find(1)
+    print(user.name)
+`,
+			expected: "find, print",
+		},
+		{
+			name:     "Java",
+			language: LanguageJava,
+			source: `class App {
+    void run() {
+        service.find(id).save();
+    }
+}
+`,
+			expected: "save, find",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			p, err := NewParser(tt.language)
+			if err != nil {
+				t.Fatalf("Failed to create parser: %v", err)
+			}
+			chunks, err := NewExtractor(p, []byte(tt.source)).ExtractFunctions(context.Background())
+			if err != nil {
+				t.Fatalf("Failed to extract chunks: %v", err)
+			}
+
+			var calls string
+			for _, chunk := range chunks {
+				if chunk.Type == ChunkTypeFunction || chunk.Type == ChunkTypeMethod {
+					calls = chunk.Metadata["calls"]
+				}
+			}
+			if calls != tt.expected {
+				t.Errorf("Expected calls '%s', got '%s'", tt.expected, calls)
+			}
+		})
+	}
+}
+
+func TestExtractCallsSkipsTypes(t *testing.T) {
+	source := `package main
+
+type Config struct {
+	Name string
+}
+`
+	p, err := NewGoParser()
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+	for _, chunk := range chunks {
+		if calls, ok := chunk.Metadata["calls"]; ok {
+			t.Errorf("Expected no calls on %s, got '%s'", chunk.Name, calls)
+		}
+	}
+}
@@ -14,6 +14,12 @@ const (
 	ChunkTypeEnum      ChunkType = "enum"
 	ChunkTypeImpl      ChunkType = "impl"
 	ChunkTypeModule    ChunkType = "module"
+	ChunkTypeTypeAlias ChunkType = "type_alias"
+	ChunkTypeProperty  ChunkType = "property"
+	ChunkTypeBlock     ChunkType = "block"
+	ChunkTypeTable     ChunkType = "table"
+	ChunkTypeView      ChunkType = "view"
+	ChunkTypeMacro     ChunkType = "macro"
 )
 
 // Chunk represents a semantic code chunk extracted from source code
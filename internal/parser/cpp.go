@@ -0,0 +1,242 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// extractCNode extracts C and C++ functions, prototypes, and type definitions.
+// Each chunk records its namespace-qualified name under the "symbol" metadata
+// key, so a prototype in a header and its definition in a source file share
+// the same symbol and can be paired up.
+func (e *Extractor) extractCNode(node *sitter.Node) *Chunk {
+	switch node.Kind() {
+	case "function_definition":
+		return e.extractCFunction(node, false)
+	case "declaration", "field_declaration":
+		// Only function prototypes, not variables or locals
+		if e.cFunctionDeclarator(node.ChildByFieldName("declarator")) == nil || cInsideFunctionBody(node) {
+			return nil
+		}
+		return e.extractCFunction(node, true)
+	case "class_specifier", "struct_specifier", "union_specifier", "enum_specifier":
+		return e.extractCType(node)
+	}
+	return nil
+}
+
+// extractCFunction extracts a function definition or, when declarationOnly is
+// set, a function prototype
+func (e *Extractor) extractCFunction(node *sitter.Node, declarationOnly bool) *Chunk {
+	declarator := e.cFunctionDeclarator(node.ChildByFieldName("declarator"))
+	if declarator == nil {
+		return nil
+	}
+
+	// Out-of-line definitions are qualified: void Animal::move(int d) { ... }
+	fullName := e.cDeclaratorName(declarator.ChildByFieldName("declarator"))
+	if fullName == "" {
+		return nil
+	}
+	name := fullName
+	qualifier := ""
+	if idx := strings.LastIndex(fullName, "::"); idx >= 0 {
+		qualifier = fullName[:idx]
+		name = fullName[idx+2:]
+	}
+
+	scope := e.cScope(node)
+	receiver := qualifier
+	if receiver == "" {
+		receiver = e.cEnclosingClass(node)
+	}
+
+	// A namespace-qualified free function also lands here as a "method"; the
+	// grammar cannot tell namespaces and classes apart in a qualified name
+	chunkType := ChunkTypeFunction
+	if receiver != "" {
+		chunkType = ChunkTypeMethod
+	}
+
+	chunk := e.newCChunk(node, chunkType, name)
+	chunk.Receiver = receiver
+	chunk.Signature = e.fieldText(declarator, "parameters")
+	if returnType := e.fieldText(node, "type"); returnType != "" {
+		chunk.Signature += " " + returnType
+	}
+
+	symbol := append(scope, fullName)
+	chunk.Metadata["symbol"] = strings.Join(symbol, "::")
+	if declarationOnly {
+		chunk.Metadata["declaration"] = "true"
+	}
+
+	return chunk
+}
+
+// extractCType extracts a class, struct, union, or enum definition. Forward
+// declarations and type references (struct Point p;) have no body and are skipped.
+func (e *Extractor) extractCType(node *sitter.Node) *Chunk {
+	if node.ChildByFieldName("body") == nil {
+		return nil
+	}
+
+	name := e.fieldText(node, "name")
+	// typedef struct { ... } Point;
+	if parent := node.Parent(); name == "" && parent != nil && parent.Kind() == "type_definition" {
+		name = e.fieldText(parent, "declarator")
+	}
+	if name == "" {
+		return nil
+	}
+
+	var chunkType ChunkType
+	switch node.Kind() {
+	case "class_specifier":
+		chunkType = ChunkTypeClass
+	case "enum_specifier":
+		chunkType = ChunkTypeEnum
+	default:
+		chunkType = ChunkTypeStruct
+	}
+
+	chunk := e.newCChunk(node, chunkType, name)
+	chunk.Metadata["symbol"] = strings.Join(append(e.cScope(node), name), "::")
+
+	return chunk
+}
+
+// newCChunk creates a chunk for a C/C++ node, widening the span to include an
+// enclosing template<...> or typedef
+func (e *Extractor) newCChunk(node *sitter.Node, chunkType ChunkType, name string) *Chunk {
+	outer := node
+	template := ""
+	if parent := node.Parent(); parent != nil {
+		switch parent.Kind() {
+		case "template_declaration":
+			outer = parent
+			template = e.fieldText(parent, "parameters")
+		case "type_definition":
+			outer = parent
+		}
+	}
+
+	startByte := outer.StartByte()
+	endByte := outer.EndByte()
+
+	chunk := &Chunk{
+		Type:       chunkType,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(outer),
+		StartLine:  int(outer.StartPosition().Row) + 1,
+		EndLine:    int(outer.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   make(map[string]string),
+	}
+
+	if template != "" {
+		chunk.Metadata["template"] = template
+	}
+	if namespaces := e.cNamespaces(node); len(namespaces) > 0 {
+		chunk.Metadata["namespace"] = strings.Join(namespaces, "::")
+	}
+
+	return chunk
+}
+
+// cFunctionDeclarator unwraps pointer and reference declarators to find a
+// function declarator. Function pointers (int (*fp)(int)) are not functions.
+func (e *Extractor) cFunctionDeclarator(node *sitter.Node) *sitter.Node {
+	for node != nil {
+		switch node.Kind() {
+		case "function_declarator":
+			if inner := node.ChildByFieldName("declarator"); inner != nil && inner.Kind() == "parenthesized_declarator" {
+				return nil
+			}
+			return node
+		case "pointer_declarator", "reference_declarator", "attributed_declarator":
+			node = cInnerDeclarator(node)
+		default:
+			return nil
+		}
+	}
+	return nil
+}
+
+// cDeclaratorName returns the (possibly qualified) name a declarator declares
+func (e *Extractor) cDeclaratorName(node *sitter.Node) string {
+	for node != nil {
+		switch node.Kind() {
+		case "identifier", "field_identifier", "type_identifier", "qualified_identifier",
+			"destructor_name", "operator_name", "template_function":
+			return node.Utf8Text(e.sourceCode)
+		}
+		node = cInnerDeclarator(node)
+	}
+	return ""
+}
+
+// cInnerDeclarator returns the declarator wrapped by a declarator node.
+// Reference declarators have no "declarator" field, so fall back to the first named child.
+func cInnerDeclarator(node *sitter.Node) *sitter.Node {
+	if inner := node.ChildByFieldName("declarator"); inner != nil {
+		return inner
+	}
+	if node.NamedChildCount() > 0 {
+		return node.NamedChild(0)
+	}
+	return nil
+}
+
+// cScope returns the enclosing namespace and class names of a node, outermost first
+func (e *Extractor) cScope(node *sitter.Node) []string {
+	var scope []string
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "namespace_definition", "class_specifier", "struct_specifier", "union_specifier":
+			if name := e.fieldText(parent, "name"); name != "" {
+				scope = append([]string{name}, scope...)
+			}
+		}
+	}
+	return scope
+}
+
+// cNamespaces returns the enclosing namespace names of a node, outermost first
+func (e *Extractor) cNamespaces(node *sitter.Node) []string {
+	var namespaces []string
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		if parent.Kind() == "namespace_definition" {
+			if name := e.fieldText(parent, "name"); name != "" {
+				namespaces = append([]string{name}, namespaces...)
+			}
+		}
+	}
+	return namespaces
+}
+
+// cEnclosingClass returns the name of the class or struct a member is declared in
+func (e *Extractor) cEnclosingClass(node *sitter.Node) string {
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "class_specifier", "struct_specifier", "union_specifier":
+			return e.fieldText(parent, "name")
+		case "function_definition", "namespace_definition":
+			return ""
+		}
+	}
+	return ""
+}
+
+// cInsideFunctionBody reports whether a node is nested in a function body
+func cInsideFunctionBody(node *sitter.Node) bool {
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		if parent.Kind() == "compound_statement" {
+			return true
+		}
+	}
+	return false
+}
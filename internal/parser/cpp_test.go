@@ -0,0 +1,108 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractCPPPairsDeclarationsWithDefinitions(t *testing.T) {
+	header := `#ifndef SHAPES_H
+#define SHAPES_H
+
+namespace geo {
+
+// Computes the area of a shape
+double area(double w, double h);
+
+class Shape {
+public:
+    virtual double perimeter() const;
+};
+
+template<typename T>
+T clamp(T v, T lo, T hi) {
+    return v < lo ? lo : (v > hi ? hi : v);
+}
+
+}
+
+#endif
+`
+
+	source := `#include "shapes.h"
+
+namespace geo {
+
+double area(double w, double h) {
+    return w * h;
+}
+
+double Shape::perimeter() const {
+    int (*callback)(int) = nullptr;
+    return 0.0;
+}
+
+}
+`
+
+	p, err := NewParser(LanguageCPP)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	headerChunks, err := NewExtractor(p, []byte(header)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract header chunks: %v", err)
+	}
+	sourceChunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract source chunks: %v", err)
+	}
+
+	headerBySymbol := make(map[string]*Chunk)
+	for _, c := range headerChunks {
+		headerBySymbol[c.Metadata["symbol"]] = c
+	}
+
+	if len(headerChunks) != 4 {
+		t.Fatalf("Expected 4 header chunks (area, Shape, perimeter, clamp), got %d", len(headerChunks))
+	}
+
+	areaDecl := headerBySymbol["geo::area"]
+	if areaDecl == nil || areaDecl.Metadata["declaration"] != "true" {
+		t.Fatalf("Expected prototype for geo::area, got %+v", areaDecl)
+	}
+	if areaDecl.DocComment != "Computes the area of a shape" {
+		t.Errorf("Expected doc comment on prototype, got '%s'", areaDecl.DocComment)
+	}
+	if areaDecl.Metadata["namespace"] != "geo" {
+		t.Errorf("Expected namespace 'geo', got '%s'", areaDecl.Metadata["namespace"])
+	}
+
+	if shape := headerBySymbol["geo::Shape"]; shape == nil || shape.Type != ChunkTypeClass {
+		t.Errorf("Expected class geo::Shape, got %+v", shape)
+	}
+
+	clamp := headerBySymbol["geo::clamp"]
+	if clamp == nil || clamp.Metadata["template"] != "<typename T>" || clamp.StartLine != 14 {
+		t.Errorf("Expected template function clamp starting at its template line, got %+v", clamp)
+	}
+
+	// Every definition in the source file pairs with a header declaration
+	if len(sourceChunks) != 2 {
+		t.Fatalf("Expected 2 source chunks, got %d", len(sourceChunks))
+	}
+	for _, def := range sourceChunks {
+		if def.Metadata["declaration"] != "" {
+			t.Errorf("Definition %s should not be marked as a declaration", def.Name)
+		}
+		if _, ok := headerBySymbol[def.Metadata["symbol"]]; !ok {
+			t.Errorf("Definition %s (symbol %s) has no matching header declaration", def.Name, def.Metadata["symbol"])
+		}
+	}
+
+	perimeter := sourceChunks[1]
+	if perimeter.Name != "perimeter" || perimeter.Receiver != "Shape" || perimeter.Type != ChunkTypeMethod {
+		t.Errorf("Expected method perimeter of Shape, got %+v", perimeter)
+	}
+}
@@ -3,6 +3,8 @@ package parser
 import (
 	"context"
 	"strings"
+	"unicode"
+	"unicode/utf8"
 
 	sitter "github.com/tree-sitter/go-tree-sitter"
 )
@@ -49,6 +51,11 @@ func (e *Extractor) ExtractFunctions(ctx context.Context) ([]*Chunk, error) {
 
 	e.walkNode(rootNode, &chunks)
 
+	// Doc comments and attributes are found the same way in every grammar
+	e.attachDocumentation(rootNode, chunks)
+	e.attachCalls(rootNode, chunks)
+	e.attachImports(rootNode, chunks)
+
 	// Enrich all chunks with file-level metadata
 	e.enrichChunksWithMetadata(chunks)
 
@@ -64,108 +71,113 @@ func (e *Extractor) walkNode(node *sitter.Node, chunks *[]*Chunk) {
 	nodeKind := node.Kind()
 
 	// Go-specific nodes
-	if nodeKind == "function_declaration" {
-		chunk := e.extractFunction(node)
-		if chunk != nil {
-			*chunks = append(*chunks, chunk)
+	if e.parser.Language() == LanguageGo {
+		if nodeKind == "function_declaration" {
+			chunk := e.extractFunction(node)
+			if chunk != nil {
+				*chunks = append(*chunks, chunk)
+			}
 		}
-	}
 
-	if nodeKind == "method_declaration" {
-		chunk := e.extractMethod(node)
-		if chunk != nil {
-			*chunks = append(*chunks, chunk)
+		if nodeKind == "method_declaration" {
+			chunk := e.extractMethod(node)
+			if chunk != nil {
+				*chunks = append(*chunks, chunk)
+			}
 		}
-	}
 
-	if nodeKind == "type_declaration" {
-		typeChunks := e.extractTypes(node)
-		*chunks = append(*chunks, typeChunks...)
+		if nodeKind == "type_declaration" {
+			typeChunks := e.extractTypes(node)
+			*chunks = append(*chunks, typeChunks...)
+		}
+
+		if nodeKind == "const_declaration" || nodeKind == "var_declaration" {
+			// Only package-level declarations; locals belong to their function
+			if parent := node.Parent(); parent != nil && parent.Kind() == "source_file" {
+				chunk := e.extractValueDeclaration(node)
+				if chunk != nil {
+					*chunks = append(*chunks, chunk)
+				}
+			}
+		}
 	}
 
 	// Python-specific nodes
-	if nodeKind == "function_definition" || nodeKind == "class_definition" {
-		chunk := e.extractGenericNode(node, nodeKind)
-		if chunk != nil {
-			*chunks = append(*chunks, chunk)
+	if e.parser.Language() == LanguagePython {
+		switch nodeKind {
+		case "decorated_definition":
+			definition := node.ChildByFieldName("definition")
+			if chunk := e.extractPythonDefinition(definition, node); chunk != nil {
+				*chunks = append(*chunks, chunk)
+			}
+			// Only descend into the inner definition's children so it isn't
+			// extracted a second time without its decorators
+			if definition != nil {
+				e.walkChildren(definition, chunks)
+			}
+			return
+		case "function_definition", "class_definition":
+			if chunk := e.extractPythonDefinition(node, node); chunk != nil {
+				*chunks = append(*chunks, chunk)
+			}
 		}
 	}
 
 	// JavaScript/TypeScript nodes
-	if nodeKind == "function" || nodeKind == "arrow_function" ||
-	   nodeKind == "class_declaration" || nodeKind == "method_definition" {
-		chunk := e.extractGenericNode(node, nodeKind)
-		if chunk != nil {
+	if e.parser.Language() == LanguageJavaScript || e.parser.Language() == LanguageTypeScript {
+		if chunk := e.extractJavaScriptNode(node); chunk != nil {
 			*chunks = append(*chunks, chunk)
 		}
 	}
 
-	// Java nodes
-	if nodeKind == "class_declaration" || nodeKind == "interface_declaration" ||
-	   nodeKind == "method_declaration" || nodeKind == "constructor_declaration" {
-		// Only process if not already handled by Go
-		if e.parser.Language() != LanguageGo {
-			chunk := e.extractGenericNode(node, nodeKind)
-			if chunk != nil {
-				*chunks = append(*chunks, chunk)
-			}
+	// Java nodes (JavaScript and PHP share some node kinds)
+	if e.parser.Language() == LanguageJava {
+		if chunk := e.extractJavaNode(node); chunk != nil {
+			*chunks = append(*chunks, chunk)
 		}
 	}
 
 	// Rust nodes
-	if nodeKind == "function_item" || nodeKind == "struct_item" ||
-	   nodeKind == "enum_item" || nodeKind == "trait_item" || nodeKind == "impl_item" {
-		chunk := e.extractGenericNode(node, nodeKind)
-		if chunk != nil {
+	if e.parser.Language() == LanguageRust {
+		if chunk := e.extractRustNode(node); chunk != nil {
 			*chunks = append(*chunks, chunk)
 		}
 	}
 
-	// C/C++ nodes
-	if nodeKind == "function_definition" || nodeKind == "class_specifier" ||
-	   nodeKind == "struct_specifier" || nodeKind == "enum_specifier" {
-		// Avoid duplicates with Python
-		if e.parser.Language() == LanguageC || e.parser.Language() == LanguageCPP {
-			chunk := e.extractGenericNode(node, nodeKind)
-			if chunk != nil {
-				*chunks = append(*chunks, chunk)
-			}
+	// C/C++ nodes (Python, PHP, and Scala share function_definition)
+	if e.parser.Language() == LanguageC || e.parser.Language() == LanguageCPP {
+		if chunk := e.extractCNode(node); chunk != nil {
+			*chunks = append(*chunks, chunk)
 		}
 	}
 
-	// Ruby nodes
-	if nodeKind == "method" || nodeKind == "class" || nodeKind == "module" {
-		chunk := e.extractGenericNode(node, nodeKind)
-		if chunk != nil {
+	// Ruby nodes (Python's root node is also called "module")
+	if e.parser.Language() == LanguageRuby {
+		if chunk := e.extractRubyNode(node); chunk != nil {
 			*chunks = append(*chunks, chunk)
 		}
 	}
 
 	// PHP nodes
-	if nodeKind == "function_definition" || nodeKind == "class_declaration" ||
-	   nodeKind == "interface_declaration" || nodeKind == "trait_declaration" {
-		// Only process for PHP
-		if e.parser.Language() == LanguagePHP {
-			chunk := e.extractGenericNode(node, nodeKind)
-			if chunk != nil {
-				*chunks = append(*chunks, chunk)
-			}
+	if e.parser.Language() == LanguagePHP {
+		if chunk := e.extractPHPNode(node); chunk != nil {
+			*chunks = append(*chunks, chunk)
 		}
 	}
 
 	// Scala nodes
-	if nodeKind == "function_definition" || nodeKind == "class_definition" ||
-	   nodeKind == "object_definition" || nodeKind == "trait_definition" {
-		// Only process for Scala
-		if e.parser.Language() == LanguageScala {
-			chunk := e.extractGenericNode(node, nodeKind)
-			if chunk != nil {
-				*chunks = append(*chunks, chunk)
-			}
+	if e.parser.Language() == LanguageScala {
+		if chunk := e.extractScalaNode(node); chunk != nil {
+			*chunks = append(*chunks, chunk)
 		}
 	}
 
 	// Recursively walk children
+	e.walkChildren(node, chunks)
+}
+
+// walkChildren walks all direct children of a node
+func (e *Extractor) walkChildren(node *sitter.Node, chunks *[]*Chunk) {
 	childCount := node.ChildCount()
 	for i := uint(0); i < childCount; i++ {
 		child := node.Child(i)
@@ -469,6 +481,71 @@ func (e *Extractor) extractFields(typeNode *sitter.Node) []string {
 	return fields
 }
 
+// extractValueDeclaration extracts a package-level const or var declaration.
+// A grouped declaration (const ( ... )) becomes a single chunk naming all its specs.
+func (e *Extractor) extractValueDeclaration(node *sitter.Node) *Chunk {
+	if node == nil {
+		return nil
+	}
+
+	names := e.collectSpecNames(node)
+	if len(names) == 0 {
+		return nil
+	}
+
+	chunkType := ChunkTypeVar
+	if node.Kind() == "const_declaration" {
+		chunkType = ChunkTypeConst
+	}
+
+	startByte := node.StartByte()
+	endByte := node.EndByte()
+
+	chunk := &Chunk{
+		Type:       chunkType,
+		Name:       strings.Join(names, ", "),
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(node),
+		StartLine:  int(node.StartPosition().Row) + 1,
+		EndLine:    int(node.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   make(map[string]string),
+	}
+
+	return chunk
+}
+
+// collectSpecNames returns the declared identifiers of every const_spec or
+// var_spec under a declaration, including specs nested in a var_spec_list
+func (e *Extractor) collectSpecNames(node *sitter.Node) []string {
+	var names []string
+	childCount := node.ChildCount()
+	for i := uint(0); i < childCount; i++ {
+		child := node.Child(i)
+		if child == nil {
+			continue
+		}
+		switch child.Kind() {
+		case "const_spec", "var_spec":
+			// Names come first in a spec: a, b int = 1, 2
+			specCount := child.ChildCount()
+			for j := uint(0); j < specCount; j++ {
+				part := child.Child(j)
+				if part == nil || (part.Kind() != "identifier" && part.Kind() != ",") {
+					break
+				}
+				if part.Kind() == "identifier" {
+					names = append(names, part.Utf8Text(e.sourceCode))
+				}
+			}
+		case "var_spec_list":
+			names = append(names, e.collectSpecNames(child)...)
+		}
+	}
+	return names
+}
+
 // extractFieldName extracts the field name from a field_declaration
 func (e *Extractor) extractFieldName(fieldNode *sitter.Node) string {
 	if fieldNode == nil {
@@ -624,11 +701,21 @@ func (e *Extractor) enrichChunksWithMetadata(chunks []*Chunk) {
 		}
 
 		// Add language
-		chunk.Metadata["language"] = "go"
+		chunk.Metadata["language"] = e.parser.Language().String()
+
+		// Go exports identifiers that start with an upper-case letter
+		if e.parser.Language() == LanguageGo && chunk.Name != "" {
+			chunk.Metadata["visibility"] = "private"
+			if first, _ := utf8.DecodeRuneInString(chunk.Name); unicode.IsUpper(first) {
+				chunk.Metadata["visibility"] = "public"
+			}
+		}
 	}
 }
 
-// findDocComment finds the documentation comment preceding a node
+// findDocComment finds the documentation comment preceding a node.
+// Consecutive line comments directly above the node are joined, so multi-line
+// "//" doc comments are kept whole; a blank line ends the comment.
 func (e *Extractor) findDocComment(node *sitter.Node) string {
 	if node == nil {
 		return ""
@@ -639,94 +726,130 @@ func (e *Extractor) findDocComment(node *sitter.Node) string {
 		return ""
 	}
 
-	// Look for a comment node immediately before this node
-	// In Go, doc comments are typically comment nodes that precede the declaration
-	prevSibling := node.PrevSibling()
-	if prevSibling != nil && prevSibling.Kind() == "comment" {
-		comment := prevSibling.Utf8Text(e.sourceCode)
-		// Remove leading // or /* */ markers
-		comment = strings.TrimPrefix(comment, "//")
-		comment = strings.TrimPrefix(comment, "/*")
-		comment = strings.TrimSuffix(comment, "*/")
-		return strings.TrimSpace(comment)
+	var parts []string
+	expectedRow := node.StartPosition().Row
+	for prev := node.PrevSibling(); prev != nil && (isCommentKind(prev.Kind()) || attributeKinds[prev.Kind()]); prev = prev.PrevSibling() {
+		// The comment must end on the line directly above what follows it.
+		// Some grammars include the trailing newline in line comments.
+		end := prev.EndPosition()
+		if end.Row+1 != expectedRow && !(end.Row == expectedRow && end.Column == 0) {
+			break
+		}
+		// Attributes such as #[derive(Debug)] may sit between a doc comment and its item
+		if isCommentKind(prev.Kind()) {
+			parts = append([]string{cleanComment(prev.Utf8Text(e.sourceCode))}, parts...)
+		}
+		expectedRow = prev.StartPosition().Row
 	}
 
-	return ""
+	return strings.TrimSpace(strings.Join(parts, "\n"))
 }
 
-// extractGenericNode extracts a generic node for non-Go languages
-// This is a simplified extractor that works across different languages
-func (e *Extractor) extractGenericNode(node *sitter.Node, nodeKind string) *Chunk {
-	if node == nil {
-		return nil
-	}
+// attributeKinds are the node kinds that annotate a declaration: Rust and PHP
+// attributes, decorators, and Java and Scala annotations
+var attributeKinds = map[string]bool{
+	"attribute_item":    true,
+	"attribute_list":    true,
+	"decorator":         true,
+	"annotation":        true,
+	"marker_annotation": true,
+}
 
-	// Try to extract name from common field names
-	var name string
-	nameNode := node.ChildByFieldName("name")
-	if nameNode != nil {
-		name = nameNode.Utf8Text(e.sourceCode)
-	}
+// attachDocumentation fills in the doc comment of chunks whose extractor did
+// not find one, and records each chunk's attributes in its metadata
+func (e *Extractor) attachDocumentation(rootNode *sitter.Node, chunks []*Chunk) {
+	for _, chunk := range chunks {
+		node := rootNode.DescendantForByteRange(uint(chunk.StartByte), uint(chunk.EndByte))
+		if node == nil {
+			continue
+		}
+		// Use the outermost node covering the chunk, which holds its attributes
+		for parent := node.Parent(); parent != nil && parent.StartByte() == node.StartByte() && parent.EndByte() == node.EndByte(); parent = node.Parent() {
+			node = parent
+		}
 
-	// If no name field, try to find identifier in first few children
-	if name == "" {
-		for i := uint(0); i < node.ChildCount() && i < 5; i++ {
-			child := node.Child(i)
-			if child != nil && child.Kind() == "identifier" {
-				name = child.Utf8Text(e.sourceCode)
-				break
+		if chunk.DocComment == "" {
+			chunk.DocComment = e.findDocComment(node)
+		}
+		if _, ok := chunk.Metadata["attributes"]; ok {
+			continue
+		}
+		if attributes := e.findAttributes(node); len(attributes) > 0 {
+			if chunk.Metadata == nil {
+				chunk.Metadata = make(map[string]string)
 			}
+			chunk.Metadata["attributes"] = strings.Join(attributes, ", ")
 		}
 	}
+}
 
-	// Get the full node text
-	startByte := node.StartByte()
-	endByte := node.EndByte()
-	content := string(e.sourceCode[startByte:endByte])
+// findAttributes returns the attributes, decorators, and annotations of a
+// declaration, whether written as siblings before it (Rust, decorators on
+// class members) or as part of it, directly or among its modifiers
+func (e *Extractor) findAttributes(node *sitter.Node) []string {
+	var attributes []string
+	for prev := node.PrevSibling(); prev != nil && (attributeKinds[prev.Kind()] || isCommentKind(prev.Kind())); prev = prev.PrevSibling() {
+		if attributeKinds[prev.Kind()] {
+			attributes = append([]string{strings.TrimSpace(prev.Utf8Text(e.sourceCode))}, attributes...)
+		}
+	}
 
-	// Calculate line numbers (1-indexed)
-	startLine := int(node.StartPosition().Row) + 1
-	endLine := int(node.EndPosition().Row) + 1
+	childCount := node.ChildCount()
+	for i := uint(0); i < childCount; i++ {
+		child := node.Child(i)
+		if child == nil {
+			continue
+		}
+		if attributeKinds[child.Kind()] {
+			attributes = append(attributes, strings.TrimSpace(child.Utf8Text(e.sourceCode)))
+			continue
+		}
+		if child.Kind() == "modifiers" {
+			modCount := child.ChildCount()
+			for j := uint(0); j < modCount; j++ {
+				if mod := child.Child(j); mod != nil && attributeKinds[mod.Kind()] {
+					attributes = append(attributes, strings.TrimSpace(mod.Utf8Text(e.sourceCode)))
+				}
+			}
+		}
+	}
+	return attributes
+}
 
-	// Map node kind to chunk type
-	chunkType := e.mapNodeKindToChunkType(nodeKind)
+// isCommentKind reports whether a node kind is a comment in any supported grammar
+func isCommentKind(kind string) bool {
+	return kind == "comment" || kind == "line_comment" || kind == "block_comment"
+}
 
-	return &Chunk{
-		Type:       chunkType,
-		Name:       name,
-		Content:    content,
-		StartLine:  startLine,
-		EndLine:    endLine,
-		StartByte:  int(startByte),
-		EndByte:    int(endByte),
-		Metadata:   make(map[string]string),
+// cleanComment removes comment markers (//, ///, #, /* */, leading *) from comment text
+func cleanComment(comment string) string {
+	comment = strings.TrimSpace(comment)
+	switch {
+	case strings.HasPrefix(comment, "//"):
+		return strings.TrimSpace(strings.TrimLeft(comment, "/!"))
+	case strings.HasPrefix(comment, "#"):
+		return strings.TrimSpace(strings.TrimLeft(comment, "#"))
+	case strings.HasPrefix(comment, "/*"):
+		comment = strings.TrimPrefix(comment, "/**")
+		comment = strings.TrimPrefix(comment, "/*")
+		comment = strings.TrimSuffix(comment, "*/")
+		lines := strings.Split(comment, "\n")
+		for i, line := range lines {
+			lines[i] = strings.TrimSpace(strings.TrimPrefix(strings.TrimSpace(line), "*"))
+		}
+		return strings.TrimSpace(strings.Join(lines, "\n"))
 	}
+	return comment
 }
 
-// mapNodeKindToChunkType maps Tree-sitter node kinds to chunk types
-func (e *Extractor) mapNodeKindToChunkType(nodeKind string) ChunkType {
-	switch nodeKind {
-	case "function_definition", "function_declaration", "function_item", "function":
-		return ChunkTypeFunction
-	case "method_declaration", "method_definition", "method":
-		return ChunkTypeMethod
-	case "class_definition", "class_declaration", "class_specifier", "class":
-		return ChunkTypeClass
-	case "struct_item", "struct_specifier":
-		return ChunkTypeStruct
-	case "enum_item", "enum_specifier", "enum_declaration":
-		return ChunkTypeEnum
-	case "interface_declaration", "trait_item", "trait_declaration":
-		return ChunkTypeInterface
-	case "impl_item":
-		return ChunkTypeImpl
-	case "module", "namespace_definition":
-		return ChunkTypeModule
-	case "arrow_function":
-		return ChunkTypeFunction
-	case "object_definition":
-		return ChunkTypeClass // Scala objects are similar to classes
-	default:
-		return ChunkTypeFunction // Default fallback
+// fieldText returns the source text of a node's named field, or "" if absent
+func (e *Extractor) fieldText(node *sitter.Node, field string) string {
+	if node == nil {
+		return ""
+	}
+	child := node.ChildByFieldName(field)
+	if child == nil {
+		return ""
 	}
+	return child.Utf8Text(e.sourceCode)
 }
@@ -309,3 +309,120 @@ func (p *Parser) GetRootNode(tree *sitter.Tree) *sitter.Node {
 			chunks[i].Name, chunks[i].Type, chunks[i].StartLine, chunks[i].EndLine)
 	}
 }
+
+func TestExtractPackageLevelValues(t *testing.T) {
+	source := `package main
+
+// MaxRetries is the number of attempts made
+// before a request is abandoned.
+const MaxRetries = 3
+
+// Colors
+const (
+	Red = iota
+	Green
+)
+
+var defaultName, fallbackName string = "a", "b"
+
+func main() {
+	const local = 1
+	var x int
+	_ = x
+}
+`
+
+	parser, err := NewGoParser()
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(parser, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("ExtractFunctions failed: %v", err)
+	}
+
+	// MaxRetries, the const group, the var, and main; locals are not extracted
+	if len(chunks) != 4 {
+		t.Fatalf("Expected 4 chunks, got %d", len(chunks))
+	}
+
+	maxRetries := chunks[0]
+	if maxRetries.Type != ChunkTypeConst || maxRetries.Name != "MaxRetries" {
+		t.Errorf("Expected const MaxRetries, got %s %s", maxRetries.Type, maxRetries.Name)
+	}
+	if maxRetries.DocComment != "MaxRetries is the number of attempts made\nbefore a request is abandoned." {
+		t.Errorf("Expected full multi-line doc comment, got '%s'", maxRetries.DocComment)
+	}
+	if maxRetries.StartLine != 5 {
+		t.Errorf("Expected start line 5, got %d", maxRetries.StartLine)
+	}
+
+	group := chunks[1]
+	if group.Type != ChunkTypeConst || group.Name != "Red, Green" {
+		t.Errorf("Expected const group 'Red, Green', got %s '%s'", group.Type, group.Name)
+	}
+	if group.DocComment != "Colors" {
+		t.Errorf("Expected doc comment 'Colors', got '%s'", group.DocComment)
+	}
+	if group.StartLine != 8 || group.EndLine != 11 {
+		t.Errorf("Expected lines 8-11, got %d-%d", group.StartLine, group.EndLine)
+	}
+
+	vars := chunks[2]
+	if vars.Type != ChunkTypeVar || vars.Name != "defaultName, fallbackName" {
+		t.Errorf("Expected var 'defaultName, fallbackName', got %s '%s'", vars.Type, vars.Name)
+	}
+
+	if chunks[3].Name != "main" {
+		t.Errorf("Expected main function last, got '%s'", chunks[3].Name)
+	}
+
+	if maxRetries.Metadata["visibility"] != "public" {
+		t.Errorf("Expected exported MaxRetries to be public, got '%s'", maxRetries.Metadata["visibility"])
+	}
+	if vars.Metadata["visibility"] != "private" {
+		t.Errorf("Expected unexported vars to be private, got '%s'", vars.Metadata["visibility"])
+	}
+}
+
+func TestExtractRustDocAndAttributes(t *testing.T) {
+	source := `/// A point in 2D space.
+#[derive(Debug, Clone)]
+pub struct Point {
+    pub x: f64,
+}
+
+#[test]
+fn test_point() {}
+`
+
+	p, err := NewParser(LanguageRust)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+	if len(chunks) != 2 {
+		t.Fatalf("Expected 2 chunks, got %d", len(chunks))
+	}
+
+	point := chunks[0]
+	if point.DocComment != "A point in 2D space." {
+		t.Errorf("Expected doc comment above the attribute, got '%s'", point.DocComment)
+	}
+	if point.Metadata["attributes"] != "#[derive(Debug, Clone)]" {
+		t.Errorf("Expected attributes '#[derive(Debug, Clone)]', got '%s'", point.Metadata["attributes"])
+	}
+
+	test := chunks[1]
+	if test.Metadata["attributes"] != "#[test]" {
+		t.Errorf("Expected attributes '#[test]', got '%s'", test.Metadata["attributes"])
+	}
+	if test.DocComment != "" {
+		t.Errorf("Expected no doc comment, got '%s'", test.DocComment)
+	}
+}
@@ -0,0 +1,51 @@
+//go:build !windows
+
+package parser
+
+/*
+#cgo LDFLAGS: -ldl
+#include <dlfcn.h>
+#include <stdlib.h>
+
+typedef const void *(*language_fn)(void);
+
+static const void *call_language_fn(void *fn) {
+	return ((language_fn)fn)();
+}
+*/
+import "C"
+
+import (
+	"fmt"
+	"unsafe"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// LoadGrammar loads a Tree-sitter grammar compiled as a shared library (.so or
+// .dylib). symbol is the grammar's exported language function, e.g.
+// tree_sitter_elixir. The library stays loaded for the life of the process.
+func LoadGrammar(libraryPath, symbol string) (*sitter.Language, error) {
+	cPath := C.CString(libraryPath)
+	defer C.free(unsafe.Pointer(cPath))
+
+	handle := C.dlopen(cPath, C.RTLD_NOW|C.RTLD_LOCAL)
+	if handle == nil {
+		return nil, fmt.Errorf("failed to load grammar %s: %s", libraryPath, C.GoString(C.dlerror()))
+	}
+
+	cSymbol := C.CString(symbol)
+	defer C.free(unsafe.Pointer(cSymbol))
+
+	fn := C.dlsym(handle, cSymbol)
+	if fn == nil {
+		C.dlclose(handle)
+		return nil, fmt.Errorf("grammar %s has no symbol %s: %s", libraryPath, symbol, C.GoString(C.dlerror()))
+	}
+
+	ptr := C.call_language_fn(fn)
+	if ptr == nil {
+		return nil, fmt.Errorf("grammar %s returned no language from %s", libraryPath, symbol)
+	}
+	return sitter.NewLanguage(unsafe.Pointer(ptr)), nil
+}
@@ -0,0 +1,12 @@
+package parser
+
+import (
+	"fmt"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// LoadGrammar is not supported on Windows
+func LoadGrammar(libraryPath, symbol string) (*sitter.Language, error) {
+	return nil, fmt.Errorf("loading grammar %s: runtime grammars are not supported on windows", libraryPath)
+}
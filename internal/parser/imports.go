@@ -0,0 +1,70 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// importKinds are the node kinds of import statements in the supported grammars
+var importKinds = map[string]bool{
+	"import_declaration":        true, // Go, Java, Scala
+	"use_declaration":           true, // Rust
+	"import_statement":          true, // Python, JavaScript, TypeScript
+	"import_from_statement":     true, // Python
+	"future_import_statement":   true, // Python
+	"namespace_use_declaration": true, // PHP
+	"preproc_include":           true, // C, C++
+	"using_declaration":         true, // C++
+}
+
+// attachImports records the import statements visible to each chunk as its
+// import_statements metadata, one per line: those at the top of the file,
+// followed by those of the Rust mod blocks the chunk is declared in
+func (e *Extractor) attachImports(rootNode *sitter.Node, chunks []*Chunk) {
+	for _, chunk := range chunks {
+		node := rootNode.DescendantForByteRange(uint(chunk.StartByte), uint(chunk.EndByte))
+		if node == nil {
+			continue
+		}
+
+		// Scopes are collected innermost first
+		var scopes [][]string
+		for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+			switch parent.Kind() {
+			case "source_file", "program", "module", "translation_unit", "compilation_unit":
+				scopes = append(scopes, e.importStatements(parent))
+			case "mod_item":
+				if body := parent.ChildByFieldName("body"); body != nil {
+					scopes = append(scopes, e.importStatements(body))
+				}
+			}
+		}
+
+		var statements []string
+		for i := len(scopes) - 1; i >= 0; i-- {
+			statements = append(statements, scopes[i]...)
+		}
+		if len(statements) == 0 {
+			continue
+		}
+		if chunk.Metadata == nil {
+			chunk.Metadata = make(map[string]string)
+		}
+		chunk.Metadata["import_statements"] = strings.Join(statements, "\n")
+	}
+}
+
+// importStatements returns the text of the import statements directly inside
+// a scope
+func (e *Extractor) importStatements(scope *sitter.Node) []string {
+	var statements []string
+	childCount := scope.NamedChildCount()
+	for i := uint(0); i < childCount; i++ {
+		child := scope.NamedChild(i)
+		if child != nil && importKinds[child.Kind()] {
+			statements = append(statements, strings.TrimSpace(child.Utf8Text(e.sourceCode)))
+		}
+	}
+	return statements
+}
@@ -0,0 +1,69 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestAttachImportsRust(t *testing.T) {
+	source := `use std::collections::HashMap;
+use std::fmt;
+
+pub fn count(words: &[&str]) -> HashMap<String, usize> {
+    HashMap::new()
+}
+
+mod tests {
+    use super::*;
+
+    fn check() {}
+}
+`
+
+	p, err := NewParser(LanguageRust)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+	if len(chunks) != 2 {
+		t.Fatalf("Expected 2 chunks, got %d", len(chunks))
+	}
+
+	expected := "use std::collections::HashMap;\nuse std::fmt;"
+	if got := chunks[0].Metadata["import_statements"]; got != expected {
+		t.Errorf("Expected file imports %q, got %q", expected, got)
+	}
+	expected += "\nuse super::*;"
+	if got := chunks[1].Metadata["import_statements"]; got != expected {
+		t.Errorf("Expected file and module imports %q, got %q", expected, got)
+	}
+}
+
+func TestAttachImportsPython(t *testing.T) {
+	source := `import os
+from typing import List
+
+def walk(root: str) -> List[str]:
+    return os.listdir(root)
+`
+
+	p, err := NewParser(LanguagePython)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+	if len(chunks) != 1 {
+		t.Fatalf("Expected 1 chunk, got %d", len(chunks))
+	}
+
+	expected := "import os\nfrom typing import List"
+	if got := chunks[0].Metadata["import_statements"]; got != expected {
+		t.Errorf("Expected imports %q, got %q", expected, got)
+	}
+}
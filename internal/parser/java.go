@@ -0,0 +1,222 @@
+package parser
+
+import (
+	"regexp"
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+var (
+	// Matches runs of blank lines left behind after eliding members from a type
+	javaBlankLinesRegex = regexp.MustCompile(`\n[ \t]*(\n[ \t]*)+\n`)
+)
+
+// extractJavaNode extracts Java types, methods, and constructors. Each method
+// gets its own chunk; the type-level chunk keeps the javadoc, annotations, and
+// fields but leaves out member bodies, so a search for a method lands on the
+// method rather than on its whole class.
+func (e *Extractor) extractJavaNode(node *sitter.Node) *Chunk {
+	var chunkType ChunkType
+	switch node.Kind() {
+	case "class_declaration", "record_declaration":
+		chunkType = ChunkTypeClass
+	case "interface_declaration", "annotation_type_declaration":
+		chunkType = ChunkTypeInterface
+	case "enum_declaration":
+		chunkType = ChunkTypeEnum
+	case "method_declaration", "constructor_declaration":
+		chunkType = ChunkTypeMethod
+	default:
+		return nil
+	}
+
+	name := e.fieldText(node, "name")
+	if name == "" {
+		return nil
+	}
+
+	startByte := node.StartByte()
+	endByte := node.EndByte()
+
+	chunk := &Chunk{
+		Type:       chunkType,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(node),
+		StartLine:  int(node.StartPosition().Row) + 1,
+		EndLine:    int(node.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   make(map[string]string),
+	}
+
+	if annotations := e.javaAnnotations(node); len(annotations) > 0 {
+		chunk.Metadata["annotations"] = strings.Join(annotations, ", ")
+		chunk.Metadata["attributes"] = chunk.Metadata["annotations"]
+	}
+
+	chunk.Metadata["visibility"] = e.javaVisibility(node)
+
+	if chunkType == ChunkTypeMethod {
+		chunk.Receiver = e.javaEnclosingType(node)
+		chunk.Signature = e.fieldText(node, "parameters")
+		if returnType := e.fieldText(node, "type"); returnType != "" {
+			chunk.Signature += " " + returnType
+		}
+		if node.Kind() == "constructor_declaration" {
+			chunk.Metadata["constructor"] = "true"
+		}
+		return chunk
+	}
+
+	// Type-level chunk: the javadoc plus the declaration with its methods,
+	// constructors, and nested types removed
+	summary := e.javaTypeSummary(node)
+	if javadoc := e.javaDocText(node); javadoc != "" {
+		summary = javadoc + "\n" + summary
+	}
+	chunk.Content = summary
+
+	return chunk
+}
+
+// javaAnnotations returns the annotations on a declaration, e.g. @GetMapping("/users")
+func (e *Extractor) javaAnnotations(node *sitter.Node) []string {
+	var annotations []string
+	childCount := node.NamedChildCount()
+	for i := uint(0); i < childCount; i++ {
+		modifiers := node.NamedChild(i)
+		if modifiers == nil || modifiers.Kind() != "modifiers" {
+			continue
+		}
+		modCount := modifiers.NamedChildCount()
+		for j := uint(0); j < modCount; j++ {
+			mod := modifiers.NamedChild(j)
+			if mod != nil && (mod.Kind() == "marker_annotation" || mod.Kind() == "annotation") {
+				annotations = append(annotations, mod.Utf8Text(e.sourceCode))
+			}
+		}
+	}
+	return annotations
+}
+
+// javaVisibility returns a declaration's access modifier (public, protected,
+// or private), or "package" when it has none
+func (e *Extractor) javaVisibility(node *sitter.Node) string {
+	childCount := node.NamedChildCount()
+	for i := uint(0); i < childCount; i++ {
+		modifiers := node.NamedChild(i)
+		if modifiers == nil || modifiers.Kind() != "modifiers" {
+			continue
+		}
+		for _, word := range strings.Fields(modifiers.Utf8Text(e.sourceCode)) {
+			switch word {
+			case "public", "protected", "private":
+				return word
+			}
+		}
+	}
+	return "package"
+}
+
+// javaEnclosingType returns the name of the type a member is declared in
+func (e *Extractor) javaEnclosingType(member *sitter.Node) string {
+	for parent := member.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "class_declaration", "interface_declaration", "enum_declaration",
+			"record_declaration", "annotation_type_declaration":
+			return e.fieldText(parent, "name")
+		}
+	}
+	return ""
+}
+
+// javaDocText returns the raw javadoc block directly above a declaration
+func (e *Extractor) javaDocText(node *sitter.Node) string {
+	prev := node.PrevSibling()
+	if prev == nil || !isCommentKind(prev.Kind()) || prev.EndPosition().Row+1 != node.StartPosition().Row {
+		return ""
+	}
+	text := prev.Utf8Text(e.sourceCode)
+	if !strings.HasPrefix(text, "/**") {
+		return ""
+	}
+	return text
+}
+
+// isJavaMember reports whether a class body member gets its own chunk
+func isJavaMember(kind string) bool {
+	switch kind {
+	case "method_declaration", "constructor_declaration", "compact_constructor_declaration",
+		"class_declaration", "interface_declaration", "enum_declaration",
+		"record_declaration", "annotation_type_declaration":
+		return true
+	}
+	return false
+}
+
+// javaTypeSummary returns the source of a type declaration with the members
+// that are chunked separately (and the comments directly above them) removed
+func (e *Extractor) javaTypeSummary(node *sitter.Node) string {
+	body := node.ChildByFieldName("body")
+	if body == nil {
+		return node.Utf8Text(e.sourceCode)
+	}
+
+	var builder strings.Builder
+	pos := node.StartByte()
+
+	memberCount := body.NamedChildCount()
+	for i := uint(0); i < memberCount; i++ {
+		member := body.NamedChild(i)
+		if member == nil || !isJavaMember(member.Kind()) {
+			continue
+		}
+
+		start := member.StartByte()
+		for prev := member.PrevNamedSibling(); prev != nil && isCommentKind(prev.Kind()) && prev.StartByte() >= pos; prev = prev.PrevNamedSibling() {
+			start = prev.StartByte()
+		}
+		start = e.lineStartByte(start)
+		end := e.lineEndByte(member.EndByte())
+		if start < pos {
+			start = pos
+		}
+
+		builder.Write(e.sourceCode[pos:start])
+		pos = end
+	}
+	builder.Write(e.sourceCode[pos:node.EndByte()])
+
+	return javaBlankLinesRegex.ReplaceAllString(builder.String(), "\n\n")
+}
+
+// lineStartByte moves a byte offset back to the start of its line when only
+// indentation precedes it
+func (e *Extractor) lineStartByte(offset uint) uint {
+	i := offset
+	for i > 0 && (e.sourceCode[i-1] == ' ' || e.sourceCode[i-1] == '\t') {
+		i--
+	}
+	if i == 0 || e.sourceCode[i-1] == '\n' {
+		return i
+	}
+	return offset
+}
+
+// lineEndByte moves a byte offset past the end of its line when only
+// whitespace follows it
+func (e *Extractor) lineEndByte(offset uint) uint {
+	i := offset
+	for i < uint(len(e.sourceCode)) && (e.sourceCode[i] == ' ' || e.sourceCode[i] == '\t' || e.sourceCode[i] == '\r') {
+		i++
+	}
+	if i < uint(len(e.sourceCode)) && e.sourceCode[i] == '\n' {
+		return i + 1
+	}
+	if i == uint(len(e.sourceCode)) {
+		return i
+	}
+	return offset
+}
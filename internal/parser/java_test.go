@@ -0,0 +1,97 @@
+package parser
+
+import (
+	"context"
+	"strings"
+	"testing"
+)
+
+func TestExtractJava(t *testing.T) {
+	source := `package com.example;
+
+/**
+ * Handles user requests.
+ */
+@RestController
+@RequestMapping("/users")
+public class UserController {
+    private final UserService service;
+
+    public UserController(UserService service) {
+        this.service = service;
+    }
+
+    /** Returns a user by id. */
+    @GetMapping("/{id}")
+    public User getUser(@PathVariable String id) {
+        return service.find(id);
+    }
+}
+`
+
+	p, err := NewParser(LanguageJava)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	if len(chunks) != 3 {
+		t.Fatalf("Expected 3 chunks, got %d", len(chunks))
+	}
+
+	class := chunks[0]
+	if class.Type != ChunkTypeClass || class.Name != "UserController" {
+		t.Fatalf("Expected class UserController, got %s %s", class.Type, class.Name)
+	}
+	if class.DocComment != "Handles user requests." {
+		t.Errorf("Expected class javadoc, got '%s'", class.DocComment)
+	}
+	if !strings.HasPrefix(class.Content, "/**") {
+		t.Error("Expected class chunk to start with its javadoc")
+	}
+	if !strings.Contains(class.Content, "@RequestMapping(\"/users\")") {
+		t.Error("Expected class chunk to include its annotations")
+	}
+	if !strings.Contains(class.Content, "private final UserService service;") {
+		t.Error("Expected class chunk to include its fields")
+	}
+	if strings.Contains(class.Content, "service.find") || strings.Contains(class.Content, "Returns a user") {
+		t.Errorf("Expected method bodies to be left out of the class chunk, got:\n%s", class.Content)
+	}
+	if class.Metadata["annotations"] != `@RestController, @RequestMapping("/users")` {
+		t.Errorf("Unexpected class annotations '%s'", class.Metadata["annotations"])
+	}
+	if class.Metadata["attributes"] != class.Metadata["annotations"] {
+		t.Errorf("Expected class attributes to match its annotations, got '%s'", class.Metadata["attributes"])
+	}
+
+	if class.Metadata["visibility"] != "public" {
+		t.Errorf("Expected public class, got '%s'", class.Metadata["visibility"])
+	}
+
+	ctor := chunks[1]
+	if ctor.Type != ChunkTypeMethod || ctor.Metadata["constructor"] != "true" || ctor.Receiver != "UserController" {
+		t.Errorf("Expected constructor of UserController, got %+v", ctor)
+	}
+
+	method := chunks[2]
+	if method.Name != "getUser" || method.Receiver != "UserController" {
+		t.Errorf("Expected method getUser of UserController, got %s (receiver %s)", method.Name, method.Receiver)
+	}
+	if !strings.HasPrefix(method.Content, `@GetMapping("/{id}")`) {
+		t.Errorf("Expected method chunk to start with its annotation, got:\n%s", method.Content)
+	}
+	if method.Metadata["attributes"] != `@GetMapping("/{id}")` {
+		t.Errorf("Expected method attributes '@GetMapping(\"/{id}\")', got '%s'", method.Metadata["attributes"])
+	}
+	if method.Signature != "(@PathVariable String id) User" {
+		t.Errorf("Unexpected signature '%s'", method.Signature)
+	}
+	if method.DocComment != "Returns a user by id." {
+		t.Errorf("Expected method javadoc, got '%s'", method.DocComment)
+	}
+}
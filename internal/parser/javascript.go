@@ -0,0 +1,125 @@
+package parser
+
+import (
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// extractJavaScriptNode extracts functions, classes, methods, and function-valued
+// variable declarations (e.g. `const add = (a, b) => a + b`) from JavaScript and
+// TypeScript. Anonymous callbacks are skipped since they have no useful symbol.
+func (e *Extractor) extractJavaScriptNode(node *sitter.Node) *Chunk {
+	var (
+		chunkType ChunkType
+		name      string
+		fnNode    *sitter.Node // node holding the parameters, for functions and methods
+	)
+
+	switch node.Kind() {
+	case "function_declaration", "generator_function_declaration":
+		chunkType = ChunkTypeFunction
+		name = e.fieldText(node, "name")
+		fnNode = node
+	case "class_declaration":
+		chunkType = ChunkTypeClass
+		name = e.fieldText(node, "name")
+	case "method_definition":
+		chunkType = ChunkTypeMethod
+		name = e.fieldText(node, "name")
+		fnNode = node
+	case "lexical_declaration", "variable_declaration":
+		declarator := e.jsFunctionDeclarator(node)
+		if declarator == nil {
+			return nil
+		}
+		chunkType = ChunkTypeFunction
+		name = e.fieldText(declarator, "name")
+		fnNode = declarator.ChildByFieldName("value")
+	default:
+		return nil
+	}
+
+	if name == "" {
+		return nil
+	}
+
+	// Include the export keyword when the declaration is exported
+	outer := node
+	exported := false
+	if parent := node.Parent(); parent != nil && parent.Kind() == "export_statement" {
+		outer = parent
+		exported = true
+	}
+
+	startByte := outer.StartByte()
+	endByte := outer.EndByte()
+
+	chunk := &Chunk{
+		Type:       chunkType,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(outer),
+		StartLine:  int(outer.StartPosition().Row) + 1,
+		EndLine:    int(outer.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   make(map[string]string),
+	}
+
+	if fnNode != nil {
+		chunk.Signature = e.jsParameters(fnNode)
+		if first := fnNode.Child(0); first != nil && first.Kind() == "async" {
+			chunk.Metadata["async"] = "true"
+		}
+	}
+	if chunkType == ChunkTypeMethod {
+		chunk.Receiver = e.jsEnclosingClass(node)
+	}
+	if exported {
+		chunk.Metadata["exported"] = "true"
+	}
+
+	return chunk
+}
+
+// jsFunctionDeclarator returns the first declarator of a variable declaration
+// whose value is a function or arrow function
+func (e *Extractor) jsFunctionDeclarator(declaration *sitter.Node) *sitter.Node {
+	childCount := declaration.NamedChildCount()
+	for i := uint(0); i < childCount; i++ {
+		declarator := declaration.NamedChild(i)
+		if declarator == nil || declarator.Kind() != "variable_declarator" {
+			continue
+		}
+		value := declarator.ChildByFieldName("value")
+		if value == nil {
+			continue
+		}
+		switch value.Kind() {
+		case "arrow_function", "function_expression", "function", "generator_function":
+			return declarator
+		}
+	}
+	return nil
+}
+
+// jsParameters returns the parameter list of a JavaScript function-like node
+func (e *Extractor) jsParameters(fnNode *sitter.Node) string {
+	if params := fnNode.ChildByFieldName("parameters"); params != nil {
+		return params.Utf8Text(e.sourceCode)
+	}
+	// Single-parameter arrow functions (x => x * 2) use the "parameter" field
+	if param := fnNode.ChildByFieldName("parameter"); param != nil {
+		return "(" + param.Utf8Text(e.sourceCode) + ")"
+	}
+	return ""
+}
+
+// jsEnclosingClass returns the name of the class a method is defined in
+func (e *Extractor) jsEnclosingClass(method *sitter.Node) string {
+	for parent := method.Parent(); parent != nil; parent = parent.Parent() {
+		if parent.Kind() == "class_declaration" || parent.Kind() == "class" {
+			return e.fieldText(parent, "name")
+		}
+	}
+	return ""
+}
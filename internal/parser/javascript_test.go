@@ -0,0 +1,84 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractJavaScript(t *testing.T) {
+	source := `/** Adds two numbers */
+export function add(a, b) {
+    return a + b;
+}
+
+const double = x => x * 2;
+
+export const fetchUser = async (id) => {
+    return [1, 2, 3].map(n => n + id);
+};
+
+class Greeter {
+    greet(name) {
+        return "Hello, " + name;
+    }
+}
+`
+
+	p, err := NewParser(LanguageJavaScript)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	byName := make(map[string]*Chunk)
+	for _, c := range chunks {
+		if c.Name == "" {
+			t.Errorf("Anonymous chunk extracted at line %d", c.StartLine)
+		}
+		if _, exists := byName[c.Name]; exists {
+			t.Errorf("Chunk %s extracted more than once", c.Name)
+		}
+		byName[c.Name] = c
+	}
+
+	if len(chunks) != 5 {
+		t.Fatalf("Expected 5 chunks, got %d", len(chunks))
+	}
+
+	add := byName["add"]
+	if add == nil || add.Type != ChunkTypeFunction {
+		t.Fatalf("Expected function chunk for add, got %+v", add)
+	}
+	if add.Metadata["exported"] != "true" {
+		t.Error("Expected add to be marked as exported")
+	}
+	if add.StartLine != 2 {
+		t.Errorf("Expected add to start at the export keyword on line 2, got %d", add.StartLine)
+	}
+	if add.Signature != "(a, b)" {
+		t.Errorf("Expected signature '(a, b)', got '%s'", add.Signature)
+	}
+
+	double := byName["double"]
+	if double == nil || double.Type != ChunkTypeFunction || double.Signature != "(x)" {
+		t.Errorf("Expected arrow function const double, got %+v", double)
+	}
+
+	fetchUser := byName["fetchUser"]
+	if fetchUser == nil || fetchUser.Metadata["async"] != "true" || fetchUser.Metadata["exported"] != "true" {
+		t.Errorf("Expected exported async arrow function fetchUser, got %+v", fetchUser)
+	}
+
+	if greeter := byName["Greeter"]; greeter == nil || greeter.Type != ChunkTypeClass {
+		t.Errorf("Expected class chunk for Greeter, got %+v", greeter)
+	}
+
+	greet := byName["greet"]
+	if greet == nil || greet.Type != ChunkTypeMethod || greet.Receiver != "Greeter" {
+		t.Errorf("Expected greet to be a method of Greeter, got %+v", greet)
+	}
+}
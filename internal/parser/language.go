@@ -54,6 +54,17 @@ func (l Language) String() string {
 	}
 }
 
+// LanguageFromName returns the language whose String() is name, or
+// LanguageUnknown when no parser exists for it
+func LanguageFromName(name string) Language {
+	for l := LanguageGo; l <= LanguageScala; l++ {
+		if l.String() == name {
+			return l
+		}
+	}
+	return LanguageUnknown
+}
+
 // DetectLanguage determines the programming language from file path and content
 func DetectLanguage(filePath string, content []byte) Language {
 	ext := strings.ToLower(filepath.Ext(filePath))
@@ -64,9 +75,9 @@ func DetectLanguage(filePath string, content []byte) Language {
 		return LanguageGo
 	case ".py":
 		return LanguagePython
-	case ".js", ".jsx":
+	case ".js", ".jsx", ".mjs", ".cjs":
 		return LanguageJavaScript
-	case ".ts", ".tsx":
+	case ".ts", ".tsx", ".mts", ".cts":
 		return LanguageTypeScript
 	case ".java":
 		return LanguageJava
@@ -76,7 +87,7 @@ func DetectLanguage(filePath string, content []byte) Language {
 		return LanguageRuby
 	case ".php":
 		return LanguagePHP
-	case ".scala":
+	case ".scala", ".sc":
 		return LanguageScala
 	case ".cpp", ".cc", ".cxx":
 		return LanguageCPP
@@ -201,9 +212,9 @@ func (l Language) FileExtensions() []string {
 	case LanguagePython:
 		return []string{".py"}
 	case LanguageJavaScript:
-		return []string{".js", ".jsx"}
+		return []string{".js", ".jsx", ".mjs", ".cjs"}
 	case LanguageTypeScript:
-		return []string{".ts", ".tsx"}
+		return []string{".ts", ".tsx", ".mts", ".cts"}
 	case LanguageJava:
 		return []string{".java"}
 	case LanguageRust:
@@ -217,7 +228,7 @@ func (l Language) FileExtensions() []string {
 	case LanguagePHP:
 		return []string{".php"}
 	case LanguageScala:
-		return []string{".scala"}
+		return []string{".scala", ".sc"}
 	default:
 		return []string{}
 	}
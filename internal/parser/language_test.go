@@ -34,6 +34,18 @@ func TestDetectLanguage(t *testing.T) {
 			content:  "function main(): void {}",
 			want:     LanguageTypeScript,
 		},
+		{
+			name:     "ES module file",
+			filePath: "app.mjs",
+			content:  "export function main() {}",
+			want:     LanguageJavaScript,
+		},
+		{
+			name:     "TSX file",
+			filePath: "App.tsx",
+			content:  "export const App = () => <div />;",
+			want:     LanguageTypeScript,
+		},
 		{
 			name:     "Java file",
 			filePath: "Main.java",
@@ -197,6 +209,28 @@ func TestLanguageString(t *testing.T) {
 	}
 }
 
+func TestLanguageFromName(t *testing.T) {
+	tests := []struct {
+		name string
+		want Language
+	}{
+		{"go", LanguageGo},
+		{"typescript", LanguageTypeScript},
+		{"cpp", LanguageCPP},
+		{"scala", LanguageScala},
+		{"kotlin", LanguageUnknown},
+		{"", LanguageUnknown},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := LanguageFromName(tt.name); got != tt.want {
+				t.Errorf("LanguageFromName(%q) = %v, want %v", tt.name, got, tt.want)
+			}
+		})
+	}
+}
+
 func TestLanguageIsSupported(t *testing.T) {
 	tests := []struct {
 		lang Language
@@ -232,15 +266,15 @@ func TestLanguageFileExtensions(t *testing.T) {
 	}{
 		{LanguageGo, []string{".go"}},
 		{LanguagePython, []string{".py"}},
-		{LanguageJavaScript, []string{".js", ".jsx"}},
-		{LanguageTypeScript, []string{".ts", ".tsx"}},
+		{LanguageJavaScript, []string{".js", ".jsx", ".mjs", ".cjs"}},
+		{LanguageTypeScript, []string{".ts", ".tsx", ".mts", ".cts"}},
 		{LanguageJava, []string{".java"}},
 		{LanguageRust, []string{".rs"}},
 		{LanguageC, []string{".c", ".h"}},
 		{LanguageCPP, []string{".cpp", ".cc", ".cxx", ".hpp", ".hxx", ".h"}},
 		{LanguageRuby, []string{".rb"}},
 		{LanguagePHP, []string{".php"}},
-		{LanguageScala, []string{".scala"}},
+		{LanguageScala, []string{".scala", ".sc"}},
 	}
 
 	for _, tt := range tests {
@@ -0,0 +1,132 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// extractPHPNode extracts PHP namespaces, classes, interfaces, traits, enums,
+// functions, and methods. PHPDoc blocks directly above a declaration become its
+// doc comment, and every chunk records the namespace it belongs to.
+func (e *Extractor) extractPHPNode(node *sitter.Node) *Chunk {
+	var chunkType ChunkType
+	kind := ""
+
+	switch node.Kind() {
+	case "namespace_definition":
+		return e.extractPHPNamespace(node)
+	case "class_declaration":
+		chunkType = ChunkTypeClass
+	case "interface_declaration":
+		chunkType = ChunkTypeInterface
+	case "trait_declaration":
+		chunkType = ChunkTypeInterface
+		kind = "trait"
+	case "enum_declaration":
+		chunkType = ChunkTypeEnum
+	case "function_definition":
+		chunkType = ChunkTypeFunction
+	case "method_declaration":
+		chunkType = ChunkTypeMethod
+	default:
+		return nil
+	}
+
+	name := e.fieldText(node, "name")
+	if name == "" {
+		return nil
+	}
+
+	startByte := node.StartByte()
+	endByte := node.EndByte()
+
+	chunk := &Chunk{
+		Type:       chunkType,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(node),
+		StartLine:  int(node.StartPosition().Row) + 1,
+		EndLine:    int(node.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   make(map[string]string),
+	}
+
+	if kind != "" {
+		chunk.Metadata["kind"] = kind
+	}
+	if namespace := e.phpNamespace(node); namespace != "" {
+		chunk.Metadata["namespace"] = namespace
+	}
+
+	if chunkType == ChunkTypeFunction || chunkType == ChunkTypeMethod {
+		chunk.Signature = e.fieldText(node, "parameters")
+		if returnType := e.fieldText(node, "return_type"); returnType != "" {
+			chunk.Signature += " " + returnType
+		}
+	}
+	if chunkType == ChunkTypeMethod {
+		chunk.Receiver = e.phpEnclosingType(node)
+	}
+
+	return chunk
+}
+
+// extractPHPNamespace extracts a namespace. A braced namespace covers its block;
+// a `namespace Foo;` statement covers everything up to the next namespace.
+func (e *Extractor) extractPHPNamespace(node *sitter.Node) *Chunk {
+	name := e.fieldText(node, "name")
+	if name == "" {
+		return nil
+	}
+
+	end := node
+	if node.ChildByFieldName("body") == nil {
+		for next := node.NextSibling(); next != nil && next.Kind() != "namespace_definition"; next = next.NextSibling() {
+			end = next
+		}
+	}
+
+	startByte := node.StartByte()
+	endByte := end.EndByte()
+
+	return &Chunk{
+		Type:       ChunkTypeModule,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(node),
+		StartLine:  int(node.StartPosition().Row) + 1,
+		EndLine:    int(end.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   map[string]string{"namespace": name},
+	}
+}
+
+// phpNamespace returns the namespace a declaration belongs to, from either an
+// enclosing braced namespace or the closest preceding `namespace Foo;` statement
+func (e *Extractor) phpNamespace(node *sitter.Node) string {
+	for current := node; current != nil; current = current.Parent() {
+		if current.Kind() == "namespace_definition" {
+			return e.fieldText(current, "name")
+		}
+		for prev := current.PrevSibling(); prev != nil; prev = prev.PrevSibling() {
+			if prev.Kind() == "namespace_definition" && prev.ChildByFieldName("body") == nil {
+				return e.fieldText(prev, "name")
+			}
+		}
+	}
+	return ""
+}
+
+// phpEnclosingType returns the name of the class, interface, trait, or enum a method is declared in
+func (e *Extractor) phpEnclosingType(method *sitter.Node) string {
+	for parent := method.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "class_declaration", "interface_declaration", "trait_declaration", "enum_declaration":
+			return strings.TrimSpace(e.fieldText(parent, "name"))
+		}
+	}
+	return ""
+}
@@ -0,0 +1,92 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractPHP(t *testing.T) {
+	source := `<?php
+
+namespace App\Models;
+
+/**
+ * Adds created/updated timestamps.
+ */
+trait Timestamps {
+    public function touch(): void {
+        $this->updated = time();
+    }
+}
+
+/**
+ * A registered user.
+ */
+class User {
+    use Timestamps;
+
+    /**
+     * Returns the display name.
+     */
+    public function displayName(string $prefix = ''): string {
+        return $prefix . $this->name;
+    }
+}
+
+function helper(int $x): int {
+    return $x * 2;
+}
+`
+
+	p, err := NewParser(LanguagePHP)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	byName := make(map[string]*Chunk)
+	for _, c := range chunks {
+		if _, exists := byName[c.Name]; exists {
+			t.Errorf("Chunk %s extracted more than once", c.Name)
+		}
+		byName[c.Name] = c
+	}
+
+	if len(chunks) != 6 {
+		t.Fatalf("Expected 6 chunks, got %d", len(chunks))
+	}
+
+	namespace := byName[`App\Models`]
+	if namespace == nil || namespace.Type != ChunkTypeModule || namespace.EndLine != 30 {
+		t.Errorf("Expected namespace chunk covering the rest of the file, got %+v", namespace)
+	}
+
+	trait := byName["Timestamps"]
+	if trait == nil || trait.Metadata["kind"] != "trait" || trait.DocComment != "Adds created/updated timestamps." {
+		t.Errorf("Expected documented trait Timestamps, got %+v", trait)
+	}
+
+	user := byName["User"]
+	if user == nil || user.Type != ChunkTypeClass || user.Metadata["namespace"] != `App\Models` {
+		t.Errorf("Expected class User in App\\Models, got %+v", user)
+	}
+
+	display := byName["displayName"]
+	if display == nil || display.Type != ChunkTypeMethod || display.Receiver != "User" {
+		t.Fatalf("Expected method displayName of User, got %+v", display)
+	}
+	if display.DocComment != "Returns the display name." {
+		t.Errorf("Expected PHPDoc on displayName, got '%s'", display.DocComment)
+	}
+	if display.Signature != "(string $prefix = '') string" {
+		t.Errorf("Unexpected signature '%s'", display.Signature)
+	}
+
+	if helper := byName["helper"]; helper == nil || helper.Type != ChunkTypeFunction {
+		t.Errorf("Expected function helper, got %+v", helper)
+	}
+}
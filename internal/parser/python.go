@@ -0,0 +1,140 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// extractPythonDefinition extracts a Python function or class definition.
+// outerNode is the node whose span becomes the chunk content: the definition
+// itself, or the enclosing decorated_definition so decorators are included.
+func (e *Extractor) extractPythonDefinition(definition, outerNode *sitter.Node) *Chunk {
+	if definition == nil || outerNode == nil {
+		return nil
+	}
+
+	nameNode := definition.ChildByFieldName("name")
+	if nameNode == nil {
+		return nil
+	}
+	name := nameNode.Utf8Text(e.sourceCode)
+
+	startByte := outerNode.StartByte()
+	endByte := outerNode.EndByte()
+
+	chunk := &Chunk{
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.pythonDocstring(definition),
+		StartLine:  int(outerNode.StartPosition().Row) + 1,
+		EndLine:    int(outerNode.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   make(map[string]string),
+	}
+
+	switch definition.Kind() {
+	case "class_definition":
+		chunk.Type = ChunkTypeClass
+		if bases := definition.ChildByFieldName("superclasses"); bases != nil {
+			text := strings.TrimSuffix(strings.TrimPrefix(bases.Utf8Text(e.sourceCode), "("), ")")
+			if text = strings.TrimSpace(text); text != "" {
+				chunk.Metadata["bases"] = text
+			}
+		}
+	default:
+		chunk.Type = ChunkTypeFunction
+		chunk.Signature = e.pythonSignature(definition)
+		if className := e.pythonEnclosingClass(definition); className != "" {
+			chunk.Type = ChunkTypeMethod
+			chunk.Receiver = className
+		}
+		if first := definition.Child(0); first != nil && first.Kind() == "async" {
+			chunk.Metadata["async"] = "true"
+		}
+	}
+
+	if outerNode.Kind() == "decorated_definition" {
+		if decorators := e.pythonDecorators(outerNode); len(decorators) > 0 {
+			chunk.Metadata["decorators"] = strings.Join(decorators, ", ")
+		}
+	}
+
+	return chunk
+}
+
+// pythonSignature builds "(params) -> return" for a Python function definition
+func (e *Extractor) pythonSignature(definition *sitter.Node) string {
+	signature := ""
+	if params := definition.ChildByFieldName("parameters"); params != nil {
+		signature = params.Utf8Text(e.sourceCode)
+	}
+	if returnType := definition.ChildByFieldName("return_type"); returnType != nil {
+		signature += " -> " + returnType.Utf8Text(e.sourceCode)
+	}
+	return signature
+}
+
+// pythonDocstring returns the docstring of a Python function or class, if present
+func (e *Extractor) pythonDocstring(definition *sitter.Node) string {
+	body := definition.ChildByFieldName("body")
+	if body == nil || body.NamedChildCount() == 0 {
+		return ""
+	}
+
+	// A docstring is a bare string literal as the first statement of the body
+	first := body.NamedChild(0)
+	if first == nil || first.Kind() != "expression_statement" || first.NamedChildCount() == 0 {
+		return ""
+	}
+	literal := first.NamedChild(0)
+	if literal == nil || literal.Kind() != "string" {
+		return ""
+	}
+
+	return trimPythonString(literal.Utf8Text(e.sourceCode))
+}
+
+// pythonDecorators returns the decorator expressions (without "@") of a decorated definition
+func (e *Extractor) pythonDecorators(decorated *sitter.Node) []string {
+	var decorators []string
+	childCount := decorated.ChildCount()
+	for i := uint(0); i < childCount; i++ {
+		child := decorated.Child(i)
+		if child != nil && child.Kind() == "decorator" {
+			text := strings.TrimPrefix(child.Utf8Text(e.sourceCode), "@")
+			decorators = append(decorators, strings.TrimSpace(text))
+		}
+	}
+	return decorators
+}
+
+// pythonEnclosingClass returns the name of the class a definition is declared in.
+// Functions nested inside other functions are not methods, so the search stops there.
+func (e *Extractor) pythonEnclosingClass(definition *sitter.Node) string {
+	for parent := definition.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "class_definition":
+			if nameNode := parent.ChildByFieldName("name"); nameNode != nil {
+				return nameNode.Utf8Text(e.sourceCode)
+			}
+			return ""
+		case "function_definition":
+			return ""
+		}
+	}
+	return ""
+}
+
+// trimPythonString removes string prefixes and quotes from a Python string literal
+func trimPythonString(literal string) string {
+	literal = strings.TrimLeft(literal, "rRuUbBfF")
+	for _, quote := range []string{`"""`, `'''`, `"`, `'`} {
+		if len(literal) >= 2*len(quote) && strings.HasPrefix(literal, quote) && strings.HasSuffix(literal, quote) {
+			literal = literal[len(quote) : len(literal)-len(quote)]
+			break
+		}
+	}
+	return strings.TrimSpace(literal)
+}
@@ -0,0 +1,101 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractPython(t *testing.T) {
+	source := `import os
+
+
+def greet(name: str) -> str:
+    """Greet someone by name."""
+    return f"Hello, {name}!"
+
+
+class Person(Base):
+    """A person."""
+
+    def __init__(self, name):
+        self.name = name
+
+    @property
+    def display(self) -> str:
+        return self.name
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+`
+
+	p, err := NewParser(LanguagePython)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	byName := make(map[string]*Chunk)
+	for _, c := range chunks {
+		if _, exists := byName[c.Name]; exists {
+			t.Errorf("Chunk %s extracted more than once", c.Name)
+		}
+		byName[c.Name] = c
+	}
+
+	if len(chunks) != 5 {
+		t.Fatalf("Expected 5 chunks, got %d", len(chunks))
+	}
+
+	greet := byName["greet"]
+	if greet == nil || greet.Type != ChunkTypeFunction {
+		t.Fatalf("Expected function chunk for greet, got %+v", greet)
+	}
+	if greet.DocComment != "Greet someone by name." {
+		t.Errorf("Expected docstring for greet, got '%s'", greet.DocComment)
+	}
+	if greet.Signature != "(name: str) -> str" {
+		t.Errorf("Expected signature '(name: str) -> str', got '%s'", greet.Signature)
+	}
+	if greet.Metadata["language"] != "python" {
+		t.Errorf("Expected language 'python', got '%s'", greet.Metadata["language"])
+	}
+
+	person := byName["Person"]
+	if person == nil || person.Type != ChunkTypeClass {
+		t.Fatalf("Expected class chunk for Person, got %+v", person)
+	}
+	if person.DocComment != "A person." {
+		t.Errorf("Expected docstring for Person, got '%s'", person.DocComment)
+	}
+	if person.Metadata["bases"] != "Base" {
+		t.Errorf("Expected bases 'Base', got '%s'", person.Metadata["bases"])
+	}
+
+	ctor := byName["__init__"]
+	if ctor == nil || ctor.Type != ChunkTypeMethod || ctor.Receiver != "Person" {
+		t.Errorf("Expected __init__ to be a method of Person, got %+v", ctor)
+	}
+
+	display := byName["display"]
+	if display == nil || display.Type != ChunkTypeMethod {
+		t.Fatalf("Expected method chunk for display, got %+v", display)
+	}
+	if display.Metadata["decorators"] != "property" {
+		t.Errorf("Expected decorators 'property', got '%s'", display.Metadata["decorators"])
+	}
+	if display.StartLine != 15 {
+		t.Errorf("Expected decorated method to start at decorator line 15, got %d", display.StartLine)
+	}
+
+	point := byName["Point"]
+	if point == nil || point.Metadata["decorators"] != "dataclass" {
+		t.Errorf("Expected Point to carry the dataclass decorator, got %+v", point)
+	}
+}
@@ -46,6 +46,10 @@
   name: (type_identifier) @type_alias.name
   type: (_) @type_alias.type) @type_alias.definition
 
+; Macro definitions
+(macro_definition
+  name: (identifier) @macro.name) @macro.definition
+
 ; Module definitions
 (mod_item
   name: (identifier) @module.name
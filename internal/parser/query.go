@@ -0,0 +1,91 @@
+package parser
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// ExtractWithQuery extracts chunks using a Tree-sitter query instead of the
+// built-in per-language extraction, following the capture convention of the
+// bundled queries (queries/*.scm): each @<type>.definition capture becomes a
+// chunk of that type, named by the @<type>.name capture of the same match.
+func (e *Extractor) ExtractWithQuery(ctx context.Context, querySource string) ([]*Chunk, error) {
+	if e.parser.tsLanguage == nil {
+		return nil, fmt.Errorf("parser has no grammar to query")
+	}
+
+	query, queryErr := sitter.NewQuery(e.parser.tsLanguage, querySource)
+	if queryErr != nil {
+		return nil, fmt.Errorf("invalid query: %w", queryErr)
+	}
+	defer query.Close()
+
+	tree, err := e.parser.Parse(ctx, e.sourceCode)
+	if err != nil {
+		return nil, err
+	}
+	if tree == nil {
+		return nil, nil
+	}
+	defer tree.Close()
+
+	cursor := sitter.NewQueryCursor()
+	defer cursor.Close()
+
+	captureNames := query.CaptureNames()
+	seen := make(map[[2]uint]bool)
+
+	var chunks []*Chunk
+	matches := cursor.Matches(query, tree.RootNode(), e.sourceCode)
+	for match := matches.Next(); match != nil; match = matches.Next() {
+		var (
+			definition *sitter.Node
+			chunkType  string
+			name       string
+		)
+		for _, capture := range match.Captures {
+			captureName := captureNames[capture.Index]
+			kind, role, ok := strings.Cut(captureName, ".")
+			if !ok {
+				continue
+			}
+			switch role {
+			case "definition":
+				node := capture.Node
+				definition = &node
+				chunkType = kind
+			case "name":
+				name = capture.Node.Utf8Text(e.sourceCode)
+			}
+		}
+		if definition == nil || name == "" {
+			continue
+		}
+
+		// Several patterns may match the same node; keep the first
+		key := [2]uint{definition.StartByte(), definition.EndByte()}
+		if seen[key] {
+			continue
+		}
+		seen[key] = true
+
+		startByte := definition.StartByte()
+		endByte := definition.EndByte()
+		chunks = append(chunks, &Chunk{
+			Type:       ChunkType(chunkType),
+			Name:       name,
+			Content:    string(e.sourceCode[startByte:endByte]),
+			DocComment: e.findDocComment(definition),
+			StartLine:  int(definition.StartPosition().Row) + 1,
+			EndLine:    int(definition.EndPosition().Row) + 1,
+			StartByte:  int(startByte),
+			EndByte:    int(endByte),
+			Metadata:   make(map[string]string),
+		})
+	}
+
+	return chunks, nil
+}
@@ -0,0 +1,65 @@
+package parser
+
+import (
+	"context"
+	"os"
+	"testing"
+)
+
+func TestExtractWithQuery(t *testing.T) {
+	sourceCode := []byte(`package main
+
+// Greet says hello
+func Greet(name string) string {
+	return "Hello, " + name
+}
+
+type Greeter struct{}
+`)
+
+	p, err := NewParser(LanguageGo)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	query := `
+(function_declaration name: (identifier) @function.name) @function.definition
+(type_spec name: (type_identifier) @struct.name type: (struct_type)) @struct.definition
+`
+	chunks, err := NewExtractor(p, sourceCode).ExtractWithQuery(context.Background(), query)
+	if err != nil {
+		t.Fatalf("ExtractWithQuery failed: %v", err)
+	}
+	if len(chunks) != 2 {
+		t.Fatalf("Expected 2 chunks, got %d", len(chunks))
+	}
+
+	greet := chunks[0]
+	if greet.Type != ChunkTypeFunction || greet.Name != "Greet" || greet.StartLine != 4 || greet.EndLine != 6 {
+		t.Errorf("Unexpected function chunk: %+v", greet)
+	}
+	if greet.DocComment != "Greet says hello" {
+		t.Errorf("Expected doc comment 'Greet says hello', got %q", greet.DocComment)
+	}
+	if chunks[1].Type != ChunkTypeStruct || chunks[1].Name != "Greeter" {
+		t.Errorf("Unexpected struct chunk: %+v", chunks[1])
+	}
+}
+
+func TestExtractWithInvalidQuery(t *testing.T) {
+	p, err := NewParser(LanguageGo)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	if _, err := NewExtractor(p, []byte("package main")).ExtractWithQuery(context.Background(), "(not_a_node) @x"); err == nil {
+		t.Error("Expected an error for a query with an unknown node type")
+	}
+}
+
+func TestLoadGrammarMissingLibrary(t *testing.T) {
+	missing := t.TempDir() + string(os.PathSeparator) + "missing.so"
+	if _, err := LoadGrammar(missing, "tree_sitter_missing"); err == nil {
+		t.Error("Expected an error loading a grammar that does not exist")
+	}
+}
@@ -0,0 +1,142 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// extractRubyNode extracts Ruby modules, classes, methods, and DSL blocks.
+// Instance methods take the enclosing class or module as their receiver;
+// `def self.name` and methods inside `class << self` are marked as class methods.
+func (e *Extractor) extractRubyNode(node *sitter.Node) *Chunk {
+	var (
+		chunkType ChunkType
+		name      string
+		receiver  string
+		signature string
+	)
+
+	metadata := make(map[string]string)
+
+	switch node.Kind() {
+	case "class":
+		chunkType = ChunkTypeClass
+		name = e.fieldText(node, "name")
+		if superclass := e.fieldText(node, "superclass"); superclass != "" {
+			metadata["superclass"] = strings.TrimSpace(strings.TrimPrefix(superclass, "<"))
+		}
+	case "module":
+		chunkType = ChunkTypeModule
+		name = e.fieldText(node, "name")
+	case "method":
+		name = e.fieldText(node, "name")
+		receiver = e.rubyEnclosingScope(node)
+		if e.rubyInSingletonClass(node) {
+			metadata["class_method"] = "true"
+		}
+	case "singleton_method":
+		name = e.fieldText(node, "name")
+		receiver = e.fieldText(node, "object")
+		if receiver == "self" {
+			receiver = e.rubyEnclosingScope(node)
+			metadata["class_method"] = "true"
+		}
+	case "call":
+		if !e.isRubyDSLBlock(node) {
+			return nil
+		}
+		chunkType = ChunkTypeBlock
+		// The call without its block, e.g. Rails.application.routes.draw
+		block := node.ChildByFieldName("block")
+		name = strings.TrimSpace(string(e.sourceCode[node.StartByte():block.StartByte()]))
+	default:
+		return nil
+	}
+
+	if name == "" {
+		return nil
+	}
+
+	if chunkType == "" {
+		chunkType = ChunkTypeFunction
+		if receiver != "" {
+			chunkType = ChunkTypeMethod
+		}
+		signature = e.fieldText(node, "parameters")
+	}
+
+	startByte := node.StartByte()
+	endByte := node.EndByte()
+
+	return &Chunk{
+		Type:       chunkType,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(node),
+		Signature:  signature,
+		Receiver:   receiver,
+		StartLine:  int(node.StartPosition().Row) + 1,
+		EndLine:    int(node.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   metadata,
+	}
+}
+
+// isRubyDSLBlock reports whether a call is a statement-level DSL block such as
+// `Rails.application.routes.draw do` or `describe User do`. Iterator blocks
+// that take parameters (`items.each do |item|`) and calls inside method bodies
+// are not treated as DSL blocks.
+func (e *Extractor) isRubyDSLBlock(call *sitter.Node) bool {
+	block := call.ChildByFieldName("block")
+	if block == nil {
+		return false
+	}
+	if block.ChildByFieldName("parameters") != nil {
+		return false
+	}
+
+	parent := call.Parent()
+	if parent == nil {
+		return false
+	}
+	switch parent.Kind() {
+	case "program", "body_statement", "block_body":
+	default:
+		return false
+	}
+
+	for ancestor := parent; ancestor != nil; ancestor = ancestor.Parent() {
+		if ancestor.Kind() == "method" || ancestor.Kind() == "singleton_method" {
+			return false
+		}
+	}
+	return true
+}
+
+// rubyEnclosingScope returns the name of the class or module a method is defined in
+func (e *Extractor) rubyEnclosingScope(method *sitter.Node) string {
+	for parent := method.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "class", "module":
+			return e.fieldText(parent, "name")
+		case "method", "singleton_method":
+			return ""
+		}
+	}
+	return ""
+}
+
+// rubyInSingletonClass reports whether a method is defined inside `class << self`
+func (e *Extractor) rubyInSingletonClass(method *sitter.Node) bool {
+	for parent := method.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "singleton_class":
+			return true
+		case "class", "module":
+			return false
+		}
+	}
+	return false
+}
@@ -0,0 +1,74 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractRuby(t *testing.T) {
+	source := `# Billing helpers
+module Billing
+  # An invoice
+  class Invoice < Record
+    def total(tax = 0)
+      items.sum { |i| i.price } + tax
+    end
+
+    def self.open
+      where(status: :open)
+    end
+  end
+end
+
+Rails.application.routes.draw do
+  resources :invoices
+end
+
+[1, 2].each do |n|
+  puts n
+end
+`
+
+	p, err := NewParser(LanguageRuby)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	byName := make(map[string]*Chunk)
+	for _, c := range chunks {
+		byName[c.Name] = c
+	}
+
+	if len(chunks) != 5 {
+		t.Fatalf("Expected 5 chunks (Billing, Invoice, total, open, routes block), got %d", len(chunks))
+	}
+
+	if billing := byName["Billing"]; billing == nil || billing.Type != ChunkTypeModule || billing.DocComment != "Billing helpers" {
+		t.Errorf("Expected documented module Billing, got %+v", billing)
+	}
+
+	invoice := byName["Invoice"]
+	if invoice == nil || invoice.Type != ChunkTypeClass || invoice.Metadata["superclass"] != "Record" {
+		t.Errorf("Expected class Invoice < Record, got %+v", invoice)
+	}
+
+	total := byName["total"]
+	if total == nil || total.Type != ChunkTypeMethod || total.Receiver != "Invoice" || total.Signature != "(tax = 0)" {
+		t.Errorf("Expected instance method total of Invoice, got %+v", total)
+	}
+
+	open := byName["open"]
+	if open == nil || open.Receiver != "Invoice" || open.Metadata["class_method"] != "true" {
+		t.Errorf("Expected class method open of Invoice, got %+v", open)
+	}
+
+	routes := byName["Rails.application.routes.draw"]
+	if routes == nil || routes.Type != ChunkTypeBlock || routes.StartLine != 15 || routes.EndLine != 17 {
+		t.Errorf("Expected routes DSL block on lines 15-17, got %+v", routes)
+	}
+}
@@ -0,0 +1,230 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// rustMacroMinLines is how many lines an item-level macro invocation must span
+// to become a chunk of its own
+const rustMacroMinLines = 3
+
+// extractRustNode extracts Rust functions, structs, enums, unions, traits,
+// impl blocks, consts, statics, type aliases, macro_rules! definitions, and
+// large item-level macro invocations such as lazy_static! blocks. Functions
+// inside an impl block become methods of its type. Every chunk records the Rust keyword it
+// was declared with as its kind and its visibility (public, private, or a
+// restricted form such as pub(crate)), and its path within the file's
+// modules as its qualified name.
+func (e *Extractor) extractRustNode(node *sitter.Node) *Chunk {
+	var chunkType ChunkType
+	kind := ""
+
+	switch node.Kind() {
+	case "function_item":
+		chunkType, kind = ChunkTypeFunction, "fn"
+	case "struct_item":
+		chunkType, kind = ChunkTypeStruct, "struct"
+	case "union_item":
+		chunkType, kind = ChunkTypeStruct, "union"
+	case "enum_item":
+		chunkType, kind = ChunkTypeEnum, "enum"
+	case "trait_item":
+		chunkType, kind = ChunkTypeInterface, "trait"
+	case "impl_item":
+		chunkType, kind = ChunkTypeImpl, "impl"
+	case "const_item", "static_item":
+		// Locals belong to their function
+		if rustInFunction(node) {
+			return nil
+		}
+		chunkType, kind = ChunkTypeConst, strings.TrimSuffix(node.Kind(), "_item")
+	case "type_item":
+		if rustInFunction(node) {
+			return nil
+		}
+		chunkType, kind = ChunkTypeTypeAlias, "type"
+	case "macro_definition":
+		chunkType, kind = ChunkTypeMacro, "macro_rules"
+	case "macro_invocation":
+		lines := node.EndPosition().Row - node.StartPosition().Row + 1
+		if !rustIsItemLevel(node) || lines < rustMacroMinLines {
+			return nil
+		}
+		chunkType, kind = ChunkTypeMacro, "macro_invocation"
+	default:
+		return nil
+	}
+
+	name := e.fieldText(node, "name")
+	switch chunkType {
+	case ChunkTypeImpl:
+		name = e.fieldText(node, "type")
+	case ChunkTypeMacro:
+		if kind == "macro_invocation" {
+			// The invoked macro, e.g. lazy_static or thread_local
+			name = e.fieldText(node, "macro")
+		}
+	}
+	if name == "" {
+		return nil
+	}
+
+	startByte := node.StartByte()
+	endByte := node.EndByte()
+
+	chunk := &Chunk{
+		Type:      chunkType,
+		Name:      name,
+		Content:   string(e.sourceCode[startByte:endByte]),
+		StartLine: int(node.StartPosition().Row) + 1,
+		EndLine:   int(node.EndPosition().Row) + 1,
+		StartByte: int(startByte),
+		EndByte:   int(endByte),
+		Metadata:  map[string]string{"kind": kind},
+	}
+
+	// Trait methods and macros have no visibility modifier of their own
+	ownVisibility := true
+	switch chunkType {
+	case ChunkTypeImpl:
+		if trait := e.fieldText(node, "trait"); trait != "" {
+			chunk.Metadata["trait"] = trait
+		}
+	case ChunkTypeMacro:
+		ownVisibility = false
+		if kind == "macro_rules" {
+			// Macros are private to their crate unless exported
+			chunk.Metadata["visibility"] = "private"
+			for _, attribute := range e.findAttributes(node) {
+				if attribute == "#[macro_export]" {
+					chunk.Metadata["visibility"] = "public"
+				}
+			}
+		}
+	case ChunkTypeFunction:
+		chunk.Signature = e.rustSignature(node)
+		if impl := rustEnclosingImpl(node); impl != nil {
+			chunk.Type = ChunkTypeMethod
+			chunk.Receiver = e.fieldText(impl, "type")
+			if trait := e.fieldText(impl, "trait"); trait != "" {
+				chunk.Metadata["trait"] = trait
+				ownVisibility = false
+			}
+		}
+	}
+
+	if ownVisibility {
+		chunk.Metadata["visibility"] = e.rustVisibility(node)
+	}
+	chunk.Metadata["qualified_name"] = e.rustQualifiedName(node, chunk)
+	if e.rustInTestModule(node) {
+		chunk.Metadata["test"] = "true"
+	}
+	return chunk
+}
+
+// rustInTestModule reports whether an item is declared inside a
+// #[cfg(test)] mod block
+func (e *Extractor) rustInTestModule(node *sitter.Node) bool {
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		if parent.Kind() != "mod_item" {
+			continue
+		}
+		for _, attribute := range e.findAttributes(parent) {
+			if strings.ReplaceAll(attribute, " ", "") == "#[cfg(test)]" {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// rustQualifiedName prefixes a chunk's name with the mod blocks it is
+// declared in and, for methods, the type of their impl block, e.g.
+// utils::slugify or Container<T>::map
+func (e *Extractor) rustQualifiedName(node *sitter.Node, chunk *Chunk) string {
+	path := []string{chunk.Name}
+	if chunk.Receiver != "" {
+		path = append(path, chunk.Receiver)
+	}
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		if parent.Kind() != "mod_item" {
+			continue
+		}
+		if module := e.fieldText(parent, "name"); module != "" {
+			path = append(path, module)
+		}
+	}
+
+	// The path was collected innermost first
+	for i, j := 0, len(path)-1; i < j; i, j = i+1, j-1 {
+		path[i], path[j] = path[j], path[i]
+	}
+	return strings.Join(path, "::")
+}
+
+// rustVisibility returns "public" for pub items, "private" for items without a
+// visibility modifier, and the modifier itself for restricted forms
+func (e *Extractor) rustVisibility(node *sitter.Node) string {
+	childCount := node.ChildCount()
+	for i := uint(0); i < childCount; i++ {
+		child := node.Child(i)
+		if child == nil || child.Kind() != "visibility_modifier" {
+			continue
+		}
+		if modifier := child.Utf8Text(e.sourceCode); modifier != "pub" {
+			return modifier
+		}
+		return "public"
+	}
+	return "private"
+}
+
+// rustSignature returns a function's declaration up to its body, e.g.
+// "pub fn new(x: f64, y: f64) -> Self", including generics and where clauses
+func (e *Extractor) rustSignature(node *sitter.Node) string {
+	body := node.ChildByFieldName("body")
+	if body == nil {
+		return ""
+	}
+	signature := string(e.sourceCode[node.StartByte():body.StartByte()])
+	return strings.Join(strings.Fields(signature), " ")
+}
+
+// rustEnclosingImpl returns the impl block a function is declared in, if any
+func rustEnclosingImpl(node *sitter.Node) *sitter.Node {
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "impl_item":
+			return parent
+		case "function_item", "trait_item", "mod_item":
+			return nil
+		}
+	}
+	return nil
+}
+
+// rustIsItemLevel reports whether a node sits where items are declared: at the
+// top of a file or in a mod, impl, or trait body
+func rustIsItemLevel(node *sitter.Node) bool {
+	parent := node.Parent()
+	if parent != nil && parent.Kind() == "expression_statement" {
+		parent = parent.Parent()
+	}
+	return parent != nil && (parent.Kind() == "source_file" || parent.Kind() == "declaration_list")
+}
+
+// rustInFunction reports whether an item is declared inside a function body
+func rustInFunction(node *sitter.Node) bool {
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "function_item":
+			return true
+		case "impl_item", "trait_item", "mod_item":
+			return false
+		}
+	}
+	return false
+}
@@ -0,0 +1,247 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractRustSymbols(t *testing.T) {
+	source := `pub struct Point {
+    x: f64,
+}
+
+impl Point {
+    pub fn new(x: f64) -> Self {
+        Point { x }
+    }
+
+    fn scale<T: Into<f64>>(&self, factor: T)
+        -> f64 {
+        self.x * factor.into()
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.x)
+    }
+}
+
+pub(crate) const ORIGIN: Point = Point { x: 0.0 };
+
+type Pair = (Point, Point);
+
+pub trait Shape {}
+
+pub enum Status { Active }
+`
+
+	p, err := NewParser(LanguageRust)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	tests := []struct {
+		name       string
+		chunkType  ChunkType
+		kind       string
+		visibility string
+		signature  string
+		receiver   string
+	}{
+		{"Point", ChunkTypeStruct, "struct", "public", "", ""},
+		{"Point", ChunkTypeImpl, "impl", "private", "", ""},
+		{"new", ChunkTypeMethod, "fn", "public", "pub fn new(x: f64) -> Self", "Point"},
+		{"scale", ChunkTypeMethod, "fn", "private", "fn scale<T: Into<f64>>(&self, factor: T) -> f64", "Point"},
+		{"Point", ChunkTypeImpl, "impl", "private", "", ""},
+		{"fmt", ChunkTypeMethod, "fn", "", "fn fmt(&self, f: &mut Formatter) -> Result", "Point"},
+		{"ORIGIN", ChunkTypeConst, "const", "pub(crate)", "", ""},
+		{"Pair", ChunkTypeTypeAlias, "type", "private", "", ""},
+		{"Shape", ChunkTypeInterface, "trait", "public", "", ""},
+		{"Status", ChunkTypeEnum, "enum", "public", "", ""},
+	}
+
+	if len(chunks) != len(tests) {
+		t.Fatalf("Expected %d chunks, got %d", len(tests), len(chunks))
+	}
+
+	for i, tt := range tests {
+		chunk := chunks[i]
+		if chunk.Name != tt.name || chunk.Type != tt.chunkType {
+			t.Errorf("Chunk %d: expected %s %s, got %s %s", i, tt.chunkType, tt.name, chunk.Type, chunk.Name)
+		}
+		if chunk.Metadata["kind"] != tt.kind {
+			t.Errorf("%s: expected kind '%s', got '%s'", tt.name, tt.kind, chunk.Metadata["kind"])
+		}
+		if chunk.Metadata["visibility"] != tt.visibility {
+			t.Errorf("%s: expected visibility '%s', got '%s'", tt.name, tt.visibility, chunk.Metadata["visibility"])
+		}
+		if chunk.Signature != tt.signature {
+			t.Errorf("%s: expected signature '%s', got '%s'", tt.name, tt.signature, chunk.Signature)
+		}
+		if chunk.Receiver != tt.receiver {
+			t.Errorf("%s: expected receiver '%s', got '%s'", tt.name, tt.receiver, chunk.Receiver)
+		}
+	}
+
+	if qualified := chunks[3].Metadata["qualified_name"]; qualified != "Point::scale" {
+		t.Errorf("Expected qualified name 'Point::scale', got '%s'", qualified)
+	}
+	if trait := chunks[4].Metadata["trait"]; trait != "Display" {
+		t.Errorf("Expected trait impl of Display, got '%s'", trait)
+	}
+	if trait := chunks[5].Metadata["trait"]; trait != "Display" {
+		t.Errorf("Expected fmt to implement Display, got '%s'", trait)
+	}
+}
+
+func TestExtractRustQualifiedNames(t *testing.T) {
+	source := `pub mod utils {
+    pub fn slugify(s: &str) -> String {
+        s.to_lowercase()
+    }
+
+    mod inner {
+        pub struct Container<T> {
+            value: T,
+        }
+
+        impl<T> Container<T> {
+            pub fn map(self) -> T {
+                self.value
+            }
+        }
+    }
+}
+
+fn main() {}
+`
+
+	p, err := NewParser(LanguageRust)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	expected := []string{
+		"utils::slugify",
+		"utils::inner::Container",
+		"utils::inner::Container<T>",
+		"utils::inner::Container<T>::map",
+		"main",
+	}
+	if len(chunks) != len(expected) {
+		t.Fatalf("Expected %d chunks, got %d", len(expected), len(chunks))
+	}
+	for i, want := range expected {
+		if got := chunks[i].Metadata["qualified_name"]; got != want {
+			t.Errorf("Chunk %d (%s): expected qualified name '%s', got '%s'", i, chunks[i].Name, want, got)
+		}
+	}
+}
+
+func TestExtractRustTestModule(t *testing.T) {
+	source := `pub fn greet() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper() {}
+}
+`
+
+	p, err := NewParser(LanguageRust)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+	if len(chunks) != 2 {
+		t.Fatalf("Expected 2 chunks, got %d", len(chunks))
+	}
+
+	if chunks[0].Metadata["test"] != "" {
+		t.Errorf("Expected greet not to be a test, got '%s'", chunks[0].Metadata["test"])
+	}
+	if chunks[1].Metadata["test"] != "true" {
+		t.Errorf("Expected helper in the cfg(test) module to be a test, got '%s'", chunks[1].Metadata["test"])
+	}
+}
+
+func TestExtractRustMacros(t *testing.T) {
+	source := `/// Builds a map from pairs.
+#[macro_export]
+macro_rules! hashmap {
+    ($($k:expr => $v:expr),*) => {{
+        let mut map = HashMap::new();
+        $(map.insert($k, $v);)*
+        map
+    }};
+}
+
+lazy_static! {
+    static ref CONFIG: Config = Config::load();
+    static ref CACHE: Cache = Cache::new();
+}
+
+thread_local!(static DEPTH: Cell<u32> = Cell::new(0));
+
+fn main() {
+    println!(
+        "{}",
+        1
+    );
+}
+`
+
+	p, err := NewParser(LanguageRust)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	// The one-line thread_local! and the println! inside main are not chunks
+	if len(chunks) != 3 {
+		t.Fatalf("Expected 3 chunks, got %d", len(chunks))
+	}
+
+	hashmap := chunks[0]
+	if hashmap.Type != ChunkTypeMacro || hashmap.Name != "hashmap" || hashmap.Metadata["kind"] != "macro_rules" {
+		t.Errorf("Expected macro_rules hashmap, got %s %s (%s)", hashmap.Type, hashmap.Name, hashmap.Metadata["kind"])
+	}
+	if hashmap.Metadata["visibility"] != "public" {
+		t.Errorf("Expected exported macro to be public, got '%s'", hashmap.Metadata["visibility"])
+	}
+	if hashmap.DocComment != "Builds a map from pairs." {
+		t.Errorf("Expected macro doc comment, got '%s'", hashmap.DocComment)
+	}
+
+	lazy := chunks[1]
+	if lazy.Type != ChunkTypeMacro || lazy.Name != "lazy_static" || lazy.Metadata["kind"] != "macro_invocation" {
+		t.Errorf("Expected lazy_static invocation, got %s %s (%s)", lazy.Type, lazy.Name, lazy.Metadata["kind"])
+	}
+	if lazy.StartLine != 11 || lazy.EndLine != 14 {
+		t.Errorf("Expected lazy_static on lines 11-14, got %d-%d", lazy.StartLine, lazy.EndLine)
+	}
+
+	if chunks[2].Name != "main" {
+		t.Errorf("Expected main last, got '%s'", chunks[2].Name)
+	}
+}
@@ -0,0 +1,135 @@
+package parser
+
+import (
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// extractScalaNode extracts Scala classes, case classes, objects, traits,
+// enums, defs, and implicit/given definitions. Defs inside a class, object, or
+// trait take it as their receiver.
+func (e *Extractor) extractScalaNode(node *sitter.Node) *Chunk {
+	var chunkType ChunkType
+	name := e.fieldText(node, "name")
+	metadata := make(map[string]string)
+
+	switch node.Kind() {
+	case "class_definition":
+		chunkType = ChunkTypeClass
+	case "object_definition":
+		chunkType = ChunkTypeClass
+		metadata["kind"] = "object"
+	case "package_object":
+		chunkType = ChunkTypeModule
+		metadata["kind"] = "package object"
+	case "trait_definition":
+		chunkType = ChunkTypeInterface
+	case "enum_definition":
+		chunkType = ChunkTypeEnum
+	case "function_definition", "function_declaration":
+		chunkType = ChunkTypeFunction
+	case "given_definition":
+		chunkType = ChunkTypeFunction
+		metadata["kind"] = "given"
+		// Anonymous givens are named after the type they provide
+		if name == "" {
+			name = e.fieldText(node, "return_type")
+		}
+	case "val_definition", "var_definition":
+		// Plain vals are left to their enclosing chunk; implicit ones are
+		// looked up by type and are worth finding on their own
+		if !e.scalaHasModifier(node, "implicit") {
+			return nil
+		}
+		chunkType = ChunkTypeVar
+		name = e.fieldText(node, "pattern")
+	default:
+		return nil
+	}
+
+	if name == "" {
+		return nil
+	}
+
+	if e.scalaIsCase(node) {
+		metadata["case"] = "true"
+	}
+	if e.scalaHasModifier(node, "implicit") {
+		metadata["implicit"] = "true"
+	}
+
+	startByte := node.StartByte()
+	endByte := node.EndByte()
+
+	chunk := &Chunk{
+		Type:       chunkType,
+		Name:       name,
+		Content:    string(e.sourceCode[startByte:endByte]),
+		DocComment: e.findDocComment(node),
+		StartLine:  int(node.StartPosition().Row) + 1,
+		EndLine:    int(node.EndPosition().Row) + 1,
+		StartByte:  int(startByte),
+		EndByte:    int(endByte),
+		Metadata:   metadata,
+	}
+
+	if chunkType == ChunkTypeFunction {
+		chunk.Signature = e.fieldText(node, "parameters")
+		if returnType := e.fieldText(node, "return_type"); returnType != "" {
+			chunk.Signature = strings.TrimSpace(chunk.Signature + " " + returnType)
+		}
+		if receiver := e.scalaEnclosingType(node); receiver != "" {
+			chunk.Type = ChunkTypeMethod
+			chunk.Receiver = receiver
+		}
+	}
+
+	return chunk
+}
+
+// scalaHasModifier reports whether a definition carries a modifier such as implicit
+func (e *Extractor) scalaHasModifier(node *sitter.Node, modifier string) bool {
+	childCount := node.ChildCount()
+	for i := uint(0); i < childCount; i++ {
+		child := node.Child(i)
+		if child == nil {
+			continue
+		}
+		if child.Kind() == modifier {
+			return true
+		}
+		if child.Kind() == "modifiers" {
+			for _, word := range strings.Fields(child.Utf8Text(e.sourceCode)) {
+				if word == modifier {
+					return true
+				}
+			}
+		}
+	}
+	return false
+}
+
+// scalaIsCase reports whether a class or object is declared with `case`
+func (e *Extractor) scalaIsCase(node *sitter.Node) bool {
+	childCount := node.ChildCount()
+	for i := uint(0); i < childCount; i++ {
+		if child := node.Child(i); child != nil && child.Kind() == "case" {
+			return true
+		}
+	}
+	return false
+}
+
+// scalaEnclosingType returns the name of the class, object, or trait a def is declared in
+func (e *Extractor) scalaEnclosingType(node *sitter.Node) string {
+	for parent := node.Parent(); parent != nil; parent = parent.Parent() {
+		switch parent.Kind() {
+		case "class_definition", "object_definition", "trait_definition", "enum_definition":
+			return e.fieldText(parent, "name")
+		case "function_definition", "package_object":
+			return ""
+		}
+	}
+	return ""
+}
@@ -0,0 +1,78 @@
+package parser
+
+import (
+	"context"
+	"testing"
+)
+
+func TestExtractScala(t *testing.T) {
+	source := `/** A shape. */
+sealed trait Shape {
+  def area: Double
+}
+
+case class Circle(r: Double) extends Shape {
+  def area: Double = math.Pi * r * r
+}
+
+object Shapes {
+  implicit val ordering: Ordering[Circle] = Ordering.by(_.r)
+
+  implicit def toCircle(r: Double): Circle = Circle(r)
+}
+
+def describe(s: Shape): String = s.toString
+`
+
+	p, err := NewParser(LanguageScala)
+	if err != nil {
+		t.Fatalf("Failed to create parser: %v", err)
+	}
+
+	chunks, err := NewExtractor(p, []byte(source)).ExtractFunctions(context.Background())
+	if err != nil {
+		t.Fatalf("Failed to extract chunks: %v", err)
+	}
+
+	var names []string
+	byName := make(map[string]*Chunk)
+	for _, c := range chunks {
+		names = append(names, c.Name)
+		if _, exists := byName[c.Name]; !exists {
+			byName[c.Name] = c
+		}
+	}
+
+	if len(chunks) != 8 {
+		t.Fatalf("Expected 8 chunks, got %d: %v", len(chunks), names)
+	}
+
+	shape := byName["Shape"]
+	if shape == nil || shape.Type != ChunkTypeInterface || shape.DocComment != "A shape." {
+		t.Errorf("Expected documented trait Shape, got %+v", shape)
+	}
+
+	circle := byName["Circle"]
+	if circle == nil || circle.Type != ChunkTypeClass || circle.Metadata["case"] != "true" {
+		t.Errorf("Expected case class Circle, got %+v", circle)
+	}
+
+	if shapes := byName["Shapes"]; shapes == nil || shapes.Metadata["kind"] != "object" {
+		t.Errorf("Expected object Shapes, got %+v", shapes)
+	}
+
+	ordering := byName["ordering"]
+	if ordering == nil || ordering.Type != ChunkTypeVar || ordering.Metadata["implicit"] != "true" {
+		t.Errorf("Expected implicit val ordering, got %+v", ordering)
+	}
+
+	toCircle := byName["toCircle"]
+	if toCircle == nil || toCircle.Type != ChunkTypeMethod || toCircle.Receiver != "Shapes" || toCircle.Metadata["implicit"] != "true" {
+		t.Errorf("Expected implicit def toCircle in Shapes, got %+v", toCircle)
+	}
+
+	describe := byName["describe"]
+	if describe == nil || describe.Type != ChunkTypeFunction || describe.Receiver != "" {
+		t.Errorf("Expected top-level function describe, got %+v", describe)
+	}
+}
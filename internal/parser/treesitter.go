@@ -19,8 +19,9 @@ import (
 
 // Parser wraps Tree-sitter functionality for parsing source code
 type Parser struct {
-	parser   *sitter.Parser
-	language Language
+	parser     *sitter.Parser
+	language   Language
+	tsLanguage *sitter.Language
 }
 
 // NewParser creates a new parser configured for the specified language
@@ -61,8 +62,24 @@ func NewParser(lang Language) (*Parser, error) {
 	}
 
 	return &Parser{
-		parser:   parser,
-		language: lang,
+		parser:     parser,
+		language:   lang,
+		tsLanguage: tsLang,
+	}, nil
+}
+
+// NewGrammarParser creates a parser for a grammar loaded at runtime (see
+// LoadGrammar). Its Language is LanguageUnknown, so extraction needs a query.
+func NewGrammarParser(grammar *sitter.Language) (*Parser, error) {
+	parser := sitter.NewParser()
+	if err := parser.SetLanguage(grammar); err != nil {
+		return nil, fmt.Errorf("failed to set grammar: %w", err)
+	}
+
+	return &Parser{
+		parser:     parser,
+		language:   LanguageUnknown,
+		tsLanguage: grammar,
 	}, nil
 }
 
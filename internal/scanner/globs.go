@@ -0,0 +1,30 @@
+package scanner
+
+import (
+	"path"
+	"strings"
+)
+
+// matchAnyGlob reports whether a slash-separated path relative to the root
+// matches any of the globs
+func matchAnyGlob(globs []string, rel string) bool {
+	for _, glob := range globs {
+		if matchGlob(glob, rel) {
+			return true
+		}
+	}
+	return false
+}
+
+// matchGlob matches a path relative to the root against a glob such as
+// src/**, **/*.min.js, or vendor. A ** segment matches any number of
+// directories, and a glob without a slash matches a name at any depth.
+func matchGlob(glob, rel string) bool {
+	glob = strings.TrimSuffix(glob, "/")
+	parts := strings.Split(rel, "/")
+	if !strings.Contains(glob, "/") {
+		ok, _ := path.Match(glob, parts[len(parts)-1])
+		return ok
+	}
+	return matchSegments(strings.Split(strings.TrimPrefix(glob, "/"), "/"), parts)
+}
@@ -0,0 +1,63 @@
+package scanner
+
+import (
+	"os"
+	"testing"
+)
+
+func TestMatchGlob(t *testing.T) {
+	tests := []struct {
+		glob     string
+		rel      string
+		expected bool
+	}{
+		{"src/**", "src/main.go", true},
+		{"src/**", "src/pkg/util/util.go", true},
+		{"src/**", "lib/main.go", false},
+		{"vendor/**", "vendor", true},
+		{"**/*.min.js", "app.min.js", true},
+		{"**/*.min.js", "web/static/app.min.js", true},
+		{"**/*.min.js", "web/static/app.js", false},
+		{"*.md", "docs/guide.md", true},
+		{"docs/*.md", "docs/api/ref.md", false},
+		{"/cmd/*/main.go", "cmd/tool/main.go", true},
+		{"testdata/", "pkg/testdata", true},
+	}
+
+	for _, tt := range tests {
+		if got := matchGlob(tt.glob, tt.rel); got != tt.expected {
+			t.Errorf("matchGlob(%q, %q) = %v, expected %v", tt.glob, tt.rel, got, tt.expected)
+		}
+	}
+}
+
+func TestScanCodeFiles_IncludeExclude(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		"main.go":                  "package main",
+		"src/app.js":               "export {}",
+		"src/app.min.js":           "export {}",
+		"src/vendor/lib/lib.go":    "package lib",
+		"src/handlers/handler.go":  "package handlers",
+		"src/handlers/handler.md":  "# Handler",
+		"vendor/github.com/x/x.go": "package x",
+	})
+
+	got := scannedPaths(t, tmpDir, Options{
+		Include: []string{"src/**"},
+		Exclude: []string{"**/vendor/**", "**/*.min.js", "*.md"},
+	})
+	expected := []string{"src/app.js", "src/handlers/handler.go"}
+	if len(got) != len(expected) {
+		t.Fatalf("Expected %v, got %v", expected, got)
+	}
+	for i := range expected {
+		if got[i] != expected[i] {
+			t.Errorf("Expected %v, got %v", expected, got)
+			break
+		}
+	}
+}
@@ -0,0 +1,191 @@
+package scanner
+
+import (
+	"bufio"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/git"
+)
+
+// ignoreFileNames are the per-directory ignore files, in increasing order of
+// precedence
+var ignoreFileNames = []string{".gitignore", ".ignore"}
+
+// ignoreRule is one pattern line of an ignore file
+type ignoreRule struct {
+	segments []string // Pattern split at slashes
+	negate   bool     // The pattern started with ! and re-includes matches
+	dirOnly  bool     // The pattern ended with / and only matches directories
+	anchored bool     // The pattern is matched from base rather than at any depth
+	base     string   // Slash-separated directory of the ignore file, relative to the root
+}
+
+// ignoreMatcher applies the rules of the ignore files found during a scan
+type ignoreMatcher struct {
+	global []ignoreRule            // Global git excludes and .git/info/exclude
+	dirs   map[string][]ignoreRule // Rules of each directory's ignore files
+}
+
+// newIgnoreMatcher loads the ignore rules that apply to the whole of rootDir:
+// the global git excludes file and the repository's .git/info/exclude
+func newIgnoreMatcher(rootDir string) *ignoreMatcher {
+	m := &ignoreMatcher{dirs: make(map[string][]ignoreRule)}
+	m.global = append(m.global, readIgnoreFile(globalExcludesFile(rootDir), "")...)
+	m.global = append(m.global, readIgnoreFile(filepath.Join(rootDir, ".git", "info", "exclude"), "")...)
+	return m
+}
+
+// globalExcludesFile returns git's core.excludesFile, or its default location
+// when none is configured
+func globalExcludesFile(rootDir string) string {
+	if file := git.ExcludesFile(rootDir); file != "" {
+		return file
+	}
+	if config := os.Getenv("XDG_CONFIG_HOME"); config != "" {
+		return filepath.Join(config, "git", "ignore")
+	}
+	if home, err := os.UserHomeDir(); err == nil {
+		return filepath.Join(home, ".config", "git", "ignore")
+	}
+	return ""
+}
+
+// loadDir reads the ignore files of a directory, given relative to the root
+func (m *ignoreMatcher) loadDir(rootDir, rel string) {
+	var rules []ignoreRule
+	for _, name := range ignoreFileNames {
+		rules = append(rules, readIgnoreFile(filepath.Join(rootDir, filepath.FromSlash(rel), name), rel)...)
+	}
+	if len(rules) > 0 {
+		m.dirs[rel] = rules
+	}
+}
+
+// ignored reports whether a slash-separated path relative to the root is
+// excluded. Rules in deeper directories override those above them, and later
+// rules in a file override earlier ones.
+func (m *ignoreMatcher) ignored(rel string, isDir bool) bool {
+	ignored := matchRules(m.global, rel, isDir, false)
+
+	// Directories from the root down to the path's parent
+	dirs := []string{""}
+	parts := strings.Split(rel, "/")
+	for i := 1; i < len(parts); i++ {
+		dirs = append(dirs, strings.Join(parts[:i], "/"))
+	}
+	for _, dir := range dirs {
+		ignored = matchRules(m.dirs[dir], rel, isDir, ignored)
+	}
+	return ignored
+}
+
+// matchRules returns whether a path is ignored after applying rules, starting
+// from the result of the rules applied before them
+func matchRules(rules []ignoreRule, rel string, isDir, ignored bool) bool {
+	for _, rule := range rules {
+		if rule.matches(rel, isDir) {
+			ignored = !rule.negate
+		}
+	}
+	return ignored
+}
+
+// matches reports whether a rule matches a slash-separated path relative to
+// the root
+func (r ignoreRule) matches(rel string, isDir bool) bool {
+	if r.dirOnly && !isDir {
+		return false
+	}
+	if r.base != "" {
+		if !strings.HasPrefix(rel, r.base+"/") {
+			return false
+		}
+		rel = strings.TrimPrefix(rel, r.base+"/")
+	}
+
+	parts := strings.Split(rel, "/")
+	if !r.anchored {
+		// A pattern without a slash matches a name at any depth
+		ok, _ := path.Match(r.segments[0], parts[len(parts)-1])
+		return ok
+	}
+	return matchSegments(r.segments, parts)
+}
+
+// matchSegments matches path segments against pattern segments, where a **
+// segment matches any number of path segments
+func matchSegments(pattern, parts []string) bool {
+	if len(pattern) == 0 {
+		return len(parts) == 0
+	}
+	if pattern[0] == "**" {
+		for i := 0; i <= len(parts); i++ {
+			if matchSegments(pattern[1:], parts[i:]) {
+				return true
+			}
+		}
+		return false
+	}
+	if len(parts) == 0 {
+		return false
+	}
+	if ok, _ := path.Match(pattern[0], parts[0]); !ok {
+		return false
+	}
+	return matchSegments(pattern[1:], parts[1:])
+}
+
+// readIgnoreFile parses an ignore file whose patterns are relative to base,
+// returning no rules when the file does not exist
+func readIgnoreFile(file, base string) []ignoreRule {
+	if file == "" {
+		return nil
+	}
+	f, err := os.Open(file)
+	if err != nil {
+		return nil
+	}
+	defer f.Close()
+
+	var rules []ignoreRule
+	lines := bufio.NewScanner(f)
+	for lines.Scan() {
+		if rule, ok := parseIgnoreLine(lines.Text(), base); ok {
+			rules = append(rules, rule)
+		}
+	}
+	return rules
+}
+
+// parseIgnoreLine parses a line of an ignore file, skipping blank lines and
+// comments
+func parseIgnoreLine(line, base string) (ignoreRule, bool) {
+	line = strings.TrimRight(line, " \t\r")
+	if line == "" || strings.HasPrefix(line, "#") {
+		return ignoreRule{}, false
+	}
+
+	rule := ignoreRule{base: base}
+	if strings.HasPrefix(line, "!") {
+		rule.negate = true
+		line = line[1:]
+	} else if strings.HasPrefix(line, `\!`) || strings.HasPrefix(line, `\#`) {
+		line = line[1:]
+	}
+	if strings.HasSuffix(line, "/") {
+		rule.dirOnly = true
+		line = strings.TrimRight(line, "/")
+	}
+	if line == "" {
+		return ignoreRule{}, false
+	}
+
+	// A slash anywhere but the end anchors the pattern to the file's directory
+	rule.anchored = strings.Contains(line, "/")
+	line = strings.TrimPrefix(line, "/")
+	rule.segments = strings.Split(line, "/")
+	return rule, true
+}
@@ -0,0 +1,121 @@
+package scanner
+
+import (
+	"os"
+	"path/filepath"
+	"sort"
+	"testing"
+)
+
+func writeFiles(t *testing.T, dir string, files map[string]string) {
+	t.Helper()
+	for name, content := range files {
+		path := filepath.Join(dir, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+}
+
+func scannedPaths(t *testing.T, dir string, options Options) []string {
+	t.Helper()
+	results, err := NewWithOptions(dir, options).ScanCodeFiles()
+	if err != nil {
+		t.Fatalf("ScanCodeFiles failed: %v", err)
+	}
+	var paths []string
+	for _, result := range results {
+		rel, err := filepath.Rel(dir, result.Path)
+		if err != nil {
+			t.Fatal(err)
+		}
+		paths = append(paths, filepath.ToSlash(rel))
+	}
+	sort.Strings(paths)
+	return paths
+}
+
+func TestScanCodeFiles_RespectsIgnoreFiles(t *testing.T) {
+	// Keep the user's global excludes out of the test
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		".gitignore":                 "target/\nnode_modules\n*.log.md\n/build.go\n",
+		"main.go":                    "package main",
+		"build.go":                   "package main",
+		"notes.log.md":               "# Log",
+		"target/debug/out.go":        "package debug",
+		"web/node_modules/lib/a.js":  "export {}",
+		"web/app.js":                 "export {}",
+		"web/build.go":               "package web",
+		"sub/.ignore":                "*.py\n!keep.py\n",
+		"sub/drop.py":                "pass",
+		"sub/keep.py":                "pass",
+		"docs/.gitignore":            "generated/**/*.md\n",
+		"docs/guide.md":              "# Guide",
+		"docs/generated/api/ref.md":  "# Ref",
+		"docs/generated/api/keep.go": "package api",
+	})
+
+	expected := []string{
+		"docs/generated/api/keep.go",
+		"docs/guide.md",
+		"main.go",
+		"sub/keep.py",
+		"web/app.js",
+		"web/build.go",
+	}
+	got := scannedPaths(t, tmpDir, Options{})
+	if len(got) != len(expected) {
+		t.Fatalf("Expected %v, got %v", expected, got)
+	}
+	for i := range expected {
+		if got[i] != expected[i] {
+			t.Errorf("Expected %v, got %v", expected, got)
+			break
+		}
+	}
+
+	// NoIgnore scans everything but hidden files
+	if all := scannedPaths(t, tmpDir, Options{NoIgnore: true}); len(all) != 12 {
+		t.Errorf("Expected 12 files without ignore rules, got %v", all)
+	}
+}
+
+func TestParseIgnoreLine(t *testing.T) {
+	tests := []struct {
+		line     string
+		ok       bool
+		negate   bool
+		dirOnly  bool
+		anchored bool
+	}{
+		{"# comment", false, false, false, false},
+		{"   ", false, false, false, false},
+		{"*.o", true, false, false, false},
+		{"!keep.o", true, true, false, false},
+		{"vendor/", true, false, true, false},
+		{"/root.go", true, false, false, true},
+		{"a/b", true, false, false, true},
+		{`\#hash`, true, false, false, false},
+	}
+
+	for _, tt := range tests {
+		rule, ok := parseIgnoreLine(tt.line, "")
+		if ok != tt.ok {
+			t.Errorf("parseIgnoreLine(%q) ok = %v, expected %v", tt.line, ok, tt.ok)
+			continue
+		}
+		if !ok {
+			continue
+		}
+		if rule.negate != tt.negate || rule.dirOnly != tt.dirOnly || rule.anchored != tt.anchored {
+			t.Errorf("parseIgnoreLine(%q) = %+v", tt.line, rule)
+		}
+	}
+}
@@ -12,34 +12,151 @@ type FileInfo struct {
 	Path     string
 	Language string
 	ModTime  time.Time
+	Size     int64
+	Repo     string // Workspace repository the file belongs to, if any
+
+	Submodule string // Name of the git submodule the file is in, if any
+}
+
+// Options configures a Scanner
+type Options struct {
+	// NoIgnore scans files excluded by .gitignore, .ignore, and global git
+	// excludes, which are skipped by default
+	NoIgnore bool
+	// Include limits the scan to files matching at least one of these globs
+	// when any are given
+	Include []string
+	// Exclude skips files and directories matching any of these globs
+	Exclude []string
+	// Languages limits the scan to files in one of these languages when any
+	// are given
+	Languages []string
+	// ExcludeLanguages skips files in any of these languages
+	ExcludeLanguages []string
+	// Submodules sets how git submodules declared in the root's .gitmodules
+	// are scanned: SubmodulesInclude (the default), SubmodulesExclude, or
+	// SubmodulesShallow
+	Submodules string
+	// Symlinks sets which symbolic links are followed: SymlinksWithinRoot
+	// (the default), SymlinksFollow, or SymlinksSkip
+	Symlinks string
+	// Subtree, when set, returns the subtree settings of each directory
+	// scanned, given by its path, or nil when it has none
+	Subtree func(dir string) (*Subtree, error)
+	// OnSkip, when set, is called with each file or directory the scan
+	// leaves out and the reason why
+	OnSkip func(path, reason string)
+	// OnDir, when set, is called with each directory the scan walks, the
+	// root included
+	OnDir func(path string)
 }
 
 // Scanner scans directories for code files
 type Scanner struct {
 	rootDir string
+	options Options
 }
 
 // New creates a new Scanner
 func New(rootDir string) *Scanner {
-	return &Scanner{rootDir: rootDir}
+	return NewWithOptions(rootDir, Options{})
+}
+
+// NewWithOptions creates a new Scanner with custom options
+func NewWithOptions(rootDir string, options Options) *Scanner {
+	return &Scanner{rootDir: rootDir, options: options}
 }
 
 // languageExtensions maps file extensions to language names
 var languageExtensions = map[string]string{
 	// Code files
-	".py": "python",
-	".go": "go",
+	".py":      "python",
+	".go":      "go",
+	".js":      "javascript",
+	".jsx":     "javascript",
+	".mjs":     "javascript",
+	".cjs":     "javascript",
+	".ts":      "typescript",
+	".tsx":     "typescript",
+	".mts":     "typescript",
+	".cts":     "typescript",
+	".java":    "java",
+	".c":       "c",
+	".h":       "c",
+	".cpp":     "cpp",
+	".cc":      "cpp",
+	".cxx":     "cpp",
+	".hpp":     "cpp",
+	".hxx":     "cpp",
+	".cs":      "csharp",
+	".rb":      "ruby",
+	".php":     "php",
+	".kt":      "kotlin",
+	".kts":     "kotlin",
+	".swift":   "swift",
+	".scala":   "scala",
+	".sc":      "scala",
+	".zig":     "zig",
+	".hs":      "haskell",
+	".ex":      "elixir",
+	".exs":     "elixir",
+	".erl":     "erlang",
+	".hrl":     "erlang",
+	".lua":     "lua",
+	".sql":     "sql",
+	".sh":      "shell",
+	".bash":    "shell",
+	".zsh":     "shell",
+	".ipynb":   "notebook",
+	".yaml":    "yaml",
+	".yml":     "yaml",
+	".tf":      "terraform",
+	".hcl":     "terraform",
+	".proto":   "protobuf",
+	".graphql": "graphql",
+	".gql":     "graphql",
 	// Documentation files
 	".md":  "markdown",
 	".txt": "text",
 	".rst": "rst",
 }
 
+// RegisterExtension maps a file extension (including the dot) to a language,
+// so files of a language added at runtime are picked up by scans. It must be
+// called before scanning starts.
+func RegisterExtension(ext, language string) {
+	languageExtensions[ext] = language
+}
+
+// detectLanguage returns the language of a file from its name, recognizing
+// Dockerfiles and compose files by name before falling back to the extension
+func detectLanguage(name string) (string, bool) {
+	lower := strings.ToLower(name)
+	switch {
+	case lower == "dockerfile" || lower == "containerfile" ||
+		strings.HasPrefix(lower, "dockerfile.") || strings.HasSuffix(lower, ".dockerfile"):
+		return "dockerfile", true
+	case (strings.HasPrefix(lower, "docker-compose") || strings.HasPrefix(lower, "compose")) &&
+		(strings.HasSuffix(lower, ".yml") || strings.HasSuffix(lower, ".yaml")):
+		return "compose", true
+	}
+
+	lang, ok := languageExtensions[filepath.Ext(name)]
+	return lang, ok
+}
+
 // ScanCodeFiles recursively scans for code and documentation files
 func (s *Scanner) ScanCodeFiles() ([]FileInfo, error) {
 	var files []FileInfo
 
-	err := filepath.Walk(s.rootDir, func(path string, info os.FileInfo, err error) error {
+	var ignores *ignoreMatcher
+	if !s.options.NoIgnore {
+		ignores = newIgnoreMatcher(s.rootDir)
+	}
+	submodules := readSubmodules(s.rootDir)
+	trees := make(subtrees)
+
+	err := walk(s.rootDir, s.options.Symlinks, func(path string, info os.FileInfo, err error) error {
 		if err != nil {
 			return err
 		}
@@ -49,26 +166,63 @@ func (s *Scanner) ScanCodeFiles() ([]FileInfo, error) {
 			return filepath.SkipDir
 		}
 
-		// Skip hidden directories
-		if info.IsDir() && strings.HasPrefix(info.Name(), ".") {
-			return filepath.SkipDir
+		// Skip hidden files and directories
+		if strings.HasPrefix(info.Name(), ".") {
+			return s.skip(path, info, "hidden")
+		}
+
+		rel, err := filepath.Rel(s.rootDir, path)
+		if err != nil {
+			return err
+		}
+		rel = filepath.ToSlash(rel)
+		if rel == "." {
+			// The root's ignore files are keyed by the empty path
+			rel = ""
+		} else if reason := s.exclusion(rel, info.IsDir(), ignores, trees); reason != "" {
+			return s.skip(path, info, reason)
+		}
+
+		// Read the ignore files of every directory that is scanned
+		if ignores != nil && info.IsDir() {
+			ignores.loadDir(s.rootDir, rel)
+		}
+		if s.options.Subtree != nil && info.IsDir() {
+			tree, err := s.options.Subtree(path)
+			if err != nil {
+				return err
+			}
+			if tree != nil {
+				trees[rel] = tree
+			}
 		}
 
-		// Skip hidden files
-		if !info.IsDir() && strings.HasPrefix(info.Name(), ".") {
-			return nil
+		submodule := submoduleOf(submodules, rel)
+		if submodule != "" && info.IsDir() && s.options.Submodules == SubmodulesExclude {
+			return s.skip(path, info, "in an excluded submodule")
+		}
+		if info.IsDir() && s.options.OnDir != nil {
+			s.options.OnDir(path)
 		}
 
 		// Check for supported code and documentation files
 		if !info.IsDir() {
-			ext := filepath.Ext(info.Name())
-			if lang, ok := languageExtensions[ext]; ok {
-				files = append(files, FileInfo{
-					Path:     path,
-					Language: lang,
-					ModTime:  info.ModTime(),
-				})
+			lang, ok := detectLanguage(info.Name())
+			switch {
+			case !ok:
+				return s.skip(path, info, "unsupported file type")
+			case !s.languageSelected(rel, lang, trees):
+				return s.skip(path, info, "language "+lang+" not selected")
+			case submodule != "" && s.options.Submodules == SubmodulesShallow && !IsDocumentation(lang):
+				return s.skip(path, info, "code in a shallow submodule")
 			}
+			files = append(files, FileInfo{
+				Path:      path,
+				Language:  lang,
+				ModTime:   info.ModTime(),
+				Size:      info.Size(),
+				Submodule: submodule,
+			})
 		}
 
 		return nil
@@ -81,6 +235,37 @@ func (s *Scanner) ScanCodeFiles() ([]FileInfo, error) {
 	return files, nil
 }
 
+// exclusion returns why a slash-separated path relative to the root is left
+// out of the scan by ignore files, the include and exclude globs, or the
+// exclude globs of the subtrees above it, or "" when it is not. Include globs
+// only apply to files, so directories are still walked for files that match
+// them.
+func (s *Scanner) exclusion(rel string, isDir bool, ignores *ignoreMatcher, trees subtrees) string {
+	switch {
+	case ignores != nil && ignores.ignored(rel, isDir):
+		return "ignored"
+	case matchAnyGlob(s.options.Exclude, rel):
+		return "excluded by a glob"
+	case trees.excluded(rel):
+		return "excluded by a directory's settings"
+	case !isDir && len(s.options.Include) > 0 && !matchAnyGlob(s.options.Include, rel):
+		return "not included by a glob"
+	}
+	return ""
+}
+
+// skip reports a file or directory left out of the scan to OnSkip and
+// returns the walk function's result for it
+func (s *Scanner) skip(path string, info os.FileInfo, reason string) error {
+	if s.options.OnSkip != nil {
+		s.options.OnSkip(path, reason)
+	}
+	if info.IsDir() {
+		return filepath.SkipDir
+	}
+	return nil
+}
+
 // ScanPythonFiles recursively scans for Python files (deprecated: use ScanCodeFiles)
 func (s *Scanner) ScanPythonFiles() ([]FileInfo, error) {
 	return s.ScanCodeFiles()
@@ -3,6 +3,7 @@ package scanner
 import (
 	"os"
 	"path/filepath"
+	"strings"
 	"testing"
 )
 
@@ -128,9 +129,32 @@ func TestLanguageExtensions(t *testing.T) {
 		{".md", "markdown", true},
 		{".txt", "text", true},
 		{".rst", "rst", true},
-		{".java", "", false},
+		{".java", "java", true},
 		{".rs", "", false},
-		{".js", "", false},
+		{".js", "javascript", true},
+		{".tsx", "typescript", true},
+		{".h", "c", true},
+		{".hpp", "cpp", true},
+		{".cs", "csharp", true},
+		{".rb", "ruby", true},
+		{".php", "php", true},
+		{".kt", "kotlin", true},
+		{".swift", "swift", true},
+		{".scala", "scala", true},
+		{".zig", "zig", true},
+		{".hs", "haskell", true},
+		{".ex", "elixir", true},
+		{".erl", "erlang", true},
+		{".lua", "lua", true},
+		{".sql", "sql", true},
+		{".sh", "shell", true},
+		{".zsh", "shell", true},
+		{".ipynb", "notebook", true},
+		{".yaml", "yaml", true},
+		{".tf", "terraform", true},
+		{".proto", "protobuf", true},
+		{".graphql", "graphql", true},
+		{".gql", "graphql", true},
 	}
 
 	for _, tt := range tests {
@@ -143,3 +167,136 @@ func TestLanguageExtensions(t *testing.T) {
 		}
 	}
 }
+
+func TestDetectLanguage(t *testing.T) {
+	tests := []struct {
+		name     string
+		expected string
+		exists   bool
+	}{
+		{"main.go", "go", true},
+		{"Dockerfile", "dockerfile", true},
+		{"Dockerfile.dev", "dockerfile", true},
+		{"api.dockerfile", "dockerfile", true},
+		{"docker-compose.yml", "compose", true},
+		{"docker-compose.override.yaml", "compose", true},
+		{"compose.yaml", "compose", true},
+		{"config.yml", "yaml", true},
+		{"Makefile", "", false},
+	}
+
+	for _, tt := range tests {
+		lang, ok := detectLanguage(tt.name)
+		if ok != tt.exists {
+			t.Errorf("File %s: expected exists=%v, got %v", tt.name, tt.exists, ok)
+		}
+		if ok && lang != tt.expected {
+			t.Errorf("File %s: expected language %s, got %s", tt.name, tt.expected, lang)
+		}
+	}
+}
+
+func TestRegisterExtension(t *testing.T) {
+	RegisterExtension(".nim", "nim")
+	defer delete(languageExtensions, ".nim")
+
+	if lang, ok := detectLanguage("main.nim"); !ok || lang != "nim" {
+		t.Errorf("Expected registered extension .nim to map to nim, got %q (exists=%v)", lang, ok)
+	}
+}
+
+func TestScanCodeFiles_Languages(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		"main.go":     "package main",
+		"lib.rs":      "fn main() {}",
+		"app.py":      "x = 1",
+		"README.md":   "# Readme",
+		"web/app.tsx": "export {}",
+	})
+
+	tests := []struct {
+		name     string
+		options  Options
+		expected []string
+	}{
+		{"allowlist", Options{Languages: []string{"rust", "python"}}, []string{"app.py", "lib.rs"}},
+		{"denylist", Options{ExcludeLanguages: []string{"go", "typescript"}}, []string{"README.md", "app.py", "lib.rs"}},
+		{"both", Options{Languages: []string{"go", "python"}, ExcludeLanguages: []string{"python"}}, []string{"main.go"}},
+	}
+	for _, tt := range tests {
+		got := scannedPaths(t, tmpDir, tt.options)
+		if strings.Join(got, ",") != strings.Join(tt.expected, ",") {
+			t.Errorf("%s: expected %v, got %v", tt.name, tt.expected, got)
+		}
+	}
+}
+
+func TestScanCodeFiles_OnSkip(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		".gitignore":        "build/\n",
+		"main.go":           "package main",
+		"lib.rs":            "fn main() {}",
+		"image.png":         "",
+		"build/out.go":      "package out",
+		"vendor/dep.go":     "package dep",
+		".hidden/secret.go": "package secret",
+	})
+
+	skipped := make(map[string]string)
+	options := Options{Exclude: []string{"vendor"}, ExcludeLanguages: []string{"rust"}}
+	options.OnSkip = func(path, reason string) {
+		rel, _ := filepath.Rel(tmpDir, path)
+		skipped[filepath.ToSlash(rel)] = reason
+	}
+	if got := scannedPaths(t, tmpDir, options); strings.Join(got, ",") != "main.go" {
+		t.Fatalf("expected [main.go], got %v", got)
+	}
+
+	expected := map[string]string{
+		".gitignore": "hidden",
+		".hidden":    "hidden",
+		"build":      "ignored",
+		"vendor":     "excluded by a glob",
+		"lib.rs":     "language rust not selected",
+		"image.png":  "unsupported file type",
+	}
+	for path, reason := range expected {
+		if skipped[path] != reason {
+			t.Errorf("%s: expected reason %q, got %q", path, reason, skipped[path])
+		}
+	}
+}
+
+func TestScanCodeFiles_OnDir(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		".gitignore":      "build/\n",
+		"main.go":         "package main",
+		"lib/util.go":     "package lib",
+		"lib/empty/x.png": "",
+		"build/out.go":    "package out",
+	})
+
+	var walked []string
+	options := Options{OnDir: func(path string) {
+		rel, _ := filepath.Rel(tmpDir, path)
+		walked = append(walked, filepath.ToSlash(rel))
+	}}
+	scannedPaths(t, tmpDir, options)
+
+	// Directories without files to index are walked, ignored ones are not
+	if got := strings.Join(walked, ","); got != ".,lib,lib/empty" {
+		t.Fatalf("expected [. lib lib/empty], got %v", walked)
+	}
+}
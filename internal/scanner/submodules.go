@@ -0,0 +1,63 @@
+package scanner
+
+import (
+	"bufio"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+// Values of Options.Submodules
+const (
+	SubmodulesInclude = "include" // Scan submodules like the rest of the repository (the default)
+	SubmodulesExclude = "exclude" // Skip submodules
+	SubmodulesShallow = "shallow" // Scan only the documentation of submodules
+)
+
+// readSubmodules returns the submodules declared in the .gitmodules file of
+// rootDir, by slash-separated path relative to it
+func readSubmodules(rootDir string) map[string]string {
+	f, err := os.Open(filepath.Join(rootDir, ".gitmodules"))
+	if err != nil {
+		return nil
+	}
+	defer f.Close()
+
+	submodules := make(map[string]string)
+	name := ""
+	lines := bufio.NewScanner(f)
+	for lines.Scan() {
+		line := strings.TrimSpace(lines.Text())
+		if strings.HasPrefix(line, "[submodule ") {
+			name = strings.Trim(strings.TrimPrefix(line, "[submodule "), `"] `)
+			continue
+		}
+		key, value, ok := strings.Cut(line, "=")
+		if ok && name != "" && strings.TrimSpace(key) == "path" {
+			submodules[path.Clean(strings.TrimSpace(value))] = name
+		}
+	}
+	return submodules
+}
+
+// submoduleOf returns the name of the submodule containing a slash-separated
+// path relative to the root, or "" when it is not in one
+func submoduleOf(submodules map[string]string, rel string) string {
+	for dir := rel; dir != "." && dir != "/" && dir != ""; dir = path.Dir(dir) {
+		if name, ok := submodules[dir]; ok {
+			return name
+		}
+	}
+	return ""
+}
+
+// IsDocumentation reports whether a language is documentation rather than
+// code
+func IsDocumentation(language string) bool {
+	switch language {
+	case "markdown", "text", "rst":
+		return true
+	}
+	return false
+}
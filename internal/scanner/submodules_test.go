@@ -0,0 +1,72 @@
+package scanner
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestScanCodeFiles_Submodules(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		".gitmodules":                   "[submodule \"proto-defs\"]\n\tpath = third_party/protos\n\turl = https://example.com/protos.git\n",
+		"main.go":                       "package main",
+		"third_party/protos/README.md":  "# Protos",
+		"third_party/protos/gen/api.go": "package gen",
+		"third_party/other/lib.go":      "package other",
+	})
+
+	tests := []struct {
+		mode     string
+		expected []string
+	}{
+		{SubmodulesInclude, []string{"main.go", "third_party/other/lib.go", "third_party/protos/README.md", "third_party/protos/gen/api.go"}},
+		{SubmodulesExclude, []string{"main.go", "third_party/other/lib.go"}},
+		{SubmodulesShallow, []string{"main.go", "third_party/other/lib.go", "third_party/protos/README.md"}},
+	}
+	for _, tt := range tests {
+		got := scannedPaths(t, tmpDir, Options{Submodules: tt.mode})
+		if len(got) != len(tt.expected) {
+			t.Errorf("%s: expected %v, got %v", tt.mode, tt.expected, got)
+			continue
+		}
+		for i := range tt.expected {
+			if got[i] != tt.expected[i] {
+				t.Errorf("%s: expected %v, got %v", tt.mode, tt.expected, got)
+				break
+			}
+		}
+	}
+
+	files, err := New(tmpDir).ScanCodeFiles()
+	if err != nil {
+		t.Fatalf("ScanCodeFiles failed: %v", err)
+	}
+	for _, f := range files {
+		inSubmodule := f.Submodule == "proto-defs"
+		if expected := strings.Contains(filepath.ToSlash(f.Path), "third_party/protos/"); inSubmodule != expected {
+			t.Errorf("%s: unexpected submodule %q", f.Path, f.Submodule)
+		}
+	}
+}
+
+func TestReadSubmodules(t *testing.T) {
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		".gitmodules": "[submodule \"a\"]\n\tpath = libs/a/\n[submodule \"b\"]\n\turl = x\n\tpath=vendor/b\n",
+	})
+	submodules := readSubmodules(tmpDir)
+	if submodules["libs/a"] != "a" || submodules["vendor/b"] != "b" || len(submodules) != 2 {
+		t.Errorf("unexpected submodules: %v", submodules)
+	}
+	if got := submoduleOf(submodules, "vendor/b/src/x.go"); got != "b" {
+		t.Errorf("expected submodule b, got %q", got)
+	}
+	if got := submoduleOf(submodules, "vendor/bb/x.go"); got != "" {
+		t.Errorf("expected no submodule, got %q", got)
+	}
+}
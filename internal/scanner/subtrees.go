@@ -0,0 +1,74 @@
+package scanner
+
+import (
+	"path"
+	"slices"
+	"strings"
+)
+
+// Subtree narrows the files scanned in a directory and everything below it,
+// the way settings files in subdirectories of a monorepo do
+type Subtree struct {
+	// Exclude skips files and directories matching any of these globs, given
+	// relative to the subtree's directory. They add to the globs that apply
+	// above it.
+	Exclude []string
+	// Languages and ExcludeLanguages replace the language filters that apply
+	// above the subtree when they are not nil
+	Languages        []string
+	ExcludeLanguages []string
+}
+
+// subtrees holds the subtrees of the directories scanned so far, by their
+// slash-separated path relative to the root
+type subtrees map[string]*Subtree
+
+// ancestors returns the directories above a slash-separated path relative to
+// the root that have subtrees, nearest first
+func (t subtrees) ancestors(rel string) []string {
+	var dirs []string
+	for dir := rel; dir != ""; {
+		dir = path.Dir(dir)
+		if dir == "." {
+			dir = ""
+		}
+		if t[dir] != nil {
+			dirs = append(dirs, dir)
+		}
+	}
+	return dirs
+}
+
+// excluded reports whether an exclude glob of a subtree above a path matches it
+func (t subtrees) excluded(rel string) bool {
+	for _, dir := range t.ancestors(rel) {
+		sub := rel
+		if dir != "" {
+			sub = strings.TrimPrefix(rel, dir+"/")
+		}
+		if matchAnyGlob(t[dir].Exclude, sub) {
+			return true
+		}
+	}
+	return false
+}
+
+// languageSelected reports whether files in a language are scanned at a path,
+// under the language filters of the nearest subtrees that set them and
+// otherwise the scanner's options
+func (s *Scanner) languageSelected(rel, language string, trees subtrees) bool {
+	languages, excluded := s.options.Languages, s.options.ExcludeLanguages
+	languagesSet, excludedSet := false, false
+	for _, dir := range trees.ancestors(rel) {
+		if tree := trees[dir]; !languagesSet && tree.Languages != nil {
+			languages, languagesSet = tree.Languages, true
+		}
+		if tree := trees[dir]; !excludedSet && tree.ExcludeLanguages != nil {
+			excluded, excludedSet = tree.ExcludeLanguages, true
+		}
+	}
+	if slices.Contains(excluded, language) {
+		return false
+	}
+	return len(languages) == 0 || slices.Contains(languages, language)
+}
@@ -0,0 +1,46 @@
+package scanner
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestScanCodeFiles_Subtrees(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		"main.go":                 "package main",
+		"app.py":                  "x = 1",
+		"services/api/main.go":    "package main",
+		"services/api/gen/x.go":   "package gen",
+		"services/api/tool.py":    "x = 1",
+		"services/api/v2/main.go": "package main",
+		"services/api/v2/tool.py": "x = 1",
+		"services/web/app.py":     "x = 1",
+		"services/web/gen/x.py":   "x = 1",
+	})
+
+	trees := map[string]*Subtree{
+		"services/api":    {Exclude: []string{"gen"}, Languages: []string{"go"}},
+		"services/api/v2": {Languages: []string{}},
+		"services/web":    {Exclude: []string{"gen/**"}},
+	}
+	options := Options{ExcludeLanguages: []string{"rust"}}
+	options.Subtree = func(dir string) (*Subtree, error) {
+		rel, err := filepath.Rel(tmpDir, dir)
+		if err != nil {
+			return nil, err
+		}
+		return trees[filepath.ToSlash(rel)], nil
+	}
+
+	got := scannedPaths(t, tmpDir, options)
+	expected := []string{"app.py", "main.go", "services/api/main.go", "services/api/v2/main.go", "services/api/v2/tool.py", "services/web/app.py"}
+	if strings.Join(got, ",") != strings.Join(expected, ",") {
+		t.Errorf("expected %v, got %v", expected, got)
+	}
+}
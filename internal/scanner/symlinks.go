@@ -0,0 +1,122 @@
+package scanner
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// Values of Options.Symlinks
+const (
+	SymlinksWithinRoot = "within-root" // Follow links whose targets are inside the root (the default)
+	SymlinksFollow     = "follow"      // Follow every link, skipping links back into a directory being walked
+	SymlinksSkip       = "skip"        // Skip links to files and directories alike
+)
+
+// walker walks a directory tree like filepath.Walk, following symbolic links
+// as its policy allows. Files reached through a link are reported under the
+// link's path. Each directory is walked once, under the first path that
+// reaches it, so a directory that is also linked is not reported twice.
+type walker struct {
+	policy   string
+	realRoot string          // Root with its symbolic links resolved
+	walked   map[string]bool // Resolved directories already walked, directly or through a link
+	fn       filepath.WalkFunc
+}
+
+// walk calls fn for root and everything under it, in lexical order
+func walk(root, policy string, fn filepath.WalkFunc) error {
+	// The root itself is always followed
+	info, err := os.Stat(root)
+	if err != nil {
+		return fn(root, nil, err)
+	}
+	realRoot, err := filepath.EvalSymlinks(root)
+	if err != nil {
+		realRoot = root
+	}
+	w := &walker{policy: policy, realRoot: realRoot, walked: make(map[string]bool), fn: fn}
+	err = w.walk(root, realRoot, realRoot, info)
+	if err == filepath.SkipDir || err == filepath.SkipAll {
+		return nil
+	}
+	return err
+}
+
+// walk visits path, whose parent directory resolves to parentReal and which
+// itself resolves to real when it is not a link
+func (w *walker) walk(path, parentReal, real string, info os.FileInfo) error {
+	if info.Mode()&os.ModeSymlink != 0 {
+		target, targetInfo, ok := w.resolve(path, parentReal)
+		if !ok {
+			return nil
+		}
+		real, info = target, targetInfo
+	}
+
+	if !info.IsDir() {
+		return w.fn(path, info, nil)
+	}
+	if w.walked[real] {
+		return nil
+	}
+	if err := w.fn(path, info, nil); err != nil {
+		if err == filepath.SkipDir {
+			return nil
+		}
+		return err
+	}
+	w.walked[real] = true
+
+	entries, err := os.ReadDir(path)
+	if err != nil {
+		return w.fn(path, info, err)
+	}
+	for _, entry := range entries {
+		child := filepath.Join(path, entry.Name())
+		childInfo, err := entry.Info()
+		if err != nil {
+			if err := w.fn(child, nil, err); err != nil && err != filepath.SkipDir {
+				return err
+			}
+			continue
+		}
+		if err := w.walk(child, real, filepath.Join(real, entry.Name()), childInfo); err != nil {
+			if err == filepath.SkipDir {
+				// A file skipping its directory skips the rest of it
+				return nil
+			}
+			return err
+		}
+	}
+	return nil
+}
+
+// resolve returns the target of a symbolic link in the directory resolving to
+// parentReal, and whether the policy follows it
+func (w *walker) resolve(path, parentReal string) (string, os.FileInfo, bool) {
+	if w.policy == SymlinksSkip {
+		return "", nil, false
+	}
+	target, err := filepath.EvalSymlinks(path)
+	if err != nil {
+		return "", nil, false // Broken link
+	}
+	info, err := os.Stat(target)
+	if err != nil {
+		return "", nil, false
+	}
+	if w.policy != SymlinksFollow && !within(target, w.realRoot) {
+		return "", nil, false
+	}
+	// A link to a directory being walked would loop forever
+	if info.IsDir() && within(parentReal, target) {
+		return "", nil, false
+	}
+	return target, info, true
+}
+
+// within reports whether path is dir or inside it
+func within(path, dir string) bool {
+	return path == dir || strings.HasPrefix(path, strings.TrimSuffix(dir, string(filepath.Separator))+string(filepath.Separator))
+}
@@ -0,0 +1,74 @@
+package scanner
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestScanCodeFiles_Symlinks(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	outside := t.TempDir()
+	writeFiles(t, outside, map[string]string{"shared/util.go": "package shared"})
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{
+		"main.go":     "package main",
+		"lib/core.go": "package lib",
+	})
+	links := map[string]string{
+		"linked":    filepath.Join(tmpDir, "lib"),              // Inside the root
+		"external":  filepath.Join(outside, "shared"),          // Outside the root
+		"lib/loop":  filepath.Join(tmpDir, "lib"),              // Back into a directory being walked
+		"broken.go": filepath.Join(tmpDir, "missing", "it.go"), // Dangling
+	}
+	for name, target := range links {
+		if err := os.Symlink(target, filepath.Join(tmpDir, filepath.FromSlash(name))); err != nil {
+			t.Skipf("symbolic links unavailable: %v", err)
+		}
+	}
+
+	tests := []struct {
+		policy   string
+		expected []string
+	}{
+		// lib is walked before the link to it, which is not walked again
+		{SymlinksWithinRoot, []string{"lib/core.go", "main.go"}},
+		{SymlinksFollow, []string{"external/util.go", "lib/core.go", "main.go"}},
+		{SymlinksSkip, []string{"lib/core.go", "main.go"}},
+	}
+	for _, tt := range tests {
+		got := scannedPaths(t, tmpDir, Options{Symlinks: tt.policy})
+		if len(got) != len(tt.expected) {
+			t.Errorf("%s: expected %v, got %v", tt.policy, tt.expected, got)
+			continue
+		}
+		for i := range tt.expected {
+			if got[i] != tt.expected[i] {
+				t.Errorf("%s: expected %v, got %v", tt.policy, tt.expected, got)
+				break
+			}
+		}
+	}
+}
+
+func TestScanCodeFiles_SymlinkToSibling(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+
+	tmpDir := t.TempDir()
+	writeFiles(t, tmpDir, map[string]string{"zlib/core.go": "package zlib"})
+	// The link sorts before the directory it points to
+	if err := os.Symlink(filepath.Join(tmpDir, "zlib"), filepath.Join(tmpDir, "alias")); err != nil {
+		t.Skipf("symbolic links unavailable: %v", err)
+	}
+
+	for _, policy := range []string{SymlinksWithinRoot, SymlinksFollow} {
+		got := scannedPaths(t, tmpDir, Options{Symlinks: policy})
+		if len(got) != 1 {
+			t.Errorf("%s: expected core.go once, got %v", policy, got)
+		}
+	}
+}
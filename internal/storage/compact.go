@@ -0,0 +1,84 @@
+package storage
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// compactDirName is the directory in the index directory that holds the
+// rewritten chunks while the index is compacted
+const compactDirName = "compact"
+
+// Compact rewrites the chunks of the index into a new table, in order of file
+// and line, and swaps it in for the old one. Every update of a table leaves
+// the files of the rows it replaced behind and adds small files of its own;
+// the rewritten table holds only the live chunks, in a few large files, with
+// the chunks of a file next to each other. Chunks keep their embeddings. The
+// index must be locked.
+func (s *LanceDBStore) Compact() error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+
+	stagingDir := filepath.Join(s.dbDir, compactDirName)
+	if err := os.RemoveAll(stagingDir); err != nil {
+		return fmt.Errorf("failed to clear the staging index: %w", err)
+	}
+	staging, err := openDBDir(stagingDir)
+	if err != nil {
+		return err
+	}
+	defer os.RemoveAll(stagingDir)
+	defer staging.Close()
+	staging.aead, staging.keyFingerprint = s.aead, s.keyFingerprint
+	staging.dimension = s.VectorWidth()
+
+	if err := s.ensureTable(); err != nil {
+		return err
+	}
+	rows, err := s.table.SelectWithFilter(context.Background(), "chunk_id IS NOT NULL")
+	if err != nil {
+		return fmt.Errorf("failed to read chunks: %w", err)
+	}
+	if err := s.decodeRows(rows); err != nil {
+		return err
+	}
+
+	chunks := make([]chunker.Chunk, len(rows))
+	vectors := make([][]float64, len(rows))
+	for i, row := range rows {
+		chunks[i], vectors[i] = chunkFromRow(row)
+	}
+	order := make([]int, len(chunks))
+	for i := range order {
+		order[i] = i
+	}
+	sort.SliceStable(order, func(i, j int) bool {
+		a, b := chunks[order[i]], chunks[order[j]]
+		if a.FilePath != b.FilePath {
+			return a.FilePath < b.FilePath
+		}
+		return a.LineStart < b.LineStart
+	})
+
+	for start := 0; start < len(order); start += relocateBatch {
+		end := min(start+relocateBatch, len(order))
+		batch := make([]chunker.Chunk, 0, end-start)
+		batchVectors := make([][]float64, 0, end-start)
+		for _, i := range order[start:end] {
+			batch = append(batch, chunks[i])
+			batchVectors = append(batchVectors, vectors[i])
+		}
+		if err := staging.addChunks(batch, batchVectors); err != nil {
+			return err
+		}
+	}
+
+	// The staged store has no metadata, so the index keeps its own
+	return s.replaceWith(staging)
+}
@@ -0,0 +1,81 @@
+package storage
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// searchedIDs returns the IDs of the chunks a search of store for query finds,
+// nearest first
+func searchedIDs(t *testing.T, store *LanceDBStore, query []float64) []string {
+	t.Helper()
+	results, err := store.Search(query, 10, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	var ids []string
+	for _, result := range results {
+		id, _ := result["chunk_id"].(string)
+		ids = append(ids, id)
+	}
+	return ids
+}
+
+func TestCompactKeepsChunksAndResults(t *testing.T) {
+	store := newTestStore(t)
+	// Batches of incremental runs scatter a file's chunks and leave deleted ones
+	batches := []struct {
+		chunks  []chunker.Chunk
+		vectors [][]float64
+	}{
+		{[]chunker.Chunk{testChunk("a1", "a.go", "ha1"), testChunk("c", "c.go", "hc")}, [][]float64{{1, 0, 0, 0}, {0, 0, 1, 0}}},
+		{[]chunker.Chunk{testChunk("b", "b.go", "hb")}, [][]float64{{0, 1, 0, 0}}},
+		{[]chunker.Chunk{testChunk("a2", "a.go", "ha2")}, [][]float64{{0.9, 0.1, 0, 0}}},
+	}
+	for _, batch := range batches {
+		if err := store.StoreChunks(batch.chunks, batch.vectors); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if err := store.DeleteChunksByFilePath([]string{"c.go"}); err != nil {
+		t.Fatal(err)
+	}
+	before, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	query := []float64{1, 0.2, 0, 0}
+	searched := searchedIDs(t, store, query)
+
+	if err := store.Compact(); err != nil {
+		t.Fatalf("Compact() error = %v", err)
+	}
+	if err := store.OpenTable(); err != nil {
+		t.Fatal(err)
+	}
+
+	after, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	vectors := make(map[string][]float64, len(after))
+	for _, c := range after {
+		vectors[c.Chunk.ID] = c.Vector
+	}
+	if len(after) != len(before) {
+		t.Errorf("compacted index holds %d chunks, want the %d live ones", len(after), len(before))
+	}
+	for _, c := range before {
+		vector, ok := vectors[c.Chunk.ID]
+		if !ok {
+			t.Errorf("chunk %s of %s was lost", c.Chunk.ID, c.Chunk.FilePath)
+		} else if !slices.Equal(vector, c.Vector) {
+			t.Errorf("vector of chunk %s = %v, want %v kept", c.Chunk.ID, vector, c.Vector)
+		}
+	}
+	if got := searchedIDs(t, store, query); !slices.Equal(got, searched) {
+		t.Errorf("search of the compacted index found %v, want %v", got, searched)
+	}
+}
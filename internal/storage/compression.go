@@ -0,0 +1,119 @@
+package storage
+
+import (
+	"encoding/base64"
+	"fmt"
+
+	"github.com/klauspost/compress/zstd"
+)
+
+// Chunk text is stored zstd-compressed; source text typically shrinks to a
+// fifth of its size. The encoder and decoder are safe for concurrent use.
+var (
+	textEncoder, _ = zstd.NewWriter(nil, zstd.WithEncoderLevel(zstd.SpeedBetterCompression))
+	textDecoder, _ = zstd.NewReader(nil)
+)
+
+// encodedColumns hold chunk text compressed, and encrypted when the store has
+// a key. They contain source text verbatim.
+var encodedColumns = []string{"code", "doc_comment", "doc_summary", "signature"}
+
+// sealedColumns hold names and other text taken from the source, which are
+// encrypted when the store has a key, as base64 so the columns stay strings.
+// Searches cannot filter on them then; without a key they are stored as they
+// are.
+var sealedColumns = []string{"name", "qualified_name", "attributes", "receiver", "calls", "heading", "parent_heading"}
+
+// encodeText compresses, and with a key encrypts, chunk text for storage.
+// Empty text is stored empty.
+func (s *LanceDBStore) encodeText(text string) []byte {
+	if text == "" {
+		return nil
+	}
+	data := textEncoder.EncodeAll([]byte(text), nil)
+	if s.aead != nil {
+		data = s.seal(data)
+	}
+	return data
+}
+
+// sealString encrypts text from the source for a sealed column when the store
+// has a key. Empty text is stored empty.
+func (s *LanceDBStore) sealString(text string) string {
+	if s.aead == nil || text == "" {
+		return text
+	}
+	return base64.StdEncoding.EncodeToString(s.seal([]byte(text)))
+}
+
+// openString restores text sealed by sealString
+func (s *LanceDBStore) openString(text string) (string, error) {
+	if s.aead == nil || text == "" {
+		return text, nil
+	}
+	data, err := base64.StdEncoding.DecodeString(text)
+	if err != nil {
+		return "", fmt.Errorf("failed to decrypt chunk text: %w", err)
+	}
+	plain, err := s.open(data)
+	if err != nil {
+		return "", err
+	}
+	return string(plain), nil
+}
+
+// decodeText restores chunk text encoded by encodeText
+func (s *LanceDBStore) decodeText(data []byte) (string, error) {
+	if len(data) == 0 {
+		return "", nil
+	}
+	if s.aead != nil {
+		var err error
+		if data, err = s.open(data); err != nil {
+			return "", err
+		}
+	}
+	text, err := textDecoder.DecodeAll(data, nil)
+	if err != nil {
+		return "", fmt.Errorf("failed to decompress chunk text: %w", err)
+	}
+	return string(text), nil
+}
+
+// decodeRows replaces the encoded text of rows read from the table with the
+// text itself, so callers see chunks as they were stored
+func (s *LanceDBStore) decodeRows(rows []map[string]interface{}) error {
+	for _, row := range rows {
+		if err := s.decodeRow(row); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// decodeRow replaces the encoded text of one row with the text itself
+func (s *LanceDBStore) decodeRow(row map[string]interface{}) error {
+	for _, column := range encodedColumns {
+		data, ok := row[column].([]byte)
+		if !ok {
+			continue
+		}
+		text, err := s.decodeText(data)
+		if err != nil {
+			return err
+		}
+		row[column] = text
+	}
+	for _, column := range sealedColumns {
+		sealed, ok := row[column].(string)
+		if !ok {
+			continue
+		}
+		text, err := s.openString(sealed)
+		if err != nil {
+			return err
+		}
+		row[column] = text
+	}
+	return nil
+}
@@ -0,0 +1,276 @@
+package storage
+
+import (
+	"context"
+	"fmt"
+	"strconv"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// hashesPerQuery caps the content hashes looked up in one filter expression
+const hashesPerQuery = 500
+
+// location is one place a chunk's code appears
+type location struct {
+	path       string
+	start, end int
+}
+
+func (l location) String() string {
+	return fmt.Sprintf("%s:%d-%d", l.path, l.start, l.end)
+}
+
+// parseLocations reads a duplicate_locations value, one path:start-end per
+// line
+func parseLocations(value string) []location {
+	var locations []location
+	for _, line := range strings.Split(value, "\n") {
+		colon := strings.LastIndex(line, ":")
+		if colon < 0 {
+			continue
+		}
+		startText, endText, _ := strings.Cut(line[colon+1:], "-")
+		start, err := strconv.Atoi(startText)
+		if err != nil {
+			continue
+		}
+		end, err := strconv.Atoi(endText)
+		if err != nil {
+			continue
+		}
+		locations = append(locations, location{path: line[:colon], start: start, end: end})
+	}
+	return locations
+}
+
+// formatLocations writes a duplicate_locations value
+func formatLocations(locations []location) string {
+	lines := make([]string, len(locations))
+	for i, l := range locations {
+		lines[i] = l.String()
+	}
+	return strings.Join(lines, "\n")
+}
+
+// dedupKey identifies chunks with the same embedded text and model, which
+// share a vector; chunks without a content hash are never merged
+func dedupKey(chunk chunker.Chunk) string {
+	hash := chunk.Metadata["content_hash"]
+	if hash == "" {
+		return ""
+	}
+	key := chunk.EmbeddingType + ":" + hash
+	// A fallback model's vector of the text is not the configured model's
+	if model := chunk.Metadata["embedding_model"]; model != "" {
+		key += ":" + model
+	}
+	return key
+}
+
+// addDuplicate records another chunk with the same code, and the copies
+// recorded on it, as copies of chunk
+func addDuplicate(chunk *chunker.Chunk, duplicate chunker.Chunk) {
+	locations := parseLocations(chunk.Metadata["duplicate_locations"])
+	locations = append(locations, location{path: duplicate.FilePath, start: duplicate.LineStart, end: duplicate.LineEnd})
+	locations = append(locations, parseLocations(duplicate.Metadata["duplicate_locations"])...)
+	chunk.Metadata["duplicate_locations"] = formatLocations(locations)
+}
+
+// removeLocations drops the copies of a chunk in deleted files, promoting a
+// remaining copy when the chunk's own file is deleted. It reports whether the
+// chunk changed and still has copies left to store.
+func removeLocations(chunk *chunker.Chunk, deleted map[string]bool) bool {
+	all := append([]location{{path: chunk.FilePath, start: chunk.LineStart, end: chunk.LineEnd}},
+		parseLocations(chunk.Metadata["duplicate_locations"])...)
+	var remaining []location
+	for _, l := range all {
+		if !deleted[l.path] {
+			remaining = append(remaining, l)
+		}
+	}
+	if len(remaining) == len(all) || len(remaining) == 0 {
+		return false
+	}
+
+	chunk.FilePath, chunk.LineStart, chunk.LineEnd = remaining[0].path, remaining[0].start, remaining[0].end
+	if len(remaining) > 1 {
+		chunk.Metadata["duplicate_locations"] = formatLocations(remaining[1:])
+	} else {
+		delete(chunk.Metadata, "duplicate_locations")
+	}
+	return true
+}
+
+// duplicateFilter matches the chunks with a copy in any of the given files.
+// LIKE treats _ and % in paths as wildcards, so matches are checked again
+// against the parsed locations.
+func duplicateFilter(filePaths []string) string {
+	filterParts := make([]string, 0, len(filePaths))
+	for _, path := range filePaths {
+		escaped := strings.ReplaceAll(path, "'", "''")
+		filterParts = append(filterParts, fmt.Sprintf("duplicate_locations LIKE '%%%s:%%'", escaped))
+	}
+	return "(" + strings.Join(filterParts, " OR ") + ")"
+}
+
+// mergeDuplicates stores each chunk of a batch whose code appears more than
+// once in it once, recording the others in its duplicate_locations. It returns
+// the chunks to store with their embeddings, the index of each dedup key among
+// them, and the content hashes to look up stored copies of.
+func mergeDuplicates(chunks []chunker.Chunk, embeddings [][]float64) ([]chunker.Chunk, [][]float64, map[string]int, []string) {
+	var (
+		unique  []chunker.Chunk
+		vectors [][]float64
+		hashes  []string
+	)
+	first := make(map[string]int)
+	for i, chunk := range chunks {
+		key := dedupKey(chunk)
+		if j, ok := first[key]; ok {
+			addDuplicate(&unique[j], chunk)
+			continue
+		}
+
+		// The caller's metadata is left as it was
+		metadata := make(map[string]string, len(chunk.Metadata)+1)
+		for k, v := range chunk.Metadata {
+			metadata[k] = v
+		}
+		chunk.Metadata = metadata
+
+		if key != "" {
+			first[key] = len(unique)
+			hashes = append(hashes, chunk.Metadata["content_hash"])
+		}
+		unique = append(unique, chunk)
+		vectors = append(vectors, embeddings[i])
+	}
+	return unique, vectors, first, hashes
+}
+
+// copiesFilter matches the stored chunks with copies in the given files that
+// are kept when those files are deleted: duplicated chunks of the files, and
+// chunks of other files with a copy in them
+func copiesFilter(filePaths []string) string {
+	return "(" + filePathFilter(filePaths) + " AND duplicate_locations <> '') OR " + duplicateFilter(filePaths)
+}
+
+// keepCopies drops the copies in the given files from the chunks of rows
+// matched by copiesFilter. It returns the chunks that changed but still have
+// copies, to be written again in place of the rows of the same IDs, with
+// their embeddings.
+func keepCopies(rows []map[string]interface{}, filePaths []string) ([]chunker.Chunk, [][]float64, []string) {
+	deleted := make(map[string]bool, len(filePaths))
+	for _, path := range filePaths {
+		deleted[path] = true
+	}
+	var (
+		kept        []chunker.Chunk
+		keptVectors [][]float64
+		rewritten   []string
+	)
+	for _, row := range rows {
+		chunk, vector := chunkFromRow(row)
+		if removeLocations(&chunk, deleted) {
+			kept = append(kept, chunk)
+			keptVectors = append(keptVectors, vector)
+			rewritten = append(rewritten, chunk.ID)
+		}
+	}
+	return kept, keptVectors, rewritten
+}
+
+// StoreChunks stores chunks with their embeddings (incremental - adds to
+// existing table). Code that appears more than once, such as a vendored copy
+// of a dependency, is stored once: the first copy keeps the vector and the
+// others are recorded in its duplicate_locations.
+func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float64) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if len(chunks) != len(embeddings) {
+		return fmt.Errorf("chunks and embeddings length mismatch: %d vs %d", len(chunks), len(embeddings))
+	}
+	if len(chunks) == 0 {
+		return nil // Nothing to store
+	}
+	if err := s.ensureTable(); err != nil {
+		return err
+	}
+
+	unique, vectors, first, hashes := mergeDuplicates(chunks, embeddings)
+
+	// Copies of code that is already stored join the stored chunk, which is
+	// written again with the new copies
+	ctx := context.Background()
+	var replaced []string
+	for start := 0; start < len(hashes); start += hashesPerQuery {
+		rows, err := s.table.SelectWithFilter(ctx, columnFilter("content_hash", hashes[start:min(start+hashesPerQuery, len(hashes))]))
+		if err != nil {
+			return fmt.Errorf("failed to find stored copies: %w", err)
+		}
+		if err := s.decodeRows(rows); err != nil {
+			return err
+		}
+		for _, row := range rows {
+			stored, vector := chunkFromRow(row)
+			j, ok := first[dedupKey(stored)]
+			if !ok || len(vector) == 0 {
+				continue
+			}
+			addDuplicate(&stored, unique[j])
+			unique[j], vectors[j] = stored, vector
+			replaced = append(replaced, stored.ID)
+		}
+	}
+	if len(replaced) > 0 {
+		if err := s.table.Delete(ctx, columnFilter("chunk_id", replaced)); err != nil {
+			return fmt.Errorf("failed to replace stored copies: %w", err)
+		}
+	}
+
+	return s.addChunks(unique, vectors)
+}
+
+// metadataColumns are the columns that hold the chunk metadata of the same key
+var metadataColumns = []string{
+	"heading", "heading_level", "parent_heading", "doc_comment", "doc_summary", "attributes",
+	"qualified_name", "kind", "visibility", "signature", "receiver", "calls", "content_hash",
+	"repo", "submodule", "duplicate_locations", "embedding_model",
+}
+
+// chunkFromRow rebuilds a stored chunk and its embedding from a table row, so
+// the row can be written again
+func chunkFromRow(row map[string]interface{}) (chunker.Chunk, []float64) {
+	text := func(column string) string {
+		value, _ := row[column].(string)
+		return value
+	}
+
+	chunk := chunker.Chunk{
+		ID:            text("chunk_id"),
+		FilePath:      text("file_path"),
+		LineStart:     toInt(row["line_start"]),
+		LineEnd:       toInt(row["line_end"]),
+		Language:      text("language"),
+		Code:          text("code"),
+		ChunkType:     text("chunk_type"),
+		Name:          text("name"),
+		Metadata:      make(map[string]string),
+		EmbeddingType: text("embedding_type"),
+	}
+	for _, column := range metadataColumns {
+		if value := text(column); value != "" {
+			chunk.Metadata[column] = value
+		}
+	}
+	if isTest, _ := row["is_test"].(bool); isTest {
+		chunk.Metadata["test"] = "true"
+	}
+	if isGenerated, _ := row["is_generated"].(bool); isGenerated {
+		chunk.Metadata["generated"] = "true"
+	}
+	return chunk, toFloat64s(row["vector"])
+}
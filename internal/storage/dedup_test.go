@@ -0,0 +1,126 @@
+package storage
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// newTestStore opens an empty LanceDB store of 4-dimension vectors
+func newTestStore(t *testing.T) *LanceDBStore {
+	t.Helper()
+	store, err := NewLanceDBStore(t.TempDir())
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { store.Close() })
+	store.SetDimension(4)
+	return store
+}
+
+// storedByPath returns the stored chunks by file path
+func storedByPath(t *testing.T, store *LanceDBStore) map[string]chunker.Chunk {
+	t.Helper()
+	stored, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	chunks := make(map[string]chunker.Chunk, len(stored))
+	for _, c := range stored {
+		chunks[c.Chunk.FilePath] = c.Chunk
+	}
+	return chunks
+}
+
+func TestStoreChunksMergesDuplicates(t *testing.T) {
+	store := newTestStore(t)
+	vector := []float64{1, 0, 0, 0}
+	chunks := []chunker.Chunk{testChunk("a", "a.go", "same"), testChunk("b", "vendor/a.go", "same"), testChunk("c", "c.go", "other")}
+	if err := store.StoreChunks(chunks, [][]float64{vector, vector, {0, 1, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+	// A copy stored by a later batch joins the stored chunk
+	if err := store.StoreChunks([]chunker.Chunk{testChunk("d", "third_party/a.go", "same")}, [][]float64{vector}); err != nil {
+		t.Fatal(err)
+	}
+
+	stored := storedByPath(t, store)
+	if len(stored) != 2 {
+		t.Fatalf("stored %v, want a.go and c.go", stored)
+	}
+	if got := stored["a.go"].Metadata["duplicate_locations"]; got != "vendor/a.go:1-3\nthird_party/a.go:1-3" {
+		t.Errorf("duplicate_locations = %q, want the copies in vendor/a.go and third_party/a.go", got)
+	}
+	if got := stored["c.go"].Metadata["duplicate_locations"]; got != "" {
+		t.Errorf("duplicate_locations of c.go = %q, want none", got)
+	}
+}
+
+func TestDeletePromotesCopyOfDeletedFile(t *testing.T) {
+	store := newTestStore(t)
+	vector := []float64{1, 0, 0, 0}
+	chunks := []chunker.Chunk{testChunk("a", "a.go", "same"), testChunk("b", "vendor/a.go", "same"), testChunk("c", "third_party/a.go", "same")}
+	if err := store.StoreChunks(chunks, [][]float64{vector, vector, vector}); err != nil {
+		t.Fatal(err)
+	}
+
+	if err := store.DeleteChunksByFilePath([]string{"a.go"}); err != nil {
+		t.Fatal(err)
+	}
+	stored := storedByPath(t, store)
+	promoted, ok := stored["vendor/a.go"]
+	if len(stored) != 1 || !ok {
+		t.Fatalf("stored %v after deleting a.go, want the copy in vendor/a.go", stored)
+	}
+	if got := promoted.Metadata["duplicate_locations"]; got != "third_party/a.go:1-3" {
+		t.Errorf("duplicate_locations = %q, want the copy in third_party/a.go", got)
+	}
+
+	// Deleting a copy drops it, and deleting the last one the chunk
+	if err := store.DeleteChunksByFilePath([]string{"third_party/a.go"}); err != nil {
+		t.Fatal(err)
+	}
+	if got := storedByPath(t, store)["vendor/a.go"].Metadata["duplicate_locations"]; got != "" {
+		t.Errorf("duplicate_locations = %q after deleting third_party/a.go, want none", got)
+	}
+	if err := store.DeleteChunksByFilePath([]string{"vendor/a.go"}); err != nil {
+		t.Fatal(err)
+	}
+	if stored := storedByPath(t, store); len(stored) != 0 {
+		t.Errorf("stored %v after deleting every copy, want nothing", stored)
+	}
+}
+
+func TestDeleteMatchesPathsWithWildcards(t *testing.T) {
+	store := newTestStore(t)
+	chunks := []chunker.Chunk{
+		testChunk("x", "one.go", "hx"), testChunk("x2", "pkg/axb.go", "hx"),
+		testChunk("y", "pkg/a_b.go", "hy"),
+		testChunk("z", "100%.go", "hz"), testChunk("z2", "other.go", "hz"),
+	}
+	vectors := [][]float64{{1, 0, 0, 0}, {1, 0, 0, 0}, {0, 1, 0, 0}, {0, 0, 1, 0}, {0, 0, 1, 0}}
+	if err := store.StoreChunks(chunks, vectors); err != nil {
+		t.Fatal(err)
+	}
+
+	// LIKE would read _ and % as wildcards matching pkg/axb.go and others
+	if err := store.DeleteChunksByFilePath([]string{"pkg/a_b.go", "100%.go"}); err != nil {
+		t.Fatal(err)
+	}
+	stored := storedByPath(t, store)
+	var paths []string
+	for path := range stored {
+		paths = append(paths, path)
+	}
+	slices.Sort(paths)
+	if !slices.Equal(paths, []string{"one.go", "other.go"}) {
+		t.Fatalf("stored %v, want one.go and the promoted copy in other.go", paths)
+	}
+	if got := stored["one.go"].Metadata["duplicate_locations"]; got != "pkg/axb.go:1-3" {
+		t.Errorf("duplicate_locations of one.go = %q, want its copy in pkg/axb.go kept", got)
+	}
+	if got := stored["other.go"].Metadata["duplicate_locations"]; got != "" {
+		t.Errorf("duplicate_locations of other.go = %q, want none", got)
+	}
+}
@@ -0,0 +1,61 @@
+package storage
+
+import (
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+)
+
+// SetKey encrypts the chunk text the store writes, and decrypts what it
+// reads, with AES-256-GCM under a 32-byte key
+func (s *LanceDBStore) SetKey(key []byte) error {
+	if len(key) != 32 {
+		return fmt.Errorf("index key must be 32 bytes, got %d", len(key))
+	}
+	block, err := aes.NewCipher(key)
+	if err != nil {
+		return fmt.Errorf("failed to create cipher: %w", err)
+	}
+	aead, err := cipher.NewGCM(block)
+	if err != nil {
+		return fmt.Errorf("failed to create cipher: %w", err)
+	}
+	s.aead = aead
+
+	// The fingerprint identifies the key without revealing it
+	sum := sha256.Sum256(append([]byte("code-scout index key\x00"), key...))
+	s.keyFingerprint = hex.EncodeToString(sum[:8])
+	return nil
+}
+
+// KeyFingerprint identifies the store's key, or is "" when the store does
+// not encrypt
+func (s *LanceDBStore) KeyFingerprint() string {
+	return s.keyFingerprint
+}
+
+// seal encrypts data, prefixing it with a random nonce
+func (s *LanceDBStore) seal(data []byte) []byte {
+	nonce := make([]byte, s.aead.NonceSize(), s.aead.NonceSize()+len(data)+s.aead.Overhead())
+	if _, err := rand.Read(nonce); err != nil {
+		// crypto/rand does not fail on supported platforms
+		panic(fmt.Sprintf("failed to generate nonce: %v", err))
+	}
+	return s.aead.Seal(nonce, nonce, data, nil)
+}
+
+// open decrypts data sealed by seal
+func (s *LanceDBStore) open(data []byte) ([]byte, error) {
+	size := s.aead.NonceSize()
+	if len(data) < size {
+		return nil, fmt.Errorf("failed to decrypt chunk text: data too short")
+	}
+	plain, err := s.aead.Open(nil, data[:size], data[size:], nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt chunk text (wrong index key?): %w", err)
+	}
+	return plain, nil
+}
@@ -0,0 +1,99 @@
+package storage
+
+import (
+	"bytes"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+func TestEncryptedStoreWritesNoPlaintext(t *testing.T) {
+	root := t.TempDir()
+	store, err := NewLanceDBStore(root)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	store.SetDimension(4)
+	if err := store.SetKey(bytes.Repeat([]byte{7}, 32)); err != nil {
+		t.Fatal(err)
+	}
+
+	// Every text taken from the source is a distinct identifier, so any of
+	// them found on disk was written in plaintext
+	secrets := map[string]string{
+		"name":           "ZephyrWidgetName",
+		"qualified_name": "billing.ZephyrWidgetQualified",
+		"attributes":     "@ZephyrWidgetAnnotation",
+		"receiver":       "ZephyrWidgetReceiver",
+		"calls":          "zephyrWidgetCallee",
+		"heading":        "Zephyr Widget Heading",
+		"parent_heading": "Zephyr Widget Parent",
+		"doc_comment":    "ZephyrWidgetDocComment",
+		"doc_summary":    "ZephyrWidgetDocSummary",
+		"signature":      "func ZephyrWidgetSignature()",
+	}
+	chunk := chunker.Chunk{
+		ID:            "chunk-1",
+		FilePath:      "src/main.go",
+		LineStart:     1,
+		LineEnd:       3,
+		Language:      "go",
+		Code:          "func ZephyrWidgetCode() {}",
+		Name:          secrets["name"],
+		Metadata:      make(map[string]string),
+		EmbeddingType: "code",
+	}
+	for column, text := range secrets {
+		if column != "name" {
+			chunk.Metadata[column] = text
+		}
+	}
+	if err := store.StoreChunks([]chunker.Chunk{chunk}, [][]float64{{1, 0, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+
+	err = filepath.WalkDir(store.dbDir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil || d.IsDir() {
+			return err
+		}
+		data, err := os.ReadFile(path)
+		if err != nil {
+			return err
+		}
+		if bytes.Contains(data, []byte("ZephyrWidget")) || bytes.Contains(data, []byte("zephyrWidget")) {
+			t.Errorf("%s holds source text in plaintext", path)
+		}
+		return nil
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The store reads back what it was given
+	stored, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(stored) != 1 {
+		t.Fatalf("read %d chunks, want 1", len(stored))
+	}
+	got := stored[0]
+	if got.DecodeErr != nil {
+		t.Fatal(got.DecodeErr)
+	}
+	if got.Chunk.Code != chunk.Code || got.Chunk.Name != chunk.Name {
+		t.Errorf("read code %q and name %q, want %q and %q", got.Chunk.Code, got.Chunk.Name, chunk.Code, chunk.Name)
+	}
+	for column, text := range secrets {
+		if column == "name" {
+			continue
+		}
+		if got.Chunk.Metadata[column] != text {
+			t.Errorf("read %s %q, want %q", column, got.Chunk.Metadata[column], text)
+		}
+	}
+}
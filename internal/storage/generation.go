@@ -0,0 +1,156 @@
+package storage
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+)
+
+const (
+	// GenerationDirName is the directory in the index directory that holds the
+	// next generation of the index while it is written
+	GenerationDirName = "generation"
+	// committedMarker marks a staged store that is complete, so a swap that was
+	// interrupted is finished rather than rolled back
+	committedMarker = "COMMITTED"
+	// resumableMarker marks a generation whose run failed cleanly after saving
+	// its metadata, so the next run can pick up where it stopped
+	resumableMarker = "RESUMABLE"
+)
+
+// linkFile hard-links a file; a variable so tests can make linking fail
+var linkFile = os.Link
+
+// BeginGeneration returns a store for the next generation of the index, which
+// starts as a copy of the index's chunks and metadata. Changes written to it
+// leave the index untouched until CommitGeneration swaps the generation in,
+// so a run that is interrupted, or killed, leaves the previous index intact.
+// A generation suspended by a run that failed is resumed; one left by a run
+// that was killed is discarded. The index must be locked.
+func (s *LanceDBStore) BeginGeneration() (*LanceDBStore, error) {
+	if s.readOnly {
+		return nil, ErrReadOnly
+	}
+	genDir := filepath.Join(s.dbDir, GenerationDirName)
+	marker := filepath.Join(genDir, resumableMarker)
+	if _, err := os.Stat(marker); err == nil {
+		if err := os.Remove(marker); err != nil {
+			return nil, fmt.Errorf("failed to resume the next index generation: %w", err)
+		}
+	} else {
+		if err := os.RemoveAll(genDir); err != nil {
+			return nil, fmt.Errorf("failed to clear the next index generation: %w", err)
+		}
+		if err := s.snapshot(genDir); err != nil {
+			os.RemoveAll(genDir)
+			return nil, fmt.Errorf("failed to start the next index generation: %w", err)
+		}
+	}
+
+	gen, err := openDBDir(genDir)
+	if err != nil {
+		return nil, err
+	}
+	gen.aead, gen.keyFingerprint = s.aead, s.keyFingerprint
+	gen.dimension, gen.quantization = s.dimension, s.quantization
+	return gen, nil
+}
+
+// CommitGeneration saves the metadata of a generation started with
+// BeginGeneration and swaps the generation in as the index
+func (s *LanceDBStore) CommitGeneration(gen *LanceDBStore, metadata *IndexMetadata) error {
+	if err := gen.SaveMetadata(metadata); err != nil {
+		return err
+	}
+	return s.replaceWith(gen)
+}
+
+// SuspendGeneration saves the metadata of a generation whose run failed and
+// keeps the generation for the next run to resume; the index is unchanged
+func (s *LanceDBStore) SuspendGeneration(gen *LanceDBStore, metadata *IndexMetadata) error {
+	if err := gen.SaveMetadata(metadata); err != nil {
+		return err
+	}
+	if err := os.WriteFile(filepath.Join(gen.dbDir, resumableMarker), nil, 0644); err != nil {
+		return fmt.Errorf("failed to keep the next index generation: %w", err)
+	}
+	return nil
+}
+
+// snapshot copies the chunks, quantized vectors, and metadata of the store
+// into dir. Lance never changes a file it has written, so the chunk files are
+// hard-linked rather than copied where the file system allows.
+func (s *LanceDBStore) snapshot(dir string) error {
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return err
+	}
+	tableDir := filepath.Join(s.dbDir, DefaultTableName+".lance")
+	if _, err := os.Stat(tableDir); err == nil {
+		if err := linkTree(tableDir, filepath.Join(dir, DefaultTableName+".lance")); err != nil {
+			return err
+		}
+	}
+	err := copyFile(filepath.Join(s.dbDir, QuantizedFileName), filepath.Join(dir, QuantizedFileName))
+	if err != nil && !os.IsNotExist(err) {
+		return err
+	}
+	err = copyFile(filepath.Join(s.dbDir, MetadataFileName), filepath.Join(dir, MetadataFileName))
+	if os.IsNotExist(err) {
+		return nil
+	}
+	return err
+}
+
+// recoverSwap finishes swapping in a committed generation that a process was
+// killed part way through swapping in. It runs when the index is locked.
+func (s *LanceDBStore) recoverSwap() error {
+	genDir := filepath.Join(s.dbDir, GenerationDirName)
+	if _, err := os.Stat(filepath.Join(genDir, committedMarker)); err != nil {
+		return nil
+	}
+	if err := s.finishSwap(genDir); err != nil {
+		return fmt.Errorf("failed to recover the index: %w", err)
+	}
+	return nil
+}
+
+// linkTree recreates the directory tree src at dst with hard links to its
+// files, copying the files that cannot be linked
+func linkTree(src, dst string) error {
+	return filepath.Walk(src, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+		if info.IsDir() {
+			return os.MkdirAll(target, 0755)
+		}
+		if err := linkFile(path, target); err == nil {
+			return nil
+		}
+		return copyFile(path, target)
+	})
+}
+
+// copyFile copies the file src to dst
+func copyFile(src, dst string) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+	out, err := os.Create(dst)
+	if err != nil {
+		return err
+	}
+	if _, err := io.Copy(out, in); err != nil {
+		out.Close()
+		return err
+	}
+	return out.Close()
+}
@@ -0,0 +1,181 @@
+package storage
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// openTestIndex opens a LanceDB index in root holding chunk a of a.go
+func openTestIndex(t *testing.T, root string) *LanceDBStore {
+	t.Helper()
+	store, err := NewLanceDBStore(root)
+	if err != nil {
+		t.Fatal(err)
+	}
+	store.SetDimension(4)
+	if err := store.StoreChunks([]chunker.Chunk{testChunk("a", "a.go", "ha")}, [][]float64{{1, 0, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+	metadata := NewIndexMetadata()
+	metadata.FileHashes["a.go"] = "ha"
+	if err := store.SaveMetadata(metadata); err != nil {
+		t.Fatal(err)
+	}
+	return store
+}
+
+// storedIDs returns the sorted IDs of the chunks of store
+func storedIDs(t *testing.T, store *LanceDBStore) []string {
+	t.Helper()
+	stored, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var ids []string
+	for _, c := range stored {
+		ids = append(ids, c.Chunk.ID)
+	}
+	slices.Sort(ids)
+	return ids
+}
+
+// beginWithChunkB starts a generation of store and stores chunk b of b.go in it
+func beginWithChunkB(t *testing.T, store *LanceDBStore) *LanceDBStore {
+	t.Helper()
+	gen, err := store.BeginGeneration()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := gen.StoreChunks([]chunker.Chunk{testChunk("b", "b.go", "hb")}, [][]float64{{0, 1, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+	return gen
+}
+
+func TestInterruptedGenerationLeavesIndexUnchanged(t *testing.T) {
+	store := openTestIndex(t, t.TempDir())
+	defer store.Close()
+
+	// A run that is killed closes the generation without committing it
+	gen := beginWithChunkB(t, store)
+	gen.Close()
+	if ids := storedIDs(t, store); !slices.Equal(ids, []string{"a"}) {
+		t.Fatalf("index holds %v after an interrupted generation, want [a]", ids)
+	}
+
+	// The next run discards it and starts again from the index
+	gen, err := store.BeginGeneration()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer gen.Close()
+	if ids := storedIDs(t, gen); !slices.Equal(ids, []string{"a"}) {
+		t.Errorf("next generation holds %v, want a copy of the index, [a]", ids)
+	}
+}
+
+func TestSuspendedGenerationIsResumed(t *testing.T) {
+	store := openTestIndex(t, t.TempDir())
+	defer store.Close()
+
+	gen := beginWithChunkB(t, store)
+	metadata := NewIndexMetadata()
+	metadata.FileHashes["a.go"], metadata.FileHashes["b.go"] = "ha", "hb"
+	if err := store.SuspendGeneration(gen, metadata); err != nil {
+		t.Fatal(err)
+	}
+	gen.Close()
+	if ids := storedIDs(t, store); !slices.Equal(ids, []string{"a"}) {
+		t.Fatalf("index holds %v after a suspended generation, want [a]", ids)
+	}
+
+	gen, err := store.BeginGeneration()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer gen.Close()
+	if ids := storedIDs(t, gen); !slices.Equal(ids, []string{"a", "b"}) {
+		t.Errorf("resumed generation holds %v, want [a b]", ids)
+	}
+	resumed, err := gen.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if resumed.FileHashes["b.go"] != "hb" {
+		t.Errorf("resumed metadata = %+v, want the suspended run's", resumed.FileHashes)
+	}
+	if _, err := os.Stat(filepath.Join(gen.dbDir, resumableMarker)); !os.IsNotExist(err) {
+		t.Errorf("resumed generation is still marked resumable: %v", err)
+	}
+
+	metadata.FileHashes["c.go"] = "hc"
+	if err := store.CommitGeneration(gen, metadata); err != nil {
+		t.Fatal(err)
+	}
+	if ids := storedIDs(t, store); !slices.Equal(ids, []string{"a", "b"}) {
+		t.Errorf("index holds %v after the commit, want [a b]", ids)
+	}
+}
+
+func TestTryLockFinishesCommittedSwap(t *testing.T) {
+	root := t.TempDir()
+	store := openTestIndex(t, root)
+
+	// A process killed while swapping a generation in leaves it committed
+	gen := beginWithChunkB(t, store)
+	metadata := NewIndexMetadata()
+	metadata.FileHashes["b.go"] = "hb"
+	if err := gen.SaveMetadata(metadata); err != nil {
+		t.Fatal(err)
+	}
+	gen.Close()
+	store.Close()
+	if err := os.WriteFile(filepath.Join(root, DefaultDBDir, GenerationDirName, committedMarker), nil, 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	reopened, err := NewLanceDBStore(root)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer reopened.Close()
+	lock, err := reopened.TryLock()
+	if err != nil {
+		t.Fatalf("TryLock() error = %v", err)
+	}
+	defer lock.Unlock()
+	if ids := storedIDs(t, reopened); !slices.Equal(ids, []string{"a", "b"}) {
+		t.Errorf("index holds %v after the swap was finished, want [a b]", ids)
+	}
+	loaded, err := reopened.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if loaded.FileHashes["b.go"] != "hb" {
+		t.Errorf("metadata = %+v, want the committed generation's", loaded.FileHashes)
+	}
+	if _, err := os.Stat(filepath.Join(root, DefaultDBDir, GenerationDirName)); !os.IsNotExist(err) {
+		t.Errorf("generation directory left after the swap: %v", err)
+	}
+}
+
+func TestGenerationCopiesFilesThatCannotBeLinked(t *testing.T) {
+	linkFile = func(oldname, newname string) error { return errors.New("links are not supported") }
+	t.Cleanup(func() { linkFile = os.Link })
+	store := openTestIndex(t, t.TempDir())
+	defer store.Close()
+
+	gen := beginWithChunkB(t, store)
+	defer gen.Close()
+	if ids := storedIDs(t, gen); !slices.Equal(ids, []string{"a", "b"}) {
+		t.Fatalf("generation holds %v, want the copied index and b, [a b]", ids)
+	}
+	if ids := storedIDs(t, store); !slices.Equal(ids, []string{"a"}) {
+		t.Errorf("index holds %v, want [a] unchanged by its copy", ids)
+	}
+}
@@ -2,9 +2,12 @@ package storage
 
 import (
 	"context"
+	"crypto/cipher"
+	"errors"
 	"fmt"
 	"os"
 	"path/filepath"
+	"sort"
 	"strings"
 
 	"github.com/apache/arrow/go/v17/arrow"
@@ -20,22 +23,67 @@ const (
 	DefaultDBDir = ".code-scout"
 	// DefaultTableName is the default table name for code chunks
 	DefaultTableName = "code_chunks"
-	// VectorDimension is the embedding dimension (nomic-embed-code uses 3584)
+	// VectorDimension is the embedding dimension (nomic-embed-code uses 3584),
+	// and the size of the stored vectors unless SetDimension sets another
 	VectorDimension = 3584
 )
 
+// ErrReadOnly is returned when a store opened read-only would be changed
+var ErrReadOnly = errors.New("the index is open read-only")
+
 // LanceDBStore handles storage and retrieval from LanceDB
 type LanceDBStore struct {
 	conn   contracts.IConnection
 	table  contracts.ITable
 	schema *arrow.Schema
 	dbDir  string
+
+	// Chunk text is encrypted when a key is set
+	aead           cipher.AEAD
+	keyFingerprint string
+
+	readOnly bool // Opened with NewReadOnlyLanceDBStore
+
+	// Size of the vectors of tables created from now on, set with
+	// SetDimension, and of the open table's, once it is known
+	dimension int
+	width     int
+
+	// Searches scan vectors quantized this way first, see SetQuantization
+	quantization string
+	quantized    *quantizedVectors // Loaded with the first search
 }
 
 // NewLanceDBStore creates a new LanceDB store
 func NewLanceDBStore(rootDir string) (*LanceDBStore, error) {
+	return openDBDir(filepath.Join(rootDir, DefaultDBDir))
+}
+
+// NewReadOnlyLanceDBStore opens an existing LanceDB store without changing
+// anything on disk. Methods that would change the store return ErrReadOnly.
+func NewReadOnlyLanceDBStore(rootDir string) (*LanceDBStore, error) {
 	dbDir := filepath.Join(rootDir, DefaultDBDir)
+	if _, err := os.Stat(dbDir); err != nil {
+		if os.IsNotExist(err) {
+			return nil, fmt.Errorf("no index in %s", rootDir)
+		}
+		return nil, fmt.Errorf("failed to open database directory: %w", err)
+	}
+
+	conn, err := lancedb.Connect(context.Background(), dbDir, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to connect to LanceDB: %w", err)
+	}
 
+	return &LanceDBStore{
+		conn:     conn,
+		dbDir:    dbDir,
+		readOnly: true,
+	}, nil
+}
+
+// openDBDir opens the store kept in dbDir
+func openDBDir(dbDir string) (*LanceDBStore, error) {
 	// Create directory if it doesn't exist
 	if err := os.MkdirAll(dbDir, 0755); err != nil {
 		return nil, fmt.Errorf("failed to create database directory: %w", err)
@@ -54,6 +102,42 @@ func NewLanceDBStore(rootDir string) (*LanceDBStore, error) {
 	}, nil
 }
 
+// SetDimension makes tables created from now on hold vectors of n dimensions
+// instead of VectorDimension, for an index of vectors shortened to n. A table
+// that exists keeps the size it was created with.
+func (s *LanceDBStore) SetDimension(n int) {
+	s.dimension = n
+}
+
+// VectorWidth returns the number of dimensions of the stored vectors
+func (s *LanceDBStore) VectorWidth() int {
+	if s.width == 0 && s.table == nil {
+		if table, err := s.conn.OpenTable(context.Background(), DefaultTableName); err == nil {
+			s.detectWidth(table)
+			table.Close()
+		}
+	}
+	switch {
+	case s.width > 0:
+		return s.width
+	case s.dimension > 0:
+		return s.dimension
+	}
+	return VectorDimension
+}
+
+// detectWidth records the size of the vectors of a table that holds any
+func (s *LanceDBStore) detectWidth(table contracts.ITable) {
+	rows, err := table.SelectWithLimit(context.Background(), 1, 0)
+	if err != nil || len(rows) == 0 {
+		return
+	}
+	if vector := toFloat64s(rows[0]["vector"]); len(vector) > 0 && len(vector) != s.width {
+		s.width = len(vector)
+		s.schema = nil
+	}
+}
+
 // getOrCreateSchema returns the schema, creating it if needed
 func (s *LanceDBStore) getOrCreateSchema() (*arrow.Schema, error) {
 	if s.schema != nil {
@@ -66,13 +150,30 @@ func (s *LanceDBStore) getOrCreateSchema() (*arrow.Schema, error) {
 		{Name: "line_start", Type: arrow.PrimitiveTypes.Int32, Nullable: false},
 		{Name: "line_end", Type: arrow.PrimitiveTypes.Int32, Nullable: false},
 		{Name: "language", Type: arrow.BinaryTypes.String, Nullable: false},
-		{Name: "code", Type: arrow.BinaryTypes.String, Nullable: false},
+		{Name: "code", Type: arrow.BinaryTypes.Binary, Nullable: false}, // encoded, see encodedColumns
 		{Name: "chunk_type", Type: arrow.BinaryTypes.String, Nullable: true},
 		{Name: "heading", Type: arrow.BinaryTypes.String, Nullable: true},
 		{Name: "heading_level", Type: arrow.BinaryTypes.String, Nullable: true},
 		{Name: "parent_heading", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "doc_comment", Type: arrow.BinaryTypes.Binary, Nullable: true},
+		{Name: "doc_summary", Type: arrow.BinaryTypes.Binary, Nullable: true},
+		{Name: "attributes", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "name", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "qualified_name", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "kind", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "visibility", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "signature", Type: arrow.BinaryTypes.Binary, Nullable: true},
+		{Name: "receiver", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "calls", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "content_hash", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "repo", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "submodule", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "duplicate_locations", Type: arrow.BinaryTypes.String, Nullable: true}, // other copies of the code, one path:start-end per line
+		{Name: "embedding_model", Type: arrow.BinaryTypes.String, Nullable: true},     // fallback model the vector is from, "" for the index's own
+		{Name: "is_test", Type: arrow.FixedWidthTypes.Boolean, Nullable: false},
+		{Name: "is_generated", Type: arrow.FixedWidthTypes.Boolean, Nullable: false},
 		{Name: "embedding_type", Type: arrow.BinaryTypes.String, Nullable: false}, // "code" or "docs"
-		{Name: "vector", Type: arrow.FixedSizeListOf(VectorDimension, arrow.PrimitiveTypes.Float32), Nullable: false},
+		{Name: "vector", Type: arrow.FixedSizeListOf(int32(s.VectorWidth()), arrow.PrimitiveTypes.Float32), Nullable: false},
 	}
 	s.schema = arrow.NewSchema(fields, nil)
 	return s.schema, nil
@@ -90,8 +191,12 @@ func (s *LanceDBStore) ensureTable() error {
 	var err error
 	s.table, err = s.conn.OpenTable(ctx, DefaultTableName)
 	if err == nil {
+		s.detectWidth(s.table)
 		return nil
 	}
+	if s.readOnly {
+		return fmt.Errorf("failed to open table: %w", err)
+	}
 
 	// Table doesn't exist, create it
 	schema, err := s.getOrCreateSchema()
@@ -112,8 +217,42 @@ func (s *LanceDBStore) ensureTable() error {
 	return nil
 }
 
-// DeleteChunksByFilePath deletes all chunks for the given file paths
+// Reset deletes every stored chunk and the metadata, so the index is rebuilt
+// from scratch
+func (s *LanceDBStore) Reset() error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if s.table != nil {
+		if err := s.table.Close(); err != nil {
+			return fmt.Errorf("failed to close table: %w", err)
+		}
+		s.table = nil
+	}
+	if err := os.RemoveAll(filepath.Join(s.dbDir, DefaultTableName+".lance")); err != nil {
+		return fmt.Errorf("failed to delete chunks: %w", err)
+	}
+	// The next table is created with the size set with SetDimension
+	s.width, s.schema = 0, nil
+	if err := os.Remove(filepath.Join(s.dbDir, MetadataFileName)); err != nil && !os.IsNotExist(err) {
+		return fmt.Errorf("failed to delete metadata: %w", err)
+	}
+	// The chunks that replace these may have the same IDs and content with
+	// vectors from other models
+	if err := os.Remove(filepath.Join(s.dbDir, QuantizedFileName)); err != nil && !os.IsNotExist(err) {
+		return fmt.Errorf("failed to delete quantized vectors: %w", err)
+	}
+	s.quantized = nil
+	return nil
+}
+
+// DeleteChunksByFilePath deletes all chunks for the given file paths. A
+// chunk whose code also appears in other files is kept for its remaining
+// copies.
 func (s *LanceDBStore) DeleteChunksByFilePath(filePaths []string) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
 	if len(filePaths) == 0 {
 		return nil
 	}
@@ -127,37 +266,155 @@ func (s *LanceDBStore) DeleteChunksByFilePath(filePaths []string) error {
 	}
 	defer table.Close()
 
-	// Build filter expression: file_path = 'path1' OR file_path = 'path2' OR ...
-	// Escape single quotes in file paths
-	filterParts := make([]string, 0, len(filePaths))
-	for _, path := range filePaths {
-		// Escape single quotes by doubling them
-		escaped := ""
-		for _, r := range path {
-			if r == '\'' {
-				escaped += "''"
-			} else {
-				escaped += string(r)
-			}
-		}
-		filterParts = append(filterParts, fmt.Sprintf("file_path = '%s'", escaped))
+	rows, err := table.SelectWithFilter(ctx, copiesFilter(filePaths))
+	if err != nil {
+		return fmt.Errorf("failed to find duplicated chunks: %w", err)
 	}
+	if err := s.decodeRows(rows); err != nil {
+		return err
+	}
+	kept, keptVectors, rewritten := keepCopies(rows, filePaths)
 
-	filter := "(" + strings.Join(filterParts, " OR ") + ")"
-
+	filter := filePathFilter(filePaths)
+	if len(rewritten) > 0 {
+		filter += " OR " + columnFilter("chunk_id", rewritten)
+	}
 	if err := table.Delete(ctx, filter); err != nil {
 		return fmt.Errorf("failed to delete chunks: %w", err)
 	}
+	return s.addChunks(kept, keptVectors)
+}
 
-	return nil
+// LoadEmbeddings returns the stored embeddings of the chunks of the given
+// files, keyed by chunk content hash, so unchanged chunks of a changed file can
+// keep their embeddings instead of being embedded again
+func (s *LanceDBStore) LoadEmbeddings(filePaths []string) (map[string][]float64, error) {
+	embeddings := make(map[string][]float64)
+	if len(filePaths) == 0 {
+		return embeddings, nil
+	}
+
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, DefaultTableName)
+	if err != nil {
+		// Table doesn't exist yet, nothing stored
+		return embeddings, nil
+	}
+	defer table.Close()
+
+	rows, err := table.SelectWithFilter(ctx, filePathFilter(filePaths)+" OR "+duplicateFilter(filePaths))
+	if err != nil {
+		return nil, fmt.Errorf("failed to load embeddings: %w", err)
+	}
+	return reusableEmbeddings(rows), nil
 }
 
-// StoreChunks stores chunks with their embeddings (incremental - adds to existing table)
-func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float64) error {
-	if len(chunks) != len(embeddings) {
-		return fmt.Errorf("chunks and embeddings length mismatch: %d vs %d", len(chunks), len(embeddings))
+// reusableEmbeddings returns the vectors of rows that the index's own models
+// embedded, keyed by chunk content hash
+func reusableEmbeddings(rows []map[string]interface{}) map[string][]float64 {
+	embeddings := make(map[string][]float64)
+	for _, row := range rows {
+		hash, _ := row["content_hash"].(string)
+		// A fallback model's vectors are embedded again with the index's model
+		if model, _ := row["embedding_model"].(string); hash == "" || model != "" {
+			continue
+		}
+		if vector := toFloat64s(row["vector"]); len(vector) > 0 {
+			embeddings[hash] = vector
+		}
 	}
+	return embeddings
+}
+
+// FallbackFiles returns the files with chunks whose vectors a fallback model
+// embedded, so they can be embedded again with the index's models
+func (s *LanceDBStore) FallbackFiles() ([]string, error) {
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, DefaultTableName)
+	if err != nil {
+		// Table doesn't exist yet, nothing stored
+		return nil, nil
+	}
+	defer table.Close()
+
+	rows, err := table.SelectWithFilter(ctx, "embedding_model <> ''")
+	if err != nil {
+		return nil, fmt.Errorf("failed to find chunks embedded by fallback models: %w", err)
+	}
+	return filesOfRows(rows), nil
+}
 
+// filesOfRows returns the files that have a copy of any of the rows' chunks,
+// sorted
+func filesOfRows(rows []map[string]interface{}) []string {
+	seen := make(map[string]bool)
+	var files []string
+	add := func(path string) {
+		if path != "" && !seen[path] {
+			seen[path] = true
+			files = append(files, path)
+		}
+	}
+	for _, row := range rows {
+		path, _ := row["file_path"].(string)
+		add(path)
+		locations, _ := row["duplicate_locations"].(string)
+		for _, l := range parseLocations(locations) {
+			add(l.path)
+		}
+	}
+	sort.Strings(files)
+	return files
+}
+
+// filePathFilter builds the filter expression file_path = 'path1' OR
+// file_path = 'path2' OR ...
+func filePathFilter(filePaths []string) string {
+	return columnFilter("file_path", filePaths)
+}
+
+// columnFilter builds the filter expression column = 'value1' OR
+// column = 'value2' OR ...
+func columnFilter(column string, values []string) string {
+	filterParts := make([]string, 0, len(values))
+	for _, value := range values {
+		// Escape single quotes by doubling them
+		escaped := strings.ReplaceAll(value, "'", "''")
+		filterParts = append(filterParts, fmt.Sprintf("%s = '%s'", column, escaped))
+	}
+	return "(" + strings.Join(filterParts, " OR ") + ")"
+}
+
+// toFloat64s converts a vector read back from the table to float64s
+func toFloat64s(value interface{}) []float64 {
+	switch v := value.(type) {
+	case []float32:
+		vector := make([]float64, len(v))
+		for i, f := range v {
+			vector[i] = float64(f)
+		}
+		return vector
+	case []float64:
+		return v
+	case []interface{}:
+		vector := make([]float64, 0, len(v))
+		for _, item := range v {
+			switch f := item.(type) {
+			case float32:
+				vector = append(vector, float64(f))
+			case float64:
+				vector = append(vector, f)
+			default:
+				return nil
+			}
+		}
+		return vector
+	}
+	return nil
+}
+
+// addChunks inserts rows for chunks and their embeddings as they are
+func (s *LanceDBStore) addChunks(chunks []chunker.Chunk, embeddings [][]float64) error {
 	if len(chunks) == 0 {
 		return nil // Nothing to store
 	}
@@ -183,13 +440,31 @@ func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float6
 	lineStarts := make([]int32, len(chunks))
 	lineEnds := make([]int32, len(chunks))
 	languages := make([]string, len(chunks))
-	codes := make([]string, len(chunks))
+	codes := make([][]byte, len(chunks))
 	chunkTypes := make([]string, len(chunks))
 	headings := make([]string, len(chunks))
 	headingLevels := make([]string, len(chunks))
 	parentHeadings := make([]string, len(chunks))
+	docComments := make([][]byte, len(chunks))
+	docSummaries := make([][]byte, len(chunks))
+	attributes := make([]string, len(chunks))
+	names := make([]string, len(chunks))
+	qualifiedNames := make([]string, len(chunks))
+	kinds := make([]string, len(chunks))
+	visibilities := make([]string, len(chunks))
+	signatures := make([][]byte, len(chunks))
+	receivers := make([]string, len(chunks))
+	calls := make([]string, len(chunks))
+	contentHashes := make([]string, len(chunks))
+	repos := make([]string, len(chunks))
+	submodules := make([]string, len(chunks))
+	duplicateLocations := make([]string, len(chunks))
+	embeddingModels := make([]string, len(chunks))
+	isTests := make([]bool, len(chunks))
+	isGenerated := make([]bool, len(chunks))
 	embeddingTypes := make([]string, len(chunks))
-	allVectors := make([]float32, len(chunks)*VectorDimension)
+	width := s.VectorWidth()
+	allVectors := make([]float32, len(chunks)*width)
 
 	for i, chunk := range chunks {
 		chunkIDs[i] = chunk.ID
@@ -197,18 +472,42 @@ func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float6
 		lineStarts[i] = int32(chunk.LineStart)
 		lineEnds[i] = int32(chunk.LineEnd)
 		languages[i] = chunk.Language
-		codes[i] = chunk.Code
+		codes[i] = s.encodeText(chunk.Code)
 		chunkTypes[i] = chunk.ChunkType
+		names[i] = s.sealString(chunk.Name)
 		if chunk.Metadata != nil {
-			headings[i] = chunk.Metadata["heading"]
+			headings[i] = s.sealString(chunk.Metadata["heading"])
 			headingLevels[i] = chunk.Metadata["heading_level"]
-			parentHeadings[i] = chunk.Metadata["parent_heading"]
+			parentHeadings[i] = s.sealString(chunk.Metadata["parent_heading"])
+			docComments[i] = s.encodeText(chunk.Metadata["doc_comment"])
+			docSummaries[i] = s.encodeText(chunk.Metadata["doc_summary"])
+			attributes[i] = s.sealString(chunk.Metadata["attributes"])
+			qualifiedNames[i] = s.sealString(chunk.Metadata["qualified_name"])
+			kinds[i] = chunk.Metadata["kind"]
+			visibilities[i] = chunk.Metadata["visibility"]
+			signatures[i] = s.encodeText(chunk.Metadata["signature"])
+			receivers[i] = s.sealString(chunk.Metadata["receiver"])
+			calls[i] = s.sealString(chunk.Metadata["calls"])
+			contentHashes[i] = chunk.Metadata["content_hash"]
+			repos[i] = chunk.Metadata["repo"]
+			submodules[i] = chunk.Metadata["submodule"]
+			duplicateLocations[i] = chunk.Metadata["duplicate_locations"]
+			embeddingModels[i] = chunk.Metadata["embedding_model"]
+			isTests[i] = chunk.Metadata["test"] == "true"
+			isGenerated[i] = chunk.Metadata["generated"] == "true"
 		}
 		embeddingTypes[i] = chunk.EmbeddingType
 
-		// Convert float64 embeddings to float32 and flatten
+		// Convert float64 embeddings to float32 and flatten. Vectors padded
+		// with zeros past the table's size lose only the padding.
 		for j, val := range embeddings[i] {
-			allVectors[i*VectorDimension+j] = float32(val)
+			if j >= width {
+				if val != 0 {
+					return fmt.Errorf("vectors of %d dimensions are larger than the index stores (%d)", len(embeddings[i]), width)
+				}
+				continue
+			}
+			allVectors[i*width+j] = float32(val)
 		}
 	}
 
@@ -238,7 +537,7 @@ func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float6
 	languageArray := languageBuilder.NewArray()
 	defer languageArray.Release()
 
-	codeBuilder := array.NewStringBuilder(pool)
+	codeBuilder := array.NewBinaryBuilder(pool, arrow.BinaryTypes.Binary)
 	codeBuilder.AppendValues(codes, nil)
 	codeArray := codeBuilder.NewArray()
 	defer codeArray.Release()
@@ -263,6 +562,91 @@ func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float6
 	parentHeadingArray := parentHeadingBuilder.NewArray()
 	defer parentHeadingArray.Release()
 
+	docCommentBuilder := array.NewBinaryBuilder(pool, arrow.BinaryTypes.Binary)
+	docCommentBuilder.AppendValues(docComments, nil)
+	docCommentArray := docCommentBuilder.NewArray()
+	defer docCommentArray.Release()
+
+	docSummaryBuilder := array.NewBinaryBuilder(pool, arrow.BinaryTypes.Binary)
+	docSummaryBuilder.AppendValues(docSummaries, nil)
+	docSummaryArray := docSummaryBuilder.NewArray()
+	defer docSummaryArray.Release()
+
+	attributesBuilder := array.NewStringBuilder(pool)
+	attributesBuilder.AppendValues(attributes, nil)
+	attributesArray := attributesBuilder.NewArray()
+	defer attributesArray.Release()
+
+	nameBuilder := array.NewStringBuilder(pool)
+	nameBuilder.AppendValues(names, nil)
+	nameArray := nameBuilder.NewArray()
+	defer nameArray.Release()
+
+	qualifiedNameBuilder := array.NewStringBuilder(pool)
+	qualifiedNameBuilder.AppendValues(qualifiedNames, nil)
+	qualifiedNameArray := qualifiedNameBuilder.NewArray()
+	defer qualifiedNameArray.Release()
+
+	kindBuilder := array.NewStringBuilder(pool)
+	kindBuilder.AppendValues(kinds, nil)
+	kindArray := kindBuilder.NewArray()
+	defer kindArray.Release()
+
+	visibilityBuilder := array.NewStringBuilder(pool)
+	visibilityBuilder.AppendValues(visibilities, nil)
+	visibilityArray := visibilityBuilder.NewArray()
+	defer visibilityArray.Release()
+
+	signatureBuilder := array.NewBinaryBuilder(pool, arrow.BinaryTypes.Binary)
+	signatureBuilder.AppendValues(signatures, nil)
+	signatureArray := signatureBuilder.NewArray()
+	defer signatureArray.Release()
+
+	receiverBuilder := array.NewStringBuilder(pool)
+	receiverBuilder.AppendValues(receivers, nil)
+	receiverArray := receiverBuilder.NewArray()
+	defer receiverArray.Release()
+
+	callsBuilder := array.NewStringBuilder(pool)
+	callsBuilder.AppendValues(calls, nil)
+	callsArray := callsBuilder.NewArray()
+	defer callsArray.Release()
+
+	contentHashBuilder := array.NewStringBuilder(pool)
+	contentHashBuilder.AppendValues(contentHashes, nil)
+	contentHashArray := contentHashBuilder.NewArray()
+	defer contentHashArray.Release()
+
+	repoBuilder := array.NewStringBuilder(pool)
+	repoBuilder.AppendValues(repos, nil)
+	repoArray := repoBuilder.NewArray()
+	defer repoArray.Release()
+
+	submoduleBuilder := array.NewStringBuilder(pool)
+	submoduleBuilder.AppendValues(submodules, nil)
+	submoduleArray := submoduleBuilder.NewArray()
+	defer submoduleArray.Release()
+
+	duplicateLocationsBuilder := array.NewStringBuilder(pool)
+	duplicateLocationsBuilder.AppendValues(duplicateLocations, nil)
+	duplicateLocationsArray := duplicateLocationsBuilder.NewArray()
+	defer duplicateLocationsArray.Release()
+
+	embeddingModelBuilder := array.NewStringBuilder(pool)
+	embeddingModelBuilder.AppendValues(embeddingModels, nil)
+	embeddingModelArray := embeddingModelBuilder.NewArray()
+	defer embeddingModelArray.Release()
+
+	isTestBuilder := array.NewBooleanBuilder(pool)
+	isTestBuilder.AppendValues(isTests, nil)
+	isTestArray := isTestBuilder.NewArray()
+	defer isTestArray.Release()
+
+	isGeneratedBuilder := array.NewBooleanBuilder(pool)
+	isGeneratedBuilder.AppendValues(isGenerated, nil)
+	isGeneratedArray := isGeneratedBuilder.NewArray()
+	defer isGeneratedArray.Release()
+
 	embeddingTypeBuilder := array.NewStringBuilder(pool)
 	embeddingTypeBuilder.AppendValues(embeddingTypes, nil)
 	embeddingTypeArray := embeddingTypeBuilder.NewArray()
@@ -274,7 +658,7 @@ func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float6
 	vectorFloat32Array := vectorFloat32Builder.NewArray()
 	defer vectorFloat32Array.Release()
 
-	vectorListType := arrow.FixedSizeListOf(VectorDimension, arrow.PrimitiveTypes.Float32)
+	vectorListType := arrow.FixedSizeListOf(int32(width), arrow.PrimitiveTypes.Float32)
 	vectorArray := array.NewFixedSizeListData(
 		array.NewData(vectorListType, len(chunks), []*memory.Buffer{nil},
 			[]arrow.ArrayData{vectorFloat32Array.Data()}, 0, 0),
@@ -293,6 +677,23 @@ func (s *LanceDBStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float6
 		headingArray,
 		headingLevelArray,
 		parentHeadingArray,
+		docCommentArray,
+		docSummaryArray,
+		attributesArray,
+		nameArray,
+		qualifiedNameArray,
+		kindArray,
+		visibilityArray,
+		signatureArray,
+		receiverArray,
+		callsArray,
+		contentHashArray,
+		repoArray,
+		submoduleArray,
+		duplicateLocationsArray,
+		embeddingModelArray,
+		isTestArray,
+		isGeneratedArray,
 		embeddingTypeArray,
 		vectorArray,
 	}
@@ -316,6 +717,7 @@ func (s *LanceDBStore) OpenTable() error {
 	if err != nil {
 		return fmt.Errorf("failed to open table: %w", err)
 	}
+	s.detectWidth(s.table)
 
 	return nil
 }
@@ -327,8 +729,9 @@ func (s *LanceDBStore) Search(queryVector []float64, limit int, filter string) (
 	}
 
 	// Convert float64 query vector to fixed-size float32 slice with padding
-	queryVectorFloat32 := make([]float32, VectorDimension)
-	for i := 0; i < VectorDimension; i++ {
+	width := s.VectorWidth()
+	queryVectorFloat32 := make([]float32, width)
+	for i := 0; i < width; i++ {
 		if i < len(queryVector) {
 			queryVectorFloat32[i] = float32(queryVector[i])
 		} else {
@@ -336,6 +739,13 @@ func (s *LanceDBStore) Search(queryVector []float64, limit int, filter string) (
 		}
 	}
 
+	if s.quantization != "" {
+		results, ok, err := s.quantizedSearch(queryVectorFloat32, limit, filter)
+		if err != nil || ok {
+			return results, err
+		}
+	}
+
 	ctx := context.Background()
 	var (
 		results []map[string]interface{}
@@ -350,6 +760,9 @@ func (s *LanceDBStore) Search(queryVector []float64, limit int, filter string) (
 	if err != nil {
 		return nil, fmt.Errorf("failed to search: %w", err)
 	}
+	if err := s.decodeRows(results); err != nil {
+		return nil, err
+	}
 
 	return results, nil
 }
@@ -0,0 +1,82 @@
+package storage
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// LockFileName is the file in the index directory that the process changing
+// the index holds an advisory lock on
+const LockFileName = "index.lock"
+
+// ErrLocked is returned by TryLock when another process holds the lock
+var ErrLocked = errors.New("the index is being updated by another process")
+
+// IndexLock is the lock on an index held by the one process that may change
+// it at a time
+type IndexLock struct {
+	file *os.File
+}
+
+// TryLock takes the lock on the index, or fails with an error wrapping
+// ErrLocked when another process holds it. The operating system releases the
+// lock when its holder exits, so a crashed process does not leave the index
+// locked; a generation it was swapping in is swapped in once the lock is
+// taken.
+func (s *LanceDBStore) TryLock() (*IndexLock, error) {
+	if s.readOnly {
+		return nil, ErrReadOnly
+	}
+	file, err := os.OpenFile(filepath.Join(s.dbDir, LockFileName), os.O_RDWR|os.O_CREATE, 0644)
+	if err != nil {
+		return nil, fmt.Errorf("failed to open the index lock: %w", err)
+	}
+	if err := lockFile(file); err != nil {
+		holder := lockHolder(file)
+		file.Close()
+		switch {
+		case errors.Is(err, ErrLocked) && holder != 0:
+			return nil, fmt.Errorf("%w (pid %d)", ErrLocked, holder)
+		case errors.Is(err, ErrLocked):
+			return nil, err
+		}
+		return nil, fmt.Errorf("failed to lock the index: %w", err)
+	}
+
+	// The holder's pid tells a process that finds the index locked who has it
+	pid := []byte(strconv.Itoa(os.Getpid()) + "\n")
+	if err := file.Truncate(0); err == nil {
+		file.WriteAt(pid, 0)
+	}
+	lock := &IndexLock{file: file}
+
+	if err := s.recoverSwap(); err != nil {
+		lock.Unlock()
+		return nil, err
+	}
+	return lock, nil
+}
+
+// Unlock releases the lock
+func (l *IndexLock) Unlock() error {
+	err := unlockFile(l.file)
+	if closeErr := l.file.Close(); err == nil {
+		err = closeErr
+	}
+	return err
+}
+
+// lockHolder returns the pid recorded in a lock file, or 0
+func lockHolder(file *os.File) int {
+	data := make([]byte, 32)
+	n, _ := file.ReadAt(data, 0)
+	pid, err := strconv.Atoi(strings.TrimSpace(string(data[:n])))
+	if err != nil {
+		return 0
+	}
+	return pid
+}
@@ -0,0 +1,54 @@
+package storage
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"strings"
+	"testing"
+)
+
+func TestTryLockReportsHolder(t *testing.T) {
+	store, err := NewLanceDBStore(t.TempDir())
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+
+	lock, err := store.TryLock()
+	if err != nil {
+		t.Fatalf("TryLock() error = %v", err)
+	}
+	// A second lock file description conflicts even within one process
+	if _, err := store.TryLock(); !errors.Is(err, ErrLocked) {
+		t.Fatalf("second TryLock() error = %v, want ErrLocked", err)
+	} else if holder := fmt.Sprintf("pid %d", os.Getpid()); !strings.Contains(err.Error(), holder) {
+		t.Errorf("second TryLock() error = %q, want it to name the holder, %s", err, holder)
+	}
+
+	if err := lock.Unlock(); err != nil {
+		t.Fatalf("Unlock() error = %v", err)
+	}
+	lock, err = store.TryLock()
+	if err != nil {
+		t.Fatalf("TryLock() after Unlock() error = %v", err)
+	}
+	lock.Unlock()
+}
+
+func TestTryLockOnReadOnlyStore(t *testing.T) {
+	root := t.TempDir()
+	store, err := NewLanceDBStore(root)
+	if err != nil {
+		t.Fatal(err)
+	}
+	store.Close()
+	readOnly, err := NewReadOnlyLanceDBStore(root)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer readOnly.Close()
+	if _, err := readOnly.TryLock(); !errors.Is(err, ErrReadOnly) {
+		t.Errorf("TryLock() error = %v, want ErrReadOnly", err)
+	}
+}
@@ -3,31 +3,161 @@ package storage
 import (
 	"encoding/json"
 	"fmt"
+	"math"
 	"os"
 	"path/filepath"
 	"time"
 )
 
-const metadataFileName = "metadata.json"
+// MetadataFileName is the file in the index directory that holds the metadata
+const MetadataFileName = "metadata.json"
+
+// SchemaVersion is the version of the chunk table's columns and of the
+// metadata. It is raised whenever either changes, and an index written with
+// another version is rebuilt rather than read.
+const SchemaVersion = 7
+
+// EmbeddingModels identifies the models an index was embedded with; vectors
+// from different models cannot be compared
+type EmbeddingModels struct {
+	CodeModel string `json:"code_model"`
+	TextModel string `json:"text_model"`
+	Dimension int    `json:"dimension"`
+}
+
+// VectorFingerprint describes the vectors a model returns: their length and
+// whether they are normalized to unit length. A model that is configured by
+// the same name but returns other vectors, as another build of it or another
+// pooling, gives similarities that mean nothing against the index's.
+type VectorFingerprint struct {
+	Dimension  int  `json:"dimension"`
+	Normalized bool `json:"normalized"`
+}
+
+// FingerprintOf returns the fingerprint of a vector
+func FingerprintOf(vector []float64) VectorFingerprint {
+	var sum float64
+	for _, v := range vector {
+		sum += v * v
+	}
+	return VectorFingerprint{Dimension: len(vector), Normalized: math.Abs(math.Sqrt(sum)-1) < 1e-3}
+}
+
+// String describes the fingerprint, as "768 dimensions, normalized"
+func (f VectorFingerprint) String() string {
+	if f.Normalized {
+		return fmt.Sprintf("%d dimensions, normalized", f.Dimension)
+	}
+	return fmt.Sprintf("%d dimensions, not normalized", f.Dimension)
+}
+
+// TokenUsage counts what indexing sent an embedding provider: its requests
+// and the texts and estimated tokens they carried
+type TokenUsage struct {
+	Requests int64 `json:"requests"`
+	Texts    int64 `json:"texts"`
+	Tokens   int64 `json:"tokens"`
+}
+
+// Add returns the sum of two usages
+func (u TokenUsage) Add(other TokenUsage) TokenUsage {
+	return TokenUsage{Requests: u.Requests + other.Requests, Texts: u.Texts + other.Texts, Tokens: u.Tokens + other.Tokens}
+}
 
 // IndexMetadata tracks indexing state
 type IndexMetadata struct {
 	LastIndexTime time.Time              `json:"last_index_time"`
-	FileModTimes  map[string]time.Time   `json:"file_mod_times"` // file path -> modification time
+	FileModTimes  map[string]time.Time   `json:"file_mod_times"`         // file path -> modification time
+	FileHashes    map[string]string      `json:"file_hashes,omitempty"`  // file path -> content hash
+	Commit        string                 `json:"commit,omitempty"`       // git commit checked out when last indexed
+	RepoCommits   map[string]string      `json:"repo_commits,omitempty"` // workspace repository -> commit checked out when last indexed
+	Root          string                 `json:"root,omitempty"`         // directory the file paths are under
+	SchemaVersion int                    `json:"schema_version,omitempty"`
+	Models        *EmbeddingModels       `json:"models,omitempty"`
+
+	// Fingerprints of the key chunk text is encrypted with, if any, and of
+	// the configuration that shaped the chunks
+	KeyFingerprint    string `json:"key_fingerprint,omitempty"`
+	ConfigFingerprint string `json:"config_fingerprint,omitempty"`
+
+	// Whether chunks were embedded with the imports they use, which their
+	// content hashes then cover as well
+	EmbedImports bool `json:"embed_imports,omitempty"`
+
+	// Files of the base index whose chunks an overlay index replaces
+	Shadowed []string `json:"shadowed,omitempty"`
+
+	// What indexing has sent each provider and model, as provider/model, over
+	// every run, including runs that rebuilt the index
+	Usage map[string]TokenUsage `json:"usage,omitempty"`
+
+	// The fingerprints of the vectors the models returned, by embedding type,
+	// which the vectors of queries and of appended chunks must match
+	Vectors map[string]VectorFingerprint `json:"vectors,omitempty"`
+}
+
+// VectorMismatch explains how vectors of an embedding type with the given
+// fingerprint differ from those the index holds, or returns "" when they can
+// be compared or the index does not record its vectors' fingerprint
+func (m *IndexMetadata) VectorMismatch(embeddingType string, fingerprint VectorFingerprint) string {
+	recorded, ok := m.Vectors[embeddingType]
+	if !ok || recorded == fingerprint {
+		return ""
+	}
+	return fmt.Sprintf("the configured %s model returns vectors of %s but the index holds vectors of %s", embeddingType, fingerprint, recorded)
+}
+
+// NewIndexMetadata returns the metadata of an empty index
+func NewIndexMetadata() *IndexMetadata {
+	return &IndexMetadata{
+		FileModTimes:  make(map[string]time.Time),
+		FileHashes:    make(map[string]string),
+		SchemaVersion: SchemaVersion,
+	}
+}
+
+// IsEmpty reports whether nothing has been indexed yet
+func (m *IndexMetadata) IsEmpty() bool {
+	return m.LastIndexTime.IsZero() && len(m.FileModTimes) == 0
+}
+
+// Incompatibility explains why an index cannot be searched or updated with
+// the given models, or returns "" when it can
+func (m *IndexMetadata) Incompatibility(models EmbeddingModels) string {
+	if m.IsEmpty() {
+		return ""
+	}
+	switch {
+	case m.SchemaVersion < SchemaVersion:
+		return fmt.Sprintf("the index was created by an older version of code-scout (format %d, current %d)", m.SchemaVersion, SchemaVersion)
+	case m.SchemaVersion > SchemaVersion:
+		return fmt.Sprintf("the index was created by a newer version of code-scout (format %d, this version reads %d)", m.SchemaVersion, SchemaVersion)
+	case m.Models == nil:
+		return "the index does not record its embedding models"
+	case *m.Models != models:
+		return fmt.Sprintf("the index was embedded with %s/%s (%d dimensions) but the configuration uses %s/%s (%d dimensions)",
+			m.Models.CodeModel, m.Models.TextModel, m.Models.Dimension, models.CodeModel, models.TextModel, models.Dimension)
+	}
+	return ""
+}
+
+// ModelsChanged reports whether the index was embedded with other models
+// than the given ones but is otherwise current, so its chunks can be embedded
+// again without being chunked again
+func (m *IndexMetadata) ModelsChanged(models EmbeddingModels) bool {
+	return !m.IsEmpty() && m.SchemaVersion == SchemaVersion && m.Models != nil &&
+		*m.Models != models && m.Models.Dimension == models.Dimension
 }
 
 // LoadMetadata loads metadata from disk
 func (s *LanceDBStore) LoadMetadata() (*IndexMetadata, error) {
-	metadataPath := filepath.Join(s.dbDir, metadataFileName)
+	metadataPath := filepath.Join(s.dbDir, MetadataFileName)
 	
 	data, err := os.ReadFile(metadataPath)
 	if err != nil {
 		if os.IsNotExist(err) {
 			// Return empty metadata if file doesn't exist
-			return &IndexMetadata{
-				LastIndexTime: time.Time{},
-				FileModTimes:  make(map[string]time.Time),
-			}, nil
+			return NewIndexMetadata(), nil
 		}
 		return nil, fmt.Errorf("failed to read metadata: %w", err)
 	}
@@ -40,20 +170,32 @@ func (s *LanceDBStore) LoadMetadata() (*IndexMetadata, error) {
 	if metadata.FileModTimes == nil {
 		metadata.FileModTimes = make(map[string]time.Time)
 	}
+	if metadata.FileHashes == nil {
+		metadata.FileHashes = make(map[string]string)
+	}
 
 	return &metadata, nil
 }
 
 // SaveMetadata saves metadata to disk
 func (s *LanceDBStore) SaveMetadata(metadata *IndexMetadata) error {
-	metadataPath := filepath.Join(s.dbDir, metadataFileName)
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	metadataPath := filepath.Join(s.dbDir, MetadataFileName)
 	
 	data, err := json.MarshalIndent(metadata, "", "  ")
 	if err != nil {
 		return fmt.Errorf("failed to marshal metadata: %w", err)
 	}
 
-	if err := os.WriteFile(metadataPath, data, 0644); err != nil {
+	// The metadata is replaced with a rename, so a crash never leaves it half
+	// written
+	tmpPath := metadataPath + ".tmp"
+	if err := os.WriteFile(tmpPath, data, 0644); err != nil {
+		return fmt.Errorf("failed to write metadata: %w", err)
+	}
+	if err := os.Rename(tmpPath, metadataPath); err != nil {
 		return fmt.Errorf("failed to write metadata: %w", err)
 	}
 
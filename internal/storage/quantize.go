@@ -0,0 +1,294 @@
+package storage
+
+import (
+	"context"
+	"encoding/gob"
+	"fmt"
+	"math"
+	"math/bits"
+	"os"
+	"path/filepath"
+	"sort"
+)
+
+const (
+	// QuantizeInt8 keeps a byte per dimension and a scale per vector, a
+	// quarter of the size of the float32 vectors
+	QuantizeInt8 = "int8"
+	// QuantizeBinary keeps the sign of each dimension as a bit, a 32nd of the
+	// size of the float32 vectors
+	QuantizeBinary = "binary"
+	// QuantizedFileName is the file in the index directory that holds the
+	// quantized vectors
+	QuantizedFileName = "vectors.quantized"
+)
+
+// Candidates rescored on the full vectors for each result asked for; binary
+// codes rank more coarsely, so more of their candidates are rescored
+const (
+	int8Candidates   = 4
+	binaryCandidates = 16
+)
+
+// quantizedVectors holds a quantized copy of the vector of every chunk of the
+// index. Searches scan it to find candidates and rescore only those on the
+// full vectors, so they read a fraction of the vectors a full search does.
+type quantizedVectors struct {
+	Mode   string
+	Dim    int       // Dimensions of the vectors quantized
+	IDs    []string  // Chunk IDs
+	Hashes []string  // Content hashes the vectors were embedded from
+	Scales []float32 // With int8, what each vector's bytes are multiplied by
+	Codes  []byte    // The vectors' codes, one after another
+}
+
+// SetQuantization makes searches scan vectors quantized with mode, one of
+// QuantizeInt8 and QuantizeBinary, and rescore the best of them on the full
+// vectors; "" searches the full vectors alone. The quantized vectors are
+// brought up to date whenever chunks are committed.
+func (s *LanceDBStore) SetQuantization(mode string) error {
+	switch mode {
+	case "", QuantizeInt8, QuantizeBinary:
+	default:
+		return fmt.Errorf("unknown quantization %q", mode)
+	}
+	s.quantization = mode
+	s.quantized = nil
+	return nil
+}
+
+// codeSize returns the size in bytes of the code of one vector of dim
+// dimensions
+func codeSize(mode string, dim int) int {
+	if mode == QuantizeBinary {
+		return (dim + 7) / 8
+	}
+	return dim
+}
+
+// quantize returns the code of a vector of dim dimensions and, with int8, its
+// scale
+func quantize(mode string, dim int, vector []float64) ([]byte, float32) {
+	code := make([]byte, codeSize(mode, dim))
+	if mode == QuantizeBinary {
+		for i, v := range vector {
+			if v > 0 && i < dim {
+				code[i/8] |= 1 << (i % 8)
+			}
+		}
+		return code, 0
+	}
+	var maxAbs float64
+	for _, v := range vector {
+		maxAbs = math.Max(maxAbs, math.Abs(v))
+	}
+	if maxAbs == 0 {
+		return code, 0
+	}
+	scale := maxAbs / 127
+	for i, v := range vector {
+		if i < dim {
+			code[i] = byte(int8(math.Round(v / scale)))
+		}
+	}
+	return code, float32(scale)
+}
+
+// approximateDistance estimates the distance between the query and the i-th
+// quantized vector: the squared L2 distance to the dequantized int8 vector, or
+// the Hamming distance between the signs of the query and the binary code
+func (q *quantizedVectors) approximateDistance(i int, query []float32, querySigns []byte) float64 {
+	size := codeSize(q.Mode, q.Dim)
+	code := q.Codes[i*size : (i+1)*size]
+	if q.Mode == QuantizeBinary {
+		distance := 0
+		for j, b := range code {
+			distance += bits.OnesCount8(b ^ querySigns[j])
+		}
+		return float64(distance)
+	}
+	scale := q.Scales[i]
+	var distance float64
+	for j, b := range code {
+		d := float64(scale)*float64(int8(b)) - float64(query[j])
+		distance += d * d
+	}
+	return distance
+}
+
+// loadQuantized reads the quantized vectors of the store, returning nil when
+// there are none or they were quantized another way
+func (s *LanceDBStore) loadQuantized(mode string, dim int) (*quantizedVectors, error) {
+	file, err := os.Open(filepath.Join(s.dbDir, QuantizedFileName))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, fmt.Errorf("failed to read quantized vectors: %w", err)
+	}
+	defer file.Close()
+	var q quantizedVectors
+	if err := gob.NewDecoder(file).Decode(&q); err != nil {
+		return nil, fmt.Errorf("failed to read quantized vectors: %w", err)
+	}
+	if q.Mode != mode || q.Dim != dim || len(q.Codes) != len(q.IDs)*codeSize(mode, dim) {
+		return nil, nil
+	}
+	return &q, nil
+}
+
+// updateQuantized brings the quantized vectors of the store up to date with
+// its chunks, quantizing only the vectors of chunks that are new or changed
+// since they were last quantized, or removes them without a quantization
+func (s *LanceDBStore) updateQuantized() error {
+	path := filepath.Join(s.dbDir, QuantizedFileName)
+	if s.quantization == "" {
+		if err := os.Remove(path); err != nil && !os.IsNotExist(err) {
+			return fmt.Errorf("failed to remove quantized vectors: %w", err)
+		}
+		return nil
+	}
+	if err := s.ensureTable(); err != nil {
+		return err
+	}
+	dim := s.VectorWidth()
+	previous, err := s.loadQuantized(s.quantization, dim)
+	if err != nil || previous == nil {
+		previous = &quantizedVectors{Mode: s.quantization, Dim: dim}
+	}
+	known := make(map[string]int, len(previous.IDs))
+	for i, id := range previous.IDs {
+		known[id+"\x00"+previous.Hashes[i]] = i
+	}
+
+	ctx := context.Background()
+	rows, err := s.table.SelectWithColumns(ctx, []string{"chunk_id", "content_hash"})
+	if err != nil {
+		return fmt.Errorf("failed to read chunks: %w", err)
+	}
+	size := codeSize(s.quantization, dim)
+	q := &quantizedVectors{Mode: s.quantization, Dim: dim}
+	var missing []string
+	for _, row := range rows {
+		id, _ := row["chunk_id"].(string)
+		hash, _ := row["content_hash"].(string)
+		i, ok := known[id+"\x00"+hash]
+		if !ok {
+			missing = append(missing, id)
+			continue
+		}
+		q.IDs = append(q.IDs, id)
+		q.Hashes = append(q.Hashes, hash)
+		q.Scales = append(q.Scales, previous.Scales[i])
+		q.Codes = append(q.Codes, previous.Codes[i*size:(i+1)*size]...)
+	}
+	for start := 0; start < len(missing); start += relocateBatch {
+		end := min(start+relocateBatch, len(missing))
+		rows, err := s.table.SelectWithFilter(ctx, columnFilter("chunk_id", missing[start:end]))
+		if err != nil {
+			return fmt.Errorf("failed to read vectors: %w", err)
+		}
+		for _, row := range rows {
+			id, _ := row["chunk_id"].(string)
+			hash, _ := row["content_hash"].(string)
+			code, scale := quantize(s.quantization, dim, toFloat64s(row["vector"]))
+			q.IDs = append(q.IDs, id)
+			q.Hashes = append(q.Hashes, hash)
+			q.Scales = append(q.Scales, scale)
+			q.Codes = append(q.Codes, code...)
+		}
+	}
+
+	// Written with a rename, so a search never reads half of the file
+	tmpPath := path + ".tmp"
+	file, err := os.Create(tmpPath)
+	if err != nil {
+		return fmt.Errorf("failed to write quantized vectors: %w", err)
+	}
+	if err := gob.NewEncoder(file).Encode(q); err != nil {
+		file.Close()
+		return fmt.Errorf("failed to write quantized vectors: %w", err)
+	}
+	if err := file.Close(); err != nil {
+		return fmt.Errorf("failed to write quantized vectors: %w", err)
+	}
+	if err := os.Rename(tmpPath, path); err != nil {
+		return fmt.Errorf("failed to write quantized vectors: %w", err)
+	}
+	s.quantized = q
+	return nil
+}
+
+// quantizedSearch finds the chunks nearest the query among the quantized
+// vectors and rescores the best candidates on their full vectors. It returns
+// false when there are no quantized vectors, or when the filter leaves fewer
+// than limit candidates, so the caller searches the full vectors instead.
+func (s *LanceDBStore) quantizedSearch(query []float32, limit int, filter string) ([]map[string]interface{}, bool, error) {
+	if s.quantized == nil {
+		q, err := s.loadQuantized(s.quantization, len(query))
+		if err != nil || q == nil {
+			return nil, false, err
+		}
+		s.quantized = q
+	}
+	q := s.quantized
+
+	var querySigns []byte
+	candidates := limit * int8Candidates
+	if q.Mode == QuantizeBinary {
+		querySigns = make([]byte, codeSize(QuantizeBinary, q.Dim))
+		for i, v := range query {
+			if v > 0 {
+				querySigns[i/8] |= 1 << (i % 8)
+			}
+		}
+		candidates = limit * binaryCandidates
+	}
+	order := make([]int, len(q.IDs))
+	distances := make([]float64, len(q.IDs))
+	for i := range order {
+		order[i] = i
+		distances[i] = q.approximateDistance(i, query, querySigns)
+	}
+	sort.Slice(order, func(a, b int) bool { return distances[order[a]] < distances[order[b]] })
+	order = order[:min(candidates, len(order))]
+	if len(order) == 0 {
+		return nil, false, nil
+	}
+
+	ids := make([]string, len(order))
+	for i, index := range order {
+		ids[i] = q.IDs[index]
+	}
+	candidateFilter := columnFilter("chunk_id", ids)
+	if filter != "" {
+		candidateFilter += " AND (" + filter + ")"
+	}
+	rows, err := s.table.SelectWithFilter(context.Background(), candidateFilter)
+	if err != nil {
+		return nil, false, fmt.Errorf("failed to search: %w", err)
+	}
+	if len(rows) < limit {
+		return nil, false, nil
+	}
+
+	// Rescored with the squared L2 distance full searches report
+	for _, row := range rows {
+		var distance float64
+		for i, v := range toFloat64s(row["vector"]) {
+			if i < len(query) {
+				d := v - float64(query[i])
+				distance += d * d
+			}
+		}
+		row["_distance"] = distance
+	}
+	sort.SliceStable(rows, func(a, b int) bool {
+		return rows[a]["_distance"].(float64) < rows[b]["_distance"].(float64)
+	})
+	rows = rows[:limit]
+	if err := s.decodeRows(rows); err != nil {
+		return nil, false, err
+	}
+	return rows, true, nil
+}
@@ -0,0 +1,140 @@
+package storage
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// reembedDirName is the directory in the index directory that holds the
+// chunks being re-embedded
+const reembedDirName = "reembed"
+
+// Reembed embeds every chunk of the index again with embed, batchSize chunks
+// at a time, into a staging table beside the index. Once all are embedded,
+// the staging table and metadata replace the index's own; until then the
+// index keeps its old vectors and can be searched with the models that made
+// them. embed returns a vector for each chunk it is given, in order. A
+// re-embedding that was interrupted or failed starts over.
+func (s *LanceDBStore) Reembed(metadata *IndexMetadata, batchSize int, embed func([]chunker.Chunk) ([][]float64, error)) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if batchSize <= 0 {
+		batchSize = relocateBatch
+	}
+
+	stagingDir := filepath.Join(s.dbDir, reembedDirName)
+	if err := os.RemoveAll(stagingDir); err != nil {
+		return fmt.Errorf("failed to clear the staging index: %w", err)
+	}
+	staging, err := openDBDir(stagingDir)
+	if err != nil {
+		return err
+	}
+	defer os.RemoveAll(stagingDir)
+	defer staging.Close()
+	staging.aead, staging.keyFingerprint = s.aead, s.keyFingerprint
+	staging.dimension = s.VectorWidth()
+
+	if err := s.ensureTable(); err != nil {
+		return err
+	}
+	rows, err := s.table.SelectWithFilter(context.Background(), "chunk_id IS NOT NULL")
+	if err != nil {
+		return fmt.Errorf("failed to read chunks: %w", err)
+	}
+	if err := s.decodeRows(rows); err != nil {
+		return err
+	}
+
+	for start := 0; start < len(rows); start += batchSize {
+		end := min(start+batchSize, len(rows))
+		chunks := make([]chunker.Chunk, 0, end-start)
+		for _, row := range rows[start:end] {
+			chunk, _ := chunkFromRow(row)
+			chunks = append(chunks, chunk)
+		}
+		vectors, err := embed(chunks)
+		if err != nil {
+			return err
+		}
+		if len(vectors) != len(chunks) {
+			return fmt.Errorf("embedded %d of %d chunks", len(vectors), len(chunks))
+		}
+		if err := staging.addChunks(chunks, vectors); err != nil {
+			return err
+		}
+	}
+
+	if err := staging.SaveMetadata(metadata); err != nil {
+		return err
+	}
+	return s.replaceWith(staging)
+}
+
+// replaceWith moves the chunks and metadata of other, a store staged in a
+// directory inside this one, into this store in place of its own. The tables
+// are swapped by renaming their directories, so a search never reads a mix of
+// old and new chunks. other is marked committed first, so a swap that is
+// interrupted is finished by the next process to lock the index.
+func (s *LanceDBStore) replaceWith(other *LanceDBStore) error {
+	// The staged store always has a table, so an empty one replaces the chunks
+	if err := other.ensureTable(); err != nil {
+		return err
+	}
+	other.quantization = s.quantization
+	if err := other.updateQuantized(); err != nil {
+		return err
+	}
+	for _, store := range []*LanceDBStore{s, other} {
+		if store.table != nil {
+			if err := store.table.Close(); err != nil {
+				return fmt.Errorf("failed to close table: %w", err)
+			}
+			store.table = nil
+		}
+	}
+	if err := os.WriteFile(filepath.Join(other.dbDir, committedMarker), nil, 0644); err != nil {
+		return fmt.Errorf("failed to commit chunks: %w", err)
+	}
+	return s.finishSwap(other.dbDir)
+}
+
+// finishSwap moves the committed store staged in stagingDir into this store,
+// picking up where an interrupted swap stopped, and removes stagingDir
+func (s *LanceDBStore) finishSwap(stagingDir string) error {
+	tableDir := filepath.Join(s.dbDir, DefaultTableName+".lance")
+	stagedTable := filepath.Join(stagingDir, DefaultTableName+".lance")
+	if _, err := os.Stat(stagedTable); err == nil {
+		// Quantized vectors of the old chunks are never kept with the new ones
+		quantized := filepath.Join(s.dbDir, QuantizedFileName)
+		if err := os.Rename(filepath.Join(stagingDir, QuantizedFileName), quantized); os.IsNotExist(err) {
+			err = os.Remove(quantized)
+			if err != nil && !os.IsNotExist(err) {
+				return fmt.Errorf("failed to replace quantized vectors: %w", err)
+			}
+		} else if err != nil {
+			return fmt.Errorf("failed to replace quantized vectors: %w", err)
+		}
+		s.quantized = nil
+		retired := filepath.Join(stagingDir, "retired.lance")
+		if err := os.Rename(tableDir, retired); err != nil && !os.IsNotExist(err) {
+			return fmt.Errorf("failed to replace chunks: %w", err)
+		}
+		if err := os.Rename(stagedTable, tableDir); err != nil {
+			return fmt.Errorf("failed to replace chunks: %w", err)
+		}
+	}
+	stagedMetadata := filepath.Join(stagingDir, MetadataFileName)
+	if err := os.Rename(stagedMetadata, filepath.Join(s.dbDir, MetadataFileName)); err != nil && !os.IsNotExist(err) {
+		return fmt.Errorf("failed to replace metadata: %w", err)
+	}
+	if err := os.RemoveAll(stagingDir); err != nil {
+		return fmt.Errorf("failed to remove the staged index: %w", err)
+	}
+	return nil
+}
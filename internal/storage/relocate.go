@@ -0,0 +1,80 @@
+package storage
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// relocateBatch caps the chunks rewritten in one insert
+const relocateBatch = 1000
+
+// Relocate moves an index built in another directory, such as on a CI
+// machine, to root: the file paths of its chunks and metadata are rewritten
+// relative to root and the metadata is saved. Chunks keep their embeddings.
+func (s *LanceDBStore) Relocate(metadata *IndexMetadata, root string) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	from := metadata.Root
+	if from == "" || from == root {
+		metadata.Root = root
+		return s.SaveMetadata(metadata)
+	}
+	move := func(path string) string {
+		rel, err := filepath.Rel(from, path)
+		if err != nil {
+			return path
+		}
+		return filepath.Join(root, rel)
+	}
+
+	if err := s.ensureTable(); err != nil {
+		return err
+	}
+	ctx := context.Background()
+	rows, err := s.table.SelectWithFilter(ctx, "chunk_id IS NOT NULL")
+	if err != nil {
+		return fmt.Errorf("failed to read chunks: %w", err)
+	}
+	if err := s.decodeRows(rows); err != nil {
+		return err
+	}
+
+	chunks := make([]chunker.Chunk, len(rows))
+	vectors := make([][]float64, len(rows))
+	for i, row := range rows {
+		chunks[i], vectors[i] = chunkFromRow(row)
+		chunks[i].FilePath = move(chunks[i].FilePath)
+		if locations := parseLocations(chunks[i].Metadata["duplicate_locations"]); len(locations) > 0 {
+			for j := range locations {
+				locations[j].path = move(locations[j].path)
+			}
+			chunks[i].Metadata["duplicate_locations"] = formatLocations(locations)
+		}
+	}
+
+	if err := s.table.Delete(ctx, "chunk_id IS NOT NULL"); err != nil {
+		return fmt.Errorf("failed to delete chunks: %w", err)
+	}
+	for start := 0; start < len(chunks); start += relocateBatch {
+		end := min(start+relocateBatch, len(chunks))
+		if err := s.addChunks(chunks[start:end], vectors[start:end]); err != nil {
+			return err
+		}
+	}
+
+	modTimes := make(map[string]time.Time, len(metadata.FileModTimes))
+	for path, modTime := range metadata.FileModTimes {
+		modTimes[move(path)] = modTime
+	}
+	hashes := make(map[string]string, len(metadata.FileHashes))
+	for path, hash := range metadata.FileHashes {
+		hashes[move(path)] = hash
+	}
+	metadata.FileModTimes, metadata.FileHashes, metadata.Root = modTimes, hashes, root
+	return s.SaveMetadata(metadata)
+}
@@ -0,0 +1,323 @@
+package storage
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/apache/arrow/go/v17/arrow"
+	"github.com/apache/arrow/go/v17/arrow/array"
+	"github.com/apache/arrow/go/v17/arrow/memory"
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/lancedb/lancedb-go/pkg/contracts"
+	"github.com/lancedb/lancedb-go/pkg/lancedb"
+)
+
+const (
+	// SpacesFileName is the file in the index directory that records the
+	// models each embedding space was embedded with
+	SpacesFileName = "spaces.json"
+	// spaceTablePrefix starts the name of the table of an embedding space
+	spaceTablePrefix = "space_"
+)
+
+// An embedding space holds a second set of vectors for the chunks of an
+// index, made with other models, in a table of its own beside the chunks.
+// A space row holds only a chunk's ID, content hash, and vector, so a space
+// costs little more than its vectors, and the chunks' text is stored once.
+// Spaces live outside the index's generations, so indexing leaves them as
+// they are; a space of chunks that changed since is brought up to date by
+// embedding it again, which embeds only those chunks.
+
+// spaceSchema returns the columns of a space's table of vectors of width
+// dimensions
+func spaceSchema(width int) *arrow.Schema {
+	return arrow.NewSchema([]arrow.Field{
+		{Name: "chunk_id", Type: arrow.BinaryTypes.String, Nullable: false},
+		{Name: "content_hash", Type: arrow.BinaryTypes.String, Nullable: true},
+		{Name: "embedding_type", Type: arrow.BinaryTypes.String, Nullable: false}, // "code" or "docs"
+		{Name: "vector", Type: arrow.FixedSizeListOf(int32(width), arrow.PrimitiveTypes.Float32), Nullable: false},
+	}, nil)
+}
+
+// spaceWidth returns the number of dimensions of the vectors of an embedding
+// space: those of the vectors its table holds, if it holds any, else those
+// its models were recorded with, as the dimensions of an index set its
+// table's. A space recorded without dimensions stores VectorDimension.
+func (s *LanceDBStore) spaceWidth(space string, table contracts.ITable) int {
+	if table != nil {
+		if rows, err := table.SelectWithLimit(context.Background(), 1, 0); err == nil && len(rows) > 0 {
+			if vector := toFloat64s(rows[0]["vector"]); len(vector) > 0 {
+				return len(vector)
+			}
+		}
+	}
+	if spaces, err := s.LoadSpaces(); err == nil && spaces[space].Dimension > 0 {
+		return spaces[space].Dimension
+	}
+	return VectorDimension
+}
+
+// LoadSpaces returns the models each embedding space of the index was
+// embedded with, by space name
+func (s *LanceDBStore) LoadSpaces() (map[string]EmbeddingModels, error) {
+	spaces := make(map[string]EmbeddingModels)
+	data, err := os.ReadFile(filepath.Join(s.dbDir, SpacesFileName))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return spaces, nil
+		}
+		return nil, fmt.Errorf("failed to read embedding spaces: %w", err)
+	}
+	if err := json.Unmarshal(data, &spaces); err != nil {
+		return nil, fmt.Errorf("failed to parse embedding spaces: %w", err)
+	}
+	return spaces, nil
+}
+
+// SaveSpaces records the models each embedding space was embedded with
+func (s *LanceDBStore) SaveSpaces(spaces map[string]EmbeddingModels) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	data, err := json.MarshalIndent(spaces, "", "  ")
+	if err != nil {
+		return fmt.Errorf("failed to marshal embedding spaces: %w", err)
+	}
+	path := filepath.Join(s.dbDir, SpacesFileName)
+	tmpPath := path + ".tmp"
+	if err := os.WriteFile(tmpPath, data, 0644); err != nil {
+		return fmt.Errorf("failed to write embedding spaces: %w", err)
+	}
+	if err := os.Rename(tmpPath, path); err != nil {
+		return fmt.Errorf("failed to write embedding spaces: %w", err)
+	}
+	return nil
+}
+
+// DropSpace deletes the vectors of an embedding space
+func (s *LanceDBStore) DropSpace(space string) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if err := os.RemoveAll(filepath.Join(s.dbDir, spaceTablePrefix+space+".lance")); err != nil {
+		return fmt.Errorf("failed to delete embedding space %s: %w", space, err)
+	}
+	return nil
+}
+
+// HasSpace reports whether an embedding space has any vectors
+func (s *LanceDBStore) HasSpace(space string) bool {
+	_, err := os.Stat(filepath.Join(s.dbDir, spaceTablePrefix+space+".lance"))
+	return err == nil
+}
+
+// SpaceHashes returns the content hash each chunk of an embedding space was
+// embedded from, by chunk ID, so chunks that have not changed since are not
+// embedded again
+func (s *LanceDBStore) SpaceHashes(space string) (map[string]string, error) {
+	hashes := make(map[string]string)
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, spaceTablePrefix+space)
+	if err != nil {
+		// The space has no vectors yet
+		return hashes, nil
+	}
+	defer table.Close()
+
+	rows, err := table.SelectWithFilter(ctx, "chunk_id IS NOT NULL")
+	if err != nil {
+		return nil, fmt.Errorf("failed to read embedding space %s: %w", space, err)
+	}
+	for _, row := range rows {
+		id, _ := row["chunk_id"].(string)
+		hash, _ := row["content_hash"].(string)
+		hashes[id] = hash
+	}
+	return hashes, nil
+}
+
+// StoreSpaceVectors stores the vectors of chunks in an embedding space,
+// creating its table if needed. Chunks already in the space are replaced.
+func (s *LanceDBStore) StoreSpaceVectors(space string, chunks []chunker.Chunk, vectors [][]float64) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if len(chunks) == 0 {
+		return nil
+	}
+
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, spaceTablePrefix+space)
+	if err != nil {
+		lanceSchema, err := lancedb.NewSchema(spaceSchema(s.spaceWidth(space, nil)))
+		if err != nil {
+			return fmt.Errorf("failed to create Lance schema: %w", err)
+		}
+		if table, err = s.conn.CreateTable(ctx, spaceTablePrefix+space, lanceSchema); err != nil {
+			return fmt.Errorf("failed to create embedding space %s: %w", space, err)
+		}
+	}
+	defer table.Close()
+
+	width := s.spaceWidth(space, table)
+	ids := make([]string, len(chunks))
+	hashes := make([]string, len(chunks))
+	embeddingTypes := make([]string, len(chunks))
+	allVectors := make([]float32, len(chunks)*width)
+	for i, chunk := range chunks {
+		ids[i] = chunk.ID
+		hashes[i] = chunk.Metadata["content_hash"]
+		embeddingTypes[i] = chunk.EmbeddingType
+		// Shorter vectors are padded with zeros, and vectors padded past the
+		// space's size lose only the padding, as the chunks' own do
+		for j, val := range vectors[i] {
+			if j >= width {
+				if val != 0 {
+					return fmt.Errorf("embedding space %s: vectors of %d dimensions are larger than the space stores (%d)", space, len(vectors[i]), width)
+				}
+				continue
+			}
+			allVectors[i*width+j] = float32(val)
+		}
+	}
+	for start := 0; start < len(ids); start += relocateBatch {
+		end := min(start+relocateBatch, len(ids))
+		if err := table.Delete(ctx, columnFilter("chunk_id", ids[start:end])); err != nil {
+			return fmt.Errorf("failed to replace vectors in embedding space %s: %w", space, err)
+		}
+	}
+
+	pool := memory.NewGoAllocator()
+	idBuilder := array.NewStringBuilder(pool)
+	idBuilder.AppendValues(ids, nil)
+	idArray := idBuilder.NewArray()
+	defer idArray.Release()
+
+	hashBuilder := array.NewStringBuilder(pool)
+	hashBuilder.AppendValues(hashes, nil)
+	hashArray := hashBuilder.NewArray()
+	defer hashArray.Release()
+
+	embeddingTypeBuilder := array.NewStringBuilder(pool)
+	embeddingTypeBuilder.AppendValues(embeddingTypes, nil)
+	embeddingTypeArray := embeddingTypeBuilder.NewArray()
+	defer embeddingTypeArray.Release()
+
+	vectorFloat32Builder := array.NewFloat32Builder(pool)
+	vectorFloat32Builder.AppendValues(allVectors, nil)
+	vectorFloat32Array := vectorFloat32Builder.NewArray()
+	defer vectorFloat32Array.Release()
+
+	vectorListType := arrow.FixedSizeListOf(int32(width), arrow.PrimitiveTypes.Float32)
+	vectorArray := array.NewFixedSizeListData(
+		array.NewData(vectorListType, len(chunks), []*memory.Buffer{nil},
+			[]arrow.ArrayData{vectorFloat32Array.Data()}, 0, 0),
+	)
+	defer vectorArray.Release()
+
+	columns := []arrow.Array{idArray, hashArray, embeddingTypeArray, vectorArray}
+	record := array.NewRecord(spaceSchema(width), columns, int64(len(chunks)))
+	defer record.Release()
+
+	if err := table.Add(ctx, record, nil); err != nil {
+		return fmt.Errorf("failed to add vectors to embedding space %s: %w", space, err)
+	}
+	return nil
+}
+
+// DeleteSpaceVectors deletes the vectors of the chunks with the given IDs
+// from an embedding space
+func (s *LanceDBStore) DeleteSpaceVectors(space string, ids []string) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if len(ids) == 0 {
+		return nil
+	}
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, spaceTablePrefix+space)
+	if err != nil {
+		// The space has no vectors yet, nothing to delete
+		return nil
+	}
+	defer table.Close()
+	for start := 0; start < len(ids); start += relocateBatch {
+		end := min(start+relocateBatch, len(ids))
+		if err := table.Delete(ctx, columnFilter("chunk_id", ids[start:end])); err != nil {
+			return fmt.Errorf("failed to delete vectors from embedding space %s: %w", space, err)
+		}
+	}
+	return nil
+}
+
+// SearchSpace performs vector similarity search in an embedding space and
+// returns the matching chunks, nearest first, each with the _distance of its
+// vector in the space. embeddingType limits the search to "code" or "docs"
+// vectors unless it is "", and filter applies to the chunks' own columns.
+// Since the chunks are filtered after the vectors are searched, limit
+// candidates are searched for and fewer chunks may be returned.
+func (s *LanceDBStore) SearchSpace(space string, queryVector []float64, limit int, embeddingType, filter string) ([]map[string]interface{}, error) {
+	if s.table == nil {
+		return nil, fmt.Errorf("table not initialized; call StoreChunks first")
+	}
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, spaceTablePrefix+space)
+	if err != nil {
+		return nil, fmt.Errorf("embedding space %s has no vectors; run 'code-scout index space %s'", space, space)
+	}
+	defer table.Close()
+
+	width := s.spaceWidth(space, table)
+	queryVectorFloat32 := make([]float32, width)
+	for i := 0; i < width && i < len(queryVector); i++ {
+		queryVectorFloat32[i] = float32(queryVector[i])
+	}
+	var matches []map[string]interface{}
+	if embeddingType != "" {
+		matches, err = table.VectorSearchWithFilter(ctx, "vector", queryVectorFloat32, limit, columnFilter("embedding_type", []string{embeddingType}))
+	} else {
+		matches, err = table.VectorSearch(ctx, "vector", queryVectorFloat32, limit)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to search embedding space %s: %w", space, err)
+	}
+	if len(matches) == 0 {
+		return nil, nil
+	}
+
+	ids := make([]string, 0, len(matches))
+	for _, match := range matches {
+		id, _ := match["chunk_id"].(string)
+		ids = append(ids, id)
+	}
+	chunkFilter := columnFilter("chunk_id", ids)
+	if filter != "" {
+		chunkFilter += " AND (" + filter + ")"
+	}
+	rows, err := s.table.SelectWithFilter(ctx, chunkFilter)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read the chunks of embedding space %s: %w", space, err)
+	}
+	if err := s.decodeRows(rows); err != nil {
+		return nil, err
+	}
+	byID := make(map[string]map[string]interface{}, len(rows))
+	for _, row := range rows {
+		id, _ := row["chunk_id"].(string)
+		byID[id] = row
+	}
+
+	results := make([]map[string]interface{}, 0, len(rows))
+	for i, match := range matches {
+		row, ok := byID[ids[i]]
+		if !ok {
+			continue
+		}
+		row["_distance"] = match["_distance"]
+		results = append(results, row)
+	}
+	return results, nil
+}
@@ -0,0 +1,575 @@
+package storage
+
+import (
+	"context"
+	"database/sql"
+	"encoding/binary"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math"
+	"slices"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// SQLiteDriver is the database/sql driver SQLite indexes are opened with. The
+// pure-Go driver of modernc.org/sqlite registers it in builds with -tags
+// sqlite, see sqlite_driver.go.
+const SQLiteDriver = "sqlite"
+
+// ErrNoSQLiteDriver is returned when a SQLite index is opened by a build
+// without a SQLite driver
+var ErrNoSQLiteDriver = errors.New("this build of code-scout cannot open SQLite indexes; run 'go get modernc.org/sqlite' and build it with -tags sqlite")
+
+// sqliteSchema creates the tables of a SQLite index. The chunks table has the
+// columns of the LanceDB table, so the same filter expressions select from
+// both, and holds the chunk text as it is, so other tools can query it.
+// Vectors are little-endian float32 BLOBs, compared by a full scan.
+const sqliteSchema = `
+CREATE TABLE IF NOT EXISTS chunks (
+	chunk_id            TEXT PRIMARY KEY,
+	file_path           TEXT NOT NULL,
+	line_start          INTEGER NOT NULL,
+	line_end            INTEGER NOT NULL,
+	language            TEXT NOT NULL,
+	code                TEXT NOT NULL,
+	chunk_type          TEXT NOT NULL DEFAULT '',
+	heading             TEXT NOT NULL DEFAULT '',
+	heading_level       TEXT NOT NULL DEFAULT '',
+	parent_heading      TEXT NOT NULL DEFAULT '',
+	doc_comment         TEXT NOT NULL DEFAULT '',
+	doc_summary         TEXT NOT NULL DEFAULT '',
+	attributes          TEXT NOT NULL DEFAULT '',
+	name                TEXT NOT NULL DEFAULT '',
+	qualified_name      TEXT NOT NULL DEFAULT '',
+	kind                TEXT NOT NULL DEFAULT '',
+	visibility          TEXT NOT NULL DEFAULT '',
+	signature           TEXT NOT NULL DEFAULT '',
+	receiver            TEXT NOT NULL DEFAULT '',
+	calls               TEXT NOT NULL DEFAULT '',
+	content_hash        TEXT NOT NULL DEFAULT '',
+	repo                TEXT NOT NULL DEFAULT '',
+	submodule           TEXT NOT NULL DEFAULT '',
+	duplicate_locations TEXT NOT NULL DEFAULT '',
+	embedding_model     TEXT NOT NULL DEFAULT '',
+	is_test             BOOLEAN NOT NULL,
+	is_generated        BOOLEAN NOT NULL,
+	embedding_type      TEXT NOT NULL,
+	vector              BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS chunks_file_path ON chunks (file_path);
+CREATE INDEX IF NOT EXISTS chunks_content_hash ON chunks (content_hash);
+CREATE TABLE IF NOT EXISTS metadata (
+	key   TEXT PRIMARY KEY,
+	value TEXT NOT NULL
+);`
+
+// sqliteTextColumns are the text columns of the chunks table, read and
+// written in this order before line_start, line_end, is_test, is_generated,
+// and vector
+var sqliteTextColumns = append([]string{
+	"chunk_id", "file_path", "language", "code", "chunk_type", "name", "embedding_type",
+}, metadataColumns...)
+
+// sqliteMetadataKey is the key of the metadata table's row holding the
+// IndexMetadata, as JSON
+const sqliteMetadataKey = "index"
+
+// querier runs statements on the database or in a transaction
+type querier interface {
+	ExecContext(ctx context.Context, query string, args ...interface{}) (sql.Result, error)
+	PrepareContext(ctx context.Context, query string) (*sql.Stmt, error)
+	QueryContext(ctx context.Context, query string, args ...interface{}) (*sql.Rows, error)
+	QueryRowContext(ctx context.Context, query string, args ...interface{}) *sql.Row
+}
+
+// SQLiteStore holds an index in a single SQLite database. Each change is made
+// in a transaction, so a reader never sees half of it; a store returned by
+// Begin makes all of its changes in one.
+type SQLiteStore struct {
+	db *sql.DB
+	tx *sql.Tx // Set on a store returned by Begin
+
+	// Size of the vectors stored in an empty database, set with SetDimension
+	dimension int
+}
+
+// OpenSQLiteStore opens the SQLite index at path, creating it if needed
+func OpenSQLiteStore(path string) (*SQLiteStore, error) {
+	if !slices.Contains(sql.Drivers(), SQLiteDriver) {
+		return nil, ErrNoSQLiteDriver
+	}
+	db, err := sql.Open(SQLiteDriver, path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to open %s: %w", path, err)
+	}
+	if _, err := db.Exec(sqliteSchema); err != nil {
+		db.Close()
+		return nil, fmt.Errorf("failed to create the tables of %s: %w", path, err)
+	}
+	return &SQLiteStore{db: db}, nil
+}
+
+// Begin starts a transaction, returning a store that makes its changes in it.
+// They are seen by other readers once Commit is called; Close before that, or
+// a crash, leaves the database as it was.
+func (s *SQLiteStore) Begin() (*SQLiteStore, error) {
+	if s.tx != nil {
+		return nil, errors.New("a transaction is already in progress")
+	}
+	tx, err := s.db.BeginTx(context.Background(), nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to begin a transaction: %w", err)
+	}
+	return &SQLiteStore{db: s.db, tx: tx, dimension: s.dimension}, nil
+}
+
+// Commit makes the changes of a store returned by Begin
+func (s *SQLiteStore) Commit() error {
+	if s.tx == nil {
+		return errors.New("no transaction in progress")
+	}
+	if err := s.tx.Commit(); err != nil {
+		return fmt.Errorf("failed to commit: %w", err)
+	}
+	return nil
+}
+
+// Close closes the database, or rolls back the transaction of a store
+// returned by Begin that was not committed
+func (s *SQLiteStore) Close() error {
+	if s.tx != nil {
+		if err := s.tx.Rollback(); err != nil && !errors.Is(err, sql.ErrTxDone) {
+			return fmt.Errorf("failed to roll back: %w", err)
+		}
+		return nil
+	}
+	return s.db.Close()
+}
+
+// querier returns what the store's statements run on
+func (s *SQLiteStore) querier() querier {
+	if s.tx != nil {
+		return s.tx
+	}
+	return s.db
+}
+
+// update runs fn in the store's transaction, or in a transaction of its own,
+// so the change it makes is made whole or not at all
+func (s *SQLiteStore) update(fn func(q querier) error) error {
+	if s.tx != nil {
+		return fn(s.tx)
+	}
+	tx, err := s.db.BeginTx(context.Background(), nil)
+	if err != nil {
+		return fmt.Errorf("failed to begin a transaction: %w", err)
+	}
+	if err := fn(tx); err != nil {
+		tx.Rollback()
+		return err
+	}
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("failed to commit: %w", err)
+	}
+	return nil
+}
+
+// SetDimension makes an empty database store vectors of n dimensions instead
+// of VectorDimension
+func (s *SQLiteStore) SetDimension(n int) {
+	s.dimension = n
+}
+
+// VectorWidth returns the number of dimensions of the stored vectors
+func (s *SQLiteStore) VectorWidth() int {
+	return s.vectorWidth(s.querier())
+}
+
+// vectorWidth returns the number of dimensions of the vectors stored as q
+// sees them
+func (s *SQLiteStore) vectorWidth(q querier) int {
+	var size int
+	err := q.QueryRowContext(context.Background(), "SELECT length(vector) FROM chunks LIMIT 1").Scan(&size)
+	switch {
+	case err == nil && size > 0:
+		return size / 4
+	case s.dimension > 0:
+		return s.dimension
+	}
+	return VectorDimension
+}
+
+// KeyFingerprint returns "": the chunk text of a SQLite index is not
+// encrypted
+func (s *SQLiteStore) KeyFingerprint() string {
+	return ""
+}
+
+// LoadMetadata loads the metadata, which is empty for a new database
+func (s *SQLiteStore) LoadMetadata() (*IndexMetadata, error) {
+	var data string
+	err := s.querier().QueryRowContext(context.Background(), "SELECT value FROM metadata WHERE key = ?", sqliteMetadataKey).Scan(&data)
+	if errors.Is(err, sql.ErrNoRows) {
+		return NewIndexMetadata(), nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to read metadata: %w", err)
+	}
+
+	var metadata IndexMetadata
+	if err := json.Unmarshal([]byte(data), &metadata); err != nil {
+		return nil, fmt.Errorf("failed to parse metadata: %w", err)
+	}
+	if metadata.FileModTimes == nil {
+		metadata.FileModTimes = make(map[string]time.Time)
+	}
+	if metadata.FileHashes == nil {
+		metadata.FileHashes = make(map[string]string)
+	}
+	return &metadata, nil
+}
+
+// SaveMetadata saves the metadata
+func (s *SQLiteStore) SaveMetadata(metadata *IndexMetadata) error {
+	data, err := json.Marshal(metadata)
+	if err != nil {
+		return fmt.Errorf("failed to marshal metadata: %w", err)
+	}
+	_, err = s.querier().ExecContext(context.Background(),
+		"INSERT OR REPLACE INTO metadata (key, value) VALUES (?, ?)", sqliteMetadataKey, string(data))
+	if err != nil {
+		return fmt.Errorf("failed to write metadata: %w", err)
+	}
+	return nil
+}
+
+// Reset deletes every stored chunk and the metadata
+func (s *SQLiteStore) Reset() error {
+	return s.update(func(q querier) error {
+		if _, err := q.ExecContext(context.Background(), "DELETE FROM chunks"); err != nil {
+			return fmt.Errorf("failed to delete chunks: %w", err)
+		}
+		if _, err := q.ExecContext(context.Background(), "DELETE FROM metadata"); err != nil {
+			return fmt.Errorf("failed to delete metadata: %w", err)
+		}
+		return nil
+	})
+}
+
+// StoreChunks stores chunks with their embeddings. Code that appears more
+// than once is stored once, as LanceDBStore.StoreChunks stores it.
+func (s *SQLiteStore) StoreChunks(chunks []chunker.Chunk, embeddings [][]float64) error {
+	if len(chunks) != len(embeddings) {
+		return fmt.Errorf("chunks and embeddings length mismatch: %d vs %d", len(chunks), len(embeddings))
+	}
+	if len(chunks) == 0 {
+		return nil // Nothing to store
+	}
+
+	return s.update(func(q querier) error {
+		unique, vectors, first, hashes := mergeDuplicates(chunks, embeddings)
+
+		// Copies of code that is already stored join the stored chunk, which
+		// is written again with the new copies
+		var replaced []string
+		for start := 0; start < len(hashes); start += hashesPerQuery {
+			rows, err := selectRows(q, columnFilter("content_hash", hashes[start:min(start+hashesPerQuery, len(hashes))]))
+			if err != nil {
+				return fmt.Errorf("failed to find stored copies: %w", err)
+			}
+			for _, row := range rows {
+				stored, vector := chunkFromRow(row)
+				j, ok := first[dedupKey(stored)]
+				if !ok || len(vector) == 0 {
+					continue
+				}
+				addDuplicate(&stored, unique[j])
+				unique[j], vectors[j] = stored, vector
+				replaced = append(replaced, stored.ID)
+			}
+		}
+		if err := deleteRows(q, replaced); err != nil {
+			return fmt.Errorf("failed to replace stored copies: %w", err)
+		}
+		return s.insertChunks(q, unique, vectors)
+	})
+}
+
+// DeleteChunksByFilePath deletes all chunks for the given file paths. A
+// chunk whose code also appears in other files is kept for its remaining
+// copies.
+func (s *SQLiteStore) DeleteChunksByFilePath(filePaths []string) error {
+	if len(filePaths) == 0 {
+		return nil
+	}
+
+	return s.update(func(q querier) error {
+		rows, err := selectRows(q, copiesFilter(filePaths))
+		if err != nil {
+			return fmt.Errorf("failed to find duplicated chunks: %w", err)
+		}
+		kept, keptVectors, rewritten := keepCopies(rows, filePaths)
+
+		if _, err := q.ExecContext(context.Background(), "DELETE FROM chunks WHERE "+filePathFilter(filePaths)); err != nil {
+			return fmt.Errorf("failed to delete chunks: %w", err)
+		}
+		if err := deleteRows(q, rewritten); err != nil {
+			return fmt.Errorf("failed to delete chunks: %w", err)
+		}
+		return s.insertChunks(q, kept, keptVectors)
+	})
+}
+
+// LoadEmbeddings returns the stored embeddings of the chunks of the given
+// files, keyed by chunk content hash
+func (s *SQLiteStore) LoadEmbeddings(filePaths []string) (map[string][]float64, error) {
+	if len(filePaths) == 0 {
+		return make(map[string][]float64), nil
+	}
+	rows, err := selectRows(s.querier(), filePathFilter(filePaths)+" OR "+duplicateFilter(filePaths))
+	if err != nil {
+		return nil, fmt.Errorf("failed to load embeddings: %w", err)
+	}
+	return reusableEmbeddings(rows), nil
+}
+
+// FallbackFiles returns the files with chunks whose vectors a fallback model
+// embedded
+func (s *SQLiteStore) FallbackFiles() ([]string, error) {
+	rows, err := selectRows(s.querier(), "embedding_model <> ''")
+	if err != nil {
+		return nil, fmt.Errorf("failed to find chunks embedded by fallback models: %w", err)
+	}
+	return filesOfRows(rows), nil
+}
+
+// ReadChunks returns every stored chunk with its vector, in order of file and
+// line
+func (s *SQLiteStore) ReadChunks() ([]StoredChunk, error) {
+	rows, err := selectRows(s.querier(), "")
+	if err != nil {
+		return nil, fmt.Errorf("failed to read chunks: %w", err)
+	}
+	chunks := make([]StoredChunk, len(rows))
+	for i, row := range rows {
+		chunks[i].Chunk, chunks[i].Vector = chunkFromRow(row)
+	}
+	return chunks, nil
+}
+
+// Search returns the limit chunks matching filter whose vectors are nearest
+// the query vector, with their squared L2 distance in _distance as LanceDB
+// reports it. Every matching vector is compared.
+func (s *SQLiteStore) Search(queryVector []float64, limit int, filter string) ([]map[string]interface{}, error) {
+	width := s.VectorWidth()
+	query := make([]float32, width)
+	for i := 0; i < width && i < len(queryVector); i++ {
+		query[i] = float32(queryVector[i])
+	}
+
+	// Only the vectors are read to rank the chunks, and only the nearest are
+	// read whole
+	statement := "SELECT chunk_id, vector FROM chunks"
+	if filter != "" {
+		statement += " WHERE " + filter
+	}
+	rows, err := s.querier().QueryContext(context.Background(), statement)
+	if err != nil {
+		return nil, fmt.Errorf("failed to search: %w", err)
+	}
+	type match struct {
+		id       string
+		distance float64
+	}
+	var matches []match
+	for rows.Next() {
+		var (
+			id   string
+			blob []byte
+		)
+		if err := rows.Scan(&id, &blob); err != nil {
+			rows.Close()
+			return nil, fmt.Errorf("failed to search: %w", err)
+		}
+		matches = append(matches, match{id: id, distance: squaredDistance(query, decodeVector(blob))})
+	}
+	rows.Close()
+	if err := rows.Err(); err != nil {
+		return nil, fmt.Errorf("failed to search: %w", err)
+	}
+	sort.SliceStable(matches, func(i, j int) bool { return matches[i].distance < matches[j].distance })
+	if limit > 0 && len(matches) > limit {
+		matches = matches[:limit]
+	}
+	if len(matches) == 0 {
+		return nil, nil
+	}
+
+	ids := make([]string, len(matches))
+	distances := make(map[string]float64, len(matches))
+	for i, m := range matches {
+		ids[i] = m.id
+		distances[m.id] = m.distance
+	}
+	results, err := selectRows(s.querier(), columnFilter("chunk_id", ids))
+	if err != nil {
+		return nil, fmt.Errorf("failed to search: %w", err)
+	}
+	for _, row := range results {
+		row["_distance"] = distances[row["chunk_id"].(string)]
+	}
+	sort.SliceStable(results, func(i, j int) bool {
+		return results[i]["_distance"].(float64) < results[j]["_distance"].(float64)
+	})
+	return results, nil
+}
+
+// selectRows reads the chunks matching filter, or every chunk for "", as rows
+// with the columns and value types of a LanceDB row
+func selectRows(q querier, filter string) ([]map[string]interface{}, error) {
+	statement := "SELECT " + strings.Join(sqliteTextColumns, ", ") + ", line_start, line_end, is_test, is_generated, vector FROM chunks"
+	if filter != "" {
+		statement += " WHERE " + filter
+	}
+	statement += " ORDER BY file_path, line_start"
+	rows, err := q.QueryContext(context.Background(), statement)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	var results []map[string]interface{}
+	for rows.Next() {
+		text := make([]string, len(sqliteTextColumns))
+		var (
+			lineStart, lineEnd  int32
+			isTest, isGenerated bool
+			blob                []byte
+		)
+		dest := make([]interface{}, 0, len(text)+5)
+		for i := range text {
+			dest = append(dest, &text[i])
+		}
+		dest = append(dest, &lineStart, &lineEnd, &isTest, &isGenerated, &blob)
+		if err := rows.Scan(dest...); err != nil {
+			return nil, err
+		}
+
+		row := make(map[string]interface{}, len(text)+5)
+		for i, column := range sqliteTextColumns {
+			row[column] = text[i]
+		}
+		row["line_start"], row["line_end"] = lineStart, lineEnd
+		row["is_test"], row["is_generated"] = isTest, isGenerated
+		row["vector"] = decodeVector(blob)
+		results = append(results, row)
+	}
+	return results, rows.Err()
+}
+
+// deleteRows deletes the chunks with the given IDs
+func deleteRows(q querier, ids []string) error {
+	for start := 0; start < len(ids); start += hashesPerQuery {
+		filter := columnFilter("chunk_id", ids[start:min(start+hashesPerQuery, len(ids))])
+		if _, err := q.ExecContext(context.Background(), "DELETE FROM chunks WHERE "+filter); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// insertChunks inserts rows for chunks and their embeddings as they are,
+// replacing the rows of the same IDs
+func (s *SQLiteStore) insertChunks(q querier, chunks []chunker.Chunk, embeddings [][]float64) error {
+	if len(chunks) == 0 {
+		return nil
+	}
+	width := s.vectorWidth(q)
+
+	columns := append(slices.Clone(sqliteTextColumns), "line_start", "line_end", "is_test", "is_generated", "vector")
+	stmt, err := q.PrepareContext(context.Background(), "INSERT OR REPLACE INTO chunks ("+strings.Join(columns, ", ")+
+		") VALUES (?"+strings.Repeat(", ?", len(columns)-1)+")")
+	if err != nil {
+		return fmt.Errorf("failed to add records: %w", err)
+	}
+	defer stmt.Close()
+
+	for i, chunk := range chunks {
+		blob, err := encodeVector(embeddings[i], width)
+		if err != nil {
+			return err
+		}
+		values := make([]interface{}, 0, len(columns))
+		for _, column := range sqliteTextColumns {
+			values = append(values, chunkColumn(chunk, column))
+		}
+		values = append(values, chunk.LineStart, chunk.LineEnd,
+			chunk.Metadata["test"] == "true", chunk.Metadata["generated"] == "true", blob)
+		if _, err := stmt.ExecContext(context.Background(), values...); err != nil {
+			return fmt.Errorf("failed to add records: %w", err)
+		}
+	}
+	return nil
+}
+
+// chunkColumn returns a chunk's value of one of sqliteTextColumns
+func chunkColumn(chunk chunker.Chunk, column string) string {
+	switch column {
+	case "chunk_id":
+		return chunk.ID
+	case "file_path":
+		return chunk.FilePath
+	case "language":
+		return chunk.Language
+	case "code":
+		return chunk.Code
+	case "chunk_type":
+		return chunk.ChunkType
+	case "name":
+		return chunk.Name
+	case "embedding_type":
+		return chunk.EmbeddingType
+	}
+	return chunk.Metadata[column]
+}
+
+// encodeVector writes a vector as width little-endian float32s. Vectors
+// padded with zeros past width lose only the padding.
+func encodeVector(vector []float64, width int) ([]byte, error) {
+	blob := make([]byte, 4*width)
+	for i, value := range vector {
+		if i >= width {
+			if value != 0 {
+				return nil, fmt.Errorf("vectors of %d dimensions are larger than the index stores (%d)", len(vector), width)
+			}
+			continue
+		}
+		binary.LittleEndian.PutUint32(blob[4*i:], math.Float32bits(float32(value)))
+	}
+	return blob, nil
+}
+
+// decodeVector reads a vector written by encodeVector
+func decodeVector(blob []byte) []float32 {
+	vector := make([]float32, len(blob)/4)
+	for i := range vector {
+		vector[i] = math.Float32frombits(binary.LittleEndian.Uint32(blob[4*i:]))
+	}
+	return vector
+}
+
+// squaredDistance returns the squared L2 distance of two vectors, comparing
+// the dimensions they both have
+func squaredDistance(a, b []float32) float64 {
+	var sum float64
+	for i := 0; i < len(a) && i < len(b); i++ {
+		d := float64(a[i]) - float64(b[i])
+		sum += d * d
+	}
+	return sum
+}
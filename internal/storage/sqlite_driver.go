@@ -0,0 +1,8 @@
+//go:build sqlite
+
+package storage
+
+// The pure-Go SQLite driver registers SQLiteDriver without cgo. It is not in
+// go.mod by default; add it with 'go get modernc.org/sqlite' before building
+// with -tags sqlite.
+import _ "modernc.org/sqlite"
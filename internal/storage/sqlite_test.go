@@ -0,0 +1,177 @@
+package storage
+
+import (
+	"database/sql"
+	"errors"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// openTestSQLite opens a SQLite index in a temporary directory, skipping the
+// test in builds without the driver
+func openTestSQLite(t *testing.T) (*SQLiteStore, string) {
+	t.Helper()
+	if !slices.Contains(sql.Drivers(), SQLiteDriver) {
+		t.Skip("built without -tags sqlite")
+	}
+	path := filepath.Join(t.TempDir(), "index.db")
+	store, err := OpenSQLiteStore(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { store.Close() })
+	store.SetDimension(4)
+	return store, path
+}
+
+func testChunk(id, path, hash string) chunker.Chunk {
+	return chunker.Chunk{
+		ID:            id,
+		FilePath:      path,
+		LineStart:     1,
+		LineEnd:       3,
+		Language:      "go",
+		Code:          "func " + id + "() {}",
+		Name:          id,
+		Metadata:      map[string]string{"content_hash": hash, "test": "true"},
+		EmbeddingType: "code",
+	}
+}
+
+func TestSQLiteStoreSearchesNearestFirst(t *testing.T) {
+	store, _ := openTestSQLite(t)
+	chunks := []chunker.Chunk{testChunk("a", "a.go", "ha"), testChunk("b", "b.go", "hb"), testChunk("c", "c.go", "hc")}
+	vectors := [][]float64{{1, 0, 0, 0}, {0, 1, 0, 0}, {0.9, 0.1, 0, 0}}
+	if err := store.StoreChunks(chunks, vectors); err != nil {
+		t.Fatal(err)
+	}
+
+	results, err := store.Search([]float64{1, 0, 0, 0}, 2, "embedding_type = 'code' AND is_test = true")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(results) != 2 || results[0]["chunk_id"] != "a" || results[1]["chunk_id"] != "c" {
+		t.Fatalf("results = %v, want a then c", results)
+	}
+	if distance := results[0]["_distance"].(float64); distance != 0 {
+		t.Errorf("distance of the query's own vector = %v, want 0", distance)
+	}
+	if code := results[0]["code"]; code != "func a() {}" {
+		t.Errorf("code = %q, want the chunk's text", code)
+	}
+	if results, err := store.Search([]float64{1, 0, 0, 0}, 10, "is_test = false"); err != nil || len(results) != 0 {
+		t.Errorf("is_test = false matched %d chunks (%v), want none", len(results), err)
+	}
+}
+
+func TestSQLiteStoreKeepsDuplicatesOfDeletedFiles(t *testing.T) {
+	store, _ := openTestSQLite(t)
+	chunks := []chunker.Chunk{testChunk("a", "a.go", "same"), testChunk("b", "vendor/a.go", "same")}
+	if err := store.StoreChunks(chunks, [][]float64{{1, 0, 0, 0}, {1, 0, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+	stored, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(stored) != 1 || stored[0].Chunk.Metadata["duplicate_locations"] != "vendor/a.go:1-3" {
+		t.Fatalf("stored %v, want one chunk with its copy in vendor/a.go", stored)
+	}
+
+	if err := store.DeleteChunksByFilePath([]string{"a.go"}); err != nil {
+		t.Fatal(err)
+	}
+	stored, err = store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(stored) != 1 || stored[0].Chunk.FilePath != "vendor/a.go" || stored[0].Chunk.Metadata["duplicate_locations"] != "" {
+		t.Fatalf("stored %v after deleting a.go, want the copy in vendor/a.go", stored)
+	}
+	if len(stored[0].Vector) != 4 || stored[0].Vector[0] != 1 {
+		t.Errorf("vector = %v, want the stored one", stored[0].Vector)
+	}
+}
+
+func TestSQLiteStoreTransactionIsAllOrNothing(t *testing.T) {
+	store, path := openTestSQLite(t)
+	metadata := NewIndexMetadata()
+	metadata.SchemaVersion = SchemaVersion
+	metadata.FileHashes["a.go"] = "ha"
+	if err := store.SaveMetadata(metadata); err != nil {
+		t.Fatal(err)
+	}
+	if err := store.StoreChunks([]chunker.Chunk{testChunk("a", "a.go", "ha")}, [][]float64{{1, 0, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+
+	// A transaction that is closed without a commit changes nothing
+	tx, err := store.Begin()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := tx.Reset(); err != nil {
+		t.Fatal(err)
+	}
+	if err := tx.StoreChunks([]chunker.Chunk{testChunk("b", "b.go", "hb")}, [][]float64{{0, 1, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+	if err := tx.Close(); err != nil {
+		t.Fatal(err)
+	}
+
+	reopened, err := OpenSQLiteStore(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer reopened.Close()
+	stored, err := reopened.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(stored) != 1 || stored[0].Chunk.ID != "a" {
+		t.Errorf("stored %v after a rolled back transaction, want chunk a only", stored)
+	}
+	loaded, err := reopened.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if loaded.FileHashes["a.go"] != "ha" {
+		t.Errorf("metadata = %+v, want the saved metadata", loaded)
+	}
+
+	// A committed one replaces the index
+	tx, err = store.Begin()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer tx.Close()
+	if err := tx.Reset(); err != nil {
+		t.Fatal(err)
+	}
+	if err := tx.StoreChunks([]chunker.Chunk{testChunk("b", "b.go", "hb")}, [][]float64{{0, 1, 0, 0}}); err != nil {
+		t.Fatal(err)
+	}
+	if err := tx.Commit(); err != nil {
+		t.Fatal(err)
+	}
+	stored, err = reopened.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(stored) != 1 || stored[0].Chunk.ID != "b" {
+		t.Errorf("stored %v after a committed transaction, want chunk b only", stored)
+	}
+}
+
+func TestOpenSQLiteStoreWithoutDriver(t *testing.T) {
+	if slices.Contains(sql.Drivers(), SQLiteDriver) {
+		t.Skip("built with -tags sqlite")
+	}
+	if _, err := OpenSQLiteStore(filepath.Join(t.TempDir(), "index.db")); !errors.Is(err, ErrNoSQLiteDriver) {
+		t.Errorf("err = %v, want ErrNoSQLiteDriver", err)
+	}
+}
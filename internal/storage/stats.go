@@ -0,0 +1,94 @@
+package storage
+
+import (
+	"context"
+	"fmt"
+	"io/fs"
+	"path/filepath"
+)
+
+// ChunkStats summarizes the chunks stored in an index
+type ChunkStats struct {
+	Chunks          int            // Total chunks, one vector each
+	ByLanguage      map[string]int // language -> chunks
+	ByEmbeddingType map[string]int // "code" or "docs" -> chunks
+	ByFile          map[string]int // file path -> chunks
+	TotalLines      int            // Lines covered by all chunks
+	TotalBytes      int            // Bytes of code in all chunks
+}
+
+// Stats counts the stored chunks
+func (s *LanceDBStore) Stats() (*ChunkStats, error) {
+	stats := &ChunkStats{
+		ByLanguage:      make(map[string]int),
+		ByEmbeddingType: make(map[string]int),
+		ByFile:          make(map[string]int),
+	}
+
+	ctx := context.Background()
+	table, err := s.conn.OpenTable(ctx, DefaultTableName)
+	if err != nil {
+		// Table doesn't exist yet, nothing stored
+		return stats, nil
+	}
+	defer table.Close()
+
+	rows, err := table.SelectWithFilter(ctx, "chunk_id IS NOT NULL")
+	if err != nil {
+		return nil, fmt.Errorf("failed to read chunks: %w", err)
+	}
+	if err := s.decodeRows(rows); err != nil {
+		return nil, err
+	}
+	for _, row := range rows {
+		stats.Chunks++
+		language, _ := row["language"].(string)
+		stats.ByLanguage[language]++
+		embeddingType, _ := row["embedding_type"].(string)
+		stats.ByEmbeddingType[embeddingType]++
+		filePath, _ := row["file_path"].(string)
+		stats.ByFile[filePath]++
+		code, _ := row["code"].(string)
+		stats.TotalBytes += len(code)
+		stats.TotalLines += toInt(row["line_end"]) - toInt(row["line_start"]) + 1
+	}
+	return stats, nil
+}
+
+// toInt converts an integer column read back from the table to an int
+func toInt(value interface{}) int {
+	switch v := value.(type) {
+	case int:
+		return v
+	case int32:
+		return int(v)
+	case int64:
+		return int(v)
+	case float64:
+		return int(v)
+	}
+	return 0
+}
+
+// DiskSize returns the number of bytes the index occupies on disk
+func (s *LanceDBStore) DiskSize() (int64, error) {
+	var size int64
+	err := filepath.WalkDir(s.dbDir, func(path string, entry fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if entry.IsDir() {
+			return nil
+		}
+		info, err := entry.Info()
+		if err != nil {
+			return err
+		}
+		size += info.Size()
+		return nil
+	})
+	if err != nil {
+		return 0, fmt.Errorf("failed to measure index size: %w", err)
+	}
+	return size, nil
+}
@@ -0,0 +1,27 @@
+package storage
+
+import "github.com/jlanders/code-scout/internal/chunker"
+
+// Store is what indexing and searching need of an index: its metadata, its
+// chunks with their embeddings, and similarity search over them.
+// LanceDBStore is the index code-scout keeps in the workspace; SQLiteStore
+// holds one in a single SQLite database.
+type Store interface {
+	LoadMetadata() (*IndexMetadata, error)
+	SaveMetadata(metadata *IndexMetadata) error
+	StoreChunks(chunks []chunker.Chunk, embeddings [][]float64) error
+	DeleteChunksByFilePath(filePaths []string) error
+	LoadEmbeddings(filePaths []string) (map[string][]float64, error)
+	FallbackFiles() ([]string, error)
+	ReadChunks() ([]StoredChunk, error)
+	Search(queryVector []float64, limit int, filter string) ([]map[string]interface{}, error)
+	VectorWidth() int
+	KeyFingerprint() string
+	Reset() error
+	Close() error
+}
+
+var (
+	_ Store = (*LanceDBStore)(nil)
+	_ Store = (*SQLiteStore)(nil)
+)
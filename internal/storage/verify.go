@@ -0,0 +1,60 @@
+package storage
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// StoredChunk is a chunk read back from the table with its vector
+type StoredChunk struct {
+	Chunk  chunker.Chunk
+	Vector []float64
+	// DecodeErr is why the chunk's text could not be decoded, if it could not
+	DecodeErr error
+}
+
+// ReadChunks returns every stored chunk with its vector. A chunk whose text
+// cannot be decoded is returned with the error rather than failing the read,
+// so a damaged index can still be checked.
+func (s *LanceDBStore) ReadChunks() ([]StoredChunk, error) {
+	table, err := s.conn.OpenTable(context.Background(), DefaultTableName)
+	if err != nil {
+		// Table doesn't exist yet, nothing stored
+		return nil, nil
+	}
+	defer table.Close()
+
+	rows, err := table.SelectWithFilter(context.Background(), "chunk_id IS NOT NULL")
+	if err != nil {
+		return nil, fmt.Errorf("failed to read chunks: %w", err)
+	}
+	chunks := make([]StoredChunk, len(rows))
+	for i, row := range rows {
+		chunks[i].DecodeErr = s.decodeRow(row)
+		chunks[i].Chunk, chunks[i].Vector = chunkFromRow(row)
+	}
+	return chunks, nil
+}
+
+// DeleteChunks deletes the chunks with the given IDs
+func (s *LanceDBStore) DeleteChunks(ids []string) error {
+	if s.readOnly {
+		return ErrReadOnly
+	}
+	if len(ids) == 0 {
+		return nil
+	}
+	if err := s.ensureTable(); err != nil {
+		return err
+	}
+	ctx := context.Background()
+	for start := 0; start < len(ids); start += relocateBatch {
+		end := min(start+relocateBatch, len(ids))
+		if err := s.table.Delete(ctx, columnFilter("chunk_id", ids[start:end])); err != nil {
+			return fmt.Errorf("failed to delete chunks: %w", err)
+		}
+	}
+	return nil
+}
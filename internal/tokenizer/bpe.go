@@ -0,0 +1,123 @@
+package tokenizer
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"strings"
+)
+
+// byteLevelSplit approximates the GPT-2 pre-tokenizer pattern; Go's regexp has
+// no lookahead, so a run of spaces is not split before a following word
+var byteLevelSplit = regexp.MustCompile(`'(?:[sdmt]|ll|ve|re)| ?\pL+| ?\pN+| ?[^\s\pL\pN]+|\s+`)
+
+// bpe counts tokens by applying a model's merge rules, lowest rank first, to
+// each pre-tokenized word
+type bpe struct {
+	ranks     map[[2]string]int
+	byteLevel bool
+	metaspace bool
+}
+
+func newBPE(file *tokenizerFile) (*bpe, error) {
+	if _, err := vocabulary(file); err != nil {
+		return nil, err
+	}
+
+	b := &bpe{
+		ranks:     make(map[[2]string]int, len(file.Model.Merges)),
+		byteLevel: file.PreTokenizer.has("ByteLevel"),
+		metaspace: file.PreTokenizer.has("Metaspace"),
+	}
+	for rank, raw := range file.Model.Merges {
+		pair, err := parseMerge(raw)
+		if err != nil {
+			return nil, fmt.Errorf("merge %d: %w", rank, err)
+		}
+		if _, ok := b.ranks[pair]; !ok {
+			b.ranks[pair] = rank
+		}
+	}
+	return b, nil
+}
+
+// parseMerge decodes a merge rule, written either as "a b" or as ["a", "b"]
+func parseMerge(raw json.RawMessage) ([2]string, error) {
+	var pair [2]string
+
+	var text string
+	if err := json.Unmarshal(raw, &text); err == nil {
+		left, right, ok := strings.Cut(text, " ")
+		if !ok {
+			return pair, fmt.Errorf("invalid merge %q", text)
+		}
+		return [2]string{left, right}, nil
+	}
+
+	var parts []string
+	if err := json.Unmarshal(raw, &parts); err != nil || len(parts) != 2 {
+		return pair, fmt.Errorf("invalid merge %s", raw)
+	}
+	return [2]string{parts[0], parts[1]}, nil
+}
+
+// Count returns the number of BPE tokens in text
+func (b *bpe) Count(text string) int {
+	count := 0
+	switch {
+	case b.byteLevel:
+		for _, word := range byteLevelSplit.FindAllString(text, -1) {
+			symbols := make([]string, len(word))
+			for i := 0; i < len(word); i++ {
+				symbols[i] = byteSymbols[word[i]]
+			}
+			count += b.merge(symbols)
+		}
+	default:
+		for _, word := range strings.Fields(text) {
+			if b.metaspace {
+				word = "▁" + word
+			}
+			symbols := make([]string, 0, len(word))
+			for _, r := range word {
+				symbols = append(symbols, string(r))
+			}
+			count += b.merge(symbols)
+		}
+	}
+	return count
+}
+
+// merge applies merge rules to a word's symbols and returns how many remain
+func (b *bpe) merge(symbols []string) int {
+	for len(symbols) > 1 {
+		best, bestRank := -1, 0
+		for i := 0; i < len(symbols)-1; i++ {
+			if rank, ok := b.ranks[[2]string{symbols[i], symbols[i+1]}]; ok && (best < 0 || rank < bestRank) {
+				best, bestRank = i, rank
+			}
+		}
+		if best < 0 {
+			break
+		}
+		symbols[best] += symbols[best+1]
+		symbols = append(symbols[:best+1], symbols[best+2:]...)
+	}
+	return len(symbols)
+}
+
+// byteSymbols maps each byte to the printable character byte-level BPE
+// vocabularies use for it, as in GPT-2's bytes_to_unicode
+var byteSymbols = func() [256]string {
+	var symbols [256]string
+	next := rune(256)
+	for i := 0; i < 256; i++ {
+		if i >= '!' && i <= '~' || i >= 0xA1 && i <= 0xAC || i >= 0xAE && i <= 0xFF {
+			symbols[i] = string(rune(i))
+		} else {
+			symbols[i] = string(next)
+			next++
+		}
+	}
+	return symbols
+}()
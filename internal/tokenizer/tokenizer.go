@@ -0,0 +1,120 @@
+package tokenizer
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+)
+
+// Tokenizer counts the tokens an embedding model sees for a piece of text
+type Tokenizer interface {
+	Count(text string) int
+}
+
+// Encoder is a Tokenizer that also returns the ids of the tokens a model is
+// fed, with the special tokens it adds around its input. The WordPiece
+// tokenizers Load returns are encoders.
+type Encoder interface {
+	Tokenizer
+	Encode(text string) []int
+}
+
+// Approximate estimates token counts without a vocabulary, assuming about four
+// bytes per token. It is used when no tokenizer is configured for a model.
+type Approximate struct{}
+
+// Count estimates the number of tokens in text
+func (Approximate) Count(text string) int {
+	return (len(text) + 3) / 4
+}
+
+// tokenizerFile is the subset of a Hugging Face tokenizer.json that is needed
+// to count tokens
+type tokenizerFile struct {
+	Normalizer   *component `json:"normalizer"`
+	PreTokenizer *component `json:"pre_tokenizer"`
+	Model        struct {
+		Type                    string            `json:"type"`
+		Vocab                   json.RawMessage   `json:"vocab"`
+		Merges                  []json.RawMessage `json:"merges"`
+		ContinuingSubwordPrefix string            `json:"continuing_subword_prefix"`
+		MaxInputCharsPerWord    int               `json:"max_input_chars_per_word"`
+	} `json:"model"`
+}
+
+// component is a normalizer or pre-tokenizer, possibly a sequence of others
+type component struct {
+	Type          string       `json:"type"`
+	Lowercase     *bool        `json:"lowercase"`
+	Normalizers   []*component `json:"normalizers"`
+	PreTokenizers []*component `json:"pretokenizers"`
+}
+
+// has reports whether the component or one of its children has the given type
+func (c *component) has(componentType string) bool {
+	if c == nil {
+		return false
+	}
+	if c.Type == componentType {
+		return true
+	}
+	for _, child := range append(c.Normalizers, c.PreTokenizers...) {
+		if child.has(componentType) {
+			return true
+		}
+	}
+	return false
+}
+
+// lowercases reports whether the normalizer lowercases its input
+func (c *component) lowercases() bool {
+	if c == nil {
+		return false
+	}
+	if c.Type == "Lowercase" || (c.Type == "BertNormalizer" && (c.Lowercase == nil || *c.Lowercase)) {
+		return true
+	}
+	for _, child := range c.Normalizers {
+		if child.lowercases() {
+			return true
+		}
+	}
+	return false
+}
+
+// Load reads a Hugging Face tokenizer.json, the file published alongside most
+// embedding models. WordPiece (BERT, nomic-embed-text) and BPE (GPT-2, Qwen,
+// nomic-embed-code) vocabularies are supported. Counts exclude the special
+// tokens a model adds around its input, such as [CLS] and [SEP].
+func Load(path string) (Tokenizer, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read tokenizer: %w", err)
+	}
+
+	var file tokenizerFile
+	if err := json.Unmarshal(data, &file); err != nil {
+		return nil, fmt.Errorf("failed to parse tokenizer %s: %w", path, err)
+	}
+
+	switch file.Model.Type {
+	case "WordPiece":
+		return newWordPiece(&file)
+	case "BPE":
+		return newBPE(&file)
+	default:
+		return nil, fmt.Errorf("unsupported tokenizer model %q in %s", file.Model.Type, path)
+	}
+}
+
+// vocabulary decodes a model's token-to-id vocabulary
+func vocabulary(file *tokenizerFile) (map[string]int, error) {
+	var vocab map[string]int
+	if err := json.Unmarshal(file.Model.Vocab, &vocab); err != nil {
+		return nil, fmt.Errorf("failed to parse %s vocabulary: %w", file.Model.Type, err)
+	}
+	if len(vocab) == 0 {
+		return nil, fmt.Errorf("%s vocabulary is empty", file.Model.Type)
+	}
+	return vocab, nil
+}
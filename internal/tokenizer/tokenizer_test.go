@@ -0,0 +1,125 @@
+package tokenizer
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+// writeTokenizer writes a tokenizer.json to a temporary directory and loads it
+func writeTokenizer(t *testing.T, content string) Tokenizer {
+	t.Helper()
+	path := filepath.Join(t.TempDir(), "tokenizer.json")
+	if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+		t.Fatalf("failed to write tokenizer: %v", err)
+	}
+	tok, err := Load(path)
+	if err != nil {
+		t.Fatalf("failed to load tokenizer: %v", err)
+	}
+	return tok
+}
+
+func TestWordPiece(t *testing.T) {
+	tok := writeTokenizer(t, `{
+  "normalizer": {"type": "BertNormalizer", "lowercase": true},
+  "pre_tokenizer": {"type": "BertPreTokenizer"},
+  "model": {
+    "type": "WordPiece",
+    "unk_token": "[UNK]",
+    "continuing_subword_prefix": "##",
+    "vocab": {"[UNK]": 0, "hello": 1, "world": 2, "un": 3, "##break": 4, "##able": 5, "!": 6}
+  }
+}`)
+
+	tests := []struct {
+		text     string
+		expected int
+	}{
+		{"", 0},
+		{"Hello world!", 3},
+		{"unbreakable", 3},
+		{"xyz", 1},         // unknown word
+		{"hello,world", 3}, // punctuation splits words
+	}
+	for _, tt := range tests {
+		if got := tok.Count(tt.text); got != tt.expected {
+			t.Errorf("Count(%q) = %d, expected %d", tt.text, got, tt.expected)
+		}
+	}
+}
+
+func TestWordPieceEncode(t *testing.T) {
+	tok := writeTokenizer(t, `{
+  "normalizer": {"type": "BertNormalizer", "lowercase": true},
+  "model": {
+    "type": "WordPiece",
+    "vocab": {"[UNK]": 0, "[CLS]": 7, "[SEP]": 8, "hello": 1, "un": 3, "##break": 4, "##able": 5, "!": 6}
+  }
+}`)
+	encoder, ok := tok.(Encoder)
+	if !ok {
+		t.Fatalf("WordPiece tokenizer %T is not an Encoder", tok)
+	}
+
+	tests := []struct {
+		text     string
+		expected []int
+	}{
+		{"", []int{7, 8}},
+		{"Hello unbreakable!", []int{7, 1, 3, 4, 5, 6, 8}},
+		{"hello xyz", []int{7, 1, 0, 8}}, // unknown word
+	}
+	for _, tt := range tests {
+		got := encoder.Encode(tt.text)
+		if fmt.Sprint(got) != fmt.Sprint(tt.expected) {
+			t.Errorf("Encode(%q) = %v, expected %v", tt.text, got, tt.expected)
+		}
+	}
+}
+
+func TestByteLevelBPE(t *testing.T) {
+	tok := writeTokenizer(t, `{
+  "pre_tokenizer": {"type": "ByteLevel", "add_prefix_space": false},
+  "model": {
+    "type": "BPE",
+    "vocab": {"a": 0, "b": 1, "ab": 2, "Ġ": 3, "Ġab": 4},
+    "merges": ["a b", ["Ġ", "ab"]]
+  }
+}`)
+
+	tests := []struct {
+		text     string
+		expected int
+	}{
+		{"", 0},
+		{"ab ab", 2},
+		{"ba", 2},
+		{"ab\n", 2},
+	}
+	for _, tt := range tests {
+		if got := tok.Count(tt.text); got != tt.expected {
+			t.Errorf("Count(%q) = %d, expected %d", tt.text, got, tt.expected)
+		}
+	}
+}
+
+func TestLoadUnsupportedModel(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "tokenizer.json")
+	if err := os.WriteFile(path, []byte(`{"model": {"type": "Unigram", "vocab": [["a", -1.0]]}}`), 0644); err != nil {
+		t.Fatalf("failed to write tokenizer: %v", err)
+	}
+	if _, err := Load(path); err == nil {
+		t.Error("expected error for unsupported tokenizer model")
+	}
+}
+
+func TestApproximate(t *testing.T) {
+	if got := (Approximate{}).Count("abcdefgh"); got != 2 {
+		t.Errorf("expected 2 tokens, got %d", got)
+	}
+	if got := (Approximate{}).Count("abc"); got != 1 {
+		t.Errorf("expected 1 token, got %d", got)
+	}
+}
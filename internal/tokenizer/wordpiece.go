@@ -0,0 +1,126 @@
+package tokenizer
+
+import (
+	"strings"
+	"unicode"
+	"unicode/utf8"
+)
+
+// wordPiece counts tokens the way BERT-style models split their input: words
+// and punctuation are separated, then each word is matched greedily against
+// the vocabulary, longest piece first
+type wordPiece struct {
+	vocab     map[string]int
+	prefix    string
+	maxChars  int
+	lowercase bool
+
+	// Ids of the special tokens: the [CLS] and [SEP] around an encoded text,
+	// and the [UNK] of a word that cannot be split
+	cls, sep, unk int
+}
+
+func newWordPiece(file *tokenizerFile) (*wordPiece, error) {
+	vocab, err := vocabulary(file)
+	if err != nil {
+		return nil, err
+	}
+
+	w := &wordPiece{
+		vocab:     vocab,
+		prefix:    file.Model.ContinuingSubwordPrefix,
+		maxChars:  file.Model.MaxInputCharsPerWord,
+		lowercase: file.Normalizer.lowercases(),
+		cls:       vocab["[CLS]"],
+		sep:       vocab["[SEP]"],
+		unk:       vocab["[UNK]"],
+	}
+	if w.prefix == "" {
+		w.prefix = "##"
+	}
+	if w.maxChars == 0 {
+		w.maxChars = 100
+	}
+	return w, nil
+}
+
+// Count returns the number of WordPiece tokens in text
+func (w *wordPiece) Count(text string) int {
+	count := 0
+	w.split(text, func(int) { count++ })
+	return count
+}
+
+// Encode returns the ids of the WordPiece tokens of text, between [CLS] and
+// [SEP]
+func (w *wordPiece) Encode(text string) []int {
+	ids := []int{w.cls}
+	w.split(text, func(id int) { ids = append(ids, id) })
+	return append(ids, w.sep)
+}
+
+// split calls emit with the id of each token of text, in order
+func (w *wordPiece) split(text string, emit func(id int)) {
+	if w.lowercase {
+		text = strings.ToLower(text)
+	}
+
+	word := 0
+	for i, r := range text {
+		if unicode.IsSpace(r) || isPunctuation(r) {
+			if word < i {
+				w.splitWord(text[word:i], emit)
+			}
+			if !unicode.IsSpace(r) {
+				w.splitWord(string(r), emit)
+			}
+			word = i + utf8.RuneLen(r)
+		}
+	}
+	if word < len(text) {
+		w.splitWord(text[word:], emit)
+	}
+}
+
+// splitWord emits the ids of the pieces a word splits into; a word that
+// cannot be split is a single unknown token
+func (w *wordPiece) splitWord(word string, emit func(id int)) {
+	if utf8.RuneCountInString(word) > w.maxChars {
+		emit(w.unk)
+		return
+	}
+
+	var ids []int
+	for start := 0; start < len(word); {
+		end := len(word)
+		for end > start {
+			piece := word[start:end]
+			if start > 0 {
+				piece = w.prefix + piece
+			}
+			if id, ok := w.vocab[piece]; ok {
+				ids = append(ids, id)
+				break
+			}
+			_, size := utf8.DecodeLastRuneInString(word[start:end])
+			end -= size
+		}
+		if end == start {
+			emit(w.unk)
+			return
+		}
+		start = end
+	}
+	for _, id := range ids {
+		emit(id)
+	}
+}
+
+// isPunctuation reports whether BERT's pre-tokenizer splits r into a word of
+// its own; like BERT, every ASCII symbol counts
+func isPunctuation(r rune) bool {
+	if r < 128 {
+		return !(r >= 'a' && r <= 'z' || r >= 'A' && r <= 'Z' || r >= '0' && r <= '9' || r <= ' ' || r == 127)
+	}
+	return unicode.IsPunct(r)
+}
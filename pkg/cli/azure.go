@@ -0,0 +1,45 @@
+package cli
+
+import (
+	"fmt"
+	"os/exec"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/embeddings"
+)
+
+// azureTokenLifetime is how long a token from azure_ad_token_command is used
+// before the command is run again; Azure AD tokens last at least an hour
+const azureTokenLifetime = 30 * time.Minute
+
+// azureADToken returns a TokenFunc that runs command for an Azure AD token and
+// reuses it for azureTokenLifetime, or nil without a command
+func azureADToken(command string) embeddings.TokenFunc {
+	if command == "" {
+		return nil
+	}
+	var (
+		mu      sync.Mutex
+		token   string
+		fetched time.Time
+	)
+	return func() (string, error) {
+		mu.Lock()
+		defer mu.Unlock()
+		if token != "" && time.Since(fetched) < azureTokenLifetime {
+			return token, nil
+		}
+		output, err := exec.Command("sh", "-c", command).Output()
+		if err != nil {
+			return "", fmt.Errorf("failed to run azure_ad_token_command: %w", err)
+		}
+		token = strings.TrimSpace(string(output))
+		if token == "" {
+			return "", fmt.Errorf("azure_ad_token_command printed no token")
+		}
+		fetched = time.Now()
+		return token, nil
+	}
+}
@@ -0,0 +1,147 @@
+package cli
+
+import (
+	"fmt"
+	"io"
+	"net/url"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/git"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// branchesDirName is the directory in .code-scout that holds the index of
+// each branch with branch_indexes
+const branchesDirName = "branches"
+
+// detachedBranch names the index used while HEAD is not on a branch
+const detachedBranch = "HEAD"
+
+// branchFlag selects the branch whose index search and index stats read
+var branchFlag string
+
+// branchIndexes reports whether each git branch has its own index
+func branchIndexes() bool {
+	return globalConfig != nil && globalConfig.BranchIndexes
+}
+
+// indexBranch returns the branch whose index is used: --branch when given,
+// otherwise the branch checked out in cwd
+func indexBranch(cwd string) (string, error) {
+	if branchFlag != "" {
+		return branchFlag, nil
+	}
+	branch, err := git.CurrentBranch(cwd)
+	if err != nil {
+		return "", fmt.Errorf("branch_indexes needs a git repository: %w", err)
+	}
+	if branch == "" {
+		return detachedBranch, nil
+	}
+	return branch, nil
+}
+
+// branchIndexDir returns the directory whose .code-scout holds the index of
+// the branch for cwd, kept under the .code-scout of dir. A branch that has
+// no index yet starts from a copy of the most recently indexed branch, so
+// only the files that differ between the two are embedded again.
+func branchIndexDir(dir, cwd string) (string, error) {
+	branch, err := indexBranch(cwd)
+	if err != nil {
+		return "", err
+	}
+	branches := filepath.Join(dir, storage.DefaultDBDir, branchesDirName)
+	branchDir := filepath.Join(branches, url.PathEscape(branch))
+	if _, err := os.Stat(branchDir); err == nil {
+		return branchDir, nil
+	} else if !os.IsNotExist(err) {
+		return "", fmt.Errorf("failed to open the index of branch %s: %w", branch, err)
+	}
+	// A read-only index cannot be seeded for a new branch
+	if branchFlag != "" || readOnly() {
+		return "", fmt.Errorf("branch %s is not indexed; check it out and run 'code-scout index'", branch)
+	}
+
+	seed, err := latestBranchIndex(branches)
+	if err != nil || seed == "" {
+		return branchDir, err
+	}
+	seedBranch, _ := url.PathUnescape(filepath.Base(seed))
+	fmt.Fprintf(os.Stderr, "Starting the index of branch %s from branch %s\n", branch, seedBranch)
+	staging := branchDir + ".tmp"
+	if err := os.RemoveAll(staging); err != nil {
+		return "", fmt.Errorf("failed to copy the index of branch %s: %w", seedBranch, err)
+	}
+	if err := copyTree(seed, staging); err != nil {
+		os.RemoveAll(staging)
+		return "", fmt.Errorf("failed to copy the index of branch %s: %w", seedBranch, err)
+	}
+	if err := os.Rename(staging, branchDir); err != nil {
+		return "", fmt.Errorf("failed to copy the index of branch %s: %w", seedBranch, err)
+	}
+	return branchDir, nil
+}
+
+// latestBranchIndex returns the directory of the branch index whose metadata
+// was saved last, or "" when no branch is indexed
+func latestBranchIndex(branches string) (string, error) {
+	entries, err := os.ReadDir(branches)
+	if os.IsNotExist(err) {
+		return "", nil
+	}
+	if err != nil {
+		return "", fmt.Errorf("failed to list branch indexes: %w", err)
+	}
+	var latest string
+	var latestTime time.Time
+	for _, entry := range entries {
+		if !entry.IsDir() || filepath.Ext(entry.Name()) == ".tmp" {
+			continue
+		}
+		dir := filepath.Join(branches, entry.Name())
+		info, err := os.Stat(filepath.Join(dir, storage.DefaultDBDir, storage.MetadataFileName))
+		if err != nil {
+			continue
+		}
+		if latest == "" || info.ModTime().After(latestTime) {
+			latest, latestTime = dir, info.ModTime()
+		}
+	}
+	return latest, nil
+}
+
+// copyTree copies the directory src and everything in it to dst
+func copyTree(src, dst string) error {
+	return filepath.Walk(src, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+		if info.IsDir() {
+			return os.MkdirAll(target, 0755)
+		}
+		if !info.Mode().IsRegular() {
+			return nil
+		}
+		in, err := os.Open(path)
+		if err != nil {
+			return err
+		}
+		defer in.Close()
+		out, err := os.OpenFile(target, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, info.Mode().Perm())
+		if err != nil {
+			return err
+		}
+		if _, err := io.Copy(out, in); err != nil {
+			out.Close()
+			return err
+		}
+		return out.Close()
+	})
+}
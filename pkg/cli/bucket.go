@@ -0,0 +1,184 @@
+package cli
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/objectstore"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// bucketObjectName is the object under the index_bucket prefix that holds the
+// index archive
+const bucketObjectName = "index.tar.gz"
+
+// openIndex opens the index of the workspace at cwd, which is kept in the
+// workspace's .code-scout directory or, with index_bucket, in an object store.
+// With branch_indexes, that directory holds an index per branch.
+func openIndex(cwd string) (*storage.LanceDBStore, error) {
+	dir, err := indexStoreDir(cwd)
+	if err != nil {
+		return nil, err
+	}
+	return openStore(dir)
+}
+
+// indexStoreDir returns the directory whose .code-scout holds the index that
+// openIndex opens
+func indexStoreDir(cwd string) (string, error) {
+	dir, err := indexDir(cwd)
+	if err != nil {
+		return "", err
+	}
+	if branchIndexes() {
+		return branchIndexDir(dir, cwd)
+	} else if branchFlag != "" {
+		return "", fmt.Errorf("--branch needs branch_indexes to be set in the configuration")
+	}
+	return dir, nil
+}
+
+// indexBucket returns the object store location of the index, if one is
+// configured
+func indexBucket() (objectstore.Location, bool, error) {
+	if globalConfig == nil || globalConfig.IndexBucket == "" {
+		return objectstore.Location{}, false, nil
+	}
+	loc, err := objectstore.ParseURL(globalConfig.IndexBucket)
+	if err != nil {
+		return objectstore.Location{}, false, err
+	}
+	return loc, true, nil
+}
+
+// bucketCacheDir returns the directory that caches the bucket's index for the
+// workspace at cwd
+func bucketCacheDir(loc objectstore.Location, cwd string) (string, error) {
+	cache, err := os.UserCacheDir()
+	if err != nil {
+		return "", fmt.Errorf("failed to find the cache directory: %w", err)
+	}
+	sum := sha256.Sum256([]byte(loc.Scheme + "://" + loc.Bucket + "/" + loc.Key + "\x00" + cwd))
+	return filepath.Join(cache, "code-scout", hex.EncodeToString(sum[:8])), nil
+}
+
+// indexDir returns the directory whose .code-scout holds the index of the
+// workspace at cwd, or of the named index selected with --index. With
+// index_bucket that is a local cache, refreshed from the bucket when the
+// bucket's copy has changed since it was cached.
+func indexDir(cwd string) (string, error) {
+	loc, ok, err := indexBucket()
+	if err != nil {
+		return "", err
+	}
+	if !ok {
+		if indexName != "" {
+			return config.NamedIndexDir(indexName)
+		}
+		return cwd, nil
+	}
+	dir, err := bucketCacheDir(loc, cwd)
+	if err != nil {
+		return "", err
+	}
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return "", fmt.Errorf("failed to create %s: %w", dir, err)
+	}
+	client, err := objectstore.NewClient(loc)
+	if err != nil {
+		return "", err
+	}
+
+	// The ETag of the cached copy, if there is one, avoids downloading it again
+	etagFile := filepath.Join(dir, "etag")
+	etag := ""
+	if data, err := os.ReadFile(etagFile); err == nil {
+		if _, err := os.Stat(filepath.Join(dir, storage.DefaultDBDir)); err == nil {
+			etag = strings.TrimSpace(string(data))
+		}
+	}
+	body, etag, err := client.Get(context.Background(), path.Join(loc.Key, bucketObjectName), etag)
+	switch {
+	case errors.Is(err, objectstore.ErrNotModified), errors.Is(err, objectstore.ErrNotFound):
+		// Nothing newer to download; the bucket holds no index until the
+		// first run uploads one
+		return dir, nil
+	case err != nil:
+		return "", err
+	}
+	fmt.Fprintf(os.Stderr, "Downloading the index from %s...\n", globalConfig.IndexBucket)
+
+	staging, err := os.MkdirTemp(dir, "download-")
+	if err != nil {
+		body.Close()
+		return "", fmt.Errorf("failed to create staging directory: %w", err)
+	}
+	defer os.RemoveAll(staging)
+	err = readIndexArchive(body, filepath.Join(staging, storage.DefaultDBDir))
+	body.Close()
+	if err != nil {
+		return "", err
+	}
+
+	// An index that cannot be used here is installed as it is, so index
+	// reports why and rebuilds it
+	if _, err := relocateUnpackedIndex(staging, cwd); err != nil {
+		return "", err
+	}
+	if err := replaceIndexDir(filepath.Join(staging, storage.DefaultDBDir), filepath.Join(dir, storage.DefaultDBDir)); err != nil {
+		return "", fmt.Errorf("failed to replace the cached index: %w", err)
+	}
+	if err := os.WriteFile(etagFile, []byte(etag), 0644); err != nil {
+		return "", fmt.Errorf("failed to record the cached index: %w", err)
+	}
+	return dir, nil
+}
+
+// uploadIndex writes the index of the workspace at cwd to index_bucket, when
+// one is configured
+func uploadIndex(cwd string) error {
+	loc, ok, err := indexBucket()
+	if err != nil || !ok {
+		return err
+	}
+	dir, err := bucketCacheDir(loc, cwd)
+	if err != nil {
+		return err
+	}
+	client, err := objectstore.NewClient(loc)
+	if err != nil {
+		return err
+	}
+
+	archive, err := os.CreateTemp("", "code-scout-index-*.tar.gz")
+	if err != nil {
+		return fmt.Errorf("failed to create archive: %w", err)
+	}
+	defer os.Remove(archive.Name())
+	defer archive.Close()
+	if err := writeIndexArchive(archive, filepath.Join(dir, storage.DefaultDBDir)); err != nil {
+		return err
+	}
+	if _, err := archive.Seek(0, io.SeekStart); err != nil {
+		return fmt.Errorf("failed to read archive: %w", err)
+	}
+
+	etag, err := client.Put(context.Background(), path.Join(loc.Key, bucketObjectName), archive)
+	if err != nil {
+		return fmt.Errorf("failed to upload the index: %w", err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "etag"), []byte(etag), 0644); err != nil {
+		return fmt.Errorf("failed to record the cached index: %w", err)
+	}
+	fmt.Printf("Uploaded the index to %s\n", globalConfig.IndexBucket)
+	return nil
+}
@@ -0,0 +1,93 @@
+package cli
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/tokenizer"
+	"github.com/spf13/cobra"
+)
+
+// chunkerOptions builds the semantic chunker options from the configuration
+// and the index command's flags
+func chunkerOptions(cmd *cobra.Command) (chunker.Options, error) {
+	var options chunker.Options
+	if globalConfig != nil {
+		options.OverlapLines = globalConfig.OverlapLines
+		options.OverlapHeader = globalConfig.OverlapHeader
+		options.MergeTokens = globalConfig.MergeTokens
+		options.GroupTypes = globalConfig.GroupTypes
+		options.SkipGenerated = globalConfig.SkipGenerated
+
+		codeLimit, err := tokenLimit(globalConfig.CodeTokenizer, maxTokens(globalConfig.CodeMaxTokens, globalConfig.CodeModel))
+		if err != nil {
+			return options, fmt.Errorf("code model: %w", err)
+		}
+		textLimit, err := tokenLimit(globalConfig.TextTokenizer, maxTokens(globalConfig.TextMaxTokens, globalConfig.TextModel))
+		if err != nil {
+			return options, fmt.Errorf("text model: %w", err)
+		}
+		codeLimit.Strategy, textLimit.Strategy = globalConfig.Truncation, globalConfig.Truncation
+		options.CodeLimit, options.DocsLimit = codeLimit, textLimit
+	}
+	if cmd.Flags().Changed("overlap") {
+		options.OverlapLines = overlapLines
+	}
+	return options, nil
+}
+
+// configFingerprint identifies the configured settings that shape chunks and
+// what is embedded for them, so an index moved between machines can be
+// checked against the local configuration
+func configFingerprint() string {
+	if globalConfig == nil {
+		return ""
+	}
+	settings, _ := json.Marshal(struct {
+		CodeModel, TextModel                          string
+		OverlapLines                                  int
+		OverlapHeader                                 bool
+		CodeMaxTokens, TextMaxTokens, MergeTokens     int
+		GroupTypes, SkipGenerated, EmbedImports       bool
+		Include, Exclude, Languages, ExcludeLanguages []string
+		Truncation                                    string `json:",omitempty"`
+	}{
+		globalConfig.CodeModel, globalConfig.TextModel,
+		globalConfig.OverlapLines,
+		globalConfig.OverlapHeader,
+		globalConfig.CodeMaxTokens, globalConfig.TextMaxTokens, globalConfig.MergeTokens,
+		globalConfig.GroupTypes, globalConfig.SkipGenerated, globalConfig.EmbedImports,
+		globalConfig.Include, globalConfig.Exclude, globalConfig.Languages, globalConfig.ExcludeLanguages,
+		globalConfig.Truncation,
+	})
+	sum := sha256.Sum256(settings)
+	return hex.EncodeToString(sum[:8])
+}
+
+// maxTokens returns the configured input limit of a model, or the limit of a
+// known model, such as OpenAI's, when none is configured
+func maxTokens(configured int, model string) int {
+	if configured > 0 {
+		return configured
+	}
+	return embeddings.MaxInputTokens(model)
+}
+
+// tokenLimit loads a model's tokenizer, if one is configured, for its token limit
+func tokenLimit(tokenizerPath string, maxTokens int) (chunker.TokenLimit, error) {
+	limit := chunker.TokenLimit{MaxTokens: maxTokens}
+	if tokenizerPath == "" || maxTokens == 0 {
+		return limit, nil
+	}
+
+	tok, err := tokenizer.Load(tokenizerPath)
+	if err != nil {
+		return limit, err
+	}
+	limit.Tokenizer = tok
+	return limit, nil
+}
@@ -0,0 +1,56 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+)
+
+var indexCompactCmd = &cobra.Command{
+	Use:   "compact",
+	Short: "Rewrite the index to reclaim space",
+	Long: `Rewrite the stored chunks into a new table and swap it in. Incremental updates
+leave behind the files of the chunks they replaced and scatter the chunks of a
+file over many small files; the rewritten table holds only the live chunks,
+grouped by file, so it takes less space and searches read fewer files. Chunks
+keep their embeddings, so nothing is embedded again.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if err := checkWritable("compact the index"); err != nil {
+			return err
+		}
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		store, _, err := openIndexForCheck(cwd)
+		if err != nil {
+			return err
+		}
+		defer store.Close()
+		lock, err := lockIndex(commandContext(cmd), store)
+		if err != nil {
+			return err
+		}
+		defer lock.Unlock()
+
+		before, err := store.DiskSize()
+		if err != nil {
+			return err
+		}
+		fmt.Println("Compacting the index...")
+		if err := store.Compact(); err != nil {
+			return fmt.Errorf("failed to compact the index: %w", err)
+		}
+		after, err := store.DiskSize()
+		if err != nil {
+			return err
+		}
+		fmt.Printf("✓ Compacted the index from %s to %s\n", formatBytes(before), formatBytes(after))
+		return uploadIndex(cwd)
+	},
+}
+
+func init() {
+	indexCmd.AddCommand(indexCompactCmd)
+}
@@ -0,0 +1,91 @@
+package cli
+
+import (
+	"path/filepath"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/scanner"
+)
+
+// dirConfigs holds the .code-scout.toml files of the directories a scan
+// walked, by directory; directories without one map to nil
+type dirConfigs map[string]*config.DirConfig
+
+// subtree loads the .code-scout.toml file of a directory for the scanner
+func (d dirConfigs) subtree(dir string) (*scanner.Subtree, error) {
+	cfg, err := config.LoadDirConfig(dir)
+	if err != nil {
+		return nil, err
+	}
+	d[dir] = cfg
+	if cfg == nil {
+		return nil, nil
+	}
+	return &scanner.Subtree{Exclude: cfg.Exclude, Languages: cfg.Languages, ExcludeLanguages: cfg.ExcludeLanguages}, nil
+}
+
+// chunkerOptions returns the chunker options of a file: the given options
+// overridden by the .code-scout.toml files of the scanned directories above
+// it, the nearest last
+func (d dirConfigs) chunkerOptions(options chunker.Options, path string) chunker.Options {
+	var chain []*config.DirConfig
+	for dir := filepath.Dir(path); ; {
+		cfg, ok := d[dir]
+		if !ok {
+			break
+		}
+		if cfg != nil {
+			chain = append(chain, cfg)
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			break
+		}
+		dir = parent
+	}
+
+	for i := len(chain) - 1; i >= 0; i-- {
+		cfg := chain[i]
+		if cfg.OverlapLines != nil {
+			options.OverlapLines = *cfg.OverlapLines
+		}
+		if cfg.MergeTokens != nil {
+			options.MergeTokens = *cfg.MergeTokens
+		}
+		if cfg.CodeMaxTokens != nil {
+			options.CodeLimit.MaxTokens = *cfg.CodeMaxTokens
+		}
+		if cfg.TextMaxTokens != nil {
+			options.DocsLimit.MaxTokens = *cfg.TextMaxTokens
+		}
+		if cfg.SkipGenerated != nil {
+			options.SkipGenerated = *cfg.SkipGenerated
+		}
+	}
+	return options
+}
+
+// chunkers creates the chunkers of the files whose directories override the
+// chunking settings, one for each distinct set of options, by file path.
+// Other files use the chunker of the given options.
+func (d dirConfigs) chunkers(options chunker.Options, files []scanner.FileInfo) (map[string]*chunker.SemanticChunker, error) {
+	byOptions := make(map[chunker.Options]*chunker.SemanticChunker)
+	chunkers := make(map[string]*chunker.SemanticChunker)
+	for _, f := range files {
+		fileOptions := d.chunkerOptions(options, f.Path)
+		if fileOptions == options {
+			continue
+		}
+		c, ok := byOptions[fileOptions]
+		if !ok {
+			var err error
+			if c, err = chunker.NewSemanticWithOptions(fileOptions); err != nil {
+				return nil, err
+			}
+			byOptions[fileOptions] = c
+		}
+		chunkers[f.Path] = c
+	}
+	return chunkers, nil
+}
@@ -0,0 +1,140 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/scanner"
+	"github.com/jlanders/code-scout/internal/tokenizer"
+	"github.com/spf13/cobra"
+)
+
+// dryRunExamples is how many files are listed for each reason files are skipped
+const dryRunExamples = 5
+
+var (
+	// dryRun reports what index would do without chunking or embedding anything
+	dryRun bool
+	// tokenPrice is the price in USD of a million embedded tokens, for the
+	// cost a dry run estimates
+	tokenPrice float64
+)
+
+// skippedFiles collects the files a scan or the chunker leaves out, by reason
+type skippedFiles map[string][]string
+
+// add records a skipped file
+func (s skippedFiles) add(path, reason string) {
+	s[reason] = append(s[reason], path)
+}
+
+// printDryRun reports what indexing would do: the files that would be chunked
+// and embedded, by language, the files that would be removed from the index,
+// the files that are skipped and why, and an estimate of the tokens embedded.
+// Unchanged chunks of changed files keep their embeddings, so the estimate is
+// an upper bound.
+func printDryRun(cmd *cobra.Command, cwd string, dirs dirConfigs, filesToIndex []scanner.FileInfo, filesToDelete, deletedFiles []string, skipped skippedFiles) error {
+	chunkOptions, err := chunkerOptions(cmd)
+	if err != nil {
+		return err
+	}
+	semanticChunker, err := chunker.NewSemanticWithOptions(chunkOptions)
+	if err != nil {
+		return fmt.Errorf("failed to create semantic chunker: %w", err)
+	}
+	dirChunkers, err := dirs.chunkers(chunkOptions, filesToIndex)
+	if err != nil {
+		return fmt.Errorf("failed to create semantic chunker: %w", err)
+	}
+
+	langCounts := make(map[string]int)
+	langTokens := make(map[string]int)
+	files, tokens := 0, 0
+	var cost float64
+	codePrice, textPrice := tokenPrice, tokenPrice
+	if globalConfig != nil {
+		codePrice = tokenPriceOf(usageKey(globalConfig, globalConfig.CodeModel))
+		textPrice = tokenPriceOf(usageKey(globalConfig, globalConfig.TextModel))
+	}
+	for _, f := range filesToIndex {
+		fileChunker, ok := dirChunkers[f.Path]
+		if !ok {
+			fileChunker = semanticChunker
+		}
+		reason, err := fileChunker.SkipReason(f.Path, f.Language)
+		if err != nil {
+			return fmt.Errorf("failed to inspect %s: %w", f.Path, err)
+		}
+		if reason != "" {
+			skipped.add(f.Path, reason)
+			continue
+		}
+		content, err := os.ReadFile(f.Path)
+		if err != nil {
+			return fmt.Errorf("failed to read %s: %w", f.Path, err)
+		}
+		limit, price := chunkOptions.CodeLimit, codePrice
+		if scanner.IsDocumentation(f.Language) {
+			limit, price = chunkOptions.DocsLimit, textPrice
+		}
+		var tok tokenizer.Tokenizer = tokenizer.Approximate{}
+		if limit.Tokenizer != nil {
+			tok = limit.Tokenizer
+		}
+		count := tok.Count(string(content))
+		files++
+		tokens += count
+		cost += float64(count) * price / 1e6
+		langCounts[f.Language]++
+		langTokens[f.Language] += count
+	}
+
+	fmt.Printf("Would index %d file(s)\n", files)
+	languages := make([]string, 0, len(langCounts))
+	for lang := range langCounts {
+		languages = append(languages, lang)
+	}
+	sort.Strings(languages)
+	for _, lang := range languages {
+		fmt.Printf("  %-12s %6d file(s) %10d token(s)\n", lang, langCounts[lang], langTokens[lang])
+	}
+
+	if len(deletedFiles) > 0 {
+		fmt.Printf("Would prune %d deleted, renamed, or excluded file(s) from index\n", len(deletedFiles))
+	}
+	if len(filesToDelete) > len(deletedFiles) {
+		fmt.Printf("Would replace the chunks of %d changed file(s)\n", len(filesToDelete)-len(deletedFiles))
+	}
+
+	reasons := make([]string, 0, len(skipped))
+	for reason := range skipped {
+		reasons = append(reasons, reason)
+	}
+	sort.Strings(reasons)
+	for _, reason := range reasons {
+		paths := skipped[reason]
+		fmt.Printf("Skipped %d: %s\n", len(paths), reason)
+		for _, path := range paths[:min(len(paths), dryRunExamples)] {
+			if rel, err := filepath.Rel(cwd, path); err == nil {
+				path = rel
+			}
+			fmt.Printf("  %s\n", path)
+		}
+		if len(paths) > dryRunExamples {
+			fmt.Printf("  ... and %d more\n", len(paths)-dryRunExamples)
+		}
+	}
+
+	fmt.Printf("Estimated embedding tokens: at most %d\n", tokens)
+	switch {
+	case codePrice > 0 && codePrice == textPrice:
+		fmt.Printf("Estimated cost: at most $%.2f at $%g per million tokens\n", cost, codePrice)
+	case codePrice > 0 || textPrice > 0:
+		fmt.Printf("Estimated cost: at most $%.2f at $%g (code) and $%g (text) per million tokens\n", cost, codePrice, textPrice)
+	}
+	fmt.Println("Dry run: nothing was chunked, embedded, or stored")
+	return nil
+}
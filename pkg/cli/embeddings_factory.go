@@ -0,0 +1,158 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+var (
+	// globalConfig holds the loaded configuration
+	globalConfig *config.Config
+
+	newCodeEmbeddingClient = func() embeddings.Client {
+		if globalConfig != nil {
+			return withFallbacks(newEmbeddingClient(globalConfig.CodeModel), false)
+		}
+		return embeddings.NewClient()
+	}
+	newDocsEmbeddingClient = func() embeddings.Client {
+		if globalConfig != nil {
+			return withFallbacks(newEmbeddingClient(globalConfig.TextModel), true)
+		}
+		return embeddings.NewClientWithModel(embeddings.DefaultTextModel)
+	}
+)
+
+// embeddingLimiter holds the clients of the configured provider to its rate
+// limits, created with the first client that needs it
+var (
+	embeddingLimiter     *embeddings.Limiter
+	embeddingLimiterOnce sync.Once
+)
+
+// newEmbeddingClient creates a client of the configured provider for a model,
+// within the configured rate limits and caching its embeddings on disk with
+// cache_embeddings
+func newEmbeddingClient(model string) embeddings.Client {
+	return newEmbeddingClientWith(globalConfig, model)
+}
+
+// newEmbeddingClientWith creates a client for a model of the provider cfg
+// configures, as newEmbeddingClient does for the configured one. Every client
+// shares the one set of rate limits.
+func newEmbeddingClientWith(cfg *config.Config, model string) embeddings.Client {
+	client := newProviderClient(cfg, model)
+	// The provider is asked for full vectors, which are cached as they are, and
+	// shortened here
+	if cfg.Matryoshka && cfg.Dimensions > 0 {
+		client = embeddings.NewTruncatingClient(client, cfg.Dimensions)
+	}
+	return client
+}
+
+// newProviderClient creates a client of the provider cfg configures for a
+// model, within the rate limits and with the cache
+func newProviderClient(cfg *config.Config, model string) embeddings.Client {
+	dimensions := cfg.Dimensions
+	if cfg.Matryoshka {
+		dimensions = 0
+	}
+	settings := embeddings.ProviderSettings{
+		Endpoint:    cfg.Endpoint,
+		APIKey:      cfg.APIKey,
+		Model:       model,
+		Dimensions:  dimensions,
+		KeepAlive:   cfg.KeepAlive,
+		GPU:         cfg.GPU,
+		Pooling:     cfg.Pooling[model],
+		APIVersion:  cfg.APIVersion,
+		ADToken:     azureADToken(cfg.AzureADTokenCommand),
+		ONNXRuntime: cfg.ONNXRuntime,
+		ONNXDevice:  cfg.ONNXDevice,
+	}
+	client := embeddings.NewProviderClient(cfg.Provider, settings)
+	client = embeddings.NewMeteredClient(client, &meterFor(usageKey(cfg, model)).usage)
+	limits := embeddings.RateLimits{
+		MaxInFlight:       cfg.MaxInFlight,
+		RequestsPerMinute: cfg.RequestsPerMinute,
+		TokensPerMinute:   cfg.TokensPerMinute,
+	}
+	if limits != (embeddings.RateLimits{}) {
+		embeddingLimiterOnce.Do(func() { embeddingLimiter = embeddings.NewLimiter(limits) })
+		client = embeddings.NewRateLimitedClient(client, embeddingLimiter)
+	}
+	// Cached embeddings are outside the limits and the usage, since they make
+	// no requests
+	if !cfg.CacheEmbeddings {
+		return client
+	}
+	root, err := os.UserCacheDir()
+	if err != nil {
+		return client
+	}
+	// Everything that changes the vectors of a text keys the cache
+	key := strings.Join([]string{cfg.Provider, settings.Endpoint, model, strconv.Itoa(settings.Dimensions), settings.Pooling}, "\x00")
+	return embeddings.NewCachingClient(client, embeddings.CacheDir(filepath.Join(root, "code-scout", "embeddings"), key))
+}
+
+// retryPolicy returns the policy failed embedding requests are retried with,
+// the default one with the configured settings in place of its own
+func retryPolicy(retry *config.RetryConfig) embeddings.RetryPolicy {
+	policy := embeddings.DefaultRetryPolicy
+	if retry == nil {
+		return policy
+	}
+	if retry.Attempts > 0 {
+		policy.Attempts = retry.Attempts
+	}
+	policy.Constant = retry.Backoff == "constant"
+	// Validation has already checked the durations
+	if d, err := time.ParseDuration(retry.Delay); err == nil {
+		policy.Delay = d
+	}
+	if d, err := time.ParseDuration(retry.MaxDelay); err == nil {
+		policy.MaxDelay = d
+	}
+	if len(retry.Statuses) > 0 {
+		policy.Statuses = retry.Statuses
+	}
+	return policy
+}
+
+// embeddingModels identifies the configured embedding models, which an index
+// must have been embedded with to be searched or updated
+func embeddingModels() storage.EmbeddingModels {
+	models := storage.EmbeddingModels{
+		CodeModel: embeddings.DefaultCodeModel,
+		TextModel: embeddings.DefaultTextModel,
+		Dimension: storage.VectorDimension,
+	}
+	if globalConfig != nil {
+		models.CodeModel, models.TextModel = globalConfig.CodeModel, globalConfig.TextModel
+		// Vectors shortened to another size cannot be compared with the index's
+		if globalConfig.Dimensions > 0 {
+			models.Dimension = globalConfig.Dimensions
+		}
+	}
+	return models
+}
+
+// useModels makes the embedding clients use the given models rather than the
+// configured ones
+func useModels(models storage.EmbeddingModels) {
+	cfg := config.Default()
+	if globalConfig != nil {
+		copied := *globalConfig
+		cfg = &copied
+	}
+	cfg.CodeModel, cfg.TextModel = models.CodeModel, models.TextModel
+	globalConfig = cfg
+}
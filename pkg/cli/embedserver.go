@@ -0,0 +1,243 @@
+package cli
+
+import (
+	"context"
+	"crypto/subtle"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"os"
+	"os/signal"
+	"strconv"
+	"strings"
+	"syscall"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/tokenizer"
+	"github.com/spf13/cobra"
+)
+
+// embedServerTokenEnv names the environment variable holding the bearer token
+// clients of an embedding server must send
+const embedServerTokenEnv = "CODE_SCOUT_SERVER_TOKEN"
+
+// maxEmbedRequestBytes caps the body of a request to an embedding server
+const maxEmbedRequestBytes = 64 << 20
+
+var embedServerListen string
+
+var embedServerCmd = &cobra.Command{
+	Use:   "embed-server",
+	Short: "Share the configured embedding provider with other machines",
+	Long: `Proxy the configured code and text models over an OpenAI-compatible
+/v1/embeddings endpoint, so the repositories and machines of a team can share
+one provider, such as Ollama or TEI running on a GPU, behind one address and
+token. The server does not run a model itself: each request is embedded with
+the configured provider and endpoint, unless the provider is onnx, which runs
+the models in this process. Point the clients' configuration's endpoint at
+this server with the openai provider. Both models are loaded before the first
+request. When CODE_SCOUT_SERVER_TOKEN is set, requests must send it as a bearer
+token, the api_key of the clients' configuration. Stop the server with Ctrl+C.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		server := newEmbedServer(os.Getenv(embedServerTokenEnv))
+		for _, model := range server.models {
+			fmt.Printf("Loading %s...\n", model)
+			if _, err := server.clients[model].Embed(probeText); err != nil {
+				return fmt.Errorf("failed to load %s: %w", model, err)
+			}
+		}
+
+		ctx, stop := signal.NotifyContext(commandContext(cmd), os.Interrupt, syscall.SIGTERM)
+		defer stop()
+		httpServer := &http.Server{
+			Addr:              embedServerListen,
+			Handler:           server.handler(),
+			ReadHeaderTimeout: 10 * time.Second,
+		}
+		go func() {
+			<-ctx.Done()
+			shutdownCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+			defer cancel()
+			httpServer.Shutdown(shutdownCtx)
+		}()
+
+		fmt.Printf("Serving %s at http://%s/v1/embeddings (Ctrl+C to stop)\n", strings.Join(server.models, " and "), embedServerListen)
+		if server.token == "" {
+			fmt.Fprintf(os.Stderr, "Warning: %s is not set, so anyone who can reach the server can use it\n", embedServerTokenEnv)
+		}
+		if err := httpServer.ListenAndServe(); !errors.Is(err, http.ErrServerClosed) {
+			return fmt.Errorf("embedding server failed: %w", err)
+		}
+		fmt.Println("\nStopped serving")
+		printUsage()
+		return nil
+	},
+}
+
+// embedServer answers embedding requests with clients of the configured
+// models
+type embedServer struct {
+	token   string
+	models  []string // The code model first, which requests naming no model get
+	clients map[string]embeddings.Client
+}
+
+// newEmbedServer creates a server of the configured models that requires
+// token, if it is not empty. The clients have no fallbacks, since the vectors
+// a server returns for a model must be that model's.
+func newEmbedServer(token string) *embedServer {
+	cfg := globalConfig
+	if cfg == nil {
+		cfg = config.Default()
+	}
+	models := embeddingModels()
+	s := &embedServer{token: token, clients: make(map[string]embeddings.Client)}
+	for _, model := range []string{models.CodeModel, models.TextModel} {
+		if _, ok := s.clients[model]; !ok {
+			s.models = append(s.models, model)
+			s.clients[model] = newEmbeddingClientWith(cfg, model)
+		}
+	}
+	return s
+}
+
+func (s *embedServer) handler() http.Handler {
+	mux := http.NewServeMux()
+	mux.HandleFunc("/v1/embeddings", s.handleEmbeddings)
+	mux.HandleFunc("/health", s.handleHealth)
+	return mux
+}
+
+// embedServerRequest is an OpenAI-compatible embedding request, whose input
+// is a text or a list of them
+type embedServerRequest struct {
+	Model string          `json:"model"`
+	Input json.RawMessage `json:"input"`
+}
+
+type embedServerData struct {
+	Object    string    `json:"object"`
+	Index     int       `json:"index"`
+	Embedding []float64 `json:"embedding"`
+}
+
+type embedServerUsage struct {
+	PromptTokens int `json:"prompt_tokens"`
+	TotalTokens  int `json:"total_tokens"`
+}
+
+// embedServerResponse is an OpenAI-compatible embedding response
+type embedServerResponse struct {
+	Object string            `json:"object"`
+	Data   []embedServerData `json:"data"`
+	Model  string            `json:"model"`
+	Usage  embedServerUsage  `json:"usage"`
+}
+
+func (s *embedServer) handleEmbeddings(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodPost {
+		writeEmbedServerError(w, http.StatusMethodNotAllowed, "use POST")
+		return
+	}
+	if !s.authorized(r) {
+		writeEmbedServerError(w, http.StatusUnauthorized, "a valid bearer token is required")
+		return
+	}
+	var req embedServerRequest
+	if err := json.NewDecoder(http.MaxBytesReader(w, r.Body, maxEmbedRequestBytes)).Decode(&req); err != nil {
+		var tooLarge *http.MaxBytesError
+		if errors.As(err, &tooLarge) {
+			writeEmbedServerError(w, http.StatusRequestEntityTooLarge, fmt.Sprintf("the request is larger than %d bytes", tooLarge.Limit))
+			return
+		}
+		writeEmbedServerError(w, http.StatusBadRequest, fmt.Sprintf("invalid request: %v", err))
+		return
+	}
+	texts, err := embedServerInput(req.Input)
+	if err != nil {
+		writeEmbedServerError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	model := req.Model
+	if model == "" {
+		model = s.models[0]
+	}
+	client, ok := s.clients[model]
+	if !ok {
+		writeEmbedServerError(w, http.StatusNotFound, fmt.Sprintf("model %q is not served here; this server serves %s", model, strings.Join(s.models, " and ")))
+		return
+	}
+
+	vectors, err := client.EmbedMany(texts)
+	if err != nil {
+		// The provider's rate limits and rejections of a text are passed on, so
+		// clients back off or give up as they would with the provider itself
+		status := http.StatusBadGateway
+		var statusErr *embeddings.StatusError
+		if errors.As(err, &statusErr) && statusErr.StatusCode >= 400 && statusErr.StatusCode < 500 {
+			status = statusErr.StatusCode
+			if statusErr.RetryAfter > 0 {
+				w.Header().Set("Retry-After", strconv.Itoa(int(statusErr.RetryAfter.Seconds()+0.5)))
+			}
+		}
+		fmt.Fprintf(os.Stderr, "Error: failed to embed %d text(s) with %s: %v\n", len(texts), model, err)
+		writeEmbedServerError(w, status, err.Error())
+		return
+	}
+
+	resp := embedServerResponse{Object: "list", Data: make([]embedServerData, len(vectors)), Model: model}
+	for i, vector := range vectors {
+		resp.Data[i] = embedServerData{Object: "embedding", Index: i, Embedding: vector}
+	}
+	for _, text := range texts {
+		resp.Usage.PromptTokens += tokenizer.Approximate{}.Count(text)
+	}
+	resp.Usage.TotalTokens = resp.Usage.PromptTokens
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(resp)
+}
+
+func (s *embedServer) handleHealth(w http.ResponseWriter, r *http.Request) {
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(map[string]interface{}{"status": "ok", "models": s.models})
+}
+
+// authorized reports whether a request sent the server's token, if it has one
+func (s *embedServer) authorized(r *http.Request) bool {
+	if s.token == "" {
+		return true
+	}
+	token, ok := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+	return ok && subtle.ConstantTimeCompare([]byte(token), []byte(s.token)) == 1
+}
+
+// embedServerInput returns the texts of a request's input, a text or a list
+// of them
+func embedServerInput(input json.RawMessage) ([]string, error) {
+	var text string
+	if err := json.Unmarshal(input, &text); err == nil {
+		return []string{text}, nil
+	}
+	var texts []string
+	if err := json.Unmarshal(input, &texts); err != nil || len(texts) == 0 {
+		return nil, fmt.Errorf("input must be a text or a non-empty list of texts")
+	}
+	return texts, nil
+}
+
+// writeEmbedServerError answers a request with an error in the form of
+// OpenAI's
+func writeEmbedServerError(w http.ResponseWriter, status int, message string) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	json.NewEncoder(w).Encode(map[string]interface{}{"error": map[string]string{"message": message}})
+}
+
+func init() {
+	rootCmd.AddCommand(embedServerCmd)
+	embedServerCmd.Flags().StringVar(&embedServerListen, "listen", "localhost:8765", "Address to listen on; use :8765 to serve other machines")
+}
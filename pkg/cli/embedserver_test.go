@@ -0,0 +1,170 @@
+package cli
+
+import (
+	"encoding/json"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/embeddings"
+)
+
+// rateLimitedClient fails every request as a provider over its rate limit
+type rateLimitedClient struct{}
+
+func (rateLimitedClient) Embed(text string) ([]float64, error) {
+	_, err := rateLimitedClient{}.EmbedMany([]string{text})
+	return nil, err
+}
+
+func (rateLimitedClient) EmbedMany(texts []string) ([][]float64, error) {
+	return nil, &embeddings.StatusError{StatusCode: http.StatusTooManyRequests, Body: "slow down", RetryAfter: 30 * time.Second}
+}
+
+// newTestEmbedServer serves code-model and text-model with fake embeddings,
+// requiring token
+func newTestEmbedServer(t *testing.T, token string) *httptest.Server {
+	t.Helper()
+	server := &embedServer{
+		token:  token,
+		models: []string{"code-model", "text-model"},
+		clients: map[string]embeddings.Client{
+			"code-model": &fakeEmbeddingClient{offset: 1},
+			"text-model": &fakeEmbeddingClient{offset: 1000},
+		},
+	}
+	testServer := httptest.NewServer(server.handler())
+	t.Cleanup(testServer.Close)
+	return testServer
+}
+
+// postEmbeddings sends body to the server's /v1/embeddings with token, if it
+// is not empty
+func postEmbeddings(t *testing.T, url, token, body string) *http.Response {
+	t.Helper()
+	req, err := http.NewRequest(http.MethodPost, url+"/v1/embeddings", strings.NewReader(body))
+	if err != nil {
+		t.Fatal(err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	if token != "" {
+		req.Header.Set("Authorization", "Bearer "+token)
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		t.Fatalf("Request failed: %v", err)
+	}
+	t.Cleanup(func() { resp.Body.Close() })
+	return resp
+}
+
+func TestEmbedServerRequiresToken(t *testing.T) {
+	testServer := newTestEmbedServer(t, "team-secret")
+
+	tests := []struct {
+		name   string
+		token  string
+		status int
+	}{
+		{"missing token", "", http.StatusUnauthorized},
+		{"wrong token", "guess", http.StatusUnauthorized},
+		{"right token", "team-secret", http.StatusOK},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			resp := postEmbeddings(t, testServer.URL, tt.token, `{"input": "hello"}`)
+			if resp.StatusCode != tt.status {
+				t.Errorf("Expected status %d, got %d", tt.status, resp.StatusCode)
+			}
+		})
+	}
+}
+
+func TestEmbedServerAcceptsTextOrList(t *testing.T) {
+	testServer := newTestEmbedServer(t, "")
+
+	tests := []struct {
+		name  string
+		body  string
+		model string
+		texts []string
+	}{
+		{"text", `{"input": "hello"}`, "code-model", []string{"hello"}},
+		{"list", `{"model": "text-model", "input": ["hello", "world"]}`, "text-model", []string{"hello", "world"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			resp := postEmbeddings(t, testServer.URL, "", tt.body)
+			if resp.StatusCode != http.StatusOK {
+				t.Fatalf("Expected status 200, got %d", resp.StatusCode)
+			}
+			var embResp embedServerResponse
+			if err := json.NewDecoder(resp.Body).Decode(&embResp); err != nil {
+				t.Fatalf("Failed to parse response: %v", err)
+			}
+			if embResp.Model != tt.model {
+				t.Errorf("Expected model %s, got %s", tt.model, embResp.Model)
+			}
+			if len(embResp.Data) != len(tt.texts) {
+				t.Fatalf("Expected %d embeddings, got %d", len(tt.texts), len(embResp.Data))
+			}
+			offset := 1.0
+			if tt.model == "text-model" {
+				offset = 1000
+			}
+			for i, text := range tt.texts {
+				if want := fakeVector(text, offset); embResp.Data[i].Index != i || embResp.Data[i].Embedding[1] != want[1] {
+					t.Errorf("Embedding %d is not the embedding of %q by %s", i, text, tt.model)
+				}
+			}
+		})
+	}
+}
+
+func TestEmbedServerRejectsUnknownModel(t *testing.T) {
+	testServer := newTestEmbedServer(t, "")
+	resp := postEmbeddings(t, testServer.URL, "", `{"model": "other-model", "input": "hello"}`)
+	if resp.StatusCode != http.StatusNotFound {
+		t.Errorf("Expected status 404, got %d", resp.StatusCode)
+	}
+}
+
+func TestEmbedServerRejectsOversizedBody(t *testing.T) {
+	server := &embedServer{models: []string{"code-model"}, clients: map[string]embeddings.Client{"code-model": &fakeEmbeddingClient{}}}
+
+	// The text never ends, so only the size limit stops the read
+	body := io.MultiReader(strings.NewReader(`{"input": "`), &endlessReader{})
+	req := httptest.NewRequest(http.MethodPost, "/v1/embeddings", body)
+	rec := httptest.NewRecorder()
+	server.handleEmbeddings(rec, req)
+	if rec.Code != http.StatusRequestEntityTooLarge {
+		t.Errorf("Expected status 413, got %d: %s", rec.Code, rec.Body.String())
+	}
+}
+
+// endlessReader reads as an endless run of the letter a
+type endlessReader struct{}
+
+func (*endlessReader) Read(p []byte) (int, error) {
+	for i := range p {
+		p[i] = 'a'
+	}
+	return len(p), nil
+}
+
+func TestEmbedServerPassesOnRateLimits(t *testing.T) {
+	server := &embedServer{models: []string{"code-model"}, clients: map[string]embeddings.Client{"code-model": rateLimitedClient{}}}
+	testServer := httptest.NewServer(server.handler())
+	defer testServer.Close()
+
+	resp := postEmbeddings(t, testServer.URL, "", `{"input": "hello"}`)
+	if resp.StatusCode != http.StatusTooManyRequests {
+		t.Errorf("Expected status 429, got %d", resp.StatusCode)
+	}
+	if retryAfter := resp.Header.Get("Retry-After"); retryAfter != "30" {
+		t.Errorf("Expected Retry-After 30, got %q", retryAfter)
+	}
+}
@@ -0,0 +1,87 @@
+package cli
+
+import (
+	"encoding/base64"
+	"fmt"
+	"os"
+	"os/exec"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// indexKeyEnv names the environment variable holding the base64-encoded key
+// the index is encrypted with
+const indexKeyEnv = "CODE_SCOUT_INDEX_KEY"
+
+// indexKey returns the key the index is encrypted with, from the environment
+// or the configured key command, or nil when the index is not encrypted
+func indexKey() ([]byte, error) {
+	encoded, source := os.Getenv(indexKeyEnv), indexKeyEnv
+	if encoded == "" && globalConfig != nil && globalConfig.IndexKeyCommand != "" {
+		output, err := exec.Command("sh", "-c", globalConfig.IndexKeyCommand).Output()
+		if err != nil {
+			return nil, fmt.Errorf("failed to run index_key_command: %w", err)
+		}
+		encoded, source = string(output), "index_key_command"
+	}
+	encoded = strings.TrimSpace(encoded)
+	if encoded == "" {
+		return nil, nil
+	}
+
+	key, err := base64.StdEncoding.DecodeString(encoded)
+	if err != nil || len(key) != 32 {
+		return nil, fmt.Errorf("%s must be a base64-encoded 32-byte key, such as the output of 'openssl rand -base64 32'", source)
+	}
+	return key, nil
+}
+
+// openStore opens the index under dir, encrypting and decrypting chunk text
+// with the index key when one is configured
+func openStore(dir string) (*storage.LanceDBStore, error) {
+	key, err := indexKey()
+	if err != nil {
+		return nil, err
+	}
+	newStore := storage.NewLanceDBStore
+	if readOnly() {
+		newStore = storage.NewReadOnlyLanceDBStore
+	}
+	store, err := newStore(dir)
+	if err != nil {
+		return nil, err
+	}
+	if key != nil {
+		if err := store.SetKey(key); err != nil {
+			store.Close()
+			return nil, err
+		}
+	}
+	if globalConfig != nil {
+		// A rebuilt index stores vectors of the size they are shortened to
+		store.SetDimension(globalConfig.Dimensions)
+		if err := store.SetQuantization(globalConfig.Quantization); err != nil {
+			store.Close()
+			return nil, err
+		}
+	}
+	return store, nil
+}
+
+// keyMismatch explains why the index cannot be read with the store's key, or
+// returns "" when it can. An unencrypted index is rebuildable: indexing it
+// again with a key encrypts it.
+func keyMismatch(metadata *storage.IndexMetadata, store *storage.LanceDBStore) (reason string, rebuildable bool) {
+	indexed, current := metadata.KeyFingerprint, store.KeyFingerprint()
+	switch {
+	case metadata.IsEmpty() || indexed == current:
+		return "", false
+	case indexed == "":
+		return "the index is not encrypted but an index key is configured", true
+	case current == "":
+		return fmt.Sprintf("the index is encrypted; set %s or index_key_command to its key", indexKeyEnv), false
+	default:
+		return "the index is encrypted with a different key than the one configured", false
+	}
+}
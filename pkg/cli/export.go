@@ -0,0 +1,80 @@
+package cli
+
+import (
+	"fmt"
+	"path/filepath"
+
+	"github.com/spf13/cobra"
+)
+
+var indexExportCmd = &cobra.Command{
+	Use:   "export <file>",
+	Short: "Write the index to a portable archive",
+	Long: `Write the index of the current directory, with its chunks, vectors, embedding
+models, and a fingerprint of the chunking configuration, to a single .tar.gz
+archive that can be moved to another machine or attached to a bug report.
+
+With --sqlite, write the chunks, vectors, and metadata to a SQLite database at
+<file> instead, which other tools can query and 'code-scout search --sqlite'
+can search. Vectors are stored as BLOBs of little-endian float32s and searched
+by comparing every one. The database is replaced in one transaction, so a
+reader sees either the old index or the new one. An encrypted index cannot be
+exported this way. SQLite needs a build with -tags sqlite, after
+'go get modernc.org/sqlite'.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		file, err := filepath.Abs(args[0])
+		if err != nil {
+			return fmt.Errorf("invalid archive path: %w", err)
+		}
+		if exportSQLiteFlag {
+			files, err := exportSQLite(cwd, file)
+			if err != nil {
+				return err
+			}
+			fmt.Printf("Exported the index of %d file(s) to the SQLite database %s\n", files, file)
+			return nil
+		}
+		files, err := pushIndex(cwd, file)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("Exported the index of %d file(s) to %s\n", files, file)
+		return nil
+	},
+}
+
+var indexImportCmd = &cobra.Command{
+	Use:   "import <file>",
+	Short: "Replace the index with an exported archive",
+	Long: `Replace the index of the current directory with an archive written by 'code-scout
+index export'. File paths are moved from the directory the index was built in to
+the current one. The archive must have been built with the configured embedding
+models; a warning is printed when its chunking configuration differs.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		file, err := filepath.Abs(args[0])
+		if err != nil {
+			return fmt.Errorf("invalid archive path: %w", err)
+		}
+		if err := pullIndex(cwd, file); err != nil {
+			return err
+		}
+		fmt.Printf("Imported the index from %s\n", file)
+		return nil
+	},
+}
+
+func init() {
+	indexExportCmd.Flags().BoolVar(&exportSQLiteFlag, "sqlite", false, "Write a SQLite database that other tools can query instead of an archive")
+	indexCmd.AddCommand(indexExportCmd)
+	indexCmd.AddCommand(indexImportCmd)
+}
@@ -0,0 +1,86 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/scanner"
+)
+
+// withFallbacks makes client fall back to the configured fallbacks' code
+// models, or with docs their text models, while its provider is down or rate
+// limited
+func withFallbacks(client embeddings.Client, docs bool) embeddings.Client {
+	if globalConfig == nil || len(globalConfig.Fallbacks) == 0 {
+		return client
+	}
+	clients := make([]embeddings.Client, len(globalConfig.Fallbacks))
+	models := make([]string, len(globalConfig.Fallbacks))
+	for i, fb := range globalConfig.Fallbacks {
+		models[i] = fb.CodeModel
+		if docs {
+			models[i] = fb.FallbackTextModel()
+		}
+		clients[i] = newEmbeddingClientWith(fallbackConfig(fb), models[i])
+	}
+	return embeddings.NewFallbackClient(client, clients, models)
+}
+
+// fallbackConfig returns the configuration the embedding clients of a
+// fallback are created with: the configured one, with the fallback's models
+// and, when the fallback has an endpoint of its own, its provider and API key
+func fallbackConfig(fb config.FallbackConfig) *config.Config {
+	copied := *globalConfig
+	cfg := &copied
+	if fb.Endpoint != "" {
+		cfg.Provider = fb.Provider
+		cfg.Endpoint = strings.TrimSuffix(fb.Endpoint, "/")
+		cfg.APIKey = fb.APIKey
+		cfg.ApplyEnv()
+	}
+	cfg.CodeModel, cfg.TextModel = fb.CodeModel, fb.FallbackTextModel()
+	cfg.Dimensions = fb.Dimensions
+	return cfg
+}
+
+// withEmbeddingModel narrows a search filter to the chunks embedded by the
+// model that embedded the query: a fallback model, or "" for the index's own.
+// Vectors of different models cannot be compared.
+func withEmbeddingModel(filter, model string) string {
+	condition := fmt.Sprintf("embedding_model = '%s'", strings.ReplaceAll(model, "'", "''"))
+	if filter == "" {
+		return condition
+	}
+	return filter + " AND " + condition
+}
+
+// addFallbackFiles adds the files the index holds chunks of that a fallback
+// model embedded to those being indexed, replacing their chunks, so the
+// configured models embed them once their provider is back
+func addFallbackFiles(fallbackFiles []string, allFiles, filesToIndex []scanner.FileInfo, filesToDelete []string) ([]scanner.FileInfo, []string) {
+	if len(fallbackFiles) == 0 {
+		return filesToIndex, filesToDelete
+	}
+	pending := make(map[string]bool, len(filesToIndex))
+	for _, f := range filesToIndex {
+		pending[f.Path] = true
+	}
+	fellBack := make(map[string]bool, len(fallbackFiles))
+	for _, path := range fallbackFiles {
+		fellBack[path] = true
+	}
+	added := 0
+	for _, f := range allFiles {
+		if fellBack[f.Path] && !pending[f.Path] {
+			filesToIndex = append(filesToIndex, f)
+			filesToDelete = append(filesToDelete, f.Path)
+			added++
+		}
+	}
+	if added > 0 {
+		fmt.Printf("Embedding %d file(s) a fallback model embedded again\n", added)
+	}
+	return filesToIndex, filesToDelete
+}
@@ -0,0 +1,66 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/jlanders/code-scout/internal/git"
+	"github.com/jlanders/code-scout/internal/scanner"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// sinceIndexed is the value of a bare --since flag, meaning the commit that
+// was checked out when the index was last updated
+const sinceIndexed = "indexed"
+
+// gitDelta asks git which files of each workspace root changed between rev
+// and HEAD, and returns the scanned files among them to index, the indexed
+// files whose chunks are replaced or removed, and the indexed files that were
+// deleted
+func gitDelta(roots []workspaceRoot, rev string, metadata *storage.IndexMetadata, allFiles []scanner.FileInfo) (filesToIndex []scanner.FileInfo, filesToDelete, deletedFiles []string, err error) {
+	scanned := make(map[string]scanner.FileInfo, len(allFiles))
+	for _, f := range allFiles {
+		scanned[f.Path] = f
+	}
+
+	for _, root := range roots {
+		rootRev := rev
+		if rev == sinceIndexed {
+			rootRev = indexedCommit(metadata, root)
+			if rootRev == "" {
+				return nil, nil, nil, fmt.Errorf("the index has no recorded commit for %s; run 'code-scout index' without --since first", root.dir)
+			}
+		}
+
+		changes, err := git.ChangedFiles(root.dir, rootRev)
+		if err != nil {
+			return nil, nil, nil, fmt.Errorf("failed to list changes in %s since %s: %w", root.dir, rootRev, err)
+		}
+
+		for _, change := range changes {
+			_, indexed := metadata.FileModTimes[change.Path]
+			if indexed {
+				filesToDelete = append(filesToDelete, change.Path)
+			}
+			// Changed files that are not code or documentation are not indexed
+			f, ok := scanned[change.Path]
+			if change.Deleted || !ok {
+				if indexed {
+					deletedFiles = append(deletedFiles, change.Path)
+				}
+				continue
+			}
+			filesToIndex = append(filesToIndex, f)
+		}
+	}
+	return filesToIndex, filesToDelete, deletedFiles, nil
+}
+
+// headCommit returns the commit checked out in dir, or "" outside a git
+// repository
+func headCommit(dir string) string {
+	commit, err := git.Head(dir)
+	if err != nil {
+		return ""
+	}
+	return commit
+}
@@ -0,0 +1,38 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/parser"
+	"github.com/jlanders/code-scout/internal/scanner"
+)
+
+// registerGrammars loads the runtime grammars listed in the configuration and
+// registers a chunker and file extensions for each of their languages
+func registerGrammars(grammars []config.GrammarConfig) error {
+	for _, g := range grammars {
+		language, err := parser.LoadGrammar(g.Library, g.LanguageSymbol())
+		if err != nil {
+			return fmt.Errorf("failed to load grammar for %s: %w", g.Language, err)
+		}
+
+		query, err := os.ReadFile(g.Query)
+		if err != nil {
+			return fmt.Errorf("failed to read query for %s: %w", g.Language, err)
+		}
+
+		grammarChunker, err := chunker.NewGrammarChunker(g.Language, language, string(query))
+		if err != nil {
+			return err
+		}
+
+		chunker.Register(g.Language, grammarChunker)
+		for _, ext := range g.Extensions {
+			scanner.RegisterExtension(ext, g.Language)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,660 @@
+package cli
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"os"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/scanner"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+var (
+	workers            int
+	embeddingBatchSize int
+	overlapLines       int
+	sinceRev           string
+	noIgnore           bool
+	includeGlobs       []string
+	excludeGlobs       []string
+	languageNames      []string
+	excludeLanguages   []string
+	submodulesMode     string
+	symlinksPolicy     string
+	pipelineFiles      int
+)
+
+// computeContentHash generates a SHA256 hash of the content
+func computeContentHash(content string) string {
+	hash := sha256.Sum256([]byte(content))
+	return hex.EncodeToString(hash[:])
+}
+
+// hashFile generates a SHA256 hash of a file's content
+func hashFile(path string) (string, error) {
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return "", err
+	}
+	return computeContentHash(string(content)), nil
+}
+
+// embeddingText returns the text embedded for a chunk
+func embeddingText(chunk chunker.Chunk) string {
+	return chunk.EmbeddingText(globalConfig != nil && globalConfig.EmbedImports)
+}
+
+// commandContext returns a command's context, or the background context for
+// commands run without one
+func commandContext(cmd *cobra.Command) context.Context {
+	if ctx := cmd.Context(); ctx != nil {
+		return ctx
+	}
+	return context.Background()
+}
+
+var indexCmd = &cobra.Command{
+	Use:   "index",
+	Short: "Index the current directory for semantic search",
+	Long: `Scan the current directory for code files, chunk them, generate embeddings,
+and store them in a local LanceDB vector database (.code-scout/).
+
+When code_model or text_model changes, the stored chunks are embedded again
+with the new models before changed files are indexed. The re-embedding runs in
+the foreground of this command, not as a background job: searches keep using
+the old vectors until it finishes, and an interrupted one starts over on the
+next run.`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		// Get current working directory
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+
+		// Load any grammars configured at runtime before scanning for their files
+		if globalConfig != nil {
+			if err := registerGrammars(globalConfig.Grammars); err != nil {
+				return err
+			}
+		}
+
+		if cmd.Flags().Changed("overlay") {
+			return indexOverlay(cmd, cwd)
+		}
+		return indexWorkspace(cmd, cwd)
+	},
+}
+
+// indexWorkspace brings the index of the workspace at cwd up to date: files
+// that changed since the last run are chunked and embedded again, and deleted
+// files are removed
+func indexWorkspace(cmd *cobra.Command, cwd string) error {
+	if !dryRun {
+		if err := checkWritable("update the index"); err != nil {
+			return err
+		}
+	}
+	if dryRun {
+		fmt.Println("Planning the index (dry run)...")
+	} else {
+		fmt.Println("Indexing codebase...")
+	}
+
+	// Initialize storage and load metadata
+	dir, err := indexStoreDir(cwd)
+	if err != nil {
+		return fmt.Errorf("failed to create LanceDB store: %w", err)
+	}
+	store, err := openStore(dir)
+	if err != nil {
+		return fmt.Errorf("failed to create LanceDB store: %w", err)
+	}
+	defer store.Close()
+
+	// Only one process changes the index at a time, and it writes a new
+	// generation of the index that replaces it only once the run succeeds, so
+	// an interrupted run leaves the index as it was. A dry run changes nothing
+	// and reads the index itself.
+	gen := store
+	if !dryRun {
+		lock, err := lockIndex(commandContext(cmd), store)
+		if err != nil {
+			return err
+		}
+		defer lock.Unlock()
+		if gen, err = store.BeginGeneration(); err != nil {
+			return err
+		}
+		defer gen.Close()
+	}
+
+	metadata, err := gen.LoadMetadata()
+	if err != nil {
+		return fmt.Errorf("failed to load metadata: %w", err)
+	}
+
+	// An index embedded with other models is embedded again from its stored
+	// chunks, while it can still be searched with the old models
+	models := embeddingModels()
+	if metadata.ModelsChanged(models) {
+		if keyReason, _ := keyMismatch(metadata, gen); keyReason == "" {
+			if dryRun {
+				fmt.Printf("Would re-embed the index with %s/%s (was %s/%s)\n",
+					models.CodeModel, models.TextModel, metadata.Models.CodeModel, metadata.Models.TextModel)
+				updated := *metadata
+				updated.Models = &models
+				metadata = &updated
+			} else if metadata, err = reembedIndex(gen, metadata, models); err != nil {
+				return err
+			}
+		}
+	}
+
+	// An index of another format cannot be updated in place, and an index
+	// encrypted with another key cannot be read at all
+	reason := metadata.Incompatibility(models)
+	if reason == "" {
+		keyReason, rebuildable := keyMismatch(metadata, gen)
+		if keyReason != "" && !rebuildable {
+			return fmt.Errorf("%s", keyReason)
+		}
+		reason = keyReason
+	}
+	rebuild := false
+	if reason != "" {
+		if dryRun {
+			fmt.Printf("Would rebuild the index: %s\n", reason)
+		} else {
+			fmt.Printf("Rebuilding the index: %s\n", reason)
+			if err := gen.Reset(); err != nil {
+				return fmt.Errorf("failed to reset index: %w", err)
+			}
+		}
+		// The tokens sent for the index so far were still paid for
+		usage := metadata.Usage
+		metadata, rebuild = storage.NewIndexMetadata(), true
+		metadata.Usage = usage
+	}
+	metadata.SchemaVersion = storage.SchemaVersion
+	metadata.Models = &models
+	metadata.KeyFingerprint = gen.KeyFingerprint()
+	metadata.Root = cwd
+	metadata.ConfigFingerprint = configFingerprint()
+	metadata.EmbedImports = globalConfig != nil && globalConfig.EmbedImports
+
+	// Scan for code files in the workspace's repositories
+	roots, err := workspaceRoots(cwd)
+	if err != nil {
+		return err
+	}
+	dirs := make(dirConfigs)
+	scanOptions := scannerOptions(cmd, dirs)
+	skipped := make(skippedFiles)
+	if dryRun {
+		scanOptions.OnSkip = skipped.add
+	}
+	allFiles, err := scanWorkspace(roots, scanOptions)
+	if err != nil {
+		return err
+	}
+
+	// Determine which files need indexing
+	var filesToIndex []scanner.FileInfo
+	var filesToDelete, deletedFiles []string
+	now := time.Now()
+
+	// A rebuilt index needs every file, not only those git reports as changed
+	if cmd.Flags().Changed("since") && !rebuild {
+		filesToIndex, filesToDelete, deletedFiles, err = gitDelta(roots, sinceRev, metadata, allFiles)
+		if err != nil {
+			return err
+		}
+	} else {
+		indexed := !metadata.IsEmpty()
+		for _, f := range allFiles {
+			lastModTime, exists := metadata.FileModTimes[f.Path]
+			if exists && !f.ModTime.After(lastModTime) {
+				continue
+			}
+			if exists {
+				// A file that was touched, as by a checkout, but has the same content needs no re-index
+				hash, err := hashFile(f.Path)
+				if err != nil {
+					return fmt.Errorf("failed to hash %s: %w", f.Path, err)
+				}
+				if hash == metadata.FileHashes[f.Path] {
+					metadata.FileModTimes[f.Path] = f.ModTime
+					continue
+				}
+				// File was previously indexed, mark for deletion
+				filesToDelete = append(filesToDelete, f.Path)
+			} else if indexed {
+				// A run that was killed may have stored chunks of a new file
+				// without recording it; they are replaced rather than kept twice
+				filesToDelete = append(filesToDelete, f.Path)
+			}
+			// File is new or has been modified
+			filesToIndex = append(filesToIndex, f)
+		}
+	}
+
+	// Files with chunks a fallback model embedded are indexed again, so the
+	// configured models embed them once their provider is back
+	if !rebuild {
+		fallbackFiles, err := gen.FallbackFiles()
+		if err != nil {
+			return err
+		}
+		filesToIndex, filesToDelete = addFallbackFiles(fallbackFiles, allFiles, filesToIndex, filesToDelete)
+	}
+
+	// Indexed files that were deleted, renamed, or are now ignored are pruned.
+	// git reports committed deletions and renames; this also catches files
+	// removed from the working tree, excluded, or left out of the workspace.
+	// A renamed file is chunked again under its new path, reusing the
+	// embeddings of its unchanged chunks.
+	for _, filePath := range vanishedFiles(metadata, allFiles, deletedFiles) {
+		filesToDelete = append(filesToDelete, filePath)
+		deletedFiles = append(deletedFiles, filePath)
+	}
+
+	if dryRun {
+		return printDryRun(cmd, cwd, dirs, filesToIndex, filesToDelete, deletedFiles, skipped)
+	}
+
+	// Chunks embedded by a model whose vectors differ from the index's would
+	// be compared with them as if they were alike, so they are not appended.
+	// An index that does not record its vectors yet records them.
+	if len(filesToIndex) > 0 || (metadata.Vectors == nil && !metadata.IsEmpty()) {
+		if err := checkVectors(metadata, dir); err != nil {
+			return err
+		}
+	}
+
+	// Unchanged chunks of changed, moved, or deleted files keep their embeddings
+	cachedEmbeddings, err := gen.LoadEmbeddings(filesToDelete)
+	if err != nil {
+		return err
+	}
+
+	// Delete old chunks for changed/deleted files
+	if len(filesToDelete) > 0 {
+		if len(deletedFiles) > 0 {
+			fmt.Printf("Pruning %d deleted, renamed, or excluded file(s) from index...\n", len(deletedFiles))
+		}
+		fmt.Printf("Removing old chunks of %d file(s) from index...\n", len(filesToDelete))
+		if err := gen.DeleteChunksByFilePath(filesToDelete); err != nil {
+			return fmt.Errorf("failed to delete old chunks: %w", err)
+		}
+	}
+
+	// If nothing to index, we're done
+	if len(filesToIndex) == 0 {
+		// Touched files, deletions, and the commit are still recorded
+		for _, filePath := range deletedFiles {
+			delete(metadata.FileModTimes, filePath)
+			delete(metadata.FileHashes, filePath)
+		}
+		recordCommits(metadata, roots)
+		recordUsage(metadata)
+		if err := store.CommitGeneration(gen, metadata); err != nil {
+			return fmt.Errorf("failed to save the index: %w", err)
+		}
+		if dualEmbedding() {
+			if err := embedSummaries(store); err != nil {
+				return err
+			}
+		}
+		fmt.Printf("✓ All files up to date. Indexing complete!\n")
+		printUsage()
+		if err := removeOverlay(dir); err != nil {
+			return err
+		}
+		return uploadIndex(cwd)
+	}
+
+	// Count files by language
+	langCounts := make(map[string]int)
+	for _, f := range filesToIndex {
+		langCounts[f.Language]++
+	}
+
+	fmt.Printf("Indexing %d file(s)", len(filesToIndex))
+	if len(langCounts) > 0 {
+		fmt.Print(" (")
+		first := true
+		for lang, count := range langCounts {
+			if !first {
+				fmt.Print(", ")
+			}
+			fmt.Printf("%d %s", count, lang)
+			first = false
+		}
+		fmt.Print(")")
+	}
+	fmt.Println()
+
+	// Chunk files that need indexing using semantic chunker
+	chunkOptions, err := chunkerOptions(cmd)
+	if err != nil {
+		return err
+	}
+	budget, err := indexMemoryBudget()
+	if err != nil {
+		return err
+	}
+	budget.limitChunking(&chunkOptions)
+	semanticChunker, err := chunker.NewSemanticWithOptions(chunkOptions)
+	if err != nil {
+		return fmt.Errorf("failed to create semantic chunker: %w", err)
+	}
+	dirChunkers, err := dirs.chunkers(chunkOptions, filesToIndex)
+	if err != nil {
+		return fmt.Errorf("failed to create semantic chunker: %w", err)
+	}
+
+	// Deleted files are forgotten even if indexing fails part way
+	for _, filePath := range deletedFiles {
+		delete(metadata.FileModTimes, filePath)
+		delete(metadata.FileHashes, filePath)
+	}
+	metadata.LastIndexTime = now
+
+	pipeline := &indexPipeline{
+		chunker:    semanticChunker,
+		chunkers:   dirChunkers,
+		codeClient: newCodeEmbeddingClient(),
+		docsClient: newDocsEmbeddingClient(),
+		cached:     cachedEmbeddings,
+		batchFiles: pipelineFiles,
+		store: func(batch indexBatch) error {
+			if err := gen.StoreChunks(batch.chunks, batch.embeddings); err != nil {
+				return fmt.Errorf("failed to store chunks: %w", err)
+			}
+			// Stored files are recorded as they go, so a failed run resumes
+			// with the files it did not reach
+			for _, f := range batch.files {
+				hash, err := hashFile(f.Path)
+				if err != nil {
+					return fmt.Errorf("failed to hash %s: %w", f.Path, err)
+				}
+				metadata.FileModTimes[f.Path] = f.ModTime
+				metadata.FileHashes[f.Path] = hash
+			}
+			return nil
+		},
+	}
+	budget.limitPipeline(pipeline)
+	fmt.Printf("Using %d files per batch and %d CPU(s) for chunking\n", max(pipelineFiles, 1), pipeline.chunkParallelism())
+	stored, err := pipeline.run(commandContext(cmd), filesToIndex)
+	if err != nil {
+		// The files stored so far are kept for the next run, which resumes
+		// where this one stopped
+		recordUsage(metadata)
+		if saveErr := store.SuspendGeneration(gen, metadata); saveErr != nil {
+			fmt.Fprintf(os.Stderr, "Warning: failed to save metadata: %v\n", saveErr)
+		}
+		printUsage()
+		return err
+	}
+	fmt.Printf("\nTotal chunks: %d\n", stored)
+	recordCommits(metadata, roots)
+	recordUsage(metadata)
+
+	if err := store.CommitGeneration(gen, metadata); err != nil {
+		return fmt.Errorf("failed to save the index: %w", err)
+	}
+
+	if dualEmbedding() {
+		if err := embedSummaries(store); err != nil {
+			return err
+		}
+	}
+	fmt.Println("✓ Indexing complete!")
+	printUsage()
+
+	// The index now covers the files an overlay held
+	if err := removeOverlay(dir); err != nil {
+		return err
+	}
+	if err := uploadIndex(cwd); err != nil {
+		return err
+	}
+	// Files that failed to embed are reported once the rest are indexed
+	return pipeline.reportFailures()
+}
+
+// vanishedFiles returns the indexed files that are no longer among the scanned
+// files and not already known to be deleted
+func vanishedFiles(metadata *storage.IndexMetadata, allFiles []scanner.FileInfo, deleted []string) []string {
+	present := make(map[string]bool, len(allFiles)+len(deleted))
+	for _, f := range allFiles {
+		present[f.Path] = true
+	}
+	for _, filePath := range deleted {
+		present[filePath] = true
+	}
+	var vanished []string
+	for filePath := range metadata.FileModTimes {
+		if !present[filePath] {
+			vanished = append(vanished, filePath)
+		}
+	}
+	sort.Strings(vanished)
+	return vanished
+}
+
+// generateEmbeddingsWithDedup generates embeddings for chunks with content
+// deduplication, reusing the cached embeddings of chunks whose content hash
+// was embedded before. Chunks whose requests failed even after retries are
+// returned by index with their errors and have no embedding; when every
+// request fails, the API is taken to be down and an error is returned. Chunks
+// a fallback model embedded are returned by index with the model.
+func generateEmbeddingsWithDedup(client embeddings.Client, chunks []chunker.Chunk, cached map[string][]float64, numWorkers, batchSize int) ([][]float64, map[int]error, map[int]string, error) {
+	if len(chunks) == 0 {
+		return nil, nil, nil, nil
+	}
+
+	// Set default workers
+	if numWorkers <= 0 {
+		numWorkers = 10
+	}
+	if batchSize <= 0 {
+		batchSize = 1
+	}
+
+	// Compute content hashes for deduplication
+	chunkHashes := make([]string, len(chunks))
+	hashToFirstIndex := make(map[string]int)
+
+	texts := make([]string, len(chunks))
+	allEmbeddings := make([][]float64, len(chunks))
+	reused := 0
+	for i, chunk := range chunks {
+		texts[i] = embeddingText(chunk)
+		hash := computeContentHash(texts[i])
+		chunkHashes[i] = hash
+
+		if embedding, ok := cached[hash]; ok {
+			allEmbeddings[i] = embedding
+			reused++
+			continue
+		}
+		if _, exists := hashToFirstIndex[hash]; !exists {
+			hashToFirstIndex[hash] = i
+		}
+	}
+
+	uniqueCount := len(hashToFirstIndex)
+	duplicateCount := len(chunks) - uniqueCount - reused
+
+	if reused > 0 {
+		fmt.Printf("Reusing %d embeddings of unchanged chunks\n", reused)
+	}
+	if duplicateCount > 0 {
+		fmt.Printf("Found %d duplicate chunks (will skip %d embeddings)\n", duplicateCount, duplicateCount)
+	}
+	if uniqueCount == 0 {
+		return allEmbeddings, nil, nil, nil
+	}
+
+	fmt.Printf("Using %d concurrent workers\n", numWorkers)
+
+	// A fallback client reports the fallback model that embedded a request
+	embedMany := func(texts []string) ([][]float64, string, error) {
+		vectors, err := client.EmbedMany(texts)
+		return vectors, "", err
+	}
+	if fallback, ok := client.(*embeddings.FallbackClient); ok {
+		embedMany = fallback.EmbedManyFrom
+	}
+
+	// Generate embeddings for unique chunks only
+
+	type job struct {
+		index int
+		text  string
+	}
+
+	type result struct {
+		index     int
+		embedding []float64
+		model     string // Fallback model that embedded the chunk, if one did
+		err       error
+	}
+
+	jobs := make(chan job, uniqueCount)
+	results := make(chan result, uniqueCount)
+
+	var wg sync.WaitGroup
+	for w := 0; w < numWorkers; w++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			buffer := make([]job, 0, batchSize)
+			flush := func() {
+				if len(buffer) == 0 {
+					return
+				}
+				texts := make([]string, len(buffer))
+				for i, jb := range buffer {
+					texts[i] = jb.text
+				}
+				embeddings, model, err := embedMany(texts)
+				if err != nil {
+					// The other requests go ahead without these chunks
+					for _, jb := range buffer {
+						results <- result{index: jb.index, err: err}
+					}
+					buffer = buffer[:0]
+					return
+				}
+				for i, emb := range embeddings {
+					results <- result{index: buffer[i].index, embedding: emb, model: model}
+				}
+				buffer = buffer[:0]
+			}
+
+			for j := range jobs {
+				buffer = append(buffer, j)
+				if len(buffer) >= batchSize {
+					flush()
+				}
+			}
+			flush()
+		}()
+	}
+
+	// Send jobs for unique chunks
+	for _, firstIdx := range hashToFirstIndex {
+		jobs <- job{
+			index: firstIdx,
+			text:  texts[firstIdx],
+		}
+	}
+	close(jobs)
+
+	// Close results when workers done
+	go func() {
+		wg.Wait()
+		close(results)
+	}()
+
+	var firstErr error
+	failed := make(map[int]error)
+	fallbacks := make(map[int]string)
+	completed := 0
+	for r := range results {
+		if r.err != nil {
+			failed[r.index] = r.err
+			if firstErr == nil {
+				firstErr = r.err
+			}
+		}
+		if r.embedding != nil {
+			allEmbeddings[r.index] = r.embedding
+		}
+		if r.model != "" {
+			fallbacks[r.index] = r.model
+		}
+		completed++
+		if r.embedding != nil {
+			if completed == 1 || completed%50 == 0 || completed == uniqueCount {
+				fmt.Printf("  Generated %d/%d unique embeddings (dim: %d)\n", completed, uniqueCount, len(r.embedding))
+			}
+		}
+		if completed == uniqueCount {
+			break
+		}
+	}
+
+	if len(failed) == uniqueCount {
+		return nil, nil, nil, fmt.Errorf("failed to generate embeddings: %w", firstErr)
+	}
+	if len(fallbacks) > 0 {
+		fmt.Printf("  %d of %d unique embeddings were generated by fallback models\n", len(fallbacks), uniqueCount)
+	}
+
+	// Copy embeddings to duplicate chunks
+	if duplicateCount > 0 {
+		fmt.Printf("Copying embeddings to %d duplicate chunks...\n", duplicateCount)
+		for i, hash := range chunkHashes {
+			if allEmbeddings[i] == nil {
+				firstIdx := hashToFirstIndex[hash]
+				allEmbeddings[i] = allEmbeddings[firstIdx]
+				if err, ok := failed[firstIdx]; ok {
+					failed[i] = err
+				}
+				if model, ok := fallbacks[firstIdx]; ok {
+					fallbacks[i] = model
+				}
+			}
+		}
+	}
+
+	return allEmbeddings, failed, fallbacks, nil
+}
+
+func init() {
+	rootCmd.AddCommand(indexCmd)
+	indexCmd.Flags().IntVarP(&workers, "workers", "w", 10, "Number of concurrent workers for embedding generation (default: 10)")
+	indexCmd.Flags().IntVar(&embeddingBatchSize, "batch-size", 8, "Number of chunks per embedding request (default: 8)")
+	indexCmd.Flags().IntVar(&overlapLines, "overlap", 0, "Lines of context shared between adjacent chunks (overrides config file)")
+	indexCmd.Flags().StringVar(&sinceRev, "since", "", "Only reindex files git reports as changed between this revision and HEAD; without a value, since the last indexed commit")
+	indexCmd.Flags().Lookup("since").NoOptDefVal = sinceIndexed
+	indexCmd.Flags().IntVar(&pipelineFiles, "pipeline-files", 100, "Number of files chunked, embedded, and stored together; lower values use less memory")
+	indexCmd.Flags().StringVar(&maxMemory, "max-memory", "", "Keep indexing under this much memory, e.g. 6GB, by shrinking batches and chunking fewer files at once")
+	indexCmd.Flags().BoolVar(&dryRun, "dry-run", false, "Report what would be indexed, skipped, and pruned, with an estimate of the embedding tokens, without indexing anything")
+	indexCmd.Flags().StringVar(&overlayRev, "overlay", "", "Index only the files git reports as changed between this revision and HEAD into an overlay index searched with the index; without a value, since the last indexed commit")
+	indexCmd.Flags().Lookup("overlay").NoOptDefVal = sinceIndexed
+	indexCmd.Flags().Float64Var(&tokenPrice, "token-price", 0, "Price in USD of a million embedding tokens, for the cost --dry-run estimates and the costs of models token_prices does not price")
+	addScanFlags(indexCmd)
+}
@@ -0,0 +1,150 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+var (
+	// indexName selects a named index with --index
+	indexName string
+	// indexRoot is the directory indexes create covers
+	indexRoot string
+)
+
+// workingDir returns the directory the command works on: the root of the
+// named index when one is selected, otherwise the current directory
+func workingDir() (string, error) {
+	if globalConfig != nil && globalConfig.Root != "" {
+		return globalConfig.Root, nil
+	}
+	return os.Getwd()
+}
+
+var indexesCmd = &cobra.Command{
+	Use:   "indexes",
+	Short: "Manage named indexes",
+	Long: `Manage named indexes, which are kept in ~/.code-scout/indexes/<name> with their
+own config.json and storage, and selected with --index <name> from any directory.`,
+}
+
+var indexesListCmd = &cobra.Command{
+	Use:   "list",
+	Short: "List the named indexes",
+	Args:  cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		names, err := config.ListIndexes()
+		if err != nil {
+			return fmt.Errorf("failed to list indexes: %w", err)
+		}
+		if len(names) == 0 {
+			fmt.Println("No named indexes; create one with 'code-scout indexes create <name>'")
+			return nil
+		}
+		for _, name := range names {
+			cfg, err := config.LoadNamed(name)
+			if err != nil {
+				fmt.Printf("%-20s (%v)\n", name, err)
+				continue
+			}
+			fmt.Printf("%-20s %s\n", name, cfg.Root)
+		}
+		return nil
+	},
+}
+
+var indexesCreateCmd = &cobra.Command{
+	Use:   "create <name>",
+	Short: "Create a named index",
+	Long: `Create a named index covering --root (default: the current directory). Its
+config.json in ~/.code-scout/indexes/<name> overrides the user-level config for
+commands run with --index <name>.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		name := args[0]
+		dir, err := config.NamedIndexDir(name)
+		if err != nil {
+			return err
+		}
+		if _, err := os.Stat(dir); err == nil {
+			return fmt.Errorf("index %s already exists", name)
+		}
+
+		root := indexRoot
+		if root == "" {
+			if root, err = os.Getwd(); err != nil {
+				return fmt.Errorf("failed to get current directory: %w", err)
+			}
+		}
+		if root, err = filepath.Abs(root); err != nil {
+			return fmt.Errorf("invalid root %s: %w", indexRoot, err)
+		}
+
+		cfg := &config.Config{Root: root}
+		if err := cfg.Save(filepath.Join(dir, "config.json")); err != nil {
+			return fmt.Errorf("failed to create index %s: %w", name, err)
+		}
+		fmt.Printf("Created index %s for %s\n", name, root)
+		fmt.Printf("Build it with 'code-scout --index %s index'\n", name)
+		return nil
+	},
+}
+
+var indexesDeleteCmd = &cobra.Command{
+	Use:   "delete <name>",
+	Short: "Delete a named index and its storage",
+	Args:  cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if err := checkWritable("delete an index"); err != nil {
+			return err
+		}
+		name := args[0]
+		dir, err := config.NamedIndexDir(name)
+		if err != nil {
+			return err
+		}
+		if _, err := os.Stat(dir); os.IsNotExist(err) {
+			return fmt.Errorf("index %s does not exist", name)
+		}
+		if err := deleteNamedIndex(commandContext(cmd), dir); err != nil {
+			return fmt.Errorf("failed to delete index %s: %w", name, err)
+		}
+		fmt.Printf("Deleted index %s\n", name)
+		return nil
+	},
+}
+
+// deleteNamedIndex removes the named index in dir under its lock, so an index
+// or watch run writing it finishes first rather than writing into a deleted
+// directory
+func deleteNamedIndex(ctx context.Context, dir string) error {
+	store, err := storage.NewLanceDBStore(dir)
+	if err != nil {
+		return err
+	}
+	lock, err := lockIndex(ctx, store)
+	store.Close()
+	if err != nil {
+		return err
+	}
+	err = os.RemoveAll(dir)
+	lock.Unlock()
+	if err != nil {
+		// Where an open file cannot be removed, the lock file is only removed
+		// once it is released
+		err = os.RemoveAll(dir)
+	}
+	return err
+}
+
+func init() {
+	rootCmd.AddCommand(indexesCmd)
+	indexesCmd.AddCommand(indexesListCmd, indexesCreateCmd, indexesDeleteCmd)
+	indexesCreateCmd.Flags().StringVar(&indexRoot, "root", "", "Directory the index covers (default: the current directory)")
+}
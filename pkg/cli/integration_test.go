@@ -0,0 +1,434 @@
+package cli
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+type fakeEmbeddingClient struct {
+	offset float64
+}
+
+func (f *fakeEmbeddingClient) Embed(text string) ([]float64, error) {
+	vecs, err := f.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return vecs[0], nil
+}
+
+func (f *fakeEmbeddingClient) EmbedMany(texts []string) ([][]float64, error) {
+	vectors := make([][]float64, len(texts))
+	for i, text := range texts {
+		vectors[i] = fakeVector(text, f.offset)
+	}
+	return vectors, nil
+}
+
+func fakeVector(text string, offset float64) []float64 {
+	vec := make([]float64, 3584)
+	var total float64
+	for _, r := range text {
+		total += float64(int(r))
+	}
+	vec[0] = float64(len(text)) + offset
+	vec[1] = total + offset
+	return vec
+}
+
+func installFakeEmbeddings(t *testing.T) {
+	codeClient := &fakeEmbeddingClient{offset: 1}
+	docsClient := &fakeEmbeddingClient{offset: 1000}
+	prevCode := newCodeEmbeddingClient
+	prevDocs := newDocsEmbeddingClient
+	newCodeEmbeddingClient = func() embeddings.Client { return codeClient }
+	newDocsEmbeddingClient = func() embeddings.Client { return docsClient }
+	t.Cleanup(func() {
+		newCodeEmbeddingClient = prevCode
+		newDocsEmbeddingClient = prevDocs
+	})
+}
+
+func TestIndexAndSearchEndToEnd(t *testing.T) {
+	installFakeEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", `package main
+
+func Add(a, b int) int {
+	return a + b
+}
+`)
+	writeTestFile(t, workDir, "README.md", `# Project Docs
+
+## Architecture Overview
+
+This section explains the architecture.
+`)
+	writeTestFile(t, workDir, "notes.txt", "some text file")
+
+	runInDir(t, workDir, func() error {
+		indexCmd.Flags().Set("workers", "2")
+		indexCmd.Flags().Set("batch-size", "2")
+		return indexCmd.RunE(indexCmd, []string{})
+	})
+
+	docs := runSearchJSON(t, workDir, "architecture overview", modeDocs)
+	if docs.Mode != string(modeDocs) {
+		t.Fatalf("expected docs mode, got %s", docs.Mode)
+	}
+	if len(docs.Results) == 0 {
+		t.Fatalf("expected documentation results")
+	}
+	if !containsFile(docs.Results, "README.md", "docs") {
+		t.Fatalf("expected README docs result, got %+v", docs.Results)
+	}
+
+	code := runSearchJSON(t, workDir, "add", modeCode)
+	if code.Mode != string(modeCode) {
+		t.Fatalf("expected code mode, got %s", code.Mode)
+	}
+	if len(code.Results) == 0 {
+		t.Fatalf("expected code results")
+	}
+	if !containsFile(code.Results, "main.go", "code") {
+		t.Fatalf("expected main.go code result, got %+v", code.Results)
+	}
+
+	hybrid := runSearchJSON(t, workDir, "architecture overview", modeHybrid)
+	if hybrid.Mode != string(modeHybrid) {
+		t.Fatalf("expected hybrid mode, got %s", hybrid.Mode)
+	}
+	var foundDocs, foundCode bool
+	for _, res := range hybrid.Results {
+		if strings.HasSuffix(res.FilePath, "README.md") {
+			foundDocs = true
+		}
+		if strings.HasSuffix(res.FilePath, "main.go") {
+			foundCode = true
+		}
+	}
+	if !foundDocs || !foundCode {
+		t.Fatalf("hybrid search did not include both docs and code results")
+	}
+}
+
+func runSearchJSON(t *testing.T, dir, query string, mode searchMode) searchResponse {
+	t.Helper()
+	prevJSON := jsonOutput
+	prevLimit := limitFlag
+	prevCode := codeMode
+	prevDocs := docsMode
+	prevHybrid := hybridMode
+	jsonOutput = true
+	limitFlag = 5
+	codeMode = mode == modeCode
+	docsMode = mode == modeDocs
+	hybridMode = mode == modeHybrid
+	defer func() {
+		jsonOutput = prevJSON
+		limitFlag = prevLimit
+		codeMode = prevCode
+		docsMode = prevDocs
+		hybridMode = prevHybrid
+	}()
+	output := captureStdout(t, func() {
+		runInDir(t, dir, func() error {
+			return searchCmd.RunE(searchCmd, []string{query})
+		})
+	})
+	var resp searchResponse
+	if err := json.Unmarshal([]byte(output), &resp); err != nil {
+		t.Fatalf("failed to parse search output: %v\n%s", err, output)
+	}
+	return resp
+}
+
+type searchResponse struct {
+	Query   string         `json:"query"`
+	Mode    string         `json:"mode"`
+	Results []SearchResult `json:"results"`
+}
+
+func captureStdout(t *testing.T, fn func()) string {
+	t.Helper()
+	old := os.Stdout
+	r, w, err := os.Pipe()
+	if err != nil {
+		t.Fatalf("pipe: %v", err)
+	}
+	os.Stdout = w
+	outC := make(chan string)
+	go func() {
+		var buf bytes.Buffer
+		_, _ = io.Copy(&buf, r)
+		outC <- buf.String()
+	}()
+	fn()
+	_ = w.Close()
+	os.Stdout = old
+	return <-outC
+}
+
+func runInDir(t *testing.T, dir string, fn func() error) {
+	t.Helper()
+	orig, err := os.Getwd()
+	if err != nil {
+		t.Fatalf("getwd: %v", err)
+	}
+	if err := os.Chdir(dir); err != nil {
+		t.Fatalf("chdir: %v", err)
+	}
+	defer func() {
+		_ = os.Chdir(orig)
+	}()
+	if err := fn(); err != nil {
+		t.Fatalf("command error: %v", err)
+	}
+}
+
+func writeTestFile(t *testing.T, dir, name, contents string) {
+	t.Helper()
+	path := filepath.Join(dir, name)
+	if err := os.WriteFile(path, []byte(contents), 0o644); err != nil {
+		t.Fatalf("write file %s: %v", name, err)
+	}
+}
+
+func containsFile(results []SearchResult, suffix, embeddingType string) bool {
+	for _, res := range results {
+		if strings.HasSuffix(res.FilePath, suffix) && res.EmbeddingType == embeddingType {
+			return true
+		}
+	}
+	return false
+}
+
+// shortVectorClient embeds like fakeEmbeddingClient with vectors cut to 768
+// dimensions, as a text model smaller than the code model
+type shortVectorClient struct {
+	fake fakeEmbeddingClient
+}
+
+func (c *shortVectorClient) Embed(text string) ([]float64, error) {
+	vecs, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return vecs[0], nil
+}
+
+func (c *shortVectorClient) EmbedMany(texts []string) ([][]float64, error) {
+	vecs, err := c.fake.EmbedMany(texts)
+	for i := range vecs {
+		vecs[i] = vecs[i][:768]
+	}
+	return vecs, err
+}
+
+func TestIndexStoresShorterTextVectors(t *testing.T) {
+	installFakeEmbeddings(t)
+	newDocsEmbeddingClient = func() embeddings.Client { return &shortVectorClient{fake: fakeEmbeddingClient{offset: 1000}} }
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	writeTestFile(t, workDir, "README.md", "# Project Docs\n\n## Architecture Overview\n\nThis section explains the architecture.\n")
+
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+
+	docs := runSearchJSON(t, workDir, "architecture overview", modeDocs)
+	if !containsFile(docs.Results, "README.md", "docs") {
+		t.Fatalf("expected README docs result, got %+v", docs.Results)
+	}
+	code := runSearchJSON(t, workDir, "add", modeCode)
+	if !containsFile(code.Results, "main.go", "code") {
+		t.Fatalf("expected main.go code result, got %+v", code.Results)
+	}
+}
+
+// recordingClient embeds like fakeEmbeddingClient and records the texts it
+// was asked to embed
+type recordingClient struct {
+	fake  fakeEmbeddingClient
+	mu    sync.Mutex
+	texts []string
+}
+
+func (c *recordingClient) Embed(text string) ([]float64, error) {
+	vecs, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return vecs[0], nil
+}
+
+func (c *recordingClient) EmbedMany(texts []string) ([][]float64, error) {
+	c.mu.Lock()
+	c.texts = append(c.texts, texts...)
+	c.mu.Unlock()
+	return c.fake.EmbedMany(texts)
+}
+
+// embedded reports whether a text containing s was embedded, and forgets the
+// texts recorded so far
+func (c *recordingClient) embedded(s string) bool {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	texts := c.texts
+	c.texts = nil
+	for _, text := range texts {
+		if strings.Contains(text, s) {
+			return true
+		}
+	}
+	return false
+}
+
+// installRecordingEmbeddings installs fake embeddings that record the texts
+// embedded, returning the code and docs clients
+func installRecordingEmbeddings(t *testing.T) (code, docs *recordingClient) {
+	installFakeEmbeddings(t)
+	code = &recordingClient{fake: fakeEmbeddingClient{offset: 1}}
+	docs = &recordingClient{fake: fakeEmbeddingClient{offset: 1000}}
+	newCodeEmbeddingClient = func() embeddings.Client { return code }
+	newDocsEmbeddingClient = func() embeddings.Client { return docs }
+	return code, docs
+}
+
+func TestIndexSkipsTouchedButUnchangedFiles(t *testing.T) {
+	code, _ := installRecordingEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+	if !code.embedded("func Add") {
+		t.Fatal("the first run did not embed main.go")
+	}
+
+	// touch: a new modification time, the same content
+	later := time.Now().Add(time.Hour)
+	if err := os.Chtimes(filepath.Join(workDir, "main.go"), later, later); err != nil {
+		t.Fatal(err)
+	}
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+	if code.embedded("func Add") {
+		t.Error("main.go was embedded again though only its modification time changed")
+	}
+	results := runSearchJSON(t, workDir, "add", modeCode)
+	if !containsFile(results.Results, "main.go", "code") {
+		t.Errorf("search after the touch found %+v, want main.go", results.Results)
+	}
+}
+
+func TestIndexReembedsAfterModelChange(t *testing.T) {
+	code, docs := installRecordingEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	writeTestFile(t, workDir, "README.md", "# Project Docs\n\n## Architecture Overview\n\nThis section explains the architecture.\n")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+	code.texts, docs.texts = nil, nil
+
+	// Record other models of the same width, as if the configuration changed
+	store, err := storage.NewLanceDBStore(workDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	old := embeddingModels()
+	old.CodeModel, old.TextModel = "old-code-model", "old-text-model"
+	metadata.Models = &old
+	err = store.SaveMetadata(metadata)
+	store.Close()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	captureStdout(t, func() {
+		runInDir(t, workDir, func() error {
+			return indexCmd.RunE(indexCmd, nil)
+		})
+	})
+	if !code.embedded("func Add") {
+		t.Error("main.go was not embedded again with the new code model")
+	}
+	if !docs.embedded("Architecture Overview") {
+		t.Error("README.md was not embedded again with the new text model")
+	}
+
+	store, err = storage.NewLanceDBStore(workDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	metadata, err = store.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if metadata.Models == nil || *metadata.Models != embeddingModels() {
+		t.Errorf("index records models %+v, want the configured %+v", metadata.Models, embeddingModels())
+	}
+	results := runSearchJSON(t, workDir, "add", modeCode)
+	if !containsFile(results.Results, "main.go", "code") {
+		t.Errorf("search after the re-embedding found %+v, want main.go", results.Results)
+	}
+}
+
+func TestIndexDropsDeletedFiles(t *testing.T) {
+	installFakeEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	writeTestFile(t, workDir, "util.go", "package main\n\nfunc Sub(a, b int) int {\n\treturn a - b\n}\n")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+	if results := runSearchJSON(t, workDir, "sub", modeCode); !containsFile(results.Results, "util.go", "code") {
+		t.Fatalf("search before the delete found %+v, want util.go", results.Results)
+	}
+
+	if err := os.Remove(filepath.Join(workDir, "util.go")); err != nil {
+		t.Fatal(err)
+	}
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+
+	results := runSearchJSON(t, workDir, "sub", modeCode)
+	if containsFile(results.Results, "util.go", "code") {
+		t.Errorf("search after the delete found %+v, want no chunks of util.go", results.Results)
+	}
+	if !containsFile(results.Results, "main.go", "code") {
+		t.Errorf("search after the delete found %+v, want main.go kept", results.Results)
+	}
+	store, err := storage.NewLanceDBStore(workDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	for path := range metadata.FileModTimes {
+		if strings.HasSuffix(path, "util.go") {
+			t.Errorf("metadata still records %s", path)
+		}
+	}
+}
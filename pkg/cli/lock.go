@@ -0,0 +1,35 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// lockPoll is how often a process waiting for the index lock tries again
+const lockPoll = 500 * time.Millisecond
+
+// lockIndex takes the lock that the process changing the index holds, waiting
+// while another index or watch run holds it
+func lockIndex(ctx context.Context, store *storage.LanceDBStore) (*storage.IndexLock, error) {
+	waiting := false
+	for {
+		lock, err := store.TryLock()
+		if !errors.Is(err, storage.ErrLocked) {
+			return lock, err
+		}
+		if !waiting {
+			fmt.Fprintf(os.Stderr, "Waiting: %v\n", err)
+			waiting = true
+		}
+		select {
+		case <-ctx.Done():
+			return nil, ctx.Err()
+		case <-time.After(lockPoll):
+		}
+	}
+}
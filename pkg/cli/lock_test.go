@@ -0,0 +1,89 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"os"
+	"testing"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// holdLock locks the index in dir as another run would, returning the lock
+func holdLock(t *testing.T, dir string) *storage.IndexLock {
+	t.Helper()
+	store, err := storage.NewLanceDBStore(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	lock, err := store.TryLock()
+	if err != nil {
+		t.Fatal(err)
+	}
+	return lock
+}
+
+func TestLockIndexWaitsForTheHolder(t *testing.T) {
+	dir := t.TempDir()
+	held := holdLock(t, dir)
+	go func() {
+		time.Sleep(100 * time.Millisecond)
+		held.Unlock()
+	}()
+
+	store, err := storage.NewLanceDBStore(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	lock, err := lockIndex(context.Background(), store)
+	if err != nil {
+		t.Fatalf("lockIndex() error = %v, want the lock once it was released", err)
+	}
+	lock.Unlock()
+}
+
+func TestLockIndexStopsWhenCancelled(t *testing.T) {
+	dir := t.TempDir()
+	held := holdLock(t, dir)
+	defer held.Unlock()
+
+	store, err := storage.NewLanceDBStore(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	ctx, cancel := context.WithTimeout(context.Background(), 50*time.Millisecond)
+	defer cancel()
+	start := time.Now()
+	if _, err := lockIndex(ctx, store); !errors.Is(err, context.DeadlineExceeded) {
+		t.Fatalf("lockIndex() error = %v, want the context's error", err)
+	}
+	if waited := time.Since(start); waited > 2*time.Second {
+		t.Errorf("lockIndex() returned after %s, want it to stop when cancelled", waited)
+	}
+}
+
+func TestDeleteNamedIndexWaitsForTheLock(t *testing.T) {
+	dir := t.TempDir()
+	held := holdLock(t, dir)
+
+	ctx, cancel := context.WithTimeout(context.Background(), 50*time.Millisecond)
+	defer cancel()
+	if err := deleteNamedIndex(ctx, dir); !errors.Is(err, context.DeadlineExceeded) {
+		t.Fatalf("deleteNamedIndex() of a locked index error = %v, want it to wait", err)
+	}
+	if _, err := os.Stat(dir); err != nil {
+		t.Fatalf("locked index was deleted: %v", err)
+	}
+
+	held.Unlock()
+	if err := deleteNamedIndex(context.Background(), dir); err != nil {
+		t.Fatalf("deleteNamedIndex() error = %v", err)
+	}
+	if _, err := os.Stat(dir); !os.IsNotExist(err) {
+		t.Errorf("index still exists after delete: %v", err)
+	}
+}
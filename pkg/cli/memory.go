@@ -0,0 +1,101 @@
+package cli
+
+import (
+	"fmt"
+	"math"
+	"runtime"
+	"runtime/debug"
+	"strconv"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+)
+
+// maxMemory caps the memory indexing uses with --max-memory, e.g. 6GB
+var maxMemory string
+
+const (
+	// pipelineBatches is how many batches the indexing pipeline holds at once:
+	// one in each stage and one in each channel between them
+	pipelineBatches = 5
+	// batchMemoryFactor estimates the memory a batch takes per byte of its
+	// files: the text, its chunks, and a vector of float64s for every chunk
+	batchMemoryFactor = 40
+	// chunkWorkerMemory is the memory set aside for each file chunked at once,
+	// mostly for its syntax tree
+	chunkWorkerMemory = 256 << 20
+)
+
+// memoryBudget holds the limits that keep indexing under --max-memory. Half
+// of the budget goes to the batches in the pipeline and the files being
+// chunked; the rest is left for the stored index, the cached embeddings, and
+// the runtime.
+type memoryBudget struct {
+	limit        int64 // Bytes; 0 without --max-memory
+	batchBytes   int64 // Bytes of files per batch
+	chunkWorkers int   // Files chunked at once
+}
+
+// indexMemoryBudget returns the limits for --max-memory, and sets the limit
+// the garbage collector keeps the heap under
+func indexMemoryBudget() (memoryBudget, error) {
+	if maxMemory == "" {
+		return memoryBudget{}, nil
+	}
+	limit, err := parseMemorySize(maxMemory)
+	if err != nil {
+		return memoryBudget{}, fmt.Errorf("invalid --max-memory: %w", err)
+	}
+	debug.SetMemoryLimit(limit)
+	share := limit / 2
+	return memoryBudget{
+		limit:        limit,
+		batchBytes:   max(share/(pipelineBatches*batchMemoryFactor), 1),
+		chunkWorkers: int(min(max(share/chunkWorkerMemory, 1), int64(runtime.NumCPU()))),
+	}, nil
+}
+
+// limitChunking streams files too large for a batch of their own rather than
+// reading them whole
+func (b memoryBudget) limitChunking(options *chunker.Options) {
+	if b.limit == 0 {
+		return
+	}
+	threshold := options.StreamThreshold
+	if threshold == 0 {
+		threshold = chunker.DefaultStreamThreshold
+	}
+	options.StreamThreshold = min(threshold, b.batchBytes)
+}
+
+// limitPipeline caps the size of the pipeline's batches and the files it
+// chunks at once
+func (b memoryBudget) limitPipeline(p *indexPipeline) {
+	if b.limit == 0 {
+		return
+	}
+	p.batchBytes, p.chunkWorkers = b.batchBytes, b.chunkWorkers
+	fmt.Printf("Limiting memory to %s: batches of up to %s of files, %d file(s) chunked at once\n",
+		formatBytes(b.limit), formatBytes(b.batchBytes), b.chunkWorkers)
+}
+
+// parseMemorySize parses a size in bytes with an optional K, M, G, or T
+// suffix, in powers of 1024, optionally followed by B or iB, e.g. 512MB or 6G
+func parseMemorySize(value string) (int64, error) {
+	s := strings.ToUpper(strings.TrimSpace(value))
+	s = strings.TrimSuffix(strings.TrimSuffix(s, "IB"), "B")
+	multiplier := int64(1)
+	if n := len(s); n > 0 {
+		if i := strings.IndexByte("KMGT", s[n-1]); i >= 0 {
+			multiplier = 1 << (10 * (i + 1))
+			s = s[:n-1]
+		}
+	}
+	n, err := strconv.ParseFloat(strings.TrimSpace(s), 64)
+	// ParseFloat accepts NaN and Inf, and a size under a byte would be no limit
+	size := n * float64(multiplier)
+	if err != nil || math.IsNaN(size) || size < 1 || size >= math.MaxInt64 {
+		return 0, fmt.Errorf("%q is not a size such as 512MB or 6GB", value)
+	}
+	return int64(size), nil
+}
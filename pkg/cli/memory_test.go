@@ -0,0 +1,45 @@
+package cli
+
+import "testing"
+
+func TestParseMemorySize(t *testing.T) {
+	tests := []struct {
+		value   string
+		want    int64
+		wantErr bool
+	}{
+		{"512", 512, false},
+		{"512B", 512, false},
+		{"4K", 4 << 10, false},
+		{"4kb", 4 << 10, false},
+		{"512MB", 512 << 20, false},
+		{"512MiB", 512 << 20, false},
+		{"6GB", 6 << 30, false},
+		{" 6g ", 6 << 30, false},
+		{"1.5G", 3 << 29, false},
+		{"2T", 2 << 40, false},
+		{"", 0, true},
+		{"GB", 0, true},
+		{"six GB", 0, true},
+		{"6PB", 0, true},
+		{"6GB6", 0, true},
+		{"NaN", 0, true},
+		{"InfG", 0, true},
+		{"0", 0, true},
+		{"0GB", 0, true},
+		{"0.5B", 0, true},
+		{"-1GB", 0, true},
+		{"1e30T", 0, true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			got, err := parseMemorySize(tt.value)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("parseMemorySize(%q) error = %v, wantErr %v", tt.value, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("parseMemorySize(%q) = %d, want %d", tt.value, got, tt.want)
+			}
+		})
+	}
+}
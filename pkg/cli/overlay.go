@@ -0,0 +1,212 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+// overlayDirName is the directory in .code-scout that holds the overlay index
+// of the files a diff touched
+const overlayDirName = "overlay"
+
+var (
+	// overlayRev makes index build an overlay index of the files changed
+	// between this revision and HEAD instead of updating the index
+	overlayRev string
+	// noOverlay makes search read the base index without its overlay
+	noOverlay bool
+)
+
+// overlayDir returns the directory whose .code-scout holds the overlay index
+// of the index kept in dir
+func overlayDir(dir string) string {
+	return filepath.Join(dir, storage.DefaultDBDir, overlayDirName)
+}
+
+// openOverlay opens the overlay index of the index kept in dir and loads its
+// metadata, returning a nil store when there is no overlay
+func openOverlay(dir string) (*storage.LanceDBStore, *storage.IndexMetadata, error) {
+	metadataFile := filepath.Join(overlayDir(dir), storage.DefaultDBDir, storage.MetadataFileName)
+	if _, err := os.Stat(metadataFile); os.IsNotExist(err) {
+		return nil, nil, nil
+	}
+	store, err := openStore(overlayDir(dir))
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to open the overlay index: %w", err)
+	}
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		store.Close()
+		return nil, nil, fmt.Errorf("failed to load the overlay index's metadata: %w", err)
+	}
+	return store, metadata, nil
+}
+
+// removeOverlay deletes the overlay index of the index kept in dir, once the
+// index itself covers the files the overlay held
+func removeOverlay(dir string) error {
+	if err := os.RemoveAll(overlayDir(dir)); err != nil {
+		return fmt.Errorf("failed to remove the overlay index: %w", err)
+	}
+	return nil
+}
+
+// indexOverlay indexes the files git reports as changed between overlayRev
+// and HEAD into the overlay index, replacing any earlier overlay, and leaves
+// the base index as it is. Searches read the overlay in place of the base
+// index's chunks of those files, so a review of a branch searches its code
+// after embedding only the files it touched.
+func indexOverlay(cmd *cobra.Command, cwd string) error {
+	if err := checkWritable("index an overlay"); err != nil {
+		return err
+	}
+	if dryRun || cmd.Flags().Changed("since") {
+		return fmt.Errorf("--overlay cannot be combined with --dry-run or --since")
+	}
+	fmt.Println("Indexing the overlay...")
+
+	dir, err := indexStoreDir(cwd)
+	if err != nil {
+		return err
+	}
+	base, err := openStore(dir)
+	if err != nil {
+		return fmt.Errorf("failed to open database: %w", err)
+	}
+	defer base.Close()
+
+	// The overlay's chunks are searched with the base index's, so both are
+	// embedded with the same models
+	baseMetadata, err := base.LoadMetadata()
+	if err != nil {
+		return fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if baseMetadata.IsEmpty() {
+		return fmt.Errorf("the index is empty; run 'code-scout index' before indexing an overlay")
+	}
+	models := embeddingModels()
+	if reason := baseMetadata.Incompatibility(models); reason != "" {
+		return fmt.Errorf("%s; run 'code-scout index' to rebuild it", reason)
+	}
+	if reason, _ := keyMismatch(baseMetadata, base); reason != "" {
+		return fmt.Errorf("%s", reason)
+	}
+	if err := checkVectors(baseMetadata, dir); err != nil {
+		return err
+	}
+
+	store, err := openStore(overlayDir(dir))
+	if err != nil {
+		return fmt.Errorf("failed to create the overlay index: %w", err)
+	}
+	defer store.Close()
+	lock, err := lockIndex(commandContext(cmd), store)
+	if err != nil {
+		return err
+	}
+	defer lock.Unlock()
+
+	// The overlay is rebuilt from scratch in a new generation, so a failed run
+	// leaves the previous overlay in place
+	gen, err := store.BeginGeneration()
+	if err != nil {
+		return err
+	}
+	defer gen.Close()
+	if err := gen.Reset(); err != nil {
+		return fmt.Errorf("failed to reset the overlay index: %w", err)
+	}
+	metadata := storage.NewIndexMetadata()
+	metadata.Models = &models
+	metadata.Vectors = baseMetadata.Vectors
+	metadata.KeyFingerprint = gen.KeyFingerprint()
+	metadata.Root = cwd
+	metadata.ConfigFingerprint = configFingerprint()
+	metadata.EmbedImports = globalConfig != nil && globalConfig.EmbedImports
+
+	roots, err := workspaceRoots(cwd)
+	if err != nil {
+		return err
+	}
+	dirs := make(dirConfigs)
+	allFiles, err := scanWorkspace(roots, scannerOptions(cmd, dirs))
+	if err != nil {
+		return err
+	}
+	filesToIndex, shadowed, _, err := gitDelta(roots, overlayRev, baseMetadata, allFiles)
+	if err != nil {
+		return err
+	}
+	metadata.Shadowed = shadowed
+
+	// Unchanged chunks of the changed files keep the base index's embeddings
+	cachedEmbeddings, err := base.LoadEmbeddings(shadowed)
+	if err != nil {
+		return err
+	}
+
+	chunkOptions, err := chunkerOptions(cmd)
+	if err != nil {
+		return err
+	}
+	budget, err := indexMemoryBudget()
+	if err != nil {
+		return err
+	}
+	budget.limitChunking(&chunkOptions)
+	semanticChunker, err := chunker.NewSemanticWithOptions(chunkOptions)
+	if err != nil {
+		return fmt.Errorf("failed to create semantic chunker: %w", err)
+	}
+	dirChunkers, err := dirs.chunkers(chunkOptions, filesToIndex)
+	if err != nil {
+		return fmt.Errorf("failed to create semantic chunker: %w", err)
+	}
+
+	fmt.Printf("Indexing %d changed file(s); %d file(s) of the index are replaced\n", len(filesToIndex), len(shadowed))
+	metadata.LastIndexTime = time.Now()
+	pipeline := &indexPipeline{
+		chunker:    semanticChunker,
+		chunkers:   dirChunkers,
+		codeClient: newCodeEmbeddingClient(),
+		docsClient: newDocsEmbeddingClient(),
+		cached:     cachedEmbeddings,
+		batchFiles: pipelineFiles,
+		store: func(batch indexBatch) error {
+			if err := gen.StoreChunks(batch.chunks, batch.embeddings); err != nil {
+				return fmt.Errorf("failed to store chunks: %w", err)
+			}
+			for _, f := range batch.files {
+				hash, err := hashFile(f.Path)
+				if err != nil {
+					return fmt.Errorf("failed to hash %s: %w", f.Path, err)
+				}
+				metadata.FileModTimes[f.Path] = f.ModTime
+				metadata.FileHashes[f.Path] = hash
+			}
+			return nil
+		},
+	}
+	budget.limitPipeline(pipeline)
+	fmt.Printf("Using %d files per batch and %d CPU(s) for chunking\n", max(pipelineFiles, 1), pipeline.chunkParallelism())
+	stored, err := pipeline.run(commandContext(cmd), filesToIndex)
+	if err != nil {
+		return err
+	}
+	fmt.Printf("\nTotal chunks: %d\n", stored)
+	recordCommits(metadata, roots)
+	recordUsage(metadata)
+
+	if err := store.CommitGeneration(gen, metadata); err != nil {
+		return fmt.Errorf("failed to save the overlay index: %w", err)
+	}
+	fmt.Println("✓ Overlay indexing complete! Searches read it together with the index")
+	printUsage()
+	return pipeline.reportFailures()
+}
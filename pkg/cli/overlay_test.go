@@ -0,0 +1,83 @@
+package cli
+
+import (
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+// runGit runs git in dir, failing the test if it fails, and returns its output
+func runGit(t *testing.T, dir string, args ...string) string {
+	t.Helper()
+	cmd := exec.Command("git", args...)
+	cmd.Dir = dir
+	output, err := cmd.CombinedOutput()
+	if err != nil {
+		t.Fatalf("git %v failed: %v\n%s", args, err, output)
+	}
+	return strings.TrimSpace(string(output))
+}
+
+func TestOverlayShadowsBaseIndex(t *testing.T) {
+	if _, err := exec.LookPath("git"); err != nil {
+		t.Skip("git is not installed")
+	}
+	installFakeEmbeddings(t)
+	workDir := t.TempDir()
+	if resolved, err := filepath.EvalSymlinks(workDir); err == nil {
+		workDir = resolved
+	}
+	runGit(t, workDir, "init", "-q")
+	runGit(t, workDir, "config", "user.email", "test@example.com")
+	runGit(t, workDir, "config", "user.name", "Test")
+	runGit(t, workDir, "config", "commit.gpgsign", "false")
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	writeTestFile(t, workDir, "util.go", "package main\n\nfunc Sub(a, b int) int {\n\treturn a - b\n}\n")
+	runGit(t, workDir, "add", "main.go", "util.go")
+	runGit(t, workDir, "commit", "-q", "-m", "base")
+	base := runGit(t, workDir, "rev-parse", "HEAD")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+
+	// A branch that changes main.go, indexed as an overlay of the base index
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc AddAll(values ...int) int {\n\ttotal := 0\n\tfor _, v := range values {\n\t\ttotal += v\n\t}\n\treturn total\n}\n")
+	runGit(t, workDir, "commit", "-q", "-am", "branch")
+	t.Cleanup(func() {
+		overlayRev = ""
+		indexCmd.Flags().Lookup("overlay").Changed = false
+	})
+	if err := indexCmd.Flags().Set("overlay", base); err != nil {
+		t.Fatal(err)
+	}
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+
+	results := runSearchJSON(t, workDir, "add", modeCode)
+	var mainResults int
+	for _, res := range results.Results {
+		if !strings.HasSuffix(res.FilePath, "main.go") {
+			continue
+		}
+		mainResults++
+		if strings.Contains(res.Code, "func Add(") {
+			t.Errorf("search returned the base index's chunk of main.go: %q", res.Code)
+		}
+	}
+	if mainResults == 0 || !containsFile(results.Results, "util.go", "code") {
+		t.Errorf("search found %+v, want the overlay's main.go and the base index's util.go", results.Results)
+	}
+
+	// Without the overlay, search reads the base index as it was
+	prevNoOverlay := noOverlay
+	noOverlay = true
+	t.Cleanup(func() { noOverlay = prevNoOverlay })
+	results = runSearchJSON(t, workDir, "add", modeCode)
+	for _, res := range results.Results {
+		if strings.HasSuffix(res.FilePath, "main.go") && strings.Contains(res.Code, "AddAll") {
+			t.Errorf("search without the overlay returned its chunk of main.go: %q", res.Code)
+		}
+	}
+}
@@ -0,0 +1,442 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"runtime"
+	"sort"
+	"sync"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/scanner"
+)
+
+// indexBatch is a group of files that moves through the indexing pipeline
+// together
+type indexBatch struct {
+	files      []scanner.FileInfo
+	chunks     []chunker.Chunk
+	embeddings [][]float64
+}
+
+// indexPipeline chunks, embeds, and stores files in three concurrent stages.
+// Files move through the stages in batches over channels, so a large
+// repository is indexed on all cores while only a few batches of chunks and
+// embeddings are in memory at once. Chunks are handed to the embedding stage
+// as soon as it can take them, so files are parsed while earlier ones are
+// embedded rather than in turns.
+type indexPipeline struct {
+	chunker    *chunker.SemanticChunker
+	chunkers   map[string]*chunker.SemanticChunker // Chunkers of files with directory settings, by path
+	codeClient embeddings.Client
+	docsClient embeddings.Client
+	cached     map[string][]float64 // Embeddings of unchanged chunks, by content hash
+	batchFiles int                  // Files per batch
+	batchBytes int64                // Bytes of files per batch; 0 for no limit
+	// chunkWorkers is how many files are chunked at once; 0 uses every core
+	chunkWorkers int
+	// store writes a batch that has been embedded; batches are stored one at
+	// a time, in order
+	store func(batch indexBatch) error
+	// failed holds the files with chunks that could not be embedded, with the
+	// error of the first; none of their chunks are stored or the files recorded,
+	// so the next run tries them again
+	failed map[string]error
+	// fellBack holds the files with chunks a fallback model embedded
+	fellBack map[string]bool
+}
+
+// run indexes files and returns the number of chunks stored. When a stage
+// fails, the batches stored before the failure stay stored.
+func (p *indexPipeline) run(ctx context.Context, files []scanner.FileInfo) (int, error) {
+	runCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	batchFiles := p.batchFiles
+	if batchFiles <= 0 {
+		batchFiles = 100
+	}
+
+	var (
+		errOnce  sync.Once
+		stageErr error
+	)
+	fail := func(err error) {
+		errOnce.Do(func() {
+			stageErr = err
+			cancel()
+		})
+	}
+
+	// Chunks are handed to the embedding stage directly, so a batch is sent
+	// without waiting only when that stage is waiting for one
+	chunked := make(chan indexBatch)
+	embedded := make(chan indexBatch, 1)
+
+	// Stage 1: chunk files in parallel and batch their chunks in file order.
+	// A batch goes on once the embedding stage is ready for it and it fills an
+	// embedding request, or once it is full. Files too large to chunk whole
+	// are streamed on their own, in batches of their chunks.
+	go func() {
+		defer close(chunked)
+		send := func(batch indexBatch) bool {
+			select {
+			case chunked <- batch:
+				return true
+			case <-runCtx.Done():
+				return false
+			}
+		}
+		var pending indexBatch
+		var pendingBytes int64
+		flush := func() bool {
+			if len(pending.files) == 0 && len(pending.chunks) == 0 {
+				return true
+			}
+			batch := pending
+			pending, pendingBytes = indexBatch{}, 0
+			return send(batch)
+		}
+		for result := range p.chunkFiles(runCtx, files, batchFiles) {
+			f := result.file
+			if result.streamed {
+				if !flush() {
+					return
+				}
+				if err := p.streamFile(runCtx, f, send); err != nil {
+					fail(err)
+					return
+				}
+				continue
+			}
+			if result.err != nil {
+				fail(fmt.Errorf("failed to chunk file %s: %w", f.Path, result.err))
+				return
+			}
+			fmt.Printf("  - %s: %d chunks\n", f.Path, len(result.chunks))
+			tagChunks(f, result.chunks)
+			pending.files = append(pending.files, f)
+			pending.chunks = append(pending.chunks, result.chunks...)
+			pendingBytes += f.Size
+
+			if len(pending.files) >= batchFiles || (p.batchBytes > 0 && pendingBytes >= p.batchBytes) {
+				if !flush() {
+					return
+				}
+			} else if len(pending.chunks) >= max(embeddingBatchSize, 1) {
+				// The embedding stage takes the batch now only if it is waiting;
+				// otherwise more files join it until it is full
+				select {
+				case chunked <- pending:
+					pending, pendingBytes = indexBatch{}, 0
+				default:
+				}
+			}
+		}
+		if runCtx.Err() == nil {
+			flush()
+		}
+	}()
+
+	// Stage 2: embed each batch with the model for its chunks
+	go func() {
+		defer close(embedded)
+		for batch := range chunked {
+			if runCtx.Err() != nil {
+				return
+			}
+			if err := p.embedBatch(&batch); err != nil {
+				fail(err)
+				return
+			}
+			select {
+			case embedded <- batch:
+			case <-runCtx.Done():
+				return
+			}
+		}
+	}()
+
+	// Stage 3: store batches as they are embedded
+	stored := 0
+	for batch := range embedded {
+		if runCtx.Err() != nil {
+			break
+		}
+		if err := p.store(batch); err != nil {
+			fail(err)
+			break
+		}
+		stored += len(batch.chunks)
+		fmt.Printf("Stored %d chunks from %d file(s)\n", len(batch.chunks), len(batch.files))
+	}
+	cancel()
+	// Let the stages finish so none is left writing to a channel
+	for range chunked {
+	}
+	for range embedded {
+	}
+
+	if stageErr != nil {
+		return stored, stageErr
+	}
+	// The run's own context is cancelled either way; only the caller's says
+	// whether the run was interrupted
+	return stored, ctx.Err()
+}
+
+// chunkerOf returns the chunker of a file
+func (p *indexPipeline) chunkerOf(path string) *chunker.SemanticChunker {
+	if c, ok := p.chunkers[path]; ok {
+		return c
+	}
+	return p.chunker
+}
+
+// chunkParallelism returns how many files are chunked at once
+func (p *indexPipeline) chunkParallelism() int {
+	if p.chunkWorkers > 0 {
+		return p.chunkWorkers
+	}
+	return runtime.NumCPU()
+}
+
+// chunkedFile is a file chunked by chunkFiles, or a file it left to be
+// streamed
+type chunkedFile struct {
+	file     scanner.FileInfo
+	chunks   []chunker.Chunk
+	err      error
+	streamed bool // The file is too large to chunk whole
+}
+
+// chunkFiles chunks files in parallel and sends each on with its chunks, in
+// file order, as soon as it and the files before it are chunked. At most
+// window files are chunked ahead of the file being received, so chunking
+// keeps going while chunks are embedded without holding the whole repository
+// in memory. Files the chunker streams are sent on unchunked.
+func (p *indexPipeline) chunkFiles(ctx context.Context, files []scanner.FileInfo, window int) <-chan chunkedFile {
+	results := make([]chan chunkedFile, len(files))
+	for i := range results {
+		results[i] = make(chan chunkedFile, 1)
+	}
+	slots := make(chan struct{}, max(window, 1))
+	indices := make(chan int)
+
+	for w := 0; w < p.chunkParallelism(); w++ {
+		go func() {
+			for i := range indices {
+				f := files[i]
+				result := chunkedFile{file: f}
+				c := p.chunkerOf(f.Path)
+				if c.Streams(f.Path) {
+					result.streamed = true
+				} else {
+					result.chunks, result.err = c.ChunkFile(f.Path, f.Language)
+				}
+				results[i] <- result
+			}
+		}()
+	}
+	go func() {
+		defer close(indices)
+		for i := range files {
+			select {
+			case slots <- struct{}{}:
+			case <-ctx.Done():
+				return
+			}
+			select {
+			case indices <- i:
+			case <-ctx.Done():
+				return
+			}
+		}
+	}()
+
+	out := make(chan chunkedFile)
+	go func() {
+		defer close(out)
+		for i := range files {
+			var result chunkedFile
+			select {
+			case result = <-results[i]:
+			case <-ctx.Done():
+				return
+			}
+			select {
+			case out <- result:
+			case <-ctx.Done():
+				return
+			}
+			<-slots
+		}
+	}()
+	return out
+}
+
+// streamFile chunks a large file as it is read and sends its chunks on as
+// they are made. Only the last batch lists the file, so the file is recorded
+// as indexed once all of its chunks are stored.
+func (p *indexPipeline) streamFile(ctx context.Context, f scanner.FileInfo, send func(indexBatch) bool) error {
+	var held []chunker.Chunk
+	total := 0
+	err := p.chunkerOf(f.Path).StreamFile(f.Path, f.Language, func(chunks []chunker.Chunk) error {
+		if held != nil && !send(indexBatch{chunks: held}) {
+			return ctx.Err()
+		}
+		tagChunks(f, chunks)
+		held = chunks
+		total += len(chunks)
+		return nil
+	})
+	if err != nil {
+		return fmt.Errorf("failed to chunk file %s: %w", f.Path, err)
+	}
+	fmt.Printf("  - %s: %d chunks (streamed)\n", f.Path, total)
+	send(indexBatch{files: []scanner.FileInfo{f}, chunks: held})
+	return nil
+}
+
+// tagChunks records the repository and submodule of a file on its chunks,
+// along with the content hash that identifies a chunk's embedding across runs
+func tagChunks(f scanner.FileInfo, chunks []chunker.Chunk) {
+	for i := range chunks {
+		if chunks[i].Metadata == nil {
+			chunks[i].Metadata = make(map[string]string)
+		}
+		if f.Repo != "" {
+			chunks[i].Metadata["repo"] = f.Repo
+		}
+		if f.Submodule != "" {
+			chunks[i].Metadata["submodule"] = f.Submodule
+		}
+		chunks[i].Metadata["content_hash"] = computeContentHash(embeddingText(chunks[i]))
+	}
+}
+
+// embedBatch embeds the code chunks of a batch with the code model and its
+// documentation chunks with the text model
+func (p *indexPipeline) embedBatch(batch *indexBatch) error {
+	var codeChunks, docsChunks []chunker.Chunk
+	var codeIndices, docsIndices []int
+	for i, chunk := range batch.chunks {
+		if chunk.EmbeddingType == "code" {
+			codeChunks = append(codeChunks, chunk)
+			codeIndices = append(codeIndices, i)
+		} else if chunk.EmbeddingType == "docs" {
+			docsChunks = append(docsChunks, chunk)
+			docsIndices = append(docsIndices, i)
+		}
+	}
+	batch.embeddings = make([][]float64, len(batch.chunks))
+
+	if len(codeChunks) > 0 {
+		codeEmbeddings, failed, fallbacks, err := generateEmbeddingsWithDedup(p.codeClient, codeChunks, p.cached, workers, embeddingBatchSize)
+		if err != nil {
+			return fmt.Errorf("failed to generate code embeddings: %w", err)
+		}
+		p.recordFailures(codeChunks, failed)
+		p.recordFallbacks(batch.chunks, codeIndices, fallbacks)
+		for i, embedding := range codeEmbeddings {
+			batch.embeddings[codeIndices[i]] = embedding
+		}
+	}
+
+	if len(docsChunks) > 0 {
+		docsEmbeddings, failed, fallbacks, err := generateEmbeddingsWithDedup(p.docsClient, docsChunks, p.cached, workers, embeddingBatchSize)
+		if err != nil {
+			return fmt.Errorf("failed to generate docs embeddings: %w", err)
+		}
+		p.recordFailures(docsChunks, failed)
+		p.recordFallbacks(batch.chunks, docsIndices, fallbacks)
+
+		// A text model's vectors may be shorter than the code model's; the
+		// store pads them with zeros to the width it holds
+		for i, embedding := range docsEmbeddings {
+			batch.embeddings[docsIndices[i]] = embedding
+		}
+	}
+	p.dropFailures(batch)
+	return nil
+}
+
+// recordFailures records the files of chunks that could not be embedded
+func (p *indexPipeline) recordFailures(chunks []chunker.Chunk, failed map[int]error) {
+	for i, err := range failed {
+		if p.failed == nil {
+			p.failed = make(map[string]error)
+		}
+		if _, ok := p.failed[chunks[i].FilePath]; !ok {
+			p.failed[chunks[i].FilePath] = err
+		}
+	}
+}
+
+// recordFallbacks records on the chunks at indices which fallback model
+// embedded them, by their position among those chunks, and clears the record
+// of chunks the configured model embedded
+func (p *indexPipeline) recordFallbacks(chunks []chunker.Chunk, indices []int, fallbacks map[int]string) {
+	for i, index := range indices {
+		chunk := &chunks[index]
+		model, ok := fallbacks[i]
+		if !ok {
+			delete(chunk.Metadata, "embedding_model")
+			continue
+		}
+		if chunk.Metadata == nil {
+			chunk.Metadata = make(map[string]string)
+		}
+		chunk.Metadata["embedding_model"] = model
+		if p.fellBack == nil {
+			p.fellBack = make(map[string]bool)
+		}
+		p.fellBack[chunk.FilePath] = true
+	}
+}
+
+// dropFailures removes the chunks and files of files that failed to embed
+// from a batch, so a file is stored whole or not at all
+func (p *indexPipeline) dropFailures(batch *indexBatch) {
+	if len(p.failed) == 0 {
+		return
+	}
+	var chunks []chunker.Chunk
+	var embeddings [][]float64
+	for i, chunk := range batch.chunks {
+		if _, ok := p.failed[chunk.FilePath]; !ok {
+			chunks = append(chunks, chunk)
+			embeddings = append(embeddings, batch.embeddings[i])
+		}
+	}
+	var files []scanner.FileInfo
+	for _, f := range batch.files {
+		if _, ok := p.failed[f.Path]; !ok {
+			files = append(files, f)
+		}
+	}
+	batch.chunks, batch.embeddings, batch.files = chunks, embeddings, files
+}
+
+// reportFailures prints the files that could not be embedded and returns an
+// error naming how many there were, or nil when every file was embedded
+func (p *indexPipeline) reportFailures() error {
+	if len(p.fellBack) > 0 {
+		fmt.Fprintf(os.Stderr, "\n%d file(s) were embedded by a fallback model; the next run embeds them again with the configured models\n", len(p.fellBack))
+	}
+	if len(p.failed) == 0 {
+		return nil
+	}
+	paths := make([]string, 0, len(p.failed))
+	for path := range p.failed {
+		paths = append(paths, path)
+	}
+	sort.Strings(paths)
+	fmt.Fprintf(os.Stderr, "\n%d file(s) could not be embedded and were left out of the index:\n", len(paths))
+	for _, path := range paths {
+		fmt.Fprintf(os.Stderr, "  %s: %v\n", path, p.failed[path])
+	}
+	return fmt.Errorf("%d file(s) could not be embedded; the next run tries them again", len(paths))
+}
@@ -0,0 +1,260 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/scanner"
+)
+
+// writeGoFiles writes n small Go files to dir and returns them in order
+func writeGoFiles(t *testing.T, dir string, n int) []scanner.FileInfo {
+	t.Helper()
+	files := make([]scanner.FileInfo, n)
+	for i := range files {
+		path := filepath.Join(dir, fmt.Sprintf("file%03d.go", i))
+		source := fmt.Sprintf("package demo\n\n// Func%d returns %d\nfunc Func%d() int {\n\treturn %d\n}\n", i, i, i, i)
+		if err := os.WriteFile(path, []byte(source), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		info, err := os.Stat(path)
+		if err != nil {
+			t.Fatal(err)
+		}
+		files[i] = scanner.FileInfo{Path: path, Language: "go", ModTime: info.ModTime(), Size: info.Size()}
+	}
+	return files
+}
+
+// newTestPipeline returns a pipeline with fake embedders whose store records
+// the batches it is given
+func newTestPipeline(t *testing.T, options chunker.Options, stored *[]indexBatch) *indexPipeline {
+	t.Helper()
+	c, err := chunker.NewSemanticWithOptions(options)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return &indexPipeline{
+		chunker:    c,
+		codeClient: &fakeEmbeddingClient{offset: 1},
+		docsClient: &fakeEmbeddingClient{offset: 1000},
+		batchFiles: 4,
+		store: func(batch indexBatch) error {
+			*stored = append(*stored, batch)
+			return nil
+		},
+	}
+}
+
+func TestPipelineStoresEveryBatch(t *testing.T) {
+	files := writeGoFiles(t, t.TempDir(), 10)
+	var stored []indexBatch
+	p := newTestPipeline(t, chunker.Options{}, &stored)
+
+	n, err := p.run(context.Background(), files)
+	if err != nil {
+		t.Fatalf("run returned %v, want nil", err)
+	}
+
+	chunks, recorded := 0, 0
+	for _, batch := range stored {
+		chunks += len(batch.chunks)
+		recorded += len(batch.files)
+		if len(batch.embeddings) != len(batch.chunks) {
+			t.Errorf("batch has %d embeddings for %d chunks", len(batch.embeddings), len(batch.chunks))
+		}
+	}
+	if recorded != len(files) {
+		t.Errorf("stored %d files, want %d", recorded, len(files))
+	}
+	if n != chunks || n == 0 {
+		t.Errorf("run reported %d chunks, stored %d", n, chunks)
+	}
+}
+
+// failingEmbeddingClient fails every request, as a provider that is down
+type failingEmbeddingClient struct{}
+
+func (failingEmbeddingClient) Embed(text string) ([]float64, error) {
+	return nil, errors.New("provider is down")
+}
+
+func (failingEmbeddingClient) EmbedMany(texts []string) ([][]float64, error) {
+	return nil, errors.New("provider is down")
+}
+
+// storedFiles returns the paths of the files batches recorded, in order
+func storedFiles(batches []indexBatch) []string {
+	var paths []string
+	for _, batch := range batches {
+		for _, f := range batch.files {
+			paths = append(paths, f.Path)
+		}
+	}
+	return paths
+}
+
+func TestPipelineKeepsFileOrder(t *testing.T) {
+	files := writeGoFiles(t, t.TempDir(), 25)
+	var stored []indexBatch
+	p := newTestPipeline(t, chunker.Options{}, &stored)
+	p.chunkWorkers = 8
+
+	if _, err := p.run(context.Background(), files); err != nil {
+		t.Fatal(err)
+	}
+
+	paths := storedFiles(stored)
+	if len(paths) != len(files) {
+		t.Fatalf("stored %d files, want %d", len(paths), len(files))
+	}
+	for i, f := range files {
+		if paths[i] != f.Path {
+			t.Fatalf("file %d stored is %s, want %s", i, paths[i], f.Path)
+		}
+	}
+	// A file's chunks follow those of the files before it
+	seen := make(map[string]bool)
+	last := ""
+	for _, batch := range stored {
+		for _, c := range batch.chunks {
+			if c.FilePath != last {
+				if seen[c.FilePath] {
+					t.Fatalf("chunks of %s are not together", c.FilePath)
+				}
+				seen[c.FilePath] = true
+				last = c.FilePath
+			}
+		}
+	}
+}
+
+func TestPipelineFlushesBeforeStreamedFile(t *testing.T) {
+	dir := t.TempDir()
+	files := writeGoFiles(t, dir, 6)
+
+	// A file over the stream threshold is streamed between the others
+	large := filepath.Join(dir, "large.go")
+	var source strings.Builder
+	source.WriteString("package demo\n")
+	for i := 0; i < 100; i++ {
+		fmt.Fprintf(&source, "\nfunc Large%d() int {\n\treturn %d\n}\n", i, i)
+	}
+	if err := os.WriteFile(large, []byte(source.String()), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	info, err := os.Stat(large)
+	if err != nil {
+		t.Fatal(err)
+	}
+	largeFile := scanner.FileInfo{Path: large, Language: "go", ModTime: info.ModTime(), Size: info.Size()}
+	files = append(files[:3], append([]scanner.FileInfo{largeFile}, files[3:]...)...)
+
+	var stored []indexBatch
+	p := newTestPipeline(t, chunker.Options{StreamThreshold: 512}, &stored)
+	if _, err := p.run(context.Background(), files); err != nil {
+		t.Fatal(err)
+	}
+
+	paths := storedFiles(stored)
+	if len(paths) != len(files) {
+		t.Fatalf("stored %d files, want %d", len(paths), len(files))
+	}
+	for i, f := range files {
+		if paths[i] != f.Path {
+			t.Fatalf("file %d stored is %s, want %s", i, paths[i], f.Path)
+		}
+	}
+	// The files before the streamed one are flushed first, so its batches
+	// hold only its own chunks
+	streamedChunks := 0
+	for _, batch := range stored {
+		if !containsChunksOf(batch, large) {
+			continue
+		}
+		for _, c := range batch.chunks {
+			if c.FilePath != large {
+				t.Fatalf("a batch of the streamed file holds chunks of %s", c.FilePath)
+			}
+		}
+		if len(batch.files) > 1 || len(batch.files) == 1 && batch.files[0].Path != large {
+			t.Fatalf("a batch of the streamed file lists %d other file(s)", len(batch.files))
+		}
+		streamedChunks += len(batch.chunks)
+	}
+	if streamedChunks == 0 {
+		t.Fatal("the large file was not streamed")
+	}
+}
+
+func containsChunksOf(batch indexBatch, path string) bool {
+	for _, c := range batch.chunks {
+		if c.FilePath == path {
+			return true
+		}
+	}
+	return false
+}
+
+func TestPipelineStopsWhenStoreFails(t *testing.T) {
+	files := writeGoFiles(t, t.TempDir(), 20)
+	var stored []indexBatch
+	p := newTestPipeline(t, chunker.Options{}, &stored)
+	storeErr := errors.New("disk full")
+	p.store = func(batch indexBatch) error {
+		if len(stored) == 1 {
+			return storeErr
+		}
+		stored = append(stored, batch)
+		return nil
+	}
+
+	_, err := p.run(context.Background(), files)
+	if !errors.Is(err, storeErr) {
+		t.Fatalf("run returned %v, want %v", err, storeErr)
+	}
+	if len(stored) != 1 {
+		t.Errorf("stored %d batches, want the 1 before the failure", len(stored))
+	}
+}
+
+func TestPipelineStopsWhenEmbeddingFails(t *testing.T) {
+	files := writeGoFiles(t, t.TempDir(), 10)
+	var stored []indexBatch
+	p := newTestPipeline(t, chunker.Options{}, &stored)
+	p.codeClient = failingEmbeddingClient{}
+
+	if _, err := p.run(context.Background(), files); err == nil {
+		t.Fatal("run returned nil, want the embedding error")
+	}
+	if len(stored) != 0 {
+		t.Errorf("stored %d batches, want none", len(stored))
+	}
+}
+
+func TestPipelineStopsWhenCancelled(t *testing.T) {
+	files := writeGoFiles(t, t.TempDir(), 20)
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	var stored []indexBatch
+	p := newTestPipeline(t, chunker.Options{}, &stored)
+	p.store = func(batch indexBatch) error {
+		stored = append(stored, batch)
+		cancel()
+		return nil
+	}
+
+	_, err := p.run(ctx, files)
+	if !errors.Is(err, context.Canceled) {
+		t.Fatalf("run returned %v, want %v", err, context.Canceled)
+	}
+	if got := len(storedFiles(stored)); got == len(files) {
+		t.Errorf("stored all %d files after being cancelled", got)
+	}
+}
@@ -0,0 +1,21 @@
+package cli
+
+import "fmt"
+
+// readOnlyFlag opens the index read-only with --read-only
+var readOnlyFlag bool
+
+// readOnly reports whether the index must not be changed, by --read-only or
+// read_only in the configuration
+func readOnly() bool {
+	return readOnlyFlag || (globalConfig != nil && globalConfig.ReadOnly)
+}
+
+// checkWritable fails when the index is read-only, naming what would have
+// changed it
+func checkWritable(action string) error {
+	if readOnly() {
+		return fmt.Errorf("cannot %s: the index is read-only (--read-only or read_only is set)", action)
+	}
+	return nil
+}
@@ -0,0 +1,62 @@
+package cli
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/jlanders/code-scout/internal/config"
+)
+
+func TestReadOnlyIndexRefusesChanges(t *testing.T) {
+	installFakeEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+	// A file the refused commands would have indexed
+	writeTestFile(t, workDir, "util.go", "package main\n\nfunc Sub(a, b int) int {\n\treturn a - b\n}\n")
+
+	modes := []struct {
+		name string
+		set  func()
+	}{
+		{"flag", func() { readOnlyFlag = true }},
+		{"config", func() { globalConfig = &config.Config{ReadOnly: true} }},
+	}
+	commands := []struct {
+		name   string
+		run    func() error
+		action string
+	}{
+		{"index", func() error { return indexCmd.RunE(indexCmd, nil) }, "update the index"},
+		{"compact", func() error { return indexCompactCmd.RunE(indexCompactCmd, nil) }, "compact the index"},
+		{"repair", func() error { return indexRepairCmd.RunE(indexRepairCmd, nil) }, "repair the index"},
+	}
+	for _, mode := range modes {
+		for _, command := range commands {
+			t.Run(mode.name+"/"+command.name, func(t *testing.T) {
+				prevFlag, prevConfig := readOnlyFlag, globalConfig
+				t.Cleanup(func() { readOnlyFlag, globalConfig = prevFlag, prevConfig })
+				mode.set()
+
+				var err error
+				runInDir(t, workDir, func() error {
+					err = command.run()
+					return nil
+				})
+				if err == nil {
+					t.Fatal("expected the read-only index to be refused")
+				}
+				if !strings.Contains(err.Error(), "cannot "+command.action) || !strings.Contains(err.Error(), "read-only") {
+					t.Errorf("error = %q, want it to say it cannot %s because the index is read-only", err, command.action)
+				}
+			})
+		}
+	}
+
+	results := runSearchJSON(t, workDir, "sub", modeCode)
+	if containsFile(results.Results, "util.go", "code") {
+		t.Errorf("search found %+v, want util.go left out of the read-only index", results.Results)
+	}
+}
@@ -0,0 +1,50 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// reembedBatch is the number of chunks re-embedded and stored at a time
+const reembedBatch = 500
+
+// reembedIndex embeds the chunks of an index made with other models again with
+// the configured ones, and returns the index's metadata afterwards. The chunks
+// are not chunked again, and search keeps using the old vectors, with the old
+// models, until the new ones are swapped in.
+func reembedIndex(store *storage.LanceDBStore, metadata *storage.IndexMetadata, models storage.EmbeddingModels) (*storage.IndexMetadata, error) {
+	fmt.Printf("Re-embedding the index with %s/%s (was %s/%s); search keeps using the old vectors until it is done\n",
+		models.CodeModel, models.TextModel, metadata.Models.CodeModel, metadata.Models.TextModel)
+
+	p := &indexPipeline{codeClient: newCodeEmbeddingClient(), docsClient: newDocsEmbeddingClient()}
+	updated := *metadata
+	updated.Models = &models
+	// The new models' vectors replace the old ones, whatever they are like
+	vectors, err := measureVectors()
+	if err != nil {
+		return nil, fmt.Errorf("failed to re-embed the index: %w", err)
+	}
+	updated.Vectors = vectors
+	done := 0
+	err = store.Reembed(&updated, reembedBatch, func(chunks []chunker.Chunk) ([][]float64, error) {
+		batch := indexBatch{chunks: chunks}
+		if err := p.embedBatch(&batch); err != nil {
+			return nil, err
+		}
+		// Files are not chunked again, so the chunks of a file that failed
+		// would be lost from the index; it keeps the old vectors instead, and
+		// the next run starts the re-embedding over
+		if len(p.failed) > 0 {
+			return nil, p.reportFailures()
+		}
+		done += len(chunks)
+		fmt.Printf("Re-embedded %d chunks\n", done)
+		return batch.embeddings, nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to re-embed the index: %w", err)
+	}
+	return &updated, nil
+}
@@ -0,0 +1,88 @@
+package cli
+
+import (
+	"errors"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// rejectingClient embeds like fakeEmbeddingClient but fails every request
+// holding a text that contains marker, as a provider that rejects one file
+type rejectingClient struct {
+	fake   fakeEmbeddingClient
+	marker string
+}
+
+func (c *rejectingClient) Embed(text string) ([]float64, error) {
+	vectors, err := c.EmbedMany([]string{text})
+	if err != nil {
+		return nil, err
+	}
+	return vectors[0], nil
+}
+
+func (c *rejectingClient) EmbedMany(texts []string) ([][]float64, error) {
+	for _, text := range texts {
+		if strings.Contains(text, c.marker) {
+			return nil, errors.New("text rejected")
+		}
+	}
+	return c.fake.EmbedMany(texts)
+}
+
+func TestReembedKeepsOldVectorsWhenAFileFails(t *testing.T) {
+	installFakeEmbeddings(t)
+	newCodeEmbeddingClient = func() embeddings.Client {
+		return &rejectingClient{fake: fakeEmbeddingClient{offset: 1}, marker: "Broken"}
+	}
+
+	store, err := storage.NewLanceDBStore(t.TempDir())
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	chunks := []chunker.Chunk{
+		{ID: "good", FilePath: "good.go", LineStart: 1, LineEnd: 1, Language: "go", Code: "func Good() {}", Metadata: map[string]string{}, EmbeddingType: "code"},
+		{ID: "broken", FilePath: "broken.go", LineStart: 1, LineEnd: 1, Language: "go", Code: "func Broken() {}", Metadata: map[string]string{}, EmbeddingType: "code"},
+	}
+	old := [][]float64{fakeVector("old", 50), fakeVector("old", 50)}
+	if err := store.StoreChunks(chunks, old); err != nil {
+		t.Fatal(err)
+	}
+	metadata := storage.NewIndexMetadata()
+	metadata.LastIndexTime = time.Now()
+	metadata.Models = &storage.EmbeddingModels{CodeModel: "old-code", TextModel: "old-text", Dimension: storage.VectorDimension}
+	if err := store.SaveMetadata(metadata); err != nil {
+		t.Fatal(err)
+	}
+
+	if _, err := reembedIndex(store, metadata, embeddingModels()); err == nil {
+		t.Fatal("re-embedding returned nil, want the failure of broken.go")
+	}
+
+	// Both chunks are still there, with the vectors they had
+	stored, err := store.ReadChunks()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(stored) != len(chunks) {
+		t.Fatalf("the index holds %d chunks, want %d", len(stored), len(chunks))
+	}
+	for _, c := range stored {
+		if len(c.Vector) == 0 || c.Vector[0] != old[0][0] {
+			t.Errorf("chunk %s was re-embedded", c.Chunk.ID)
+		}
+	}
+	after, err := store.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if after.Models.CodeModel != "old-code" {
+		t.Errorf("the index records code model %s, want old-code", after.Models.CodeModel)
+	}
+}
@@ -0,0 +1,449 @@
+package cli
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/objectstore"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+// remoteTokenEnv names the environment variable holding the bearer token sent
+// to an HTTP index remote
+const remoteTokenEnv = "CODE_SCOUT_REMOTE_TOKEN"
+
+var remoteFlag string
+
+var indexPushCmd = &cobra.Command{
+	Use:   "push",
+	Short: "Upload the index so others can pull it",
+	Long: `Upload the index of the current directory to the index remote, so that a CI job
+can build the index once and developers can download it with 'code-scout index
+pull' instead of embedding the repository themselves.
+
+The remote is an http(s) URL the index archive is PUT to, an s3:// or gs://
+object URL, or a file path such as a shared drive. Set it with --remote or
+index_remote in the configuration. The bearer token in CODE_SCOUT_REMOTE_TOKEN,
+if set, is sent to an HTTP remote.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		remote, err := indexRemote()
+		if err != nil {
+			return err
+		}
+		files, err := pushIndex(cwd, remote)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("Pushed the index of %d file(s) to %s\n", files, remote)
+		return nil
+	},
+}
+
+var indexPullCmd = &cobra.Command{
+	Use:   "pull",
+	Short: "Download an index pushed with index push",
+	Long: `Download the index from the index remote and replace the index of the current
+directory with it. File paths are moved from the directory the index was built
+in to the current one. Run 'code-scout index' afterwards to pick up files that
+changed since the index was pushed; only those are embedded again.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		remote, err := indexRemote()
+		if err != nil {
+			return err
+		}
+		if err := pullIndex(cwd, remote); err != nil {
+			return err
+		}
+		fmt.Printf("Pulled the index from %s\n", remote)
+		return nil
+	},
+}
+
+// indexRemote returns where index push and pull share the index
+func indexRemote() (string, error) {
+	if remoteFlag != "" {
+		return remoteFlag, nil
+	}
+	if globalConfig != nil && globalConfig.IndexRemote != "" {
+		return globalConfig.IndexRemote, nil
+	}
+	return "", fmt.Errorf("no index remote configured; set --remote or index_remote")
+}
+
+// isHTTPRemote reports whether a remote is a URL rather than a file path
+func isHTTPRemote(remote string) bool {
+	return strings.HasPrefix(remote, "http://") || strings.HasPrefix(remote, "https://")
+}
+
+// pushIndex archives the index of the workspace at cwd to remote and returns
+// the number of files it covers
+func pushIndex(cwd, remote string) (int, error) {
+	dir, err := indexDir(cwd)
+	if err != nil {
+		return 0, err
+	}
+	store, err := openStore(dir)
+	if err != nil {
+		return 0, fmt.Errorf("failed to open database: %w", err)
+	}
+	metadata, err := store.LoadMetadata()
+	store.Close()
+	if err != nil {
+		return 0, fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if metadata.IsEmpty() {
+		return 0, fmt.Errorf("nothing is indexed yet; run 'code-scout index' first")
+	}
+
+	archive, err := os.CreateTemp("", "code-scout-index-*.tar.gz")
+	if err != nil {
+		return 0, fmt.Errorf("failed to create archive: %w", err)
+	}
+	defer os.Remove(archive.Name())
+	defer archive.Close()
+	if err := writeIndexArchive(archive, filepath.Join(dir, storage.DefaultDBDir)); err != nil {
+		return 0, err
+	}
+	if err := uploadArchive(remote, archive); err != nil {
+		return 0, err
+	}
+	return len(metadata.FileModTimes), nil
+}
+
+// pullIndex replaces the index of the workspace at cwd with the archive at
+// remote
+func pullIndex(cwd, remote string) error {
+	if err := checkWritable("replace the index"); err != nil {
+		return err
+	}
+	dir, err := indexDir(cwd)
+	if err != nil {
+		return err
+	}
+	store, err := openStore(dir)
+	if err != nil {
+		return fmt.Errorf("failed to open database: %w", err)
+	}
+	lock, err := lockIndex(context.Background(), store)
+	store.Close()
+	if err != nil {
+		return err
+	}
+	defer lock.Unlock()
+
+	// Unpack beside the current index, so a failed pull leaves it intact and
+	// the finished one replaces it with a rename
+	staging, err := os.MkdirTemp(dir, storage.DefaultDBDir+"-pull-")
+	if err != nil {
+		return fmt.Errorf("failed to create staging directory: %w", err)
+	}
+	defer os.RemoveAll(staging)
+
+	archive, err := downloadArchive(remote)
+	if err != nil {
+		return err
+	}
+	err = readIndexArchive(archive, filepath.Join(staging, storage.DefaultDBDir))
+	archive.Close()
+	if err != nil {
+		return err
+	}
+
+	reason, err := relocateUnpackedIndex(staging, cwd)
+	if err != nil {
+		return err
+	}
+	if reason != "" {
+		return fmt.Errorf("the index in %s cannot be used: %s", remote, reason)
+	}
+
+	if err := replaceIndexDir(filepath.Join(staging, storage.DefaultDBDir), filepath.Join(dir, storage.DefaultDBDir)); err != nil {
+		return fmt.Errorf("failed to install the index: %w", err)
+	}
+	return uploadIndex(cwd)
+}
+
+// replaceIndexDir installs an unpacked index directory in place of dbDir. The
+// lock file moves into the new directory, so a process holding the lock keeps
+// holding it.
+func replaceIndexDir(unpacked, dbDir string) error {
+	lockFile := filepath.Join(dbDir, storage.LockFileName)
+	if err := os.Rename(lockFile, filepath.Join(unpacked, storage.LockFileName)); err != nil && !os.IsNotExist(err) {
+		return err
+	}
+	if err := os.RemoveAll(dbDir); err != nil {
+		return err
+	}
+	return os.Rename(unpacked, dbDir)
+}
+
+// relocateUnpackedIndex moves the file paths of the index unpacked under
+// staging to cwd, or explains why the index cannot be used here
+func relocateUnpackedIndex(staging, cwd string) (string, error) {
+	store, err := openStore(staging)
+	if err != nil {
+		return "", fmt.Errorf("failed to open the unpacked index: %w", err)
+	}
+	defer store.Close()
+
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		return "", fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if metadata.IsEmpty() {
+		return "the index is empty", nil
+	}
+	if reason := metadata.Incompatibility(embeddingModels()); reason != "" {
+		return reason, nil
+	}
+	if reason, _ := keyMismatch(metadata, store); reason != "" {
+		return reason, nil
+	}
+	if metadata.ConfigFingerprint != "" && metadata.ConfigFingerprint != configFingerprint() {
+		fmt.Fprintln(os.Stderr, "Warning: the index was built with different chunking settings than configured here; "+
+			"files reindexed from now on use the local settings")
+	}
+	// Relocating rewrites every chunk, so it runs in a generation that only
+	// replaces the unpacked chunks once all of them are moved
+	gen, err := store.BeginGeneration()
+	if err != nil {
+		return "", err
+	}
+	defer gen.Close()
+	if err := gen.Relocate(metadata, cwd); err != nil {
+		return "", fmt.Errorf("failed to move the index: %w", err)
+	}
+	if err := store.CommitGeneration(gen, metadata); err != nil {
+		return "", fmt.Errorf("failed to move the index: %w", err)
+	}
+	return "", nil
+}
+
+// writeIndexArchive writes the files of the index directory as a gzipped tar
+// archive
+func writeIndexArchive(w io.Writer, dbDir string) error {
+	gz := gzip.NewWriter(w)
+	tw := tar.NewWriter(gz)
+	err := filepath.Walk(dbDir, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(dbDir, path)
+		if err != nil || rel == "." {
+			return err
+		}
+		// The lock belongs to the process changing this copy of the index, a
+		// generation being written is not part of it yet, and an overlay
+		// covers only this checkout's diff
+		if rel == storage.LockFileName {
+			return nil
+		}
+		if (rel == storage.GenerationDirName || rel == overlayDirName) && info.IsDir() {
+			return filepath.SkipDir
+		}
+		header, err := tar.FileInfoHeader(info, "")
+		if err != nil {
+			return err
+		}
+		header.Name = filepath.ToSlash(rel)
+		if err := tw.WriteHeader(header); err != nil {
+			return err
+		}
+		if !info.Mode().IsRegular() {
+			return nil
+		}
+		f, err := os.Open(path)
+		if err != nil {
+			return err
+		}
+		defer f.Close()
+		_, err = io.Copy(tw, f)
+		return err
+	})
+	if err != nil {
+		return fmt.Errorf("failed to archive the index: %w", err)
+	}
+	if err := tw.Close(); err != nil {
+		return fmt.Errorf("failed to archive the index: %w", err)
+	}
+	if err := gz.Close(); err != nil {
+		return fmt.Errorf("failed to archive the index: %w", err)
+	}
+	return nil
+}
+
+// readIndexArchive unpacks an archive written by writeIndexArchive into
+// dbDir, refusing entries that would land outside it
+func readIndexArchive(r io.Reader, dbDir string) error {
+	gz, err := gzip.NewReader(r)
+	if err != nil {
+		return fmt.Errorf("failed to read the index archive: %w", err)
+	}
+	defer gz.Close()
+
+	tr := tar.NewReader(gz)
+	for {
+		header, err := tr.Next()
+		if err == io.EOF {
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("failed to read the index archive: %w", err)
+		}
+		target := filepath.Join(dbDir, filepath.FromSlash(header.Name))
+		if !strings.HasPrefix(target, filepath.Clean(dbDir)+string(filepath.Separator)) {
+			return fmt.Errorf("the index archive contains an invalid path: %s", header.Name)
+		}
+
+		switch header.Typeflag {
+		case tar.TypeDir:
+			if err := os.MkdirAll(target, 0755); err != nil {
+				return fmt.Errorf("failed to unpack the index: %w", err)
+			}
+		case tar.TypeReg:
+			if err := os.MkdirAll(filepath.Dir(target), 0755); err != nil {
+				return fmt.Errorf("failed to unpack the index: %w", err)
+			}
+			f, err := os.OpenFile(target, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, 0644)
+			if err != nil {
+				return fmt.Errorf("failed to unpack the index: %w", err)
+			}
+			_, err = io.Copy(f, tr)
+			f.Close()
+			if err != nil {
+				return fmt.Errorf("failed to unpack the index: %w", err)
+			}
+		}
+	}
+}
+
+// uploadArchive copies the archive to the remote
+func uploadArchive(remote string, archive *os.File) error {
+	info, err := archive.Stat()
+	if err != nil {
+		return fmt.Errorf("failed to read archive: %w", err)
+	}
+	if _, err := archive.Seek(0, io.SeekStart); err != nil {
+		return fmt.Errorf("failed to read archive: %w", err)
+	}
+
+	if objectstore.IsURL(remote) {
+		loc, err := objectstore.ParseURL(remote)
+		if err != nil {
+			return err
+		}
+		client, err := objectstore.NewClient(loc)
+		if err != nil {
+			return err
+		}
+		_, err = client.Put(context.Background(), loc.Key, archive)
+		return err
+	}
+	if !isHTTPRemote(remote) {
+		if err := os.MkdirAll(filepath.Dir(remote), 0755); err != nil {
+			return fmt.Errorf("failed to create %s: %w", filepath.Dir(remote), err)
+		}
+		// Write beside the destination and rename, so a reader never sees
+		// half an archive
+		tmp := remote + ".tmp"
+		f, err := os.Create(tmp)
+		if err != nil {
+			return fmt.Errorf("failed to write %s: %w", remote, err)
+		}
+		_, err = io.Copy(f, archive)
+		if closeErr := f.Close(); err == nil {
+			err = closeErr
+		}
+		if err != nil {
+			os.Remove(tmp)
+			return fmt.Errorf("failed to write %s: %w", remote, err)
+		}
+		return os.Rename(tmp, remote)
+	}
+
+	req, err := http.NewRequest(http.MethodPut, remote, archive)
+	if err != nil {
+		return fmt.Errorf("failed to create request: %w", err)
+	}
+	req.ContentLength = info.Size()
+	req.Header.Set("Content-Type", "application/gzip")
+	if token := os.Getenv(remoteTokenEnv); token != "" {
+		req.Header.Set("Authorization", "Bearer "+token)
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return fmt.Errorf("failed to upload the index: %w", err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 1024))
+		return fmt.Errorf("failed to upload the index: %s: %s", resp.Status, strings.TrimSpace(string(body)))
+	}
+	return nil
+}
+
+// downloadArchive opens the archive at the remote
+func downloadArchive(remote string) (io.ReadCloser, error) {
+	if objectstore.IsURL(remote) {
+		loc, err := objectstore.ParseURL(remote)
+		if err != nil {
+			return nil, err
+		}
+		client, err := objectstore.NewClient(loc)
+		if err != nil {
+			return nil, err
+		}
+		body, _, err := client.Get(context.Background(), loc.Key, "")
+		return body, err
+	}
+	if !isHTTPRemote(remote) {
+		f, err := os.Open(remote)
+		if err != nil {
+			return nil, fmt.Errorf("failed to read %s: %w", remote, err)
+		}
+		return f, nil
+	}
+
+	req, err := http.NewRequest(http.MethodGet, remote, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	if token := os.Getenv(remoteTokenEnv); token != "" {
+		req.Header.Set("Authorization", "Bearer "+token)
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to download the index: %w", err)
+	}
+	if resp.StatusCode != http.StatusOK {
+		resp.Body.Close()
+		return nil, fmt.Errorf("failed to download the index: %s", resp.Status)
+	}
+	return resp.Body, nil
+}
+
+func init() {
+	indexCmd.AddCommand(indexPushCmd)
+	indexCmd.AddCommand(indexPullCmd)
+	for _, cmd := range []*cobra.Command{indexPushCmd, indexPullCmd} {
+		cmd.Flags().StringVar(&remoteFlag, "remote", "", "URL or path of the index remote (overrides index_remote)")
+	}
+}
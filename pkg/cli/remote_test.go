@@ -0,0 +1,63 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestPushAndPullMoveTheIndex(t *testing.T) {
+	installFakeEmbeddings(t)
+	builtDir, pulledDir := t.TempDir(), t.TempDir()
+	for _, dir := range []string{builtDir, pulledDir} {
+		writeTestFile(t, dir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	}
+	remote := filepath.Join(t.TempDir(), "remote", "index.tar.gz")
+
+	runInDir(t, builtDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+	files, err := pushIndex(builtDir, remote)
+	if err != nil {
+		t.Fatalf("pushIndex() error = %v", err)
+	}
+	if files != 1 {
+		t.Errorf("pushed the index of %d files, want 1", files)
+	}
+	if err := pullIndex(pulledDir, remote); err != nil {
+		t.Fatalf("pullIndex() error = %v", err)
+	}
+
+	code := runSearchJSON(t, pulledDir, "add", modeCode)
+	if !containsFile(code.Results, "main.go", "code") {
+		t.Fatalf("search after the pull found %+v, want main.go", code.Results)
+	}
+	for _, result := range code.Results {
+		if !strings.HasPrefix(result.FilePath, pulledDir) {
+			t.Errorf("result in %s, want it moved to %s", result.FilePath, pulledDir)
+		}
+	}
+}
+
+func TestFailedPullKeepsTheIndex(t *testing.T) {
+	installFakeEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+
+	remote := filepath.Join(t.TempDir(), "index.tar.gz")
+	if err := os.WriteFile(remote, []byte("not an archive"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := pullIndex(workDir, remote); err == nil {
+		t.Fatal("pullIndex() of a corrupt archive succeeded")
+	}
+
+	code := runSearchJSON(t, workDir, "add", modeCode)
+	if !containsFile(code.Results, "main.go", "code") {
+		t.Fatalf("search after the failed pull found %+v, want the index kept", code.Results)
+	}
+}
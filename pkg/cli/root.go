@@ -0,0 +1,72 @@
+// Package cli is the code-scout command. A binary of another module can run
+// it with Execute after registering embedding providers of its own with
+// pkg/embeddings, or add commands to RootCommand.
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/spf13/cobra"
+)
+
+var rootCmd = &cobra.Command{
+	Use:   "code-scout",
+	Short: "Code Scout - Semantic code search with dual-model embeddings",
+	Long: `Code Scout is a CLI tool for semantic code search using dual-model embeddings.
+It provides AI coding agents with deep codebase understanding by embedding both
+code and documentation into a local vector database.`,
+	PersistentPreRunE: func(cmd *cobra.Command, args []string) error {
+		// Load configuration from file, or the named index's configuration
+		var cfg *config.Config
+		var err error
+		if indexName != "" {
+			cfg, err = config.LoadNamed(indexName)
+		} else {
+			cfg, err = config.Load()
+		}
+		if err != nil {
+			return fmt.Errorf("failed to load config: %w", err)
+		}
+
+		// Check for endpoint flag override
+		endpoint, _ := cmd.Flags().GetString("endpoint")
+		if endpoint != "" {
+			cfg.Endpoint = endpoint
+		}
+		cfg.ApplyEnv()
+
+		// Validate configuration
+		if err := cfg.Validate(); err != nil {
+			return fmt.Errorf("invalid configuration: %w", err)
+		}
+
+		globalConfig = cfg
+		embeddings.SetRetryPolicy(retryPolicy(cfg.Retry))
+		return nil
+	},
+}
+
+func init() {
+	// Add global flags
+	rootCmd.PersistentFlags().String("endpoint", "", "Embedding API endpoint (overrides config file)")
+	rootCmd.PersistentFlags().StringVar(&indexName, "index", "", "Use this named index, with its own config and storage, instead of the current directory's")
+	rootCmd.PersistentFlags().BoolVar(&readOnlyFlag, "read-only", false, "Open the index read-only and fail instead of changing it (overrides config file)")
+}
+
+// RootCommand returns the code-scout command, to which a binary can add
+// commands of its own before calling Execute
+func RootCommand() *cobra.Command {
+	return rootCmd
+}
+
+// Execute runs the code-scout command with the process's arguments and exits
+// the process with status 1 when the command fails
+func Execute() {
+	if err := rootCmd.Execute(); err != nil {
+		fmt.Fprintln(os.Stderr, err)
+		os.Exit(1)
+	}
+}
@@ -0,0 +1,51 @@
+package cli
+
+import (
+	"github.com/jlanders/code-scout/internal/scanner"
+	"github.com/spf13/cobra"
+)
+
+// scannerOptions builds the scanner options from the configuration and the
+// command's flags, which replace the configured globs when given. The
+// .code-scout.toml files of the directories scanned are loaded into dirs.
+func scannerOptions(cmd *cobra.Command, dirs dirConfigs) scanner.Options {
+	options := scanner.Options{NoIgnore: noIgnore, Subtree: dirs.subtree}
+	if globalConfig != nil {
+		options.Include = globalConfig.Include
+		options.Exclude = globalConfig.Exclude
+		options.Languages = globalConfig.Languages
+		options.ExcludeLanguages = globalConfig.ExcludeLanguages
+		options.Submodules = globalConfig.Submodules
+		options.Symlinks = globalConfig.Symlinks
+	}
+	if cmd.Flags().Changed("include") {
+		options.Include = includeGlobs
+	}
+	if cmd.Flags().Changed("exclude") {
+		options.Exclude = excludeGlobs
+	}
+	if cmd.Flags().Changed("languages") {
+		options.Languages = languageNames
+	}
+	if cmd.Flags().Changed("exclude-languages") {
+		options.ExcludeLanguages = excludeLanguages
+	}
+	if cmd.Flags().Changed("submodules") {
+		options.Submodules = submodulesMode
+	}
+	if cmd.Flags().Changed("symlinks") {
+		options.Symlinks = symlinksPolicy
+	}
+	return options
+}
+
+// addScanFlags registers the flags that choose which files a command indexes
+func addScanFlags(cmd *cobra.Command) {
+	cmd.Flags().BoolVar(&noIgnore, "no-ignore", false, "Also index files excluded by .gitignore, .ignore, and global git excludes")
+	cmd.Flags().StringSliceVar(&includeGlobs, "include", nil, "Only index files matching these globs, e.g. 'src/**' (overrides config file)")
+	cmd.Flags().StringSliceVar(&excludeGlobs, "exclude", nil, "Skip files and directories matching these globs, e.g. 'vendor/**' (overrides config file)")
+	cmd.Flags().StringSliceVar(&languageNames, "languages", nil, "Only index files in these languages, e.g. 'rust,python' (overrides config file)")
+	cmd.Flags().StringSliceVar(&excludeLanguages, "exclude-languages", nil, "Skip files in these languages (overrides config file)")
+	cmd.Flags().StringVar(&submodulesMode, "submodules", scanner.SubmodulesInclude, "How to index git submodules: include, exclude, or shallow (documentation only) (overrides config file)")
+	cmd.Flags().StringVar(&symlinksPolicy, "symlinks", scanner.SymlinksWithinRoot, "Which symbolic links to follow: within-root, follow, or skip (overrides config file)")
+}
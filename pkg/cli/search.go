@@ -0,0 +1,624 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+var (
+	jsonOutput  bool
+	limitFlag   int
+	codeMode    bool
+	docsMode    bool
+	hybridMode  bool
+	symbolFlag  string
+	testsFlag   bool
+	noTests     bool
+	noGenerated bool
+	repoFlag    string
+)
+
+type searchMode string
+
+const (
+	modeCode   searchMode = "code"
+	modeDocs   searchMode = "docs"
+	modeHybrid searchMode = "hybrid"
+)
+
+var searchCmd = &cobra.Command{
+	Use:   "search [query]",
+	Short: "Search the codebase semantically",
+	Long: `Search the indexed codebase using semantic similarity.
+Returns relevant code chunks with file paths, line numbers, and relevance scores.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		query := args[0]
+
+		mode, err := resolveSearchMode()
+		if err != nil {
+			return err
+		}
+
+		// A SQLite index is searched as it is, without the workspace's
+		// overlay or embedding spaces
+		var indexes []searchIndex
+		if sqliteFile != "" {
+			store, err := openSQLiteSearch(sqliteFile)
+			if err != nil {
+				return err
+			}
+			defer store.Close()
+			indexes = []searchIndex{{store: store}}
+		} else {
+			// Get current working directory
+			cwd, err := workingDir()
+			if err != nil {
+				return fmt.Errorf("failed to get current directory: %w", err)
+			}
+
+			if maxStaleness > 0 {
+				if err := refreshIfStale(cmd, cwd); err != nil {
+					return err
+				}
+			}
+
+			// Open existing LanceDB store
+			dir, err := indexStoreDir(cwd)
+			if err != nil {
+				return fmt.Errorf("failed to open database: %w", err)
+			}
+			store, err := openStore(dir)
+			if err != nil {
+				return fmt.Errorf("failed to open database: %w", err)
+			}
+			defer store.Close()
+
+			// Results from an index of another format or model would be meaningless
+			metadata, err := store.LoadMetadata()
+			if err != nil {
+				return fmt.Errorf("failed to load metadata: %w", err)
+			}
+			models := embeddingModels()
+			if metadata.ModelsChanged(models) {
+				// Until the index is re-embedded, queries are embedded with the
+				// models its vectors came from
+				fmt.Fprintf(os.Stderr, "Warning: the index was embedded with %s/%s; searching with them until 'code-scout index' re-embeds it with %s/%s\n",
+					metadata.Models.CodeModel, metadata.Models.TextModel, models.CodeModel, models.TextModel)
+				useModels(*metadata.Models)
+			} else if reason := metadata.Incompatibility(models); reason != "" {
+				return fmt.Errorf("%s; run 'code-scout index' to rebuild it", reason)
+			}
+			if reason, rebuildable := keyMismatch(metadata, store); reason != "" {
+				if rebuildable {
+					return fmt.Errorf("%s; run 'code-scout index' to encrypt it", reason)
+				}
+				return fmt.Errorf("%s", reason)
+			}
+
+			warnIfStale(metadata, cwd)
+			searchedMetadata, searchedDir = metadata, dir
+			// An encrypted index's names cannot be filtered on where they are
+			// stored, so --symbol is applied to the results instead
+			symbolAfterSearch = metadata.KeyFingerprint != ""
+
+			// Load existing table
+			if err := store.OpenTable(); err != nil {
+				return fmt.Errorf("failed to open table: %w (have you run 'code-scout index' first?)", err)
+			}
+			indexes = []searchIndex{{store: store}}
+
+			// An overlay index replaces the base index's chunks of the files a
+			// diff touched
+			if !noOverlay {
+				overlay, overlayMetadata, err := openOverlay(dir)
+				if err != nil {
+					return err
+				}
+				if overlay != nil {
+					defer overlay.Close()
+					if reason := overlayMetadata.Incompatibility(embeddingModels()); reason != "" {
+						fmt.Fprintf(os.Stderr, "Warning: ignoring the overlay index: %s; run 'code-scout index --overlay' again\n", reason)
+					} else {
+						indexes[0].shadowed = overlayMetadata.Shadowed
+						// An overlay of a diff that only deleted files has no chunks
+						if len(overlayMetadata.FileModTimes) > 0 {
+							if err := overlay.OpenTable(); err != nil {
+								return fmt.Errorf("failed to open the overlay index's table: %w", err)
+							}
+							indexes = append(indexes, searchIndex{store: overlay})
+						}
+					}
+				}
+			}
+		}
+
+		var (
+			results      []SearchResult
+			totalMatches int
+		)
+
+		limit := limitFlag
+		if symbolAfterSearch && symbolFlag != "" {
+			limit = max(limitFlag, 10) * symbolCandidates
+		}
+		switch {
+		case spaceFlag != "":
+			results, totalMatches, err = searchSpaces(indexes, query, limit, mode)
+		case mode == modeHybrid:
+			results, totalMatches, err = runHybridSearch(indexes, query, limit)
+		default:
+			results, totalMatches, err = runSingleModeSearch(indexes, query, limit, mode)
+		}
+		if err == nil && spaceFlag == "" && mode != modeDocs && dualEmbedding() {
+			results, totalMatches, err = fuseSummaries(indexes, query, limit, results, totalMatches)
+		}
+		if err != nil {
+			return err
+		}
+		if symbolAfterSearch && symbolFlag != "" {
+			results = matchingSymbol(results, symbolFlag)
+		}
+
+		if len(results) > limitFlag && limitFlag > 0 {
+			results = results[:limitFlag]
+		}
+
+		// Format output
+		output := map[string]interface{}{
+			"query":         query,
+			"mode":          string(mode),
+			"total_results": totalMatches,
+			"returned":      len(results),
+			"results":       results,
+		}
+		if spaceFlag != "" {
+			output["spaces"] = strings.Split(spaceFlag, ",")
+		}
+
+		if jsonOutput {
+			jsonBytes, err := json.MarshalIndent(output, "", "  ")
+			if err != nil {
+				return fmt.Errorf("failed to marshal JSON: %w", err)
+			}
+			fmt.Println(string(jsonBytes))
+		} else {
+			fmt.Printf("Found %d unique %s results (from %d total) for: %s\n\n",
+				len(results), string(mode), totalMatches, query)
+			for i, result := range results {
+				fmt.Printf("%d. %s:%d-%d (score: %.4f)\n",
+					i+1, result.FilePath, result.LineStart, result.LineEnd, result.Score)
+				fmt.Printf("   Language: %s | Source: %s", result.Language, result.EmbeddingType)
+				if result.Repo != "" {
+					fmt.Printf(" | Repo: %s", result.Repo)
+				}
+				if result.Submodule != "" {
+					fmt.Printf(" | Submodule: %s", result.Submodule)
+				}
+				if result.ChunkType != "" {
+					fmt.Printf(" | Chunk: %s", result.ChunkType)
+				}
+				if result.IsTest {
+					fmt.Print(" | Test")
+				}
+				if result.IsGenerated {
+					fmt.Print(" | Generated")
+				}
+				fmt.Println()
+				if result.Heading != "" {
+					fmt.Printf("   Heading: %s", result.Heading)
+					if result.HeadingLevel != "" {
+						fmt.Printf(" (level %s)", result.HeadingLevel)
+					}
+					if result.ParentHeading != "" {
+						fmt.Printf(" | Parents: %s", result.ParentHeading)
+					}
+					fmt.Println()
+				}
+				symbol := result.QualifiedName
+				if symbol == "" {
+					symbol = result.Name
+				}
+				if symbol != "" {
+					fmt.Printf("   Symbol: %s", symbol)
+					if result.Kind != "" {
+						fmt.Printf(" (%s)", result.Kind)
+					}
+					if result.Visibility != "" {
+						fmt.Printf(" | %s", result.Visibility)
+					}
+					fmt.Println()
+				}
+				if result.Signature != "" {
+					fmt.Printf("   Signature: %s\n", result.Signature)
+				}
+				if result.DocSummary != "" {
+					fmt.Printf("   Summary: %s\n", result.DocSummary)
+				}
+				if result.Attributes != "" {
+					fmt.Printf("   Attributes: %s\n", result.Attributes)
+				}
+				if len(result.Duplicates) > 0 {
+					fmt.Printf("   Also at: %s\n", strings.Join(result.Duplicates, ", "))
+				}
+				// Show first 100 chars of code
+				code := result.Code
+				if len(code) > 100 {
+					code = code[:100] + "..."
+				}
+				fmt.Printf("   %s\n\n", code)
+			}
+		}
+
+		return nil
+	},
+}
+
+type SearchResult struct {
+	ChunkID       string  `json:"chunk_id"`
+	FilePath      string  `json:"file_path"`
+	LineStart     int     `json:"line_start"`
+	LineEnd       int     `json:"line_end"`
+	Language      string  `json:"language"`
+	Code          string  `json:"code"`
+	Score         float64 `json:"score"`
+	EmbeddingType string  `json:"embedding_type"`
+	ChunkType     string  `json:"chunk_type,omitempty"`
+	Heading       string  `json:"heading,omitempty"`
+	HeadingLevel  string  `json:"heading_level,omitempty"`
+	ParentHeading string  `json:"parent_heading,omitempty"`
+	DocSummary    string  `json:"doc_summary,omitempty"`
+	DocComment    string  `json:"doc_comment,omitempty"`
+	Attributes    string  `json:"attributes,omitempty"`
+	Name          string  `json:"name,omitempty"`
+	QualifiedName string  `json:"qualified_name,omitempty"`
+	Kind          string  `json:"kind,omitempty"`
+	Visibility    string  `json:"visibility,omitempty"`
+	Signature     string  `json:"signature,omitempty"`
+	Receiver      string  `json:"receiver,omitempty"`
+	Calls         string  `json:"calls,omitempty"`
+	Repo          string  `json:"repo,omitempty"`
+	Submodule     string  `json:"submodule,omitempty"`
+	Fallback      string  `json:"fallback_model,omitempty"` // Fallback model that embedded the chunk, if one did
+	IsTest        bool    `json:"is_test,omitempty"`
+	IsGenerated   bool    `json:"is_generated,omitempty"`
+
+	// Other places the same code appears, as path:start-end
+	Duplicates []string `json:"duplicate_locations,omitempty"`
+}
+
+func resolveSearchMode() (searchMode, error) {
+	selectionCount := 0
+	var selected searchMode
+
+	if codeMode {
+		selectionCount++
+		selected = modeCode
+	}
+	if docsMode {
+		selectionCount++
+		selected = modeDocs
+	}
+	if hybridMode {
+		selectionCount++
+		selected = modeHybrid
+	}
+
+	if selectionCount > 1 {
+		return "", fmt.Errorf("flags --code, --docs, and --hybrid are mutually exclusive")
+	}
+	if testsFlag && noTests {
+		return "", fmt.Errorf("flags --tests and --no-tests are mutually exclusive")
+	}
+	if selectionCount == 0 {
+		return modeHybrid, nil
+	}
+	return selected, nil
+}
+
+// searchIndex is an index a search reads, with the files whose chunks it
+// leaves out because an overlay index replaces them
+type searchIndex struct {
+	store    storage.Store
+	shadowed []string
+}
+
+func runSingleModeSearch(indexes []searchIndex, query string, limit int, mode searchMode) ([]SearchResult, int, error) {
+	if limit <= 0 {
+		limit = 10
+	}
+
+	queryEmbedding, model, err := embedQueryForMode(query, mode)
+	if err != nil {
+		return nil, 0, err
+	}
+
+	var rawResults []map[string]interface{}
+	for _, index := range indexes {
+		results, err := index.store.Search(queryEmbedding, limit, withEmbeddingModel(filterForMode(mode, index.shadowed), model))
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to search %s embeddings: %w", mode, err)
+		}
+		rawResults = append(rawResults, results...)
+	}
+
+	deduplicated := deduplicateResults(formatResults(rawResults))
+	return deduplicated, len(rawResults), nil
+}
+
+func runHybridSearch(indexes []searchIndex, query string, limit int) ([]SearchResult, int, error) {
+	if limit <= 0 {
+		limit = 10
+	}
+
+	codeEmbedding, codeModel, err := embedQueryForMode(query, modeCode)
+	if err != nil {
+		return nil, 0, err
+	}
+	docsEmbedding, docsModel, err := embedQueryForMode(query, modeDocs)
+	if err != nil {
+		return nil, 0, err
+	}
+
+	var codeResults, docsResults []map[string]interface{}
+	for _, index := range indexes {
+		results, err := index.store.Search(codeEmbedding, limit, withEmbeddingModel(filterForMode(modeCode, index.shadowed), codeModel))
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to search code embeddings: %w", err)
+		}
+		codeResults = append(codeResults, results...)
+
+		results, err = index.store.Search(docsEmbedding, limit, withEmbeddingModel(filterForMode(modeDocs, index.shadowed), docsModel))
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to search documentation embeddings: %w", err)
+		}
+		docsResults = append(docsResults, results...)
+	}
+
+	formatted := append(formatResults(codeResults), formatResults(docsResults)...)
+	deduplicated := deduplicateResults(formatted)
+
+	return deduplicated, len(codeResults) + len(docsResults), nil
+}
+
+// embedQueryForMode embeds a query with the model of mode and returns the
+// fallback model that embedded it in that model's place, if one did
+func embedQueryForMode(query string, mode searchMode) ([]float64, string, error) {
+	var client embeddings.Client
+	switch mode {
+	case modeDocs:
+		client = newDocsEmbeddingClient()
+	default:
+		client = newCodeEmbeddingClient()
+	}
+
+	embedding, model, err := embedFrom(client, query)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to generate %s query embedding: %w", mode, err)
+	}
+	if model != "" {
+		fmt.Fprintf(os.Stderr, "Warning: the %s query was embedded by fallback model %s; only chunks it embedded are searched\n", mode, model)
+	} else if err := checkQueryVector(mode, embedding); err != nil {
+		return nil, "", err
+	}
+	return embedding, model, nil
+}
+
+// symbolCandidates is how many results are searched for each one asked for
+// when --symbol filters the results after the search
+const symbolCandidates = 10
+
+// symbolAfterSearch is set when --symbol cannot filter the chunks searched,
+// as in an encrypted index, and filters the results instead
+var symbolAfterSearch bool
+
+// matchingSymbol returns the results whose qualified names contain symbol,
+// as the --symbol filter matches them
+func matchingSymbol(results []SearchResult, symbol string) []SearchResult {
+	var matched []SearchResult
+	for _, r := range results {
+		if strings.Contains(r.QualifiedName, symbol) {
+			matched = append(matched, r)
+		}
+	}
+	return matched
+}
+
+// likePattern escapes a string to match itself in a LIKE pattern with
+// ESCAPE '\', so the _ and % of a name such as my_func are not wildcards
+func likePattern(s string) string {
+	s = strings.NewReplacer(`\`, `\\`, "%", `\%`, "_", `\_`).Replace(s)
+	return strings.ReplaceAll(s, "'", "''")
+}
+
+func filterForMode(mode searchMode, shadowed []string) string {
+	var conditions []string
+	switch mode {
+	case modeCode:
+		conditions = append(conditions, "embedding_type = 'code'")
+	case modeDocs:
+		conditions = append(conditions, "embedding_type = 'docs'")
+	}
+	if symbolFlag != "" && !symbolAfterSearch {
+		// Matches any part of the path, so "utils" finds everything in the utils module
+		conditions = append(conditions, fmt.Sprintf(`qualified_name LIKE '%%%s%%' ESCAPE '\'`, likePattern(symbolFlag)))
+	}
+	switch {
+	case testsFlag:
+		conditions = append(conditions, "is_test = true")
+	case noTests:
+		conditions = append(conditions, "is_test = false")
+	}
+	if noGenerated {
+		conditions = append(conditions, "is_generated = false")
+	}
+	if repoFlag != "" {
+		conditions = append(conditions, fmt.Sprintf("repo = '%s'", strings.ReplaceAll(repoFlag, "'", "''")))
+	}
+	if len(shadowed) > 0 {
+		quoted := make([]string, len(shadowed))
+		for i, path := range shadowed {
+			quoted[i] = "'" + strings.ReplaceAll(path, "'", "''") + "'"
+		}
+		conditions = append(conditions, fmt.Sprintf("file_path NOT IN (%s)", strings.Join(quoted, ", ")))
+	}
+	return strings.Join(conditions, " AND ")
+}
+
+func formatResults(results []map[string]interface{}) []SearchResult {
+	formatted := make([]SearchResult, len(results))
+	for i, r := range results {
+		formatted[i] = SearchResult{
+			ChunkID:       getStringOrDefault(r, "chunk_id", ""),
+			FilePath:      getStringOrDefault(r, "file_path", ""),
+			LineStart:     getIntOrDefault(r, "line_start", 0),
+			LineEnd:       getIntOrDefault(r, "line_end", 0),
+			Language:      getStringOrDefault(r, "language", ""),
+			Code:          getStringOrDefault(r, "code", ""),
+			Score:         getFloat64OrDefault(r, "_distance", 0.0),
+			EmbeddingType: getStringOrDefault(r, "embedding_type", ""),
+			ChunkType:     getStringOrDefault(r, "chunk_type", ""),
+			Heading:       getStringOrDefault(r, "heading", ""),
+			HeadingLevel:  getStringOrDefault(r, "heading_level", ""),
+			ParentHeading: getStringOrDefault(r, "parent_heading", ""),
+			DocSummary:    getStringOrDefault(r, "doc_summary", ""),
+			DocComment:    getStringOrDefault(r, "doc_comment", ""),
+			Attributes:    getStringOrDefault(r, "attributes", ""),
+			Name:          getStringOrDefault(r, "name", ""),
+			QualifiedName: getStringOrDefault(r, "qualified_name", ""),
+			Kind:          getStringOrDefault(r, "kind", ""),
+			Visibility:    getStringOrDefault(r, "visibility", ""),
+			Signature:     getStringOrDefault(r, "signature", ""),
+			Receiver:      getStringOrDefault(r, "receiver", ""),
+			Calls:         getStringOrDefault(r, "calls", ""),
+			Repo:          getStringOrDefault(r, "repo", ""),
+			Submodule:     getStringOrDefault(r, "submodule", ""),
+			Fallback:      getStringOrDefault(r, "embedding_model", ""),
+			Duplicates:    splitLines(getStringOrDefault(r, "duplicate_locations", "")),
+			IsTest:        getBoolOrDefault(r, "is_test", false),
+			IsGenerated:   getBoolOrDefault(r, "is_generated", false),
+		}
+	}
+	return formatted
+}
+
+// deduplicateResults removes duplicate code chunks, keeping the highest-scoring (lowest distance) entry
+func deduplicateResults(results []SearchResult) []SearchResult {
+	if len(results) == 0 {
+		return results
+	}
+
+	// Group by code content
+	type resultGroup struct {
+		bestResult SearchResult
+		bestScore  float64
+	}
+
+	groups := make(map[string]*resultGroup)
+
+	for _, result := range results {
+		if group, exists := groups[result.Code]; exists {
+			// Keep the result with the lower distance (better match)
+			if result.Score < group.bestScore {
+				group.bestResult = result
+				group.bestScore = result.Score
+			}
+		} else {
+			// New unique code
+			groups[result.Code] = &resultGroup{
+				bestResult: result,
+				bestScore:  result.Score,
+			}
+		}
+	}
+
+	// Extract deduplicated results
+	deduplicated := make([]SearchResult, 0, len(groups))
+	for _, group := range groups {
+		deduplicated = append(deduplicated, group.bestResult)
+	}
+
+	// Sort by score (ascending - lower distance is better)
+	sort.Slice(deduplicated, func(i, j int) bool {
+		return deduplicated[i].Score < deduplicated[j].Score
+	})
+
+	return deduplicated
+}
+
+// splitLines splits a newline-separated column value, returning nil for ""
+func splitLines(value string) []string {
+	if value == "" {
+		return nil
+	}
+	return strings.Split(value, "\n")
+}
+
+func getStringOrDefault(m map[string]interface{}, key string, defaultVal string) string {
+	if val, ok := m[key]; ok {
+		if str, ok := val.(string); ok {
+			return str
+		}
+	}
+	return defaultVal
+}
+
+func getIntOrDefault(m map[string]interface{}, key string, defaultVal int) int {
+	if val, ok := m[key]; ok {
+		switch v := val.(type) {
+		case int:
+			return v
+		case int32:
+			return int(v)
+		case int64:
+			return int(v)
+		case float64:
+			return int(v)
+		}
+	}
+	return defaultVal
+}
+
+func getBoolOrDefault(m map[string]interface{}, key string, defaultVal bool) bool {
+	if val, ok := m[key]; ok {
+		if b, ok := val.(bool); ok {
+			return b
+		}
+	}
+	return defaultVal
+}
+
+func getFloat64OrDefault(m map[string]interface{}, key string, defaultVal float64) float64 {
+	if val, ok := m[key]; ok {
+		if f, ok := val.(float64); ok {
+			return f
+		}
+	}
+	return defaultVal
+}
+
+func init() {
+	searchCmd.Flags().BoolVarP(&codeMode, "code", "c", false, "Search code embeddings only")
+	searchCmd.Flags().BoolVarP(&docsMode, "docs", "d", false, "Search documentation embeddings only")
+	searchCmd.Flags().BoolVar(&hybridMode, "hybrid", false, "Search both code and documentation embeddings (default)")
+	searchCmd.Flags().BoolVar(&jsonOutput, "json", false, "Output results as JSON")
+	searchCmd.Flags().IntVar(&limitFlag, "limit", 10, "Maximum number of results to return")
+	searchCmd.Flags().BoolVar(&testsFlag, "tests", false, "Only return test code")
+	searchCmd.Flags().BoolVar(&noTests, "no-tests", false, "Leave test code out of the results")
+	searchCmd.Flags().BoolVar(&noGenerated, "no-generated", false, "Leave generated code out of the results")
+	searchCmd.Flags().StringVar(&repoFlag, "repo", "", "Only return results from this repository of a multi-repo workspace")
+	searchCmd.Flags().StringVar(&branchFlag, "branch", "", "Search the index of this branch instead of the checked-out one (with branch_indexes)")
+	searchCmd.Flags().DurationVar(&maxStaleness, "max-staleness", 0, "Refresh the index incrementally before searching when it is older than this, e.g. 1h")
+	searchCmd.Flags().BoolVar(&noOverlay, "no-overlay", false, "Search the index without the overlay index of 'code-scout index --overlay'")
+	searchCmd.Flags().StringVar(&spaceFlag, "space", "", "Search these embedding spaces, comma-separated, fusing their results by rank (default: the index's own vectors)")
+	searchCmd.Flags().StringVar(&sqliteFile, "sqlite", "", "Search the SQLite index written by 'code-scout index export --sqlite' to this file instead of the workspace's")
+	searchCmd.Flags().StringVar(&symbolFlag, "symbol", "", "Only return chunks whose qualified name contains this text (e.g. utils::slugify)")
+	rootCmd.AddCommand(searchCmd)
+}
@@ -0,0 +1,20 @@
+package cli
+
+import "testing"
+
+func TestLikePattern(t *testing.T) {
+	tests := []struct {
+		in, want string
+	}{
+		{"my_func", `my\_func`},
+		{"100%", `100\%`},
+		{`a\b`, `a\\b`},
+		{"it's", "it''s"},
+		{"plain", "plain"},
+	}
+	for _, tt := range tests {
+		if got := likePattern(tt.in); got != tt.want {
+			t.Errorf("likePattern(%q) = %q, want %q", tt.in, got, tt.want)
+		}
+	}
+}
@@ -0,0 +1,331 @@
+package cli
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+const (
+	// defaultSpace names the index's own vectors in --space
+	defaultSpace = "default"
+	// spaceCandidates is how many vectors of a space are searched for each
+	// result asked for, since the chunks are filtered after the search
+	spaceCandidates = 4
+	// rrfK damps the weight reciprocal rank fusion gives the top ranks, as
+	// in the original paper
+	rrfK = 60
+)
+
+var (
+	// spaceFlag selects the embedding spaces a search reads, comma-separated
+	spaceFlag string
+	// dropSpace makes index space delete a space's vectors
+	dropSpace bool
+)
+
+var indexSpaceCmd = &cobra.Command{
+	Use:   "space <name>",
+	Short: "Embed the index's chunks into an embedding space",
+	Long: `Embed the chunks of the index with the models of a space configured in
+"spaces", and store the vectors beside the index's own, so searches can read
+the space with --space and compare its models with the index's. The chunks are
+not chunked again. Indexing leaves spaces as they are; running this again
+embeds only the chunks that changed since, and removes the vectors of chunks
+that are gone. A space whose models changed is embedded from scratch.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if err := checkWritable("embed a space"); err != nil {
+			return err
+		}
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		store, _, err := openIndexForCheck(cwd)
+		if err != nil {
+			return err
+		}
+		defer store.Close()
+		lock, err := lockIndex(commandContext(cmd), store)
+		if err != nil {
+			return err
+		}
+		defer lock.Unlock()
+
+		name := args[0]
+		spaces, err := store.LoadSpaces()
+		if err != nil {
+			return err
+		}
+		if dropSpace {
+			if err := store.DropSpace(name); err != nil {
+				return err
+			}
+			delete(spaces, name)
+			if err := store.SaveSpaces(spaces); err != nil {
+				return err
+			}
+			fmt.Printf("✓ Deleted embedding space %s\n", name)
+			return uploadIndex(cwd)
+		}
+
+		space, err := findSpace(name)
+		if err != nil {
+			return err
+		}
+		if err := embedSpace(store, spaces, space); err != nil {
+			return err
+		}
+		return uploadIndex(cwd)
+	},
+}
+
+// findSpace returns the configured embedding space with the given name
+func findSpace(name string) (config.SpaceConfig, error) {
+	if globalConfig != nil {
+		for _, space := range globalConfig.Spaces {
+			if space.Name == name {
+				return space, nil
+			}
+		}
+	}
+	return config.SpaceConfig{}, fmt.Errorf("no embedding space named %s in the configuration's spaces", name)
+}
+
+// spaceConfig returns the configuration embedding clients of a space are
+// created with: the configured one, with the space's models and, when the
+// space has an endpoint of its own, its provider and API key
+func spaceConfig(space config.SpaceConfig) *config.Config {
+	cfg := config.Default()
+	if globalConfig != nil {
+		copied := *globalConfig
+		cfg = &copied
+	}
+	if space.Endpoint != "" {
+		cfg.Provider = space.Provider
+		cfg.Endpoint = strings.TrimSuffix(space.Endpoint, "/")
+		cfg.APIKey = space.APIKey
+		cfg.ApplyEnv()
+	}
+	cfg.CodeModel, cfg.TextModel = space.CodeModel, space.SpaceTextModel()
+	cfg.Dimensions = space.Dimensions
+	return cfg
+}
+
+// spaceModels identifies the models of an embedding space, which its vectors
+// must have been embedded with to be searched
+func spaceModels(space config.SpaceConfig) storage.EmbeddingModels {
+	models := storage.EmbeddingModels{
+		CodeModel: space.CodeModel,
+		TextModel: space.SpaceTextModel(),
+		Dimension: storage.VectorDimension,
+	}
+	if space.Dimensions > 0 {
+		models.Dimension = space.Dimensions
+	}
+	return models
+}
+
+// embedSpace brings the vectors of an embedding space up to date with the
+// chunks of the index, embedding the chunks the space has no vectors of or
+// whose content changed, and returns once they are stored
+func embedSpace(store *storage.LanceDBStore, spaces map[string]storage.EmbeddingModels, space config.SpaceConfig) error {
+	models := spaceModels(space)
+	if recorded, ok := spaces[space.Name]; ok && recorded != models {
+		fmt.Printf("Embedding space %s was embedded with %s/%s; embedding it again with %s/%s\n",
+			space.Name, recorded.CodeModel, recorded.TextModel, models.CodeModel, models.TextModel)
+		if err := store.DropSpace(space.Name); err != nil {
+			return err
+		}
+	}
+	// The models are recorded first, so an interrupted run carries on where
+	// it stopped
+	spaces[space.Name] = models
+	if err := store.SaveSpaces(spaces); err != nil {
+		return err
+	}
+
+	hashes, err := store.SpaceHashes(space.Name)
+	if err != nil {
+		return err
+	}
+	stored, err := store.ReadChunks()
+	if err != nil {
+		return err
+	}
+	var pending []chunker.Chunk
+	live := make(map[string]bool, len(stored))
+	for _, c := range stored {
+		if c.DecodeErr != nil {
+			return fmt.Errorf("chunk %s cannot be read: %w; run 'code-scout index verify'", c.Chunk.ID, c.DecodeErr)
+		}
+		live[c.Chunk.ID] = true
+		if hash, ok := hashes[c.Chunk.ID]; !ok || hash != c.Chunk.Metadata["content_hash"] {
+			pending = append(pending, c.Chunk)
+		}
+	}
+	var vanished []string
+	for id := range hashes {
+		if !live[id] {
+			vanished = append(vanished, id)
+		}
+	}
+	if err := store.DeleteSpaceVectors(space.Name, vanished); err != nil {
+		return err
+	}
+
+	fmt.Printf("Embedding %d of %d chunks into space %s with %s/%s\n",
+		len(pending), len(stored), space.Name, models.CodeModel, models.TextModel)
+	cfg := spaceConfig(space)
+	p := &indexPipeline{
+		codeClient: newEmbeddingClientWith(cfg, cfg.CodeModel),
+		docsClient: newEmbeddingClientWith(cfg, cfg.TextModel),
+	}
+	done := 0
+	for start := 0; start < len(pending); start += reembedBatch {
+		batch := indexBatch{chunks: pending[start:min(start+reembedBatch, len(pending))]}
+		if err := p.embedBatch(&batch); err != nil {
+			return fmt.Errorf("failed to embed space %s: %w", space.Name, err)
+		}
+		if err := store.StoreSpaceVectors(space.Name, batch.chunks, batch.embeddings); err != nil {
+			return err
+		}
+		done += len(batch.chunks)
+		fmt.Printf("Embedded %d chunks\n", done)
+	}
+	fmt.Printf("✓ Embedding space %s is up to date\n", space.Name)
+	printUsage()
+	return p.reportFailures()
+}
+
+// searchSpaces searches each embedding space named in --space and, when
+// there are several, fuses their results by reciprocal rank. The default
+// space is the index's own vectors, searched as without --space; other spaces
+// cover the index itself, so an overlay index is left out of them.
+func searchSpaces(indexes []searchIndex, query string, limit int, mode searchMode) ([]SearchResult, int, error) {
+	if limit <= 0 {
+		limit = 10
+	}
+	names := strings.Split(spaceFlag, ",")
+	var (
+		ranked [][]SearchResult
+		total  int
+	)
+	for _, name := range names {
+		name = strings.TrimSpace(name)
+		var (
+			results []SearchResult
+			matches int
+			err     error
+		)
+		switch {
+		case name == defaultSpace && mode == modeHybrid:
+			results, matches, err = runHybridSearch(indexes, query, limit)
+		case name == defaultSpace:
+			results, matches, err = runSingleModeSearch(indexes, query, limit, mode)
+		default:
+			store, ok := indexes[0].store.(*storage.LanceDBStore)
+			if !ok {
+				return nil, 0, fmt.Errorf("embedding space %q is not in a SQLite index, which holds the index's own vectors only", name)
+			}
+			results, matches, err = searchSpace(store, name, query, limit, mode)
+		}
+		if err != nil {
+			return nil, 0, err
+		}
+		ranked = append(ranked, results)
+		total += matches
+	}
+	if len(ranked) == 1 {
+		return ranked[0], total, nil
+	}
+	return fuseResults(ranked), total, nil
+}
+
+// searchSpace searches the vectors of one embedding space, with queries
+// embedded by the space's models
+func searchSpace(store *storage.LanceDBStore, name, query string, limit int, mode searchMode) ([]SearchResult, int, error) {
+	space, err := findSpace(name)
+	if err != nil {
+		return nil, 0, err
+	}
+	spaces, err := store.LoadSpaces()
+	if err != nil {
+		return nil, 0, err
+	}
+	models := spaceModels(space)
+	recorded, ok := spaces[name]
+	if !ok {
+		return nil, 0, fmt.Errorf("embedding space %s has no vectors; run 'code-scout index space %s'", name, name)
+	}
+	if recorded != models {
+		return nil, 0, fmt.Errorf("embedding space %s was embedded with %s/%s (%d dimensions) but the configuration uses %s/%s (%d dimensions); run 'code-scout index space %s'",
+			name, recorded.CodeModel, recorded.TextModel, recorded.Dimension, models.CodeModel, models.TextModel, models.Dimension, name)
+	}
+
+	modes := []searchMode{mode}
+	if mode == modeHybrid {
+		modes = []searchMode{modeCode, modeDocs}
+	}
+	cfg := spaceConfig(space)
+	var rawResults []map[string]interface{}
+	for _, m := range modes {
+		model := cfg.CodeModel
+		if m == modeDocs {
+			model = cfg.TextModel
+		}
+		queryEmbedding, err := newEmbeddingClientWith(cfg, model).Embed(query)
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to generate %s query embedding for space %s: %w", m, name, err)
+		}
+		results, err := store.SearchSpace(name, queryEmbedding, limit*spaceCandidates, string(m), filterForMode(m, nil))
+		if err != nil {
+			return nil, 0, err
+		}
+		rawResults = append(rawResults, results...)
+	}
+	return deduplicateResults(formatResults(rawResults)), len(rawResults), nil
+}
+
+// fuseResults merges the ranked results of several spaces by reciprocal rank
+// fusion: a result scores 1/(rrfK+rank) in each space that returned it, and
+// the scores add up. Distances of different models cannot be compared, but
+// their ranks can. Unlike distances, a higher fused score is a better match.
+func fuseResults(ranked [][]SearchResult) []SearchResult {
+	scores := make(map[string]float64)
+	best := make(map[string]SearchResult)
+	for _, results := range ranked {
+		for rank, result := range results {
+			scores[result.Code] += 1.0 / float64(rrfK+rank+1)
+			if _, ok := best[result.Code]; !ok {
+				best[result.Code] = result
+			}
+		}
+	}
+	fused := make([]SearchResult, 0, len(best))
+	for code, result := range best {
+		result.Score = scores[code]
+		fused = append(fused, result)
+	}
+	sort.Slice(fused, func(i, j int) bool {
+		if fused[i].Score != fused[j].Score {
+			return fused[i].Score > fused[j].Score
+		}
+		if fused[i].FilePath != fused[j].FilePath {
+			return fused[i].FilePath < fused[j].FilePath
+		}
+		return fused[i].LineStart < fused[j].LineStart
+	})
+	return fused
+}
+
+func init() {
+	indexSpaceCmd.Flags().BoolVar(&dropSpace, "drop", false, "Delete the space's vectors instead of embedding them")
+	indexCmd.AddCommand(indexSpaceCmd)
+}
@@ -0,0 +1,112 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+var (
+	exportSQLiteFlag bool   // index export writes a SQLite database
+	sqliteFile       string // search reads this SQLite index
+)
+
+// exportSQLite writes the index of the workspace at cwd to the SQLite
+// database at file, replacing the index it held in one transaction, so a
+// reader of the database sees either the old index or the new one. It returns
+// the number of files indexed.
+func exportSQLite(cwd, file string) (int, error) {
+	dir, err := indexDir(cwd)
+	if err != nil {
+		return 0, err
+	}
+	store, err := openStore(dir)
+	if err != nil {
+		return 0, fmt.Errorf("failed to open database: %w", err)
+	}
+	defer store.Close()
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		return 0, fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if metadata.IsEmpty() {
+		return 0, fmt.Errorf("nothing is indexed yet; run 'code-scout index' first")
+	}
+	// The database holds the chunk text as it is, for other tools to query
+	if metadata.KeyFingerprint != "" {
+		return 0, fmt.Errorf("the index is encrypted, and a SQLite index would hold its text unencrypted; export it as an archive instead")
+	}
+
+	stored, err := store.ReadChunks()
+	if err != nil {
+		return 0, err
+	}
+	chunks := make([]chunker.Chunk, len(stored))
+	vectors := make([][]float64, len(stored))
+	for i, c := range stored {
+		if c.DecodeErr != nil {
+			return 0, fmt.Errorf("chunk %s cannot be read: %w; run 'code-scout index verify'", c.Chunk.ID, c.DecodeErr)
+		}
+		chunks[i], vectors[i] = c.Chunk, c.Vector
+	}
+
+	db, err := storage.OpenSQLiteStore(file)
+	if err != nil {
+		return 0, err
+	}
+	defer db.Close()
+	tx, err := db.Begin()
+	if err != nil {
+		return 0, err
+	}
+	defer tx.Close()
+	if err := tx.Reset(); err != nil {
+		return 0, err
+	}
+	tx.SetDimension(store.VectorWidth())
+	if err := tx.StoreChunks(chunks, vectors); err != nil {
+		return 0, fmt.Errorf("failed to store chunks: %w", err)
+	}
+	if err := tx.SaveMetadata(metadata); err != nil {
+		return 0, err
+	}
+	if err := tx.Commit(); err != nil {
+		return 0, err
+	}
+	return len(metadata.FileModTimes), nil
+}
+
+// openSQLiteSearch opens the SQLite index at file for a search, checking it
+// was embedded with models the query can be embedded with
+func openSQLiteSearch(file string) (*storage.SQLiteStore, error) {
+	if _, err := os.Stat(file); err != nil {
+		return nil, fmt.Errorf("failed to open the SQLite index: %w", err)
+	}
+	store, err := storage.OpenSQLiteStore(file)
+	if err != nil {
+		return nil, err
+	}
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		store.Close()
+		return nil, fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if metadata.IsEmpty() {
+		store.Close()
+		return nil, fmt.Errorf("%s holds no index; write one with 'code-scout index export --sqlite %s'", file, file)
+	}
+	models := embeddingModels()
+	if metadata.ModelsChanged(models) {
+		// Queries are embedded with the models the exported vectors came from
+		fmt.Fprintf(os.Stderr, "Warning: the SQLite index was embedded with %s/%s; searching with them\n",
+			metadata.Models.CodeModel, metadata.Models.TextModel)
+		useModels(*metadata.Models)
+	} else if reason := metadata.Incompatibility(models); reason != "" {
+		store.Close()
+		return nil, fmt.Errorf("%s; export the index to %s again", reason, file)
+	}
+	searchedMetadata, searchedDir = metadata, file
+	return store, nil
+}
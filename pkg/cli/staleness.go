@@ -0,0 +1,88 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/git"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+// staleFiles is how many files the working tree may differ from the indexed
+// commits by before search warns that the index is stale
+const staleFiles = 25
+
+// maxStaleness is the age past which search refreshes the index first
+var maxStaleness time.Duration
+
+// indexDrift counts the files of the workspace roots that differ from the
+// commits they were indexed at and were changed or deleted since. Files that
+// already differed when they were indexed are not counted. Roots outside git,
+// or indexed at no recorded commit, count none.
+func indexDrift(metadata *storage.IndexMetadata, roots []workspaceRoot) int {
+	drift := 0
+	for _, root := range roots {
+		commit := indexedCommit(metadata, root)
+		if commit == "" {
+			continue
+		}
+		paths, err := git.DriftedFiles(root.dir, commit)
+		if err != nil {
+			continue
+		}
+		for _, path := range paths {
+			info, err := os.Stat(path)
+			if err != nil || info.ModTime().After(metadata.LastIndexTime) {
+				drift++
+			}
+		}
+	}
+	return drift
+}
+
+// warnIfStale warns when the working tree has drifted far from the commits
+// the index was built at
+func warnIfStale(metadata *storage.IndexMetadata, cwd string) {
+	roots, err := workspaceRoots(cwd)
+	if err != nil {
+		return
+	}
+	if drift := indexDrift(metadata, roots); drift >= staleFiles {
+		age := time.Since(metadata.LastIndexTime).Round(time.Minute)
+		fmt.Fprintf(os.Stderr, "Warning: %d file(s) changed since the index was built %s ago; run 'code-scout index' or search with --max-staleness to refresh it\n", drift, age)
+	}
+}
+
+// refreshIfStale brings the index up to date before a search when it is
+// older than --max-staleness. The refresh reports its progress on stderr, so
+// it does not mix with the search results.
+func refreshIfStale(cmd *cobra.Command, cwd string) error {
+	store, err := openIndex(cwd)
+	if err != nil {
+		return fmt.Errorf("failed to open database: %w", err)
+	}
+	metadata, err := store.LoadMetadata()
+	store.Close()
+	if err != nil {
+		return fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if !metadata.IsEmpty() && time.Since(metadata.LastIndexTime) <= maxStaleness {
+		return nil
+	}
+
+	if globalConfig != nil {
+		if err := registerGrammars(globalConfig.Grammars); err != nil {
+			return err
+		}
+	}
+	stdout := os.Stdout
+	os.Stdout = os.Stderr
+	defer func() { os.Stdout = stdout }()
+	fmt.Printf("The index is older than %s, refreshing it...\n", maxStaleness)
+	if err := indexWorkspace(cmd, cwd); err != nil {
+		return fmt.Errorf("failed to refresh the index: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,221 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+var statsJSON bool
+
+// indexStats is the report printed by index stats
+type indexStats struct {
+	Files           int                      `json:"files"`
+	Chunks          int                      `json:"chunks"`
+	ByLanguage      map[string]int           `json:"by_language"`
+	ByDirectory     map[string]int           `json:"by_directory"`
+	ByEmbeddingType map[string]int           `json:"by_embedding_type"`
+	AvgChunkLines   float64                  `json:"avg_chunk_lines"`
+	AvgChunkBytes   float64                  `json:"avg_chunk_bytes"`
+	DiskBytes       int64                    `json:"disk_bytes"`
+	SchemaVersion   int                      `json:"schema_version"`
+	Models          *storage.EmbeddingModels `json:"models,omitempty"`
+	LastIndexTime   time.Time                `json:"last_index_time"`
+	Commit          string                   `json:"commit,omitempty"`
+	RepoCommits     map[string]string        `json:"repo_commits,omitempty"`
+
+	// The fingerprints of the vectors the models return, by embedding type
+	Vectors map[string]storage.VectorFingerprint `json:"vectors,omitempty"`
+
+	// What indexing sent each provider and model over every run, and the cost
+	// of the tokens of models with a known price
+	Usage map[string]storage.TokenUsage `json:"usage,omitempty"`
+	Cost  float64                       `json:"estimated_cost_usd,omitempty"`
+}
+
+var indexStatsCmd = &cobra.Command{
+	Use:   "stats",
+	Short: "Report what the index covers",
+	Long: `Report the chunks in the index by language and directory, the number of vectors,
+the index's size on disk, the embedding models it was built with, the commit
+it was last indexed at, and the requests and estimated tokens indexing has sent
+each embedding provider, with their cost where token_prices or --token-price
+gives one.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+
+		store, err := openIndex(cwd)
+		if err != nil {
+			return fmt.Errorf("failed to open database: %w", err)
+		}
+		defer store.Close()
+
+		metadata, err := store.LoadMetadata()
+		if err != nil {
+			return fmt.Errorf("failed to load metadata: %w", err)
+		}
+		if metadata.IsEmpty() {
+			return fmt.Errorf("nothing is indexed yet; run 'code-scout index' first")
+		}
+		if reason, _ := keyMismatch(metadata, store); reason != "" {
+			return fmt.Errorf("%s", reason)
+		}
+		chunkStats, err := store.Stats()
+		if err != nil {
+			return err
+		}
+		diskBytes, err := store.DiskSize()
+		if err != nil {
+			return err
+		}
+
+		stats := indexStats{
+			Files:           len(metadata.FileModTimes),
+			Chunks:          chunkStats.Chunks,
+			ByLanguage:      chunkStats.ByLanguage,
+			ByDirectory:     make(map[string]int),
+			ByEmbeddingType: chunkStats.ByEmbeddingType,
+			DiskBytes:       diskBytes,
+			SchemaVersion:   metadata.SchemaVersion,
+			Models:          metadata.Models,
+			Vectors:         metadata.Vectors,
+			LastIndexTime:   metadata.LastIndexTime,
+			Commit:          metadata.Commit,
+			RepoCommits:     metadata.RepoCommits,
+			Usage:           metadata.Usage,
+		}
+		for key, usage := range stats.Usage {
+			stats.Cost += float64(usage.Tokens) * tokenPriceOf(key) / 1e6
+		}
+		for filePath, chunks := range chunkStats.ByFile {
+			stats.ByDirectory[topDirectory(cwd, filePath)] += chunks
+		}
+		if chunkStats.Chunks > 0 {
+			stats.AvgChunkLines = float64(chunkStats.TotalLines) / float64(chunkStats.Chunks)
+			stats.AvgChunkBytes = float64(chunkStats.TotalBytes) / float64(chunkStats.Chunks)
+		}
+
+		if statsJSON {
+			jsonBytes, err := json.MarshalIndent(stats, "", "  ")
+			if err != nil {
+				return fmt.Errorf("failed to marshal JSON: %w", err)
+			}
+			fmt.Println(string(jsonBytes))
+			return nil
+		}
+		printIndexStats(stats)
+		return nil
+	},
+}
+
+// topDirectory returns the directory directly under cwd that contains a
+// file, "." for files in cwd itself, or the repository directory for files of
+// a workspace repository outside it
+func topDirectory(cwd, filePath string) string {
+	rel, err := filepath.Rel(cwd, filePath)
+	if err != nil {
+		return filepath.Dir(filePath)
+	}
+	parts := strings.Split(filepath.ToSlash(rel), "/")
+	up := 0
+	for up < len(parts) && parts[up] == ".." {
+		up++
+	}
+	if up >= len(parts)-1 {
+		if up == 0 {
+			return "."
+		}
+		return strings.Join(parts[:up], "/")
+	}
+	return strings.Join(parts[:up+1], "/")
+}
+
+// printIndexStats prints the report as text
+func printIndexStats(stats indexStats) {
+	fmt.Printf("Files:    %d\n", stats.Files)
+	fmt.Printf("Chunks:   %d (%d vectors", stats.Chunks, stats.Chunks)
+	for _, embeddingType := range sortedByCount(stats.ByEmbeddingType) {
+		fmt.Printf(", %d %s", stats.ByEmbeddingType[embeddingType], embeddingType)
+	}
+	fmt.Println(")")
+	fmt.Printf("Average:  %.1f lines, %.0f bytes per chunk\n", stats.AvgChunkLines, stats.AvgChunkBytes)
+	fmt.Printf("On disk:  %s\n", formatBytes(stats.DiskBytes))
+	if stats.Models != nil {
+		fmt.Printf("Models:   %s (code), %s (text), %d dimensions\n", stats.Models.CodeModel, stats.Models.TextModel, stats.Models.Dimension)
+	}
+	for _, embeddingType := range sortedKeys(stats.Vectors) {
+		fmt.Printf("Vectors:  %s (%s)\n", stats.Vectors[embeddingType], embeddingType)
+	}
+	fmt.Printf("Format:   version %d\n", stats.SchemaVersion)
+	fmt.Printf("Indexed:  %s\n", stats.LastIndexTime.Format(time.RFC3339))
+	if stats.Commit != "" {
+		fmt.Printf("Commit:   %s\n", stats.Commit)
+	}
+	for _, repo := range sortedKeys(stats.RepoCommits) {
+		fmt.Printf("Commit:   %s (%s)\n", stats.RepoCommits[repo], repo)
+	}
+
+	if len(stats.Usage) > 0 {
+		fmt.Println("\nEmbedding usage over every run (estimated tokens):")
+		printUsageTable(stats.Usage)
+	}
+
+	fmt.Println("\nBy language:")
+	for _, language := range sortedByCount(stats.ByLanguage) {
+		fmt.Printf("  %-20s %d\n", language, stats.ByLanguage[language])
+	}
+	fmt.Println("\nBy directory:")
+	for _, dir := range sortedByCount(stats.ByDirectory) {
+		fmt.Printf("  %-20s %d\n", dir, stats.ByDirectory[dir])
+	}
+}
+
+// sortedByCount returns the keys of counts from the largest count down
+func sortedByCount(counts map[string]int) []string {
+	keys := sortedKeys(counts)
+	sort.SliceStable(keys, func(i, j int) bool {
+		return counts[keys[i]] > counts[keys[j]]
+	})
+	return keys
+}
+
+// sortedKeys returns the keys of a map in order
+func sortedKeys[V any](m map[string]V) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
+
+// formatBytes formats a size in bytes for display, e.g. 12.3 MB
+func formatBytes(size int64) string {
+	const unit = 1024
+	if size < unit {
+		return fmt.Sprintf("%d B", size)
+	}
+	div, exp := int64(unit), 0
+	for n := size / unit; n >= unit; n /= unit {
+		div *= unit
+		exp++
+	}
+	return fmt.Sprintf("%.1f %cB", float64(size)/float64(div), "KMGTPE"[exp])
+}
+
+func init() {
+	indexCmd.AddCommand(indexStatsCmd)
+	indexStatsCmd.Flags().BoolVar(&statsJSON, "json", false, "Output the report as JSON")
+	indexStatsCmd.Flags().Float64Var(&tokenPrice, "token-price", 0, "Price in USD of a million embedding tokens, for models token_prices does not price")
+	indexStatsCmd.Flags().StringVar(&branchFlag, "branch", "", "Report on the index of this branch instead of the checked-out one (with branch_indexes)")
+}
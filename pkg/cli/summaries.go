@@ -0,0 +1,182 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// summarySpace is the embedding space holding the vectors dual_embedding
+// makes of code chunks' doc comments
+const summarySpace = "summary"
+
+// dualEmbedding reports whether code chunks' doc comments are embedded too
+func dualEmbedding() bool {
+	return globalConfig != nil && globalConfig.DualEmbedding
+}
+
+// summaryText returns the text of a code chunk that dual_embedding embeds
+// with the text model: its symbol and doc comment, or its summary without
+// one. Chunks without either get no second vector.
+func summaryText(chunk chunker.Chunk) string {
+	doc := chunk.Metadata["doc_comment"]
+	if doc == "" {
+		doc = chunk.Metadata["doc_summary"]
+	}
+	if doc == "" {
+		return ""
+	}
+	symbol := chunk.Metadata["qualified_name"]
+	if symbol == "" {
+		symbol = chunk.Name
+	}
+	if symbol == "" {
+		return doc
+	}
+	return symbol + ": " + doc
+}
+
+// summaryModels identifies the model the summary space is embedded with,
+// the index's text model
+func summaryModels() storage.EmbeddingModels {
+	models := embeddingModels()
+	models.CodeModel = ""
+	return models
+}
+
+// embedSummaries brings the summary space up to date with the code chunks of
+// the index: the doc comments of chunks that are new or whose doc comment
+// changed are embedded with the text model, and the vectors of chunks that
+// are gone or lost their doc comment are removed. Like other spaces, the
+// vectors are kept beside the index's own.
+func embedSummaries(store *storage.LanceDBStore) error {
+	spaces, err := store.LoadSpaces()
+	if err != nil {
+		return err
+	}
+	models := summaryModels()
+	if recorded, ok := spaces[summarySpace]; ok && recorded != models {
+		if err := store.DropSpace(summarySpace); err != nil {
+			return err
+		}
+	}
+	spaces[summarySpace] = models
+	if err := store.SaveSpaces(spaces); err != nil {
+		return err
+	}
+
+	hashes, err := store.SpaceHashes(summarySpace)
+	if err != nil {
+		return err
+	}
+	stored, err := store.ReadChunks()
+	if err != nil {
+		return err
+	}
+	// Each doc comment is embedded as a docs chunk of its own, under the ID
+	// of the code chunk it documents
+	var pending []chunker.Chunk
+	live := make(map[string]bool, len(stored))
+	for _, c := range stored {
+		if c.DecodeErr != nil || c.Chunk.EmbeddingType != "code" {
+			continue
+		}
+		text := summaryText(c.Chunk)
+		if text == "" {
+			continue
+		}
+		hash := computeContentHash(text)
+		live[c.Chunk.ID] = true
+		if hashes[c.Chunk.ID] != hash {
+			pending = append(pending, chunker.Chunk{
+				ID:            c.Chunk.ID,
+				FilePath:      c.Chunk.FilePath,
+				Code:          text,
+				EmbeddingType: "docs",
+				Metadata:      map[string]string{"content_hash": hash},
+			})
+		}
+	}
+	var vanished []string
+	for id := range hashes {
+		if !live[id] {
+			vanished = append(vanished, id)
+		}
+	}
+	if err := store.DeleteSpaceVectors(summarySpace, vanished); err != nil {
+		return err
+	}
+	if len(pending) == 0 {
+		return nil
+	}
+
+	fmt.Printf("Embedding the doc comments of %d code chunk(s) with %s\n", len(pending), models.TextModel)
+	// The summaries are searched with the text model's own query vectors, so
+	// they are not embedded by fallbacks
+	p := &indexPipeline{docsClient: newEmbeddingClient(globalConfig.TextModel)}
+	for start := 0; start < len(pending); start += reembedBatch {
+		batch := indexBatch{chunks: pending[start:min(start+reembedBatch, len(pending))]}
+		if err := p.embedBatch(&batch); err != nil {
+			return fmt.Errorf("failed to embed doc comments: %w", err)
+		}
+		if err := store.StoreSpaceVectors(summarySpace, batch.chunks, batch.embeddings); err != nil {
+			return err
+		}
+	}
+	return p.reportFailures()
+}
+
+// searchSummaries searches the summary space with a docs query vector for the
+// code chunks whose doc comments match it, returning nil when the space is not
+// embedded with the configured text model yet
+func searchSummaries(index searchIndex, queryEmbedding []float64, limit int) ([]SearchResult, int, error) {
+	// Doc comments are embedded in the workspace's index only
+	store, ok := index.store.(*storage.LanceDBStore)
+	if !ok {
+		return nil, 0, nil
+	}
+	spaces, err := store.LoadSpaces()
+	if err != nil {
+		return nil, 0, err
+	}
+	if recorded, ok := spaces[summarySpace]; !ok || recorded != summaryModels() {
+		fmt.Fprintln(os.Stderr, "Warning: doc comments are not embedded yet; run 'code-scout index' to embed them")
+		return nil, 0, nil
+	}
+	// An index without doc comments has none to search
+	if !store.HasSpace(summarySpace) {
+		return nil, 0, nil
+	}
+	results, err := store.SearchSpace(summarySpace, queryEmbedding, limit*spaceCandidates, "", filterForMode(modeCode, index.shadowed))
+	if err != nil {
+		return nil, 0, err
+	}
+	return deduplicateResults(formatResults(results)), len(results), nil
+}
+
+// fuseSummaries fuses the results of a code or hybrid search by reciprocal
+// rank with the code chunks whose doc comments match the query, so a query in
+// plain words finds code whose names do not say what it does
+func fuseSummaries(indexes []searchIndex, query string, limit int, results []SearchResult, total int) ([]SearchResult, int, error) {
+	if limit <= 0 {
+		limit = 10
+	}
+	queryEmbedding, model, err := embedQueryForMode(query, modeDocs)
+	if err != nil {
+		return nil, 0, err
+	}
+	// A fallback's query vector cannot be compared with the text model's
+	if model != "" {
+		return results, total, nil
+	}
+	summaries, matches, err := searchSummaries(indexes[0], queryEmbedding, limit)
+	if err != nil {
+		return nil, 0, err
+	}
+	if len(summaries) == 0 {
+		return results, total, nil
+	}
+	return fuseResults([][]SearchResult{results, summaries}), total + matches, nil
+}
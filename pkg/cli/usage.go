@@ -0,0 +1,136 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+	"sync"
+
+	"github.com/jlanders/code-scout/internal/config"
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// usageMeter counts what this run sent one provider and model, and how much
+// of it is recorded in the index's metadata already
+type usageMeter struct {
+	usage    embeddings.Usage
+	recorded storage.TokenUsage
+}
+
+// usageMeters holds a meter per provider/model, created with the first
+// client of the model
+var (
+	usageMeters   = make(map[string]*usageMeter)
+	usageMetersMu sync.Mutex
+)
+
+// usageKey identifies the provider and model of a client as provider/model
+func usageKey(cfg *config.Config, model string) string {
+	provider := cfg.Provider
+	if provider == "" {
+		provider = embeddings.ProviderOpenAI
+	}
+	return provider + "/" + model
+}
+
+// meterFor returns the meter of a provider and model
+func meterFor(key string) *usageMeter {
+	usageMetersMu.Lock()
+	defer usageMetersMu.Unlock()
+	meter, ok := usageMeters[key]
+	if !ok {
+		meter = &usageMeter{}
+		usageMeters[key] = meter
+	}
+	return meter
+}
+
+// snapshot returns what the meter has counted so far
+func (m *usageMeter) snapshot() storage.TokenUsage {
+	return storage.TokenUsage{
+		Requests: m.usage.Requests.Load(),
+		Texts:    m.usage.Texts.Load(),
+		Tokens:   m.usage.Tokens.Load(),
+	}
+}
+
+// recordUsage adds what this run sent since it was last recorded to the
+// usage the index's metadata keeps over every run
+func recordUsage(metadata *storage.IndexMetadata) {
+	usageMetersMu.Lock()
+	defer usageMetersMu.Unlock()
+	for key, meter := range usageMeters {
+		current := meter.snapshot()
+		sent := storage.TokenUsage{
+			Requests: current.Requests - meter.recorded.Requests,
+			Texts:    current.Texts - meter.recorded.Texts,
+			Tokens:   current.Tokens - meter.recorded.Tokens,
+		}
+		meter.recorded = current
+		if sent == (storage.TokenUsage{}) {
+			continue
+		}
+		if metadata.Usage == nil {
+			metadata.Usage = make(map[string]storage.TokenUsage)
+		}
+		metadata.Usage[key] = metadata.Usage[key].Add(sent)
+	}
+}
+
+// tokenPriceOf returns the price in USD of a million tokens of the model of a
+// provider/model key: its token_prices entry, else --token-price, else 0 for
+// an unknown price
+func tokenPriceOf(key string) float64 {
+	// Provider names have no slash, though model names may
+	_, model, _ := strings.Cut(key, "/")
+	if globalConfig != nil {
+		if price, ok := globalConfig.TokenPrices[model]; ok {
+			return price
+		}
+	}
+	return tokenPrice
+}
+
+// printUsageTable prints usages by provider/model with their estimated
+// costs, and their total
+func printUsageTable(usage map[string]storage.TokenUsage) {
+	var total storage.TokenUsage
+	var cost float64
+	priced := false
+	for _, key := range sortedKeys(usage) {
+		u := usage[key]
+		total = total.Add(u)
+		line := fmt.Sprintf("  %-40s %8d request(s) %10d text(s) %12d token(s)", key, u.Requests, u.Texts, u.Tokens)
+		if price := tokenPriceOf(key); price > 0 {
+			line += fmt.Sprintf("  $%.2f", float64(u.Tokens)*price/1e6)
+			cost += float64(u.Tokens) * price / 1e6
+			priced = true
+		}
+		fmt.Println(line)
+	}
+	if len(usage) > 1 {
+		line := fmt.Sprintf("  %-40s %8d request(s) %10d text(s) %12d token(s)", "total", total.Requests, total.Texts, total.Tokens)
+		if priced {
+			line += fmt.Sprintf("  $%.2f", cost)
+		}
+		fmt.Println(line)
+	}
+}
+
+// printUsage prints what this run sent each provider and model, with the cost
+// of the tokens of models with a known price
+func printUsage() {
+	usageMetersMu.Lock()
+	usage := make(map[string]storage.TokenUsage)
+	for key, meter := range usageMeters {
+		if u := meter.snapshot(); u.Requests > 0 {
+			usage[key] = u
+		}
+	}
+	usageMetersMu.Unlock()
+	if len(usage) == 0 {
+		return
+	}
+	fmt.Println("\nEmbedding usage (estimated tokens):")
+	printUsageTable(usage)
+}
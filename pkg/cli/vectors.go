@@ -0,0 +1,102 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/jlanders/code-scout/internal/embeddings"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// probeText is embedded to measure the vectors the configured models return
+const probeText = "func main() {}"
+
+// The metadata and directory of the index being searched, whose vectors
+// query vectors must match
+var (
+	searchedMetadata *storage.IndexMetadata
+	searchedDir      string
+)
+
+// embedFrom embeds a text with client and returns the model of the fallback
+// that embedded it, or "" when the client's own model did
+func embedFrom(client embeddings.Client, text string) ([]float64, string, error) {
+	if fallback, ok := client.(*embeddings.FallbackClient); ok {
+		return fallback.EmbedFrom(text)
+	}
+	embedding, err := client.Embed(text)
+	return embedding, "", err
+}
+
+// embeddingTypeOf returns the embedding type of the chunks a search mode
+// compares its query vector with
+func embeddingTypeOf(mode searchMode) string {
+	if mode == modeDocs {
+		return "docs"
+	}
+	return "code"
+}
+
+// measureVectors embeds a probe text with the configured code and text models
+// and returns the fingerprints of their vectors, by embedding type. A type
+// whose probe a fallback embedded is left out, since its vectors are not the
+// model's.
+func measureVectors() (map[string]storage.VectorFingerprint, error) {
+	clients := map[string]embeddings.Client{
+		"code": newCodeEmbeddingClient(),
+		"docs": newDocsEmbeddingClient(),
+	}
+	fingerprints := make(map[string]storage.VectorFingerprint)
+	for embeddingType, client := range clients {
+		vector, model, err := embedFrom(client, probeText)
+		if err != nil {
+			return nil, fmt.Errorf("failed to embed with the %s model: %w", embeddingType, err)
+		}
+		if model == "" {
+			fingerprints[embeddingType] = storage.FingerprintOf(vector)
+		}
+	}
+	return fingerprints, nil
+}
+
+// checkVectors measures the vectors the configured models return and records
+// their fingerprints in the index's metadata, or returns an error suggesting a
+// fix when they differ from the vectors the index holds, so chunks whose
+// vectors cannot be compared with the rest are never appended to it
+func checkVectors(metadata *storage.IndexMetadata, dir string) error {
+	measured, err := measureVectors()
+	if err != nil {
+		return err
+	}
+	for _, embeddingType := range []string{"code", "docs"} {
+		fingerprint, ok := measured[embeddingType]
+		if !ok {
+			continue
+		}
+		if reason := metadata.VectorMismatch(embeddingType, fingerprint); reason != "" {
+			return fmt.Errorf("%s; %s", reason, vectorMismatchFix(dir))
+		}
+		if metadata.Vectors == nil {
+			metadata.Vectors = make(map[string]storage.VectorFingerprint)
+		}
+		metadata.Vectors[embeddingType] = fingerprint
+	}
+	return nil
+}
+
+// checkQueryVector returns an error suggesting a fix when a query vector of a
+// search mode cannot be compared with the vectors of the index searched
+func checkQueryVector(mode searchMode, vector []float64) error {
+	if searchedMetadata == nil {
+		return nil
+	}
+	if reason := searchedMetadata.VectorMismatch(embeddingTypeOf(mode), storage.FingerprintOf(vector)); reason != "" {
+		return fmt.Errorf("%s, so their similarities would mean nothing; %s", reason, vectorMismatchFix(searchedDir))
+	}
+	return nil
+}
+
+// vectorMismatchFix suggests how to fix an index whose vectors do not match
+// the configured models'
+func vectorMismatchFix(dir string) string {
+	return fmt.Sprintf("configure the model the index was embedded with, or delete %s and run 'code-scout index' to rebuild it with this one", dir)
+}
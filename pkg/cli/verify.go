@@ -0,0 +1,242 @@
+package cli
+
+import (
+	"fmt"
+	"math"
+	"sort"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/storage"
+	"github.com/spf13/cobra"
+)
+
+// indexProblem is a stored chunk that index verify found broken
+type indexProblem struct {
+	kind   string // orphaned, missing text, checksum, or dimension
+	chunk  chunker.Chunk
+	detail string
+}
+
+var indexVerifyCmd = &cobra.Command{
+	Use:   "verify",
+	Short: "Check the index for broken entries",
+	Long: `Check every stored chunk for vectors of files the index no longer covers, chunk
+text that is missing or cannot be decoded, content hashes that do not match the
+chunk text, and vectors of the wrong dimension or with invalid values.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		store, metadata, err := openIndexForCheck(cwd)
+		if err != nil {
+			return err
+		}
+		defer store.Close()
+
+		problems, checked, err := verifyIndex(store, metadata)
+		if err != nil {
+			return err
+		}
+		printProblems(problems)
+		fmt.Printf("Checked %d chunks: %d problem(s)\n", checked, len(problems))
+		if len(problems) > 0 {
+			return fmt.Errorf("the index has %d broken chunk(s); run 'code-scout index repair' to fix them", len(problems))
+		}
+		return nil
+	},
+}
+
+var indexRepairCmd = &cobra.Command{
+	Use:   "repair",
+	Short: "Fix or prune the broken entries of the index",
+	Long: `Run the checks of index verify and fix what they find: vectors of files the
+index no longer covers are deleted, and the files of every other broken chunk
+are chunked and embedded again.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if err := checkWritable("repair the index"); err != nil {
+			return err
+		}
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+		reindex, err := pruneBrokenChunks(cmd, cwd)
+		if err != nil || reindex == 0 {
+			return err
+		}
+
+		fmt.Printf("Reindexing %d file(s)\n", reindex)
+		if globalConfig != nil {
+			if err := registerGrammars(globalConfig.Grammars); err != nil {
+				return err
+			}
+		}
+		return indexWorkspace(cmd, cwd)
+	},
+}
+
+// pruneBrokenChunks deletes the chunks index verify finds broken and forgets
+// the files that should have them, returning how many files must be indexed
+// again. The index is locked while it is checked, and changed in a generation
+// so that an interrupted repair leaves it as it was.
+func pruneBrokenChunks(cmd *cobra.Command, cwd string) (int, error) {
+	store, _, err := openIndexForCheck(cwd)
+	if err != nil {
+		return 0, err
+	}
+	defer store.Close()
+	lock, err := lockIndex(commandContext(cmd), store)
+	if err != nil {
+		return 0, err
+	}
+	defer lock.Unlock()
+	gen, err := store.BeginGeneration()
+	if err != nil {
+		return 0, err
+	}
+	defer gen.Close()
+	// Another process may have changed the index while this one waited
+	metadata, err := gen.LoadMetadata()
+	if err != nil {
+		return 0, fmt.Errorf("failed to load metadata: %w", err)
+	}
+
+	problems, checked, err := verifyIndex(gen, metadata)
+	if err != nil {
+		return 0, err
+	}
+	printProblems(problems)
+	fmt.Printf("Checked %d chunks: %d problem(s)\n", checked, len(problems))
+	if len(problems) == 0 {
+		return 0, nil
+	}
+
+	// Broken chunks are deleted, and files that should have them are
+	// forgotten so that they are indexed again
+	ids := make([]string, len(problems))
+	reindex := make(map[string]bool)
+	for i, problem := range problems {
+		ids[i] = problem.chunk.ID
+		if problem.kind != "orphaned" {
+			reindex[problem.chunk.FilePath] = true
+		}
+	}
+	if err := gen.DeleteChunks(ids); err != nil {
+		return 0, err
+	}
+	for path := range reindex {
+		delete(metadata.FileModTimes, path)
+		delete(metadata.FileHashes, path)
+	}
+	if err := store.CommitGeneration(gen, metadata); err != nil {
+		return 0, fmt.Errorf("failed to save metadata: %w", err)
+	}
+	fmt.Printf("Deleted %d broken chunk(s)\n", len(ids))
+	return len(reindex), nil
+}
+
+// openIndexForCheck opens the index of cwd and loads its metadata, failing
+// when there is nothing to check or its chunk text cannot be read
+func openIndexForCheck(cwd string) (*storage.LanceDBStore, *storage.IndexMetadata, error) {
+	store, err := openIndex(cwd)
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to open database: %w", err)
+	}
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		store.Close()
+		return nil, nil, fmt.Errorf("failed to load metadata: %w", err)
+	}
+	if metadata.IsEmpty() {
+		store.Close()
+		return nil, nil, fmt.Errorf("nothing is indexed yet; run 'code-scout index' first")
+	}
+	// Without the right key every chunk would look broken
+	if reason, _ := keyMismatch(metadata, store); reason != "" {
+		store.Close()
+		return nil, nil, fmt.Errorf("%s", reason)
+	}
+	return store, metadata, nil
+}
+
+// verifyIndex checks every stored chunk and returns the broken ones with the
+// number of chunks checked
+func verifyIndex(store *storage.LanceDBStore, metadata *storage.IndexMetadata) ([]indexProblem, int, error) {
+	chunks, err := store.ReadChunks()
+	if err != nil {
+		return nil, 0, err
+	}
+	// Hashes of chunks embedded with their imports cover text that is not stored
+	checkHashes := !metadata.EmbedImports
+	width := store.VectorWidth()
+
+	var problems []indexProblem
+	for _, stored := range chunks {
+		if kind, detail := chunkProblem(stored, metadata, checkHashes, width); kind != "" {
+			problems = append(problems, indexProblem{kind: kind, chunk: stored.Chunk, detail: detail})
+		}
+	}
+	sort.SliceStable(problems, func(i, j int) bool {
+		if problems[i].chunk.FilePath != problems[j].chunk.FilePath {
+			return problems[i].chunk.FilePath < problems[j].chunk.FilePath
+		}
+		return problems[i].chunk.LineStart < problems[j].chunk.LineStart
+	})
+	return problems, len(chunks), nil
+}
+
+// chunkProblem returns what is wrong with a stored chunk, or "" when nothing is
+func chunkProblem(stored storage.StoredChunk, metadata *storage.IndexMetadata, checkHashes bool, width int) (kind, detail string) {
+	chunk := stored.Chunk
+	if _, ok := metadata.FileModTimes[chunk.FilePath]; !ok {
+		return "orphaned", "the file is not in the index"
+	}
+	if stored.DecodeErr != nil {
+		return "missing text", stored.DecodeErr.Error()
+	}
+	if chunk.Code == "" {
+		return "missing text", "the chunk has no text"
+	}
+	if problem := vectorProblem(stored.Vector, width); problem != "" {
+		return "dimension", problem
+	}
+	if hash := chunk.Metadata["content_hash"]; checkHashes && hash != "" && hash != computeContentHash(chunk.Code) {
+		return "checksum", "the content hash does not match the chunk text"
+	}
+	return "", ""
+}
+
+// vectorProblem explains what is wrong with a stored vector of an index whose
+// vectors have width dimensions, or returns ""
+func vectorProblem(vector []float64, width int) string {
+	if len(vector) != width {
+		return fmt.Sprintf("the vector has %d dimensions, expected %d", len(vector), width)
+	}
+	zero := true
+	for _, v := range vector {
+		if math.IsNaN(v) || math.IsInf(v, 0) {
+			return "the vector has invalid values"
+		}
+		if v != 0 {
+			zero = false
+		}
+	}
+	if zero {
+		return "the vector is all zeros"
+	}
+	return ""
+}
+
+// printProblems lists the broken chunks found by verifyIndex
+func printProblems(problems []indexProblem) {
+	for _, problem := range problems {
+		fmt.Printf("  %-13s %s:%d-%d: %s\n", problem.kind, problem.chunk.FilePath, problem.chunk.LineStart, problem.chunk.LineEnd, problem.detail)
+	}
+}
+
+func init() {
+	indexCmd.AddCommand(indexVerifyCmd, indexRepairCmd)
+}
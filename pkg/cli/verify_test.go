@@ -0,0 +1,149 @@
+package cli
+
+import (
+	"errors"
+	"math"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/chunker"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+func TestChunkProblem(t *testing.T) {
+	code := "func Add(a, b int) int { return a + b }"
+	metadata := storage.NewIndexMetadata()
+	metadata.FileModTimes["add.go"] = time.Now()
+	chunk := func(path, text, hash string) chunker.Chunk {
+		return chunker.Chunk{ID: "c", FilePath: path, Code: text, Metadata: map[string]string{"content_hash": hash}}
+	}
+	vector := []float64{1, 2, 3}
+
+	tests := []struct {
+		name        string
+		stored      storage.StoredChunk
+		checkHashes bool
+		want        string
+	}{
+		{"healthy", storage.StoredChunk{Chunk: chunk("add.go", code, computeContentHash(code)), Vector: vector}, true, ""},
+		{"orphaned", storage.StoredChunk{Chunk: chunk("gone.go", code, computeContentHash(code)), Vector: vector}, true, "orphaned"},
+		{"undecodable text", storage.StoredChunk{Chunk: chunk("add.go", "", ""), Vector: vector, DecodeErr: errors.New("bad key")}, true, "missing text"},
+		{"empty text", storage.StoredChunk{Chunk: chunk("add.go", "", ""), Vector: vector}, true, "missing text"},
+		{"zero vector", storage.StoredChunk{Chunk: chunk("add.go", code, ""), Vector: []float64{0, 0, 0}}, true, "dimension"},
+		{"NaN", storage.StoredChunk{Chunk: chunk("add.go", code, ""), Vector: []float64{1, math.NaN(), 3}}, true, "dimension"},
+		{"wrong width", storage.StoredChunk{Chunk: chunk("add.go", code, ""), Vector: []float64{1, 2}}, true, "dimension"},
+		{"checksum mismatch", storage.StoredChunk{Chunk: chunk("add.go", code, computeContentHash("other")), Vector: vector}, true, "checksum"},
+		// Hashes of chunks embedded with their imports cover more than the text
+		{"checksum with imports", storage.StoredChunk{Chunk: chunk("add.go", code, computeContentHash("other")), Vector: vector}, false, ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			kind, detail := chunkProblem(tt.stored, metadata, tt.checkHashes, len(vector))
+			if kind != tt.want {
+				t.Errorf("chunkProblem() = %q (%s), want %q", kind, detail, tt.want)
+			}
+		})
+	}
+}
+
+func TestVectorProblem(t *testing.T) {
+	tests := []struct {
+		name   string
+		vector []float64
+		want   string
+	}{
+		{"valid", []float64{0.5, -0.5, 0}, ""},
+		{"all zeros", []float64{0, 0, 0}, "all zeros"},
+		{"NaN", []float64{0.5, math.NaN(), 0}, "invalid values"},
+		{"infinite", []float64{math.Inf(1), 0, 0}, "invalid values"},
+		{"too short", []float64{0.5, 0.5}, "2 dimensions, expected 3"},
+		{"too long", []float64{0.5, 0.5, 0.5, 0.5}, "4 dimensions, expected 3"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := vectorProblem(tt.vector, 3)
+			if (tt.want == "") != (got == "") || !strings.Contains(got, tt.want) {
+				t.Errorf("vectorProblem() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestRepairReindexesBrokenChunks(t *testing.T) {
+	installFakeEmbeddings(t)
+	workDir := t.TempDir()
+	writeTestFile(t, workDir, "main.go", "package main\n\nfunc Add(a, b int) int {\n\treturn a + b\n}\n")
+	writeTestFile(t, workDir, "util.go", "package main\n\nfunc Sub(a, b int) int {\n\treturn a - b\n}\n")
+	runInDir(t, workDir, func() error {
+		return indexCmd.RunE(indexCmd, nil)
+	})
+
+	// Break the index: a chunk of util.go with a zero vector, and one of a
+	// file the index does not cover
+	store, err := storage.NewLanceDBStore(workDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	metadata, err := store.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var utilPath string
+	for path := range metadata.FileModTimes {
+		if strings.HasSuffix(path, "util.go") {
+			utilPath = path
+		}
+	}
+	if utilPath == "" {
+		t.Fatalf("util.go is not indexed: %v", metadata.FileModTimes)
+	}
+	broken := []chunker.Chunk{
+		{ID: "zero", FilePath: utilPath, LineStart: 1, LineEnd: 1, Language: "go", Code: "func Zero() {}", Metadata: map[string]string{}, EmbeddingType: "code"},
+		{ID: "orphan", FilePath: strings.TrimSuffix(utilPath, "util.go") + "gone.go", LineStart: 1, LineEnd: 1, Language: "go", Code: "func Gone() {}", Metadata: map[string]string{}, EmbeddingType: "code"},
+	}
+	if err := store.StoreChunks(broken, [][]float64{make([]float64, store.VectorWidth()), fakeVector("gone", 1)}); err != nil {
+		t.Fatal(err)
+	}
+	problems, _, err := verifyIndex(store, metadata)
+	store.Close()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(problems) != 2 {
+		t.Fatalf("verify found %d problems (%+v), want the zero vector and the orphan", len(problems), problems)
+	}
+
+	captureStdout(t, func() {
+		runInDir(t, workDir, func() error {
+			return indexRepairCmd.RunE(indexRepairCmd, nil)
+		})
+	})
+
+	store, err = storage.NewLanceDBStore(workDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer store.Close()
+	metadata, err = store.LoadMetadata()
+	if err != nil {
+		t.Fatal(err)
+	}
+	problems, checked, err := verifyIndex(store, metadata)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(problems) != 0 {
+		t.Errorf("verify found %+v after the repair, want none", problems)
+	}
+	if checked == 0 {
+		t.Fatal("the repaired index holds no chunks")
+	}
+	if _, ok := metadata.FileModTimes[utilPath]; !ok {
+		t.Errorf("util.go is not indexed after the repair")
+	}
+	code := runSearchJSON(t, workDir, "sub", modeCode)
+	if !containsFile(code.Results, "util.go", "code") {
+		t.Errorf("search after the repair found %+v, want util.go", code.Results)
+	}
+}
@@ -0,0 +1,269 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/signal"
+	"sync"
+	"syscall"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/scanner"
+	"github.com/spf13/cobra"
+)
+
+var (
+	watchInterval time.Duration
+	watchDebounce time.Duration
+)
+
+var watchCmd = &cobra.Command{
+	Use:   "watch",
+	Short: "Keep the index up to date as files change",
+	Long: `Index the current directory, then keep watching it and reindex whenever code
+or documentation files are added, changed, or deleted. On Linux the directories
+scanned are watched with inotify, so changes are seen as they happen. Elsewhere,
+or when the limit on inotify watches is reached, the files and directories are
+polled every --interval instead: each poll checks their modification times, and
+the tree is only scanned again once one changes. Changes that arrive in quick
+succession are batched into a single incremental reindex. Stop watching with
+Ctrl+C.`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cwd, err := workingDir()
+		if err != nil {
+			return fmt.Errorf("failed to get current directory: %w", err)
+		}
+
+		if globalConfig != nil {
+			if err := registerGrammars(globalConfig.Grammars); err != nil {
+				return err
+			}
+		}
+
+		// Stopping also interrupts a reindex that is under way
+		ctx, stop := signal.NotifyContext(commandContext(cmd), os.Interrupt, syscall.SIGTERM)
+		defer stop()
+		cmd.SetContext(ctx)
+
+		roots, err := workspaceRoots(cwd)
+		if err != nil {
+			return err
+		}
+		if err := indexWorkspace(cmd, cwd); err != nil {
+			return err
+		}
+		return watchWorkspace(ctx, roots, scannerOptions(cmd, make(dirConfigs)), func() {
+			if err := indexWorkspace(cmd, cwd); err != nil {
+				fmt.Fprintf(os.Stderr, "Error: reindexing failed: %v\n", err)
+			}
+		})
+	},
+}
+
+// watchWorkspace watches the files of the workspace roots that options select
+// and calls reindex once no further change has been seen for watchDebounce,
+// until ctx is cancelled
+func watchWorkspace(ctx context.Context, roots []workspaceRoot, options scanner.Options, reindex func()) error {
+	previous, err := snapshotWorkspace(roots, options)
+	if err != nil {
+		return err
+	}
+
+	// Where the platform's notifications cannot be used, such as when the
+	// limit on watches is reached, the workspace is polled instead
+	watcher, err := newDirWatcher()
+	if err == nil {
+		if err = watcher.Watch(previous); err != nil {
+			watcher.Close()
+		}
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Warning: polling for changes every %s, since the file system cannot be watched: %v\n", watchInterval, err)
+		watcher = newPollWatcher(watchInterval)
+		if err := watcher.Watch(previous); err != nil {
+			return err
+		}
+	}
+	// The watcher is replaced by a poller if it stops being able to watch
+	defer func() { watcher.Close() }()
+	fmt.Printf("\nWatching %d file(s) for changes (Ctrl+C to stop)...\n", len(previous.files))
+
+	// The debounce timer only runs while changes are waiting to be reindexed
+	settled := time.NewTimer(watchDebounce)
+	settled.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			fmt.Println("\nStopped watching")
+			return nil
+		case <-watcher.Changed():
+			settled.Reset(watchDebounce)
+		case <-settled.C:
+			current, err := snapshotWorkspace(roots, options)
+			if err != nil {
+				fmt.Fprintf(os.Stderr, "Warning: failed to scan for changes: %v\n", err)
+				continue
+			}
+			if err := watcher.Watch(current); err != nil {
+				// Directories left unwatched would miss changes, so the whole
+				// workspace is polled from now on
+				fmt.Fprintf(os.Stderr, "Warning: polling for changes every %s, since the file system cannot be watched: %v\n", watchInterval, err)
+				watcher.Close()
+				watcher = newPollWatcher(watchInterval)
+				if err := watcher.Watch(current); err != nil {
+					return err
+				}
+			}
+			if changedFiles(previous.files, current.files) > 0 {
+				fmt.Println("\nChanges detected, reindexing...")
+				reindex()
+			}
+			previous = current
+		}
+	}
+}
+
+// workspaceSnapshot records the files the index covers and the directories
+// walked to find them
+type workspaceSnapshot struct {
+	files map[string]time.Time // Modification time by path
+	dirs  []string
+}
+
+// snapshotWorkspace records the modification time of every file the index
+// covers and the directories that may hold more
+func snapshotWorkspace(roots []workspaceRoot, options scanner.Options) (*workspaceSnapshot, error) {
+	snapshot := &workspaceSnapshot{}
+	options.OnDir = func(path string) {
+		snapshot.dirs = append(snapshot.dirs, path)
+	}
+	files, err := scanWorkspace(roots, options)
+	if err != nil {
+		return nil, err
+	}
+	snapshot.files = make(map[string]time.Time, len(files))
+	for _, f := range files {
+		snapshot.files[f.Path] = f.ModTime
+	}
+	return snapshot, nil
+}
+
+// changedFiles counts the files added, modified, or deleted between two
+// snapshots
+func changedFiles(previous, current map[string]time.Time) int {
+	changed := 0
+	for path, modTime := range current {
+		if before, ok := previous[path]; !ok || !before.Equal(modTime) {
+			changed++
+		}
+	}
+	for path := range previous {
+		if _, ok := current[path]; !ok {
+			changed++
+		}
+	}
+	return changed
+}
+
+// dirWatcher reports changes to the directories of a workspace snapshot and
+// the files in them
+type dirWatcher interface {
+	// Watch replaces the directories and files watched with a snapshot's
+	Watch(snapshot *workspaceSnapshot) error
+	// Changed receives a value after something watched may have changed
+	Changed() <-chan struct{}
+	Close() error
+}
+
+// pollWatcher checks the modification times of the files and directories of
+// a snapshot every interval. A directory's mtime changes when an entry is added,
+// removed, or renamed, so the tree is only walked again once it or a file
+// changes.
+type pollWatcher struct {
+	mu       sync.Mutex
+	modTimes map[string]time.Time
+	changed  chan struct{}
+	done     chan struct{}
+}
+
+func newPollWatcher(interval time.Duration) *pollWatcher {
+	w := &pollWatcher{changed: make(chan struct{}, 1), done: make(chan struct{})}
+	go func() {
+		ticker := time.NewTicker(interval)
+		defer ticker.Stop()
+		for {
+			select {
+			case <-w.done:
+				return
+			case <-ticker.C:
+				if w.poll() {
+					notify(w.changed)
+				}
+			}
+		}
+	}()
+	return w
+}
+
+func (w *pollWatcher) Watch(snapshot *workspaceSnapshot) error {
+	modTimes := make(map[string]time.Time, len(snapshot.files)+len(snapshot.dirs))
+	for path, modTime := range snapshot.files {
+		modTimes[path] = modTime
+	}
+	for _, dir := range snapshot.dirs {
+		if info, err := os.Stat(dir); err == nil {
+			modTimes[dir] = info.ModTime()
+		}
+	}
+	w.mu.Lock()
+	w.modTimes = modTimes
+	w.mu.Unlock()
+	return nil
+}
+
+// poll reports whether a file or directory watched was modified or removed
+// since it was recorded, and records its new time so it is reported once
+func (w *pollWatcher) poll() bool {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	changed := false
+	for path, modTime := range w.modTimes {
+		info, err := os.Stat(path)
+		if err != nil {
+			delete(w.modTimes, path)
+			changed = true
+		} else if !info.ModTime().Equal(modTime) {
+			w.modTimes[path] = info.ModTime()
+			changed = true
+		}
+	}
+	return changed
+}
+
+func (w *pollWatcher) Changed() <-chan struct{} {
+	return w.changed
+}
+
+func (w *pollWatcher) Close() error {
+	close(w.done)
+	return nil
+}
+
+// notify sends on a channel with room for one value without waiting, since a
+// change already waiting to be received covers any that follow it
+func notify(changed chan struct{}) {
+	select {
+	case changed <- struct{}{}:
+	default:
+	}
+}
+
+func init() {
+	rootCmd.AddCommand(watchCmd)
+	watchCmd.Flags().DurationVar(&watchInterval, "interval", 2*time.Second, "How often to check for changed files when they are polled instead of watched")
+	watchCmd.Flags().DurationVar(&watchDebounce, "debounce", 500*time.Millisecond, "How long files must stay unchanged before a batch of changes is reindexed")
+	watchCmd.Flags().IntVarP(&workers, "workers", "w", 10, "Number of concurrent workers for embedding generation (default: 10)")
+	watchCmd.Flags().IntVar(&embeddingBatchSize, "batch-size", 8, "Number of chunks per embedding request (default: 8)")
+	addScanFlags(watchCmd)
+}
@@ -0,0 +1,140 @@
+package cli
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sync"
+	"unsafe"
+
+	"golang.org/x/sys/unix"
+)
+
+// inotifyEvents are the events of a watched directory that may change the
+// files the index covers
+const inotifyEvents = unix.IN_CREATE | unix.IN_DELETE | unix.IN_MODIFY | unix.IN_CLOSE_WRITE |
+	unix.IN_MOVED_FROM | unix.IN_MOVED_TO | unix.IN_DELETE_SELF | unix.IN_MOVE_SELF
+
+// inotifyWatcher watches the directories of a snapshot with inotify, which
+// reports changes to a directory's entries and to the contents of its files
+type inotifyWatcher struct {
+	fd      int
+	file    *os.File
+	mu      sync.Mutex
+	dirs    map[string]int // Watch descriptor by directory
+	paths   map[int]string // Directory by watch descriptor
+	changed chan struct{}
+}
+
+func newDirWatcher() (dirWatcher, error) {
+	fd, err := unix.InotifyInit1(unix.IN_CLOEXEC | unix.IN_NONBLOCK)
+	if err != nil {
+		return nil, fmt.Errorf("failed to start inotify: %w", err)
+	}
+	// A non-blocking descriptor is read through the runtime's poller, so
+	// closing the file ends a read under way. The file's Fd is never called,
+	// since it would make the descriptor blocking.
+	w := &inotifyWatcher{
+		fd:      fd,
+		file:    os.NewFile(uintptr(fd), "inotify"),
+		dirs:    make(map[string]int),
+		paths:   make(map[int]string),
+		changed: make(chan struct{}, 1),
+	}
+	go w.read()
+	return w, nil
+}
+
+func (w *inotifyWatcher) Watch(snapshot *workspaceSnapshot) error {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	keep := make(map[string]bool, len(snapshot.dirs))
+	for _, dir := range snapshot.dirs {
+		keep[dir] = true
+	}
+	// Directories no longer scanned, such as ones newly ignored, are dropped
+	// first, freeing their watches for the new ones
+	for dir, wd := range w.dirs {
+		if !keep[dir] {
+			unix.InotifyRmWatch(w.fd, uint32(wd))
+			delete(w.dirs, dir)
+			delete(w.paths, wd)
+		}
+	}
+	var errs []error
+	for _, dir := range snapshot.dirs {
+		if err := w.add(dir); err != nil {
+			errs = append(errs, err)
+		}
+	}
+	return errors.Join(errs...)
+}
+
+// add watches a directory, if it is not watched already. w.mu must be held.
+func (w *inotifyWatcher) add(dir string) error {
+	if _, ok := w.dirs[dir]; ok {
+		return nil
+	}
+	wd, err := unix.InotifyAddWatch(w.fd, dir, inotifyEvents)
+	switch {
+	case errors.Is(err, unix.ENOENT):
+		// Removed since it was scanned, which the next scan records
+		return nil
+	case errors.Is(err, unix.ENOSPC):
+		return fmt.Errorf("failed to watch %s: the limit on inotify watches is reached; raise fs.inotify.max_user_watches", dir)
+	case err != nil:
+		return fmt.Errorf("failed to watch %s: %w", dir, err)
+	}
+	w.dirs[dir] = wd
+	w.paths[wd] = dir
+	return nil
+}
+
+// read reports the events of the watched directories until the watcher is
+// closed
+func (w *inotifyWatcher) read() {
+	buf := make([]byte, 4096*(unix.SizeofInotifyEvent+unix.NAME_MAX+1))
+	for {
+		n, err := w.file.Read(buf)
+		if err != nil {
+			return
+		}
+		for offset := 0; offset+unix.SizeofInotifyEvent <= n; {
+			event := (*unix.InotifyEvent)(unsafe.Pointer(&buf[offset]))
+			nameStart := offset + unix.SizeofInotifyEvent
+			name := string(bytes.TrimRight(buf[nameStart:nameStart+int(event.Len)], "\x00"))
+			offset = nameStart + int(event.Len)
+			w.handle(int(event.Wd), event.Mask, name)
+		}
+		notify(w.changed)
+	}
+}
+
+// handle keeps the watches up to date with an event of a directory
+func (w *inotifyWatcher) handle(wd int, mask uint32, name string) {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	dir, ok := w.paths[wd]
+	switch {
+	case mask&unix.IN_IGNORED != 0:
+		// The directory was removed or its watch dropped
+		if ok {
+			delete(w.dirs, dir)
+			delete(w.paths, wd)
+		}
+	case ok && mask&unix.IN_ISDIR != 0 && mask&(unix.IN_CREATE|unix.IN_MOVED_TO) != 0:
+		// A new directory is watched at once, so files written to it before the
+		// next scan are not missed; the scan drops it again if it is ignored
+		w.add(filepath.Join(dir, name))
+	}
+}
+
+func (w *inotifyWatcher) Changed() <-chan struct{} {
+	return w.changed
+}
+
+func (w *inotifyWatcher) Close() error {
+	return w.file.Close()
+}
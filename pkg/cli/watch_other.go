@@ -0,0 +1,12 @@
+//go:build !linux
+
+package cli
+
+import "errors"
+
+// newDirWatcher returns an error on platforms without inotify, so their
+// workspaces are polled. kqueue only reports edits to a file through a
+// descriptor open on it, which would hold one open for every file indexed.
+func newDirWatcher() (dirWatcher, error) {
+	return nil, errors.New("watching is only supported on Linux")
+}
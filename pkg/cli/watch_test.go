@@ -0,0 +1,140 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/jlanders/code-scout/internal/scanner"
+)
+
+// startWatching watches dir until the test ends and returns a channel that
+// receives a value for every reindex
+func startWatching(t *testing.T, dir string) <-chan struct{} {
+	t.Helper()
+	interval, debounce := watchInterval, watchDebounce
+	watchInterval, watchDebounce = 20*time.Millisecond, 50*time.Millisecond
+	ctx, cancel := context.WithCancel(context.Background())
+	reindexed := make(chan struct{}, 16)
+	done := make(chan error, 1)
+	go func() {
+		done <- watchWorkspace(ctx, []workspaceRoot{{dir: dir}}, scanner.Options{}, func() {
+			reindexed <- struct{}{}
+		})
+	}()
+	t.Cleanup(func() {
+		cancel()
+		if err := <-done; err != nil {
+			t.Errorf("watchWorkspace returned %v", err)
+		}
+		watchInterval, watchDebounce = interval, debounce
+	})
+	return reindexed
+}
+
+// waitForReindex makes a change until the watcher reindexes, since the
+// watcher may not have started when the change is first made
+func waitForReindex(t *testing.T, reindexed <-chan struct{}, change func(attempt int)) {
+	t.Helper()
+	deadline := time.After(5 * time.Second)
+	for attempt := 0; ; attempt++ {
+		change(attempt)
+		select {
+		case <-reindexed:
+			// Changes the attempts made after the one seen are reindexed too
+			time.Sleep(200 * time.Millisecond)
+			for len(reindexed) > 0 {
+				<-reindexed
+			}
+			return
+		case <-time.After(250 * time.Millisecond):
+		case <-deadline:
+			t.Fatal("the change was not reindexed")
+		}
+	}
+}
+
+func TestWatchReindexesChangedFiles(t *testing.T) {
+	dir := t.TempDir()
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+	writeTestFile(t, dir, "main.go", "package main\n")
+	reindexed := startWatching(t, dir)
+
+	waitForReindex(t, reindexed, func(attempt int) {
+		writeTestFile(t, dir, "main.go", fmt.Sprintf("package main\n\nconst version = %d\n", attempt))
+	})
+
+	// A directory created while watching is watched too
+	if err := os.Mkdir(filepath.Join(dir, "lib"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	waitForReindex(t, reindexed, func(attempt int) {
+		writeTestFile(t, dir, "lib/util.go", fmt.Sprintf("package lib\n\nconst version = %d\n", attempt))
+	})
+	waitForReindex(t, reindexed, func(attempt int) {
+		writeTestFile(t, dir, "lib/util.go", fmt.Sprintf("package lib\n\nconst edit = %d\n", attempt))
+	})
+
+	waitForReindex(t, reindexed, func(attempt int) {
+		os.Remove(filepath.Join(dir, "main.go"))
+	})
+
+	// Files the index does not cover are not reindexed
+	writeTestFile(t, dir, "image.png", "")
+	select {
+	case <-reindexed:
+		t.Fatal("reindexed after a file the index does not cover was added")
+	case <-time.After(300 * time.Millisecond):
+	}
+}
+
+func TestPollWatcherReportsChanges(t *testing.T) {
+	dir := t.TempDir()
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	t.Setenv("GIT_CONFIG_GLOBAL", os.DevNull)
+	writeTestFile(t, dir, "main.go", "package main\n")
+	snapshot, err := snapshotWorkspace([]workspaceRoot{{dir: dir}}, scanner.Options{})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(snapshot.files) != 1 || len(snapshot.dirs) != 1 {
+		t.Fatalf("snapshot has %d file(s) and %d dir(s), want 1 of each", len(snapshot.files), len(snapshot.dirs))
+	}
+
+	w := newPollWatcher(10 * time.Millisecond)
+	defer w.Close()
+	if err := w.Watch(snapshot); err != nil {
+		t.Fatal(err)
+	}
+	select {
+	case <-w.Changed():
+		t.Fatal("reported a change before anything changed")
+	case <-time.After(100 * time.Millisecond):
+	}
+
+	// Adding a file changes its directory's modification time. The time is
+	// moved forward, since a file system may only record whole seconds.
+	writeTestFile(t, dir, "util.go", "package main\n")
+	later := time.Now().Add(time.Minute)
+	if err := os.Chtimes(dir, later, later); err != nil {
+		t.Fatal(err)
+	}
+	select {
+	case <-w.Changed():
+	case <-time.After(5 * time.Second):
+		t.Fatal("adding a file was not reported")
+	}
+
+	if err := os.Remove(filepath.Join(dir, "main.go")); err != nil {
+		t.Fatal(err)
+	}
+	select {
+	case <-w.Changed():
+	case <-time.After(5 * time.Second):
+		t.Fatal("removing a file was not reported")
+	}
+}
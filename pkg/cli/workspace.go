@@ -0,0 +1,78 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/jlanders/code-scout/internal/scanner"
+	"github.com/jlanders/code-scout/internal/storage"
+)
+
+// workspaceRoot is a directory whose files are indexed into the workspace's
+// index
+type workspaceRoot struct {
+	name string // Repository name recorded on chunks; empty for a single-directory workspace
+	dir  string
+}
+
+// workspaceRoots returns the repositories configured for the workspace at
+// cwd, or cwd itself when none are
+func workspaceRoots(cwd string) ([]workspaceRoot, error) {
+	if globalConfig == nil || len(globalConfig.Repos) == 0 {
+		return []workspaceRoot{{dir: cwd}}, nil
+	}
+
+	roots := make([]workspaceRoot, 0, len(globalConfig.Repos))
+	for _, repo := range globalConfig.Repos {
+		dir := repo.Path
+		if !filepath.IsAbs(dir) {
+			dir = filepath.Join(cwd, dir)
+		}
+		if info, err := os.Stat(dir); err != nil || !info.IsDir() {
+			return nil, fmt.Errorf("repository %s: %s is not a directory", repo.RepoName(), dir)
+		}
+		roots = append(roots, workspaceRoot{name: repo.RepoName(), dir: dir})
+	}
+	return roots, nil
+}
+
+// scanWorkspace scans every root of the workspace, recording the repository
+// each file belongs to
+func scanWorkspace(roots []workspaceRoot, options scanner.Options) ([]scanner.FileInfo, error) {
+	var files []scanner.FileInfo
+	for _, root := range roots {
+		found, err := scanner.NewWithOptions(root.dir, options).ScanCodeFiles()
+		if err != nil {
+			return nil, fmt.Errorf("failed to scan %s: %w", root.dir, err)
+		}
+		for i := range found {
+			found[i].Repo = root.name
+		}
+		files = append(files, found...)
+	}
+	return files, nil
+}
+
+// recordCommits records the commit each root of the workspace has checked out
+func recordCommits(metadata *storage.IndexMetadata, roots []workspaceRoot) {
+	for _, root := range roots {
+		if root.name == "" {
+			metadata.Commit = headCommit(root.dir)
+			continue
+		}
+		if metadata.RepoCommits == nil {
+			metadata.RepoCommits = make(map[string]string)
+		}
+		metadata.RepoCommits[root.name] = headCommit(root.dir)
+	}
+}
+
+// indexedCommit returns the commit a root had checked out when it was last
+// indexed
+func indexedCommit(metadata *storage.IndexMetadata, root workspaceRoot) string {
+	if root.name == "" {
+		return metadata.Commit
+	}
+	return metadata.RepoCommits[root.name]
+}
@@ -0,0 +1,62 @@
+// Package embeddings is the interface code-scout embeds text through and the
+// registry of the providers that implement it. A binary that runs the
+// code-scout command of pkg/cli adds a provider by registering it here, and
+// selects it with the provider setting.
+package embeddings
+
+import "sync"
+
+// Client is the interface for embedding clients. Embed embeds search queries
+// and EmbedMany the chunks being indexed, which providers such as Gemini,
+// Cohere, Voyage AI, and Jina AI embed differently.
+type Client interface {
+	Embed(text string) ([]float64, error)
+	EmbedMany(texts []string) ([][]float64, error)
+}
+
+// TokenFunc returns a bearer token for a request, such as an Azure AD token
+type TokenFunc func() (string, error)
+
+// ProviderSettings holds the configuration a provider creates a client from;
+// a provider reads the settings that apply to it
+type ProviderSettings struct {
+	Endpoint    string
+	APIKey      string
+	Model       string
+	Dimensions  int       // Size of the vectors requested; 0 for the model's own
+	KeepAlive   string    // How long Ollama keeps the model loaded
+	GPU         string    // Whether Ollama runs the model on the GPU: "on", "off", or "" for its own choice
+	Pooling     string    // How token embeddings are pooled: "mean" or "cls"
+	APIVersion  string    // Azure OpenAI API version
+	ADToken     TokenFunc // Azure AD token source, used without an API key
+	ONNXRuntime string    // Path to the ONNX Runtime shared library the onnx provider loads
+	ONNXDevice  string    // Where the onnx provider runs the model: "cpu", "cuda", "coreml", or "" for the CPU
+}
+
+// ProviderFactory creates a client of a provider for the model in settings
+type ProviderFactory func(settings ProviderSettings) Client
+
+// providers are the factories of the providers added with RegisterProvider,
+// by name. The built-in providers are not listed; one registered under the
+// name of a built-in one takes its place.
+var (
+	providersMu sync.RWMutex
+	providers   = make(map[string]ProviderFactory)
+)
+
+// RegisterProvider adds a provider, or replaces the one of the same name, so
+// that a provider setting of name creates its clients with factory. It is
+// meant to be called before the command runs, from main or an init function.
+func RegisterProvider(name string, factory ProviderFactory) {
+	providersMu.Lock()
+	defer providersMu.Unlock()
+	providers[name] = factory
+}
+
+// LookupProvider returns the factory registered for a provider name
+func LookupProvider(name string) (ProviderFactory, bool) {
+	providersMu.RLock()
+	defer providersMu.RUnlock()
+	factory, ok := providers[name]
+	return factory, ok
+}
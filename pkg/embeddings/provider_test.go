@@ -0,0 +1,68 @@
+package embeddings
+
+import "testing"
+
+// staticClient returns the same vector for every text
+type staticClient struct {
+	vector []float64
+}
+
+func (c staticClient) Embed(text string) ([]float64, error) {
+	return c.vector, nil
+}
+
+func (c staticClient) EmbedMany(texts []string) ([][]float64, error) {
+	vectors := make([][]float64, len(texts))
+	for i := range texts {
+		vectors[i] = c.vector
+	}
+	return vectors, nil
+}
+
+// unregister removes a provider registered by a test
+func unregister(t *testing.T, name string) {
+	t.Cleanup(func() {
+		providersMu.Lock()
+		defer providersMu.Unlock()
+		delete(providers, name)
+	})
+}
+
+func TestRegisterProvider(t *testing.T) {
+	unregister(t, "test-service")
+	if _, ok := LookupProvider("test-service"); ok {
+		t.Fatal("found a provider before registering it")
+	}
+
+	var got ProviderSettings
+	RegisterProvider("test-service", func(s ProviderSettings) Client {
+		got = s
+		return staticClient{vector: []float64{1, 2}}
+	})
+	factory, ok := LookupProvider("test-service")
+	if !ok {
+		t.Fatal("the registered provider was not found")
+	}
+	vector, err := factory(ProviderSettings{Endpoint: "http://embed.internal", Model: "m"}).Embed("text")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(vector) != 2 || vector[0] != 1 {
+		t.Errorf("the client embedded %v, want [1 2]", vector)
+	}
+	if got.Endpoint != "http://embed.internal" || got.Model != "m" {
+		t.Errorf("the factory was given %+v", got)
+	}
+}
+
+func TestRegisterProviderReplacesDuplicate(t *testing.T) {
+	unregister(t, "test-duplicate")
+	RegisterProvider("test-duplicate", func(ProviderSettings) Client { return staticClient{vector: []float64{1}} })
+	RegisterProvider("test-duplicate", func(ProviderSettings) Client { return staticClient{vector: []float64{2}} })
+
+	factory, _ := LookupProvider("test-duplicate")
+	vector, _ := factory(ProviderSettings{}).Embed("text")
+	if len(vector) != 1 || vector[0] != 2 {
+		t.Errorf("the client embedded %v, want the later registration's [2]", vector)
+	}
+}